@@ -0,0 +1,523 @@
+//! Subcommand implementations
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use serde_json::{json, Value};
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::execution::{round_px, round_sz};
+use hyperliquid_core::killswitch::{KillSwitchRegistry, Scope};
+use hyperliquid_core::store::SqliteStore;
+use hyperliquid_core::stream::{
+    WebSocketClient, WebSocketClientConfig, WebSocketError, WebSocketResponse,
+};
+use hyperliquid_core::types::precision::float_to_wire;
+use hyperliquid_core::{
+    Config, Environment, ExchangeClient, ExchangeClientConfig, HttpClient, HyperliquidError,
+    Subscription,
+};
+
+use crate::{Side, StreamChannel, Tif, TradingAction};
+
+/// Clients and settings shared by the subcommands
+pub struct Context {
+    http: HttpClient,
+    signer: Option<(ExchangeClient, Wallet)>,
+    ws_url: String,
+    json: bool,
+}
+
+impl Context {
+    pub fn new(config: &Config, testnet: bool, json: bool) -> Result<Self, HyperliquidError> {
+        let (env, base_url, ws_url) = if testnet {
+            let env = Environment::Testnet;
+            (
+                env,
+                env.base_url().to_string(),
+                env.websocket_url().to_string(),
+            )
+        } else {
+            (
+                config.get_environment(),
+                config.get_base_url(),
+                config.get_websocket_url(),
+            )
+        };
+
+        let signer = match &config.security.private_key {
+            Some(key) => {
+                let wallet = Wallet::new(key.expose(), env == Environment::Mainnet)?;
+                let account = wallet
+                    .address()
+                    .parse()
+                    .map_err(|_| HyperliquidError::Signing("invalid wallet address".to_string()))?;
+                let mut exchange_config = if env == Environment::Mainnet {
+                    ExchangeClientConfig::mainnet(account)
+                } else {
+                    ExchangeClientConfig::testnet(account)
+                };
+                exchange_config.base_url = base_url.clone();
+                Some((ExchangeClient::new(exchange_config), wallet))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            http: HttpClient::with_default_config(base_url)?,
+            signer,
+            ws_url,
+            json,
+        })
+    }
+
+    async fn info(&self, request: Value) -> Result<Value, HyperliquidError> {
+        self.http.post("/info", &request).await
+    }
+
+    fn signer(&self) -> Result<&(ExchangeClient, Wallet), HyperliquidError> {
+        self.signer.as_ref().ok_or_else(|| {
+            HyperliquidError::Config(
+                "no signing key configured; set security.private_key".to_string(),
+            )
+        })
+    }
+
+    /// `user`, or the configured key's address
+    fn user(&self, user: Option<&str>) -> Result<String, HyperliquidError> {
+        match user {
+            Some(user) => Ok(user.to_string()),
+            None => Ok(self.signer()?.1.address()),
+        }
+    }
+
+    async fn submit(&self, action: Value) -> Result<Value, HyperliquidError> {
+        let (exchange, wallet) = self.signer()?;
+        exchange.post_signed_action(action, wallet, None).await
+    }
+
+    /// Perp asset index and size decimals per coin
+    async fn assets(&self) -> Result<HashMap<String, (u32, u32)>, HyperliquidError> {
+        let meta = self.info(json!({"type": "meta"})).await?;
+        Ok(meta
+            .get("universe")
+            .and_then(Value::as_array)
+            .map(|universe| {
+                universe
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, asset)| {
+                        let name = asset.get("name")?.as_str()?;
+                        let sz_decimals = asset.get("szDecimals")?.as_u64()?;
+                        Some((name.to_string(), (index as u32, sz_decimals as u32)))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn print_json(&self, value: &Value) {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+        );
+    }
+}
+
+/// Parse a limit price written as `3000` or `@3000`
+pub fn parse_price(price: &str) -> Result<f64, HyperliquidError> {
+    price
+        .trim_start_matches('@')
+        .parse::<f64>()
+        .ok()
+        .filter(|px| px.is_finite() && *px > 0.0)
+        .ok_or_else(|| HyperliquidError::Validation(format!("invalid price: {}", price)))
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> &'a str {
+    value.get(field).and_then(Value::as_str).unwrap_or("-")
+}
+
+fn wire(value: f64) -> Result<String, HyperliquidError> {
+    float_to_wire(value).map_err(|e| HyperliquidError::Validation(e.to_string()))
+}
+
+/// Fail on a whole-request error response
+fn check_response(response: &Value) -> Result<(), HyperliquidError> {
+    if response.get("status").and_then(Value::as_str) == Some("err") {
+        return Err(HyperliquidError::Client {
+            code: 0,
+            message: str_field(response, "response").to_string(),
+            data: Some(response.clone()),
+        });
+    }
+    Ok(())
+}
+
+fn print_statuses(response: &Value) {
+    let statuses = response
+        .pointer("/response/data/statuses")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for status in statuses {
+        if let Some(oid) = status.pointer("/resting/oid") {
+            println!("resting  oid={}", oid);
+        } else if let Some(filled) = status.get("filled") {
+            println!(
+                "filled   oid={} {} @ {}",
+                filled.get("oid").unwrap_or(&Value::Null),
+                str_field(filled, "totalSz"),
+                str_field(filled, "avgPx")
+            );
+        } else if let Some(error) = status.get("error") {
+            println!("rejected {}", error.as_str().unwrap_or_default());
+        } else if status.as_str() == Some("success") {
+            println!("success");
+        } else {
+            println!("{}", status);
+        }
+    }
+}
+
+pub async fn book(ctx: &Context, coin: &str, depth: usize) -> Result<(), HyperliquidError> {
+    let book = ctx.info(json!({"type": "l2Book", "coin": coin})).await?;
+    if ctx.json {
+        ctx.print_json(&book);
+        return Ok(());
+    }
+
+    let side = |index: usize| -> Vec<Value> {
+        book.pointer(&format!("/levels/{}", index))
+            .and_then(Value::as_array)
+            .map(|levels| levels.iter().take(depth).cloned().collect())
+            .unwrap_or_default()
+    };
+    let row = |level: &Value| {
+        println!(
+            "{:>14} {:>14} {:>5}",
+            str_field(level, "px"),
+            str_field(level, "sz"),
+            level.get("n").and_then(Value::as_u64).unwrap_or(0)
+        );
+    };
+
+    println!("{:>14} {:>14} {:>5}", "price", "size", "n");
+    side(1).iter().rev().for_each(row);
+    println!("{:-^35}", format!(" {} ", coin));
+    side(0).iter().for_each(row);
+    Ok(())
+}
+
+pub async fn order(
+    ctx: &Context,
+    side: Side,
+    coin: &str,
+    size: f64,
+    price: f64,
+    tif: Tif,
+    reduce_only: bool,
+) -> Result<(), HyperliquidError> {
+    let assets = ctx.assets().await?;
+    let (asset, sz_decimals) = *assets
+        .get(coin)
+        .ok_or_else(|| HyperliquidError::Validation(format!("unknown coin: {}", coin)))?;
+    let sz = round_sz(size, sz_decimals);
+    if sz <= 0.0 {
+        return Err(HyperliquidError::Validation(format!(
+            "size {} rounds to zero at {} decimals",
+            size, sz_decimals
+        )));
+    }
+
+    let action = json!({
+        "type": "order",
+        "orders": [{
+            "a": asset,
+            "b": side == Side::Buy,
+            "p": wire(round_px(price, sz_decimals))?,
+            "s": wire(sz)?,
+            "r": reduce_only,
+            "t": {"limit": {"tif": tif.wire()}},
+        }],
+        "grouping": "na",
+    });
+    let response = ctx.submit(action).await?;
+    if ctx.json {
+        ctx.print_json(&response);
+    }
+    check_response(&response)?;
+    if !ctx.json {
+        print_statuses(&response);
+    }
+    Ok(())
+}
+
+pub async fn positions(ctx: &Context, user: Option<&str>) -> Result<(), HyperliquidError> {
+    let user = ctx.user(user)?;
+    let state = ctx
+        .info(json!({"type": "clearinghouseState", "user": user}))
+        .await?;
+    if ctx.json {
+        ctx.print_json(&state);
+        return Ok(());
+    }
+
+    let summary = state.get("marginSummary").cloned().unwrap_or_default();
+    println!(
+        "account value {}  margin used {}  withdrawable {}",
+        str_field(&summary, "accountValue"),
+        str_field(&summary, "totalMarginUsed"),
+        str_field(&state, "withdrawable")
+    );
+    let positions = state
+        .get("assetPositions")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if positions.is_empty() {
+        println!("no open positions");
+        return Ok(());
+    }
+    println!(
+        "{:<10} {:>14} {:>14} {:>14} {:>14} {:>14}",
+        "coin", "size", "entry", "value", "upnl", "liq"
+    );
+    for entry in positions {
+        let position = entry.get("position").cloned().unwrap_or_default();
+        println!(
+            "{:<10} {:>14} {:>14} {:>14} {:>14} {:>14}",
+            str_field(&position, "coin"),
+            str_field(&position, "szi"),
+            str_field(&position, "entryPx"),
+            str_field(&position, "positionValue"),
+            str_field(&position, "unrealizedPnl"),
+            str_field(&position, "liquidationPx")
+        );
+    }
+    Ok(())
+}
+
+async fn open_orders(ctx: &Context, user: &str) -> Result<Vec<Value>, HyperliquidError> {
+    let orders = ctx
+        .info(json!({"type": "openOrders", "user": user}))
+        .await?;
+    Ok(orders.as_array().cloned().unwrap_or_default())
+}
+
+pub async fn orders(ctx: &Context, user: Option<&str>) -> Result<(), HyperliquidError> {
+    let user = ctx.user(user)?;
+    let orders = open_orders(ctx, &user).await?;
+    if ctx.json {
+        ctx.print_json(&Value::Array(orders));
+        return Ok(());
+    }
+    if orders.is_empty() {
+        println!("no open orders");
+        return Ok(());
+    }
+    println!(
+        "{:<10} {:<5} {:>14} {:>14} {:>14}",
+        "coin", "side", "price", "size", "oid"
+    );
+    for order in orders {
+        println!(
+            "{:<10} {:<5} {:>14} {:>14} {:>14}",
+            str_field(&order, "coin"),
+            if str_field(&order, "side") == "B" {
+                "buy"
+            } else {
+                "sell"
+            },
+            str_field(&order, "limitPx"),
+            str_field(&order, "sz"),
+            order.get("oid").unwrap_or(&Value::Null)
+        );
+    }
+    Ok(())
+}
+
+pub async fn cancel_all(ctx: &Context, coin: Option<&str>) -> Result<(), HyperliquidError> {
+    let user = ctx.user(None)?;
+    let orders = open_orders(ctx, &user).await?;
+    let assets = ctx.assets().await?;
+
+    let cancels: Vec<Value> = orders
+        .iter()
+        .filter(|order| coin.map_or(true, |coin| str_field(order, "coin") == coin))
+        .filter_map(|order| {
+            let (asset, _) = assets.get(str_field(order, "coin"))?;
+            Some(json!({"a": asset, "o": order.get("oid")?.as_u64()?}))
+        })
+        .collect();
+    if cancels.is_empty() {
+        println!("no open orders to cancel");
+        return Ok(());
+    }
+
+    let response = ctx
+        .submit(json!({"type": "cancel", "cancels": cancels}))
+        .await?;
+    if ctx.json {
+        ctx.print_json(&response);
+    }
+    check_response(&response)?;
+    if !ctx.json {
+        print_statuses(&response);
+    }
+    Ok(())
+}
+
+/// Show or flip the kill switches kept in the state database at `store`
+///
+/// Processes opening the same database with
+/// [`KillSwitchRegistry::with_store`] see the change on their next reload.
+pub async fn trading(
+    ctx: &Context,
+    store: &Path,
+    action: TradingAction,
+) -> Result<(), HyperliquidError> {
+    let switches = KillSwitchRegistry::new().with_store(Arc::new(SqliteStore::open(store)?))?;
+    let scope = |coin: Option<&str>| coin.map_or(Scope::All, Scope::coin);
+
+    match action {
+        TradingAction::Status => {
+            let halts = switches.halts();
+            if ctx.json {
+                ctx.print_json(&serde_json::to_value(&halts)?);
+            } else if halts.is_empty() {
+                println!("trading enabled");
+            } else {
+                for halt in halts {
+                    println!(
+                        "disabled {:<10} since {}  {}",
+                        halt.scope.to_string(),
+                        halt.since,
+                        halt.reason
+                    );
+                }
+            }
+        }
+        TradingAction::Disable {
+            coin,
+            reason,
+            cancel,
+        } => {
+            let scope = scope(coin.as_deref());
+            let reason = reason.unwrap_or_else(|| "disabled from hl".to_string());
+            switches.disable(scope.clone(), reason)?;
+            println!("trading disabled for {}", scope);
+            if cancel {
+                cancel_all(ctx, coin.as_deref()).await?;
+            }
+        }
+        TradingAction::Enable { coin } => {
+            let scope = scope(coin.as_deref());
+            switches.enable(&scope)?;
+            println!("trading enabled for {}", scope);
+        }
+    }
+    Ok(())
+}
+
+pub async fn stream(ctx: &Context, channel: StreamChannel) -> Result<(), HyperliquidError> {
+    let (subscription, name, coin) = match channel {
+        StreamChannel::Trades { coin } => {
+            (Subscription::Trades { coin: coin.clone() }, "trades", coin)
+        }
+        StreamChannel::Book { coin } => {
+            (Subscription::L2Book { coin: coin.clone() }, "l2Book", coin)
+        }
+        StreamChannel::Bbo { coin } => (Subscription::Bbo { coin: coin.clone() }, "bbo", coin),
+    };
+
+    let ws_error = |e: WebSocketError| HyperliquidError::WebSocket(e.to_string());
+    let mut ws = WebSocketClient::with_config(WebSocketClientConfig {
+        url: ctx.ws_url.clone(),
+        ..Default::default()
+    })
+    .map_err(ws_error)?;
+    ws.connect().await.map_err(ws_error)?;
+
+    let json = ctx.json;
+    ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+        // Unrouted messages are broadcast to every handler
+        if response.channel != name {
+            return;
+        }
+        if json {
+            println!("{}", response.data);
+            return;
+        }
+        match name {
+            "trades" => {
+                for trade in response.data.as_array().into_iter().flatten() {
+                    println!(
+                        "{} {:<4} {:>14} {:>14}",
+                        trade.get("time").unwrap_or(&Value::Null),
+                        if str_field(trade, "side") == "B" {
+                            "buy"
+                        } else {
+                            "sell"
+                        },
+                        str_field(trade, "px"),
+                        str_field(trade, "sz")
+                    );
+                }
+            }
+            "bbo" => {
+                let level = |index: usize| {
+                    response
+                        .data
+                        .pointer(&format!("/bbo/{}", index))
+                        .map(|level| {
+                            format!("{} x {}", str_field(level, "px"), str_field(level, "sz"))
+                        })
+                        .unwrap_or_else(|| "-".to_string())
+                };
+                println!("{} bid {}  ask {}", coin, level(0), level(1));
+            }
+            _ => {
+                let best = |index: usize| {
+                    response
+                        .data
+                        .pointer(&format!("/levels/{}/0/px", index))
+                        .and_then(Value::as_str)
+                        .unwrap_or("-")
+                        .to_string()
+                };
+                println!("{} bid {}  ask {}", coin, best(0), best(1));
+            }
+        }
+    })
+    .await;
+    ws.subscribe(subscription).await.map_err(ws_error)?;
+
+    tokio::signal::ctrl_c()
+        .await
+        .map_err(|e| HyperliquidError::Unknown(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_price() {
+        assert_eq!(parse_price("@3000").unwrap(), 3000.0);
+        assert_eq!(parse_price("65000.5").unwrap(), 65000.5);
+        assert!(parse_price("@").is_err());
+        assert!(parse_price("-1").is_err());
+    }
+
+    #[test]
+    fn test_check_response() {
+        assert!(check_response(&json!({"status": "ok", "response": {}})).is_ok());
+        let err = check_response(
+            &json!({"status": "err", "response": "User or API Wallet does not exist."}),
+        );
+        assert!(err.unwrap_err().to_string().contains("does not exist"));
+    }
+}
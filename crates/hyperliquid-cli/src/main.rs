@@ -0,0 +1,198 @@
+//! `hl`: command-line client for Hyperliquid
+//!
+//! Reads the environment and signing key from the SDK configuration
+//! (`--config`, `HYPERLIQUID_CONFIG` or the default search paths), so secret
+//! references such as `env:` and `vault:` work as they do for applications.
+//!
+//! ```text
+//! hl book BTC
+//! hl order buy ETH 0.5 @3000
+//! hl positions
+//! hl cancel-all
+//! hl trading --store state.db disable BTC --cancel
+//! hl stream trades BTC
+//! ```
+
+mod commands;
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use hyperliquid_core::{Config, HyperliquidError};
+use tracing_subscriber::EnvFilter;
+
+use crate::commands::Context;
+
+#[derive(Debug, Parser)]
+#[command(name = "hl", version, about = "Command-line client for Hyperliquid")]
+struct Cli {
+    /// Configuration file (defaults to HYPERLIQUID_CONFIG or config/default.toml)
+    #[arg(long, global = true, env = "HYPERLIQUID_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Configuration profile to apply
+    #[arg(long, global = true, env = "HYPERLIQUID_PROFILE")]
+    profile: Option<String>,
+
+    /// Use testnet regardless of the configured environment
+    #[arg(long, global = true)]
+    testnet: bool,
+
+    /// Print raw JSON responses
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Show a coin's order book
+    Book {
+        coin: String,
+        /// Levels per side
+        #[arg(long, default_value_t = 10)]
+        depth: usize,
+    },
+    /// Place a limit order, e.g. `hl order buy ETH 0.5 @3000`
+    Order {
+        side: Side,
+        coin: String,
+        size: f64,
+        /// Limit price, with or without a leading `@`
+        price: String,
+        #[arg(long, value_enum, default_value_t = Tif::Gtc)]
+        tif: Tif,
+        #[arg(long)]
+        reduce_only: bool,
+    },
+    /// Show open positions and account value
+    Positions {
+        /// Account to show (defaults to the configured key's address)
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Show open orders
+    Orders {
+        #[arg(long)]
+        user: Option<String>,
+    },
+    /// Cancel all open orders, optionally only for one coin
+    CancelAll { coin: Option<String> },
+    /// Show or flip the trading kill switches
+    Trading {
+        /// SQLite state database shared with the trading processes
+        #[arg(long, env = "HYPERLIQUID_STATE_DB")]
+        store: PathBuf,
+        #[command(subcommand)]
+        action: TradingAction,
+    },
+    /// Print a market data stream until interrupted
+    Stream {
+        #[command(subcommand)]
+        channel: StreamChannel,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Tif {
+    /// Good till canceled
+    Gtc,
+    /// Immediate or cancel
+    Ioc,
+    /// Add liquidity only (post only)
+    Alo,
+}
+
+impl Tif {
+    pub fn wire(self) -> &'static str {
+        match self {
+            Tif::Gtc => "Gtc",
+            Tif::Ioc => "Ioc",
+            Tif::Alo => "Alo",
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TradingAction {
+    /// List the disabled scopes
+    Status,
+    /// Reject new orders for a coin, or every coin if none is given
+    Disable {
+        coin: Option<String>,
+        #[arg(long)]
+        reason: Option<String>,
+        /// Also cancel the open orders in the scope
+        #[arg(long)]
+        cancel: bool,
+    },
+    /// Accept orders for a coin, or every coin, again
+    Enable { coin: Option<String> },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StreamChannel {
+    /// Trades
+    Trades { coin: String },
+    /// L2 book snapshots
+    Book { coin: String },
+    /// Best bid and offer
+    Bbo { coin: String },
+}
+
+fn load_config(cli: &Cli) -> Result<Config, HyperliquidError> {
+    match &cli.config {
+        Some(path) => Config::load_with_profile(path, cli.profile.as_deref()),
+        None => Config::load_auto(),
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), HyperliquidError> {
+    let config = load_config(&cli)?;
+    let ctx = Context::new(&config, cli.testnet, cli.json)?;
+
+    match cli.command {
+        Command::Book { coin, depth } => commands::book(&ctx, &coin, depth).await,
+        Command::Order {
+            side,
+            coin,
+            size,
+            price,
+            tif,
+            reduce_only,
+        } => {
+            let price = commands::parse_price(&price)?;
+            commands::order(&ctx, side, &coin, size, price, tif, reduce_only).await
+        }
+        Command::Positions { user } => commands::positions(&ctx, user.as_deref()).await,
+        Command::Orders { user } => commands::orders(&ctx, user.as_deref()).await,
+        Command::CancelAll { coin } => commands::cancel_all(&ctx, coin.as_deref()).await,
+        Command::Trading { store, action } => commands::trading(&ctx, &store, action).await,
+        Command::Stream { channel } => commands::stream(&ctx, channel).await,
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(std::io::stderr)
+        .init();
+
+    match run(Cli::parse()).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -3,14 +3,45 @@
 //! This module provides benchmarks to measure the performance improvements
 //! from memory allocation optimizations.
 
-use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, BenchmarkId};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Duration;
 
 use hyperliquid_core::{
+    crypto::Signature,
+    exchange::{ExchangePools, SignedActionBody},
     memory::{ArenaAllocator, StringInterner, ZeroCopyValue, ObjectPool},
-    types::{SymbolInterner, SymbolId, OptimizedOrder, OrderSide, OrderType, TradingAllocator},
+    stream::ArenaMessageDecoder,
+    types::{L2BookSnapshot, SymbolInterner, SymbolId, OptimizedOrder, OrderSide, OrderType, Trade, TradingAllocator},
 };
 
+/// Global allocator wrapper that counts heap allocations
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Count heap allocations performed by `f`
+fn count_allocations<F: FnMut()>(mut f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
 /// Benchmark string interning vs regular string allocation
 fn bench_string_interning(c: &mut Criterion) {
     let mut group = c.benchmark_group("string_interning");
@@ -256,6 +287,119 @@ fn bench_zero_copy_parsing(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark l2Book/trades decoding through serde vs the arena decoder
+fn bench_market_data_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("market_data_decode");
+
+    let l2_book = r#"{"channel":"l2Book","data":{"coin":"BTC","time":1700000000000,"levels":[[{"px":"50000.0","sz":"1.5","n":3},{"px":"49999.0","sz":"2.0","n":5},{"px":"49998.0","sz":"0.7","n":1}],[{"px":"50001.0","sz":"0.5","n":1},{"px":"50002.0","sz":"2.0","n":4},{"px":"50003.0","sz":"1.1","n":2}]]}}"#;
+    let trades = r#"{"channel":"trades","data":[{"coin":"ETH","side":"B","px":"3000.5","sz":"0.1","time":1700000000001,"hash":"0xabc","tid":1},{"coin":"ETH","side":"A","px":"3000.0","sz":"0.2","time":1700000000002,"hash":"0xdef","tid":2}]}"#;
+
+    let serde_decode = |text: &str| {
+        let value: serde_json::Value = serde_json::from_str(text).unwrap();
+        match value["channel"].as_str() {
+            Some("l2Book") => {
+                black_box(serde_json::from_value::<L2BookSnapshot>(value["data"].clone()).unwrap());
+            }
+            _ => {
+                black_box(serde_json::from_value::<Vec<Trade>>(value["data"].clone()).unwrap());
+            }
+        }
+    };
+
+    // The decoder only resolves coins already registered by the asset map
+    SymbolId::intern("BTC");
+    SymbolId::intern("ETH");
+
+    // Report allocations per message once, outside of timed iterations
+    let mut decoder = ArenaMessageDecoder::new();
+    for (name, text) in [("l2Book", l2_book), ("trades", trades)] {
+        // Warm up the arena so steady-state allocations are measured
+        decoder.decode(text).unwrap();
+        let serde_allocs = count_allocations(|| serde_decode(text));
+        let arena_allocs = count_allocations(|| {
+            black_box(decoder.decode(text).unwrap());
+        });
+        println!(
+            "{}: serde {} allocations/message, arena {} allocations/message",
+            name, serde_allocs, arena_allocs
+        );
+    }
+
+    for (name, text) in [("l2Book", l2_book), ("trades", trades)] {
+        group.bench_with_input(BenchmarkId::new("serde", name), &text, |b, text| {
+            b.iter(|| serde_decode(black_box(text)))
+        });
+
+        group.bench_with_input(BenchmarkId::new("arena", name), &text, |b, text| {
+            b.iter(|| {
+                black_box(decoder.decode(black_box(text)).unwrap());
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark building and serializing a signed `/exchange` order body
+///
+/// The `value` path wraps the action in a `serde_json::Value` and serializes
+/// it separately for the request log, the request and the audit hash; the
+/// `pooled` path writes it once into a pooled buffer.
+fn bench_exchange_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("exchange_serialization");
+
+    let action = serde_json::json!({
+        "type": "order",
+        "orders": [{"a": 0, "b": true, "p": "50000", "s": "0.01", "r": false,
+                    "t": {"limit": {"tif": "Gtc"}}, "c": "0x00000000000000000000000000000001"}],
+        "grouping": "na"
+    });
+    let signature = Signature::new(
+        "0x5c3ee5f7b2d8c4ed1b4e6b1c8f0f1e7e0c4a8d6a2b5b7a9e3f1d0c2b4a6e8f01",
+        "0x1a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f809",
+        27,
+    );
+    let nonce = 1_700_000_000_000u64;
+    let pools = ExchangePools::new();
+
+    let value_path = |action: serde_json::Value| {
+        let body = serde_json::json!({
+            "action": action,
+            "nonce": nonce,
+            "signature": signature,
+            "vaultAddress": Option::<&str>::None,
+        });
+        black_box(serde_json::to_string(&body).unwrap());
+        black_box(serde_json::to_vec(&body).unwrap());
+        black_box(serde_json::to_vec(&body).unwrap());
+    };
+    let pooled_path = |action: &serde_json::Value| {
+        let body = SignedActionBody {
+            action,
+            nonce,
+            signature: &signature,
+            vault_address: None,
+        };
+        black_box(pools.serialize(&body).unwrap());
+    };
+
+    // Report allocations per order once, outside of timed iterations
+    pooled_path(&action);
+    let value_allocs = count_allocations(|| value_path(action.clone()));
+    let pooled_allocs = count_allocations(|| pooled_path(&action));
+    println!(
+        "order: value {} allocations/submission, pooled {} allocations/submission",
+        value_allocs, pooled_allocs
+    );
+
+    group.bench_function("value", |b| {
+        b.iter_batched(|| action.clone(), value_path, BatchSize::SmallInput)
+    });
+    group.bench_function("pooled", |b| b.iter(|| pooled_path(black_box(&action))));
+
+    group.finish();
+}
+
 /// Benchmark different memory allocation strategies
 fn bench_allocation_strategies(c: &mut Criterion) {
     let mut group = c.benchmark_group("allocation_strategies");
@@ -304,6 +448,8 @@ criterion_group!(
     bench_trading_allocator,
     bench_memory_usage,
     bench_zero_copy_parsing,
+    bench_market_data_decode,
+    bench_exchange_serialization,
     bench_allocation_strategies
 );
 
@@ -0,0 +1,325 @@
+//! Several trading accounts in one process
+//!
+//! An [`AccountManager`] owns an [`Account`] per label: the master account,
+//! API wallets trading for it, sub-accounts and vaults. Every account gets
+//! its own [`ExchangeClient`] and [`UserStream`], but they share
+//! - the HTTP connection pool of the template exchange client and the
+//!   [`HttpClient`] used for REST recovery;
+//! - one [`WebSocketClient`] for the user streams
+//!   ([`attach`](AccountManager::attach));
+//! - the per-IP [`RateLimiter`], which every action draws from.
+//!
+//! Actions are routed by label with [`post_action`](AccountManager::post_action),
+//! which signs with the account's key and vault address and records the
+//! account's usage. Each account can additionally be held to its own budget
+//! ([`with_account_limit`](AccountManager::with_account_limit)), so one busy
+//! strategy can't starve the others.
+//!
+//! ```no_run
+//! # async fn example(http: hyperliquid_core::HttpClient, master: hyperliquid_core::ExchangeClientConfig, vault: hyperliquid_core::ExchangeClientConfig) -> hyperliquid_core::Result<()> {
+//! use hyperliquid_core::accounts::{AccountKind, AccountManager};
+//! use hyperliquid_core::ExchangeClient;
+//!
+//! let manager = AccountManager::new(http, ExchangeClient::new(master.clone()));
+//! manager.add("main", AccountKind::Master, master)?;
+//! manager.add("fund", AccountKind::Vault, vault)?;
+//! let action = serde_json::json!({"type": "cancel", "cancels": [{"a": 0, "o": 123}]});
+//! manager.post_action("fund", action).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde_json::Value;
+use tracing::info;
+
+use crate::client::HttpClient;
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::exchange::{ExchangeClient, ExchangeClientConfig, SignerConfig};
+use crate::scheduler::{rate_limit_retry_after, RateLimiter, EXCHANGE_WEIGHT};
+use crate::stream::{UserStream, WebSocketClient};
+
+/// Role of an account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    /// An account signing with its own key
+    Master,
+    /// An API wallet trading for its account
+    Agent,
+    /// A sub-account traded by its master
+    SubAccount,
+    /// A vault traded by its leader
+    Vault,
+}
+
+/// Actions sent for an account
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccountUsage {
+    pub actions: u64,
+    /// Weight drawn from the shared budget
+    pub weight: u64,
+    pub errors: u64,
+    /// Actions refused with a 429
+    pub rate_limited: u64,
+}
+
+/// Account managed by an [`AccountManager`]
+#[derive(Debug, Clone)]
+pub struct Account {
+    label: String,
+    kind: AccountKind,
+    exchange: ExchangeClient,
+    wallet: Wallet,
+    /// Vault or sub-account traded for, as sent with actions
+    vault_address: Option<String>,
+    stream: UserStream,
+    limiter: Option<RateLimiter>,
+    usage: Arc<Mutex<AccountUsage>>,
+}
+
+impl Account {
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn kind(&self) -> AccountKind {
+        self.kind
+    }
+
+    pub fn exchange(&self) -> &ExchangeClient {
+        &self.exchange
+    }
+
+    /// Key the account's actions are signed with
+    pub fn wallet(&self) -> &Wallet {
+        &self.wallet
+    }
+
+    pub fn vault_address(&self) -> Option<&str> {
+        self.vault_address.as_deref()
+    }
+
+    /// Address holding the positions: the vault or sub-account if any
+    pub fn user(&self) -> &str {
+        self.stream.user()
+    }
+
+    /// Fills and order updates of [`user`](Self::user)
+    pub fn stream(&self) -> &UserStream {
+        &self.stream
+    }
+
+    pub fn usage(&self) -> AccountUsage {
+        *self.lock_usage()
+    }
+
+    fn lock_usage(&self) -> MutexGuard<'_, AccountUsage> {
+        self.usage.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Accounts of one process, sharing connections and the rate-limit budget
+#[derive(Debug, Clone)]
+pub struct AccountManager {
+    http: HttpClient,
+    exchange: ExchangeClient,
+    limiter: RateLimiter,
+    account_limit: Option<u32>,
+    accounts: Arc<Mutex<BTreeMap<String, Account>>>,
+}
+
+impl AccountManager {
+    /// Manage accounts over `http` and the connection pool of `exchange`
+    ///
+    /// Accounts added later must use the same endpoint as `exchange`.
+    pub fn new(http: HttpClient, exchange: ExchangeClient) -> Self {
+        Self {
+            http,
+            exchange,
+            limiter: RateLimiter::hyperliquid(),
+            account_limit: None,
+            accounts: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    /// Draw actions from `limiter`, e.g. a [`Scheduler`](crate::scheduler::Scheduler)'s
+    pub fn with_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Limit every account added afterwards to `weight_per_minute`
+    pub fn with_account_limit(mut self, weight_per_minute: u32) -> Self {
+        self.account_limit = Some(weight_per_minute);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Account>> {
+        self.accounts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Shared per-IP budget
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    /// Add an account under `label`
+    ///
+    /// `config` must carry a local key: the account's own for
+    /// [`AccountKind::Master`], an approved API wallet for
+    /// [`AccountKind::Agent`]; sub-accounts and vaults need their address
+    /// as the config's vault address.
+    pub fn add(
+        &self,
+        label: impl Into<String>,
+        kind: AccountKind,
+        config: ExchangeClientConfig,
+    ) -> Result<Account, HyperliquidError> {
+        let label = label.into();
+        let invalid = |message: String| Err(HyperliquidError::Config(message));
+
+        if config.base_url != self.exchange.config().base_url {
+            return invalid(format!(
+                "account {} uses {}, not the shared endpoint {}",
+                label,
+                config.base_url,
+                self.exchange.config().base_url
+            ));
+        }
+        let wallet = match (&config.signer, kind) {
+            (Some(SignerConfig::Wallet(wallet)), AccountKind::Master)
+            | (Some(SignerConfig::Agent(wallet)), AccountKind::Agent)
+            | (
+                Some(SignerConfig::Wallet(wallet) | SignerConfig::Agent(wallet)),
+                AccountKind::SubAccount | AccountKind::Vault,
+            ) => wallet.clone(),
+            _ => {
+                let expected = match kind {
+                    AccountKind::Master => "the account's own key",
+                    AccountKind::Agent => "an agent key",
+                    AccountKind::SubAccount | AccountKind::Vault => "a local key",
+                };
+                return invalid(format!("{:?} account {} needs {}", kind, label, expected));
+            }
+        };
+        let needs_vault = matches!(kind, AccountKind::SubAccount | AccountKind::Vault);
+        if needs_vault != config.vault_address.is_some() {
+            return invalid(format!(
+                "account {} of kind {:?} {} a vault address",
+                label,
+                kind,
+                if needs_vault { "needs" } else { "can't have" }
+            ));
+        }
+
+        let vault_address = config.vault_address.map(|vault| format!("{:?}", vault));
+        let user = vault_address
+            .clone()
+            .unwrap_or_else(|| format!("{:?}", config.account));
+        let account = Account {
+            label: label.clone(),
+            kind,
+            exchange: self.exchange.for_account(config),
+            wallet,
+            vault_address,
+            stream: UserStream::new(self.http.clone(), user),
+            limiter: self.account_limit.map(RateLimiter::new),
+            usage: Arc::new(Mutex::new(AccountUsage::default())),
+        };
+
+        let mut accounts = self.lock();
+        if accounts.contains_key(&label) {
+            return invalid(format!("account {} already exists", label));
+        }
+        accounts.insert(label.clone(), account.clone());
+        info!("Added {:?} account {} for {}", kind, label, account.user());
+        Ok(account)
+    }
+
+    /// Stop managing the account under `label`
+    pub fn remove(&self, label: &str) -> Option<Account> {
+        self.lock().remove(label)
+    }
+
+    pub fn get(&self, label: &str) -> Option<Account> {
+        self.lock().get(label).cloned()
+    }
+
+    /// Labels of the managed accounts, sorted
+    pub fn labels(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// Usage of every account by label
+    pub fn usage(&self) -> BTreeMap<String, AccountUsage> {
+        self.lock()
+            .iter()
+            .map(|(label, account)| (label.clone(), account.usage()))
+            .collect()
+    }
+
+    /// Feed the user streams of every account from `ws`
+    ///
+    /// Accounts added afterwards need [`UserStream::attach`] on their own.
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        let accounts: Vec<Account> = self.lock().values().cloned().collect();
+        for account in accounts {
+            account.stream.attach(ws).await?;
+        }
+        Ok(())
+    }
+
+    /// Sign an L1 action for the account under `label` and submit it
+    ///
+    /// Waits for the weight in the shared budget and the account's own, if
+    /// limited. A 429 pauses the account's budget as well as the shared one.
+    pub async fn post_action(&self, label: &str, action: Value) -> Result<Value, HyperliquidError> {
+        let account = self
+            .get(label)
+            .ok_or_else(|| HyperliquidError::Config(format!("unknown account {}", label)))?;
+
+        if let Some(limiter) = &account.limiter {
+            limiter.acquire(EXCHANGE_WEIGHT).await;
+        }
+        self.limiter.acquire(EXCHANGE_WEIGHT).await;
+
+        let result = account
+            .exchange
+            .post_signed_action(action, &account.wallet, account.vault_address())
+            .await;
+
+        {
+            let mut usage = account.lock_usage();
+            usage.actions += 1;
+            usage.weight += EXCHANGE_WEIGHT as u64;
+            if result.is_err() {
+                usage.errors += 1;
+            }
+        }
+        crate::telemetry::counter!("hyperliquid_account_actions_total", "account" => label.to_string())
+            .increment(1);
+        match &result {
+            Ok(_) => {
+                self.limiter.record_success();
+                if let Some(limiter) = &account.limiter {
+                    limiter.record_success();
+                }
+            }
+            Err(e) => {
+                if let Some(retry_after) = rate_limit_retry_after(e) {
+                    account.lock_usage().rate_limited += 1;
+                    crate::telemetry::counter!("hyperliquid_account_rate_limited_total", "account" => label.to_string())
+                        .increment(1);
+                    self.limiter.back_off(retry_after);
+                    if let Some(limiter) = &account.limiter {
+                        limiter.back_off(retry_after);
+                    }
+                }
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,286 @@
+//! Waiting for an order's acknowledgement
+//!
+//! The placement response only says an order was accepted for processing.
+//! [`AckWaiter::wait_for_order_ack`] resolves once the exchange reports a
+//! status for the order's cloid: resting, filled, canceled or rejected. It
+//! watches the `orderUpdates` stream and, in case the message is missed or
+//! the stream is down, polls `orderStatus` on an interval.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient, ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::time::Duration;
+//! use hyperliquid_core::ack::AckWaiter;
+//! use hyperliquid_core::oms::OrderState;
+//!
+//! let acks = AckWaiter::new(client, "0x1234...");
+//! acks.attach(&ws).await?;
+//! // ... place an order with cloid 0x0000000000000000000000000000002a
+//! let ack = acks
+//!     .wait_for_order_ack("0x0000000000000000000000000000002a", Duration::from_secs(5))
+//!     .await?;
+//! if ack.state == OrderState::Rejected {
+//!     println!("order {} rejected: {}", ack.oid, ack.status);
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, warn};
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::oms::OrderState;
+use crate::stream::{UserEvent, UserEventKind};
+#[cfg(feature = "ws")]
+use crate::stream::{WebSocketClient, WebSocketResponse};
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the update channel; waiters that fall behind rely on polling
+const UPDATE_CAPACITY: usize = 1024;
+
+/// Default time between `orderStatus` polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Where an acknowledgement was seen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AckSource {
+    Stream,
+    Poll,
+}
+
+/// Status the exchange reported for an order
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderAck {
+    pub oid: u64,
+    pub cloid: Option<String>,
+    pub coin: String,
+    /// `Acked`, `Filled`, `Canceled` or `Rejected`
+    pub state: OrderState,
+    /// Exchange status, e.g. `open` or `perpMarginRejected`
+    pub status: String,
+    /// When the status was set (ms)
+    pub status_timestamp: u64,
+    pub source: AckSource,
+}
+
+impl OrderAck {
+    /// Whether the order can no longer change
+    pub fn is_terminal(&self) -> bool {
+        self.state.is_terminal()
+    }
+}
+
+/// Entry of the `orderUpdates` stream, also the `order` of an `orderStatus`
+/// response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsOrderUpdate {
+    order: WsOrder,
+    status: String,
+    #[serde(default)]
+    status_timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsOrder {
+    oid: u64,
+    coin: String,
+    #[serde(default)]
+    cloid: Option<String>,
+}
+
+/// State an exchange order status puts an order in
+fn state_of(status: &str) -> Option<OrderState> {
+    match status {
+        "open" | "triggered" => Some(OrderState::Acked),
+        "filled" => Some(OrderState::Filled),
+        "rejected" => Some(OrderState::Rejected),
+        other if other.ends_with("Rejected") => Some(OrderState::Rejected),
+        // "canceled", "marginCanceled", "reduceOnlyCanceled", ...
+        other if other.ends_with("anceled") => Some(OrderState::Canceled),
+        _ => None,
+    }
+}
+
+fn parse_ack(update: &Value, source: AckSource) -> Option<OrderAck> {
+    let update: WsOrderUpdate = match serde_json::from_value(update.clone()) {
+        Ok(update) => update,
+        Err(e) => {
+            warn!("Failed to parse order update: {}", e);
+            return None;
+        }
+    };
+    let Some(state) = state_of(&update.status) else {
+        debug!("Ignoring order status {}", update.status);
+        return None;
+    };
+    Some(OrderAck {
+        oid: update.order.oid,
+        cloid: update.order.cloid,
+        coin: update.order.coin,
+        state,
+        status: update.status,
+        status_timestamp: update.status_timestamp,
+        source,
+    })
+}
+
+/// Resolves order acknowledgements of one user by cloid
+///
+/// Cheap to clone; clones share the update feed.
+#[derive(Clone)]
+pub struct AckWaiter {
+    client: HttpClient,
+    user: String,
+    poll_interval: Duration,
+    updates: broadcast::Sender<OrderAck>,
+}
+
+impl std::fmt::Debug for AckWaiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AckWaiter")
+            .field("user", &self.user)
+            .field("poll_interval", &self.poll_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AckWaiter {
+    pub fn new(client: HttpClient, user: impl Into<String>) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CAPACITY);
+        Self {
+            client,
+            user: user.into(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            updates,
+        }
+    }
+
+    /// Time between `orderStatus` polls while waiting (default 500ms)
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Apply a `data` payload from the `orderUpdates` channel
+    pub fn handle_order_updates(&self, data: &Value) {
+        let Some(updates) = data.as_array() else {
+            warn!("Ignoring malformed orderUpdates message");
+            return;
+        };
+        for update in updates {
+            if let Some(ack) = parse_ack(update, AckSource::Stream) {
+                // No waiters is fine
+                let _ = self.updates.send(ack);
+            }
+        }
+    }
+
+    /// Apply an event of a [`UserStream`](crate::stream::UserStream)
+    pub fn handle_user_event(&self, event: &UserEvent) {
+        if let UserEventKind::OrderUpdate(update) = &event.kind {
+            self.handle_order_updates(&json!([update]));
+        }
+    }
+
+    /// Subscribe `ws` to the user's order updates and feed them in
+    ///
+    /// Registers the handler for the `orderUpdates` subscription, replacing
+    /// any existing one. To share the subscription with an
+    /// [`OrderManager`](crate::oms::OrderManager), feed both from a
+    /// [`UserStream`](crate::stream::UserStream) instead.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        let subscription: Subscription =
+            serde_json::from_value(json!({"type": "orderUpdates", "user": self.user}))?;
+        let acks = self.clone();
+        ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            if response.channel.starts_with("orderUpdates") {
+                acks.handle_order_updates(&response.data);
+            }
+        })
+        .await;
+        ws.subscribe(subscription)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))
+    }
+
+    /// Look the order up with `orderStatus`; `None` while it is unknown
+    async fn poll(&self, cloid: &str) -> Result<Option<OrderAck>, HyperliquidError> {
+        // `oid` takes a cloid as well
+        let request = json!({"type": "orderStatus", "user": self.user, "oid": cloid});
+        let response: Value = self.client.post("/info", &request).await?;
+        match response.get("status").and_then(Value::as_str) {
+            Some("order") => Ok(response
+                .get("order")
+                .and_then(|order| parse_ack(order, AckSource::Poll))
+                .map(|mut ack| {
+                    ack.cloid.get_or_insert_with(|| cloid.to_string());
+                    ack
+                })),
+            _ => Ok(None),
+        }
+    }
+
+    /// Wait until the exchange reports a status for the order with `cloid`
+    ///
+    /// Resolves on the first of `open`, `filled`, a cancel or a rejection,
+    /// whichever the stream or a poll sees first; check
+    /// [`OrderAck::is_terminal`] to tell a resting order from a finished
+    /// one. The first poll goes out after one poll interval, so an update
+    /// already streamed before the call is still found. Failed polls are
+    /// logged and retried. Fails with [`HyperliquidError::Timeout`] if
+    /// nothing is seen within `timeout`.
+    pub async fn wait_for_order_ack(
+        &self,
+        cloid: &str,
+        timeout: Duration,
+    ) -> Result<OrderAck, HyperliquidError> {
+        let mut updates = self.updates.subscribe();
+        let deadline = tokio::time::sleep(timeout);
+        tokio::pin!(deadline);
+        let mut poll =
+            tokio::time::interval_at(Instant::now() + self.poll_interval, self.poll_interval);
+        poll.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let matches = |ack: &OrderAck| {
+            ack.cloid
+                .as_deref()
+                .is_some_and(|id| id.eq_ignore_ascii_case(cloid))
+        };
+
+        loop {
+            tokio::select! {
+                _ = &mut deadline => {
+                    return Err(HyperliquidError::Timeout(format!(
+                        "no acknowledgement for order {} within {:?}",
+                        cloid, timeout
+                    )));
+                }
+                update = updates.recv() => match update {
+                    Ok(ack) if matches(&ack) => return Ok(ack),
+                    Ok(_) => {}
+                    // Lagged; polling covers what was missed. The sender
+                    // lives in `self`, so the channel never closes.
+                    Err(_) => {}
+                },
+                _ = poll.tick() => match self.poll(cloid).await {
+                    Ok(Some(ack)) => return Ok(ack),
+                    Ok(None) => {}
+                    Err(e) => warn!("Failed to poll status of order {}: {}", cloid, e),
+                },
+            }
+        }
+    }
+}
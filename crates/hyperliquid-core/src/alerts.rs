@@ -0,0 +1,716 @@
+//! Notification hooks for account and connection alerts
+//!
+//! An [`AlertManager`] turns the SDK's event streams into [`Alert`]s and
+//! delivers them to registered [`AlertHook`]s: a generic JSON webhook, Slack,
+//! Telegram, or any async closure via [`FnHook`]. Built-in triggers cover:
+//!
+//! - positions within a configured distance of liquidation, and the cross
+//!   margin ratio, from [`MarginCalculator`](crate::margin::MarginCalculator) reports;
+//! - order rejections from the [`OrderManager`](crate::oms::OrderManager) event stream;
+//! - websocket outages longer than a threshold, from [`WebSocketEvent`]s;
+//! - liquidation warnings and system notices from the `notification`
+//!   channel, via [`AlertManager::attach_notifications`];
+//! - circuit breakers and anything else the application reports through
+//!   [`AlertManager::notify`].
+//!
+//! Alerts below the minimum severity are dropped, and repeats of the same
+//! alert within the cooldown are suppressed. Hook failures are logged and do
+//! not affect other hooks.
+//!
+//! ```no_run
+//! # fn example(margin: hyperliquid_core::margin::MarginCalculator) {
+//! use hyperliquid_core::alerts::{AlertManager, SlackHook, TelegramHook};
+//!
+//! let alerts = AlertManager::new()
+//!     .with_hook(SlackHook::new("https://hooks.slack.com/services/..."))
+//!     .with_hook(TelegramHook::new("bot-token", "chat-id"))
+//!     .with_liquidation_threshold(0.05);
+//! let _job = alerts.watch_margin(margin.updates());
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+use crate::margin::MarginReport;
+use crate::oms::{OrderEvent, OrderEventKind};
+use crate::stream::WebSocketResponse;
+#[cfg(feature = "ws")]
+use crate::stream::{WebSocketClient, WebSocketEvent};
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+use crate::types::{Notification, NotificationMsg};
+
+const DEFAULT_COOLDOWN: Duration = Duration::from_secs(300);
+const DEFAULT_LIQUIDATION_THRESHOLD: f64 = 0.1;
+const DEFAULT_MARGIN_RATIO_THRESHOLD: f64 = 0.8;
+const DEFAULT_DISCONNECT_THRESHOLD: Duration = Duration::from_secs(30);
+/// A risk alert re-arms once the metric recovers this far past its threshold
+const REARM_FACTOR: f64 = 1.5;
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// What an alert is about
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AlertKind {
+    LiquidationRisk {
+        coin: String,
+        /// Relative mark move to the liquidation price
+        distance: f64,
+        liquidation_px: Option<f64>,
+    },
+    MarginRatio {
+        ratio: f64,
+    },
+    WsDisconnected {
+        down_for_secs: u64,
+    },
+    WsReconnected {
+        down_for_secs: u64,
+    },
+    OrderRejected {
+        coin: String,
+        cloid: Option<String>,
+        reason: String,
+    },
+    CircuitBreakerOpen {
+        name: String,
+        reason: String,
+    },
+    /// Pushed by the exchange on the `notification` channel
+    Notification {
+        notification: Notification,
+    },
+    Custom {
+        name: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub severity: Severity,
+    #[serde(flatten)]
+    pub kind: AlertKind,
+    pub message: String,
+    /// Milliseconds since the epoch
+    pub time: u64,
+}
+
+impl Alert {
+    pub fn new(severity: Severity, kind: AlertKind, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            kind,
+            message: message.into(),
+            time: now_millis(),
+        }
+    }
+
+    /// Identity used for cooldown; alerts about the same subject share a key
+    fn key(&self) -> String {
+        match &self.kind {
+            AlertKind::LiquidationRisk { coin, .. } => format!("liquidationRisk:{}", coin),
+            AlertKind::MarginRatio { .. } => "marginRatio".to_string(),
+            AlertKind::WsDisconnected { .. } => "wsDisconnected".to_string(),
+            AlertKind::WsReconnected { .. } => "wsReconnected".to_string(),
+            AlertKind::OrderRejected { coin, reason, .. } => {
+                format!("orderRejected:{}:{}", coin, reason)
+            }
+            AlertKind::CircuitBreakerOpen { name, .. } => format!("circuitBreaker:{}", name),
+            AlertKind::Notification { notification } => {
+                format!("notification:{}", notification.message())
+            }
+            AlertKind::Custom { name } => format!("custom:{}:{}", name, self.message),
+        }
+    }
+
+    /// One-line rendering for chat hooks
+    pub fn text(&self) -> String {
+        let label = match self.severity {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        };
+        format!("[{}] {}", label, self.message)
+    }
+}
+
+/// Future returned by [`AlertHook::send`]
+pub type HookFuture<'a> = Pin<Box<dyn Future<Output = Result<(), HyperliquidError>> + Send + 'a>>;
+
+/// Destination for alerts
+pub trait AlertHook: Send + Sync + 'static {
+    /// Name used in delivery failure logs
+    fn name(&self) -> &str;
+
+    fn send<'a>(&'a self, alert: &'a Alert) -> HookFuture<'a>;
+}
+
+/// POSTs each alert as JSON to a URL
+#[derive(Debug, Clone)]
+pub struct WebhookHook {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookHook {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl AlertHook for WebhookHook {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn send<'a>(&'a self, alert: &'a Alert) -> HookFuture<'a> {
+        Box::pin(async move {
+            self.client
+                .post(&self.url)
+                .json(alert)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+/// Posts alerts to a Slack incoming webhook
+#[derive(Debug, Clone)]
+pub struct SlackHook {
+    webhook_url: String,
+    client: reqwest::Client,
+}
+
+impl SlackHook {
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl AlertHook for SlackHook {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    fn send<'a>(&'a self, alert: &'a Alert) -> HookFuture<'a> {
+        Box::pin(async move {
+            self.client
+                .post(&self.webhook_url)
+                .json(&json!({"text": alert.text()}))
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+/// Sends alerts to a Telegram chat through a bot
+#[derive(Clone)]
+pub struct TelegramHook {
+    bot_token: String,
+    chat_id: String,
+    api_url: String,
+    client: reqwest::Client,
+}
+
+impl TelegramHook {
+    pub fn new(bot_token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        Self {
+            bot_token: bot_token.into(),
+            chat_id: chat_id.into(),
+            api_url: TELEGRAM_API_URL.to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use a different Bot API server
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl std::fmt::Debug for TelegramHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramHook")
+            .field("bot_token", &"<redacted>")
+            .field("chat_id", &self.chat_id)
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+impl AlertHook for TelegramHook {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    fn send<'a>(&'a self, alert: &'a Alert) -> HookFuture<'a> {
+        Box::pin(async move {
+            let url = format!("{}/bot{}/sendMessage", self.api_url, self.bot_token);
+            self.client
+                .post(url)
+                .json(&json!({"chat_id": self.chat_id, "text": alert.text()}))
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(())
+        })
+    }
+}
+
+/// Hook backed by an async closure
+pub struct FnHook<F> {
+    name: String,
+    f: F,
+}
+
+impl<F, Fut> FnHook<F>
+where
+    F: Fn(Alert) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HyperliquidError>> + Send + 'static,
+{
+    pub fn new(name: impl Into<String>, f: F) -> Self {
+        Self {
+            name: name.into(),
+            f,
+        }
+    }
+}
+
+impl<F, Fut> AlertHook for FnHook<F>
+where
+    F: Fn(Alert) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), HyperliquidError>> + Send + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send<'a>(&'a self, alert: &'a Alert) -> HookFuture<'a> {
+        Box::pin((self.f)(alert.clone()))
+    }
+}
+
+#[derive(Default)]
+struct State {
+    last_sent: HashMap<String, Instant>,
+    /// Coins with an outstanding liquidation alert
+    at_risk: HashSet<String>,
+    margin_alerted: bool,
+    disconnected_at: Option<Instant>,
+    disconnect_alerted: bool,
+}
+
+/// Routes alerts from event streams to hooks
+#[derive(Clone)]
+pub struct AlertManager {
+    hooks: Vec<Arc<dyn AlertHook>>,
+    state: Arc<Mutex<State>>,
+    min_severity: Severity,
+    cooldown: Duration,
+    liquidation_threshold: f64,
+    margin_ratio_threshold: f64,
+    disconnect_threshold: Duration,
+}
+
+impl Default for AlertManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AlertManager {
+    pub fn new() -> Self {
+        Self {
+            hooks: Vec::new(),
+            state: Arc::new(Mutex::new(State::default())),
+            min_severity: Severity::Info,
+            cooldown: DEFAULT_COOLDOWN,
+            liquidation_threshold: DEFAULT_LIQUIDATION_THRESHOLD,
+            margin_ratio_threshold: DEFAULT_MARGIN_RATIO_THRESHOLD,
+            disconnect_threshold: DEFAULT_DISCONNECT_THRESHOLD,
+        }
+    }
+
+    pub fn with_hook(mut self, hook: impl AlertHook) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Drop alerts below `severity`
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = severity;
+        self
+    }
+
+    /// Suppress repeats of the same alert within `cooldown`
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Alert when a position is within `distance` (relative mark move) of liquidation
+    ///
+    /// Positions within half the distance raise a critical alert.
+    pub fn with_liquidation_threshold(mut self, distance: f64) -> Self {
+        self.liquidation_threshold = distance;
+        self
+    }
+
+    /// Alert when the cross margin ratio reaches `ratio` (liquidation at 1)
+    pub fn with_margin_ratio_threshold(mut self, ratio: f64) -> Self {
+        self.margin_ratio_threshold = ratio;
+        self
+    }
+
+    /// Alert when the websocket has been down for `threshold`
+    pub fn with_disconnect_threshold(mut self, threshold: Duration) -> Self {
+        self.disconnect_threshold = threshold;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply severity and cooldown filters, recording the alert as sent
+    fn admit(&self, alert: &Alert) -> bool {
+        if alert.severity < self.min_severity {
+            return false;
+        }
+        let mut state = self.lock();
+        let now = Instant::now();
+        let key = alert.key();
+        if let Some(last) = state.last_sent.get(&key) {
+            if now.duration_since(*last) < self.cooldown {
+                return false;
+            }
+        }
+        state.last_sent.insert(key, now);
+        true
+    }
+
+    /// Deliver `alert` to every hook and wait for them
+    ///
+    /// Returns the number of hooks that accepted it; 0 when filtered out.
+    pub async fn send(&self, alert: Alert) -> usize {
+        if !self.admit(&alert) {
+            return 0;
+        }
+        let deliveries = self.hooks.iter().map(|hook| {
+            let alert = &alert;
+            async move {
+                match hook.send(alert).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        warn!(hook = hook.name(), "Alert delivery failed: {}", e);
+                        false
+                    }
+                }
+            }
+        });
+        futures::future::join_all(deliveries)
+            .await
+            .into_iter()
+            .filter(|delivered| *delivered)
+            .count()
+    }
+
+    /// Deliver `alert` in the background
+    pub fn notify(&self, alert: Alert) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            manager.send(alert).await;
+        });
+    }
+
+    /// Report an opened circuit breaker
+    pub fn circuit_breaker_open(&self, name: &str, reason: &str) {
+        self.notify(Alert::new(
+            Severity::Critical,
+            AlertKind::CircuitBreakerOpen {
+                name: name.to_string(),
+                reason: reason.to_string(),
+            },
+            format!("Circuit breaker {} opened: {}", name, reason),
+        ));
+    }
+
+    /// Check a margin report against the liquidation and margin ratio thresholds
+    pub fn check_margin(&self, report: &MarginReport) {
+        let mut alerts = Vec::new();
+        {
+            let mut state = self.lock();
+            for position in &report.positions {
+                let Some(distance) = position.distance_to_liquidation else {
+                    state.at_risk.remove(&position.coin);
+                    continue;
+                };
+                if distance <= self.liquidation_threshold {
+                    if state.at_risk.insert(position.coin.clone()) {
+                        let severity = if distance <= self.liquidation_threshold / 2.0 {
+                            Severity::Critical
+                        } else {
+                            Severity::Warning
+                        };
+                        alerts.push(Alert::new(
+                            severity,
+                            AlertKind::LiquidationRisk {
+                                coin: position.coin.clone(),
+                                distance,
+                                liquidation_px: position.liquidation_px,
+                            },
+                            format!(
+                                "{} is {:.2}% from liquidation (mark {}, liquidation {})",
+                                position.coin,
+                                distance * 100.0,
+                                position.mark_px,
+                                position
+                                    .liquidation_px
+                                    .map_or_else(|| "-".to_string(), |px| px.to_string())
+                            ),
+                        ));
+                    }
+                } else if distance > self.liquidation_threshold * REARM_FACTOR {
+                    state.at_risk.remove(&position.coin);
+                }
+            }
+            // Closed positions re-arm too
+            state
+                .at_risk
+                .retain(|coin| report.positions.iter().any(|p| &p.coin == coin));
+
+            if let Some(ratio) = report.cross_margin_ratio {
+                if ratio >= self.margin_ratio_threshold && !state.margin_alerted {
+                    state.margin_alerted = true;
+                    alerts.push(Alert::new(
+                        Severity::Critical,
+                        AlertKind::MarginRatio { ratio },
+                        format!("Cross margin ratio at {:.1}%", ratio * 100.0),
+                    ));
+                } else if ratio < self.margin_ratio_threshold / REARM_FACTOR {
+                    state.margin_alerted = false;
+                }
+            }
+        }
+        for alert in alerts {
+            self.notify(alert);
+        }
+    }
+
+    /// Alert on order rejections
+    pub fn handle_order_event(&self, event: &OrderEvent) {
+        if let OrderEventKind::Rejected { reason } = &event.kind {
+            self.notify(Alert::new(
+                Severity::Warning,
+                AlertKind::OrderRejected {
+                    coin: event.order.coin.clone(),
+                    cloid: event.order.cloid.clone(),
+                    reason: reason.clone(),
+                },
+                format!("{} order rejected: {}", event.order.coin, reason),
+            ));
+        }
+    }
+
+    /// Alert on an exchange notification
+    ///
+    /// Liquidation warnings are critical, system notices warnings, and
+    /// anything else informational.
+    pub fn handle_notification(&self, notification: &Notification) {
+        let severity = match notification {
+            Notification::LiquidationWarning { .. } => Severity::Critical,
+            Notification::SystemNotice { .. } => Severity::Warning,
+            Notification::Other { .. } => Severity::Info,
+        };
+        self.notify(Alert::new(
+            severity,
+            AlertKind::Notification {
+                notification: notification.clone(),
+            },
+            notification.message(),
+        ));
+    }
+
+    /// Subscribe `ws` to `user`'s `notification` channel and alert on each message
+    ///
+    /// Registers the handler for the subscription, replacing any existing one.
+    #[cfg(feature = "ws")]
+    pub async fn attach_notifications(
+        &self,
+        ws: &WebSocketClient,
+        user: &str,
+    ) -> Result<(), HyperliquidError> {
+        let subscription: Subscription =
+            serde_json::from_value(json!({"type": "notification", "user": user}))?;
+        let manager = self.clone();
+        ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            if !response.channel.starts_with("notification") {
+                return;
+            }
+            match serde_json::from_value::<NotificationMsg>(response.data) {
+                Ok(msg) => manager.handle_notification(&Notification::from(msg)),
+                Err(e) => warn!("Unparseable notification: {}", e),
+            }
+        })
+        .await;
+        ws.subscribe(subscription)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))
+    }
+
+    /// Track websocket connectivity
+    ///
+    /// Feed every event from [`WebSocketClient::next_event`](crate::stream::WebSocketClient::next_event);
+    /// outages are reported by [`check_connection`](Self::check_connection).
+    #[cfg(feature = "ws")]
+    pub fn handle_ws_event(&self, event: &WebSocketEvent) {
+        let mut state = self.lock();
+        match event {
+            WebSocketEvent::Disconnected | WebSocketEvent::Reconnecting(_) => {
+                state.disconnected_at.get_or_insert_with(Instant::now);
+            }
+            WebSocketEvent::Connected => {
+                let Some(since) = state.disconnected_at.take() else {
+                    return;
+                };
+                if std::mem::take(&mut state.disconnect_alerted) {
+                    let down_for_secs = since.elapsed().as_secs();
+                    drop(state);
+                    self.notify(Alert::new(
+                        Severity::Info,
+                        AlertKind::WsReconnected { down_for_secs },
+                        format!("Websocket reconnected after {}s", down_for_secs),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Alert if the websocket has been down longer than the threshold
+    pub fn check_connection(&self) {
+        let down_for = {
+            let mut state = self.lock();
+            match state.disconnected_at {
+                Some(since)
+                    if !state.disconnect_alerted
+                        && since.elapsed() >= self.disconnect_threshold =>
+                {
+                    state.disconnect_alerted = true;
+                    since.elapsed()
+                }
+                _ => return,
+            }
+        };
+        self.notify(Alert::new(
+            Severity::Critical,
+            AlertKind::WsDisconnected {
+                down_for_secs: down_for.as_secs(),
+            },
+            format!("Websocket down for {}s", down_for.as_secs()),
+        ));
+    }
+
+    /// Check each report from `updates`
+    pub fn watch_margin(&self, mut updates: broadcast::Receiver<MarginReport>) -> JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(report) => manager.check_margin(&report),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Alert on rejections from an order manager's `events`
+    pub fn watch_orders(&self, mut events: broadcast::Receiver<OrderEvent>) -> JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => manager.handle_order_event(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Run [`check_connection`](Self::check_connection) every `period`
+    pub fn watch_connection(&self, period: Duration) -> JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                manager.check_connection();
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for AlertManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AlertManager")
+            .field(
+                "hooks",
+                &self.hooks.iter().map(|h| h.name()).collect::<Vec<_>>(),
+            )
+            .field("min_severity", &self.min_severity)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
@@ -0,0 +1,358 @@
+//! Spot/perp basis monitor
+//!
+//! [`BasisMonitor`] joins spot mids from the `allMids` stream with perp marks
+//! from `activeAssetCtx` for configured [`BasisPair`]s and publishes a
+//! [`BasisUpdate`] whenever either side moves the premium by at least the
+//! pair's threshold. Spot markets are named as in `allMids`: `PURR/USDC` for
+//! the canonical pair, `@<index>` for the others.
+//!
+//! Basis is `perp_mark - spot_mid`; the premium is the same as a fraction of
+//! the spot mid. Updates are only emitted while both prices are within the
+//! pair's maximum skew of each other, so a stalled side doesn't produce a
+//! stale signal.
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::analytics::{BasisMonitor, BasisPair};
+//!
+//! let monitor = BasisMonitor::new()
+//!     .with_pair(BasisPair::new("HYPE", "@107").with_min_change_bps(5.0));
+//! let mut updates = monitor.updates();
+//! monitor.attach(&ws).await?;
+//! while let Ok(update) = updates.recv().await {
+//!     println!("{} premium {:.1} bps", update.pair, update.premium_bps());
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the basis update channel
+const UPDATE_CAPACITY: usize = 1024;
+
+/// Largest gap between the two prices' timestamps by default
+const DEFAULT_MAX_SKEW_MS: u64 = 5_000;
+
+/// A perp and the spot market it is compared against
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasisPair {
+    /// Name used in updates, `<perp>:<spot>` by default
+    pub name: String,
+    pub perp: String,
+    pub spot: String,
+    /// Minimum premium change, in basis points, before a new update
+    pub min_change_bps: f64,
+    /// Largest gap between the spot and perp timestamps to join them
+    pub max_skew_ms: u64,
+}
+
+impl BasisPair {
+    pub fn new(perp: impl Into<String>, spot: impl Into<String>) -> Self {
+        let perp = perp.into();
+        let spot = spot.into();
+        Self {
+            name: format!("{}:{}", perp, spot),
+            perp,
+            spot,
+            min_change_bps: 0.0,
+            max_skew_ms: DEFAULT_MAX_SKEW_MS,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_min_change_bps(mut self, min_change_bps: f64) -> Self {
+        self.min_change_bps = min_change_bps.max(0.0);
+        self
+    }
+
+    pub fn with_max_skew_ms(mut self, max_skew_ms: u64) -> Self {
+        self.max_skew_ms = max_skew_ms;
+        self
+    }
+}
+
+/// Basis of one pair at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BasisUpdate {
+    pub pair: String,
+    pub perp: String,
+    pub spot: String,
+    pub perp_mark: f64,
+    pub spot_mid: f64,
+    /// `perp_mark - spot_mid`
+    pub basis: f64,
+    /// `perp_mark / spot_mid - 1`
+    pub premium: f64,
+    /// Current hourly funding of the perp, when known
+    pub funding: Option<f64>,
+    /// Later of the two price timestamps, in ms
+    pub time: u64,
+}
+
+impl BasisUpdate {
+    pub fn premium_bps(&self) -> f64 {
+        self.premium * 10_000.0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    px: f64,
+    time: u64,
+}
+
+#[derive(Default)]
+struct State {
+    pairs: Vec<BasisPair>,
+    spot: HashMap<String, Quote>,
+    perp: HashMap<String, Quote>,
+    funding: HashMap<String, f64>,
+    /// Premium of the last update sent per pair
+    last_premium: HashMap<String, f64>,
+}
+
+impl State {
+    fn compute(&self, pair: &BasisPair) -> Option<BasisUpdate> {
+        let perp = self.perp.get(&pair.perp)?;
+        let spot = self.spot.get(&pair.spot)?;
+        if spot.px <= 0.0 || perp.time.abs_diff(spot.time) > pair.max_skew_ms {
+            return None;
+        }
+        Some(BasisUpdate {
+            pair: pair.name.clone(),
+            perp: pair.perp.clone(),
+            spot: pair.spot.clone(),
+            perp_mark: perp.px,
+            spot_mid: spot.px,
+            basis: perp.px - spot.px,
+            premium: perp.px / spot.px - 1.0,
+            funding: self.funding.get(&pair.perp).copied(),
+            time: perp.time.max(spot.time),
+        })
+    }
+
+    /// Updates for the pairs involving `coin` that moved past their threshold
+    fn changed(&mut self, is_perp: bool, coin: &str) -> Vec<BasisUpdate> {
+        let updates: Vec<BasisUpdate> = self
+            .pairs
+            .iter()
+            .filter(|pair| {
+                if is_perp {
+                    pair.perp == coin
+                } else {
+                    pair.spot == coin
+                }
+            })
+            .filter_map(|pair| {
+                let update = self.compute(pair)?;
+                let moved = match self.last_premium.get(&pair.name) {
+                    Some(last) => (update.premium - last).abs() * 10_000.0 >= pair.min_change_bps,
+                    None => true,
+                };
+                moved.then_some(update)
+            })
+            .collect();
+        for update in &updates {
+            self.last_premium
+                .insert(update.pair.clone(), update.premium);
+        }
+        updates
+    }
+}
+
+/// Joins spot mids and perp marks into basis updates
+#[derive(Clone)]
+pub struct BasisMonitor {
+    state: Arc<Mutex<State>>,
+    updates: broadcast::Sender<BasisUpdate>,
+}
+
+impl Default for BasisMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for BasisMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BasisMonitor")
+            .field("pairs", &self.lock().pairs)
+            .finish()
+    }
+}
+
+impl BasisMonitor {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            updates,
+        }
+    }
+
+    /// Monitor `pair`, replacing any pair with the same name
+    pub fn with_pair(self, pair: BasisPair) -> Self {
+        self.add_pair(pair);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start monitoring `pair`, replacing any pair with the same name
+    pub fn add_pair(&self, pair: BasisPair) {
+        let mut state = self.lock();
+        state.last_premium.remove(&pair.name);
+        state.pairs.retain(|p| p.name != pair.name);
+        state.pairs.push(pair);
+    }
+
+    /// Stop monitoring the pair named `name`
+    pub fn remove_pair(&self, name: &str) -> bool {
+        let mut state = self.lock();
+        state.last_premium.remove(name);
+        let before = state.pairs.len();
+        state.pairs.retain(|p| p.name != name);
+        state.pairs.len() != before
+    }
+
+    pub fn pairs(&self) -> Vec<BasisPair> {
+        self.lock().pairs.clone()
+    }
+
+    /// Subscribe to basis updates
+    pub fn updates(&self) -> broadcast::Receiver<BasisUpdate> {
+        self.updates.subscribe()
+    }
+
+    /// Current basis of the pair named `name`, ignoring its threshold
+    pub fn basis(&self, name: &str) -> Option<BasisUpdate> {
+        let state = self.lock();
+        let pair = state.pairs.iter().find(|p| p.name == name)?;
+        state.compute(pair)
+    }
+
+    /// Record a spot mid
+    pub fn update_spot_mid(&self, spot: &str, px: f64, time: u64) {
+        let updates = {
+            let mut state = self.lock();
+            state.spot.insert(spot.to_string(), Quote { px, time });
+            state.changed(false, spot)
+        };
+        self.publish(updates);
+    }
+
+    /// Record a perp mark
+    pub fn update_perp_mark(&self, perp: &str, px: f64, funding: Option<f64>, time: u64) {
+        let updates = {
+            let mut state = self.lock();
+            state.perp.insert(perp.to_string(), Quote { px, time });
+            if let Some(funding) = funding {
+                state.funding.insert(perp.to_string(), funding);
+            }
+            state.changed(true, perp)
+        };
+        self.publish(updates);
+    }
+
+    fn publish(&self, updates: Vec<BasisUpdate>) {
+        for update in updates {
+            let _ = self.updates.send(update);
+        }
+    }
+
+    /// Apply an `allMids` payload, recording the mids of monitored spots
+    ///
+    /// `allMids` carries no timestamp, so `time` is when it was received.
+    pub fn handle_all_mids(&self, data: &Value, time: u64) {
+        let Some(mids) = data.get("mids").and_then(Value::as_object) else {
+            warn!("Ignoring malformed allMids payload");
+            return;
+        };
+        let spots: Vec<String> = self.lock().pairs.iter().map(|p| p.spot.clone()).collect();
+        for spot in spots {
+            let px = mids
+                .get(&spot)
+                .and_then(Value::as_str)
+                .and_then(|px| px.parse::<f64>().ok());
+            if let Some(px) = px {
+                self.update_spot_mid(&spot, px, time);
+            }
+        }
+    }
+
+    /// Apply an `activeAssetCtx` payload for a perp
+    pub fn handle_active_asset_ctx(&self, data: &Value, time: u64) {
+        let coin = data.get("coin").and_then(Value::as_str);
+        let field = |name: &str| {
+            data.pointer(&format!("/ctx/{}", name))
+                .and_then(Value::as_str)
+                .and_then(|v| v.parse::<f64>().ok())
+        };
+        match (coin, field("markPx")) {
+            (Some(coin), Some(mark)) => self.update_perp_mark(coin, mark, field("funding"), time),
+            _ => warn!("Ignoring malformed activeAssetCtx payload"),
+        }
+    }
+
+    /// Subscribe `ws` to `allMids` and the asset contexts of every perp
+    ///
+    /// Registers the handlers for those subscriptions, replacing any existing
+    /// handlers for them. Pairs added afterwards need another `attach`.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        let monitor = self.clone();
+        ws.register_handler(Subscription::AllMids, move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            if response.channel == "allMids" {
+                monitor.handle_all_mids(&response.data, now_millis());
+            }
+        })
+        .await;
+        ws.subscribe(Subscription::AllMids)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+
+        let mut perps: Vec<String> = self.lock().pairs.iter().map(|p| p.perp.clone()).collect();
+        perps.sort();
+        perps.dedup();
+        for coin in perps {
+            let subscription = Subscription::ActiveAssetCtx { coin: coin.clone() };
+            let monitor = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                if response.channel == "activeAssetCtx"
+                    && response.data.get("coin").and_then(Value::as_str) == Some(&coin)
+                {
+                    monitor.handle_active_asset_ctx(&response.data, now_millis());
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
@@ -0,0 +1,268 @@
+//! Builder fee revenue
+//!
+//! Orders placed through a builder carry a `builderFee` on their fills,
+//! paid by the user to the builder address. [`BuilderRevenue`] collects the
+//! fills routed through one builder, from `userFillsByTime`, `userFills`
+//! stream payloads or recorded JSONL, and totals the fees by day and coin.
+//!
+//! Fills that name their builder (`builder` field, as in node fill dumps)
+//! count only when it is this builder; fills without one count whenever they
+//! carry a positive `builderFee`, so only feed fills of users trading through
+//! this builder.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::analytics::BuilderRevenue;
+//!
+//! let mut revenue = BuilderRevenue::new("0x1234567890123456789012345678901234567890")
+//!     .with_range(1_700_000_000_000..1_702_592_000_000);
+//! for user in ["0xaaaa...", "0xbbbb..."] {
+//!     revenue.fetch_user(&client, user).await?;
+//! }
+//! revenue.write_csv(std::io::stdout())?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, Write};
+use std::ops::Range;
+use std::pin::pin;
+
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::info::TimePaginator;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireFill {
+    coin: String,
+    px: String,
+    sz: String,
+    time: u64,
+    #[serde(default)]
+    builder_fee: Option<String>,
+    #[serde(default)]
+    builder: Option<String>,
+    #[serde(default)]
+    tid: Option<u64>,
+    #[serde(default)]
+    oid: Option<u64>,
+}
+
+/// Revenue from one coin on one UTC day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RevenueRow {
+    /// `YYYY-MM-DD`
+    pub day: String,
+    pub coin: String,
+    pub fills: u64,
+    /// Distinct users, where the fill source names them
+    pub users: usize,
+    pub notional: f64,
+    pub builder_fee: f64,
+}
+
+#[derive(Debug, Default)]
+struct Bucket {
+    fills: u64,
+    users: HashSet<String>,
+    notional: f64,
+    builder_fee: f64,
+}
+
+/// Builder fees aggregated by day and coin
+#[derive(Debug)]
+pub struct BuilderRevenue {
+    builder: String,
+    range: Range<u64>,
+    buckets: BTreeMap<(String, String), Bucket>,
+    seen: HashSet<(u64, u64)>,
+}
+
+impl BuilderRevenue {
+    /// Report for the builder at `builder`
+    pub fn new(builder: impl Into<String>) -> Self {
+        Self {
+            builder: builder.into().to_lowercase(),
+            range: 0..u64::MAX,
+            buckets: BTreeMap::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Only count fills with a time (ms) in `range` (default: all)
+    pub fn with_range(mut self, range: Range<u64>) -> Self {
+        self.range = range;
+        self
+    }
+
+    pub fn builder(&self) -> &str {
+        &self.builder
+    }
+
+    /// Add one fill of `user`; returns whether it was counted
+    ///
+    /// Fills outside the range, without a builder fee, naming another
+    /// builder, or already counted (same `tid` and `oid`) are skipped.
+    pub fn ingest_fill(&mut self, fill: &Value, user: Option<&str>) -> bool {
+        let fill = match WireFill::deserialize(fill) {
+            Ok(fill) => fill,
+            Err(e) => {
+                warn!("Ignoring malformed fill: {}", e);
+                return false;
+            }
+        };
+        if !self.range.contains(&fill.time) {
+            return false;
+        }
+        if let Some(builder) = &fill.builder {
+            if builder.to_lowercase() != self.builder {
+                return false;
+            }
+        }
+        let fee: f64 = match fill.builder_fee.as_deref().map(str::parse) {
+            Some(Ok(fee)) if fee > 0.0 => fee,
+            _ => return false,
+        };
+        if let (Some(tid), Some(oid)) = (fill.tid, fill.oid) {
+            if !self.seen.insert((tid, oid)) {
+                return false;
+            }
+        }
+        let px: f64 = fill.px.parse().unwrap_or(0.0);
+        let sz: f64 = fill.sz.parse().unwrap_or(0.0);
+        let day = Utc
+            .timestamp_millis_opt(fill.time as i64)
+            .single()
+            .map(|time| time.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let bucket = self.buckets.entry((day, fill.coin)).or_default();
+        bucket.fills += 1;
+        bucket.notional += px * sz;
+        bucket.builder_fee += fee;
+        if let Some(user) = user {
+            bucket.users.insert(user.to_lowercase());
+        }
+        true
+    }
+
+    /// Add the fills of a `userFills` stream payload; returns how many counted
+    pub fn ingest_user_fills(&mut self, data: &Value) -> usize {
+        let user = data.get("user").and_then(Value::as_str);
+        let Some(fills) = data.get("fills").and_then(Value::as_array) else {
+            warn!("Ignoring malformed userFills payload");
+            return 0;
+        };
+        fills
+            .iter()
+            .filter(|fill| self.ingest_fill(fill, user))
+            .count()
+    }
+
+    /// Add fills from JSON lines; returns how many counted
+    ///
+    /// Each line is a recorded message (`{"channel": "userFills", "data":
+    /// ...}`, as written by the data recorder), a `[user, fill]` pair, or a
+    /// bare fill. Other lines are skipped.
+    pub fn ingest_jsonl(&mut self, reader: impl BufRead) -> Result<usize, HyperliquidError> {
+        let mut counted = 0;
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value: Value = serde_json::from_str(&line)?;
+            counted += match &value {
+                Value::Object(object) if object.contains_key("channel") => {
+                    if object.get("channel").and_then(Value::as_str) == Some("userFills") {
+                        self.ingest_user_fills(&value["data"])
+                    } else {
+                        0
+                    }
+                }
+                Value::Array(pair) if pair.len() == 2 => {
+                    usize::from(self.ingest_fill(&pair[1], pair[0].as_str()))
+                }
+                Value::Object(_) => usize::from(self.ingest_fill(&value, None)),
+                _ => 0,
+            };
+        }
+        Ok(counted)
+    }
+
+    /// Fetch `user`'s fills in the range and add them; returns how many counted
+    ///
+    /// Pages through `userFillsByTime`, which returns at most
+    /// [`USER_FILLS_PAGE_LIMIT`](crate::info::USER_FILLS_PAGE_LIMIT) fills per request.
+    pub async fn fetch_user(
+        &mut self,
+        client: &HttpClient,
+        user: &str,
+    ) -> Result<usize, HyperliquidError> {
+        let range = self.range.clone();
+        let mut fills = pin!(TimePaginator::user_fills(client.clone(), user, range).stream());
+        let mut counted = 0;
+        while let Some(fill) = fills.next().await {
+            counted += usize::from(self.ingest_fill(&fill?, Some(user)));
+        }
+        Ok(counted)
+    }
+
+    /// Revenue by day, then coin
+    pub fn rows(&self) -> Vec<RevenueRow> {
+        self.buckets
+            .iter()
+            .map(|((day, coin), bucket)| RevenueRow {
+                day: day.clone(),
+                coin: coin.clone(),
+                fills: bucket.fills,
+                users: bucket.users.len(),
+                notional: bucket.notional,
+                builder_fee: bucket.builder_fee,
+            })
+            .collect()
+    }
+
+    /// Fees by coin over the whole range
+    pub fn by_coin(&self) -> BTreeMap<String, f64> {
+        let mut totals = BTreeMap::new();
+        for ((_, coin), bucket) in &self.buckets {
+            *totals.entry(coin.clone()).or_insert(0.0) += bucket.builder_fee;
+        }
+        totals
+    }
+
+    /// Fees by day over all coins
+    pub fn by_day(&self) -> BTreeMap<String, f64> {
+        let mut totals = BTreeMap::new();
+        for ((day, _), bucket) in &self.buckets {
+            *totals.entry(day.clone()).or_insert(0.0) += bucket.builder_fee;
+        }
+        totals
+    }
+
+    pub fn total_fee(&self) -> f64 {
+        self.buckets.values().map(|bucket| bucket.builder_fee).sum()
+    }
+
+    /// Write [`rows`](Self::rows) as CSV
+    ///
+    /// Columns: `day,coin,fills,users,notional,builder_fee`.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), HyperliquidError> {
+        writeln!(writer, "day,coin,fills,users,notional,builder_fee")?;
+        for row in self.rows() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{}",
+                row.day, row.coin, row.fills, row.users, row.notional, row.builder_fee
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+//! Rolling order book depth statistics
+//!
+//! [`DepthMonitor`] samples [`LocalBook`]s and keeps, per coin, the samples
+//! of the last window (60s by default). Its [`DepthSnapshot`]s report:
+//!
+//! - the average size at each of the top N levels per side, and their sum;
+//! - the replenishment rate per side: size added per second at prices
+//!   within the previous sample's top levels, whether by new orders or by
+//!   a level refilling after it was traded through;
+//! - with an [`OrderManager`], an estimate of the size queued ahead of each
+//!   resting order.
+//!
+//! Queue estimates assume the order joined the back of its level and that
+//! every later decrease of the level was ahead of it, while increases queue
+//! behind. This is optimistic when orders behind cancel, but never counts
+//! size ahead that can't be.
+//!
+//! Books are sampled with [`DepthMonitor::sample`], for every tracked book
+//! that changed, or one at a time with [`DepthMonitor::handle_book`].
+//! [`DepthMonitor::start`] samples on an interval and publishes a snapshot
+//! of every tracked coin on each tick.
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient, oms: hyperliquid_core::oms::OrderManager) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::time::Duration;
+//! use hyperliquid_core::analytics::DepthMonitor;
+//! use hyperliquid_core::stream::LocalBook;
+//!
+//! let book = LocalBook::new("BTC");
+//! book.attach(&ws).await?;
+//! let monitor = DepthMonitor::new().with_levels(10).with_orders(oms);
+//! monitor.track(book);
+//! let mut snapshots = monitor.snapshots();
+//! let _job = monitor.start(Duration::from_secs(1));
+//! while let Ok(snapshot) = snapshots.recv().await {
+//!     println!(
+//!         "{}: {:.2} bid depth, {:.3}/s bid replenishment",
+//!         snapshot.coin, snapshot.bid_depth, snapshot.bid_replenish_rate
+//!     );
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::clock::{self, Clock};
+use crate::oms::{OrderManager, OrderState};
+use crate::stream::{BookLevel, LocalBook};
+
+/// Capacity of the snapshot channel
+const SNAPSHOT_CAPACITY: usize = 1024;
+
+/// Levels per side averaged by default
+const DEFAULT_LEVELS: usize = 5;
+
+/// Default span of samples kept per coin
+const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Estimated place of a resting order in its level
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueuePosition {
+    pub oid: u64,
+    pub is_buy: bool,
+    pub px: f64,
+    /// Size of the order still open
+    pub remaining_sz: f64,
+    /// Size estimated to be queued ahead of the order
+    pub ahead_sz: f64,
+    /// Size of the order's level in the last sample, if it was in the book
+    pub level_sz: Option<f64>,
+}
+
+/// Depth statistics of one coin over the window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DepthSnapshot {
+    pub coin: String,
+    /// When the snapshot was taken (ms)
+    pub time: u64,
+    /// Samples in the window
+    pub samples: usize,
+    /// Average size at each of the top levels, best first
+    pub avg_bid_sizes: Vec<f64>,
+    pub avg_ask_sizes: Vec<f64>,
+    /// Sum of the average sizes
+    pub bid_depth: f64,
+    pub ask_depth: f64,
+    /// Size added per second
+    pub bid_replenish_rate: f64,
+    pub ask_replenish_rate: f64,
+    /// Resting orders of the coin, if orders are tracked
+    pub queue: Vec<QueuePosition>,
+}
+
+#[derive(Debug)]
+struct Sample {
+    time: u64,
+    bids: Vec<BookLevel>,
+    asks: Vec<BookLevel>,
+    /// Time since the previous sample (ms), 0 for the first
+    interval_ms: u64,
+    bid_added: f64,
+    ask_added: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Queued {
+    is_buy: bool,
+    px: f64,
+    remaining_sz: f64,
+    ahead_sz: f64,
+    level_sz: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct CoinState {
+    samples: VecDeque<Sample>,
+    /// Exchange time of the last sampled book
+    book_time: Option<u64>,
+    queue: BTreeMap<u64, Queued>,
+}
+
+#[derive(Default)]
+struct State {
+    books: BTreeMap<String, LocalBook>,
+    coins: HashMap<String, CoinState>,
+}
+
+/// Size added at prices within `prev`'s range, going from `prev` to `cur`
+fn added(prev: &[BookLevel], cur: &[BookLevel], bids: bool) -> f64 {
+    let Some(deepest) = prev.last().map(|level| level.px) else {
+        return 0.0;
+    };
+    cur.iter()
+        .filter(|level| {
+            if bids {
+                level.px >= deepest
+            } else {
+                level.px <= deepest
+            }
+        })
+        .map(|level| {
+            let before = prev
+                .iter()
+                .find(|prev| prev.px == level.px)
+                .map_or(0.0, |prev| prev.sz);
+            (level.sz - before).max(0.0)
+        })
+        .sum()
+}
+
+fn average(
+    samples: &VecDeque<Sample>,
+    levels: usize,
+    side: fn(&Sample) -> &[BookLevel],
+) -> Vec<f64> {
+    let mut sums = vec![0.0; levels];
+    for sample in samples {
+        for (sum, level) in sums.iter_mut().zip(side(sample)) {
+            *sum += level.sz;
+        }
+    }
+    let count = samples.len().max(1) as f64;
+    sums.into_iter().map(|sum| sum / count).collect()
+}
+
+/// Rolling depth statistics of tracked books
+///
+/// Cheap to clone; clones share state.
+#[derive(Clone)]
+pub struct DepthMonitor {
+    state: Arc<Mutex<State>>,
+    levels: usize,
+    window: Duration,
+    orders: Option<OrderManager>,
+    clock: Arc<dyn Clock>,
+    snapshots: broadcast::Sender<DepthSnapshot>,
+}
+
+impl Default for DepthMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for DepthMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DepthMonitor")
+            .field("coins", &self.lock().books.keys().collect::<Vec<_>>())
+            .field("levels", &self.levels)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DepthMonitor {
+    pub fn new() -> Self {
+        let (snapshots, _) = broadcast::channel(SNAPSHOT_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            levels: DEFAULT_LEVELS,
+            window: DEFAULT_WINDOW,
+            orders: None,
+            clock: clock::system(),
+            snapshots,
+        }
+    }
+
+    /// Levels per side to average (default 5)
+    pub fn with_levels(mut self, levels: usize) -> Self {
+        self.levels = levels.max(1);
+        self
+    }
+
+    /// Span of samples the statistics cover (default 60s)
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Estimate queue positions of the resting orders in `orders`
+    pub fn with_orders(mut self, orders: OrderManager) -> Self {
+        self.orders = Some(orders);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Sample `book` on every [`sample`](Self::sample)
+    pub fn track(&self, book: LocalBook) {
+        self.lock().books.insert(book.coin().to_string(), book);
+    }
+
+    /// Stop sampling `coin` and drop its statistics
+    pub fn untrack(&self, coin: &str) {
+        let mut state = self.lock();
+        state.books.remove(coin);
+        state.coins.remove(coin);
+    }
+
+    pub fn snapshots(&self) -> broadcast::Receiver<DepthSnapshot> {
+        self.snapshots.subscribe()
+    }
+
+    /// Sample every tracked book that changed since its last sample
+    ///
+    /// Returns the number of books sampled.
+    pub fn sample(&self) -> usize {
+        let books: Vec<LocalBook> = self.lock().books.values().cloned().collect();
+        books.iter().filter(|book| self.handle_book(book)).count()
+    }
+
+    /// Sample `book` unless it is empty or unchanged since its last sample
+    pub fn handle_book(&self, book: &LocalBook) -> bool {
+        let book_time = book.time();
+        if book_time == 0 {
+            return false;
+        }
+        let now = self.clock.now_ms();
+        let (bids, asks) = (book.bids(), book.asks());
+        let open: Vec<_> = self
+            .orders
+            .iter()
+            .flat_map(OrderManager::open_orders)
+            .filter(|order| {
+                order.coin == book.coin()
+                    && matches!(order.state, OrderState::Acked | OrderState::PartiallyFilled)
+            })
+            .collect();
+
+        let mut state = self.lock();
+        let coin = state.coins.entry(book.coin().to_string()).or_default();
+        if coin.book_time == Some(book_time) {
+            return false;
+        }
+        coin.book_time = Some(book_time);
+
+        // Queue positions, against the full book
+        let mut queue = BTreeMap::new();
+        for order in open {
+            let Some(oid) = order.oid else {
+                continue;
+            };
+            let levels = if order.is_buy { &bids } else { &asks };
+            let level_sz = levels
+                .iter()
+                .find(|level| level.px == order.limit_px)
+                .map(|level| level.sz);
+            let remaining_sz = order.remaining_sz();
+            let behind_us = level_sz.map(|sz| (sz - remaining_sz).max(0.0));
+            let ahead_sz = match (coin.queue.get(&oid), behind_us) {
+                (Some(prev), Some(ahead)) => prev.ahead_sz.min(ahead),
+                (Some(prev), None) => prev.ahead_sz,
+                (None, Some(ahead)) => ahead,
+                // Not in the visible book yet
+                (None, None) => 0.0,
+            };
+            queue.insert(
+                oid,
+                Queued {
+                    is_buy: order.is_buy,
+                    px: order.limit_px,
+                    remaining_sz,
+                    ahead_sz,
+                    level_sz,
+                },
+            );
+        }
+        coin.queue = queue;
+
+        let bids: Vec<_> = bids.into_iter().take(self.levels).collect();
+        let asks: Vec<_> = asks.into_iter().take(self.levels).collect();
+        let sample = match coin.samples.back() {
+            Some(prev) => Sample {
+                time: now,
+                interval_ms: now.saturating_sub(prev.time),
+                bid_added: added(&prev.bids, &bids, true),
+                ask_added: added(&prev.asks, &asks, false),
+                bids,
+                asks,
+            },
+            None => Sample {
+                time: now,
+                interval_ms: 0,
+                bid_added: 0.0,
+                ask_added: 0.0,
+                bids,
+                asks,
+            },
+        };
+        coin.samples.push_back(sample);
+        let cutoff = now.saturating_sub(self.window.as_millis() as u64);
+        while coin
+            .samples
+            .front()
+            .is_some_and(|sample| sample.time < cutoff)
+        {
+            coin.samples.pop_front();
+        }
+        true
+    }
+
+    /// Statistics of `coin` over the window, once it has been sampled
+    pub fn snapshot(&self, coin: &str) -> Option<DepthSnapshot> {
+        let state = self.lock();
+        let coin_state = state.coins.get(coin)?;
+        let samples = &coin_state.samples;
+        if samples.is_empty() {
+            return None;
+        }
+        let avg_bid_sizes = average(samples, self.levels, |sample| &sample.bids);
+        let avg_ask_sizes = average(samples, self.levels, |sample| &sample.asks);
+        // The first sample's interval may reach before the window
+        let span_ms: u64 = samples
+            .iter()
+            .skip(1)
+            .map(|sample| sample.interval_ms)
+            .sum();
+        let rate = |added: fn(&Sample) -> f64| {
+            if span_ms == 0 {
+                return 0.0;
+            }
+            let total: f64 = samples.iter().skip(1).map(added).sum();
+            total / (span_ms as f64 / 1000.0)
+        };
+        Some(DepthSnapshot {
+            coin: coin.to_string(),
+            time: self.clock.now_ms(),
+            samples: samples.len(),
+            bid_depth: avg_bid_sizes.iter().sum(),
+            ask_depth: avg_ask_sizes.iter().sum(),
+            avg_bid_sizes,
+            avg_ask_sizes,
+            bid_replenish_rate: rate(|sample| sample.bid_added),
+            ask_replenish_rate: rate(|sample| sample.ask_added),
+            queue: coin_state
+                .queue
+                .iter()
+                .map(|(oid, queued)| QueuePosition {
+                    oid: *oid,
+                    is_buy: queued.is_buy,
+                    px: queued.px,
+                    remaining_sz: queued.remaining_sz,
+                    ahead_sz: queued.ahead_sz,
+                    level_sz: queued.level_sz,
+                })
+                .collect(),
+        })
+    }
+
+    /// Sample and publish a snapshot of every tracked coin
+    ///
+    /// Returns the snapshots published.
+    pub fn publish(&self) -> Vec<DepthSnapshot> {
+        self.sample();
+        let coins: Vec<String> = self.lock().books.keys().cloned().collect();
+        let snapshots: Vec<_> = coins
+            .iter()
+            .filter_map(|coin| self.snapshot(coin))
+            .collect();
+        for snapshot in &snapshots {
+            // No receivers is fine
+            let _ = self.snapshots.send(snapshot.clone());
+        }
+        snapshots
+    }
+
+    /// [`publish`](Self::publish) every `period` in the background until the
+    /// handle is aborted
+    pub fn start(&self, period: Duration) -> JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                monitor.publish();
+            }
+        })
+    }
+}
@@ -0,0 +1,405 @@
+//! Funding-rate carry analytics
+//!
+//! [`FundingAnalytics`] joins a coin's `fundingHistory`, the venue rates from
+//! `predictedFundings` and the mark/oracle prices from `metaAndAssetCtxs` into
+//! [`CarryMetrics`]: the annualized carry of holding the perp, how the rate has
+//! behaved over the loaded history, and how it compares with the same perp on
+//! other venues.
+//!
+//! Rates are per funding interval (hourly on Hyperliquid) and are paid by
+//! longs to shorts when positive. Annualized figures scale a rate by the
+//! number of intervals in a year without compounding.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::time::Duration;
+//! use hyperliquid_core::analytics::FundingAnalytics;
+//!
+//! let mut analytics = FundingAnalytics::new();
+//! analytics
+//!     .refresh(&client, &["BTC", "ETH"], Duration::from_secs(7 * 24 * 3600))
+//!     .await?;
+//! for metrics in analytics.all_metrics() {
+//!     println!("{}: {:?} annualized", metrics.coin, metrics.current_annualized);
+//! }
+//! analytics.write_csv("BTC", std::io::stdout())?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::client::HttpClient;
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::info::TimePaginator;
+use crate::types::AssetContext;
+
+/// Funding intervals per year at Hyperliquid's hourly funding
+pub const HOURS_PER_YEAR: f64 = 24.0 * 365.0;
+
+/// Venue name of Hyperliquid's own perp in `predictedFundings`
+pub const HL_VENUE: &str = "HlPerp";
+
+/// Interval assumed for other venues when the response doesn't say
+const DEFAULT_VENUE_INTERVAL_HOURS: f64 = 8.0;
+
+/// One settled funding interval
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct FundingSample {
+    /// Settlement time in milliseconds
+    pub time: u64,
+    pub rate: f64,
+    pub premium: f64,
+}
+
+/// Predicted funding on one venue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VenueFunding {
+    pub venue: String,
+    /// Rate for the venue's next interval
+    pub rate: f64,
+    pub interval_hours: f64,
+    pub next_funding_time: Option<u64>,
+}
+
+impl VenueFunding {
+    pub fn annualized(&self) -> f64 {
+        self.rate * HOURS_PER_YEAR / self.interval_hours
+    }
+}
+
+/// Point of an exported carry time series
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct CarrySample {
+    pub time: u64,
+    pub rate: f64,
+    pub annualized: f64,
+    /// Sum of rates up to and including this interval
+    pub cumulative: f64,
+}
+
+/// Carry summary for one coin
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CarryMetrics {
+    pub coin: String,
+    /// Current hourly rate from the asset context
+    pub current_rate: Option<f64>,
+    pub current_annualized: Option<f64>,
+    /// Hyperliquid's predicted rate for the next interval, annualized
+    pub predicted_annualized: Option<f64>,
+    /// Mean of the loaded history, annualized
+    pub mean_annualized: Option<f64>,
+    /// Standard deviation of the loaded history, annualized
+    pub volatility_annualized: Option<f64>,
+    /// Sum of rates over the loaded history, as a fraction of notional
+    pub cumulative: f64,
+    /// Share of loaded intervals with a positive rate
+    pub positive_share: Option<f64>,
+    pub samples: usize,
+    pub mark_px: Option<f64>,
+    pub oracle_px: Option<f64>,
+    /// Mark premium over oracle, `mark / oracle - 1`
+    pub basis: Option<f64>,
+    /// Predicted funding on other venues
+    pub venues: Vec<VenueFunding>,
+}
+
+impl CarryMetrics {
+    /// Annualized funding on `venue` minus Hyperliquid's
+    ///
+    /// Positive when long here and short on `venue` nets funding. Uses the
+    /// predicted Hyperliquid rate, falling back to the current one.
+    pub fn venue_spread(&self, venue: &str) -> Option<f64> {
+        let hl = self.predicted_annualized.or(self.current_annualized)?;
+        let other = self.venues.iter().find(|v| v.venue == venue)?;
+        Some(other.annualized() - hl)
+    }
+
+    /// Venue with the widest spread against Hyperliquid, by magnitude
+    pub fn best_venue_spread(&self) -> Option<(&str, f64)> {
+        self.venues
+            .iter()
+            .filter_map(|v| Some((v.venue.as_str(), self.venue_spread(&v.venue)?)))
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsFundingHistory {
+    coin: String,
+    funding_rate: String,
+    #[serde(default)]
+    premium: Option<String>,
+    time: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WirePredicted {
+    funding_rate: String,
+    #[serde(default)]
+    next_funding_time: Option<u64>,
+    #[serde(default)]
+    funding_interval_hours: Option<f64>,
+}
+
+#[derive(Debug, Default)]
+struct CoinData {
+    history: BTreeMap<u64, FundingSample>,
+    current_rate: Option<f64>,
+    mark_px: Option<f64>,
+    oracle_px: Option<f64>,
+    venues: Vec<VenueFunding>,
+}
+
+fn parse(value: &Option<String>) -> Option<f64> {
+    value.as_deref().and_then(|s| s.parse().ok())
+}
+
+/// Funding history, predictions and prices per coin
+#[derive(Debug)]
+pub struct FundingAnalytics {
+    coins: HashMap<String, CoinData>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for FundingAnalytics {
+    fn default() -> Self {
+        Self {
+            coins: HashMap::new(),
+            clock: clock::system(),
+        }
+    }
+}
+
+impl FundingAnalytics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clock that [`refresh`](Self::refresh) measures its lookback from
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Add a `fundingHistory` response, returning how many intervals were new
+    pub fn ingest_history(&mut self, data: &Value) -> Result<usize, HyperliquidError> {
+        let entries: Vec<WsFundingHistory> = serde_json::from_value(data.clone())?;
+        let mut added = 0;
+        for entry in entries {
+            let Ok(rate) = entry.funding_rate.parse() else {
+                continue;
+            };
+            let sample = FundingSample {
+                time: entry.time,
+                rate,
+                premium: parse(&entry.premium).unwrap_or(0.0),
+            };
+            let history = &mut self.coins.entry(entry.coin).or_default().history;
+            if history.insert(entry.time, sample).is_none() {
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Replace venue predictions from a `predictedFundings` response
+    pub fn ingest_predicted(&mut self, data: &Value) -> Result<(), HyperliquidError> {
+        let entries: Vec<(String, Vec<(String, Option<WirePredicted>)>)> =
+            serde_json::from_value(data.clone())?;
+        for (coin, venues) in entries {
+            let venues = venues
+                .into_iter()
+                .filter_map(|(venue, predicted)| {
+                    let predicted = predicted?;
+                    let default_interval = if venue == HL_VENUE {
+                        1.0
+                    } else {
+                        DEFAULT_VENUE_INTERVAL_HOURS
+                    };
+                    Some(VenueFunding {
+                        rate: predicted.funding_rate.parse().ok()?,
+                        interval_hours: predicted
+                            .funding_interval_hours
+                            .filter(|hours| *hours > 0.0)
+                            .unwrap_or(default_interval),
+                        next_funding_time: predicted.next_funding_time,
+                        venue,
+                    })
+                })
+                .collect();
+            self.coins.entry(coin).or_default().venues = venues;
+        }
+        Ok(())
+    }
+
+    /// Update current rates and prices from a `metaAndAssetCtxs` response
+    pub fn ingest_asset_ctxs(&mut self, data: &Value) -> Result<(), HyperliquidError> {
+        let (meta, ctxs): (Value, Vec<AssetContext>) = serde_json::from_value(data.clone())?;
+        let names = meta
+            .get("universe")
+            .and_then(Value::as_array)
+            .ok_or_else(|| {
+                HyperliquidError::Validation("metaAndAssetCtxs has no universe".to_string())
+            })?;
+        for (asset, ctx) in names.iter().zip(ctxs) {
+            let Some(name) = asset.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let coin = self.coins.entry(name.to_string()).or_default();
+            coin.current_rate = parse(&ctx.funding);
+            coin.mark_px = parse(&ctx.markPx);
+            coin.oracle_px = parse(&ctx.oraclePx);
+        }
+        Ok(())
+    }
+
+    /// Load `coin`'s funding history from `start_time` (ms), paging as needed
+    pub async fn fetch_history(
+        &mut self,
+        client: &HttpClient,
+        coin: &str,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<usize, HyperliquidError> {
+        let range = start_time..end_time.map_or(u64::MAX, |end| end.saturating_add(1));
+        let entries: Vec<Value> = TimePaginator::funding_history(client.clone(), coin, range)
+            .stream()
+            .try_collect()
+            .await?;
+        self.ingest_history(&Value::Array(entries))
+    }
+
+    pub async fn fetch_predicted(&mut self, client: &HttpClient) -> Result<(), HyperliquidError> {
+        let data: Value = client
+            .post("/info", &json!({"type": "predictedFundings"}))
+            .await?;
+        self.ingest_predicted(&data)
+    }
+
+    pub async fn fetch_asset_ctxs(&mut self, client: &HttpClient) -> Result<(), HyperliquidError> {
+        let data: Value = client
+            .post("/info", &json!({"type": "metaAndAssetCtxs"}))
+            .await?;
+        self.ingest_asset_ctxs(&data)
+    }
+
+    /// Fetch prices, predictions and `lookback` of history for `coins`
+    pub async fn refresh(
+        &mut self,
+        client: &HttpClient,
+        coins: &[&str],
+        lookback: Duration,
+    ) -> Result<(), HyperliquidError> {
+        self.fetch_asset_ctxs(client).await?;
+        self.fetch_predicted(client).await?;
+        let now = self.clock.now_ms();
+        let start = now.saturating_sub(lookback.as_millis() as u64);
+        for coin in coins {
+            let from = self
+                .coins
+                .get(*coin)
+                .and_then(|data| data.history.keys().next_back())
+                .map_or(start, |last| (*last + 1).max(start));
+            self.fetch_history(client, coin, from, None).await?;
+        }
+        Ok(())
+    }
+
+    /// Loaded funding history for `coin`, oldest first
+    pub fn history(&self, coin: &str) -> Vec<FundingSample> {
+        self.coins
+            .get(coin)
+            .map(|data| data.history.values().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Carry summary for `coin`
+    pub fn metrics(&self, coin: &str) -> Option<CarryMetrics> {
+        let data = self.coins.get(coin)?;
+        let rates: Vec<f64> = data.history.values().map(|sample| sample.rate).collect();
+        let samples = rates.len();
+        let (mean, volatility, positive_share) = if samples == 0 {
+            (None, None, None)
+        } else {
+            let n = samples as f64;
+            let mean = rates.iter().sum::<f64>() / n;
+            let variance = rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+            let positive = rates.iter().filter(|r| **r > 0.0).count() as f64;
+            (Some(mean), Some(variance.sqrt()), Some(positive / n))
+        };
+
+        let (hl, venues): (Vec<VenueFunding>, Vec<VenueFunding>) = data
+            .venues
+            .iter()
+            .cloned()
+            .partition(|v| v.venue == HL_VENUE);
+        Some(CarryMetrics {
+            coin: coin.to_string(),
+            current_rate: data.current_rate,
+            current_annualized: data.current_rate.map(|rate| rate * HOURS_PER_YEAR),
+            predicted_annualized: hl.first().map(VenueFunding::annualized),
+            mean_annualized: mean.map(|rate| rate * HOURS_PER_YEAR),
+            volatility_annualized: volatility.map(|rate| rate * HOURS_PER_YEAR),
+            cumulative: rates.iter().sum(),
+            positive_share,
+            samples,
+            mark_px: data.mark_px,
+            oracle_px: data.oracle_px,
+            basis: match (data.mark_px, data.oracle_px) {
+                (Some(mark), Some(oracle)) if oracle > 0.0 => Some(mark / oracle - 1.0),
+                _ => None,
+            },
+            venues,
+        })
+    }
+
+    /// Carry summary for every known coin, sorted by coin
+    pub fn all_metrics(&self) -> Vec<CarryMetrics> {
+        let mut coins: Vec<&String> = self.coins.keys().collect();
+        coins.sort();
+        coins
+            .into_iter()
+            .filter_map(|coin| self.metrics(coin))
+            .collect()
+    }
+
+    /// Carry time series for `coin`, oldest first
+    pub fn series(&self, coin: &str) -> Vec<CarrySample> {
+        let mut cumulative = 0.0;
+        self.history(coin)
+            .into_iter()
+            .map(|sample| {
+                cumulative += sample.rate;
+                CarrySample {
+                    time: sample.time,
+                    rate: sample.rate,
+                    annualized: sample.rate * HOURS_PER_YEAR,
+                    cumulative,
+                }
+            })
+            .collect()
+    }
+
+    /// Write `coin`'s carry time series as CSV
+    pub fn write_csv(&self, coin: &str, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(writer, "time,rate,annualized,cumulative")?;
+        for sample in self.series(coin) {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                sample.time, sample.rate, sample.annualized, sample.cumulative
+            )?;
+        }
+        Ok(())
+    }
+}
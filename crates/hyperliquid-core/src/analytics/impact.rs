@@ -0,0 +1,183 @@
+//! Price impact of market orders from the L2 book
+//!
+//! [`estimate_fill_price`] walks the side of the book an order would take
+//! (asks for a buy, bids for a sell) and reports the average and worst
+//! price of filling a size, and its impact against the mid.
+//! [`max_size_within_slippage`] answers the inverse question: the largest
+//! size whose average fill price stays within a slippage budget.
+//!
+//! Both work on a REST [`L2BookSnapshot`] or a streamed [`LocalBook`]. The
+//! book only shows the top levels, so sizes that go past its last level are
+//! reported as partially fillable rather than extrapolated.
+
+use crate::stream::{BookLevel, LocalBook};
+use crate::types::{L2BookSnapshot, OrderLevel};
+
+/// A book the estimators can read
+pub trait BookDepth {
+    /// Bids, best first
+    fn bid_levels(&self) -> Vec<BookLevel>;
+    /// Asks, best first
+    fn ask_levels(&self) -> Vec<BookLevel>;
+}
+
+impl BookDepth for LocalBook {
+    fn bid_levels(&self) -> Vec<BookLevel> {
+        self.bids()
+    }
+
+    fn ask_levels(&self) -> Vec<BookLevel> {
+        self.asks()
+    }
+}
+
+fn parse_levels(levels: &[OrderLevel]) -> Vec<BookLevel> {
+    levels
+        .iter()
+        .filter_map(|level| {
+            Some(BookLevel {
+                px: level.px.parse().ok()?,
+                sz: level.sz.parse().ok()?,
+                n: level.n.max(0) as u64,
+            })
+        })
+        .collect()
+}
+
+impl BookDepth for L2BookSnapshot {
+    fn bid_levels(&self) -> Vec<BookLevel> {
+        parse_levels(&self.levels[0])
+    }
+
+    fn ask_levels(&self) -> Vec<BookLevel> {
+        parse_levels(&self.levels[1])
+    }
+}
+
+/// Expected outcome of taking `requested_sz` from the book
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEstimate {
+    pub is_buy: bool,
+    pub requested_sz: f64,
+    /// Size the visible book can fill; less than requested if it is too thin
+    pub filled_sz: f64,
+    /// Size-weighted average price of the filled size
+    pub avg_px: f64,
+    /// Price of the last level taken, the limit needed to fill it all
+    pub worst_px: f64,
+    /// Mid before the order, or the touch if the other side is empty
+    pub reference_px: f64,
+    /// Levels taken, including a partly taken last one
+    pub levels: usize,
+}
+
+impl FillEstimate {
+    /// Whether the visible book covers the whole size
+    pub fn is_complete(&self) -> bool {
+        self.filled_sz >= self.requested_sz
+    }
+
+    /// Adverse move of the average price from the reference, in basis points
+    pub fn impact_bps(&self) -> f64 {
+        let side = if self.is_buy { 1.0 } else { -1.0 };
+        side * (self.avg_px - self.reference_px) / self.reference_px * 10_000.0
+    }
+
+    /// Adverse move of the worst price from the reference, in basis points
+    pub fn worst_bps(&self) -> f64 {
+        let side = if self.is_buy { 1.0 } else { -1.0 };
+        side * (self.worst_px - self.reference_px) / self.reference_px * 10_000.0
+    }
+}
+
+/// Levels an order takes, and the price it is measured against
+fn taken_side(book: &impl BookDepth, is_buy: bool) -> Option<(Vec<BookLevel>, f64)> {
+    let bids = book.bid_levels();
+    let asks = book.ask_levels();
+    let reference = match (bids.first(), asks.first()) {
+        (Some(bid), Some(ask)) => (bid.px + ask.px) / 2.0,
+        (_, Some(ask)) if is_buy => ask.px,
+        (Some(bid), _) if !is_buy => bid.px,
+        _ => return None,
+    };
+    let levels = if is_buy { asks } else { bids };
+    let levels: Vec<BookLevel> = levels
+        .into_iter()
+        .filter(|level| level.px > 0.0 && level.sz > 0.0)
+        .collect();
+    (!levels.is_empty()).then_some((levels, reference))
+}
+
+/// Estimate the fill of a market order of `size`
+///
+/// Returns `None` when the side the order takes is empty.
+pub fn estimate_fill_price(book: &impl BookDepth, is_buy: bool, size: f64) -> Option<FillEstimate> {
+    let (levels, reference_px) = taken_side(book, is_buy)?;
+    let size = size.max(0.0);
+    let mut filled_sz = 0.0;
+    let mut notional = 0.0;
+    let mut worst_px = levels[0].px;
+    let mut taken = 0;
+    for level in &levels {
+        if filled_sz >= size {
+            break;
+        }
+        let sz = level.sz.min(size - filled_sz);
+        filled_sz += sz;
+        notional += sz * level.px;
+        worst_px = level.px;
+        taken += 1;
+    }
+    Some(FillEstimate {
+        is_buy,
+        requested_sz: size,
+        filled_sz,
+        avg_px: if filled_sz > 0.0 {
+            notional / filled_sz
+        } else {
+            levels[0].px
+        },
+        worst_px,
+        reference_px,
+        levels: taken,
+    })
+}
+
+/// Largest size whose average fill price is within `bps` of the mid
+///
+/// Returns 0 when the side the order takes is empty or already starts
+/// beyond the budget, and the whole visible side when it all fits.
+pub fn max_size_within_slippage(book: &impl BookDepth, is_buy: bool, bps: f64) -> f64 {
+    let Some((levels, reference_px)) = taken_side(book, is_buy) else {
+        return 0.0;
+    };
+    let slippage = bps.max(0.0) / 10_000.0;
+    let limit = if is_buy {
+        reference_px * (1.0 + slippage)
+    } else {
+        reference_px * (1.0 - slippage)
+    };
+    // Distance of a price beyond the limit; positive is worse
+    let beyond = |px: f64| if is_buy { px - limit } else { limit - px };
+
+    let mut size = 0.0;
+    // Sum of sz * (px - limit) signed as above; the average is within the
+    // limit as long as this stays at or below zero
+    let mut excess = 0.0;
+    for level in &levels {
+        let over = beyond(level.px);
+        if over <= 0.0 {
+            size += level.sz;
+            excess += level.sz * over;
+            continue;
+        }
+        // Only the part that keeps the average at the limit
+        let sz = (-excess / over).min(level.sz);
+        size += sz.max(0.0);
+        if sz < level.sz {
+            break;
+        }
+        excess += sz * over;
+    }
+    size
+}
@@ -0,0 +1,17 @@
+//! Market and account analytics built on the info endpoints
+
+pub mod basis;
+pub mod builder;
+pub mod depth;
+pub mod funding;
+pub mod impact;
+pub mod pnl;
+pub mod tca;
+
+pub use basis::{BasisMonitor, BasisPair, BasisUpdate};
+pub use builder::{BuilderRevenue, RevenueRow};
+pub use depth::{DepthMonitor, DepthSnapshot, QueuePosition};
+pub use funding::{CarryMetrics, CarrySample, FundingAnalytics, VenueFunding};
+pub use impact::{estimate_fill_price, max_size_within_slippage, BookDepth, FillEstimate};
+pub use pnl::{CostBasis, Execution, FundingPayment, PnlLedger, PnlRow, PnlRowKind};
+pub use tca::{TcaFill, TcaRecorder, TcaReport};
@@ -0,0 +1,632 @@
+//! Trade-by-trade PnL attribution
+//!
+//! [`PnlLedger`] collects an account's executions and funding payments and
+//! replays them in time order into one [`PnlRow`] per fill: the size it
+//! closed, the cost basis of that size, the realized PnL, the fill's fee and
+//! the funding earned by the closed size while it was open. Fills are read
+//! from `userFills` payloads or `userFillsByTime`, funding from
+//! `userFundings` payloads or `userFunding`; both are deduplicated, so
+//! overlapping sources can be fed in.
+//!
+//! Cost basis is [`CostBasis::Fifo`] (closing the oldest lots first) or
+//! [`CostBasis::AverageCost`] (one lot per coin at the average entry). Funding
+//! is spread over a coin's open lots by size; a payment while flat, e.g. one
+//! settled just after a close, gets a row of its own.
+//!
+//! Fees are charged on the row of the fill that paid them, opening fills
+//! included. Fees paid in another token than USDC, as on spot buys, are
+//! valued at the fill price. With [`PnlLedger::with_orders`] each row also
+//! carries the cloid of the [`OrderManager`] order it filled.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::analytics::{CostBasis, PnlLedger};
+//!
+//! let mut ledger = PnlLedger::new().with_method(CostBasis::AverageCost);
+//! ledger
+//!     .fetch(&client, "0x1234...", 1_672_531_200_000..1_704_067_200_000)
+//!     .await?;
+//! ledger.write_csv(std::fs::File::create("pnl-2023.csv")?)?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::ops::Range;
+use std::pin::pin;
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::info::TimePaginator;
+use crate::oms::OrderManager;
+
+/// Sizes below this count as zero
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// How the cost of closed size is determined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CostBasis {
+    /// Close the oldest open lots first
+    #[default]
+    Fifo,
+    /// Close at the average entry price of the position
+    AverageCost,
+}
+
+/// One fill of the account
+#[derive(Debug, Clone, PartialEq)]
+pub struct Execution {
+    pub coin: String,
+    pub is_buy: bool,
+    pub px: f64,
+    pub sz: f64,
+    /// Fee in USDC; negative for rebates
+    pub fee: f64,
+    /// Time of the fill (ms)
+    pub time: u64,
+    pub tid: Option<u64>,
+    pub oid: Option<u64>,
+    pub cloid: Option<String>,
+}
+
+/// A funding payment, positive when received
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingPayment {
+    pub coin: String,
+    pub usdc: f64,
+    pub time: u64,
+}
+
+/// Kind of ledger row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PnlRowKind {
+    Trade,
+    /// Funding paid while flat
+    Funding,
+}
+
+impl PnlRowKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PnlRowKind::Trade => "trade",
+            PnlRowKind::Funding => "funding",
+        }
+    }
+}
+
+/// PnL attributed to one fill, or to funding paid while flat
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PnlRow {
+    pub time: u64,
+    pub coin: String,
+    pub kind: PnlRowKind,
+    /// `None` on funding rows
+    pub is_buy: Option<bool>,
+    pub px: f64,
+    pub sz: f64,
+    pub tid: Option<u64>,
+    pub oid: Option<u64>,
+    pub cloid: Option<String>,
+    /// Size of the position the fill closed
+    pub closed_sz: f64,
+    /// Average entry price of the closed size
+    pub cost_basis: Option<f64>,
+    /// PnL of the closed size, before fees and funding
+    pub realized_pnl: f64,
+    pub fee: f64,
+    /// Funding earned by the closed size while it was open
+    pub funding: f64,
+    /// `realized_pnl - fee + funding`
+    pub net_pnl: f64,
+    /// Signed position after the row
+    pub position: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WireFill {
+    coin: String,
+    px: String,
+    sz: String,
+    side: String,
+    time: u64,
+    #[serde(default)]
+    fee: Option<String>,
+    #[serde(default)]
+    fee_token: Option<String>,
+    #[serde(default)]
+    tid: Option<u64>,
+    #[serde(default)]
+    oid: Option<u64>,
+    #[serde(default)]
+    cloid: Option<String>,
+}
+
+/// Entry of `userFundings` payloads, also the `delta` of `userFunding`
+#[derive(Debug, Deserialize)]
+struct WireFunding {
+    coin: String,
+    usdc: String,
+    #[serde(default)]
+    time: Option<u64>,
+}
+
+#[derive(Debug)]
+struct Lot {
+    px: f64,
+    /// Unsigned open size
+    sz: f64,
+    /// Funding earned and not yet realized
+    funding: f64,
+}
+
+#[derive(Debug, Default)]
+struct Book {
+    /// Signed position
+    szi: f64,
+    lots: VecDeque<Lot>,
+}
+
+enum Event<'a> {
+    Funding(&'a FundingPayment),
+    Fill(&'a Execution),
+}
+
+/// Per-trade PnL ledger of one account
+#[derive(Debug, Clone, Default)]
+pub struct PnlLedger {
+    method: CostBasis,
+    orders: Option<OrderManager>,
+    executions: Vec<Execution>,
+    fundings: Vec<FundingPayment>,
+    seen_fills: HashSet<u64>,
+    seen_fundings: HashSet<(String, u64)>,
+}
+
+impl PnlLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cost basis method (default FIFO)
+    pub fn with_method(mut self, method: CostBasis) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Take the cloids of fills that don't carry one from `orders`
+    pub fn with_orders(mut self, orders: OrderManager) -> Self {
+        self.orders = Some(orders);
+        self
+    }
+
+    pub fn method(&self) -> CostBasis {
+        self.method
+    }
+
+    /// Add an execution; returns false if its trade id was already seen
+    pub fn record_execution(&mut self, mut execution: Execution) -> bool {
+        if let Some(tid) = execution.tid {
+            if !self.seen_fills.insert(tid) {
+                return false;
+            }
+        }
+        if execution.cloid.is_none() {
+            execution.cloid = execution
+                .oid
+                .zip(self.orders.as_ref())
+                .and_then(|(oid, orders)| orders.order_by_oid(oid))
+                .and_then(|order| order.cloid);
+        }
+        self.executions.push(execution);
+        true
+    }
+
+    /// Add a funding payment; returns false if the coin's payment at that
+    /// time was already seen
+    pub fn record_funding(&mut self, funding: FundingPayment) -> bool {
+        if !self
+            .seen_fundings
+            .insert((funding.coin.clone(), funding.time))
+        {
+            return false;
+        }
+        self.fundings.push(funding);
+        true
+    }
+
+    /// Add one fill of `userFills` or `userFillsByTime`; returns whether it
+    /// was new
+    pub fn ingest_fill(&mut self, fill: &Value) -> bool {
+        let fill = match WireFill::deserialize(fill) {
+            Ok(fill) => fill,
+            Err(e) => {
+                warn!("Ignoring malformed fill: {}", e);
+                return false;
+            }
+        };
+        let (Ok(px), Ok(sz)) = (fill.px.parse::<f64>(), fill.sz.parse::<f64>()) else {
+            warn!("Ignoring fill with malformed px/sz for {}", fill.coin);
+            return false;
+        };
+        let mut fee = fill
+            .fee
+            .as_deref()
+            .and_then(|fee| fee.parse().ok())
+            .unwrap_or(0.0);
+        if fill
+            .fee_token
+            .as_deref()
+            .is_some_and(|token| token != "USDC")
+        {
+            fee *= px;
+        }
+        self.record_execution(Execution {
+            coin: fill.coin,
+            is_buy: fill.side == "B",
+            px,
+            sz,
+            fee,
+            time: fill.time,
+            tid: fill.tid,
+            oid: fill.oid,
+            cloid: fill.cloid,
+        })
+    }
+
+    /// Add the fills of a `userFills` stream payload, snapshots included;
+    /// returns how many were new
+    pub fn ingest_user_fills(&mut self, data: &Value) -> usize {
+        let Some(fills) = data.get("fills").and_then(Value::as_array) else {
+            warn!("Ignoring malformed userFills payload");
+            return 0;
+        };
+        fills.iter().filter(|fill| self.ingest_fill(fill)).count()
+    }
+
+    /// Add one entry of `userFunding` (`{"time", "delta": {...}}`) or of a
+    /// `userFundings` payload; returns whether it was new
+    pub fn ingest_funding(&mut self, entry: &Value) -> bool {
+        let time = entry.get("time").and_then(Value::as_u64);
+        let wire = match WireFunding::deserialize(entry.get("delta").unwrap_or(entry)) {
+            Ok(wire) => wire,
+            Err(e) => {
+                warn!("Ignoring malformed funding: {}", e);
+                return false;
+            }
+        };
+        let (Ok(usdc), Some(time)) = (wire.usdc.parse::<f64>(), time.or(wire.time)) else {
+            warn!("Ignoring malformed funding for {}", wire.coin);
+            return false;
+        };
+        self.record_funding(FundingPayment {
+            coin: wire.coin,
+            usdc,
+            time,
+        })
+    }
+
+    /// Add the payments of a `userFundings` stream payload, snapshots
+    /// included; returns how many were new
+    pub fn ingest_user_fundings(&mut self, data: &Value) -> usize {
+        let Some(fundings) = data.get("fundings").and_then(Value::as_array) else {
+            warn!("Ignoring malformed userFundings payload");
+            return 0;
+        };
+        fundings
+            .iter()
+            .filter(|funding| self.ingest_funding(funding))
+            .count()
+    }
+
+    /// Fetch `user`'s fills and funding payments in `range` and add them;
+    /// returns how many were new
+    pub async fn fetch(
+        &mut self,
+        client: &HttpClient,
+        user: &str,
+        range: Range<u64>,
+    ) -> Result<usize, HyperliquidError> {
+        let mut added = 0;
+        let mut fills =
+            pin!(TimePaginator::user_fills(client.clone(), user, range.clone()).stream());
+        while let Some(fill) = fills.next().await {
+            added += usize::from(self.ingest_fill(&fill?));
+        }
+        let mut fundings = pin!(TimePaginator::user_funding(client.clone(), user, range).stream());
+        while let Some(funding) = fundings.next().await {
+            added += usize::from(self.ingest_funding(&funding?));
+        }
+        Ok(added)
+    }
+
+    /// The ledger, in time order
+    ///
+    /// Positions start flat, so the fills must go back to when each coin's
+    /// position was last opened. At equal times funding is applied before
+    /// fills, and fills in trade id order.
+    pub fn rows(&self) -> Vec<PnlRow> {
+        let mut events: Vec<(u64, u8, u64, Event)> = self
+            .fundings
+            .iter()
+            .map(|funding| (funding.time, 0, 0, Event::Funding(funding)))
+            .chain(self.executions.iter().map(|execution| {
+                let tid = execution.tid.unwrap_or(0);
+                (execution.time, 1, tid, Event::Fill(execution))
+            }))
+            .collect();
+        // Stable, so fills without a trade id keep their ingestion order
+        events.sort_by_key(|(time, order, tid, _)| (*time, *order, *tid));
+
+        let mut books: HashMap<&str, Book> = HashMap::new();
+        let mut rows = Vec::new();
+        for (_, _, _, event) in events {
+            match event {
+                Event::Funding(funding) => {
+                    let book = books.entry(&funding.coin).or_default();
+                    if let Some(row) = self.apply_funding(book, funding) {
+                        rows.push(row);
+                    }
+                }
+                Event::Fill(execution) => {
+                    let book = books.entry(&execution.coin).or_default();
+                    rows.push(self.apply_fill(book, execution));
+                }
+            }
+        }
+        rows
+    }
+
+    fn apply_funding(&self, book: &mut Book, funding: &FundingPayment) -> Option<PnlRow> {
+        let open: f64 = book.lots.iter().map(|lot| lot.sz).sum();
+        if open > SIZE_EPSILON {
+            for lot in &mut book.lots {
+                lot.funding += funding.usdc * lot.sz / open;
+            }
+            return None;
+        }
+        Some(PnlRow {
+            time: funding.time,
+            coin: funding.coin.clone(),
+            kind: PnlRowKind::Funding,
+            is_buy: None,
+            px: 0.0,
+            sz: 0.0,
+            tid: None,
+            oid: None,
+            cloid: None,
+            closed_sz: 0.0,
+            cost_basis: None,
+            realized_pnl: 0.0,
+            fee: 0.0,
+            funding: funding.usdc,
+            net_pnl: funding.usdc,
+            position: book.szi,
+        })
+    }
+
+    fn apply_fill(&self, book: &mut Book, execution: &Execution) -> PnlRow {
+        let long = book.szi > 0.0;
+        let closing = book.szi.abs() > SIZE_EPSILON && long != execution.is_buy;
+        let closed_sz = if closing {
+            execution.sz.min(book.szi.abs())
+        } else {
+            0.0
+        };
+
+        // Close the oldest lots; average cost keeps a single lot
+        let mut remaining = closed_sz;
+        let mut cost = 0.0;
+        let mut funding = 0.0;
+        while remaining > SIZE_EPSILON {
+            let Some(lot) = book.lots.front_mut() else {
+                break;
+            };
+            let sz = remaining.min(lot.sz);
+            let share = lot.funding * sz / lot.sz;
+            cost += sz * lot.px;
+            funding += share;
+            lot.funding -= share;
+            lot.sz -= sz;
+            remaining -= sz;
+            if lot.sz <= SIZE_EPSILON {
+                book.lots.pop_front();
+            }
+        }
+        let realized_pnl = if closed_sz > 0.0 {
+            let proceeds = closed_sz * execution.px;
+            if long {
+                proceeds - cost
+            } else {
+                cost - proceeds
+            }
+        } else {
+            0.0
+        };
+
+        let opened = execution.sz - closed_sz;
+        if opened > SIZE_EPSILON {
+            match (self.method, book.lots.back_mut()) {
+                (CostBasis::AverageCost, Some(lot)) => {
+                    lot.px = (lot.px * lot.sz + execution.px * opened) / (lot.sz + opened);
+                    lot.sz += opened;
+                }
+                _ => book.lots.push_back(Lot {
+                    px: execution.px,
+                    sz: opened,
+                    funding: 0.0,
+                }),
+            }
+        }
+        book.szi += if execution.is_buy {
+            execution.sz
+        } else {
+            -execution.sz
+        };
+        if book.szi.abs() <= SIZE_EPSILON {
+            book.szi = 0.0;
+            book.lots.clear();
+        }
+
+        PnlRow {
+            time: execution.time,
+            coin: execution.coin.clone(),
+            kind: PnlRowKind::Trade,
+            is_buy: Some(execution.is_buy),
+            px: execution.px,
+            sz: execution.sz,
+            tid: execution.tid,
+            oid: execution.oid,
+            cloid: execution.cloid.clone(),
+            closed_sz,
+            cost_basis: (closed_sz > 0.0).then(|| cost / closed_sz),
+            realized_pnl,
+            fee: execution.fee,
+            funding,
+            net_pnl: realized_pnl - execution.fee + funding,
+            position: book.szi,
+        }
+    }
+
+    /// Write [`rows`](Self::rows) as CSV
+    ///
+    /// Columns: `time,coin,kind,side,px,sz,tid,oid,cloid,closed_sz,cost_basis,
+    /// realized_pnl,fee,funding,net_pnl,position`; empty where not
+    /// applicable.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), HyperliquidError> {
+        writeln!(
+            writer,
+            "time,coin,kind,side,px,sz,tid,oid,cloid,closed_sz,cost_basis,\
+             realized_pnl,fee,funding,net_pnl,position"
+        )?;
+        let optional = |value: Option<String>| value.unwrap_or_default();
+        for row in self.rows() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                row.time,
+                row.coin,
+                row.kind.as_str(),
+                match row.is_buy {
+                    Some(true) => "buy",
+                    Some(false) => "sell",
+                    None => "",
+                },
+                row.px,
+                row.sz,
+                optional(row.tid.map(|tid| tid.to_string())),
+                optional(row.oid.map(|oid| oid.to_string())),
+                optional(row.cloid),
+                row.closed_sz,
+                optional(row.cost_basis.map(|px| px.to_string())),
+                row.realized_pnl,
+                row.fee,
+                row.funding,
+                row.net_pnl,
+                row.position,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write [`rows`](Self::rows) as a Parquet file
+    ///
+    /// Same columns as [`write_csv`](Self::write_csv), with `time` as a UTC
+    /// millisecond timestamp and nulls where not applicable.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet(&self, writer: impl Write + Send) -> Result<(), HyperliquidError> {
+        parquet_output::write(&self.rows(), writer)
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_output {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use arrow_array::{
+        ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray,
+        UInt64Array,
+    };
+    use arrow_schema::{DataType, Field, Schema, TimeUnit};
+    use parquet::arrow::ArrowWriter;
+
+    use super::PnlRow;
+    use crate::error::HyperliquidError;
+
+    fn storage_error(e: impl std::fmt::Display) -> HyperliquidError {
+        HyperliquidError::Storage(format!("parquet: {}", e))
+    }
+
+    pub(super) fn write(
+        rows: &[PnlRow],
+        writer: impl Write + Send,
+    ) -> Result<(), HyperliquidError> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("coin", DataType::Utf8, false),
+            Field::new("kind", DataType::Utf8, false),
+            Field::new("is_buy", DataType::Boolean, true),
+            Field::new("px", DataType::Float64, false),
+            Field::new("sz", DataType::Float64, false),
+            Field::new("tid", DataType::UInt64, true),
+            Field::new("oid", DataType::UInt64, true),
+            Field::new("cloid", DataType::Utf8, true),
+            Field::new("closed_sz", DataType::Float64, false),
+            Field::new("cost_basis", DataType::Float64, true),
+            Field::new("realized_pnl", DataType::Float64, false),
+            Field::new("fee", DataType::Float64, false),
+            Field::new("funding", DataType::Float64, false),
+            Field::new("net_pnl", DataType::Float64, false),
+            Field::new("position", DataType::Float64, false),
+        ]));
+        let f64s = |value: fn(&PnlRow) -> f64| -> ArrayRef {
+            Arc::new(Float64Array::from_iter_values(rows.iter().map(value)))
+        };
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(
+                TimestampMillisecondArray::from_iter_values(rows.iter().map(|row| row.time as i64))
+                    .with_timezone("UTC"),
+            ),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|row| &row.coin),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|row| row.kind.as_str()),
+            )),
+            Arc::new(BooleanArray::from_iter(rows.iter().map(|row| row.is_buy))),
+            f64s(|row| row.px),
+            f64s(|row| row.sz),
+            Arc::new(UInt64Array::from_iter(rows.iter().map(|row| row.tid))),
+            Arc::new(UInt64Array::from_iter(rows.iter().map(|row| row.oid))),
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|row| row.cloid.as_deref()),
+            )),
+            f64s(|row| row.closed_sz),
+            Arc::new(Float64Array::from_iter(
+                rows.iter().map(|row| row.cost_basis),
+            )),
+            f64s(|row| row.realized_pnl),
+            f64s(|row| row.fee),
+            f64s(|row| row.funding),
+            f64s(|row| row.net_pnl),
+            f64s(|row| row.position),
+        ];
+        let batch = RecordBatch::try_new(schema.clone(), columns).map_err(storage_error)?;
+        let mut writer = ArrowWriter::try_new(writer, schema, None).map_err(storage_error)?;
+        writer.write(&batch).map_err(storage_error)?;
+        writer.close().map_err(storage_error)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,476 @@
+//! Transaction cost analysis for parent orders
+//!
+//! A [`TcaRecorder`] follows parent orders worked by the
+//! [`Executor`](crate::execution::Executor) (or placed by hand through the
+//! OMS) and, from the account's fills and the market's trades, builds a
+//! [`TcaReport`] per parent:
+//!
+//! - slippage of the average fill price against the mid at arrival;
+//! - slippage against the market VWAP over the execution horizon;
+//! - maker/taker split of size and fees, and the fee total.
+//!
+//! Fills are attributed to a parent by the child oids linked with
+//! [`TcaRecorder::link_child`]; for parents without linked children, every
+//! fill on the same coin and side between [`start`](TcaRecorder::start) and
+//! [`finish`](TcaRecorder::finish) counts. Slippage is in basis points and
+//! signed so that positive is a cost for either side.
+//!
+//! Feed [`handle_user_fills`](TcaRecorder::handle_user_fills) the same
+//! `userFills` payloads given to the
+//! [`OrderManager`](crate::oms::OrderManager), and market trades through
+//! [`attach`](TcaRecorder::attach) or
+//! [`handle_trades`](TcaRecorder::handle_trades).
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient, parent: hyperliquid_core::execution::ParentOrder) -> Result<(), Box<dyn std::error::Error>> {
+//! use hyperliquid_core::analytics::TcaRecorder;
+//!
+//! let tca = TcaRecorder::new();
+//! tca.attach(&ws, &["BTC"]).await?;
+//! tca.start("twap-1", &parent, 66_000.0, None);
+//! // ... run the execution, feeding userFills into `tca` ...
+//! tca.finish("twap-1", None);
+//! tca.write_csv(std::io::stdout())?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+use crate::execution::ParentOrder;
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Market trades kept per coin for horizon VWAPs
+const MAX_TRADES_PER_COIN: usize = 100_000;
+
+/// An account fill
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TcaFill {
+    pub oid: u64,
+    pub coin: String,
+    pub is_buy: bool,
+    pub px: f64,
+    pub sz: f64,
+    pub fee: f64,
+    /// Whether the fill took liquidity
+    pub taker: bool,
+    pub time: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsFill {
+    coin: String,
+    px: String,
+    sz: String,
+    side: String,
+    time: u64,
+    oid: u64,
+    #[serde(default)]
+    fee: Option<String>,
+    #[serde(default)]
+    crossed: bool,
+    #[serde(default)]
+    tid: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsTrade {
+    coin: String,
+    px: String,
+    sz: String,
+    time: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct MarketTrade {
+    time: u64,
+    px: f64,
+    sz: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Execution {
+    id: String,
+    coin: String,
+    is_buy: bool,
+    target_sz: f64,
+    arrival_mid: f64,
+    start_time: u64,
+    end_time: Option<u64>,
+    children: HashSet<u64>,
+    fills: Vec<TcaFill>,
+}
+
+impl Execution {
+    fn claims(&self, fill: &TcaFill) -> bool {
+        if !self.children.is_empty() {
+            return self.children.contains(&fill.oid);
+        }
+        fill.coin == self.coin
+            && fill.is_buy == self.is_buy
+            && fill.time >= self.start_time
+            && self.end_time.map_or(true, |end| fill.time <= end)
+    }
+}
+
+/// Cost breakdown of one parent order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TcaReport {
+    pub id: String,
+    pub coin: String,
+    pub is_buy: bool,
+    pub target_sz: f64,
+    pub filled_sz: f64,
+    /// Volume-weighted average fill price (0 without fills)
+    pub avg_px: f64,
+    pub arrival_mid: f64,
+    /// Average price against the arrival mid, in bps (positive is a cost)
+    pub arrival_slippage_bps: Option<f64>,
+    /// Market VWAP from start to finish (or the last fill)
+    pub market_vwap: Option<f64>,
+    /// Average price against the market VWAP, in bps (positive is a cost)
+    pub vwap_slippage_bps: Option<f64>,
+    pub maker_sz: f64,
+    pub taker_sz: f64,
+    pub maker_fees: f64,
+    pub taker_fees: f64,
+    pub total_fees: f64,
+    /// Fees as a fraction of filled notional, in bps
+    pub fee_bps: Option<f64>,
+    pub fills: usize,
+    pub start_time: u64,
+    pub end_time: Option<u64>,
+}
+
+impl TcaReport {
+    /// Share of the filled size that rested
+    pub fn maker_share(&self) -> Option<f64> {
+        (self.filled_sz > 0.0).then(|| self.maker_sz / self.filled_sz)
+    }
+
+    /// Arrival slippage plus fees, in bps
+    pub fn total_cost_bps(&self) -> Option<f64> {
+        Some(self.arrival_slippage_bps? + self.fee_bps?)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    executions: Vec<Execution>,
+    trades: HashMap<String, VecDeque<MarketTrade>>,
+    seen_fills: HashSet<u64>,
+}
+
+impl State {
+    fn report(&self, execution: &Execution) -> TcaReport {
+        let side = if execution.is_buy { 1.0 } else { -1.0 };
+        let mut report = TcaReport {
+            id: execution.id.clone(),
+            coin: execution.coin.clone(),
+            is_buy: execution.is_buy,
+            target_sz: execution.target_sz,
+            filled_sz: 0.0,
+            avg_px: 0.0,
+            arrival_mid: execution.arrival_mid,
+            arrival_slippage_bps: None,
+            market_vwap: None,
+            vwap_slippage_bps: None,
+            maker_sz: 0.0,
+            taker_sz: 0.0,
+            maker_fees: 0.0,
+            taker_fees: 0.0,
+            total_fees: 0.0,
+            fee_bps: None,
+            fills: execution.fills.len(),
+            start_time: execution.start_time,
+            end_time: execution.end_time,
+        };
+
+        let mut notional = 0.0;
+        for fill in &execution.fills {
+            notional += fill.px * fill.sz;
+            report.filled_sz += fill.sz;
+            if fill.taker {
+                report.taker_sz += fill.sz;
+                report.taker_fees += fill.fee;
+            } else {
+                report.maker_sz += fill.sz;
+                report.maker_fees += fill.fee;
+            }
+        }
+        report.total_fees = report.maker_fees + report.taker_fees;
+        if report.filled_sz <= 0.0 {
+            return report;
+        }
+        report.avg_px = notional / report.filled_sz;
+        report.fee_bps = Some(report.total_fees / notional * 10_000.0);
+        if execution.arrival_mid > 0.0 {
+            report.arrival_slippage_bps =
+                Some(side * (report.avg_px / execution.arrival_mid - 1.0) * 10_000.0);
+        }
+
+        let end = execution
+            .end_time
+            .or_else(|| execution.fills.iter().map(|f| f.time).max())
+            .unwrap_or(execution.start_time);
+        report.market_vwap = self.vwap(&execution.coin, execution.start_time, end);
+        report.vwap_slippage_bps = report
+            .market_vwap
+            .map(|vwap| side * (report.avg_px / vwap - 1.0) * 10_000.0);
+        report
+    }
+
+    fn vwap(&self, coin: &str, start: u64, end: u64) -> Option<f64> {
+        let (notional, size) = self
+            .trades
+            .get(coin)?
+            .iter()
+            .filter(|t| t.time >= start && t.time <= end)
+            .fold((0.0, 0.0), |(n, s), t| (n + t.px * t.sz, s + t.sz));
+        (size > 0.0).then(|| notional / size)
+    }
+}
+
+/// Follows parent orders and produces their [`TcaReport`]s
+///
+/// Cheap to clone; clones share state.
+#[derive(Debug, Clone, Default)]
+pub struct TcaRecorder {
+    state: Arc<Mutex<State>>,
+}
+
+impl TcaRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start following `parent` under `id`, with the mid when it arrived
+    ///
+    /// `time` defaults to now. Restarting an existing id discards its fills.
+    pub fn start(&self, id: &str, parent: &ParentOrder, arrival_mid: f64, time: Option<u64>) {
+        let mut state = self.lock();
+        state.executions.retain(|e| e.id != id);
+        state.executions.push(Execution {
+            id: id.to_string(),
+            coin: parent.coin.clone(),
+            is_buy: parent.is_buy,
+            target_sz: parent.sz,
+            arrival_mid,
+            start_time: time.unwrap_or_else(now_millis),
+            end_time: None,
+            children: HashSet::new(),
+            fills: Vec::new(),
+        });
+    }
+
+    /// Attribute fills of child order `oid` to `id`
+    pub fn link_child(&self, id: &str, oid: u64) -> Result<(), HyperliquidError> {
+        let mut state = self.lock();
+        let execution = state
+            .executions
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown execution: {}", id)))?;
+        execution.children.insert(oid);
+        Ok(())
+    }
+
+    /// Mark `id` finished at `time` (default now), closing its horizon
+    pub fn finish(&self, id: &str, time: Option<u64>) -> Option<TcaReport> {
+        let mut state = self.lock();
+        let execution = state.executions.iter_mut().find(|e| e.id == id)?;
+        execution.end_time = Some(time.unwrap_or_else(now_millis));
+        let execution = execution.clone();
+        Some(state.report(&execution))
+    }
+
+    /// Record an account fill; returns the id it was attributed to
+    ///
+    /// Fills go to the most recently started execution that claims them.
+    pub fn record_fill(&self, fill: TcaFill) -> Option<String> {
+        let mut state = self.lock();
+        let execution = state
+            .executions
+            .iter_mut()
+            .rev()
+            .find(|e| e.claims(&fill))?;
+        execution.fills.push(fill);
+        Some(execution.id.clone())
+    }
+
+    /// Apply a `data` payload from the `userFills` channel
+    ///
+    /// Fills are deduplicated by trade id, so the snapshot sent on
+    /// (re)subscription is harmless.
+    pub fn handle_user_fills(&self, data: &Value) {
+        let fills = data.get("fills").cloned().unwrap_or(Value::Null);
+        let fills: Vec<WsFill> = match serde_json::from_value(fills) {
+            Ok(fills) => fills,
+            Err(e) => {
+                warn!("Failed to parse userFills message: {}", e);
+                return;
+            }
+        };
+        for fill in fills {
+            if let Some(tid) = fill.tid {
+                if !self.lock().seen_fills.insert(tid) {
+                    continue;
+                }
+            }
+            let (Ok(px), Ok(sz)) = (fill.px.parse::<f64>(), fill.sz.parse::<f64>()) else {
+                warn!("Ignoring fill with malformed px/sz: {:?}", fill);
+                continue;
+            };
+            self.record_fill(TcaFill {
+                oid: fill.oid,
+                coin: fill.coin,
+                is_buy: fill.side == "B",
+                px,
+                sz,
+                fee: fill
+                    .fee
+                    .and_then(|fee| fee.parse::<f64>().ok())
+                    .unwrap_or(0.0),
+                taker: fill.crossed,
+                time: fill.time,
+            });
+        }
+    }
+
+    /// Record a market trade for horizon VWAPs
+    pub fn record_trade(&self, coin: &str, px: f64, sz: f64, time: u64) {
+        let mut state = self.lock();
+        let trades = state.trades.entry(coin.to_string()).or_default();
+        trades.push_back(MarketTrade { time, px, sz });
+        if trades.len() > MAX_TRADES_PER_COIN {
+            trades.pop_front();
+        }
+    }
+
+    /// Apply a `data` payload from the `trades` channel
+    pub fn handle_trades(&self, data: &Value) {
+        let trades: Vec<WsTrade> = match serde_json::from_value(data.clone()) {
+            Ok(trades) => trades,
+            Err(e) => {
+                warn!("Failed to parse trades message: {}", e);
+                return;
+            }
+        };
+        for trade in trades {
+            if let (Ok(px), Ok(sz)) = (trade.px.parse::<f64>(), trade.sz.parse::<f64>()) {
+                self.record_trade(&trade.coin, px, sz, trade.time);
+            }
+        }
+    }
+
+    /// Subscribe `ws` to the trades of `coins` and record them
+    ///
+    /// Registers the handlers for the `trades` subscriptions, replacing any
+    /// existing handlers for them.
+    #[cfg(feature = "ws")]
+    pub async fn attach(
+        &self,
+        ws: &WebSocketClient,
+        coins: &[&str],
+    ) -> Result<(), HyperliquidError> {
+        for coin in coins {
+            let subscription = Subscription::Trades {
+                coin: coin.to_string(),
+            };
+            let recorder = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if response.channel == "trades" {
+                    recorder.handle_trades(&response.data);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Current report for `id`
+    pub fn report(&self, id: &str) -> Option<TcaReport> {
+        let state = self.lock();
+        let execution = state.executions.iter().find(|e| e.id == id)?;
+        Some(state.report(execution))
+    }
+
+    /// Reports for every execution, in start order
+    pub fn reports(&self) -> Vec<TcaReport> {
+        let state = self.lock();
+        state.executions.iter().map(|e| state.report(e)).collect()
+    }
+
+    /// Forget finished executions and return how many were removed
+    pub fn prune_finished(&self) -> usize {
+        let mut state = self.lock();
+        let before = state.executions.len();
+        state.executions.retain(|e| e.end_time.is_none());
+        before - state.executions.len()
+    }
+
+    /// Write every report as a JSON array
+    pub fn write_json(&self, writer: impl Write) -> Result<(), HyperliquidError> {
+        serde_json::to_writer_pretty(writer, &self.reports())?;
+        Ok(())
+    }
+
+    /// Write every report as CSV, one row per execution
+    pub fn write_csv(&self, mut writer: impl Write) -> std::io::Result<()> {
+        writeln!(
+            writer,
+            "id,coin,side,target_sz,filled_sz,avg_px,arrival_mid,arrival_slippage_bps,\
+             market_vwap,vwap_slippage_bps,maker_sz,taker_sz,maker_fees,taker_fees,\
+             total_fees,fee_bps,fills,start_time,end_time"
+        )?;
+        let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        for report in self.reports() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                report.id,
+                report.coin,
+                if report.is_buy { "buy" } else { "sell" },
+                report.target_sz,
+                report.filled_sz,
+                report.avg_px,
+                report.arrival_mid,
+                optional(report.arrival_slippage_bps),
+                optional(report.market_vwap),
+                optional(report.vwap_slippage_bps),
+                report.maker_sz,
+                report.taker_sz,
+                report.maker_fees,
+                report.taker_fees,
+                report.total_fees,
+                optional(report.fee_bps),
+                report.fills,
+                report.start_time,
+                report.end_time.map(|t| t.to_string()).unwrap_or_default(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
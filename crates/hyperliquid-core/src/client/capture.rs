@@ -0,0 +1,196 @@
+//! Request/response capture for debugging
+//!
+//! An [`HttpCapture`] attached with [`HttpClient::with_capture`] records the
+//! raw request body, the request headers (credentials redacted) and the raw
+//! response of every attempt against the selected endpoints. Captures are
+//! kept in a ring buffer readable through [`HttpCapture::captures`] and, if
+//! a file was given, appended to it as JSON lines, so a rejected order can be
+//! inspected without putting a proxy in front of the client.
+//!
+//! ```no_run
+//! # fn example() -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::sync::Arc;
+//! use hyperliquid_core::client::HttpCapture;
+//! use hyperliquid_core::HttpClient;
+//!
+//! let capture = Arc::new(HttpCapture::new(100).with_endpoints(["/exchange"]));
+//! let client = HttpClient::with_default_config("https://api.hyperliquid.xyz")?
+//!     .with_capture(capture.clone());
+//! // ... after a rejection
+//! for exchange in capture.captures() {
+//!     println!("{} -> {:?}", exchange.request_body.unwrap_or_default(), exchange.response_body);
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! [`HttpClient::with_capture`]: super::HttpClient::with_capture
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+
+/// Replacement for redacted header values
+pub const REDACTED: &str = "[redacted]";
+
+/// Header names whose values are never captured (compared case-insensitively)
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "api-key",
+];
+
+/// One captured request attempt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedRequest {
+    /// Capture time in milliseconds since the epoch
+    pub time: i64,
+    /// Trace id shared by all attempts of a request
+    pub trace_id: String,
+    /// Attempt number, starting at 1
+    pub attempt: u32,
+    pub method: String,
+    pub url: String,
+    /// Request headers, sensitive values redacted
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: Option<String>,
+    /// HTTP status, absent if no response was received
+    pub status: Option<u16>,
+    /// Response headers, sensitive values redacted
+    pub response_headers: Vec<(String, String)>,
+    pub response_body: Option<String>,
+    /// Transport error, if the request failed before a response
+    pub error: Option<String>,
+    pub latency_ms: u64,
+}
+
+/// Ring buffer, and optionally a file, of captured requests
+pub struct HttpCapture {
+    capacity: usize,
+    endpoints: Vec<String>,
+    buffer: Mutex<VecDeque<CapturedRequest>>,
+    file: Option<(PathBuf, Mutex<File>)>,
+}
+
+impl HttpCapture {
+    /// Keep the last `capacity` captures of every endpoint in memory
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            endpoints: Vec::new(),
+            buffer: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+            file: None,
+        }
+    }
+
+    /// Only capture requests to these paths (e.g. `"/exchange"`)
+    pub fn with_endpoints<I, S>(mut self, endpoints: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.endpoints = endpoints.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Also append every capture to `path` as a JSON line
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> Result<Self, HyperliquidError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| {
+                HyperliquidError::Config(format!("capture file {}: {}", path.display(), e))
+            })?;
+        self.file = Some((path, Mutex::new(file)));
+        Ok(self)
+    }
+
+    /// Whether requests to `path` are captured
+    pub fn captures_path(&self, path: &str) -> bool {
+        self.endpoints.is_empty() || self.endpoints.iter().any(|endpoint| endpoint == path)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, VecDeque<CapturedRequest>> {
+        self.buffer.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Store a capture, evicting the oldest when the buffer is full
+    pub fn record(&self, capture: CapturedRequest) {
+        if let Some((path, file)) = &self.file {
+            let written = serde_json::to_vec(&capture)
+                .map_err(std::io::Error::from)
+                .and_then(|mut line| {
+                    line.push(b'\n');
+                    file.lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .write_all(&line)
+                });
+            if let Err(e) = written {
+                warn!("Failed to write capture to {}: {}", path.display(), e);
+            }
+        }
+
+        if self.capacity == 0 {
+            return;
+        }
+        let mut buffer = self.lock();
+        if buffer.len() >= self.capacity {
+            buffer.pop_front();
+        }
+        buffer.push_back(capture);
+    }
+
+    /// Captures in memory, oldest first
+    pub fn captures(&self) -> Vec<CapturedRequest> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Most recent capture
+    pub fn last(&self) -> Option<CapturedRequest> {
+        self.lock().back().cloned()
+    }
+
+    /// Drop the captures in memory
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+}
+
+impl std::fmt::Debug for HttpCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpCapture")
+            .field("capacity", &self.capacity)
+            .field("endpoints", &self.endpoints)
+            .field("captured", &self.lock().len())
+            .field("file", &self.file.as_ref().map(|(path, _)| path))
+            .finish()
+    }
+}
+
+/// Header pairs with sensitive values replaced by [`REDACTED`]
+pub fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let name = name.as_str().to_string();
+            let value = if SENSITIVE_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name, value)
+        })
+        .collect()
+}
@@ -1,11 +1,14 @@
-use reqwest::{Client, ClientBuilder, Method, RequestBuilder, StatusCode};
+use reqwest::{header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE}, Client, ClientBuilder, Method, RequestBuilder, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
+use std::io::Read;
 use std::time::Duration;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
 use crate::error::HyperliquidError;
-use crate::logging::{generate_trace_id, request_span, log_request, log_response, log_error, log_retry};
+use crate::health::ExchangeHealth;
+use super::capture::{redact_headers, CapturedRequest, HttpCapture};
+use crate::logging::{log_request, log_response, log_error, log_retry, RequestTrace};
 
 // Certificate pinning imports
 use rustls::{
@@ -21,6 +24,11 @@ use crate::types::response_utils::*;
 /// Re-export commonly used types
 pub use crate::types::{BaseResponse, ErrorResponse, ApiResponse, parse_response, parse_success_response, parse_error_response, wrap_success, wrap_error, is_error_response, extract_status, extract_nested_data};
 
+/// Default cap on a decompressed response body (64 MiB)
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024 * 1024;
+/// Default size above which a compressed body is decoded off the reactor (256 KiB)
+pub const DEFAULT_BLOCKING_DECOMPRESS_BYTES: usize = 256 * 1024;
+
 /// Configuration for HTTP client connection pooling and timeouts
 #[derive(Clone, Debug)]
 pub struct HttpClientConfig {
@@ -34,8 +42,12 @@ pub struct HttpClientConfig {
     pub request_timeout_ms: u64,
     /// Enable HTTP/2
     pub http2: bool,
-    /// Enable compression
+    /// Ask for gzip or brotli encoded responses
     pub compression: bool,
+    /// Largest response body accepted, after decompression, in bytes
+    pub max_response_bytes: usize,
+    /// Compressed bodies larger than this are decompressed on the blocking pool
+    pub blocking_decompress_bytes: usize,
     /// Enable keepalive
     pub keepalive: bool,
     /// Keepalive duration in milliseconds
@@ -83,6 +95,8 @@ impl Default for HttpClientConfig {
             request_timeout_ms: 30000,
             http2: true,
             compression: true,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            blocking_decompress_bytes: DEFAULT_BLOCKING_DECOMPRESS_BYTES,
             keepalive: true,
             keepalive_ms: 30000,
             user_agent: "hyperliquid-rs/0.1.0".to_string(),
@@ -120,14 +134,17 @@ impl ConnectionStats {
 
     pub fn increment_total(&self) {
         self.total_requests.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_http_requests_total").increment(1);
     }
 
     pub fn increment_successful(&self) {
         self.successful_requests.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_http_requests_succeeded_total").increment(1);
     }
 
     pub fn increment_failed(&self) {
         self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_http_requests_failed_total").increment(1);
     }
 
     pub fn increment_reuses(&self) {
@@ -136,6 +153,7 @@ impl ConnectionStats {
 
     pub fn increment_retries_attempted(&self) {
         self.retries_attempted.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_http_retries_total").increment(1);
     }
 
     pub fn increment_retries_succeeded(&self) {
@@ -144,6 +162,7 @@ impl ConnectionStats {
 
     pub fn increment_retry_exhausted(&self) {
         self.retry_exhausted.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_http_retries_exhausted_total").increment(1);
     }
 
     pub fn get_stats(&self) -> (u64, u64, u64, u64, u64, u64, u64) {
@@ -310,6 +329,8 @@ pub struct HttpClient {
     base_url: String,
     config: HttpClientConfig,
     stats: Arc<ConnectionStats>,
+    capture: Option<Arc<HttpCapture>>,
+    health: Option<ExchangeHealth>,
 }
 
 impl HttpClient {
@@ -331,12 +352,11 @@ impl HttpClient {
             builder = builder.http2_prior_knowledge();
         }
 
-        // Compression
-        if !config.compression {
-            builder = builder.no_gzip();
-            builder = builder.no_brotli();
-            builder = builder.no_deflate();
-        }
+        // Compression is negotiated in `send` and decoded in `read_response`,
+        // so the size limit applies to the decompressed body
+        builder = builder.no_gzip();
+        builder = builder.no_brotli();
+        builder = builder.no_deflate();
 
         // Keepalive
         if config.keepalive {
@@ -395,9 +415,40 @@ impl HttpClient {
             base_url: base_url.into(),
             config,
             stats: Arc::new(ConnectionStats::new()),
+            capture: None,
+            health: None,
         })
     }
 
+    /// Record raw requests and responses of the endpoints `capture` selects
+    pub fn with_capture(mut self, capture: Arc<HttpCapture>) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Capture attached with [`with_capture`](Self::with_capture)
+    pub fn capture(&self) -> Option<&Arc<HttpCapture>> {
+        self.capture.as_ref()
+    }
+
+    /// Feed request outcomes to `health` and hold requests back while it
+    /// reports the exchange unavailable
+    pub fn with_health(mut self, health: ExchangeHealth) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// Health signal attached with [`with_health`](Self::with_health)
+    pub fn health(&self) -> Option<&ExchangeHealth> {
+        self.health.as_ref()
+    }
+
+    /// This client without its health signal, for probing the exchange
+    pub(crate) fn without_health(mut self) -> Self {
+        self.health = None;
+        self
+    }
+
     /// Create a new HTTP client with default configuration
     pub fn with_default_config(base_url: impl Into<String>) -> Result<Self, HyperliquidError> {
         Self::new(base_url, HttpClientConfig::default())
@@ -476,6 +527,17 @@ impl HttpClient {
         self.request(Method::POST, path, Some(body)).await
     }
 
+    /// Make a POST request with a body already serialized as JSON
+    ///
+    /// Lets hot paths serialize into a reused buffer instead of handing over
+    /// a value to be serialized per request.
+    pub async fn post_raw<R>(&self, path: &str, body: &[u8]) -> Result<R, HyperliquidError>
+    where
+        R: DeserializeOwned,
+    {
+        self.send(Method::POST, path, Some(body)).await
+    }
+
     /// Make a GET request
     pub async fn get<R>(&self, path: &str) -> Result<R, HyperliquidError>
     where
@@ -506,34 +568,90 @@ impl HttpClient {
     where
         T: Serialize,
         R: DeserializeOwned,
+    {
+        // Serialize once: the same bytes are logged and sent on every attempt
+        let body = body.map(serde_json::to_vec).transpose()?;
+        self.send(method, path, body.as_deref()).await
+    }
+
+    /// Send a request with a pre-serialized JSON body, retrying as configured
+    async fn send<R>(&self, method: Method, path: &str, body: Option<&[u8]>) -> Result<R, HyperliquidError>
+    where
+        R: DeserializeOwned,
     {
         self.stats.increment_total();
 
-        let trace_id = generate_trace_id();
         let url = format!("{}{}", self.base_url, path);
+        let trace = RequestTrace::http(method.as_str(), &url);
+        let trace_id = trace.trace_id().to_string();
 
         // Log request details
-        let body_str = body.map(|b| serde_json::to_string(b).unwrap_or_default());
+        let body_str = body.map(String::from_utf8_lossy);
         log_request(&trace_id, method.as_str(), &url, body_str.as_deref());
 
         let start_time = std::time::Instant::now();
         let mut attempt = 0;
         let mut last_error = None;
+        let capture = self.capture.as_ref().filter(|capture| capture.captures_path(path));
 
         loop {
+            // The circuit breaker also stops retries once the exchange is down
+            if let Some(health) = &self.health {
+                health.allow_request()?;
+            }
             debug!("Making {} request to {} (attempt {})", method, url, attempt + 1);
 
+            // Each attempt is a child of the request span and carries the same trace id
+            let attempt_span = trace.attempt_span(attempt + 1);
             let mut request_builder = self.client.request(method.clone(), &url);
+            for (name, value) in trace.headers(&attempt_span) {
+                request_builder = request_builder.header(name, value);
+            }
+
+            if self.config.compression {
+                request_builder = request_builder.header(ACCEPT_ENCODING, "gzip, br");
+            }
 
             // Add body if provided
             if let Some(body) = body {
-                request_builder = request_builder.json(body);
+                request_builder = request_builder
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(body.to_vec());
             }
 
+            let mut captured = capture.map(|_| {
+                let request_headers = request_builder
+                    .try_clone()
+                    .and_then(|builder| builder.build().ok())
+                    .map(|request| redact_headers(request.headers()))
+                    .unwrap_or_default();
+                CapturedRequest {
+                    time: chrono::Utc::now().timestamp_millis(),
+                    trace_id: trace_id.clone(),
+                    attempt: attempt + 1,
+                    method: method.to_string(),
+                    url: url.clone(),
+                    request_headers,
+                    request_body: body_str.as_deref().map(str::to_string),
+                    status: None,
+                    response_headers: Vec::new(),
+                    response_body: None,
+                    error: None,
+                    latency_ms: 0,
+                }
+            });
+            let attempt_start = std::time::Instant::now();
+
             // Send request
-            let response = match request_builder.send().await {
+            let response = match request_builder.send().instrument(attempt_span.clone()).await {
                 Ok(response) => response,
                 Err(e) => {
+                    if let (Some(capture), Some(mut captured)) = (capture, captured.take()) {
+                        captured.error = Some(e.to_string());
+                        captured.latency_ms = attempt_start.elapsed().as_millis() as u64;
+                        capture.record(captured);
+                    }
+
                     // Network errors are immediately retryable
                     let error = if e.is_connect() {
                         HyperliquidError::Timeout(format!("Connection timeout: {}", e))
@@ -542,6 +660,9 @@ impl HttpClient {
                     } else {
                         HyperliquidError::Network(e)
                     };
+                    if let Some(health) = &self.health {
+                        health.record_error(&error);
+                    }
 
                     if attempt < self.config.retry_policy.max_retries && error.is_retryable() {
                         self.stats.increment_retries_attempted();
@@ -555,6 +676,8 @@ impl HttpClient {
                     } else {
                         self.stats.increment_failed();
                         let latency_ms = start_time.elapsed().as_millis() as u64;
+                        crate::telemetry::histogram!("hyperliquid_http_request_duration_seconds")
+                            .record(start_time.elapsed().as_secs_f64());
                         log_response(&trace_id, 0, latency_ms, Some(&format!("Network error: {}", error)));
                         return Err(error);
                     }
@@ -562,13 +685,41 @@ impl HttpClient {
             };
 
             // Handle response
-            match self.handle_response(response).await {
+            let result = match self.read_response(response).instrument(attempt_span).await {
+                Ok((status, headers, text)) => {
+                    if let (Some(capture), Some(mut captured)) = (capture, captured.take()) {
+                        captured.status = Some(status.as_u16());
+                        captured.response_headers = redact_headers(&headers);
+                        captured.response_body = Some(text.clone());
+                        captured.latency_ms = attempt_start.elapsed().as_millis() as u64;
+                        capture.record(captured);
+                    }
+                    self.parse_response(status, &headers, text)
+                }
+                Err(e) => {
+                    if let (Some(capture), Some(mut captured)) = (capture, captured.take()) {
+                        captured.error = Some(e.to_string());
+                        captured.latency_ms = attempt_start.elapsed().as_millis() as u64;
+                        capture.record(captured);
+                    }
+                    Err(e)
+                }
+            };
+            if let Some(health) = &self.health {
+                match &result {
+                    Ok(_) => health.record_success(),
+                    Err(error) => health.record_error(error),
+                }
+            }
+            match result {
                 Ok(result) => {
                     if attempt > 0 {
                         self.stats.increment_retries_succeeded();
                     }
                     self.stats.increment_successful();
                     let latency_ms = start_time.elapsed().as_millis() as u64;
+                    crate::telemetry::histogram!("hyperliquid_http_request_duration_seconds")
+                        .record(start_time.elapsed().as_secs_f64());
                     log_response(&trace_id, 200, latency_ms, Some("Success"));
                     return Ok(result);
                 }
@@ -576,7 +727,11 @@ impl HttpClient {
                     if attempt < self.config.retry_policy.max_retries && error.is_retryable() {
                         self.stats.increment_retries_attempted();
                         last_error = Some(error.clone());
-                        let delay = self.calculate_delay(attempt);
+                        // Honour the server's Retry-After over our own backoff
+                        let delay = error
+                            .retry_after()
+                            .map(|retry_after| retry_after.as_millis() as u64)
+                            .unwrap_or_else(|| self.calculate_delay(attempt));
                         log_retry(&trace_id, attempt + 1, self.config.retry_policy.max_retries, delay, &error.to_string());
                         debug!("Retryable error on attempt {}, sleeping for {}ms: {:?}", attempt + 1, delay, error);
                         tokio::time::sleep(Duration::from_millis(delay)).await;
@@ -585,6 +740,8 @@ impl HttpClient {
                     } else {
                         self.stats.increment_failed();
                         let latency_ms = start_time.elapsed().as_millis() as u64;
+                        crate::telemetry::histogram!("hyperliquid_http_request_duration_seconds")
+                            .record(start_time.elapsed().as_secs_f64());
                         log_error(&trace_id, &error.to_string(), "http_client");
                         if attempt > 0 {
                             self.stats.increment_retry_exhausted();
@@ -619,21 +776,60 @@ impl HttpClient {
         capped_delay.saturating_add(jitter)
     }
 
-    /// Handle HTTP response and convert to appropriate error
-    async fn handle_response<R>(&self, response: reqwest::Response) -> Result<R, HyperliquidError>
+    /// Read the status, headers and body of an HTTP response
+    async fn read_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<(StatusCode, reqwest::header::HeaderMap, String), HyperliquidError> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let limit = self.config.max_response_bytes;
+        if response.content_length().is_some_and(|len| len > limit as u64) {
+            return Err(HyperliquidError::ResponseTooLarge { limit });
+        }
+
+        let mut response = response;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(HyperliquidError::ResponseTooLarge { limit });
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let encoding = headers
+            .get(CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_ascii_lowercase());
+        let body = match encoding {
+            None => body,
+            Some(encoding) if body.len() > self.config.blocking_decompress_bytes => {
+                tokio::task::spawn_blocking(move || decode_body(&encoding, &body, limit))
+                    .await
+                    .map_err(|e| HyperliquidError::Unknown(format!("decompression task failed: {}", e)))??
+            }
+            Some(encoding) => decode_body(&encoding, &body, limit)?,
+        };
+        let text = String::from_utf8_lossy(&body).into_owned();
+        Ok((status, headers, text))
+    }
+
+    /// Convert a read HTTP response to the result or an appropriate error
+    fn parse_response<R>(
+        &self,
+        status: StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        text: String,
+    ) -> Result<R, HyperliquidError>
     where
         R: DeserializeOwned,
     {
-        let status = response.status();
-
         if status.is_success() {
-            let text = response.text().await?;
             let parsed: R = serde_json::from_str(&text)
                 .map_err(|e| HyperliquidError::Json(e))?;
             Ok(parsed)
         } else if status.is_client_error() {
             // Handle 4xx errors
-            let text = response.text().await?;
             match serde_json::from_str::<serde_json::Value>(&text) {
                 Ok(json) => {
                     if let (Some(code), Some(msg)) = (json.get("code"), json.get("msg")) {
@@ -655,10 +851,8 @@ impl HttpClient {
             })
         } else if status == StatusCode::TOO_MANY_REQUESTS {
             // Handle rate limiting with retry-after header parsing
-            let text = response.text().await?;
-
             // Parse retry-after header if present
-            let retry_after = response.headers()
+            let retry_after = headers
                 .get("retry-after")
                 .and_then(|value| value.to_str().ok())
                 .and_then(|s| s.parse::<u64>().ok());
@@ -673,14 +867,12 @@ impl HttpClient {
             }
         } else if status.is_server_error() {
             // Handle 5xx errors
-            let text = response.text().await?;
             Err(HyperliquidError::Server {
                 status,
                 message: text,
             })
         } else {
             // Handle other errors
-            let text = response.text().await?;
             Err(HyperliquidError::Http {
                 status,
                 message: text,
@@ -690,6 +882,29 @@ impl HttpClient {
     }
 }
 
+/// Decode a response body by its `Content-Encoding`, failing past `limit` bytes
+fn decode_body(encoding: &str, body: &[u8], limit: usize) -> Result<Vec<u8>, HyperliquidError> {
+    let decoder: Box<dyn Read + '_> = match encoding {
+        "" | "identity" => return Ok(body.to_vec()),
+        "gzip" | "x-gzip" => Box::new(flate2::read::GzDecoder::new(body)),
+        "br" => Box::new(brotli::Decompressor::new(body, 4096)),
+        other => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("unsupported Content-Encoding: {}", other),
+            )
+            .into())
+        }
+    };
+    // One byte past the limit tells an exact fit from an overflow
+    let mut decoded = Vec::new();
+    decoder.take(limit as u64 + 1).read_to_end(&mut decoded)?;
+    if decoded.len() > limit {
+        return Err(HyperliquidError::ResponseTooLarge { limit });
+    }
+    Ok(decoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
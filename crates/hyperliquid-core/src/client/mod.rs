@@ -1,7 +1,11 @@
 //! HTTP and WebSocket client implementations for Hyperliquid API
 
+pub mod capture;
 pub mod http;
+#[cfg(feature = "ws")]
 pub mod websocket;
 
+pub use capture::{CapturedRequest, HttpCapture};
 pub use http::{HttpClient, HttpClientConfig};
+#[cfg(feature = "ws")]
 pub use websocket::{WebSocketClient, WebSocketConfig};
\ No newline at end of file
@@ -3,11 +3,26 @@
 //! This module provides TOML-based configuration loading with environment
 //! variable overrides and validation. It supports both file-based and
 //! programmatic configuration.
+//!
+//! Files can pull in shared settings with `include = ["base.toml"]` (paths are
+//! relative to the including file; later files override earlier ones) and
+//! define named overrides under `[profile.<name>]`, selected with
+//! `HYPERLIQUID_PROFILE`.
+//!
+//! String values such as `private_key = "env:HL_KEY"` are resolved through the
+//! [`SecretsProvider`]s registered with a [`SecretsResolver`]; see [`secrets`].
+
+pub mod secrets;
+
+pub use secrets::{
+    EnvSecretsProvider, FileSecretsProvider, Secret, SecretsProvider, SecretsResolver,
+    VaultSecretsProvider,
+};
 
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Main configuration for the Hyperliquid SDK
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +54,14 @@ pub struct Config {
     /// Metrics configuration
     #[serde(default)]
     pub metrics: MetricsConfig,
+
+    /// Strategy runner settings
+    #[serde(default)]
+    pub strategy: StrategyConfig,
+
+    /// Profile applied when loading, if any
+    #[serde(skip)]
+    pub profile: Option<String>,
 }
 
 impl Default for Config {
@@ -51,6 +74,8 @@ impl Default for Config {
             logging: LoggingConfig::default(),
             security: SecurityConfig::default(),
             metrics: MetricsConfig::default(),
+            strategy: StrategyConfig::default(),
+            profile: None,
         }
     }
 }
@@ -74,20 +99,79 @@ impl Config {
     /// let config = Config::load("config/default.toml")?;
     /// ```
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, crate::error::HyperliquidError> {
-        let path = path.as_ref();
+        let profile = env::var("HYPERLIQUID_PROFILE").ok().filter(|p| !p.is_empty());
+        Self::load_with_profile(path, profile.as_deref())
+    }
 
-        // Load base configuration from file
-        let toml_content = fs::read_to_string(path)
-            .map_err(|e| crate::error::HyperliquidError::Config(format!(
-                "Failed to read config file {}: {}",
-                path.display(), e
-            )))?;
+    /// Load configuration from TOML file, applying the named profile
+    ///
+    /// Includes are resolved first, then `[profile.<name>]` is merged over the
+    /// result. Passing `None` ignores `HYPERLIQUID_PROFILE` and applies no profile.
+    /// Secret references are resolved with the default `env`, `file` and
+    /// `vault` providers.
+    pub fn load_with_profile<P: AsRef<Path>>(
+        path: P,
+        profile: Option<&str>,
+    ) -> Result<Self, crate::error::HyperliquidError> {
+        Self::load_with_secrets(path, profile, &SecretsResolver::default())
+    }
+
+    /// Load configuration, resolving secret references with `secrets`
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use hyperliquid_core::config::{Config, SecretsResolver, VaultSecretsProvider};
+    ///
+    /// let secrets = SecretsResolver::default()
+    ///     .with_provider(VaultSecretsProvider::new("https://vault:8200", token));
+    /// let config = Config::load_with_secrets("config/prod.toml", Some("prod"), &secrets)?;
+    /// ```
+    pub fn load_with_secrets<P: AsRef<Path>>(
+        path: P,
+        profile: Option<&str>,
+        secrets: &SecretsResolver,
+    ) -> Result<Self, crate::error::HyperliquidError> {
+        let mut table = load_table(path.as_ref(), &mut Vec::new())?;
+
+        let profiles = match table.remove("profile") {
+            Some(toml::Value::Table(profiles)) => profiles,
+            Some(_) => {
+                return Err(crate::error::HyperliquidError::Config(
+                    "`profile` must be a table of named profiles".to_string()
+                ));
+            }
+            None => toml::Table::new(),
+        };
+
+        if let Some(name) = profile {
+            match profiles.get(name) {
+                Some(toml::Value::Table(overrides)) => merge_tables(&mut table, overrides.clone()),
+                Some(_) => {
+                    return Err(crate::error::HyperliquidError::Config(format!(
+                        "Profile {} must be a table",
+                        name
+                    )));
+                }
+                None => {
+                    let available: Vec<&str> = profiles.keys().map(String::as_str).collect();
+                    return Err(crate::error::HyperliquidError::Config(format!(
+                        "Unknown config profile {} (available: {})",
+                        name,
+                        if available.is_empty() { "none".to_string() } else { available.join(", ") }
+                    )));
+                }
+            }
+        }
+
+        secrets.resolve_table(&mut table)?;
 
-        let mut config: Config = toml::from_str(&toml_content)
+        let mut config: Config = toml::Value::Table(table).try_into()
             .map_err(|e| crate::error::HyperliquidError::Config(format!(
                 "Failed to parse TOML config: {}",
                 e
             )))?;
+        config.profile = profile.map(str::to_string);
 
         // Apply environment variable overrides
         config.apply_env_overrides()?;
@@ -185,39 +269,288 @@ impl Config {
     }
 
     /// Validate configuration values
-    fn validate(&self) -> Result<(), crate::error::HyperliquidError> {
-        // Validate HTTP timeout
-        if self.http.request_timeout_ms < 1000 {
-            return Err(crate::error::HyperliquidError::Config(
-                "HTTP request timeout must be at least 1000ms".to_string()
-            ));
+    ///
+    /// Runs every check and reports all problems in one error, one line per
+    /// offending field.
+    pub fn validate(&self) -> Result<(), crate::error::HyperliquidError> {
+        let issues = self.validate_all();
+        if issues.is_empty() {
+            return Ok(());
         }
 
-        // Validate connection pool size
-        if self.http.max_connections_per_host < 1 {
-            return Err(crate::error::HyperliquidError::Config(
-                "Max connections per host must be at least 1".to_string()
-            ));
+        let mut message = format!(
+            "Invalid configuration ({} problem{}):",
+            issues.len(),
+            if issues.len() == 1 { "" } else { "s" }
+        );
+        for issue in &issues {
+            message.push_str("\n  - ");
+            message.push_str(&issue.to_string());
         }
+        Err(crate::error::HyperliquidError::Config(message))
+    }
 
-        // Validate worker threads
-        if self.runtime.worker_threads < 1 {
-            return Err(crate::error::HyperliquidError::Config(
-                "Worker threads must be at least 1".to_string()
-            ));
+    /// Check every section and return all problems found
+    pub fn validate_all(&self) -> Vec<ConfigIssue> {
+        let mut issues = Vec::new();
+        let mut check = |ok: bool, path: &str, message: String| {
+            if !ok {
+                issues.push(ConfigIssue {
+                    path: path.to_string(),
+                    message,
+                });
+            }
+        };
+
+        // Environment
+        if let Some(url) = &self.environment.base_url {
+            check(
+                url.starts_with("http://") || url.starts_with("https://"),
+                "environment.base_url",
+                format!("{} is not an http(s) URL", url),
+            );
+        }
+        if let Some(url) = &self.environment.websocket_url {
+            check(
+                url.starts_with("ws://") || url.starts_with("wss://"),
+                "environment.websocket_url",
+                format!("{} is not a ws(s) URL", url),
+            );
         }
 
-        // Validate log level
-        match self.logging.level.to_lowercase().as_str() {
-            "trace" | "debug" | "info" | "warn" | "error" => {},
-            _ => {
-                return Err(crate::error::HyperliquidError::Config(
-                    format!("Invalid log level: {}", self.logging.level)
-                ));
+        // HTTP
+        let http = &self.http;
+        check(
+            http.request_timeout_ms >= 1000,
+            "http.request_timeout_ms",
+            "must be at least 1000ms".to_string(),
+        );
+        check(
+            http.connect_timeout_ms >= 1,
+            "http.connect_timeout_ms",
+            "must be at least 1ms".to_string(),
+        );
+        check(
+            http.max_connections_per_host >= 1,
+            "http.max_connections_per_host",
+            "must be at least 1".to_string(),
+        );
+        check(
+            http.max_total_connections >= http.max_connections_per_host,
+            "http.max_total_connections",
+            format!(
+                "must be at least http.max_connections_per_host ({})",
+                http.max_connections_per_host
+            ),
+        );
+
+        // WebSocket
+        let ws = &self.websocket;
+        check(
+            ws.connect_timeout_ms >= 1,
+            "websocket.connect_timeout_ms",
+            "must be at least 1ms".to_string(),
+        );
+        check(
+            ws.buffer_size >= 1,
+            "websocket.buffer_size",
+            "must be at least 1".to_string(),
+        );
+        check(
+            (1000..=MAX_WS_PING_INTERVAL_MS).contains(&ws.ping_interval_ms),
+            "websocket.ping_interval_ms",
+            format!(
+                "must be between 1000ms and {}ms (the server drops idle connections)",
+                MAX_WS_PING_INTERVAL_MS
+            ),
+        );
+        // A stalled consumer is only noticed at the next ping; the buffer has
+        // to absorb at least one message per second until then.
+        let min_buffer = (ws.ping_interval_ms / 1000) as usize;
+        check(
+            ws.buffer_size >= min_buffer,
+            "websocket.buffer_size",
+            format!(
+                "must be at least {} to cover websocket.ping_interval_ms ({}ms)",
+                min_buffer, ws.ping_interval_ms
+            ),
+        );
+        check(
+            ws.connect_timeout_ms < ws.ping_interval_ms,
+            "websocket.connect_timeout_ms",
+            format!(
+                "must be shorter than websocket.ping_interval_ms ({}ms)",
+                ws.ping_interval_ms
+            ),
+        );
+        check(
+            ws.max_reconnect_attempts == 0 || ws.reconnect_delay_ms >= 1,
+            "websocket.reconnect_delay_ms",
+            "must be at least 1ms when reconnects are enabled".to_string(),
+        );
+
+        // Runtime
+        let runtime = &self.runtime;
+        check(
+            runtime.worker_threads >= 1,
+            "runtime.worker_threads",
+            "must be at least 1".to_string(),
+        );
+        check(
+            runtime.max_blocking_threads >= 1,
+            "runtime.max_blocking_threads",
+            "must be at least 1".to_string(),
+        );
+        check(
+            runtime.thread_stack_size >= MIN_THREAD_STACK_SIZE,
+            "runtime.thread_stack_size",
+            format!("must be at least {} bytes", MIN_THREAD_STACK_SIZE),
+        );
+        check(
+            runtime.global_queue_interval >= 1,
+            "runtime.global_queue_interval",
+            "must be at least 1".to_string(),
+        );
+        check(
+            !runtime.enable_metrics || runtime.metrics_interval_ms >= 1,
+            "runtime.metrics_interval_ms",
+            "must be at least 1ms when runtime metrics are enabled".to_string(),
+        );
+
+        // Logging
+        let logging = &self.logging;
+        check(
+            matches!(
+                logging.level.to_lowercase().as_str(),
+                "trace" | "debug" | "info" | "warn" | "error"
+            ),
+            "logging.level",
+            format!("invalid log level {}", logging.level),
+        );
+        check(
+            matches!(logging.format.as_str(), "json" | "pretty" | "compact"),
+            "logging.format",
+            format!("must be json, pretty or compact, got {}", logging.format),
+        );
+        if logging.file_path.is_some() {
+            check(
+                logging.max_log_size_mb >= 1,
+                "logging.max_log_size_mb",
+                "must be at least 1 when logging to a file".to_string(),
+            );
+            check(
+                logging.max_log_files >= 1,
+                "logging.max_log_files",
+                "must be at least 1 when logging to a file".to_string(),
+            );
+        }
+        check(
+            (0.0..=1.0).contains(&logging.trace_sample_ratio),
+            "logging.trace_sample_ratio",
+            "must be between 0.0 and 1.0".to_string(),
+        );
+        if let Some(endpoint) = &logging.otlp_endpoint {
+            check(
+                endpoint.starts_with("http://") || endpoint.starts_with("https://"),
+                "logging.otlp_endpoint",
+                format!("{} is not an http(s) URL", endpoint),
+            );
+            check(
+                !logging.service_name.is_empty(),
+                "logging.service_name",
+                "must not be empty when exporting traces".to_string(),
+            );
+        }
+
+        // Security
+        let security = &self.security;
+        let pinning = &security.cert_pinning;
+        if security.enable_cert_pinning || http.enable_cert_pinning {
+            check(
+                !pinning.domain.is_empty(),
+                "security.cert_pinning.domain",
+                "must be set when certificate pinning is enabled".to_string(),
+            );
+            check(
+                !pinning.pins.is_empty(),
+                "security.cert_pinning.pins",
+                "must contain at least one pin when certificate pinning is enabled".to_string(),
+            );
+            check(
+                !pinning.enable_backup_pins || pinning.pins.len() >= 2,
+                "security.cert_pinning.pins",
+                "must contain a primary and at least one backup pin".to_string(),
+            );
+        }
+        for (index, pin) in pinning.pins.iter().enumerate() {
+            check(
+                is_sha256_pin(pin),
+                &format!("security.cert_pinning.pins[{}]", index),
+                "must be a SHA-256 hash (hex, or base64 with a sha256/ prefix)".to_string(),
+            );
+        }
+        check(
+            security.key_rotation_hours >= 1,
+            "security.key_rotation_hours",
+            "must be at least 1".to_string(),
+        );
+        if let Some(key) = &security.private_key {
+            let hex = key.expose().strip_prefix("0x").unwrap_or(key.expose());
+            check(
+                hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+                "security.private_key",
+                "must be a 32-byte hex private key".to_string(),
+            );
+        }
+        for (index, entry) in security.withdrawal_allowlist.iter().enumerate() {
+            check(
+                crate::exchange::WithdrawalAllowlist::new([entry], false).is_ok(),
+                &format!("security.withdrawal_allowlist[{}]", index),
+                "must be a 0x address, or a pattern of one using * and ?".to_string(),
+            );
+        }
+
+        // Metrics
+        let metrics = &self.metrics;
+        if metrics.enabled {
+            check(
+                metrics.endpoint.starts_with('/'),
+                "metrics.endpoint",
+                format!("must be a path starting with '/', got {}", metrics.endpoint),
+            );
+            check(
+                metrics.collection_interval_secs >= 1,
+                "metrics.collection_interval_secs",
+                "must be at least 1".to_string(),
+            );
+            check(
+                metrics.namespace.is_empty() || is_metric_name(&metrics.namespace),
+                "metrics.namespace",
+                format!("{} is not a valid Prometheus metric prefix", metrics.namespace),
+            );
+            if metrics.enable_prometheus {
+                check(
+                    metrics.listen_addr.parse::<std::net::SocketAddr>().is_ok(),
+                    "metrics.listen_addr",
+                    format!("{} is not a socket address", metrics.listen_addr),
+                );
             }
         }
 
-        Ok(())
+        // Strategy
+        let strategy = &self.strategy;
+        check(
+            strategy.timer_interval_ms >= 1,
+            "strategy.timer_interval_ms",
+            "must be at least 1ms".to_string(),
+        );
+        check(
+            strategy.coins.iter().all(|coin| !coin.is_empty()),
+            "strategy.coins",
+            "must not contain empty names".to_string(),
+        );
+
+        issues
     }
 
     /// Get the effective base URL (from env or environment config)
@@ -240,6 +573,48 @@ impl Config {
     }
 }
 
+/// Hyperliquid closes connections that send nothing for 60 seconds
+const MAX_WS_PING_INTERVAL_MS: u64 = 60_000;
+
+/// Smallest thread stack size accepted by validation
+const MIN_THREAD_STACK_SIZE: usize = 64 * 1024;
+
+/// A single problem found by [`Config::validate_all`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Dotted path of the offending field (e.g. `websocket.buffer_size`)
+    pub path: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Check `pin` is a hex SHA-256 hash or a `sha256/<base64>` pin
+fn is_sha256_pin(pin: &str) -> bool {
+    match pin.strip_prefix("sha256/") {
+        Some(encoded) => {
+            encoded.len() == 44
+                && encoded.ends_with('=')
+                && encoded[..43]
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        }
+        None => pin.len() == 64 && pin.chars().all(|c| c.is_ascii_hexdigit()),
+    }
+}
+
+/// Check `name` is a valid Prometheus metric name
+fn is_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
 /// Environment configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnvironmentConfig {
@@ -331,13 +706,17 @@ pub struct WebSocketConfig {
     #[serde(default = "default_buffer_size")]
     pub buffer_size: usize,
 
-    /// Enable compression
+    /// Negotiate permessage-deflate and inflate compressed messages
     #[serde(default)]
     pub enable_compression: bool,
 
     /// Ping interval in milliseconds
     #[serde(default = "default_ping_interval")]
     pub ping_interval_ms: u64,
+
+    /// Reader to router hand-off buffer (circular or lock-free SPSC)
+    #[serde(default)]
+    pub buffer_mode: crate::stream::BufferMode,
 }
 
 fn default_ws_timeout() -> u64 { 10000 }
@@ -355,6 +734,7 @@ impl Default for WebSocketConfig {
             buffer_size: default_buffer_size(),
             enable_compression: false,
             ping_interval_ms: default_ping_interval(),
+            buffer_mode: crate::stream::BufferMode::default(),
         }
     }
 }
@@ -389,6 +769,14 @@ pub struct RuntimeConfig {
     /// Shutdown timeout in seconds
     #[serde(default = "default_shutdown_timeout")]
     pub shutdown_timeout_secs: u64,
+
+    /// Publish tokio runtime metrics (queue depth, park counts, blocking pool)
+    #[serde(default)]
+    pub enable_metrics: bool,
+
+    /// Runtime metrics publish interval in milliseconds
+    #[serde(default = "default_runtime_metrics_interval")]
+    pub metrics_interval_ms: u64,
 }
 
 fn default_max_blocking_threads() -> usize { 512 }
@@ -397,6 +785,7 @@ fn default_enable_io() -> bool { true }
 fn default_enable_time() -> bool { true }
 fn default_global_queue_interval() -> u32 { 61 }
 fn default_shutdown_timeout() -> u64 { 30 }
+fn default_runtime_metrics_interval() -> u64 { 1000 }
 
 impl Default for RuntimeConfig {
     fn default() -> Self {
@@ -410,6 +799,8 @@ impl Default for RuntimeConfig {
             enable_time: default_enable_time(),
             global_queue_interval: default_global_queue_interval(),
             shutdown_timeout_secs: default_shutdown_timeout(),
+            enable_metrics: false,
+            metrics_interval_ms: default_runtime_metrics_interval(),
         }
     }
 }
@@ -440,6 +831,22 @@ pub struct LoggingConfig {
     /// Enable colored output
     #[serde(default = "default_colored_output")]
     pub colored_output: bool,
+
+    /// OTLP collector endpoint for span export (requires the `otel` feature)
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+
+    /// Service name reported with exported spans
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+
+    /// Fraction of new traces to sample (0.0 - 1.0)
+    #[serde(default = "default_trace_sample_ratio")]
+    pub trace_sample_ratio: f64,
+
+    /// Masking of private keys, signatures and addresses in log output
+    #[serde(default)]
+    pub redaction: crate::logging::RedactionConfig,
 }
 
 fn default_log_level() -> String { "info".to_string() }
@@ -447,6 +854,8 @@ fn default_log_format() -> String { "json".to_string() }
 fn default_max_log_size() -> u64 { 100 }
 fn default_max_log_files() -> u32 { 5 }
 fn default_colored_output() -> bool { true }
+fn default_service_name() -> String { "hyperliquid-rs".to_string() }
+fn default_trace_sample_ratio() -> f64 { 1.0 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
@@ -457,6 +866,10 @@ impl Default for LoggingConfig {
             max_log_size_mb: default_max_log_size(),
             max_log_files: default_max_log_files(),
             colored_output: default_colored_output(),
+            otlp_endpoint: None,
+            service_name: default_service_name(),
+            trace_sample_ratio: default_trace_sample_ratio(),
+            redaction: crate::logging::RedactionConfig::default(),
         }
     }
 }
@@ -483,6 +896,15 @@ pub struct SecurityConfig {
     /// Strict mode (fail on security warnings)
     #[serde(default)]
     pub strict_mode: bool,
+
+    /// Signing key (usually a secret reference such as `env:HL_KEY`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<Secret>,
+
+    /// Addresses or patterns (`*`, `?`) withdrawals and transfers may go to;
+    /// empty allows any destination
+    #[serde(default)]
+    pub withdrawal_allowlist: Vec<String>,
 }
 
 fn default_rotation_interval() -> u64 { 24 }
@@ -495,6 +917,8 @@ impl Default for SecurityConfig {
             key_rotation_hours: default_rotation_interval(),
             enable_request_signing: true,
             strict_mode: false,
+            private_key: None,
+            withdrawal_allowlist: Vec::new(),
         }
     }
 }
@@ -547,11 +971,16 @@ pub struct MetricsConfig {
     /// Custom metrics namespace
     #[serde(default = "default_metrics_namespace")]
     pub namespace: String,
+
+    /// Address the Prometheus endpoint listens on
+    #[serde(default = "default_prometheus_addr")]
+    pub listen_addr: String,
 }
 
 fn default_metrics_path() -> String { "/metrics".to_string() }
 fn default_metrics_interval() -> u64 { 10 }
 fn default_metrics_namespace() -> String { "hyperliquid".to_string() }
+fn default_prometheus_addr() -> String { "127.0.0.1:9090".to_string() }
 
 impl Default for MetricsConfig {
     fn default() -> Self {
@@ -561,6 +990,136 @@ impl Default for MetricsConfig {
             collection_interval_secs: default_metrics_interval(),
             enable_prometheus: true,
             namespace: default_metrics_namespace(),
+            listen_addr: default_prometheus_addr(),
+        }
+    }
+}
+
+/// Strategy runner configuration, see [`crate::runner`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyConfig {
+    /// Coins whose books are streamed to the strategy
+    #[serde(default)]
+    pub coins: Vec<String>,
+
+    /// Interval between `on_timer` calls in milliseconds
+    #[serde(default = "default_timer_interval")]
+    pub timer_interval_ms: u64,
+
+    /// Cancel the strategy's open orders on shutdown
+    #[serde(default = "default_cancel_on_shutdown")]
+    pub cancel_on_shutdown: bool,
+
+    /// Strategy-specific parameters
+    #[serde(default)]
+    pub params: toml::Table,
+}
+
+fn default_timer_interval() -> u64 { 1000 }
+fn default_cancel_on_shutdown() -> bool { true }
+
+impl Default for StrategyConfig {
+    fn default() -> Self {
+        Self {
+            coins: Vec::new(),
+            timer_interval_ms: default_timer_interval(),
+            cancel_on_shutdown: default_cancel_on_shutdown(),
+            params: toml::Table::new(),
+        }
+    }
+}
+
+impl StrategyConfig {
+    /// Deserialize the `[strategy.params]` table
+    pub fn params<T: serde::de::DeserializeOwned>(&self) -> Result<T, crate::error::HyperliquidError> {
+        toml::Value::Table(self.params.clone())
+            .try_into()
+            .map_err(|e| crate::error::HyperliquidError::Config(format!(
+                "Invalid strategy.params: {}",
+                e
+            )))
+    }
+}
+
+/// Read a TOML file and resolve its `include` list
+///
+/// `stack` holds the files currently being loaded, to reject include cycles.
+fn load_table(
+    path: &Path,
+    stack: &mut Vec<PathBuf>,
+) -> Result<toml::Table, crate::error::HyperliquidError> {
+    let canonical = fs::canonicalize(path)
+        .map_err(|e| crate::error::HyperliquidError::Config(format!(
+            "Failed to read config file {}: {}",
+            path.display(), e
+        )))?;
+    if stack.contains(&canonical) {
+        return Err(crate::error::HyperliquidError::Config(format!(
+            "Config include cycle at {}",
+            path.display()
+        )));
+    }
+
+    let toml_content = fs::read_to_string(&canonical)
+        .map_err(|e| crate::error::HyperliquidError::Config(format!(
+            "Failed to read config file {}: {}",
+            path.display(), e
+        )))?;
+    let mut table: toml::Table = toml::from_str(&toml_content)
+        .map_err(|e| crate::error::HyperliquidError::Config(format!(
+            "Failed to parse TOML config {}: {}",
+            path.display(), e
+        )))?;
+
+    let includes = match table.remove("include") {
+        None => Vec::new(),
+        Some(toml::Value::String(include)) => vec![include],
+        Some(toml::Value::Array(includes)) => includes
+            .into_iter()
+            .map(|value| match value {
+                toml::Value::String(include) => Ok(include),
+                other => Err(crate::error::HyperliquidError::Config(format!(
+                    "Invalid include entry {} in {}",
+                    other, path.display()
+                ))),
+            })
+            .collect::<Result<_, _>>()?,
+        Some(other) => {
+            return Err(crate::error::HyperliquidError::Config(format!(
+                "Invalid include {} in {}: expected a path or list of paths",
+                other, path.display()
+            )));
+        }
+    };
+
+    if includes.is_empty() {
+        return Ok(table);
+    }
+
+    // Included files form the base; this file overrides them
+    let base_dir = canonical.parent().map(Path::to_path_buf).unwrap_or_default();
+    stack.push(canonical);
+    let mut merged = toml::Table::new();
+    for include in includes {
+        let included = load_table(&base_dir.join(include), stack)?;
+        merge_tables(&mut merged, included);
+    }
+    stack.pop();
+
+    merge_tables(&mut merged, table);
+    Ok(merged)
+}
+
+/// Deep-merge `overlay` into `base`; nested tables merge, other values replace
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(overlay)) => {
+                merge_tables(existing, overlay);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
         }
     }
 }
@@ -589,6 +1148,41 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_validate_reports_all_issues() {
+        let mut config = Config::default();
+        config.http.request_timeout_ms = 500;
+        config.websocket.buffer_size = 10;
+        config.security.enable_cert_pinning = true;
+        config.security.cert_pinning.pins = vec!["not-a-hash".to_string()];
+        config.metrics.listen_addr = "nowhere".to_string();
+
+        let paths: Vec<String> = config.validate_all().into_iter().map(|i| i.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "http.request_timeout_ms",
+                "websocket.buffer_size",
+                "security.cert_pinning.domain",
+                "security.cert_pinning.pins[0]",
+                "metrics.listen_addr",
+            ]
+        );
+
+        let message = config.validate().unwrap_err().to_string();
+        assert!(message.contains("5 problems"));
+        assert!(message.contains("websocket.buffer_size: must be at least 30"));
+
+        config.security.cert_pinning.domain = "api.hyperliquid.xyz".to_string();
+        config.security.cert_pinning.pins = vec![
+            format!("sha256/{}=", "A".repeat(43)),
+            "ab".repeat(32),
+        ];
+        config.security.cert_pinning.enable_backup_pins = true;
+        let paths: Vec<String> = config.validate_all().into_iter().map(|i| i.path).collect();
+        assert!(!paths.iter().any(|p| p.starts_with("security")));
+    }
+
     #[test]
     fn test_env_overrides() {
         env::set_var("HYPERLIQUID_ENV", "testnet");
@@ -644,4 +1238,70 @@ mod tests {
         // Clean up
         fs::remove_file("test_config.toml").unwrap();
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_config_include_and_profiles() {
+        let dir = env::temp_dir().join(format!("hyperliquid-config-{}", std::process::id()));
+        fs::create_dir_all(dir.join("shared")).unwrap();
+
+        fs::write(dir.join("shared/base.toml"), r#"
+            [http]
+            request_timeout_ms = 10000
+            max_connections_per_host = 8
+
+            [logging]
+            level = "warn"
+
+            [profile.staging.environment]
+            env = "testnet"
+        "#).unwrap();
+
+        fs::write(dir.join("app.toml"), r#"
+            include = ["shared/base.toml"]
+
+            [http]
+            request_timeout_ms = 20000
+
+            [profile.prod.http]
+            request_timeout_ms = 5000
+
+            [profile.prod.logging]
+            level = "error"
+        "#).unwrap();
+
+        let path = dir.join("app.toml");
+
+        let config = Config::load_with_profile(&path, None).unwrap();
+        assert_eq!(config.http.request_timeout_ms, 20000);
+        assert_eq!(config.http.max_connections_per_host, 8);
+        assert_eq!(config.logging.level, "warn");
+        assert!(config.profile.is_none());
+
+        let config = Config::load_with_profile(&path, Some("prod")).unwrap();
+        assert_eq!(config.http.request_timeout_ms, 5000);
+        assert_eq!(config.http.max_connections_per_host, 8);
+        assert_eq!(config.logging.level, "error");
+        assert_eq!(config.profile.as_deref(), Some("prod"));
+
+        // Profiles defined in an included file are available too
+        let config = Config::load_with_profile(&path, Some("staging")).unwrap();
+        assert_eq!(config.environment.env, crate::types::Environment::Testnet);
+
+        assert!(Config::load_with_profile(&path, Some("missing")).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_include_cycle() {
+        let dir = env::temp_dir().join(format!("hyperliquid-config-cycle-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.toml"), "include = [\"b.toml\"]").unwrap();
+        fs::write(dir.join("b.toml"), "include = [\"a.toml\"]").unwrap();
+
+        let err = Config::load_with_profile(dir.join("a.toml"), None).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
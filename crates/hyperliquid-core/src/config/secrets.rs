@@ -0,0 +1,371 @@
+//! Secret references in configuration values
+//!
+//! Any string value in a config file of the form `<scheme>:<reference>` whose
+//! scheme has a registered [`SecretsProvider`] is replaced with the resolved
+//! secret at load time:
+//!
+//! ```toml
+//! [security]
+//! private_key = "env:HL_KEY"
+//! # private_key = "file:/run/secrets/hl_key"
+//! # private_key = "vault:kv/hl#key"
+//! ```
+//!
+//! Strings with an unregistered scheme (e.g. `https://...`) are left as-is.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::HyperliquidError;
+
+/// Source of secret values
+pub trait SecretsProvider: Send + Sync {
+    /// Scheme this provider handles, without the trailing `:` (e.g. `"env"`)
+    fn scheme(&self) -> &str;
+
+    /// Resolve the part of the reference after `<scheme>:`
+    fn resolve(&self, reference: &str) -> Result<String, HyperliquidError>;
+}
+
+/// Reads secrets from environment variables (`env:VAR`)
+#[derive(Debug, Clone, Default)]
+pub struct EnvSecretsProvider;
+
+impl SecretsProvider for EnvSecretsProvider {
+    fn scheme(&self) -> &str {
+        "env"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String, HyperliquidError> {
+        std::env::var(reference).map_err(|_| {
+            HyperliquidError::Config(format!("environment variable {} is not set", reference))
+        })
+    }
+}
+
+/// Reads secrets from files, trimming surrounding whitespace (`file:/path`)
+#[derive(Debug, Clone, Default)]
+pub struct FileSecretsProvider;
+
+impl SecretsProvider for FileSecretsProvider {
+    fn scheme(&self) -> &str {
+        "file"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String, HyperliquidError> {
+        std::fs::read_to_string(reference)
+            .map(|contents| contents.trim().to_string())
+            .map_err(|e| {
+                HyperliquidError::Config(format!("failed to read secret file {}: {}", reference, e))
+            })
+    }
+}
+
+/// Reads secrets from a HashiCorp Vault KV v2 engine (`vault:<mount>/<path>#<field>`)
+///
+/// Uses a blocking HTTP client, so configs with vault references must be
+/// loaded outside of an async context (or via `spawn_blocking`).
+#[derive(Clone)]
+pub struct VaultSecretsProvider {
+    addr: Option<String>,
+    token: Option<String>,
+    timeout: Duration,
+}
+
+impl VaultSecretsProvider {
+    /// Create a provider for the Vault server at `addr`
+    pub fn new(addr: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            addr: Some(addr.into()),
+            token: Some(token.into()),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Create a provider from `VAULT_ADDR` and `VAULT_TOKEN`
+    ///
+    /// Missing variables only cause an error once a vault reference is resolved.
+    pub fn from_env() -> Self {
+        Self {
+            addr: std::env::var("VAULT_ADDR").ok(),
+            token: std::env::var("VAULT_TOKEN").ok(),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Set the request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl std::fmt::Debug for VaultSecretsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultSecretsProvider")
+            .field("addr", &self.addr)
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl SecretsProvider for VaultSecretsProvider {
+    fn scheme(&self) -> &str {
+        "vault"
+    }
+
+    fn resolve(&self, reference: &str) -> Result<String, HyperliquidError> {
+        let (mount, path, field) = parse_vault_reference(reference)?;
+        let (addr, token) = match (&self.addr, &self.token) {
+            (Some(addr), Some(token)) => (addr, token),
+            _ => {
+                return Err(HyperliquidError::Config(
+                    "vault secret referenced but VAULT_ADDR/VAULT_TOKEN are not set".to_string(),
+                ))
+            }
+        };
+
+        let url = format!("{}/v1/{}/data/{}", addr.trim_end_matches('/'), mount, path);
+        let response = reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .build()
+            .and_then(|client| client.get(&url).header("X-Vault-Token", token).send())
+            .map_err(|e| HyperliquidError::Config(format!("vault request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(HyperliquidError::Config(format!(
+                "vault returned {} for {}/{}",
+                status, mount, path
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .map_err(|e| HyperliquidError::Config(format!("invalid vault response: {}", e)))?;
+        body.pointer(&format!("/data/data/{}", field))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                HyperliquidError::Config(format!(
+                    "vault secret {}/{} has no string field {}",
+                    mount, path, field
+                ))
+            })
+    }
+}
+
+/// Split `kv/hl#key` into mount, path and field
+fn parse_vault_reference(reference: &str) -> Result<(&str, &str, &str), HyperliquidError> {
+    let invalid = || {
+        HyperliquidError::Config(format!(
+            "invalid vault reference {} (expected <mount>/<path>#<field>)",
+            reference
+        ))
+    };
+
+    let (location, field) = reference.split_once('#').ok_or_else(invalid)?;
+    let (mount, path) = location.split_once('/').ok_or_else(invalid)?;
+    if mount.is_empty() || path.is_empty() || field.is_empty() {
+        return Err(invalid());
+    }
+    Ok((mount, path, field))
+}
+
+/// Registry of secrets providers keyed by scheme
+#[derive(Clone)]
+pub struct SecretsResolver {
+    providers: HashMap<String, Arc<dyn SecretsProvider>>,
+}
+
+impl SecretsResolver {
+    /// Create a resolver with no providers (secret references are left as-is)
+    pub fn new() -> Self {
+        Self {
+            providers: HashMap::new(),
+        }
+    }
+
+    /// Register a provider, replacing any existing provider for its scheme
+    pub fn with_provider(mut self, provider: impl SecretsProvider + 'static) -> Self {
+        self.providers
+            .insert(provider.scheme().to_string(), Arc::new(provider));
+        self
+    }
+
+    /// Resolve `value` if it references a registered scheme
+    ///
+    /// Returns `Ok(None)` for plain values.
+    pub fn resolve(&self, value: &str) -> Result<Option<String>, HyperliquidError> {
+        let Some((scheme, reference)) = value.split_once(':') else {
+            return Ok(None);
+        };
+        match self.providers.get(scheme) {
+            Some(provider) => provider.resolve(reference).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Replace every secret reference in a parsed config table
+    ///
+    /// Errors name the field that failed to resolve.
+    pub(crate) fn resolve_table(&self, table: &mut toml::Table) -> Result<(), HyperliquidError> {
+        if self.providers.is_empty() {
+            return Ok(());
+        }
+        for (key, value) in table.iter_mut() {
+            self.resolve_value(key, value)?;
+        }
+        Ok(())
+    }
+
+    fn resolve_value(&self, path: &str, value: &mut toml::Value) -> Result<(), HyperliquidError> {
+        match value {
+            toml::Value::String(s) => {
+                if let Some(resolved) = self.resolve(s).map_err(|e| {
+                    HyperliquidError::Config(format!(
+                        "failed to resolve secret for {}: {}",
+                        path, e
+                    ))
+                })? {
+                    *s = resolved;
+                }
+            }
+            toml::Value::Table(table) => {
+                for (key, value) in table.iter_mut() {
+                    self.resolve_value(&format!("{}.{}", path, key), value)?;
+                }
+            }
+            toml::Value::Array(items) => {
+                for (index, value) in items.iter_mut().enumerate() {
+                    self.resolve_value(&format!("{}[{}]", path, index), value)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl Default for SecretsResolver {
+    /// Resolver with the `env`, `file` and `vault` providers
+    fn default() -> Self {
+        Self::new()
+            .with_provider(EnvSecretsProvider)
+            .with_provider(FileSecretsProvider)
+            .with_provider(VaultSecretsProvider::from_env())
+    }
+}
+
+impl std::fmt::Debug for SecretsResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut schemes: Vec<&str> = self.providers.keys().map(String::as_str).collect();
+        schemes.sort_unstable();
+        f.debug_struct("SecretsResolver")
+            .field("schemes", &schemes)
+            .finish()
+    }
+}
+
+/// Secret string that is never printed by `Debug` or `Display`
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Wrap a secret value
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Access the secret value
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StaticProvider;
+
+    impl SecretsProvider for StaticProvider {
+        fn scheme(&self) -> &str {
+            "static"
+        }
+
+        fn resolve(&self, reference: &str) -> Result<String, HyperliquidError> {
+            match reference {
+                "key" => Ok("0xabc".to_string()),
+                _ => Err(HyperliquidError::Config(format!("no secret {}", reference))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_table() {
+        let resolver = SecretsResolver::new().with_provider(StaticProvider);
+        let mut table: toml::Table = toml::from_str(
+            r#"
+            url = "https://api.hyperliquid.xyz"
+            [security]
+            private_key = "static:key"
+            pins = ["static:key", "plain"]
+            "#,
+        )
+        .unwrap();
+
+        resolver.resolve_table(&mut table).unwrap();
+        assert_eq!(table["url"].as_str(), Some("https://api.hyperliquid.xyz"));
+        assert_eq!(table["security"]["private_key"].as_str(), Some("0xabc"));
+        assert_eq!(table["security"]["pins"][0].as_str(), Some("0xabc"));
+        assert_eq!(table["security"]["pins"][1].as_str(), Some("plain"));
+
+        let mut table: toml::Table =
+            toml::from_str("[security]\nprivate_key = \"static:missing\"").unwrap();
+        let err = resolver.resolve_table(&mut table).unwrap_err();
+        assert!(err.to_string().contains("security.private_key"));
+    }
+
+    #[test]
+    fn test_vault_reference() {
+        assert_eq!(
+            parse_vault_reference("kv/trading/hl#key").unwrap(),
+            ("kv", "trading/hl", "key")
+        );
+        assert!(parse_vault_reference("kv/hl").is_err());
+        assert!(parse_vault_reference("kv#key").is_err());
+
+        let provider = VaultSecretsProvider {
+            addr: None,
+            token: None,
+            timeout: Duration::from_secs(1),
+        };
+        assert!(provider.resolve("kv/hl#key").is_err());
+    }
+
+    #[test]
+    fn test_secret_is_not_printed() {
+        let secret = Secret::new("0xdeadbeef");
+        assert_eq!(secret.expose(), "0xdeadbeef");
+        assert!(!format!("{:?}", secret).contains("dead"));
+        assert!(!secret.to_string().contains("dead"));
+    }
+}
@@ -14,4 +14,4 @@ pub use types::{
     EIP712Domain, EIP712Type, PhantomAgent, EIP712Message, Signature, Environment,
     action_types, MultiSigEnvelope, MultiSigUser, MultiSigSignature,
 };
-pub use nonce::{generate_nonce, generate_timestamp_nonce, NonceGenerator, PrivateKeySecure};
\ No newline at end of file
+pub use nonce::{generate_nonce, generate_timestamp_nonce, NonceGenerator, NonceManager, PrivateKeySecure};
\ No newline at end of file
@@ -9,10 +9,15 @@ extern crate libc;
 use chrono::{DateTime, Utc};
 use rand::Rng;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::ptr;
 use std::alloc::{alloc, dealloc, Layout};
 
+use HyperliquidError;
+use crate::clock::{self, Clock};
+use crate::store::{self, StateStore};
+
 /// Generate a unique nonce for cryptographic operations
 ///
 /// A nonce is a number used once to ensure that each operation is unique and
@@ -132,6 +137,74 @@ impl NonceGenerator {
     }
 }
 
+/// Store key prefix of the last nonce per signer
+const NONCE_PREFIX: &str = "nonce/";
+
+/// Strictly increasing millisecond nonces for one signer
+///
+/// Each nonce is the current time in milliseconds, or one more than the last
+/// nonce if the clock hasn't moved past it. With a
+/// [`StateStore`] attached the last nonce is
+/// written through, so a restarted process never reuses a nonce even if the
+/// clock stepped backwards.
+#[derive(Debug)]
+pub struct NonceManager {
+    last: Mutex<u64>,
+    store: Option<(Arc<dyn StateStore>, String)>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NonceManager {
+    pub fn new() -> Self {
+        Self {
+            last: Mutex::new(0),
+            store: None,
+            clock: clock::system(),
+        }
+    }
+
+    /// Read the time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Persist nonces for `signer` in `store`, resuming after the last one
+    pub fn with_store(
+        mut self,
+        store: Arc<dyn StateStore>,
+        signer: &str,
+    ) -> Result<Self, HyperliquidError> {
+        let key = format!("{}{}", NONCE_PREFIX, signer.to_lowercase());
+        let last: Option<u64> = store::get_json(store.as_ref(), &key)?;
+        *self.last.get_mut().unwrap_or_else(|e| e.into_inner()) = last.unwrap_or(0);
+        self.store = Some((store, key));
+        Ok(self)
+    }
+
+    /// Next nonce, recorded before it is returned
+    pub fn next(&self) -> Result<u64, HyperliquidError> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+        let nonce = self.clock.now_ms().max(*last + 1);
+        if let Some((store, key)) = &self.store {
+            store::put_json(store.as_ref(), key, &nonce)?;
+        }
+        *last = nonce;
+        Ok(nonce)
+    }
+
+    /// Last nonce handed out (0 if none)
+    pub fn last(&self) -> u64 {
+        *self.last.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
 /// Verify that a nonce is not too old (potential replay attack prevention)
 ///
 /// # Arguments
@@ -153,11 +226,13 @@ impl NonceGenerator {
 /// assert!(is_recent);
 /// ```
 pub fn verify_nonce_age(nonce: u64, max_age_seconds: Option<u64>) -> bool {
+    verify_nonce_age_at(&clock::SystemClock, nonce, max_age_seconds)
+}
+
+/// [`verify_nonce_age`] against the time of `clock`
+pub fn verify_nonce_age_at(clock: &dyn Clock, nonce: u64, max_age_seconds: Option<u64>) -> bool {
     let max_age = max_age_seconds.unwrap_or(300); // Default 5 minutes
-    let current_time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("System time is before Unix epoch")
-        .as_secs();
+    let current_time = clock.now_ms() / 1000;
 
     // Extract timestamp from nonce (assuming it's in the upper bits)
     // For timestamp-based nonces, we can check the age
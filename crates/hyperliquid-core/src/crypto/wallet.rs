@@ -4,7 +4,8 @@ use crate::crypto::signing::{sign_l1_action, sign_user_signed_action};
 use crate::crypto::types::*;
 use crate::crypto::nonce::{generate_nonce, generate_timestamp_nonce};
 use crate::error::HyperliquidError;
-use k256::ecdsa::{SigningKey, VerifyingKey};
+use crate::types::Address;
+use k256::ecdsa::SigningKey;
 use serde_json::Value;
 use std::str::FromStr;
 
@@ -55,17 +56,7 @@ impl PrivateKey {
 
     /// Get the public address (20 bytes)
     pub fn address(&self) -> String {
-        let verifying_key = VerifyingKey::from(&self.inner);
-        let pub_key_bytes = verifying_key.to_sec1_bytes();
-
-        // Take the keccak256 hash of the public key (excluding the 0x04 prefix)
-        let mut hasher = k256::sha3::Keccak256::new();
-        hasher.update(&pub_key_bytes[1..]); // Skip the 0x04 prefix
-        let hash = hasher.finalize();
-
-        // Take the last 20 bytes
-        let address_bytes = &hash[12..];
-        format!("0x{}", hex::encode(address_bytes))
+        Address::from_private_key(&self.inner).to_hex()
     }
 
     /// Get the inner signing key (for advanced usage)
@@ -0,0 +1,667 @@
+//! Downloader for Hyperliquid's S3 data archives
+//!
+//! Hyperliquid publishes hourly L2 book snapshots and daily asset contexts in
+//! the `hyperliquid-archive` bucket, and node trade logs in
+//! `hl-mainnet-node-data`. Both are requester-pays: requests are signed with
+//! the caller's AWS credentials and transfer is billed to them.
+//!
+//! [`ArchiveDownloader::download`] fetches the objects for a date range,
+//! decompresses them (lz4) and writes them in the local recording format
+//! described in [`crate::data`]. Partial downloads are kept as `.part` files
+//! and continued with a range request, and objects whose output already
+//! exists are skipped, so an interrupted run can simply be restarted. Hours
+//! the archive doesn't have are reported as missing rather than failing.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use chrono::NaiveDate;
+//! use hyperliquid_core::data::{ArchiveDownloader, ArchiveKind, DateRange, S3Credentials};
+//!
+//! let range = DateRange::new(
+//!     NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+//!     NaiveDate::from_ymd_opt(2024, 3, 7).unwrap(),
+//! )?;
+//! let report = ArchiveDownloader::new("data")
+//!     .with_credentials(S3Credentials::from_env())
+//!     .download(&range, &[ArchiveKind::L2Book, ArchiveKind::AssetCtxs], &["BTC", "ETH"])
+//!     .await?;
+//! println!("{:?}", report);
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, warn};
+
+use crate::data::s3::{self, S3Credentials, UNSIGNED_PAYLOAD};
+use crate::error::HyperliquidError;
+
+/// Bucket with L2 snapshots and asset contexts
+pub const MARKET_DATA_BUCKET: &str = "hyperliquid-archive";
+/// Bucket with node trade logs
+pub const NODE_DATA_BUCKET: &str = "hl-mainnet-node-data";
+/// Region of the archive buckets
+pub const DEFAULT_REGION: &str = "ap-northeast-1";
+
+const DEFAULT_CONCURRENCY: usize = 4;
+const HOURS_PER_DAY: u32 = 24;
+
+/// Archive dataset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArchiveKind {
+    /// Hourly L2 book snapshots, one object per coin
+    L2Book,
+    /// Hourly node trade logs covering all coins
+    Trades,
+    /// Daily asset context CSV covering all coins
+    AssetCtxs,
+}
+
+/// Inclusive range of UTC dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateRange {
+    start: NaiveDate,
+    end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Result<Self, HyperliquidError> {
+        if end < start {
+            return Err(HyperliquidError::Validation(format!(
+                "date range ends ({}) before it starts ({})",
+                end, start
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    pub fn day(date: NaiveDate) -> Self {
+        Self {
+            start: date,
+            end: date,
+        }
+    }
+
+    pub fn start(&self) -> NaiveDate {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveDate {
+        self.end
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = NaiveDate> {
+        let end = self.end;
+        self.start.iter_days().take_while(move |day| *day <= end)
+    }
+}
+
+/// One archive object and where its conversion is written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveObject {
+    pub kind: ArchiveKind,
+    pub bucket: String,
+    pub key: String,
+    pub date: NaiveDate,
+    pub hour: Option<u32>,
+    pub output: PathBuf,
+}
+
+/// Outcome of a download run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadReport {
+    /// Objects fetched and converted
+    pub downloaded: usize,
+    /// Objects whose output already existed
+    pub skipped: usize,
+    /// Keys the archive doesn't have
+    pub missing: Vec<String>,
+    /// Messages written by this run
+    pub messages: usize,
+}
+
+enum Outcome {
+    Converted(usize),
+    Skipped,
+    Missing(String),
+}
+
+/// Fetches archive objects and converts them to local recordings
+#[derive(Debug, Clone)]
+pub struct ArchiveDownloader {
+    root: PathBuf,
+    cache_dir: PathBuf,
+    client: reqwest::Client,
+    credentials: Option<S3Credentials>,
+    region: String,
+    endpoint: Option<String>,
+    concurrency: usize,
+    keep_archives: bool,
+}
+
+impl ArchiveDownloader {
+    /// Write recordings under `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            cache_dir: root.join(".archive"),
+            root,
+            client: reqwest::Client::new(),
+            credentials: None,
+            region: DEFAULT_REGION.to_string(),
+            endpoint: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            keep_archives: false,
+        }
+    }
+
+    /// Sign requests; without credentials requests are sent unsigned, which
+    /// only works against a mirror set with [`with_endpoint`](Self::with_endpoint)
+    pub fn with_credentials(mut self, credentials: Option<S3Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    pub fn with_region(mut self, region: impl Into<String>) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    /// Fetch from an S3-compatible endpoint or mirror using path-style URLs
+    pub fn with_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.endpoint = Some(endpoint.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Keep raw and partial downloads in `cache_dir` instead of `<root>/.archive`
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Number of objects fetched at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Keep the compressed archives after conversion
+    pub fn with_keep_archives(mut self, keep: bool) -> Self {
+        self.keep_archives = keep;
+        self
+    }
+
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Archive objects covering `range` for `kinds`
+    ///
+    /// L2 books are stored per coin, so [`ArchiveKind::L2Book`] needs at least
+    /// one coin; the other kinds cover every coin and are filtered on
+    /// conversion.
+    pub fn objects(
+        &self,
+        range: &DateRange,
+        kinds: &[ArchiveKind],
+        coins: &[&str],
+    ) -> Result<Vec<ArchiveObject>, HyperliquidError> {
+        if kinds.contains(&ArchiveKind::L2Book) && coins.is_empty() {
+            return Err(HyperliquidError::Validation(
+                "L2 book archives are per coin; name at least one coin".to_string(),
+            ));
+        }
+
+        let mut objects = Vec::new();
+        for date in range.days() {
+            let day = date.format("%Y%m%d").to_string();
+            for kind in kinds {
+                match kind {
+                    ArchiveKind::L2Book => {
+                        for hour in 0..HOURS_PER_DAY {
+                            for coin in coins {
+                                objects.push(ArchiveObject {
+                                    kind: *kind,
+                                    bucket: MARKET_DATA_BUCKET.to_string(),
+                                    key: format!(
+                                        "market_data/{}/{}/l2Book/{}.lz4",
+                                        day, hour, coin
+                                    ),
+                                    date,
+                                    hour: Some(hour),
+                                    output: self
+                                        .root
+                                        .join("l2Book")
+                                        .join(coin)
+                                        .join(date.to_string())
+                                        .join(format!("{:02}.jsonl", hour)),
+                                });
+                            }
+                        }
+                    }
+                    ArchiveKind::Trades => {
+                        for hour in 0..HOURS_PER_DAY {
+                            objects.push(ArchiveObject {
+                                kind: *kind,
+                                bucket: NODE_DATA_BUCKET.to_string(),
+                                key: format!("node_trades/hourly/{}/{}", day, hour),
+                                date,
+                                hour: Some(hour),
+                                output: self
+                                    .root
+                                    .join("trades")
+                                    .join(date.to_string())
+                                    .join(format!("{:02}.jsonl", hour)),
+                            });
+                        }
+                    }
+                    ArchiveKind::AssetCtxs => objects.push(ArchiveObject {
+                        kind: *kind,
+                        bucket: MARKET_DATA_BUCKET.to_string(),
+                        key: format!("asset_ctxs/{}.csv.lz4", day),
+                        date,
+                        hour: None,
+                        output: self
+                            .root
+                            .join("activeAssetCtx")
+                            .join(format!("{}.jsonl", date)),
+                    }),
+                }
+            }
+        }
+        Ok(objects)
+    }
+
+    /// Download and convert every object for `range`
+    pub async fn download(
+        &self,
+        range: &DateRange,
+        kinds: &[ArchiveKind],
+        coins: &[&str],
+    ) -> Result<DownloadReport, HyperliquidError> {
+        let objects = self.objects(range, kinds, coins)?;
+        let coins: Vec<String> = coins.iter().map(|c| c.to_string()).collect();
+
+        let outcomes: Vec<Result<Outcome, HyperliquidError>> = futures::stream::iter(objects)
+            .map(|object| {
+                let coins = coins.clone();
+                async move { self.process(object, coins).await }
+            })
+            .buffer_unordered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut report = DownloadReport::default();
+        for outcome in outcomes {
+            match outcome? {
+                Outcome::Converted(messages) => {
+                    report.downloaded += 1;
+                    report.messages += messages;
+                }
+                Outcome::Skipped => report.skipped += 1,
+                Outcome::Missing(key) => report.missing.push(key),
+            }
+        }
+        report.missing.sort();
+        Ok(report)
+    }
+
+    async fn process(
+        &self,
+        object: ArchiveObject,
+        coins: Vec<String>,
+    ) -> Result<Outcome, HyperliquidError> {
+        if object.output.exists() {
+            return Ok(Outcome::Skipped);
+        }
+        let Some(archive) = self.fetch(&object).await? else {
+            return Ok(Outcome::Missing(format!(
+                "s3://{}/{}",
+                object.bucket, object.key
+            )));
+        };
+
+        let output = object.output.clone();
+        let kind = object.kind;
+        let source = archive.clone();
+        let messages =
+            tokio::task::spawn_blocking(move || convert_file(kind, &source, &output, &coins))
+                .await
+                .map_err(|e| {
+                    HyperliquidError::Unknown(format!("conversion task failed: {}", e))
+                })??;
+
+        if !self.keep_archives {
+            let _ = tokio::fs::remove_file(&archive).await;
+        }
+        debug!(key = %object.key, messages, "Converted archive object");
+        Ok(Outcome::Converted(messages))
+    }
+
+    /// Download `object` into the cache, resuming a partial download
+    ///
+    /// Returns `None` if the archive has no such object.
+    pub async fn fetch(&self, object: &ArchiveObject) -> Result<Option<PathBuf>, HyperliquidError> {
+        let path = self.cache_dir.join(&object.bucket).join(&object.key);
+        if path.exists() {
+            return Ok(Some(path));
+        }
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let part = PathBuf::from(format!("{}.part", path.display()));
+        let offset = tokio::fs::metadata(&part)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let key = s3::encode_key(&object.key);
+        let (url, host, canonical_path) = match &self.endpoint {
+            Some(endpoint) => {
+                let parsed = reqwest::Url::parse(endpoint)
+                    .map_err(|e| HyperliquidError::InvalidUrl(format!("{}: {}", endpoint, e)))?;
+                let host = match (parsed.host_str(), parsed.port()) {
+                    (Some(host), Some(port)) => format!("{}:{}", host, port),
+                    (Some(host), None) => host.to_string(),
+                    (None, _) => return Err(HyperliquidError::InvalidUrl(endpoint.clone())),
+                };
+                let path = format!("/{}/{}", object.bucket, key);
+                (format!("{}{}", endpoint, path), host, path)
+            }
+            None => {
+                let host = format!("{}.s3.{}.amazonaws.com", object.bucket, self.region);
+                let path = format!("/{}", key);
+                (format!("https://{}{}", host, path), host, path)
+            }
+        };
+
+        let mut headers: Vec<(&str, String)> = vec![
+            ("host", host),
+            ("x-amz-content-sha256", UNSIGNED_PAYLOAD.to_string()),
+            (
+                "x-amz-date",
+                chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+            ),
+        ];
+        if offset > 0 {
+            headers.push(("range", format!("bytes={}-", offset)));
+        }
+        let mut request = self.client.get(&url);
+        if let Some(credentials) = &self.credentials {
+            headers.push(("x-amz-request-payer", "requester".to_string()));
+            if let Some(token) = &credentials.session_token {
+                headers.push(("x-amz-security-token", token.clone()));
+            }
+            request = request.header(
+                "authorization",
+                s3::authorization(credentials, &self.region, &canonical_path, &headers),
+            );
+        }
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, value);
+        }
+
+        let mut response = request.send().await?;
+        let status = response.status();
+        match status.as_u16() {
+            404 => return Ok(None),
+            416 if offset > 0 => {
+                tokio::fs::rename(&part, &path).await?;
+                return Ok(Some(path));
+            }
+            403 => {
+                return Err(HyperliquidError::Authentication(format!(
+                    "access denied to s3://{}/{}; the archive buckets are requester-pays and need AWS credentials",
+                    object.bucket, object.key
+                )))
+            }
+            _ if !status.is_success() => {
+                return Err(HyperliquidError::Http {
+                    status,
+                    message: format!("failed to fetch s3://{}/{}", object.bucket, object.key),
+                    cause: None,
+                })
+            }
+            _ => {}
+        }
+
+        // A 200 means the server ignored the range; start over
+        let append = status.as_u16() == 206;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&part)
+            .await?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        drop(file);
+        tokio::fs::rename(&part, &path).await?;
+        Ok(Some(path))
+    }
+}
+
+/// Convert a downloaded archive into a recording at `output`
+///
+/// Writes to a temporary file first so a crash never leaves a truncated
+/// recording that a later run would skip.
+fn convert_file(
+    kind: ArchiveKind,
+    archive: &Path,
+    output: &Path,
+    coins: &[String],
+) -> Result<usize, HyperliquidError> {
+    let file = File::open(archive)?;
+    let input: Box<dyn Read> = if archive.extension().is_some_and(|ext| ext == "lz4") {
+        Box::new(lz4_flex::frame::FrameDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    let input = BufReader::new(input);
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let temp = PathBuf::from(format!("{}.tmp", output.display()));
+    let mut writer = BufWriter::new(File::create(&temp)?);
+    let messages = match kind {
+        ArchiveKind::L2Book => convert_l2_book(input, &mut writer)?,
+        ArchiveKind::Trades => convert_trades(input, coins, &mut writer)?,
+        ArchiveKind::AssetCtxs => convert_asset_ctxs(input, coins, &mut writer)?,
+    };
+    writer.flush()?;
+    drop(writer);
+    std::fs::rename(&temp, output)?;
+    Ok(messages)
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchivedBook {
+    raw: Value,
+}
+
+/// Convert an hourly L2 archive (`{"time", "ver_num", "raw"}` lines)
+///
+/// Each `raw` entry is already an `l2Book` websocket message.
+pub fn convert_l2_book(
+    input: impl BufRead,
+    mut output: impl Write,
+) -> Result<usize, HyperliquidError> {
+    let mut messages = 0;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<ArchivedBook>(&line) {
+            Ok(book) => {
+                serde_json::to_writer(&mut output, &book.raw)?;
+                output.write_all(b"\n")?;
+                messages += 1;
+            }
+            Err(e) => warn!("Skipping malformed L2 archive line: {}", e),
+        }
+    }
+    Ok(messages)
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchivedTrade {
+    coin: String,
+    side: String,
+    time: String,
+    px: String,
+    sz: String,
+    #[serde(default)]
+    hash: Option<String>,
+    #[serde(default)]
+    tid: Option<u64>,
+}
+
+/// Convert an hourly node trade log, keeping `coins` (all if empty)
+///
+/// Each trade becomes a `trades` message with its time in ms.
+pub fn convert_trades(
+    input: impl BufRead,
+    coins: &[String],
+    mut output: impl Write,
+) -> Result<usize, HyperliquidError> {
+    let mut messages = 0;
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let trade = match serde_json::from_str::<ArchivedTrade>(&line) {
+            Ok(trade) => trade,
+            Err(e) => {
+                warn!("Skipping malformed trade archive line: {}", e);
+                continue;
+            }
+        };
+        if !coins.is_empty() && !coins.contains(&trade.coin) {
+            continue;
+        }
+        let Some(time) = parse_time(&trade.time) else {
+            warn!("Skipping trade with unparseable time {}", trade.time);
+            continue;
+        };
+        let message = json!({
+            "channel": "trades",
+            "data": [{
+                "coin": trade.coin,
+                "side": trade.side,
+                "px": trade.px,
+                "sz": trade.sz,
+                "time": time,
+                "hash": trade.hash,
+                "tid": trade.tid,
+            }],
+        });
+        serde_json::to_writer(&mut output, &message)?;
+        output.write_all(b"\n")?;
+        messages += 1;
+    }
+    Ok(messages)
+}
+
+/// Convert a daily asset context CSV, keeping `coins` (all if empty)
+///
+/// Each row becomes an `activeAssetCtx` message with the row's time in ms.
+pub fn convert_asset_ctxs(
+    input: impl BufRead,
+    coins: &[String],
+    mut output: impl Write,
+) -> Result<usize, HyperliquidError> {
+    let mut lines = input.lines();
+    let Some(header) = lines.next().transpose()? else {
+        return Ok(0);
+    };
+    let columns: HashMap<&str, usize> = header
+        .split(',')
+        .enumerate()
+        .map(|(index, name)| (name.trim(), index))
+        .collect();
+    let column = |name: &str| {
+        columns.get(name).copied().ok_or_else(|| {
+            HyperliquidError::Validation(format!("asset context archive has no {} column", name))
+        })
+    };
+    let time_col = column("time")?;
+    let coin_col = column("coin")?;
+    let fields = [
+        ("funding", "funding"),
+        ("openInterest", "open_interest"),
+        ("prevDayPx", "prev_day_px"),
+        ("dayNtlVlm", "day_ntl_vlm"),
+        ("premium", "premium"),
+        ("oraclePx", "oracle_px"),
+        ("markPx", "mark_px"),
+        ("midPx", "mid_px"),
+    ];
+
+    let mut messages = 0;
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row: Vec<&str> = line.split(',').map(str::trim).collect();
+        let cell = |index: usize| row.get(index).copied().filter(|v| !v.is_empty());
+        let Some(coin) = cell(coin_col) else {
+            continue;
+        };
+        if !coins.is_empty() && !coins.iter().any(|c| c == coin) {
+            continue;
+        }
+        let Some(time) = cell(time_col).and_then(parse_time) else {
+            warn!("Skipping asset context row with unparseable time: {}", line);
+            continue;
+        };
+
+        let mut ctx = serde_json::Map::new();
+        for (field, name) in fields {
+            if let Some(value) = columns.get(name).and_then(|i| cell(*i)) {
+                ctx.insert(field.to_string(), json!(value));
+            }
+        }
+        let impact = |name: &str| columns.get(name).and_then(|i| cell(*i));
+        if let (Some(bid), Some(ask)) = (impact("impact_bid_px"), impact("impact_ask_px")) {
+            ctx.insert("impactPxs".to_string(), json!([bid, ask]));
+        }
+
+        let message = json!({
+            "channel": "activeAssetCtx",
+            "data": {"coin": coin, "ctx": ctx},
+            "time": time,
+        });
+        serde_json::to_writer(&mut output, &message)?;
+        output.write_all(b"\n")?;
+        messages += 1;
+    }
+    Ok(messages)
+}
+
+/// Parse an archive timestamp (RFC 3339, or naive UTC with optional fraction) to ms
+fn parse_time(value: &str) -> Option<u64> {
+    let millis = match DateTime::parse_from_rfc3339(value) {
+        Ok(time) => time.timestamp_millis(),
+        Err(_) => NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()?
+            .and_utc()
+            .timestamp_millis(),
+    };
+    u64::try_from(millis).ok()
+}
@@ -0,0 +1,31 @@
+//! Historical market data
+//!
+//! Recordings are stored as JSON lines, one websocket-style message per line
+//! (`{"channel": ..., "data": ...}`, plus `time` in ms where the payload
+//! lacks one), so backtests can replay them through the same handlers that
+//! consume the live stream (for example `SimExchange::apply_l2_book` with the
+//! `sim` feature). Files are laid out under a root directory as:
+//!
+//! ```text
+//! l2Book/<coin>/<YYYY-MM-DD>/<HH>.jsonl
+//! trades/<YYYY-MM-DD>/<HH>.jsonl
+//! activeAssetCtx/<YYYY-MM-DD>.jsonl
+//! ```
+//!
+//! With the `recorder` feature, `Recorder` writes live messages in the same
+//! line format, or as Arrow IPC or protobuf with the `arrow` and `protobuf`
+//! features.
+
+pub mod downloader;
+#[cfg(feature = "recorder")]
+pub mod recorder;
+pub mod s3;
+
+pub use downloader::{ArchiveDownloader, ArchiveKind, ArchiveObject, DateRange, DownloadReport};
+#[cfg(feature = "arrow")]
+pub use recorder::ArrowIpcSink;
+#[cfg(feature = "protobuf")]
+pub use recorder::ProtobufSink;
+#[cfg(feature = "recorder")]
+pub use recorder::{JsonlSink, RecordFormat, RecordSink, RecordedMessage, Recorder};
+pub use s3::S3Credentials;
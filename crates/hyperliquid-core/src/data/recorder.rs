@@ -0,0 +1,463 @@
+//! Recording live websocket messages
+//!
+//! A [`Recorder`] writes every message of its subscriptions, as a
+//! [`RecordedMessage`], to a [`RecordSink`]. The format is selectable with
+//! [`RecordFormat`]:
+//!
+//! - JSON lines in the layout the rest of this module reads
+//!   (`{"channel": ..., "data": ..., "time": ...}`);
+//! - an Arrow IPC stream with `channel`, `time` and `data` columns, with the
+//!   `arrow` feature;
+//! - varint length-delimited protobuf messages, with the `protobuf` feature.
+//!
+//! `data` is kept as JSON text in the binary formats, since its shape differs
+//! per channel. Other encodings can be added by implementing [`RecordSink`].
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::data::{RecordFormat, Recorder};
+//! use hyperliquid_core::types::Subscription;
+//!
+//! let recorder = Recorder::create("btc.jsonl", RecordFormat::Jsonl)?;
+//! let job = recorder
+//!     .forward(&ws, vec![Subscription::L2Book { coin: "BTC".to_string() }])
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+use crate::stream::{WebSocketClient, WebSocketResponse};
+use crate::types::Subscription;
+
+/// Message as recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    pub channel: String,
+    pub data: Value,
+    /// Exchange time in ms, or the receive time when the message has none
+    pub time: u64,
+}
+
+impl RecordedMessage {
+    pub fn from_response(response: &WebSocketResponse) -> Self {
+        let time = response
+            .exchange_time()
+            .unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as u64);
+        Self {
+            channel: response.channel.clone(),
+            data: response.data.clone(),
+            time,
+        }
+    }
+}
+
+/// Destination for recorded messages
+pub trait RecordSink: Send + 'static {
+    fn write(&mut self, message: &RecordedMessage) -> Result<(), HyperliquidError>;
+
+    /// Push buffered messages to the underlying writer
+    fn flush(&mut self) -> Result<(), HyperliquidError>;
+
+    /// Flush and write any trailer; nothing is written afterwards
+    fn finish(&mut self) -> Result<(), HyperliquidError> {
+        self.flush()
+    }
+}
+
+/// Output encoding of a recording
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordFormat {
+    #[default]
+    Jsonl,
+    /// Arrow IPC stream (see [`ArrowIpcSink`])
+    #[cfg(feature = "arrow")]
+    ArrowIpc,
+    /// Length-delimited protobuf (see [`ProtobufSink`])
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+}
+
+impl RecordFormat {
+    /// Conventional file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RecordFormat::Jsonl => "jsonl",
+            #[cfg(feature = "arrow")]
+            RecordFormat::ArrowIpc => "arrows",
+            #[cfg(feature = "protobuf")]
+            RecordFormat::Protobuf => "pb",
+        }
+    }
+
+    /// Sink writing this format to `writer`
+    pub fn sink<W: Write + Send + 'static>(
+        &self,
+        writer: W,
+    ) -> Result<Box<dyn RecordSink>, HyperliquidError> {
+        Ok(match self {
+            RecordFormat::Jsonl => Box::new(JsonlSink::new(writer)),
+            #[cfg(feature = "arrow")]
+            RecordFormat::ArrowIpc => Box::new(ArrowIpcSink::new(writer)?),
+            #[cfg(feature = "protobuf")]
+            RecordFormat::Protobuf => Box::new(ProtobufSink::new(writer)),
+        })
+    }
+}
+
+/// One JSON object per line
+#[derive(Debug)]
+pub struct JsonlSink<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JsonlSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send + 'static> RecordSink for JsonlSink<W> {
+    fn write(&mut self, message: &RecordedMessage) -> Result<(), HyperliquidError> {
+        serde_json::to_writer(&mut self.writer, message)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), HyperliquidError> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[cfg(feature = "arrow")]
+pub use arrow_sink::{record_schema, ArrowIpcSink};
+
+#[cfg(feature = "arrow")]
+mod arrow_sink {
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, RecordBatch, StringArray, TimestampMillisecondArray};
+    use arrow_ipc::writer::StreamWriter;
+    use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit};
+
+    use super::{RecordSink, RecordedMessage};
+    use crate::error::HyperliquidError;
+
+    /// Rows buffered before a record batch is written
+    const DEFAULT_BATCH_SIZE: usize = 1024;
+
+    fn arrow_error(e: ArrowError) -> HyperliquidError {
+        HyperliquidError::Storage(format!("arrow: {}", e))
+    }
+
+    /// Schema of recorded batches
+    ///
+    /// `channel: utf8`, `time: timestamp[ms, UTC]`, `data: utf8` (JSON).
+    pub fn record_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("channel", DataType::Utf8, false),
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("data", DataType::Utf8, false),
+        ]))
+    }
+
+    /// Arrow IPC stream of [`record_schema`] batches
+    ///
+    /// Rows are buffered and written as one batch every `batch_size`
+    /// messages and on flush. The stream is only complete once
+    /// [`finish`](RecordSink::finish) has written its end marker.
+    pub struct ArrowIpcSink<W: Write> {
+        writer: StreamWriter<W>,
+        schema: SchemaRef,
+        batch_size: usize,
+        channels: Vec<String>,
+        times: Vec<i64>,
+        data: Vec<String>,
+    }
+
+    impl<W: Write> ArrowIpcSink<W> {
+        pub fn new(writer: W) -> Result<Self, HyperliquidError> {
+            let schema = record_schema();
+            Ok(Self {
+                writer: StreamWriter::try_new(writer, &schema).map_err(arrow_error)?,
+                schema,
+                batch_size: DEFAULT_BATCH_SIZE,
+                channels: Vec::new(),
+                times: Vec::new(),
+                data: Vec::new(),
+            })
+        }
+
+        /// Rows per record batch (default 1024)
+        pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+            self.batch_size = batch_size.max(1);
+            self
+        }
+
+        fn write_batch(&mut self) -> Result<(), HyperliquidError> {
+            if self.channels.is_empty() {
+                return Ok(());
+            }
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(StringArray::from(std::mem::take(&mut self.channels))),
+                Arc::new(
+                    TimestampMillisecondArray::from(std::mem::take(&mut self.times))
+                        .with_timezone("UTC"),
+                ),
+                Arc::new(StringArray::from(std::mem::take(&mut self.data))),
+            ];
+            let batch = RecordBatch::try_new(self.schema.clone(), columns).map_err(arrow_error)?;
+            self.writer.write(&batch).map_err(arrow_error)
+        }
+    }
+
+    impl<W: Write + Send + 'static> RecordSink for ArrowIpcSink<W> {
+        fn write(&mut self, message: &RecordedMessage) -> Result<(), HyperliquidError> {
+            self.channels.push(message.channel.clone());
+            self.times.push(message.time as i64);
+            self.data.push(serde_json::to_string(&message.data)?);
+            if self.channels.len() >= self.batch_size {
+                self.write_batch()?;
+            }
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), HyperliquidError> {
+            self.write_batch()?;
+            self.writer.flush().map_err(arrow_error)
+        }
+
+        fn finish(&mut self) -> Result<(), HyperliquidError> {
+            self.write_batch()?;
+            self.writer.finish().map_err(arrow_error)
+        }
+    }
+
+    impl<W: Write> std::fmt::Debug for ArrowIpcSink<W> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ArrowIpcSink")
+                .field("batch_size", &self.batch_size)
+                .field("buffered", &self.channels.len())
+                .finish()
+        }
+    }
+}
+
+#[cfg(feature = "protobuf")]
+pub use protobuf_sink::{decode_protobuf, ProtobufSink, RecordProto};
+
+#[cfg(feature = "protobuf")]
+mod protobuf_sink {
+    use std::io::Write;
+
+    use prost::Message;
+
+    use super::{RecordSink, RecordedMessage};
+    use crate::error::HyperliquidError;
+
+    /// Wire message of [`ProtobufSink`]
+    ///
+    /// ```proto
+    /// message RecordedMessage {
+    ///   string channel = 1;
+    ///   uint64 time = 2;
+    ///   string data = 3; // JSON
+    /// }
+    /// ```
+    #[derive(Clone, PartialEq, Message)]
+    pub struct RecordProto {
+        #[prost(string, tag = "1")]
+        pub channel: String,
+        #[prost(uint64, tag = "2")]
+        pub time: u64,
+        #[prost(string, tag = "3")]
+        pub data: String,
+    }
+
+    /// [`RecordProto`] messages, each prefixed with its varint length
+    ///
+    /// The framing of `writeDelimitedTo` / `parseDelimitedFrom`; see
+    /// [`decode_protobuf`] to read it back.
+    #[derive(Debug)]
+    pub struct ProtobufSink<W: Write> {
+        writer: W,
+        buf: Vec<u8>,
+    }
+
+    impl<W: Write> ProtobufSink<W> {
+        pub fn new(writer: W) -> Self {
+            Self {
+                writer,
+                buf: Vec::new(),
+            }
+        }
+
+        pub fn into_inner(self) -> W {
+            self.writer
+        }
+    }
+
+    impl<W: Write + Send + 'static> RecordSink for ProtobufSink<W> {
+        fn write(&mut self, message: &RecordedMessage) -> Result<(), HyperliquidError> {
+            let record = RecordProto {
+                channel: message.channel.clone(),
+                time: message.time,
+                data: serde_json::to_string(&message.data)?,
+            };
+            self.buf.clear();
+            record
+                .encode_length_delimited(&mut self.buf)
+                .map_err(|e| HyperliquidError::Storage(format!("protobuf: {}", e)))?;
+            self.writer.write_all(&self.buf)?;
+            Ok(())
+        }
+
+        fn flush(&mut self) -> Result<(), HyperliquidError> {
+            Ok(self.writer.flush()?)
+        }
+    }
+
+    /// Messages of a [`ProtobufSink`] recording
+    pub fn decode_protobuf(mut bytes: &[u8]) -> Result<Vec<RecordedMessage>, HyperliquidError> {
+        let mut messages = Vec::new();
+        while !bytes.is_empty() {
+            let record = RecordProto::decode_length_delimited(&mut bytes)
+                .map_err(|e| HyperliquidError::Storage(format!("protobuf: {}", e)))?;
+            messages.push(RecordedMessage {
+                channel: record.channel,
+                data: serde_json::from_str(&record.data)?,
+                time: record.time,
+            });
+        }
+        Ok(messages)
+    }
+}
+
+/// Writes websocket messages to a [`RecordSink`]
+#[derive(Clone)]
+pub struct Recorder {
+    sink: Arc<Mutex<Box<dyn RecordSink>>>,
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Recorder").finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    pub fn new(sink: impl RecordSink) -> Self {
+        Self::from_boxed(Box::new(sink))
+    }
+
+    pub fn from_boxed(sink: Box<dyn RecordSink>) -> Self {
+        Self {
+            sink: Arc::new(Mutex::new(sink)),
+        }
+    }
+
+    /// Record to a new file at `path` in `format`, replacing any existing one
+    pub fn create(path: impl AsRef<Path>, format: RecordFormat) -> Result<Self, HyperliquidError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        Ok(Self::from_boxed(format.sink(writer)?))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Box<dyn RecordSink>> {
+        self.sink.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn write(&self, message: &RecordedMessage) -> Result<(), HyperliquidError> {
+        self.lock().write(message)
+    }
+
+    /// Record a websocket message
+    pub fn record(&self, response: &WebSocketResponse) -> Result<(), HyperliquidError> {
+        self.write(&RecordedMessage::from_response(response))
+    }
+
+    pub fn flush(&self) -> Result<(), HyperliquidError> {
+        self.lock().flush()
+    }
+
+    /// Complete the recording; see [`RecordSink::finish`]
+    pub fn finish(&self) -> Result<(), HyperliquidError> {
+        self.lock().finish()
+    }
+
+    /// Subscribe to `subscriptions` and record their messages
+    ///
+    /// Messages are written in arrival order by a background task, which
+    /// logs failures, and finishes the recording when the websocket client
+    /// is dropped.
+    pub async fn forward(
+        &self,
+        ws: &WebSocketClient,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<JoinHandle<()>, HyperliquidError> {
+        let (tx, mut rx) = mpsc::unbounded_channel::<WebSocketResponse>();
+        for subscription in subscriptions {
+            let tx = tx.clone();
+            let filter = serde_json::to_value(&subscription)?;
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if is_for(&filter, &response) {
+                    let _ = tx.send(response);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+
+        let recorder = self.clone();
+        Ok(tokio::spawn(async move {
+            while let Some(response) = rx.recv().await {
+                if let Err(e) = recorder.record(&response) {
+                    warn!("Failed to record {} message: {}", response.channel, e);
+                }
+            }
+            if let Err(e) = recorder.finish() {
+                warn!("Failed to finish recording: {}", e);
+            }
+        }))
+    }
+}
+
+/// Whether `response` is a message of the serialized `subscription`
+fn is_for(subscription: &Value, response: &WebSocketResponse) -> bool {
+    if subscription.get("type").and_then(Value::as_str) != Some(response.channel.as_str()) {
+        return false;
+    }
+    match subscription.get("coin") {
+        Some(coin) => {
+            let data = &response.data;
+            data.get("coin").or_else(|| data.pointer("/0/coin")) == Some(coin)
+        }
+        None => true,
+    }
+}
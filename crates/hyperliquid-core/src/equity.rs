@@ -0,0 +1,319 @@
+//! Account equity history
+//!
+//! An [`EquitySampler`] polls `clearinghouseState` on an interval and writes
+//! each snapshot, as an [`EquitySample`], to a [`StateStore`] under
+//! `equity/<user>/<time>`. The history survives restarts and can be read
+//! back by time range, summarized with [`max_drawdown`], or exported as CSV,
+//! so drawdowns can be watched without an external database.
+//!
+//! ```no_run
+//! # fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use hyperliquid_core::equity::EquitySampler;
+//! use hyperliquid_core::store::MemoryStore;
+//!
+//! let user = "0x0000000000000000000000000000000000000000";
+//! let sampler = EquitySampler::new(client, user, Arc::new(MemoryStore::new()))
+//!     .with_interval(Duration::from_secs(30));
+//! let _job = sampler.start();
+//! // Later
+//! if let Some(drawdown) = sampler.drawdown(0..u64::MAX)? {
+//!     println!("max drawdown {:.2}%", drawdown.fraction * 100.0);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io::Write;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::store::{self, StateStore};
+use crate::types::ClearinghouseState;
+
+const EQUITY_PREFIX: &str = "equity/";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Open position at sample time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionSample {
+    pub coin: String,
+    /// Signed size
+    pub szi: f64,
+    pub entry_px: Option<f64>,
+    pub position_value: f64,
+    pub unrealized_pnl: f64,
+}
+
+/// Account snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EquitySample {
+    /// Exchange time in ms
+    pub time: u64,
+    pub account_value: f64,
+    pub margin_used: f64,
+    pub withdrawable: f64,
+    pub positions: Vec<PositionSample>,
+}
+
+fn parse(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+impl From<&ClearinghouseState> for EquitySample {
+    fn from(state: &ClearinghouseState) -> Self {
+        Self {
+            time: state.time,
+            account_value: parse(&state.margin_summary.accountValue),
+            margin_used: parse(&state.margin_summary.totalMarginUsed),
+            withdrawable: parse(&state.withdrawable),
+            positions: state
+                .asset_positions
+                .iter()
+                .map(|entry| &entry.position)
+                .filter(|position| position.szi() != 0.0)
+                .map(|position| PositionSample {
+                    coin: position.coin.clone(),
+                    szi: position.szi(),
+                    entry_px: position.entry_px.as_ref().and_then(|px| px.parse().ok()),
+                    position_value: parse(&position.position_value),
+                    unrealized_pnl: parse(&position.unrealized_pnl),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl EquitySample {
+    /// Margin in use as a fraction of account value
+    pub fn margin_usage(&self) -> f64 {
+        if self.account_value > 0.0 {
+            self.margin_used / self.account_value
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Largest peak-to-trough fall in account value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drawdown {
+    pub peak: f64,
+    pub peak_time: u64,
+    pub trough: f64,
+    pub trough_time: u64,
+    /// Fall as a fraction of the peak
+    pub fraction: f64,
+}
+
+/// Maximum drawdown of `samples`, which must be in time order
+///
+/// Returns `None` for fewer than two samples or a history that never fell.
+pub fn max_drawdown(samples: &[EquitySample]) -> Option<Drawdown> {
+    let mut peak: Option<&EquitySample> = None;
+    let mut worst: Option<Drawdown> = None;
+    for sample in samples {
+        let current_peak = match peak {
+            Some(peak) if peak.account_value >= sample.account_value => peak,
+            _ => {
+                peak = Some(sample);
+                continue;
+            }
+        };
+        if current_peak.account_value <= 0.0 {
+            continue;
+        }
+        let fraction =
+            (current_peak.account_value - sample.account_value) / current_peak.account_value;
+        if fraction > 0.0 && worst.map_or(true, |worst| fraction > worst.fraction) {
+            worst = Some(Drawdown {
+                peak: current_peak.account_value,
+                peak_time: current_peak.time,
+                trough: sample.account_value,
+                trough_time: sample.time,
+                fraction,
+            });
+        }
+    }
+    worst
+}
+
+/// Write `samples` as CSV, one row per sample
+///
+/// Columns: `time,account_value,margin_used,withdrawable,positions`, where
+/// `positions` is the number of open positions.
+pub fn write_csv(samples: &[EquitySample], mut output: impl Write) -> Result<(), HyperliquidError> {
+    writeln!(
+        output,
+        "time,account_value,margin_used,withdrawable,positions"
+    )?;
+    for sample in samples {
+        writeln!(
+            output,
+            "{},{},{},{},{}",
+            sample.time,
+            sample.account_value,
+            sample.margin_used,
+            sample.withdrawable,
+            sample.positions.len()
+        )?;
+    }
+    Ok(())
+}
+
+/// Key prefix of `user`'s samples
+fn user_prefix(user: &str) -> String {
+    format!("{}{}/", EQUITY_PREFIX, user.to_lowercase())
+}
+
+/// Zero-padded so keys sort by time
+fn sample_key(user: &str, time: u64) -> String {
+    format!("{}{:020}", user_prefix(user), time)
+}
+
+/// `user`'s samples with a time in `range`, in time order
+pub fn load_samples(
+    store: &dyn StateStore,
+    user: &str,
+    range: Range<u64>,
+) -> Result<Vec<EquitySample>, HyperliquidError> {
+    Ok(store::scan_json::<EquitySample>(store, &user_prefix(user))?
+        .into_iter()
+        .map(|(_, sample)| sample)
+        .filter(|sample| range.contains(&sample.time))
+        .collect())
+}
+
+/// Polls an account and records its equity in a [`StateStore`]
+#[derive(Clone)]
+pub struct EquitySampler {
+    client: HttpClient,
+    user: String,
+    store: Arc<dyn StateStore>,
+    interval: Duration,
+    retention: Option<Duration>,
+}
+
+impl std::fmt::Debug for EquitySampler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EquitySampler")
+            .field("user", &self.user)
+            .field("store", &self.store)
+            .field("interval", &self.interval)
+            .field("retention", &self.retention)
+            .finish()
+    }
+}
+
+impl EquitySampler {
+    pub fn new(client: HttpClient, user: impl Into<String>, store: Arc<dyn StateStore>) -> Self {
+        Self {
+            client,
+            user: user.into(),
+            store,
+            interval: DEFAULT_INTERVAL,
+            retention: None,
+        }
+    }
+
+    /// Time between samples (default 60s)
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Delete samples older than `retention` after each new one (default: keep all)
+    pub fn with_retention(mut self, retention: Duration) -> Self {
+        self.retention = Some(retention);
+        self
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// Store `sample`, then apply the retention
+    pub fn record(&self, sample: &EquitySample) -> Result<(), HyperliquidError> {
+        store::put_json(
+            self.store.as_ref(),
+            &sample_key(&self.user, sample.time),
+            sample,
+        )?;
+        if let Some(retention) = self.retention {
+            let cutoff = sample.time.saturating_sub(retention.as_millis() as u64);
+            let expired = sample_key(&self.user, cutoff);
+            for (key, _) in self.store.scan(&user_prefix(&self.user))? {
+                if key >= expired {
+                    break;
+                }
+                self.store.delete(&key)?;
+            }
+        }
+        self.store.flush()
+    }
+
+    /// Fetch `clearinghouseState` and record it
+    pub async fn sample(&self) -> Result<EquitySample, HyperliquidError> {
+        let state: ClearinghouseState = self
+            .client
+            .post(
+                "/info",
+                &json!({"type": "clearinghouseState", "user": self.user}),
+            )
+            .await?;
+        let sample = EquitySample::from(&state);
+        self.record(&sample)?;
+        Ok(sample)
+    }
+
+    /// Sample every interval in the background until the handle is aborted
+    ///
+    /// Failed samples are logged and skipped.
+    pub fn start(&self) -> JoinHandle<()> {
+        let sampler = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sampler.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = sampler.sample().await {
+                    warn!(user = %sampler.user, "Equity sample failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Recorded samples with a time in `range`, in time order
+    pub fn history(&self, range: Range<u64>) -> Result<Vec<EquitySample>, HyperliquidError> {
+        load_samples(self.store.as_ref(), &self.user, range)
+    }
+
+    /// Most recent sample
+    pub fn latest(&self) -> Result<Option<EquitySample>, HyperliquidError> {
+        Ok(self.history(0..u64::MAX)?.pop())
+    }
+
+    /// Maximum drawdown over `range`
+    pub fn drawdown(&self, range: Range<u64>) -> Result<Option<Drawdown>, HyperliquidError> {
+        Ok(max_drawdown(&self.history(range)?))
+    }
+
+    /// Write the samples in `range` as CSV; see [`write_csv`]
+    pub fn export_csv(
+        &self,
+        range: Range<u64>,
+        output: impl Write,
+    ) -> Result<(), HyperliquidError> {
+        write_csv(&self.history(range)?, output)
+    }
+}
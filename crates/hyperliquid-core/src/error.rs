@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use reqwest::StatusCode;
 use thiserror::Error;
+#[cfg(feature = "ws")]
+use tokio_tungstenite::tungstenite;
 
 #[derive(Error, Debug)]
 pub enum HyperliquidError {
@@ -18,16 +22,10 @@ pub enum HyperliquidError {
     RateLimit(String),
 
     #[error("Rate limit exceeded. Retry after {retry_after}s: {message}")]
-    RateLimitWithRetry {
-        message: String,
-        retry_after: u64,
-    },
+    RateLimitWithRetry { message: String, retry_after: u64 },
 
     #[error("Server error: {status} - {message}")]
-    Server {
-        status: StatusCode,
-        message: String,
-    },
+    Server { status: StatusCode, message: String },
 
     #[error("Client error: {code} - {message}")]
     Client {
@@ -42,15 +40,28 @@ pub enum HyperliquidError {
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
+
     #[error("Timeout error: {0}")]
     Timeout(String),
 
+    #[error("Response body exceeds {limit} bytes")]
+    ResponseTooLarge { limit: usize },
+
     #[error("Retry exhausted after {attempts} attempts")]
     RetryExhausted { attempts: u32 },
 
     #[error("WebSocket error: {0}")]
     WebSocket(String),
 
+    #[cfg(feature = "ws")]
+    #[error("WebSocket transport error: {0}")]
+    Transport(#[from] tungstenite::Error),
+
     #[error("Signing error: {0}")]
     Signing(String),
 
@@ -66,11 +77,43 @@ pub enum HyperliquidError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// An order would need more margin than the account has available
+    #[error("Insufficient margin for {coin}: {required} required, {available} available")]
+    InsufficientMargin {
+        coin: String,
+        required: f64,
+        available: f64,
+    },
+
+    /// Orders in the scope are refused by a kill switch
+    #[error("Trading disabled for {scope}: {reason}")]
+    TradingDisabled { scope: String, reason: String },
+
+    /// A fund transfer's destination is not on the withdrawal allowlist
+    #[error("Destination {destination} of {action} is not on the withdrawal allowlist")]
+    DestinationNotAllowed { action: String, destination: String },
+
+    /// The exchange health signal is holding requests back
+    #[error("Exchange {status}: {reason}")]
+    ExchangeUnavailable { status: String, reason: String },
+
     #[error("Unknown error: {0}")]
     Unknown(String),
+
+    /// An error with a description of what was being done
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<HyperliquidError>,
+    },
 }
 
 impl HyperliquidError {
+    /// Whether the same request may succeed if sent again
+    ///
+    /// Context is looked through, so a wrapped error classifies like the
+    /// error it wraps.
     pub fn is_retryable(&self) -> bool {
         match self {
             HyperliquidError::Network(_) => true,
@@ -81,10 +124,74 @@ impl HyperliquidError {
             HyperliquidError::RateLimit(_) => true,
             HyperliquidError::RateLimitWithRetry { .. } => true,
             HyperliquidError::Server { .. } => true,
+            #[cfg(feature = "ws")]
+            HyperliquidError::Transport(e) => match e {
+                tungstenite::Error::Io(_)
+                | tungstenite::Error::ConnectionClosed
+                | tungstenite::Error::AlreadyClosed
+                | tungstenite::Error::Protocol(
+                    tungstenite::error::ProtocolError::ResetWithoutClosingHandshake,
+                ) => true,
+                tungstenite::Error::Http(response) => {
+                    matches!(response.status().as_u16(), 500..=599 | 429)
+                }
+                _ => false,
+            },
+            HyperliquidError::Context { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Whether the request was refused for exceeding a rate limit
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            HyperliquidError::RateLimit(_) | HyperliquidError::RateLimitWithRetry { .. } => true,
+            HyperliquidError::Http { status, .. } | HyperliquidError::Server { status, .. } => {
+                *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            #[cfg(feature = "ws")]
+            HyperliquidError::Transport(tungstenite::Error::Http(response)) => {
+                response.status() == StatusCode::TOO_MANY_REQUESTS
+            }
+            HyperliquidError::Context { source, .. } => source.is_rate_limited(),
             _ => false,
         }
     }
 
+    /// How long the server asked to wait before retrying, if it did
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            HyperliquidError::RateLimitWithRetry { retry_after, .. } => {
+                Some(Duration::from_secs(*retry_after))
+            }
+            HyperliquidError::Context { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
+
+    /// Wrap the error with a description of what was being done
+    pub fn context(self, context: impl Into<String>) -> Self {
+        HyperliquidError::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// This error followed by its sources, outermost first
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn std::error::Error + 'static)> {
+        let mut next: Option<&(dyn std::error::Error + 'static)> = Some(self);
+        std::iter::from_fn(move || {
+            let current = next?;
+            next = current.source();
+            Some(current)
+        })
+    }
+
+    /// The innermost source, e.g. the underlying reqwest or serde error
+    pub fn root_cause(&self) -> &(dyn std::error::Error + 'static) {
+        self.chain().last().unwrap_or(self)
+    }
+
     pub fn should_retry_immediately(&self) -> bool {
         match self {
             HyperliquidError::Network(_) => true,
@@ -92,7 +199,37 @@ impl HyperliquidError {
             HyperliquidError::Http { status, .. } => {
                 matches!(status.as_u16(), 502 | 503 | 504)
             }
+            HyperliquidError::Context { source, .. } => source.should_retry_immediately(),
             _ => false,
         }
     }
-}
\ No newline at end of file
+}
+
+/// Add context to the error of a result
+pub trait ResultExt<T> {
+    /// Wrap the error with `context`
+    fn context(self, context: impl Into<String>) -> Result<T, HyperliquidError>;
+
+    /// Wrap the error with a lazily built context
+    fn with_context<C, F>(self, context: F) -> Result<T, HyperliquidError>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    E: Into<HyperliquidError>,
+{
+    fn context(self, context: impl Into<String>) -> Result<T, HyperliquidError> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context<C, F>(self, context: F) -> Result<T, HyperliquidError>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| e.into().context(context()))
+    }
+}
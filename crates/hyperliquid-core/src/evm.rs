@@ -0,0 +1,536 @@
+//! HyperEVM JSON-RPC client and HyperCore bridging helpers
+//!
+//! [`EvmClient`] is a thin JSON-RPC client for the HyperEVM endpoints; it
+//! covers reads (balances, `eth_call`, receipts) and broadcasting already
+//! signed transactions. The chain ids and RPC URLs are exported as
+//! [`EvmNetwork`] so they can be handed to an alloy or ethers provider when
+//! transactions need to be built and signed there.
+//!
+//! Tokens move between HyperCore and HyperEVM through per-token system
+//! addresses:
+//!
+//! - Core to EVM is a `spotSend` to the token's system address
+//!   ([`bridge_to_evm`]);
+//! - EVM to Core is a native HYPE transfer or an ERC-20 `transfer` to the same
+//!   address ([`LinkedToken::evm_to_core`]).
+//!
+//! Amounts on the two sides use different decimals; [`LinkedToken`] converts
+//! between them using the `evmContract` data from `spotMeta`.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+
+use crate::client::HttpClient;
+use crate::crypto::{action_types, Wallet};
+use crate::error::HyperliquidError;
+use crate::exchange::ExchangeClient;
+use crate::types::Environment;
+
+/// HyperEVM mainnet chain id
+pub const MAINNET_CHAIN_ID: u64 = 999;
+/// HyperEVM testnet chain id
+pub const TESTNET_CHAIN_ID: u64 = 998;
+/// Public HyperEVM mainnet RPC endpoint
+pub const MAINNET_RPC_URL: &str = "https://rpc.hyperliquid.xyz/evm";
+/// Public HyperEVM testnet RPC endpoint
+pub const TESTNET_RPC_URL: &str = "https://rpc.hyperliquid-testnet.xyz/evm";
+/// System address that bridges native HYPE
+pub const HYPE_SYSTEM_ADDRESS: &str = "0x2222222222222222222222222222222222222222";
+/// Decimals of native HYPE on HyperEVM
+pub const HYPE_EVM_DECIMALS: u32 = 18;
+
+/// `balanceOf(address)`
+const BALANCE_OF_SELECTOR: &str = "70a08231";
+/// `transfer(address,uint256)`
+const TRANSFER_SELECTOR: &str = "a9059cbb";
+
+/// HyperEVM network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EvmNetwork {
+    Mainnet,
+    Testnet,
+}
+
+impl EvmNetwork {
+    /// Network paired with a HyperCore environment, `None` for `Local`
+    pub fn for_environment(env: Environment) -> Option<Self> {
+        match env {
+            Environment::Mainnet => Some(EvmNetwork::Mainnet),
+            Environment::Testnet => Some(EvmNetwork::Testnet),
+            Environment::Local => None,
+        }
+    }
+
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            EvmNetwork::Mainnet => MAINNET_CHAIN_ID,
+            EvmNetwork::Testnet => TESTNET_CHAIN_ID,
+        }
+    }
+
+    pub fn rpc_url(&self) -> &'static str {
+        match self {
+            EvmNetwork::Mainnet => MAINNET_RPC_URL,
+            EvmNetwork::Testnet => TESTNET_RPC_URL,
+        }
+    }
+}
+
+/// System address bridging the spot token with index `token_index`
+///
+/// `0x20` followed by the big-endian token index; HYPE uses
+/// [`HYPE_SYSTEM_ADDRESS`] instead.
+pub fn system_address(token_index: u32) -> String {
+    format!("0x20{:038x}", token_index)
+}
+
+/// Spot token that exists on both HyperCore and HyperEVM
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkedToken {
+    pub name: String,
+    pub index: u32,
+    /// `tokenId` hash, used in `spotSend` token strings
+    pub token_id: String,
+    /// Decimals of the HyperCore balance
+    pub wei_decimals: u32,
+    /// ERC-20 contract, `None` for native HYPE
+    pub evm_address: Option<String>,
+    /// Extra decimals of the EVM representation (may be negative)
+    pub evm_extra_wei_decimals: i32,
+}
+
+impl LinkedToken {
+    /// Tokens from a `spotMeta` response that are linked to HyperEVM
+    pub fn from_spot_meta(spot_meta: &Value) -> Vec<LinkedToken> {
+        let tokens = match spot_meta.get("tokens").and_then(Value::as_array) {
+            Some(tokens) => tokens,
+            None => return Vec::new(),
+        };
+        tokens
+            .iter()
+            .filter_map(|token| {
+                let name = token.get("name")?.as_str()?.to_string();
+                let index = token.get("index")?.as_u64()? as u32;
+                let wei_decimals = token.get("weiDecimals")?.as_u64()? as u32;
+                let token_id = token
+                    .get("tokenId")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let contract = token.get("evmContract").filter(|c| !c.is_null());
+                let (evm_address, evm_extra_wei_decimals) = match contract {
+                    Some(contract) => (
+                        Some(contract.get("address")?.as_str()?.to_lowercase()),
+                        contract
+                            .get("evm_extra_wei_decimals")
+                            .and_then(Value::as_i64)
+                            .unwrap_or(0) as i32,
+                    ),
+                    None if name == "HYPE" => {
+                        (None, HYPE_EVM_DECIMALS as i32 - wei_decimals as i32)
+                    }
+                    None => return None,
+                };
+                Some(LinkedToken {
+                    name,
+                    index,
+                    token_id,
+                    wei_decimals,
+                    evm_address,
+                    evm_extra_wei_decimals,
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.evm_address.is_none()
+    }
+
+    /// Address that credits transfers to the other side of the bridge
+    pub fn system_address(&self) -> String {
+        if self.is_native() {
+            HYPE_SYSTEM_ADDRESS.to_string()
+        } else {
+            system_address(self.index)
+        }
+    }
+
+    /// Token string used by `spotSend`
+    pub fn spot_token(&self) -> String {
+        format!("{}:{}", self.name, self.token_id)
+    }
+
+    /// Decimals of the EVM representation
+    pub fn evm_decimals(&self) -> u32 {
+        (self.wei_decimals as i32 + self.evm_extra_wei_decimals).max(0) as u32
+    }
+
+    /// Convert a token amount to EVM base units
+    pub fn to_evm_units(&self, amount: Decimal) -> Result<u128, HyperliquidError> {
+        let scale = Decimal::from(10u64.pow(self.evm_decimals()));
+        amount
+            .checked_mul(scale)
+            .filter(|units| units.fract().is_zero() && !units.is_sign_negative())
+            .and_then(|units| u128::try_from(units).ok())
+            .ok_or_else(|| {
+                HyperliquidError::Validation(format!(
+                    "{} {} is not representable with {} decimals",
+                    amount,
+                    self.name,
+                    self.evm_decimals()
+                ))
+            })
+    }
+
+    /// Convert EVM base units to a token amount
+    pub fn from_evm_units(&self, units: u128) -> Result<Decimal, HyperliquidError> {
+        i128::try_from(units)
+            .ok()
+            .and_then(|units| Decimal::try_from_i128_with_scale(units, self.evm_decimals()).ok())
+            .map(|amount| amount.normalize())
+            .ok_or_else(|| {
+                HyperliquidError::Validation(format!("{} {} base units overflow", units, self.name))
+            })
+    }
+
+    /// `spotSend` action moving `amount` from HyperCore to HyperEVM
+    ///
+    /// Amounts finer than the Core balance's decimals are rejected rather
+    /// than truncated.
+    pub fn core_to_evm_action(
+        &self,
+        amount: Decimal,
+        time: u64,
+    ) -> Result<Value, HyperliquidError> {
+        if amount.is_sign_negative() || amount.is_zero() {
+            return Err(HyperliquidError::Validation(format!(
+                "bridge amount must be positive, got {}",
+                amount
+            )));
+        }
+        if amount.round_dp(self.wei_decimals) != amount {
+            return Err(HyperliquidError::Validation(format!(
+                "{} has at most {} decimals on HyperCore",
+                self.name, self.wei_decimals
+            )));
+        }
+        Ok(json!({
+            "type": "spotSend",
+            "destination": self.system_address(),
+            "token": self.spot_token(),
+            "amount": amount.normalize().to_string(),
+            "time": time,
+        }))
+    }
+
+    /// Unsigned HyperEVM transaction moving `amount` to HyperCore
+    pub fn evm_to_core(&self, amount: Decimal) -> Result<EvmTransfer, HyperliquidError> {
+        let units = self.to_evm_units(amount)?;
+        Ok(match &self.evm_address {
+            None => EvmTransfer {
+                to: HYPE_SYSTEM_ADDRESS.to_string(),
+                value: units,
+                data: None,
+            },
+            Some(contract) => EvmTransfer {
+                to: contract.clone(),
+                value: 0,
+                data: Some(format!(
+                    "0x{}{}{:064x}",
+                    TRANSFER_SELECTOR,
+                    encode_address(&self.system_address())?,
+                    units
+                )),
+            },
+        })
+    }
+}
+
+/// Transaction to sign and send on HyperEVM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvmTransfer {
+    pub to: String,
+    /// Native value in wei
+    pub value: u128,
+    /// Hex-encoded calldata
+    pub data: Option<String>,
+}
+
+/// Balance of one token on both sides of the bridge
+#[derive(Debug, Clone, PartialEq)]
+pub struct BridgedBalance {
+    pub token: String,
+    pub core: Decimal,
+    pub evm: Decimal,
+}
+
+impl BridgedBalance {
+    pub fn total(&self) -> Decimal {
+        self.core + self.evm
+    }
+}
+
+/// JSON-RPC client for HyperEVM
+pub struct EvmClient {
+    http: reqwest::Client,
+    rpc_url: String,
+    chain_id: u64,
+    next_id: AtomicU64,
+}
+
+impl std::fmt::Debug for EvmClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvmClient")
+            .field("rpc_url", &self.rpc_url)
+            .field("chain_id", &self.chain_id)
+            .finish()
+    }
+}
+
+impl EvmClient {
+    pub fn new(network: EvmNetwork) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            rpc_url: network.rpc_url().to_string(),
+            chain_id: network.chain_id(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Use a different RPC endpoint, e.g. a private node
+    pub fn with_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = rpc_url.into();
+        self
+    }
+
+    pub fn with_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    /// Configured chain id, for signing transactions
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Send a JSON-RPC request and return its `result`
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value, HyperliquidError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let response = self
+            .http
+            .post(&self.rpc_url)
+            .json(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(HyperliquidError::Http {
+                status,
+                message: format!(
+                    "{} failed: {}",
+                    method,
+                    response.text().await.unwrap_or_default()
+                ),
+                cause: None,
+            });
+        }
+
+        let mut body: Value = response.json().await?;
+        if let Some(error) = body.get("error") {
+            return Err(HyperliquidError::Client {
+                code: error.get("code").and_then(Value::as_i64).unwrap_or(0) as i32,
+                message: error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("JSON-RPC error")
+                    .to_string(),
+                data: error.get("data").cloned(),
+            });
+        }
+        Ok(body
+            .get_mut("result")
+            .map(Value::take)
+            .unwrap_or(Value::Null))
+    }
+
+    /// Chain id reported by the endpoint
+    pub async fn remote_chain_id(&self) -> Result<u64, HyperliquidError> {
+        parse_quantity(&self.request("eth_chainId", json!([])).await?).map(|id| id as u64)
+    }
+
+    pub async fn block_number(&self) -> Result<u64, HyperliquidError> {
+        parse_quantity(&self.request("eth_blockNumber", json!([])).await?).map(|n| n as u64)
+    }
+
+    pub async fn gas_price(&self) -> Result<u128, HyperliquidError> {
+        parse_quantity(&self.request("eth_gasPrice", json!([])).await?)
+    }
+
+    /// Pending transaction count of `address`
+    pub async fn nonce(&self, address: &str) -> Result<u64, HyperliquidError> {
+        let count = self
+            .request("eth_getTransactionCount", json!([address, "pending"]))
+            .await?;
+        parse_quantity(&count).map(|n| n as u64)
+    }
+
+    /// Native HYPE balance in wei
+    pub async fn balance(&self, address: &str) -> Result<u128, HyperliquidError> {
+        parse_quantity(
+            &self
+                .request("eth_getBalance", json!([address, "latest"]))
+                .await?,
+        )
+    }
+
+    /// ERC-20 balance of `owner` in base units
+    pub async fn erc20_balance(&self, token: &str, owner: &str) -> Result<u128, HyperliquidError> {
+        let data = format!("0x{}{}", BALANCE_OF_SELECTOR, encode_address(owner)?);
+        parse_quantity(&Value::String(self.call(token, &data).await?))
+    }
+
+    /// `eth_call` against the latest block, returning the hex result
+    pub async fn call(&self, to: &str, data: &str) -> Result<String, HyperliquidError> {
+        let result = self
+            .request("eth_call", json!([{"to": to, "data": data}, "latest"]))
+            .await?;
+        result.as_str().map(str::to_string).ok_or_else(|| {
+            HyperliquidError::Validation(format!("unexpected eth_call result: {}", result))
+        })
+    }
+
+    /// Broadcast a signed transaction, returning its hash
+    pub async fn send_raw_transaction(&self, raw: &str) -> Result<String, HyperliquidError> {
+        let result = self.request("eth_sendRawTransaction", json!([raw])).await?;
+        result.as_str().map(str::to_string).ok_or_else(|| {
+            HyperliquidError::Validation(format!(
+                "unexpected eth_sendRawTransaction result: {}",
+                result
+            ))
+        })
+    }
+
+    /// Receipt of a mined transaction, `None` while pending
+    pub async fn transaction_receipt(&self, hash: &str) -> Result<Option<Value>, HyperliquidError> {
+        let receipt = self
+            .request("eth_getTransactionReceipt", json!([hash]))
+            .await?;
+        Ok(Some(receipt).filter(|r| !r.is_null()))
+    }
+
+    /// EVM balance of `token` held by `address`
+    pub async fn token_balance(
+        &self,
+        token: &LinkedToken,
+        address: &str,
+    ) -> Result<Decimal, HyperliquidError> {
+        let units = match &token.evm_address {
+            None => self.balance(address).await?,
+            Some(contract) => self.erc20_balance(contract, address).await?,
+        };
+        token.from_evm_units(units)
+    }
+}
+
+/// Move `amount` of `token` from HyperCore to HyperEVM
+///
+/// Funds arrive at the same address on HyperEVM once the `spotSend` is
+/// processed.
+pub async fn bridge_to_evm(
+    exchange: &ExchangeClient,
+    wallet: &Wallet,
+    token: &LinkedToken,
+    amount: Decimal,
+) -> Result<Value, HyperliquidError> {
+    let time = chrono::Utc::now().timestamp_millis() as u64;
+    let action = token.core_to_evm_action(amount, time)?;
+    exchange
+        .post_user_signed_action(
+            action,
+            wallet,
+            action_types::SPOT_TRANSFER,
+            "HyperliquidTransaction:SpotSend",
+        )
+        .await
+}
+
+/// Fetch `spotMeta` and return the tokens linked to HyperEVM
+pub async fn linked_tokens(client: &HttpClient) -> Result<Vec<LinkedToken>, HyperliquidError> {
+    let spot_meta: Value = client.post("/info", &json!({"type": "spotMeta"})).await?;
+    Ok(LinkedToken::from_spot_meta(&spot_meta))
+}
+
+/// Balances of `tokens` held by `user` on HyperCore and HyperEVM
+pub async fn bridged_balances(
+    client: &HttpClient,
+    evm: &EvmClient,
+    user: &str,
+    tokens: &[LinkedToken],
+) -> Result<Vec<BridgedBalance>, HyperliquidError> {
+    let state: Value = client
+        .post(
+            "/info",
+            &json!({"type": "spotClearinghouseState", "user": user}),
+        )
+        .await?;
+    let core_balances = state
+        .get("balances")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut balances = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let core = core_balances
+            .iter()
+            .find(|b| b.get("token").and_then(Value::as_u64) == Some(token.index as u64))
+            .and_then(|b| b.get("total").and_then(Value::as_str))
+            .map(Decimal::from_str)
+            .transpose()
+            .map_err(|e| {
+                HyperliquidError::Validation(format!("invalid {} balance: {}", token.name, e))
+            })?
+            .unwrap_or_default();
+        let evm = evm.token_balance(token, user).await?;
+        balances.push(BridgedBalance {
+            token: token.name.clone(),
+            core,
+            evm,
+        });
+    }
+    Ok(balances)
+}
+
+/// 32-byte ABI encoding of a `0x` address
+fn encode_address(address: &str) -> Result<String, HyperliquidError> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(HyperliquidError::Validation(format!(
+            "invalid address: {}",
+            address
+        )));
+    }
+    Ok(format!("{:0>64}", hex.to_lowercase()))
+}
+
+/// Parse a hex quantity (`0x...`), accepting 32-byte `eth_call` words
+fn parse_quantity(value: &Value) -> Result<u128, HyperliquidError> {
+    let invalid = || HyperliquidError::Validation(format!("invalid hex quantity: {}", value));
+    let hex = value
+        .as_str()
+        .and_then(|s| s.strip_prefix("0x"))
+        .ok_or_else(invalid)?;
+    let hex = hex.trim_start_matches('0');
+    if hex.is_empty() {
+        return Ok(0);
+    }
+    u128::from_str_radix(hex, 16).map_err(|_| invalid())
+}
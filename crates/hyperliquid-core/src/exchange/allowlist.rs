@@ -0,0 +1,151 @@
+//! Allowlist of destinations funds may be sent to
+//!
+//! With a [`WithdrawalAllowlist`] on the [`ExchangeClient`], every
+//! `withdraw3`, `usdSend`, `spotSend` and `sendAsset` action has its
+//! destination checked before it is signed, as does every `transfer` sent
+//! by [`ExchangeClient::transfer`](super::ExchangeClient::transfer).
+//! Entries are addresses or patterns where `*` matches any run of hex
+//! digits and `?` exactly one, e.g. `0x20*` for the spot token system
+//! addresses used by HyperEVM bridging. Matching ignores case.
+//!
+//! In strict mode a destination that isn't allowed fails the action with
+//! [`HyperliquidError::DestinationNotAllowed`]; otherwise it is logged and
+//! sent. Either way the check is logged, and refused actions are recorded
+//! in the client's audit log when one is configured.
+//!
+//! [`ExchangeClient`]: super::ExchangeClient
+
+use tracing::{error, info, warn};
+
+use crate::config::SecurityConfig;
+use crate::error::HyperliquidError;
+
+/// Action types moving funds to a `destination`
+pub const GUARDED_ACTIONS: &[&str] = &["withdraw3", "usdSend", "spotSend", "sendAsset", "transfer"];
+
+/// Whether `pattern` matches all of `text`
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.split_first(), text.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            glob(rest, text)
+                || text
+                    .split_first()
+                    .is_some_and(|(_, tail)| glob(pattern, tail))
+        }
+        (Some((b'?', rest)), Some((_, tail))) => glob(rest, tail),
+        (Some((p, rest)), Some((t, tail))) => p == t && glob(rest, tail),
+        _ => false,
+    }
+}
+
+/// Destinations fund transfers may go to
+#[derive(Debug, Clone, Default)]
+pub struct WithdrawalAllowlist {
+    /// Lowercased addresses and patterns
+    entries: Vec<String>,
+    strict: bool,
+}
+
+impl WithdrawalAllowlist {
+    /// Allowlist of `entries`, refusing other destinations when `strict`
+    ///
+    /// Fails with [`HyperliquidError::Validation`] on an entry that is
+    /// neither a `0x` address nor a pattern of one.
+    pub fn new<I, S>(entries: I, strict: bool) -> Result<Self, HyperliquidError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let entry = entry.as_ref().trim().to_ascii_lowercase();
+                Self::validate(&entry)?;
+                Ok(entry)
+            })
+            .collect::<Result<_, HyperliquidError>>()?;
+        Ok(Self { entries, strict })
+    }
+
+    /// Allowlist of `security.withdrawal_allowlist`, strict with
+    /// `security.strict_mode`; `None` if no entries are configured
+    pub fn from_config(config: &SecurityConfig) -> Result<Option<Self>, HyperliquidError> {
+        if config.withdrawal_allowlist.is_empty() {
+            return Ok(None);
+        }
+        Self::new(&config.withdrawal_allowlist, config.strict_mode)
+            .map(Some)
+            .map_err(|e| HyperliquidError::Config(format!("security.withdrawal_allowlist: {}", e)))
+    }
+
+    fn validate(entry: &str) -> Result<(), HyperliquidError> {
+        let invalid =
+            || HyperliquidError::Validation(format!("invalid allowlist entry: {}", entry));
+        let body = entry.strip_prefix("0x").ok_or_else(invalid)?;
+        if !body
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() || b == b'*' || b == b'?')
+        {
+            return Err(invalid());
+        }
+        let fixed = body.bytes().filter(|&b| b != b'*').count();
+        let wildcard = body.contains('*');
+        if fixed > 40 || (!wildcard && fixed != 40) {
+            return Err(invalid());
+        }
+        Ok(())
+    }
+
+    /// Whether a destination that isn't allowed fails the action instead of being logged
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Lowercased address patterns, in configuration order
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Whether `destination` matches an entry
+    pub fn is_allowed(&self, destination: &str) -> bool {
+        let destination = destination.trim().to_ascii_lowercase();
+        let Some(body) = destination.strip_prefix("0x") else {
+            return false;
+        };
+        if body.len() != 40 || !body.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+        self.entries
+            .iter()
+            .any(|entry| glob(entry.as_bytes(), destination.as_bytes()))
+    }
+
+    /// Check `destination` of an action of type `action`
+    ///
+    /// Fails with [`HyperliquidError::DestinationNotAllowed`] in strict mode
+    /// if it isn't allowed; in lenient mode that is only logged.
+    pub fn check(&self, action: &str, destination: &str) -> Result<(), HyperliquidError> {
+        if self.is_allowed(destination) {
+            info!("Allowlisted {} to {}", action, destination);
+            return Ok(());
+        }
+        crate::telemetry::counter!("hyperliquid_withdrawal_allowlist_violations_total", "action" => action.to_string())
+            .increment(1);
+        if self.strict {
+            error!(
+                "Refused {} to {}: not on the allowlist",
+                action, destination
+            );
+            return Err(HyperliquidError::DestinationNotAllowed {
+                action: action.to_string(),
+                destination: destination.to_string(),
+            });
+        }
+        warn!(
+            "{} to {} is not on the allowlist; sending since strict mode is off",
+            action, destination
+        );
+        Ok(())
+    }
+}
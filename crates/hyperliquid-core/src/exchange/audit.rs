@@ -0,0 +1,389 @@
+//! Append-only audit log of exchange actions
+//!
+//! Each submitted action is written as one JSON line carrying its nonce, the
+//! keccak256 hash of the action payload, the signer address and the
+//! submission result. Records are hash-chained: every record's `hash` covers
+//! its own fields plus the previous record's hash, so any edit, deletion or
+//! reordering of earlier lines is detected by [`verify_audit_log`].
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ethers_core::utils::keccak256;
+use serde::{Deserialize, Serialize};
+
+use crate::error::HyperliquidError;
+
+/// `prev_hash` of the first record in a log
+pub const GENESIS_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Outcome of submitting an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum AuditResult {
+    /// Exchange accepted the request
+    Submitted,
+    /// Exchange answered with `"status": "err"`
+    Rejected {
+        /// Error message from the response
+        error: String,
+    },
+    /// Submission failed
+    Failed {
+        /// Error message
+        error: String,
+    },
+}
+
+impl AuditResult {
+    /// Classify the outcome of posting an action to `/exchange`
+    pub(crate) fn from_response(result: &Result<String, HyperliquidError>) -> Self {
+        let body = match result {
+            Ok(body) => body,
+            Err(e) => {
+                return AuditResult::Failed {
+                    error: e.to_string(),
+                }
+            }
+        };
+        let response: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        if response.get("status").and_then(|status| status.as_str()) != Some("err") {
+            return AuditResult::Submitted;
+        }
+        let error = match response.get("response") {
+            Some(serde_json::Value::String(error)) => error.clone(),
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        AuditResult::Rejected { error }
+    }
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position in the log, starting at 0
+    pub seq: u64,
+    /// Record time in milliseconds since the epoch
+    pub timestamp: i64,
+    /// Action type ("order", "cancel", ...)
+    pub action: String,
+    /// Action nonce
+    pub nonce: Option<i64>,
+    /// keccak256 of the serialized action (0x-prefixed hex)
+    pub action_hash: String,
+    /// Signer address (lowercase 0x-prefixed hex)
+    pub signer: String,
+    /// Submission result
+    pub result: AuditResult,
+    /// Hash of the previous record
+    pub prev_hash: String,
+    /// Hash of this record
+    pub hash: String,
+}
+
+impl AuditRecord {
+    /// Compute the chained hash over every field except `hash`
+    pub fn compute_hash(&self) -> String {
+        let body = serde_json::json!({
+            "seq": self.seq,
+            "timestamp": self.timestamp,
+            "action": self.action,
+            "nonce": self.nonce,
+            "action_hash": self.action_hash,
+            "signer": self.signer,
+            "result": self.result,
+            "prev_hash": self.prev_hash,
+        });
+        format!("0x{}", hex::encode(keccak256(body.to_string().as_bytes())))
+    }
+}
+
+/// Hash an action payload for the audit log
+pub fn hash_action<T: Serialize>(action: &T) -> Result<String, HyperliquidError> {
+    Ok(hash_action_bytes(&serde_json::to_vec(action)?))
+}
+
+/// Hash an action payload already serialized as JSON
+pub fn hash_action_bytes(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(keccak256(bytes)))
+}
+
+struct AuditLogState {
+    file: File,
+    next_seq: u64,
+    last_hash: String,
+}
+
+/// Hash-chained JSONL audit log
+pub struct AuditLog {
+    path: PathBuf,
+    sync: bool,
+    state: Mutex<AuditLogState>,
+}
+
+impl AuditLog {
+    /// Open (or create) an audit log, continuing the existing chain
+    ///
+    /// The existing file is verified first; a broken chain is an error rather
+    /// than something to append to.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HyperliquidError> {
+        let path = path.as_ref().to_path_buf();
+
+        let (next_seq, last_hash) = if path.exists() {
+            match read_records(&path)?.last() {
+                Some(last) => (last.seq + 1, last.hash.clone()),
+                None => (0, GENESIS_HASH.to_string()),
+            }
+        } else {
+            (0, GENESIS_HASH.to_string())
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| audit_error(&path, e))?;
+
+        Ok(Self {
+            path,
+            sync: false,
+            state: Mutex::new(AuditLogState {
+                file,
+                next_seq,
+                last_hash,
+            }),
+        })
+    }
+
+    /// fsync after every record (durable, but slower)
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Append a record for a submitted action
+    pub fn record(
+        &self,
+        action: &str,
+        nonce: Option<i64>,
+        action_hash: &str,
+        signer: &str,
+        result: AuditResult,
+    ) -> Result<AuditRecord, HyperliquidError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut record = AuditRecord {
+            seq: state.next_seq,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            action: action.to_string(),
+            nonce,
+            action_hash: action_hash.to_string(),
+            signer: signer.to_string(),
+            result,
+            prev_hash: state.last_hash.clone(),
+            hash: String::new(),
+        };
+        record.hash = record.compute_hash();
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        state
+            .file
+            .write_all(&line)
+            .map_err(|e| audit_error(&self.path, e))?;
+        if self.sync {
+            state
+                .file
+                .sync_data()
+                .map_err(|e| audit_error(&self.path, e))?;
+        }
+
+        state.next_seq += 1;
+        state.last_hash = record.hash.clone();
+        Ok(record)
+    }
+
+    /// Path of the log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of records in the log
+    pub fn len(&self) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .next_seq
+    }
+
+    /// Check if the log has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for AuditLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditLog")
+            .field("path", &self.path)
+            .field("records", &self.len())
+            .finish()
+    }
+}
+
+/// Verify the hash chain of an audit log, returning the number of records
+pub fn verify_audit_log(path: impl AsRef<Path>) -> Result<u64, HyperliquidError> {
+    Ok(read_records(path.as_ref())?.len() as u64)
+}
+
+/// Read and verify every record
+fn read_records(path: &Path) -> Result<Vec<AuditRecord>, HyperliquidError> {
+    let file = File::open(path).map_err(|e| audit_error(path, e))?;
+    let mut records = Vec::new();
+    let mut prev_hash = GENESIS_HASH.to_string();
+
+    for (index, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| audit_error(path, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_no = index + 1;
+
+        let record: AuditRecord = serde_json::from_str(&line).map_err(|e| {
+            HyperliquidError::Validation(format!("audit log line {} is malformed: {}", line_no, e))
+        })?;
+
+        if record.seq != records.len() as u64 || record.prev_hash != prev_hash {
+            return Err(HyperliquidError::Validation(format!(
+                "audit log chain broken at line {}",
+                line_no
+            )));
+        }
+        if record.compute_hash() != record.hash {
+            return Err(HyperliquidError::Validation(format!(
+                "audit log record at line {} was modified",
+                line_no
+            )));
+        }
+
+        prev_hash = record.hash.clone();
+        records.push(record);
+    }
+
+    Ok(records)
+}
+
+fn audit_error(path: &Path, e: std::io::Error) -> HyperliquidError {
+    HyperliquidError::Config(format!("audit log {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_log(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "hyperliquid-audit-{}-{}.jsonl",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn test_records_are_chained() {
+        let path = temp_log("chain");
+        let log = AuditLog::open(&path).unwrap();
+
+        let first = log
+            .record("order", Some(1), "0xaa", "0xsigner", AuditResult::Submitted)
+            .unwrap();
+        let second = log
+            .record(
+                "cancel",
+                Some(2),
+                "0xbb",
+                "0xsigner",
+                AuditResult::Failed {
+                    error: "rejected".to_string(),
+                },
+            )
+            .unwrap();
+
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(verify_audit_log(&path).unwrap(), 2);
+
+        // Reopening continues the chain
+        drop(log);
+        let log = AuditLog::open(&path).unwrap();
+        let third = log
+            .record("order", Some(3), "0xcc", "0xsigner", AuditResult::Submitted)
+            .unwrap();
+        assert_eq!(third.seq, 2);
+        assert_eq!(third.prev_hash, second.hash);
+        assert_eq!(verify_audit_log(&path).unwrap(), 3);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_error_responses_are_rejections() {
+        let ok = Ok(r#"{"status":"ok","response":{"type":"default"}}"#.to_string());
+        assert_eq!(AuditResult::from_response(&ok), AuditResult::Submitted);
+
+        let err = Ok(r#"{"status":"err","response":"Insufficient margin"}"#.to_string());
+        assert_eq!(
+            AuditResult::from_response(&err),
+            AuditResult::Rejected {
+                error: "Insufficient margin".to_string()
+            }
+        );
+
+        let failed = Err(HyperliquidError::Timeout("timed out".to_string()));
+        assert!(matches!(
+            AuditResult::from_response(&failed),
+            AuditResult::Failed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_tampering_is_detected() {
+        let path = temp_log("tamper");
+        let log = AuditLog::open(&path).unwrap();
+        for nonce in 0..3 {
+            log.record(
+                "order",
+                Some(nonce),
+                "0xaa",
+                "0xsigner",
+                AuditResult::Submitted,
+            )
+            .unwrap();
+        }
+        drop(log);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, contents.replacen("\"nonce\":1", "\"nonce\":9", 1)).unwrap();
+        assert!(verify_audit_log(&path).is_err());
+
+        // Dropping a line breaks the chain too
+        let lines: Vec<&str> = contents.lines().collect();
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        assert!(verify_audit_log(&path).is_err());
+        assert!(AuditLog::open(&path).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_hash_action_is_stable() {
+        let action = serde_json::json!({"type": "order", "nonce": 1});
+        assert_eq!(hash_action(&action).unwrap(), hash_action(&action).unwrap());
+        assert_eq!(hash_action(&action).unwrap().len(), 66);
+    }
+}
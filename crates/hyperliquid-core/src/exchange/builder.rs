@@ -0,0 +1,277 @@
+//! Validated construction of [`ExchangeClientConfig`]
+//!
+//! [`ExchangeClientConfig::builder`] collects the endpoint, account, signer,
+//! vault, dex and default slippage, and [`build`](ExchangeClientConfigBuilder::build)
+//! rejects combinations the exchange would refuse later: a key that is not
+//! the account's own used as the main signer, a mainnet key against the
+//! testnet endpoint, a vault that is the account itself, and so on.
+//!
+//! ```no_run
+//! # fn example() -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::crypto::Wallet;
+//! use hyperliquid_core::types::Environment;
+//! use hyperliquid_core::{ExchangeClient, ExchangeClientConfig};
+//!
+//! let account = "0x1234567890abcdef1234567890abcdef12345678".parse().unwrap();
+//! let agent = Wallet::mainnet("0x...agent key...")?;
+//! let config = ExchangeClientConfig::builder()
+//!     .environment(Environment::Mainnet)
+//!     .account(account)
+//!     .agent(agent)
+//!     .default_slippage_bps(30)
+//!     .build()?;
+//! let exchange = ExchangeClient::new(config);
+//! # Ok(()) }
+//! ```
+
+use std::str::FromStr;
+
+use ethers_core::types::Address;
+use reqwest::Url;
+
+use super::client::ExchangeClientConfig;
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::types::Environment;
+
+/// Slippage applied to market orders unless configured, in basis points
+pub const DEFAULT_SLIPPAGE_BPS: u32 = 500;
+
+/// How actions are signed
+#[derive(Debug, Clone)]
+pub enum SignerConfig {
+    /// The account's own key
+    Wallet(Wallet),
+    /// An API wallet the account approved with `approveAgent`
+    Agent(Wallet),
+    /// An external service holding the key of `address`
+    Remote { url: String, address: Address },
+}
+
+impl SignerConfig {
+    /// Address whose key produces the signatures
+    pub fn address(&self) -> Result<Address, HyperliquidError> {
+        match self {
+            SignerConfig::Wallet(wallet) | SignerConfig::Agent(wallet) => {
+                Address::from_str(&wallet.address())
+                    .map_err(|e| HyperliquidError::Config(format!("invalid signer address: {}", e)))
+            }
+            SignerConfig::Remote { address, .. } => Ok(*address),
+        }
+    }
+
+    /// Local key, unless signing is remote
+    pub fn wallet(&self) -> Option<&Wallet> {
+        match self {
+            SignerConfig::Wallet(wallet) | SignerConfig::Agent(wallet) => Some(wallet),
+            SignerConfig::Remote { .. } => None,
+        }
+    }
+}
+
+/// Builder for [`ExchangeClientConfig`]
+#[derive(Debug, Clone, Default)]
+pub struct ExchangeClientConfigBuilder {
+    environment: Option<Environment>,
+    base_url: Option<String>,
+    account: Option<Address>,
+    signer: Option<SignerConfig>,
+    api_key: Option<String>,
+    timeout: Option<u64>,
+    vault_address: Option<Address>,
+    dex: Option<String>,
+    default_slippage_bps: Option<u32>,
+}
+
+impl ExchangeClientConfig {
+    /// Start a validated configuration; see [`ExchangeClientConfigBuilder`]
+    pub fn builder() -> ExchangeClientConfigBuilder {
+        ExchangeClientConfigBuilder::default()
+    }
+
+    /// Local signing key, if one is configured
+    pub fn wallet(&self) -> Option<&Wallet> {
+        self.signer.as_ref().and_then(SignerConfig::wallet)
+    }
+}
+
+impl ExchangeClientConfigBuilder {
+    /// Use the endpoint of `environment` (mainnet if neither this nor
+    /// [`base_url`](Self::base_url) is set)
+    pub fn environment(mut self, environment: Environment) -> Self {
+        self.environment = Some(environment);
+        self
+    }
+
+    /// Use a custom endpoint, e.g. a proxy or a local node
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Account that trades; defaults to the address of a [`wallet`](Self::wallet)
+    pub fn account(mut self, account: Address) -> Self {
+        self.account = Some(account);
+        self
+    }
+
+    /// Sign with the account's own key
+    pub fn wallet(mut self, wallet: Wallet) -> Self {
+        self.signer = Some(SignerConfig::Wallet(wallet));
+        self
+    }
+
+    /// Sign with an approved API wallet on behalf of the [`account`](Self::account)
+    pub fn agent(mut self, agent: Wallet) -> Self {
+        self.signer = Some(SignerConfig::Agent(agent));
+        self
+    }
+
+    /// Have the service at `url` sign with the key of `address`
+    pub fn remote_signer(mut self, url: impl Into<String>, address: Address) -> Self {
+        self.signer = Some(SignerConfig::Remote {
+            url: url.into(),
+            address,
+        });
+        self
+    }
+
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Request timeout in seconds
+    pub fn timeout(mut self, timeout: u64) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Trade for a vault or sub-account the account manages
+    pub fn vault_address(mut self, vault_address: Address) -> Self {
+        self.vault_address = Some(vault_address);
+        self
+    }
+
+    /// Trade on a builder-deployed perp dex instead of the default one
+    pub fn dex(mut self, dex: impl Into<String>) -> Self {
+        self.dex = Some(dex.into());
+        self
+    }
+
+    /// Slippage for market orders, in basis points (default 500)
+    pub fn default_slippage_bps(mut self, slippage_bps: u32) -> Self {
+        self.default_slippage_bps = Some(slippage_bps);
+        self
+    }
+
+    /// Validate the settings and produce the configuration
+    pub fn build(self) -> Result<ExchangeClientConfig, HyperliquidError> {
+        let invalid = |message: String| Err(HyperliquidError::Config(message));
+
+        let base_url = match (&self.base_url, self.environment) {
+            (Some(url), _) => url.trim_end_matches('/').to_string(),
+            (None, Some(environment)) => environment.base_url().to_string(),
+            (None, None) => Environment::Mainnet.base_url().to_string(),
+        };
+        let url = Url::parse(&base_url).map_err(|e| {
+            HyperliquidError::Config(format!("invalid base URL {}: {}", base_url, e))
+        })?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return invalid(format!("base URL {} is not http(s)", base_url));
+        }
+
+        let url_environment = [Environment::Mainnet, Environment::Testnet]
+            .into_iter()
+            .find(|environment| environment.base_url() == base_url);
+        if let (Some(environment), Some(url_environment)) = (self.environment, url_environment) {
+            if environment != url_environment {
+                return invalid(format!(
+                    "base URL {} is the {:?} endpoint, not {:?}",
+                    base_url, url_environment, environment
+                ));
+            }
+        }
+        let is_mainnet = match self.environment.or(url_environment) {
+            Some(Environment::Mainnet) => Some(true),
+            Some(Environment::Testnet) => Some(false),
+            _ => None,
+        };
+
+        let signer_address = self
+            .signer
+            .as_ref()
+            .map(SignerConfig::address)
+            .transpose()?;
+        let account = match (&self.signer, self.account, signer_address) {
+            (_, Some(account), _) => account,
+            (Some(SignerConfig::Wallet(_)), None, Some(address)) => address,
+            (Some(_), None, _) => {
+                return invalid("an agent or remote signer needs the account it signs for".into())
+            }
+            (None, None, _) => return invalid("no account or signer configured".into()),
+        };
+
+        match &self.signer {
+            Some(SignerConfig::Wallet(_)) if signer_address != Some(account) => {
+                return invalid(format!(
+                    "wallet {:?} is not the key of account {:?}; configure it as an agent",
+                    signer_address.unwrap_or_default(),
+                    account
+                ));
+            }
+            Some(SignerConfig::Agent(_)) if signer_address == Some(account) => {
+                return invalid(
+                    "agent key is the account's own key; configure it as the wallet".into(),
+                );
+            }
+            Some(SignerConfig::Remote { url, .. }) => {
+                Url::parse(url).map_err(|e| {
+                    HyperliquidError::Config(format!("invalid remote signer URL {}: {}", url, e))
+                })?;
+            }
+            _ => {}
+        }
+        if let (Some(wallet), Some(is_mainnet)) = (
+            self.signer.as_ref().and_then(SignerConfig::wallet),
+            is_mainnet,
+        ) {
+            if wallet.is_mainnet() != is_mainnet {
+                let network = |mainnet: bool| if mainnet { "mainnet" } else { "testnet" };
+                return invalid(format!(
+                    "{} key used against the {} endpoint {}",
+                    network(wallet.is_mainnet()),
+                    network(is_mainnet),
+                    base_url
+                ));
+            }
+        }
+
+        if self.vault_address == Some(account) {
+            return invalid("vault address is the account itself".into());
+        }
+        if let Some(dex) = &self.dex {
+            if dex.is_empty() || !dex.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return invalid(format!("invalid dex name {:?}", dex));
+            }
+        }
+        let default_slippage_bps = self.default_slippage_bps.unwrap_or(DEFAULT_SLIPPAGE_BPS);
+        if default_slippage_bps > 10_000 {
+            return invalid(format!(
+                "default slippage of {} bps is over 100%",
+                default_slippage_bps
+            ));
+        }
+
+        Ok(ExchangeClientConfig {
+            base_url,
+            account,
+            api_key: self.api_key,
+            timeout: self.timeout,
+            signer: self.signer,
+            vault_address: self.vault_address,
+            dex: self.dex,
+            default_slippage_bps,
+        })
+    }
+}
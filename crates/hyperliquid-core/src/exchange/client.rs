@@ -9,9 +9,25 @@ use crate::{
         OpenOrdersRequest, OrderRequest, OrderResponse, OrderType, TimeInForce, TransferRequest,
         UpdateLeverageRequest, UpdateMarginRequest, Environment, UserState, UserStateRequest,
     },
+    memory::PooledObject,
     Client,
 };
+use crate::info::InfoClient;
+use crate::killswitch::{KillSwitchRegistry, Scope};
+use crate::margin::MarginCalculator;
+use crate::crypto::{action_types, generate_timestamp_nonce, EIP712Type, Signature, Wallet};
+use super::allowlist::{WithdrawalAllowlist, GUARDED_ACTIONS};
+use super::audit::{hash_action, hash_action_bytes, AuditLog, AuditResult};
+use super::builder::{SignerConfig, DEFAULT_SLIPPAGE_BPS};
+use super::dedup::{order_cloids, CloidRegistry};
+use super::latency::{OrderLatencyTracker, SubmissionId};
+use super::pool::{ExchangePoolStats, ExchangePools};
+use super::signer::{SigningExecutor, SigningExecutorStats};
+use super::signing::{sign_order_with_buffer, SignedActionBody};
+#[cfg(feature = "ws")]
+use crate::stream::{PostRequestType, WebSocketClient};
 use ethers_core::types::Address;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, instrument};
 
@@ -26,6 +42,14 @@ pub struct ExchangeClientConfig {
     pub api_key: Option<String>,
     /// Request timeout in seconds
     pub timeout: Option<u64>,
+    /// Key that signs actions (optional)
+    pub signer: Option<SignerConfig>,
+    /// Vault or sub-account traded on behalf of (optional)
+    pub vault_address: Option<Address>,
+    /// Builder-deployed perp dex (optional)
+    pub dex: Option<String>,
+    /// Slippage for market orders in basis points
+    pub default_slippage_bps: u32,
 }
 
 impl ExchangeClientConfig {
@@ -36,6 +60,10 @@ impl ExchangeClientConfig {
             account,
             api_key: None,
             timeout: None,
+            signer: None,
+            vault_address: None,
+            dex: None,
+            default_slippage_bps: DEFAULT_SLIPPAGE_BPS,
         }
     }
 
@@ -46,6 +74,10 @@ impl ExchangeClientConfig {
             account,
             api_key: None,
             timeout: None,
+            signer: None,
+            vault_address: None,
+            dex: None,
+            default_slippage_bps: DEFAULT_SLIPPAGE_BPS,
         }
     }
 
@@ -69,6 +101,24 @@ pub struct ExchangeClient {
     client: Client,
     /// Client configuration
     config: ExchangeClientConfig,
+    /// Recycled serialization buffers
+    pools: Arc<ExchangePools>,
+    /// Dedicated signing threads (signs inline when unset)
+    signer: Option<Arc<SigningExecutor>>,
+    /// Audit log of submitted actions
+    audit: Option<Arc<AuditLog>>,
+    /// Latency instrumentation of order actions
+    latency: Option<Arc<OrderLatencyTracker>>,
+    /// Recently submitted cloids, for duplicate suppression
+    cloids: Option<Arc<CloidRegistry>>,
+    /// Local margin check run before orders are submitted
+    margin_check: Option<MarginCalculator>,
+    /// Switches that refuse orders for disabled coins
+    kill_switches: Option<KillSwitchRegistry>,
+    /// Destinations fund transfers may go to
+    allowlist: Option<WithdrawalAllowlist>,
+    /// Asset index to coin mapping for checking signed order actions
+    assets: Option<Arc<InfoClient>>,
 }
 
 impl ExchangeClient {
@@ -89,9 +139,555 @@ impl ExchangeClient {
         Self {
             client: client_builder.build(),
             config,
+            pools: Arc::new(ExchangePools::new()),
+            signer: None,
+            audit: None,
+            latency: None,
+            cloids: None,
+            margin_check: None,
+            kill_switches: None,
+            allowlist: None,
+            assets: None,
         }
     }
 
+    /// Dispatch signing onto a dedicated executor instead of the calling thread
+    ///
+    /// Covers the L1 and user-signed actions of
+    /// [`post_signed_action`](Self::post_signed_action),
+    /// [`post_signed_action_ws`](Self::post_signed_action_ws) and
+    /// [`post_user_signed_action`](Self::post_user_signed_action).
+    pub fn with_signing_executor(mut self, executor: Arc<SigningExecutor>) -> Self {
+        self.signer = Some(executor);
+        self
+    }
+
+    /// Serialize an action as JSON into a pooled buffer
+    pub fn serialize_action<T: Serialize>(
+        &self,
+        action: &T,
+    ) -> Result<PooledObject<Vec<u8>>, HyperliquidError> {
+        self.pools.serialize(action)
+    }
+
+    /// Sign a batch of orders, reusing one pooled buffer for serialization
+    pub fn sign_orders(
+        &self,
+        orders: &[OrderRequest],
+        private_key: &[u8],
+    ) -> Result<Vec<String>, HyperliquidError> {
+        let mut buf = self.pools.buffer();
+        orders
+            .iter()
+            .map(|order| sign_order_with_buffer(order, private_key, &mut buf))
+            .collect()
+    }
+
+    /// Record every submitted action in a hash-chained audit log
+    pub fn with_audit_log(mut self, audit: Arc<AuditLog>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Timestamp order actions for latency measurement
+    pub fn with_latency_tracker(mut self, tracker: Arc<OrderLatencyTracker>) -> Self {
+        self.latency = Some(tracker);
+        self
+    }
+
+    /// Get the latency tracker, if one is configured
+    pub fn latency_tracker(&self) -> Option<&Arc<OrderLatencyTracker>> {
+        self.latency.as_ref()
+    }
+
+    /// Suppress duplicate submissions of orders by cloid
+    pub fn with_cloid_registry(mut self, registry: Arc<CloidRegistry>) -> Self {
+        self.cloids = Some(registry);
+        self
+    }
+
+    /// Get the cloid registry, if one is configured
+    pub fn cloid_registry(&self) -> Option<&Arc<CloidRegistry>> {
+        self.cloids.as_ref()
+    }
+
+    /// Reject orders locally when the account lacks the margin for them
+    ///
+    /// `calculator` should track this client's account. Orders that are not
+    /// reduce-only are checked with [`MarginCalculator::check_order`] and fail
+    /// with [`HyperliquidError::InsufficientMargin`] instead of being sent.
+    /// Signed order actions name assets by index, so they also need
+    /// [`with_assets`](Self::with_assets); without it they are refused.
+    pub fn with_margin_check(mut self, calculator: MarginCalculator) -> Self {
+        self.margin_check = Some(calculator);
+        self
+    }
+
+    /// Refuse orders for coins disabled in `switches`
+    ///
+    /// Orders that are not reduce-only fail with
+    /// [`HyperliquidError::TradingDisabled`] instead of being sent. While a
+    /// coin is disabled, signed order actions for assets that
+    /// [`with_assets`](Self::with_assets) can't map to a coin are refused.
+    pub fn with_kill_switches(mut self, switches: KillSwitchRegistry) -> Self {
+        self.kill_switches = Some(switches);
+        self
+    }
+
+    /// Get the kill switches, if configured
+    pub fn kill_switches(&self) -> Option<&KillSwitchRegistry> {
+        self.kill_switches.as_ref()
+    }
+
+    /// Map the asset indices of signed order actions to coins with `info`
+    ///
+    /// `info` should have its assets loaded with
+    /// [`InfoClient::initialize_assets`]; the kill switches and margin check
+    /// look orders up by coin.
+    pub fn with_assets(mut self, info: InfoClient) -> Self {
+        self.assets = Some(Arc::new(info));
+        self
+    }
+
+    /// Check the destination of withdrawals and transfers before signing
+    ///
+    /// See [`WithdrawalAllowlist`] for which actions are checked and how.
+    pub fn with_withdrawal_allowlist(mut self, allowlist: WithdrawalAllowlist) -> Self {
+        self.allowlist = Some(allowlist);
+        self
+    }
+
+    /// Get the withdrawal allowlist, if configured
+    pub fn withdrawal_allowlist(&self) -> Option<&WithdrawalAllowlist> {
+        self.allowlist.as_ref()
+    }
+
+    /// Check a user-signed action's destination against the allowlist, if configured
+    ///
+    /// Refused actions are recorded in the audit log under `signer`, since
+    /// they never reach [`submit`](Self::submit).
+    fn check_destination(
+        &self,
+        action: &serde_json::Value,
+        nonce: u64,
+        signer: &str,
+    ) -> Result<(), HyperliquidError> {
+        let action_type = action_type(action)?;
+        let destination = action.get("destination").and_then(|d| d.as_str()).unwrap_or_default();
+        self.guard_destination(&action_type, destination, action, nonce, signer)
+    }
+
+    /// Check the `destination` of a fund transfer against the allowlist, if configured
+    ///
+    /// Only [`GUARDED_ACTIONS`] are checked; `action` is hashed for the audit
+    /// record of a refusal.
+    fn guard_destination<T: Serialize>(
+        &self,
+        action_type: &str,
+        destination: &str,
+        action: &T,
+        nonce: u64,
+        signer: &str,
+    ) -> Result<(), HyperliquidError> {
+        let Some(allowlist) = &self.allowlist else {
+            return Ok(());
+        };
+        if !GUARDED_ACTIONS.contains(&action_type) {
+            return Ok(());
+        }
+        let checked = allowlist.check(action_type, destination);
+        if let (Err(e), Some(audit)) = (&checked, &self.audit) {
+            let recorded = hash_action(action).and_then(|hash| {
+                audit.record(
+                    action_type,
+                    Some(nonce as i64),
+                    &hash,
+                    signer,
+                    AuditResult::Failed { error: e.to_string() },
+                )
+            });
+            if let Err(e) = recorded {
+                error!("Failed to write audit record for refused {} action: {}", action_type, e);
+            }
+        }
+        checked
+    }
+
+    /// Check an order against the kill switches and margin calculator, if configured
+    ///
+    /// An order without a `limit_px`, such as a TWAP slice, is priced at the mark.
+    fn pre_check(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        sz: &str,
+        limit_px: Option<&str>,
+        reduce_only: Option<bool>,
+    ) -> Result<(), HyperliquidError> {
+        if let Some(switches) = &self.kill_switches {
+            if reduce_only != Some(true) {
+                switches.check(coin)?;
+            }
+        }
+        let calculator = match &self.margin_check {
+            Some(calculator) if reduce_only != Some(true) => calculator,
+            _ => return Ok(()),
+        };
+        let sz: f64 = sz
+            .parse()
+            .map_err(|_| HyperliquidError::Validation(format!("invalid size: {}", sz)))?;
+        let px = limit_px
+            .map(|limit_px| {
+                limit_px
+                    .parse::<f64>()
+                    .map_err(|_| HyperliquidError::Validation(format!("invalid price: {}", limit_px)))
+            })
+            .transpose()?;
+        calculator.check_order(coin, is_buy, sz, px)?;
+        Ok(())
+    }
+
+    /// Run the kill switch and margin checks on each order of an L1 action
+    ///
+    /// Covers `order`, `modify`, `batchModify` and `twapOrder`; reduce-only orders pass.
+    /// Orders for assets the asset map can't name are refused while a check
+    /// needs their coin.
+    fn check_action_orders(&self, action: &serde_json::Value) -> Result<(), HyperliquidError> {
+        let halts = self.kill_switches.as_ref().map(|switches| switches.halts()).unwrap_or_default();
+        if halts.is_empty() && self.margin_check.is_none() {
+            return Ok(());
+        }
+        for order in action_orders(action) {
+            if order.get("r").and_then(|r| r.as_bool()) == Some(true) {
+                continue;
+            }
+            let asset = order.get("a").and_then(|a| a.as_u64()).ok_or_else(|| {
+                HyperliquidError::Validation("order has no asset index `a`".to_string())
+            })?;
+            let coin = self
+                .assets
+                .as_ref()
+                .and_then(|info| info.symbol_for_asset(asset as u32))
+                .and_then(|symbol| symbol.name());
+            let Some(coin) = coin else {
+                // A global halt stops every order, whatever its coin
+                if let Some(halt) = halts.iter().find(|halt| halt.scope == Scope::All) {
+                    return Err(halt.error());
+                }
+                return Err(HyperliquidError::Validation(format!("cannot check order for unknown asset {}", asset)));
+            };
+            let is_buy = order.get("b").and_then(|b| b.as_bool()).unwrap_or(false);
+            let sz = order.get("s").and_then(|s| s.as_str()).unwrap_or_default();
+            let limit_px = order.get("p").and_then(|p| p.as_str());
+            self.pre_check(coin, is_buy, sz, limit_px, Some(false))?;
+        }
+        Ok(())
+    }
+
+    /// Client for another account sharing this one's connection pool
+    ///
+    /// The buffer pools, signing executor, audit log, latency tracker,
+    /// cloid registry, kill switches, withdrawal allowlist and asset map are shared too;
+    /// a margin check is not. The endpoint, API key and timeout of `config` are ignored
+    /// in favour of this client's.
+    pub fn for_account(&self, config: ExchangeClientConfig) -> Self {
+        Self {
+            client: self.client.clone(),
+            config,
+            pools: self.pools.clone(),
+            signer: self.signer.clone(),
+            audit: self.audit.clone(),
+            latency: self.latency.clone(),
+            cloids: self.cloids.clone(),
+            margin_check: None,
+            kill_switches: self.kill_switches.clone(),
+            allowlist: self.allowlist.clone(),
+            assets: self.assets.clone(),
+        }
+    }
+
+    /// Get signing executor statistics, if one is configured
+    pub fn signing_stats(&self) -> Option<SigningExecutorStats> {
+        self.signer.as_ref().map(|signer| signer.stats())
+    }
+
+    /// Submit an exchange action, recording per-action metrics
+    async fn post_action(&self, request: &ExchangeRequest) -> Result<String, HyperliquidError> {
+        self.submit(&request.type_, request.nonce.or(request.time), request, &self.account())
+            .await
+    }
+
+    /// Lowercase hex address of the configured account, recorded as the
+    /// signer of actions sent without a wallet
+    fn account(&self) -> String {
+        format!("{:#x}", self.config.account)
+    }
+
+    /// Sign an L1 action (orders, cancels, leverage, TWAP, ...) and submit it
+    ///
+    /// `action` is the wire-format action object including its `type` field.
+    #[instrument(skip(self, action, wallet))]
+    pub async fn post_signed_action(
+        &self,
+        action: serde_json::Value,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let action_type = action_type(&action)?;
+        self.check_action_orders(&action)?;
+        let cloids = self.cloids.as_ref().map(|_| order_cloids(&action)).unwrap_or_default();
+        if let Some(original) = self.claim_cloids(&cloids)? {
+            return Ok(serde_json::from_str(&original)?);
+        }
+        let tracked = self.track_latency(&action_type, &action);
+        let signed = self.sign_l1(&action, wallet, vault_address, tracked).await;
+        let (nonce, signature) = match signed {
+            Ok(signed) => signed,
+            Err(e) => {
+                self.complete_cloids(&cloids, None);
+                return Err(e);
+            }
+        };
+        let body = SignedActionBody {
+            action: &action,
+            nonce,
+            signature: &signature,
+            vault_address,
+        };
+
+        let signer = wallet.address().to_lowercase();
+        let submitted = self.submit(&action_type, Some(nonce as i64), &body, &signer).await;
+        self.complete_cloids(&cloids, submitted.as_deref().ok());
+        let response =
+            submitted.and_then(|response| Ok(serde_json::from_str::<serde_json::Value>(&response)?));
+        if let Some((tracker, id)) = tracked {
+            tracker.sent(id);
+            tracker.responded(id, response.as_ref().unwrap_or(&serde_json::Value::Null));
+        }
+        response
+    }
+
+    /// Sign an L1 action and submit it as a WebSocket post
+    ///
+    /// Same as [`post_signed_action`](Self::post_signed_action) but over an
+    /// open connection, which saves the HTTP round-trip setup and lets a
+    /// latency tracker stamp the send separately from the response.
+    #[instrument(skip(self, ws, action, wallet))]
+    #[cfg(feature = "ws")]
+    pub async fn post_signed_action_ws(
+        &self,
+        ws: &WebSocketClient,
+        action: serde_json::Value,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let action_type = action_type(&action)?;
+        self.check_action_orders(&action)?;
+        let cloids = self.cloids.as_ref().map(|_| order_cloids(&action)).unwrap_or_default();
+        if let Some(original) = self.claim_cloids(&cloids)? {
+            return Ok(serde_json::from_str(&original)?);
+        }
+        let tracked = self.track_latency(&action_type, &action);
+        let body = match self.sign_l1_body(action, wallet, vault_address, tracked).await {
+            Ok(body) => body,
+            Err(e) => {
+                self.complete_cloids(&cloids, None);
+                return Err(e);
+            }
+        };
+
+        let response = ws
+            .post_with_sent_hook(PostRequestType::Action, body, || {
+                if let Some((tracker, id)) = tracked {
+                    tracker.sent(id);
+                }
+            })
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()));
+        self.complete_cloids(&cloids, response.as_ref().ok().map(|r| r.to_string()).as_deref());
+        if let Some((tracker, id)) = tracked {
+            tracker.responded(id, response.as_ref().unwrap_or(&serde_json::Value::Null));
+        }
+        response
+    }
+
+    /// Register the cloids of an order action with the registry, if any
+    ///
+    /// Returns the raw response of an earlier submission to return instead.
+    fn claim_cloids(&self, cloids: &[String]) -> Result<Option<String>, HyperliquidError> {
+        match &self.cloids {
+            Some(registry) if !cloids.is_empty() => registry.claim(cloids),
+            _ => Ok(None),
+        }
+    }
+
+    /// Record the raw response of a claimed submission, or release its cloids
+    fn complete_cloids(&self, cloids: &[String], response: Option<&str>) {
+        if let Some(registry) = self.cloids.as_ref().filter(|_| !cloids.is_empty()) {
+            registry.complete(cloids, response);
+        }
+    }
+
+    /// Start a latency measurement if a tracker is set and this is an order
+    fn track_latency(
+        &self,
+        action_type: &str,
+        action: &serde_json::Value,
+    ) -> Option<(&OrderLatencyTracker, SubmissionId)> {
+        let tracker = self.latency.as_deref().filter(|_| action_type == "order")?;
+        Some((tracker, tracker.start(action)))
+    }
+
+    /// Sign an L1 action with a fresh nonce, on the signing executor if configured
+    async fn sign_l1(
+        &self,
+        action: &serde_json::Value,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+        tracked: Option<(&OrderLatencyTracker, SubmissionId)>,
+    ) -> Result<(u64, Signature), HyperliquidError> {
+        let nonce = generate_timestamp_nonce();
+        let signature = match &self.signer {
+            Some(signer) => {
+                let (action, wallet) = (action.clone(), wallet.clone());
+                let vault_address = vault_address.map(str::to_string);
+                signer
+                    .execute(move || wallet.sign_l1_action(&action, vault_address.as_deref(), nonce, None))
+                    .await??
+            }
+            None => wallet.sign_l1_action(action, vault_address, nonce, None)?,
+        };
+        if let Some((tracker, id)) = tracked {
+            tracker.signed(id);
+        }
+        Ok((nonce, signature))
+    }
+
+    /// Sign an L1 action into an `/exchange` request body for a WebSocket post
+    async fn sign_l1_body(
+        &self,
+        action: serde_json::Value,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+        tracked: Option<(&OrderLatencyTracker, SubmissionId)>,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let (nonce, signature) = self.sign_l1(&action, wallet, vault_address, tracked).await?;
+        Ok(serde_json::json!({
+            "action": action,
+            "nonce": nonce,
+            "signature": signature,
+            "vaultAddress": vault_address,
+        }))
+    }
+
+    /// Sign a user-signed action (USD/spot transfers, withdrawals) and submit it
+    ///
+    /// The action's `time` field doubles as its nonce. Destinations are
+    /// checked against the withdrawal allowlist, if configured, before
+    /// anything is signed.
+    #[instrument(skip(self, action, wallet, payload_types))]
+    pub async fn post_user_signed_action(
+        &self,
+        mut action: serde_json::Value,
+        wallet: &Wallet,
+        payload_types: &[EIP712Type],
+        primary_type: &str,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let nonce = action.get("time").and_then(|t| t.as_u64()).ok_or_else(|| {
+            HyperliquidError::Validation("user-signed action needs a numeric `time`".to_string())
+        })?;
+        let signer = wallet.address().to_lowercase();
+        self.check_destination(&action, nonce, &signer)?;
+        if let Some(fields) = action.as_object_mut() {
+            let chain = if wallet.is_mainnet() { "Mainnet" } else { "Testnet" };
+            fields.insert("hyperliquidChain".to_string(), chain.into());
+            fields.insert("signatureChainId".to_string(), "0x66eee".into());
+        }
+        let signature = match &self.signer {
+            Some(signer) => {
+                let (signed, wallet) = (action.clone(), wallet.clone());
+                let (payload_types, primary_type) = (payload_types.to_vec(), primary_type.to_string());
+                signer
+                    .execute(move || wallet.sign_user_signed_action(&signed, &payload_types, &primary_type))
+                    .await??
+            }
+            None => wallet.sign_user_signed_action(&action, payload_types, primary_type)?,
+        };
+        let action_type = action_type(&action)?;
+
+        let body = serde_json::json!({
+            "action": action,
+            "nonce": nonce,
+            "signature": signature,
+        });
+        let response = self.submit(&action_type, Some(nonce as i64), &body, &signer).await?;
+        Ok(serde_json::from_str(&response)?)
+    }
+
+    /// POST a body to `/exchange`, recording metrics and the audit trail
+    ///
+    /// The body is serialized once into a pooled buffer, which is both sent
+    /// and hashed for the audit log, where it is recorded under `signer`.
+    async fn submit<T: Serialize>(
+        &self,
+        action: &str,
+        nonce: Option<i64>,
+        body: &T,
+        signer: &str,
+    ) -> Result<String, HyperliquidError> {
+        let action = action.to_string();
+        let body = self.pools.serialize(body)?;
+        let start = std::time::Instant::now();
+        let result = self.client.post_raw("/exchange", &body).await;
+
+        crate::telemetry::counter!("hyperliquid_exchange_actions_total", "action" => action.clone()).increment(1);
+        crate::telemetry::histogram!("hyperliquid_exchange_action_duration_seconds", "action" => action.clone())
+            .record(start.elapsed().as_secs_f64());
+        if result.is_err() {
+            crate::telemetry::counter!("hyperliquid_exchange_action_errors_total", "action" => action.clone()).increment(1);
+        }
+
+        if let Some(audit) = &self.audit {
+            self.audit_action(audit, &action, nonce, &body, signer, &result);
+        }
+        result
+    }
+
+    /// Append a submitted action to the audit log
+    ///
+    /// The action has already been sent, so audit failures are logged rather
+    /// than returned in place of the exchange result.
+    fn audit_action(
+        &self,
+        audit: &AuditLog,
+        action: &str,
+        nonce: Option<i64>,
+        body: &[u8],
+        signer: &str,
+        result: &Result<String, HyperliquidError>,
+    ) {
+        let recorded = audit.record(
+            action,
+            nonce,
+            &hash_action_bytes(body),
+            signer,
+            AuditResult::from_response(result),
+        );
+        if let Err(e) = recorded {
+            error!("Failed to write audit record for {} action: {}", action, e);
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &ExchangeClientConfig {
+        &self.config
+    }
+
+    /// Get buffer pool statistics
+    pub fn pool_stats(&self) -> ExchangePoolStats {
+        self.pools.stats()
+    }
+
     /// Place a new order (replaces place_order for Feature #101 compatibility)
     #[instrument(skip(self))]
     pub async fn order(
@@ -105,6 +701,11 @@ impl ExchangeClient {
         cloid: Option<String>,
         time_in_force: Option<TimeInForce>,
     ) -> Result<OrderResponse, HyperliquidError> {
+        self.pre_check(coin, is_buy, sz, Some(limit_px), reduce_only)?;
+        let cloids: Vec<String> = cloid.iter().cloned().collect();
+        if let Some(original) = self.claim_cloids(&cloids)? {
+            return Ok(serde_json::from_str(&original)?);
+        }
         let order = OrderRequest {
             coin: coin.to_string(),
             is_buy,
@@ -135,8 +736,9 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
-        let order_response: OrderResponse = serde_json::from_str(&response)?;
+        let response = self.post_action(&request).await;
+        self.complete_cloids(&cloids, response.as_deref().ok());
+        let order_response: OrderResponse = serde_json::from_str(&response?)?;
         Ok(order_response)
     }
 
@@ -193,7 +795,14 @@ impl ExchangeClient {
         orders: Vec<OrderRequest>,
         _private_key: &[u8],
     ) -> Result<OrderResponse, HyperliquidError> {
-        let bulk_request = BulkOrderRequest { orders };
+        for order in &orders {
+            self.pre_check(&order.coin, order.is_buy, &order.sz, Some(&order.limit_px), order.reduce_only)?;
+        }
+        let cloids: Vec<String> = orders.iter().filter_map(|order| order.cloid.clone()).collect();
+        if let Some(original) = self.claim_cloids(&cloids)? {
+            return Ok(serde_json::from_str(&original)?);
+        }
+        let bulk_request = BulkOrderRequest { orders: orders.into() };
         let request = ExchangeRequest {
             type_: "bulkOrder".to_string(),
             time: Some(chrono::Utc::now().timestamp_millis()),
@@ -210,8 +819,9 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
-        let order_response: OrderResponse = serde_json::from_str(&response)?;
+        let response = self.post_action(&request).await;
+        self.complete_cloids(&cloids, response.as_deref().ok());
+        let order_response: OrderResponse = serde_json::from_str(&response?)?;
         Ok(order_response)
     }
 
@@ -238,7 +848,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -266,7 +876,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -294,7 +904,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -306,6 +916,8 @@ impl ExchangeClient {
         modify: ModifyRequest,
         _private_key: &[u8],
     ) -> Result<OrderResponse, HyperliquidError> {
+        let order = &modify.order;
+        self.pre_check(&order.coin, order.is_buy, &order.sz, Some(&order.limit_px), order.reduce_only)?;
         let request = ExchangeRequest {
             type_: "modify".to_string(),
             time: Some(chrono::Utc::now().timestamp_millis()),
@@ -322,7 +934,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -350,7 +962,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -362,7 +974,7 @@ impl ExchangeClient {
         cancels: Vec<CancelRequest>,
         _private_key: &[u8],
     ) -> Result<OrderResponse, HyperliquidError> {
-        let bulk_cancel = BulkCancelRequest { cancels };
+        let bulk_cancel = BulkCancelRequest { cancels: cancels.into() };
         let request = ExchangeRequest {
             type_: "bulkCancel".to_string(),
             time: Some(chrono::Utc::now().timestamp_millis()),
@@ -379,7 +991,7 @@ impl ExchangeClient {
             bulk_cancel: Some(bulk_cancel),
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -411,6 +1023,59 @@ impl ExchangeClient {
         Ok(open_orders_response)
     }
 
+    /// Withdraw `amount` USDC to `destination` on Arbitrum
+    pub async fn withdraw(
+        &self,
+        destination: &str,
+        amount: &str,
+        wallet: &Wallet,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let action = serde_json::json!({
+            "type": "withdraw3",
+            "destination": destination,
+            "amount": amount,
+            "time": chrono::Utc::now().timestamp_millis() as u64,
+        });
+        self.post_user_signed_action(action, wallet, action_types::WITHDRAW, "HyperliquidTransaction:Withdraw")
+            .await
+    }
+
+    /// Send `amount` USDC of perp balance to `destination`
+    pub async fn usd_send(
+        &self,
+        destination: &str,
+        amount: &str,
+        wallet: &Wallet,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let action = serde_json::json!({
+            "type": "usdSend",
+            "destination": destination,
+            "amount": amount,
+            "time": chrono::Utc::now().timestamp_millis() as u64,
+        });
+        self.post_user_signed_action(action, wallet, action_types::USD_SEND, "HyperliquidTransaction:UsdSend")
+            .await
+    }
+
+    /// Send `amount` of spot `token` (`NAME:0x<token id>`) to `destination`
+    pub async fn spot_send(
+        &self,
+        destination: &str,
+        token: &str,
+        amount: &str,
+        wallet: &Wallet,
+    ) -> Result<serde_json::Value, HyperliquidError> {
+        let action = serde_json::json!({
+            "type": "spotSend",
+            "destination": destination,
+            "token": token,
+            "amount": amount,
+            "time": chrono::Utc::now().timestamp_millis() as u64,
+        });
+        self.post_user_signed_action(action, wallet, action_types::SPOT_TRANSFER, "HyperliquidTransaction:SpotSend")
+            .await
+    }
+
     /// Transfer funds between accounts
     #[instrument(skip(self))]
     pub async fn transfer(
@@ -418,9 +1083,12 @@ impl ExchangeClient {
         transfer: TransferRequest,
         _private_key: &[u8],
     ) -> Result<types::TransferResponse, HyperliquidError> {
+        let time = chrono::Utc::now().timestamp_millis();
+        let account = self.account();
+        self.guard_destination("transfer", &transfer.destination, &transfer, time as u64, &account)?;
         let request = ExchangeRequest {
             type_: "transfer".to_string(),
-            time: Some(chrono::Utc::now().timestamp_millis()),
+            time: Some(time),
             nonce: None,
             orders: None,
             cancels: None,
@@ -434,7 +1102,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let transfer_response: types::TransferResponse = serde_json::from_str(&response)?;
         Ok(transfer_response)
     }
@@ -462,7 +1130,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -490,7 +1158,7 @@ impl ExchangeClient {
             bulk_cancel: None,
         };
 
-        let response = self.client.post("/exchange", &request).await?;
+        let response = self.post_action(&request).await?;
         let order_response: OrderResponse = serde_json::from_str(&response)?;
         Ok(order_response)
     }
@@ -511,6 +1179,27 @@ impl ExchangeClient {
     }
 }
 
+/// Orders of an `order`, `modify`, `batchModify` or `twapOrder` action
+fn action_orders(action: &serde_json::Value) -> Vec<&serde_json::Value> {
+    let list = |key: &str| action.get(key).and_then(|v| v.as_array()).into_iter().flatten();
+    match action.get("type").and_then(|t| t.as_str()) {
+        Some("order") => list("orders").collect(),
+        Some("modify") => action.get("order").into_iter().collect(),
+        Some("batchModify") => list("modifies").filter_map(|modify| modify.get("order")).collect(),
+        Some("twapOrder") => action.get("twap").into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Read the `type` field of a wire-format action
+fn action_type(action: &serde_json::Value) -> Result<String, HyperliquidError> {
+    action
+        .get("type")
+        .and_then(|t| t.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| HyperliquidError::Validation("action has no `type` field".to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -527,6 +1216,52 @@ mod tests {
         assert!(config.timeout.is_none());
     }
 
+    #[test]
+    fn test_serialize_action_pooling() {
+        let address = "0x1234567890abcdef1234567890abcdef12345678".parse().unwrap();
+        let client = ExchangeClient::new(ExchangeClientConfig::testnet(address));
+        let action = serde_json::json!({"type": "cancel", "cancels": [{"a": 0, "o": 1}]});
+
+        for _ in 0..100 {
+            let json = client.serialize_action(&action).unwrap();
+            assert_eq!(&json[..], serde_json::to_vec(&action).unwrap().as_slice());
+        }
+
+        let stats = client.pool_stats();
+        assert_eq!(stats.buffers.pool_misses, 1);
+        assert_eq!(stats.buffers.pool_hits, 99);
+    }
+
+    #[tokio::test]
+    async fn test_signing_runs_on_signing_executor() {
+        let mut server = mockito::Server::new_async().await;
+        let sent = server
+            .mock("POST", "/exchange")
+            .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        let wallet = Wallet::new("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318", false).unwrap();
+        let mut config = ExchangeClientConfig::testnet(wallet.address().parse().unwrap());
+        config.base_url = server.url();
+        let client = ExchangeClient::new(config)
+            .with_signing_executor(Arc::new(SigningExecutor::new().unwrap()));
+
+        // Same signature as signing inline
+        let cancel = serde_json::json!({"type": "cancel", "cancels": [{"a": 0, "o": 1}]});
+        let (nonce, signature) = client.sign_l1(&cancel, &wallet, None, None).await.unwrap();
+        let inline = wallet.sign_l1_action(&cancel, None, nonce, None).unwrap();
+        assert_eq!(serde_json::to_value(&signature).unwrap(), serde_json::to_value(&inline).unwrap());
+
+        client.post_signed_action(cancel, &wallet, None).await.unwrap();
+        client
+            .usd_send("0x1111111111111111111111111111111111111111", "1", &wallet)
+            .await
+            .unwrap();
+        sent.assert_async().await;
+        assert_eq!(client.signing_stats().unwrap().submitted, 3);
+    }
+
     #[test]
     fn test_exchange_client_config_with_api_key() {
         let address = "0x1234567890abcdef1234567890abcdef12345678".parse().unwrap();
@@ -0,0 +1,223 @@
+//! Closing a perp position with a reduce-only IOC order
+//!
+//! [`ExchangeClient::close_position`] reads the position size from
+//! `clearinghouseState`; [`ExchangeClient::close_position_with_szi`] takes it
+//! from the caller, e.g. a [`PositionTracker`](crate::positions::PositionTracker).
+//! Either way the order is sided against the position, priced off the mid
+//! with the given slippage, and sized so that what is left is never too
+//! small to close later.
+
+use serde_json::{json, Value};
+
+use super::client::ExchangeClient;
+use super::replace::{leg_status, LegStatus};
+use crate::client::HttpClient;
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::execution::{round_px, round_sz};
+use crate::types::precision::{PrecisionError, WireFormat};
+use crate::types::Meta;
+
+/// Smallest order value the exchange accepts, in USD
+///
+/// A remainder worth less than this can only be closed by a reduce-only
+/// order for the whole position, so partial closes that would leave one
+/// close everything instead.
+pub const MIN_ORDER_VALUE: f64 = 10.0;
+
+/// How much of a position to close
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CloseAmount {
+    All,
+    /// Share of the position between 0 and 1
+    Fraction(f64),
+    /// Size in coins, capped at the position size
+    Size(f64),
+}
+
+/// Outcome of [`ExchangeClient::close_position`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CloseResult {
+    pub coin: String,
+    /// Signed position size before the close
+    pub szi: f64,
+    /// Whether the close was a buy (closing a short)
+    pub is_buy: bool,
+    /// Size sent; 0 if nothing was sent
+    pub sz: f64,
+    pub limit_px: f64,
+    /// [`LegStatus::Skipped`] when the position was flat or the size rounded
+    /// to zero
+    pub leg: LegStatus,
+    /// Order status reported by the exchange (`{"filled": ...}` for a fill)
+    pub status: Option<Value>,
+}
+
+impl CloseResult {
+    /// Size filled by the IOC
+    pub fn filled_sz(&self) -> f64 {
+        self.status
+            .as_ref()
+            .and_then(|status| status.pointer("/filled/totalSz"))
+            .and_then(Value::as_str)
+            .and_then(|sz| sz.parse().ok())
+            .unwrap_or(0.0)
+    }
+}
+
+/// Size and limit price of an order closing `amount` of `szi`
+///
+/// Returns `(is_buy, sz, limit_px)`; `sz` is 0 when there is nothing to close.
+pub fn close_order_params(
+    szi: f64,
+    amount: CloseAmount,
+    sz_decimals: u32,
+    mid: f64,
+    slippage_bps: u32,
+) -> (bool, f64, f64) {
+    let scale = 10f64.powi(sz_decimals as i32);
+    // Positions are on the size grid; tracked ones may carry float noise
+    let position = (szi.abs() * scale).round() / scale;
+    let requested = match amount {
+        CloseAmount::All => position,
+        CloseAmount::Fraction(fraction) => position * fraction.clamp(0.0, 1.0),
+        CloseAmount::Size(sz) => sz.max(0.0).min(position),
+    };
+
+    let mut sz = round_sz(requested, sz_decimals);
+    let remainder = position - sz;
+    if sz > 0.0 && (remainder < 1.0 / scale || remainder * mid < MIN_ORDER_VALUE) {
+        sz = position;
+    }
+
+    let is_buy = szi < 0.0;
+    let slippage = f64::from(slippage_bps) / 10_000.0;
+    let px = if is_buy {
+        mid * (1.0 + slippage)
+    } else {
+        mid * (1.0 - slippage)
+    };
+    (is_buy, sz, round_px(px, sz_decimals))
+}
+
+impl ExchangeClient {
+    /// Close `amount` of the position in `coin` with a reduce-only IOC
+    ///
+    /// The position is that of `vault_address` when given, otherwise of the
+    /// configured account. `info` is used for `clearinghouseState`, `meta` and
+    /// `allMids`. The IOC is priced `slippage_bps` through the mid, so it may
+    /// fill only partly; see [`CloseResult::filled_sz`].
+    pub async fn close_position(
+        &self,
+        info: &HttpClient,
+        coin: &str,
+        amount: CloseAmount,
+        slippage_bps: u32,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<CloseResult, HyperliquidError> {
+        let user = match vault_address {
+            Some(vault) => vault.to_string(),
+            None => format!("{:?}", self.config().account),
+        };
+        let state: Value = info
+            .post(
+                "/info",
+                &json!({"type": "clearinghouseState", "user": user}),
+            )
+            .await?;
+        let szi = state
+            .get("assetPositions")
+            .and_then(Value::as_array)
+            .and_then(|positions| {
+                positions
+                    .iter()
+                    .filter_map(|entry| entry.get("position"))
+                    .find(|position| position.get("coin").and_then(Value::as_str) == Some(coin))
+            })
+            .and_then(|position| position.get("szi"))
+            .and_then(Value::as_str)
+            .and_then(|szi| szi.parse::<f64>().ok())
+            .unwrap_or(0.0);
+
+        self.close_position_with_szi(info, coin, szi, amount, slippage_bps, wallet, vault_address)
+            .await
+    }
+
+    /// Like [`close_position`](Self::close_position) for a known position size
+    #[allow(clippy::too_many_arguments)]
+    pub async fn close_position_with_szi(
+        &self,
+        info: &HttpClient,
+        coin: &str,
+        szi: f64,
+        amount: CloseAmount,
+        slippage_bps: u32,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<CloseResult, HyperliquidError> {
+        let skipped = |is_buy, limit_px| CloseResult {
+            coin: coin.to_string(),
+            szi,
+            is_buy,
+            sz: 0.0,
+            limit_px,
+            leg: LegStatus::Skipped,
+            status: None,
+        };
+        if szi == 0.0 {
+            return Ok(skipped(false, 0.0));
+        }
+
+        let meta: Meta = info.post("/info", &json!({"type": "meta"})).await?;
+        let (asset, sz_decimals) = meta
+            .universe
+            .iter()
+            .position(|asset| asset.name == coin)
+            .map(|index| (index as u32, meta.universe[index].szDecimals.max(0) as u32))
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown coin: {}", coin)))?;
+        let mids: Value = info.post("/info", &json!({"type": "allMids"})).await?;
+        let mid = mids
+            .get(coin)
+            .and_then(Value::as_str)
+            .and_then(|mid| mid.parse::<f64>().ok())
+            .ok_or_else(|| HyperliquidError::Validation(format!("no mid price for {}", coin)))?;
+
+        let (is_buy, sz, limit_px) =
+            close_order_params(szi, amount, sz_decimals, mid, slippage_bps);
+        if sz <= 0.0 {
+            return Ok(skipped(is_buy, limit_px));
+        }
+
+        let format = WireFormat::perp(sz_decimals);
+        let wire = |value: Result<String, PrecisionError>| {
+            value.map_err(|e| HyperliquidError::Validation(e.to_string()))
+        };
+        let action = json!({
+            "type": "order",
+            "orders": [{
+                "a": asset,
+                "b": is_buy,
+                "p": wire(format.price_f64(limit_px))?,
+                "s": wire(format.size_f64(sz))?,
+                "r": true,
+                "t": {"limit": {"tif": "Ioc"}},
+            }],
+            "grouping": "na",
+        });
+        let response = self
+            .post_signed_action(action, wallet, vault_address)
+            .await?;
+
+        let (leg, status) = leg_status(&response);
+        Ok(CloseResult {
+            coin: coin.to_string(),
+            szi,
+            is_buy,
+            sz,
+            limit_px,
+            leg,
+            status,
+        })
+    }
+}
@@ -0,0 +1,236 @@
+//! Duplicate order suppression by cloid
+//!
+//! A [`CloidRegistry`] attached with [`ExchangeClient::with_cloid_registry`]
+//! remembers the cloids of recently submitted orders. Submitting one of them
+//! again within the registry's time-to-live is a double fire, which is
+//! rejected, or answered with the response of the first submission under
+//! [`DuplicatePolicy::ReturnOriginal`]. A submission that fails without a
+//! response releases its cloids, so it can be retried with the same ones.
+//!
+//! Orders without a cloid are not tracked.
+//!
+//! [`ExchangeClient::with_cloid_registry`]: super::ExchangeClient::with_cloid_registry
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use crate::error::HyperliquidError;
+
+/// Default time a submitted cloid is remembered
+pub const DEFAULT_CLOID_TTL: Duration = Duration::from_secs(60);
+
+/// What to do with an order whose cloid was recently submitted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Fail with a validation error
+    #[default]
+    Reject,
+    /// Return the response of the first submission once it has one
+    ReturnOriginal,
+}
+
+struct Entry {
+    submitted_at: Instant,
+    /// Raw response of the submission, `None` while in flight
+    response: Option<String>,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    entries: HashMap<String, Entry>,
+    /// Cloids in submission order, for expiry
+    order: VecDeque<(Instant, String)>,
+}
+
+impl RegistryState {
+    fn expire(&mut self, now: Instant, ttl: Duration) {
+        while let Some((submitted_at, _)) = self.order.front() {
+            if now.duration_since(*submitted_at) < ttl {
+                break;
+            }
+            let (submitted_at, cloid) = self.order.pop_front().expect("front exists");
+            // A released and re-submitted cloid has a newer entry
+            if self
+                .entries
+                .get(&cloid)
+                .is_some_and(|entry| entry.submitted_at == submitted_at)
+            {
+                self.entries.remove(&cloid);
+            }
+        }
+    }
+}
+
+/// Short-lived registry of submitted cloids
+pub struct CloidRegistry {
+    ttl: Duration,
+    policy: DuplicatePolicy,
+    state: Mutex<RegistryState>,
+}
+
+impl CloidRegistry {
+    /// Remember cloids for `ttl`, rejecting duplicates
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            policy: DuplicatePolicy::default(),
+            state: Mutex::new(RegistryState::default()),
+        }
+    }
+
+    /// Set how a duplicate cloid is handled
+    pub fn with_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// How long a submitted cloid is remembered
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// How a duplicate cloid is handled
+    pub fn policy(&self) -> DuplicatePolicy {
+        self.policy
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RegistryState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Register the cloids of an order action about to be submitted
+    ///
+    /// Returns `Ok(None)` if none of them was seen within the time-to-live,
+    /// or the response of the earlier submission to return instead under
+    /// [`DuplicatePolicy::ReturnOriginal`]. Fails if any cloid is a
+    /// duplicate otherwise, including a cloid repeated within `cloids`.
+    pub fn claim(&self, cloids: &[String]) -> Result<Option<String>, HyperliquidError> {
+        let mut unique = HashSet::with_capacity(cloids.len());
+        if let Some(repeated) = cloids.iter().find(|cloid| !unique.insert(cloid.as_str())) {
+            return Err(HyperliquidError::Validation(format!(
+                "cloid {} appears twice in one action",
+                repeated
+            )));
+        }
+
+        let now = Instant::now();
+        let mut state = self.lock();
+        state.expire(now, self.ttl);
+
+        let seen: Vec<&Entry> = cloids
+            .iter()
+            .filter_map(|cloid| state.entries.get(cloid))
+            .collect();
+        if let Some(first) = seen.first() {
+            crate::telemetry::counter!("hyperliquid_exchange_duplicate_orders_total").increment(1);
+            // Replaying needs the whole action to be the one first submitted
+            let replay = self.policy == DuplicatePolicy::ReturnOriginal
+                && seen.len() == cloids.len()
+                && seen.iter().all(|entry| {
+                    entry.submitted_at == first.submitted_at && entry.response.is_some()
+                });
+            if replay {
+                return Ok(first.response.clone());
+            }
+            let duplicates: Vec<&str> = cloids
+                .iter()
+                .filter(|cloid| state.entries.contains_key(*cloid))
+                .map(String::as_str)
+                .collect();
+            return Err(HyperliquidError::Validation(format!(
+                "duplicate submission of cloid {}",
+                duplicates.join(", ")
+            )));
+        }
+
+        for cloid in cloids {
+            state.entries.insert(
+                cloid.clone(),
+                Entry {
+                    submitted_at: now,
+                    response: None,
+                },
+            );
+            state.order.push_back((now, cloid.clone()));
+        }
+        Ok(None)
+    }
+
+    /// Record the outcome of a claimed submission
+    ///
+    /// Without a response the cloids are released for a retry.
+    pub fn complete(&self, cloids: &[String], response: Option<&str>) {
+        let mut state = self.lock();
+        for cloid in cloids {
+            match response {
+                Some(response) => {
+                    if let Some(entry) = state.entries.get_mut(cloid) {
+                        entry.response = Some(response.to_string());
+                    }
+                }
+                None => {
+                    state.entries.remove(cloid);
+                }
+            }
+        }
+    }
+
+    /// Whether `cloid` was submitted within the time-to-live
+    pub fn contains(&self, cloid: &str) -> bool {
+        let mut state = self.lock();
+        state.expire(Instant::now(), self.ttl);
+        state.entries.contains_key(cloid)
+    }
+
+    /// Number of cloids remembered
+    pub fn len(&self) -> usize {
+        let mut state = self.lock();
+        state.expire(Instant::now(), self.ttl);
+        state.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget every cloid
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+}
+
+impl Default for CloidRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLOID_TTL)
+    }
+}
+
+impl std::fmt::Debug for CloidRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CloidRegistry")
+            .field("ttl", &self.ttl)
+            .field("policy", &self.policy)
+            .field("cloids", &self.lock().entries.len())
+            .finish()
+    }
+}
+
+/// Cloids of the orders in an `order` action
+pub(crate) fn order_cloids(action: &Value) -> Vec<String> {
+    if action.get("type").and_then(Value::as_str) != Some("order") {
+        return Vec::new();
+    }
+    action
+        .get("orders")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|order| order.get("c").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,353 @@
+//! Order placement latency measurement
+//!
+//! An [`OrderLatencyTracker`] attached with
+//! [`ExchangeClient::with_latency_tracker`] timestamps every order action
+//! before signing, after signing and after sending, then again when the
+//! placement response and the `orderUpdates` acknowledgement arrive. Orders
+//! are matched to their acknowledgement by cloid, or by the oid from the
+//! placement response. Each acknowledged order yields an [`OrderLatency`]
+//! breakdown, recorded in the `hyperliquid_order_latency_seconds` histogram
+//! with a `stage` label of `sign`, `send`, `response`, `ack` or `total`.
+//!
+//! Over HTTP the request is only known to be sent once its response arrives,
+//! so the `send` stage includes the round-trip and `response` is zero; use
+//! [`ExchangeClient::post_signed_action_ws`] to separate them.
+//!
+//! ```no_run
+//! # async fn example(
+//! #     exchange: hyperliquid_core::ExchangeClient,
+//! #     ws: hyperliquid_core::stream::WebSocketClient,
+//! #     user: hyperliquid_core::types::Address,
+//! # ) -> Result<(), Box<dyn std::error::Error>> {
+//! use std::sync::Arc;
+//! use hyperliquid_core::exchange::OrderLatencyTracker;
+//! use hyperliquid_core::Subscription;
+//!
+//! let tracker = Arc::new(OrderLatencyTracker::new(1000));
+//! let exchange = exchange.with_latency_tracker(tracker.clone());
+//! let acks = tracker.clone();
+//! ws.register_handler(Subscription::OrderUpdates { user }, move |message| {
+//!     acks.handle_order_updates(&message.data);
+//! })
+//! .await;
+//! // ... place orders with exchange.post_signed_action_ws(&ws, ...)
+//! for latency in tracker.completed() {
+//!     println!("{:?}: {:?}", latency.cloid, latency.total);
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! [`ExchangeClient::with_latency_tracker`]: super::ExchangeClient::with_latency_tracker
+//! [`ExchangeClient::post_signed_action_ws`]: super::ExchangeClient::post_signed_action_ws
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Acknowledgements kept while waiting for the placement response that
+/// names their oid
+const MAX_EARLY_ACKS: usize = 1024;
+
+/// Handle of one submitted order action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubmissionId(u64);
+
+/// Latency breakdown of one order
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderLatency {
+    pub submission: SubmissionId,
+    /// Asset index of the order
+    pub asset: Option<u32>,
+    pub cloid: Option<String>,
+    pub oid: Option<u64>,
+    /// Pre-sign to post-sign
+    pub sign: Option<Duration>,
+    /// Post-sign to post-send
+    pub send: Option<Duration>,
+    /// Post-send to the placement response
+    pub response: Option<Duration>,
+    /// Post-send to the `orderUpdates` acknowledgement; `None` if the order
+    /// was rejected in the placement response
+    pub ack: Option<Duration>,
+    /// Pre-sign to the acknowledgement
+    pub total: Option<Duration>,
+}
+
+struct PendingOrder {
+    submission: u64,
+    asset: Option<u32>,
+    cloid: Option<String>,
+    oid: Option<u64>,
+    pre_sign: Instant,
+    post_sign: Option<Instant>,
+    post_send: Option<Instant>,
+    response: Option<Instant>,
+}
+
+impl PendingOrder {
+    fn complete(self, ack: Option<Instant>) -> OrderLatency {
+        let between =
+            |from: Option<Instant>, to: Option<Instant>| Some(to?.saturating_duration_since(from?));
+        let latency = OrderLatency {
+            submission: SubmissionId(self.submission),
+            asset: self.asset,
+            cloid: self.cloid,
+            oid: self.oid,
+            sign: between(Some(self.pre_sign), self.post_sign),
+            send: between(self.post_sign, self.post_send),
+            response: between(self.post_send, self.response),
+            ack: between(self.post_send, ack),
+            total: between(Some(self.pre_sign), ack),
+        };
+        for (stage, duration) in [
+            ("sign", latency.sign),
+            ("send", latency.send),
+            ("response", latency.response),
+            ("ack", latency.ack),
+            ("total", latency.total),
+        ] {
+            if let Some(duration) = duration {
+                crate::telemetry::histogram!("hyperliquid_order_latency_seconds", "stage" => stage)
+                    .record(duration.as_secs_f64());
+            }
+        }
+        latency
+    }
+}
+
+#[derive(Default)]
+struct LatencyState {
+    next_submission: u64,
+    pending: Vec<PendingOrder>,
+    /// Acknowledgements by oid that arrived before the placement response
+    early_acks: HashMap<u64, Instant>,
+    completed: VecDeque<OrderLatency>,
+}
+
+/// Timestamps order actions and matches them to their acknowledgements
+pub struct OrderLatencyTracker {
+    capacity: usize,
+    state: Mutex<LatencyState>,
+}
+
+impl OrderLatencyTracker {
+    /// Keep the breakdowns of the last `capacity` orders
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(LatencyState::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LatencyState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stamp an order action as about to be signed
+    pub fn start(&self, action: &Value) -> SubmissionId {
+        let now = Instant::now();
+        let mut state = self.lock();
+        let submission = state.next_submission;
+        state.next_submission += 1;
+
+        let orders = action.get("orders").and_then(Value::as_array);
+        for order in orders.into_iter().flatten() {
+            state.pending.push(PendingOrder {
+                submission,
+                asset: order.get("a").and_then(Value::as_u64).map(|a| a as u32),
+                cloid: order.get("c").and_then(Value::as_str).map(str::to_string),
+                oid: None,
+                pre_sign: now,
+                post_sign: None,
+                post_send: None,
+                response: None,
+            });
+        }
+        SubmissionId(submission)
+    }
+
+    /// Stamp a submission as signed
+    pub fn signed(&self, id: SubmissionId) {
+        let now = Instant::now();
+        for order in self.lock().pending.iter_mut() {
+            if order.submission == id.0 {
+                order.post_sign = Some(now);
+            }
+        }
+    }
+
+    /// Stamp a submission as handed to the transport
+    pub fn sent(&self, id: SubmissionId) {
+        let now = Instant::now();
+        for order in self.lock().pending.iter_mut() {
+            if order.submission == id.0 {
+                order.post_send = Some(now);
+            }
+        }
+    }
+
+    /// Stamp the placement response and learn the oids it assigns
+    ///
+    /// Orders rejected by the response, or all of them if the whole action
+    /// failed, complete here without an acknowledgement.
+    pub fn responded(&self, id: SubmissionId, response: &Value) {
+        let now = Instant::now();
+        let failed = response.get("status").and_then(Value::as_str) != Some("ok");
+        let statuses = response
+            .pointer("/response/data/statuses")
+            .and_then(Value::as_array);
+
+        let mut state = self.lock();
+        let state = &mut *state;
+        let mut index = 0;
+        let mut i = 0;
+        while i < state.pending.len() {
+            if state.pending[i].submission != id.0 {
+                i += 1;
+                continue;
+            }
+            let status = statuses.and_then(|statuses| statuses.get(index));
+            index += 1;
+
+            let order = &mut state.pending[i];
+            order.response = Some(now);
+            order.oid = status.and_then(|status| {
+                status
+                    .pointer("/resting/oid")
+                    .or_else(|| status.pointer("/filled/oid"))
+                    .and_then(Value::as_u64)
+            });
+            let rejected = failed || status.is_some_and(|status| status.get("error").is_some());
+            let early_ack = order.oid.and_then(|oid| state.early_acks.remove(&oid));
+
+            if rejected || early_ack.is_some() {
+                let latency = state.pending.remove(i).complete(early_ack);
+                Self::push_completed(&mut state.completed, self.capacity, latency);
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    /// Feed an `orderUpdates` payload to stamp acknowledgements
+    ///
+    /// Returns the number of orders that completed.
+    pub fn handle_order_updates(&self, data: &Value) -> usize {
+        let now = Instant::now();
+        let updates = data.as_array().map(Vec::as_slice).unwrap_or_default();
+        let mut completed = 0;
+
+        let mut state = self.lock();
+        let state = &mut *state;
+        for update in updates {
+            let Some(order) = update.get("order") else {
+                continue;
+            };
+            let oid = order.get("oid").and_then(Value::as_u64);
+            let cloid = order.get("cloid").and_then(Value::as_str);
+
+            let position = state.pending.iter().position(|pending| {
+                (cloid.is_some() && pending.cloid.as_deref() == cloid)
+                    || (oid.is_some() && pending.oid == oid)
+            });
+            match position {
+                Some(position) => {
+                    let latency = state.pending.remove(position).complete(Some(now));
+                    Self::push_completed(&mut state.completed, self.capacity, latency);
+                    completed += 1;
+                }
+                None => {
+                    // The update may beat the placement response over the socket
+                    let awaiting_response = state
+                        .pending
+                        .iter()
+                        .any(|pending| pending.response.is_none());
+                    if let (Some(oid), true) = (oid, awaiting_response) {
+                        if state.early_acks.len() >= MAX_EARLY_ACKS {
+                            state.early_acks.clear();
+                        }
+                        state.early_acks.entry(oid).or_insert(now);
+                    }
+                }
+            }
+        }
+        completed
+    }
+
+    fn push_completed(
+        completed: &mut VecDeque<OrderLatency>,
+        capacity: usize,
+        latency: OrderLatency,
+    ) {
+        if capacity == 0 {
+            return;
+        }
+        if completed.len() >= capacity {
+            completed.pop_front();
+        }
+        completed.push_back(latency);
+    }
+
+    /// Drop orders still unacknowledged after `max_age`
+    ///
+    /// Returns the number dropped; they are counted in
+    /// `hyperliquid_order_latency_unacked_total`.
+    pub fn expire(&self, max_age: Duration) -> usize {
+        let mut state = self.lock();
+        let before = state.pending.len();
+        state
+            .pending
+            .retain(|order| order.pre_sign.elapsed() < max_age);
+        let expired = before - state.pending.len();
+        if expired > 0 {
+            crate::telemetry::counter!("hyperliquid_order_latency_unacked_total")
+                .increment(expired as u64);
+        }
+        if state.pending.is_empty() {
+            state.early_acks.clear();
+        }
+        expired
+    }
+
+    /// Breakdowns of completed orders, oldest first
+    pub fn completed(&self) -> Vec<OrderLatency> {
+        self.lock().completed.iter().cloned().collect()
+    }
+
+    /// Most recently completed breakdown
+    pub fn last(&self) -> Option<OrderLatency> {
+        self.lock().completed.back().cloned()
+    }
+
+    /// Number of orders awaiting their acknowledgement
+    pub fn pending_count(&self) -> usize {
+        self.lock().pending.len()
+    }
+
+    /// How long the oldest unacknowledged order has been waiting
+    pub fn oldest_pending(&self) -> Option<Duration> {
+        self.lock()
+            .pending
+            .iter()
+            .map(|order| order.pre_sign.elapsed())
+            .max()
+    }
+
+    /// Drop the completed breakdowns
+    pub fn clear(&self) {
+        self.lock().completed.clear();
+    }
+}
+
+impl std::fmt::Debug for OrderLatencyTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("OrderLatencyTracker")
+            .field("capacity", &self.capacity)
+            .field("pending", &state.pending.len())
+            .field("completed", &state.completed.len())
+            .finish()
+    }
+}
@@ -0,0 +1,133 @@
+//! Market orders as IOC limits checked against the book
+//!
+//! [`ExchangeClient::market_order`] fetches the `l2Book` snapshot, estimates
+//! the fill with [`estimate_fill_price`] and sends an IOC priced at the mid
+//! plus the slippage budget. The estimate is returned with the result; the
+//! order is not sent when the visible book has nothing inside the budget.
+
+use serde_json::{json, Value};
+
+use super::client::ExchangeClient;
+use super::replace::{leg_status, LegStatus};
+use crate::analytics::impact::{estimate_fill_price, max_size_within_slippage, FillEstimate};
+use crate::client::HttpClient;
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::execution::{round_px, round_sz};
+use crate::types::precision::{PrecisionError, WireFormat};
+use crate::types::{L2BookSnapshot, Meta};
+
+/// Outcome of [`ExchangeClient::market_order`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketOrderResult {
+    pub coin: String,
+    pub is_buy: bool,
+    /// Size sent, on the size grid
+    pub sz: f64,
+    pub limit_px: f64,
+    /// Expected fill from the book at send time
+    pub estimate: FillEstimate,
+    pub leg: LegStatus,
+    /// Order status reported by the exchange (`{"filled": ...}` for a fill)
+    pub status: Option<Value>,
+}
+
+impl MarketOrderResult {
+    /// Size filled by the IOC
+    pub fn filled_sz(&self) -> f64 {
+        self.status
+            .as_ref()
+            .and_then(|status| status.pointer("/filled/totalSz"))
+            .and_then(Value::as_str)
+            .and_then(|sz| sz.parse().ok())
+            .unwrap_or(0.0)
+    }
+}
+
+impl ExchangeClient {
+    /// Buy or sell `sz` of the perp `coin` at up to `slippage_bps` from the mid
+    ///
+    /// `info` is used for `meta` and `l2Book`. The IOC takes levels up to
+    /// its limit price only, so it fills partly when the estimate's
+    /// `worst_px` is beyond `limit_px`; see [`MarketOrderResult::filled_sz`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn market_order(
+        &self,
+        info: &HttpClient,
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        slippage_bps: u32,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<MarketOrderResult, HyperliquidError> {
+        let meta: Meta = info.post("/info", &json!({"type": "meta"})).await?;
+        let (asset, sz_decimals) = meta
+            .universe
+            .iter()
+            .position(|asset| asset.name == coin)
+            .map(|index| (index as u32, meta.universe[index].szDecimals.max(0) as u32))
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown coin: {}", coin)))?;
+        let sz = round_sz(sz, sz_decimals);
+        if sz <= 0.0 {
+            return Err(HyperliquidError::Validation(format!(
+                "order size rounds to zero at {} decimals",
+                sz_decimals
+            )));
+        }
+
+        let book: L2BookSnapshot = info
+            .post("/info", &json!({"type": "l2Book", "coin": coin}))
+            .await?;
+        let estimate = estimate_fill_price(&book, is_buy, sz).ok_or_else(|| {
+            HyperliquidError::Validation(format!("no liquidity on the {} book", coin))
+        })?;
+        if max_size_within_slippage(&book, is_buy, f64::from(slippage_bps)) <= 0.0 {
+            return Err(HyperliquidError::Validation(format!(
+                "no {} liquidity within {} bps of the mid",
+                coin, slippage_bps
+            )));
+        }
+
+        let slippage = f64::from(slippage_bps) / 10_000.0;
+        let limit_px = round_px(
+            if is_buy {
+                estimate.reference_px * (1.0 + slippage)
+            } else {
+                estimate.reference_px * (1.0 - slippage)
+            },
+            sz_decimals,
+        );
+
+        let format = WireFormat::perp(sz_decimals);
+        let wire = |value: Result<String, PrecisionError>| {
+            value.map_err(|e| HyperliquidError::Validation(e.to_string()))
+        };
+        let action = json!({
+            "type": "order",
+            "orders": [{
+                "a": asset,
+                "b": is_buy,
+                "p": wire(format.price_f64(limit_px))?,
+                "s": wire(format.size_f64(sz))?,
+                "r": false,
+                "t": {"limit": {"tif": "Ioc"}},
+            }],
+            "grouping": "na",
+        });
+        let response = self
+            .post_signed_action(action, wallet, vault_address)
+            .await?;
+
+        let (leg, status) = leg_status(&response);
+        Ok(MarketOrderResult {
+            coin: coin.to_string(),
+            is_buy,
+            sz,
+            limit_px,
+            estimate,
+            leg,
+            status,
+        })
+    }
+}
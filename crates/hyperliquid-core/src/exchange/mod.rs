@@ -1,7 +1,27 @@
 //! Exchange API client for trading operations
 
+mod allowlist;
+mod audit;
+mod builder;
 mod client;
+mod close;
+mod dedup;
+mod latency;
+mod market;
+mod pool;
+mod replace;
+mod signer;
 mod signing;
 
-pub use client::ExchangeClient;
-pub use signing::{sign_order, sign_request};
\ No newline at end of file
+pub use allowlist::{WithdrawalAllowlist, GUARDED_ACTIONS};
+pub use audit::{hash_action, hash_action_bytes, verify_audit_log, AuditLog, AuditRecord, AuditResult, GENESIS_HASH};
+pub use builder::{ExchangeClientConfigBuilder, SignerConfig, DEFAULT_SLIPPAGE_BPS};
+pub use client::{ExchangeClient, ExchangeClientConfig};
+pub use close::{close_order_params, CloseAmount, CloseResult, MIN_ORDER_VALUE};
+pub use dedup::{CloidRegistry, DuplicatePolicy, DEFAULT_CLOID_TTL};
+pub use latency::{OrderLatency, OrderLatencyTracker, SubmissionId};
+pub use market::MarketOrderResult;
+pub use pool::{ExchangePoolStats, ExchangePools};
+pub use replace::{LegStatus, OrderRef, ReplaceMethod, ReplaceResult};
+pub use signer::{SigningExecutor, SigningExecutorConfig, SigningExecutorStats};
+pub use signing::{sign_order, sign_order_with_buffer, sign_request, SignedActionBody};
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Object pools for the exchange hot path
+//!
+//! Sustained order bursts would otherwise allocate a JSON buffer per order.
+//! The pool recycles buffers while keeping their byte capacity, so
+//! steady-state order flow does not hit the allocator.
+
+use serde::Serialize;
+
+use crate::error::HyperliquidError;
+use crate::memory::{ObjectPool, PoolStats, PooledObject};
+
+/// Default number of idle serialization buffers kept for reuse
+const DEFAULT_BUFFER_POOL_SIZE: usize = 64;
+
+/// Pool statistics for the exchange hot path
+#[derive(Debug, Clone)]
+pub struct ExchangePoolStats {
+    /// Serialization buffer pool statistics
+    pub buffers: PoolStats,
+}
+
+/// Pool of serialization/signing buffers
+pub struct ExchangePools {
+    buffers: ObjectPool<Vec<u8>>,
+}
+
+impl ExchangePools {
+    /// Create pools with default sizes
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_BUFFER_POOL_SIZE)
+    }
+
+    /// Create pools keeping at most the given number of idle buffers
+    pub fn with_capacity(buffers: usize) -> Self {
+        Self {
+            buffers: ObjectPool::with_reset(buffers, Vec::clear),
+        }
+    }
+
+    /// Get an empty byte buffer from the pool
+    pub fn buffer(&self) -> PooledObject<Vec<u8>> {
+        self.buffers.get()
+    }
+
+    /// Serialize a value as JSON into a pooled buffer
+    pub fn serialize<T: Serialize>(
+        &self,
+        value: &T,
+    ) -> Result<PooledObject<Vec<u8>>, HyperliquidError> {
+        let mut buf = self.buffer();
+        serde_json::to_writer(&mut *buf, value)?;
+        Ok(buf)
+    }
+
+    /// Get pool hit/miss statistics
+    pub fn stats(&self) -> ExchangePoolStats {
+        ExchangePoolStats {
+            buffers: self.buffers.get_stats(),
+        }
+    }
+}
+
+impl Default for ExchangePools {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ExchangePools {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExchangePools")
+            .field("idle_buffers", &self.buffers.pool_size())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OrderWire;
+
+    #[test]
+    fn test_serialize_reuses_buffer() {
+        let pools = ExchangePools::new();
+        let wire = OrderWire::new_limit(
+            "ETH".to_string(),
+            true,
+            "1.0".to_string(),
+            "3000".to_string(),
+        );
+
+        for _ in 0..10 {
+            let buf = pools.serialize(&wire).unwrap();
+            assert_eq!(&buf[..], serde_json::to_vec(&wire).unwrap().as_slice());
+        }
+
+        let stats = pools.stats().buffers;
+        assert_eq!(stats.total_allocations, 10);
+        assert_eq!(stats.pool_misses, 1);
+        assert_eq!(stats.pool_hits, 9);
+    }
+}
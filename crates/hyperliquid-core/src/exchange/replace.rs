@@ -0,0 +1,205 @@
+//! Cancel-and-replace of a resting order
+//!
+//! [`ExchangeClient::cancel_and_replace`] swaps a resting order for a new one
+//! in a single `modify` action when the exchange can modify it in place, and
+//! otherwise cancels it and places the replacement as two actions. Either way
+//! the [`ReplaceResult`] says which leg, if any, failed, so the caller knows
+//! whether the original, the replacement or neither is live.
+
+use serde_json::{json, Value};
+use tracing::warn;
+
+use super::client::ExchangeClient;
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+
+/// Order to replace, by exchange or client order id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRef {
+    Oid(u64),
+    Cloid(String),
+}
+
+impl OrderRef {
+    fn to_wire(&self) -> Value {
+        match self {
+            OrderRef::Oid(oid) => json!(oid),
+            OrderRef::Cloid(cloid) => json!(cloid),
+        }
+    }
+}
+
+impl From<u64> for OrderRef {
+    fn from(oid: u64) -> Self {
+        OrderRef::Oid(oid)
+    }
+}
+
+impl From<&str> for OrderRef {
+    fn from(cloid: &str) -> Self {
+        OrderRef::Cloid(cloid.to_string())
+    }
+}
+
+/// Outcome of one leg of a replace
+#[derive(Debug, Clone, PartialEq)]
+pub enum LegStatus {
+    /// Accepted by the exchange
+    Done,
+    /// Rejected, with the exchange's reason
+    Failed(String),
+    /// Not sent because an earlier leg failed
+    Skipped,
+}
+
+impl LegStatus {
+    pub fn is_done(&self) -> bool {
+        matches!(self, LegStatus::Done)
+    }
+}
+
+/// How a replace was carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplaceMethod {
+    /// One `modify` action; both legs succeed or fail together
+    Modify,
+    /// A `cancel` followed, if it succeeded, by an `order`
+    CancelPlace,
+}
+
+/// Combined result of [`ExchangeClient::cancel_and_replace`]
+///
+/// | cancel | place | live afterwards |
+/// |--------|-------|-----------------|
+/// | Done | Done | the replacement |
+/// | Failed | Skipped | the original, unless it had already filled or been canceled |
+/// | Done | Failed | neither |
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplaceResult {
+    pub method: ReplaceMethod,
+    pub cancel: LegStatus,
+    pub place: LegStatus,
+    /// Status of the replacement (`{"resting": ...}` or `{"filled": ...}`)
+    /// when the exchange reported one
+    pub status: Option<Value>,
+}
+
+impl ReplaceResult {
+    pub fn is_success(&self) -> bool {
+        self.cancel.is_done() && self.place.is_done()
+    }
+
+    /// Oid of the replacement if it is resting
+    pub fn oid(&self) -> Option<u64> {
+        self.status
+            .as_ref()?
+            .pointer("/resting/oid")
+            .and_then(Value::as_u64)
+    }
+
+    /// Whether the original order was pulled and nothing replaced it
+    pub fn left_flat(&self) -> bool {
+        self.cancel.is_done() && !self.place.is_done()
+    }
+}
+
+impl ExchangeClient {
+    /// Replace `target`, resting on `asset`, with `new_order`
+    ///
+    /// `new_order` is an order in wire format (`a`, `b`, `p`, `s`, `r`, `t`
+    /// and optionally `c`). A `modify` is used when the replacement is on the
+    /// same asset; otherwise the original is canceled and, only if the cancel
+    /// succeeded, the replacement placed. For cloid continuity a replacement
+    /// without a `c` takes the cloid of a target referenced by cloid.
+    ///
+    /// A rejected leg is reported in the result; `Err` is returned only when
+    /// the exchange couldn't be reached, in which case the state of the
+    /// original is unknown.
+    pub async fn cancel_and_replace(
+        &self,
+        asset: u32,
+        target: impl Into<OrderRef>,
+        mut new_order: Value,
+        wallet: &Wallet,
+        vault_address: Option<&str>,
+    ) -> Result<ReplaceResult, HyperliquidError> {
+        let target = target.into();
+        if let (OrderRef::Cloid(cloid), Some(order)) = (&target, new_order.as_object_mut()) {
+            order
+                .entry("c")
+                .or_insert_with(|| Value::String(cloid.clone()));
+        }
+
+        if new_order.get("a").and_then(Value::as_u64) == Some(u64::from(asset)) {
+            let action = json!({
+                "type": "modify",
+                "oid": target.to_wire(),
+                "order": new_order,
+            });
+            let response = self
+                .post_signed_action(action, wallet, vault_address)
+                .await?;
+            let (leg, status) = leg_status(&response);
+            return Ok(ReplaceResult {
+                method: ReplaceMethod::Modify,
+                cancel: leg.clone(),
+                place: leg,
+                status,
+            });
+        }
+
+        let cancel = match &target {
+            OrderRef::Oid(oid) => json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]}),
+            OrderRef::Cloid(cloid) => {
+                json!({"type": "cancelByCloid", "cancels": [{"asset": asset, "cloid": cloid}]})
+            }
+        };
+        let response = self
+            .post_signed_action(cancel, wallet, vault_address)
+            .await?;
+        let (cancel, _) = leg_status(&response);
+        if !cancel.is_done() {
+            return Ok(ReplaceResult {
+                method: ReplaceMethod::CancelPlace,
+                cancel,
+                place: LegStatus::Skipped,
+                status: None,
+            });
+        }
+
+        let action = json!({"type": "order", "orders": [new_order], "grouping": "na"});
+        let (place, status) = match self.post_signed_action(action, wallet, vault_address).await {
+            Ok(response) => leg_status(&response),
+            Err(e) => {
+                // The original is gone; report the failure rather than losing that
+                warn!("Replacement order after cancel failed: {}", e);
+                (LegStatus::Failed(e.to_string()), None)
+            }
+        };
+        Ok(ReplaceResult {
+            method: ReplaceMethod::CancelPlace,
+            cancel,
+            place,
+            status,
+        })
+    }
+}
+
+/// Outcome of an action carrying at most one order or cancel
+pub(super) fn leg_status(response: &Value) -> (LegStatus, Option<Value>) {
+    if response.get("status").and_then(Value::as_str) != Some("ok") {
+        let reason = response
+            .get("response")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return (LegStatus::Failed(reason.to_string()), None);
+    }
+    match response.pointer("/response/data/statuses/0") {
+        Some(status) => match status.get("error").and_then(Value::as_str) {
+            Some(error) => (LegStatus::Failed(error.to_string()), None),
+            None => (LegStatus::Done, Some(status.clone())),
+        },
+        // `modify` answers with a bare `{"type": "default"}`
+        None => (LegStatus::Done, None),
+    }
+}
@@ -0,0 +1,264 @@
+//! Dedicated signing executor
+//!
+//! secp256k1 signing is CPU-bound. Running it inline on tokio workers stalls
+//! the reactor during order bursts, so `SigningExecutor` moves it onto
+//! dedicated OS threads fed through a bounded queue. Submitting awaits queue
+//! space, which gives natural backpressure instead of unbounded buffering.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{mpsc, oneshot};
+use tracing::debug;
+
+use crate::error::HyperliquidError;
+use crate::runtime::{spawn_role_thread, ThreadPriority, ThreadRole};
+
+/// Job executed on a signing thread
+type SigningJob = Box<dyn FnOnce() + Send + 'static>;
+
+/// Configuration for the signing executor
+#[derive(Clone, Debug)]
+pub struct SigningExecutorConfig {
+    /// Maximum number of queued signing jobs
+    pub queue_capacity: usize,
+    /// Number of signing threads
+    pub threads: usize,
+    /// Cores to pin signing threads to, assigned round-robin (empty = no pinning)
+    pub core_ids: Vec<usize>,
+    /// Scheduling priority for signing threads
+    pub priority: ThreadPriority,
+}
+
+impl Default for SigningExecutorConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 1024,
+            threads: 1,
+            core_ids: Vec::new(),
+            priority: ThreadPriority::Normal,
+        }
+    }
+}
+
+/// Signing executor statistics
+#[derive(Clone, Debug)]
+pub struct SigningExecutorStats {
+    /// Jobs accepted into the queue
+    pub submitted: u64,
+    /// Jobs finished by a signing thread
+    pub completed: u64,
+    /// Jobs currently queued or running
+    pub in_flight: u64,
+    /// Average time spent waiting in the queue
+    pub avg_queue_wait: Duration,
+    /// Average time spent executing
+    pub avg_execution: Duration,
+    /// Maximum time spent executing
+    pub max_execution: Duration,
+}
+
+#[derive(Default)]
+struct StatsInternal {
+    submitted: AtomicU64,
+    completed: AtomicU64,
+    total_queue_wait_ns: AtomicU64,
+    total_execution_ns: AtomicU64,
+    max_execution_ns: AtomicU64,
+}
+
+/// Executor that runs signing work on dedicated threads
+pub struct SigningExecutor {
+    tx: mpsc::Sender<(Instant, SigningJob)>,
+    stats: Arc<StatsInternal>,
+    config: SigningExecutorConfig,
+}
+
+impl SigningExecutor {
+    /// Create an executor with the default configuration
+    pub fn new() -> Result<Self, HyperliquidError> {
+        Self::with_config(SigningExecutorConfig::default())
+    }
+
+    /// Create an executor with a custom configuration
+    pub fn with_config(config: SigningExecutorConfig) -> Result<Self, HyperliquidError> {
+        if config.queue_capacity == 0 || config.threads == 0 {
+            return Err(HyperliquidError::Config(
+                "signing executor needs a non-zero queue capacity and thread count".to_string(),
+            ));
+        }
+
+        let (tx, rx) = mpsc::channel::<(Instant, SigningJob)>(config.queue_capacity);
+        let rx = Arc::new(Mutex::new(rx));
+        let stats = Arc::new(StatsInternal::default());
+
+        for index in 0..config.threads {
+            let rx = Arc::clone(&rx);
+            let stats = Arc::clone(&stats);
+            let core = if config.core_ids.is_empty() {
+                None
+            } else {
+                Some(config.core_ids[index % config.core_ids.len()])
+            };
+
+            spawn_role_thread(ThreadRole::Signer, core, config.priority, move || loop {
+                // Only one idle thread waits on the queue at a time
+                let next = rx.lock().unwrap_or_else(|e| e.into_inner()).blocking_recv();
+                let (queued_at, job) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+
+                let started = Instant::now();
+                job();
+                stats.record(started - queued_at, started.elapsed());
+            })
+            .map_err(|e| {
+                HyperliquidError::Config(format!("failed to spawn signing thread: {}", e))
+            })?;
+        }
+
+        debug!(
+            threads = config.threads,
+            queue_capacity = config.queue_capacity,
+            "Signing executor started"
+        );
+
+        Ok(Self { tx, stats, config })
+    }
+
+    /// Run `f` on a signing thread and await its result
+    ///
+    /// Waits for queue space when the queue is full.
+    pub async fn execute<F, T>(&self, f: F) -> Result<T, HyperliquidError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (result_tx, result_rx) = oneshot::channel();
+        let job: SigningJob = Box::new(move || {
+            let _ = result_tx.send(f());
+        });
+
+        self.tx
+            .send((Instant::now(), job))
+            .await
+            .map_err(|_| HyperliquidError::Signing("signing executor is shut down".to_string()))?;
+        self.stats.submitted.fetch_add(1, Ordering::Relaxed);
+
+        result_rx
+            .await
+            .map_err(|_| HyperliquidError::Signing("signing job was dropped".to_string()))
+    }
+
+    /// Get executor statistics
+    pub fn stats(&self) -> SigningExecutorStats {
+        let submitted = self.stats.submitted.load(Ordering::Relaxed);
+        let completed = self.stats.completed.load(Ordering::Relaxed);
+        let avg = |total: &AtomicU64| {
+            if completed == 0 {
+                Duration::ZERO
+            } else {
+                Duration::from_nanos(total.load(Ordering::Relaxed) / completed)
+            }
+        };
+
+        SigningExecutorStats {
+            submitted,
+            completed,
+            in_flight: submitted.saturating_sub(completed),
+            avg_queue_wait: avg(&self.stats.total_queue_wait_ns),
+            avg_execution: avg(&self.stats.total_execution_ns),
+            max_execution: Duration::from_nanos(
+                self.stats.max_execution_ns.load(Ordering::Relaxed),
+            ),
+        }
+    }
+
+    /// Get the executor configuration
+    pub fn config(&self) -> &SigningExecutorConfig {
+        &self.config
+    }
+}
+
+impl StatsInternal {
+    fn record(&self, queue_wait: Duration, execution: Duration) {
+        let execution_ns = execution.as_nanos() as u64;
+        self.completed.fetch_add(1, Ordering::Relaxed);
+        self.total_queue_wait_ns
+            .fetch_add(queue_wait.as_nanos() as u64, Ordering::Relaxed);
+        self.total_execution_ns
+            .fetch_add(execution_ns, Ordering::Relaxed);
+        self.max_execution_ns
+            .fetch_max(execution_ns, Ordering::Relaxed);
+
+        crate::telemetry::histogram!("hyperliquid_signing_queue_wait_seconds")
+            .record(queue_wait.as_secs_f64());
+        crate::telemetry::histogram!("hyperliquid_signing_duration_seconds")
+            .record(execution.as_secs_f64());
+    }
+}
+
+impl std::fmt::Debug for SigningExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SigningExecutor")
+            .field("config", &self.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute_runs_on_signer_thread() {
+        let executor = SigningExecutor::new().unwrap();
+
+        let name = executor
+            .execute(|| std::thread::current().name().map(str::to_string))
+            .await
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("hyperliquid-signer"));
+
+        let stats = executor.stats();
+        assert_eq!(stats.submitted, 1);
+        assert_eq!(stats.completed, 1);
+        assert_eq!(stats.in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_small_queue_applies_backpressure() {
+        let executor = Arc::new(
+            SigningExecutor::with_config(SigningExecutorConfig {
+                queue_capacity: 1,
+                threads: 2,
+                ..Default::default()
+            })
+            .unwrap(),
+        );
+
+        let jobs = (0..32u64).map(|i| {
+            let executor = Arc::clone(&executor);
+            tokio::spawn(async move { executor.execute(move || i * 2).await.unwrap() })
+        });
+
+        let mut results = Vec::new();
+        for job in jobs {
+            results.push(job.await.unwrap());
+        }
+        assert_eq!(results, (0..32u64).map(|i| i * 2).collect::<Vec<_>>());
+        assert_eq!(executor.stats().completed, 32);
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let result = SigningExecutor::with_config(SigningExecutorConfig {
+            queue_capacity: 0,
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(HyperliquidError::Config(_))));
+    }
+}
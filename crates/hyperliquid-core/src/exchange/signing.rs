@@ -18,13 +18,25 @@ use rand_core::OsRng;
 pub fn sign_order(
     order: &OrderRequest,
     private_key: &[u8],
+) -> Result<String, HyperliquidError> {
+    sign_order_with_buffer(order, private_key, &mut Vec::new())
+}
+
+/// Sign an order request, serializing into a caller-provided buffer
+///
+/// The buffer is cleared first so pooled buffers can be reused across orders.
+pub fn sign_order_with_buffer(
+    order: &OrderRequest,
+    private_key: &[u8],
+    buf: &mut Vec<u8>,
 ) -> Result<String, HyperliquidError> {
     // Serialize order to JSON for signing
-    let order_json = serde_json::to_string(order)
+    buf.clear();
+    serde_json::to_writer(&mut *buf, order)
         .map_err(|e| HyperliquidError::Signing(format!("Failed to serialize order: {}", e)))?;
 
     // Compute keccak256 hash
-    let hash = keccak256(order_json.as_bytes());
+    let hash = keccak256(&buf[..]);
 
     // Sign the hash with private key
     let signature = sign_hash(&hash, private_key)?;
@@ -67,6 +79,20 @@ pub fn sign_request(
     Ok(signed_request)
 }
 
+/// Signed L1 action as posted to `/exchange`
+///
+/// Borrows the action and signature so the body can be written straight into
+/// a buffer, without first building a `serde_json::Value` around them.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedActionBody<'a, T: Serialize> {
+    pub action: &'a T,
+    pub nonce: u64,
+    pub signature: &'a crate::crypto::Signature,
+    /// Vault or sub-account traded for, sent as `null` when absent
+    pub vault_address: Option<&'a str>,
+}
+
 /// Sign a hash with a private key using ECDSA secp256k1
 fn sign_hash(hash: &[u8], private_key: &[u8]) -> Result<Vec<u8>, HyperliquidError> {
     // Validate private key format
@@ -148,6 +174,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sign_order_with_buffer_matches_sign_order() {
+        let order = OrderRequest {
+            coin: "ETH".to_string(),
+            is_buy: false,
+            sz: "0.5".to_string(),
+            limit_px: "3000".to_string(),
+            reduce_only: None,
+            order_type: None,
+            time_in_force: None,
+            trigger_price: None,
+            trail_value: None,
+            close_on_trigger: None,
+        };
+
+        let mut rng = OsRng;
+        let secret_key = SecretKey::random(&mut rng);
+        let private_key = secret_key.to_be_bytes().to_vec();
+
+        // Stale contents must not leak into the signed payload
+        let mut buf = b"stale".to_vec();
+        let pooled = sign_order_with_buffer(&order, &private_key, &mut buf).unwrap();
+        assert_eq!(pooled, sign_order(&order, &private_key).unwrap());
+        assert_eq!(buf, serde_json::to_vec(&order).unwrap());
+    }
+
+    #[test]
+    fn test_signed_action_body_matches_json_value() {
+        let action = serde_json::json!({"type": "cancel", "cancels": [{"a": 0, "o": 1}]});
+        let signature = crate::crypto::Signature::new("0x01", "0x02", 27);
+        for vault_address in [None, Some("0xdfc24b077bc1425ad1dea75bcb6f8158e10df303")] {
+            let body = SignedActionBody {
+                action: &action,
+                nonce: 1_700_000_000_000,
+                signature: &signature,
+                vault_address,
+            };
+            let expected = serde_json::json!({
+                "action": action,
+                "nonce": 1_700_000_000_000u64,
+                "signature": signature,
+                "vaultAddress": vault_address,
+            });
+            assert_eq!(
+                serde_json::to_vec(&body).unwrap(),
+                serde_json::to_vec(&expected).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_sign_request() {
         let request = ExchangeRequest {
@@ -0,0 +1,832 @@
+//! Client-side execution algorithms
+//!
+//! An [`Executor`] works a [`ParentOrder`] by sending child orders to a
+//! [`Venue`]:
+//!
+//! - **TWAP** splits the size into equal slices sent at a fixed interval.
+//! - **VWAP** sizes the slices by a volume profile (e.g. historical volume per
+//!   bucket) over the same schedule.
+//! - **Iceberg** keeps one resting child of the display size at the limit
+//!   price, replacing it as it fills, so only part of the size is visible.
+//!
+//! TWAP/VWAP children are IOC orders priced off the latest quote from the
+//! stream (see [`watch_quotes`]) and never beyond the parent's limit price;
+//! size left unfilled by a slice rolls into the next. Iceberg children rest,
+//! so their fills are followed through an [`OrderManager`] attached to the
+//! account's streams.
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient, venue: hyperliquid_core::execution::ExchangeVenue) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::time::Duration;
+//! use hyperliquid_core::execution::{watch_quotes, Executor, ParentOrder};
+//!
+//! let quotes = watch_quotes(&ws, "BTC").await?;
+//! let executor = Executor::new(venue, quotes);
+//! let handle = executor.start(ParentOrder::twap("BTC", true, 1.0, 66000.0, Duration::from_secs(600), 20))?;
+//!
+//! handle.pause();
+//! handle.resume();
+//! let report = handle.wait().await;
+//! println!("filled {} @ {}", report.filled_sz, report.avg_px);
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep_until, Instant};
+use tracing::{debug, warn};
+
+#[cfg(feature = "exchange-signing")]
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+#[cfg(feature = "exchange-signing")]
+use crate::exchange::ExchangeClient;
+use crate::killswitch::KillSwitchRegistry;
+use crate::oms::{OrderManager, OrderState};
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+#[cfg(feature = "exchange-signing")]
+use crate::types::precision::{PrecisionError, WireFormat};
+#[cfg(feature = "exchange-signing")]
+use crate::types::Meta;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Consecutive rejected children after which an execution fails
+const MAX_CONSECUTIVE_REJECTIONS: u32 = 3;
+
+/// How long to wait for a pulled iceberg child to be reported canceled
+const CANCEL_CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Sizes below this are treated as zero
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Best bid and ask
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+/// Subscribe `ws` to `coin`'s best bid/offer and follow it in a watch channel
+///
+/// Registers the handler for the `bbo` subscription of `coin`, replacing any
+/// existing handler for it.
+#[cfg(feature = "ws")]
+pub async fn watch_quotes(
+    ws: &WebSocketClient,
+    coin: &str,
+) -> Result<watch::Receiver<Option<Quote>>, HyperliquidError> {
+    let (tx, rx) = watch::channel(None);
+    let subscription = Subscription::Bbo {
+        coin: coin.to_string(),
+    };
+    let coin = coin.to_string();
+    ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+        if response.channel != "bbo"
+            || response.data.get("coin").and_then(Value::as_str) != Some(&coin)
+        {
+            return;
+        }
+        let level = |index: usize| {
+            response
+                .data
+                .pointer(&format!("/bbo/{}/px", index))
+                .and_then(Value::as_str)
+                .and_then(|px| px.parse::<f64>().ok())
+        };
+        if let (Some(bid), Some(ask)) = (level(0), level(1)) {
+            let _ = tx.send(Some(Quote { bid, ask }));
+        }
+    })
+    .await;
+    ws.subscribe(subscription)
+        .await
+        .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+    Ok(rx)
+}
+
+/// How a parent order is worked
+#[derive(Debug, Clone, PartialEq)]
+pub enum Algo {
+    /// Equal slices over `duration`
+    Twap { duration: Duration, slices: u32 },
+    /// Slices over `duration` weighted by `profile` (one weight per slice)
+    Vwap {
+        duration: Duration,
+        profile: Vec<f64>,
+    },
+    /// One resting child of `display_sz` at a time
+    Iceberg { display_sz: f64 },
+}
+
+/// Order to be worked by an [`Executor`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParentOrder {
+    pub coin: String,
+    pub is_buy: bool,
+    pub sz: f64,
+    /// Worst acceptable price; iceberg children rest here
+    pub limit_px: f64,
+    pub algo: Algo,
+    /// How far through the quote IOC children may be priced, as a fraction
+    pub slippage: f64,
+}
+
+impl ParentOrder {
+    fn new(coin: &str, is_buy: bool, sz: f64, limit_px: f64, algo: Algo) -> Self {
+        Self {
+            coin: coin.to_string(),
+            is_buy,
+            sz,
+            limit_px,
+            algo,
+            slippage: 0.001,
+        }
+    }
+
+    pub fn twap(
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        duration: Duration,
+        slices: u32,
+    ) -> Self {
+        Self::new(coin, is_buy, sz, limit_px, Algo::Twap { duration, slices })
+    }
+
+    pub fn vwap(
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        duration: Duration,
+        profile: Vec<f64>,
+    ) -> Self {
+        Self::new(coin, is_buy, sz, limit_px, Algo::Vwap { duration, profile })
+    }
+
+    pub fn iceberg(coin: &str, is_buy: bool, sz: f64, limit_px: f64, display_sz: f64) -> Self {
+        Self::new(coin, is_buy, sz, limit_px, Algo::Iceberg { display_sz })
+    }
+
+    /// Set the slippage allowed through the quote for IOC children
+    pub fn with_slippage(mut self, slippage: f64) -> Self {
+        self.slippage = slippage;
+        self
+    }
+
+    fn validate(&self) -> Result<(), HyperliquidError> {
+        let invalid = |message: &str| Err(HyperliquidError::Validation(message.to_string()));
+        if !(self.sz > 0.0 && self.limit_px > 0.0) {
+            return invalid("size and limit price must be positive");
+        }
+        if !(0.0..1.0).contains(&self.slippage) {
+            return invalid("slippage must be in [0, 1)");
+        }
+        match &self.algo {
+            Algo::Twap { slices: 0, .. } => invalid("TWAP needs at least one slice"),
+            Algo::Vwap { profile, .. }
+                if profile.is_empty()
+                    || profile.iter().any(|w| *w < 0.0)
+                    || profile.iter().sum::<f64>() <= 0.0 =>
+            {
+                invalid("VWAP profile must be non-empty, non-negative and not all zero")
+            }
+            Algo::Iceberg { display_sz } if !(*display_sz > 0.0) => {
+                invalid("iceberg display size must be positive")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Cumulative fraction of the size due after each slice
+    fn schedule(&self) -> (Duration, Vec<f64>) {
+        let (duration, weights) = match &self.algo {
+            Algo::Twap { duration, slices } => (*duration, vec![1.0; *slices as usize]),
+            Algo::Vwap { duration, profile } => (*duration, profile.clone()),
+            Algo::Iceberg { .. } => (Duration::ZERO, vec![1.0]),
+        };
+        let total: f64 = weights.iter().sum();
+        let mut cumulative = 0.0;
+        let fractions = weights
+            .iter()
+            .map(|weight| {
+                cumulative += weight / total;
+                cumulative
+            })
+            .collect();
+        (duration, fractions)
+    }
+}
+
+/// Child order sent to a [`Venue`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildOrder {
+    pub coin: String,
+    pub is_buy: bool,
+    pub sz: f64,
+    pub limit_px: f64,
+    /// `"Ioc"` or `"Gtc"`
+    pub tif: &'static str,
+}
+
+/// Outcome of placing a child order
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChildResult {
+    Filled { sz: f64, avg_px: f64 },
+    Resting { oid: u64 },
+    Rejected(String),
+}
+
+impl ChildResult {
+    /// The matching entry of an exchange `statuses` array
+    pub(crate) fn to_status(&self) -> Value {
+        match self {
+            ChildResult::Filled { sz, avg_px } => {
+                json!({"filled": {"totalSz": sz.to_string(), "avgPx": avg_px.to_string()}})
+            }
+            ChildResult::Resting { oid } => json!({"resting": {"oid": oid}}),
+            ChildResult::Rejected(reason) => json!({"error": reason}),
+        }
+    }
+}
+
+/// Where child orders are sent
+pub trait Venue: Send + Sync + 'static {
+    fn place(
+        &self,
+        order: &ChildOrder,
+    ) -> impl Future<Output = Result<ChildResult, HyperliquidError>> + Send;
+
+    fn cancel(
+        &self,
+        coin: &str,
+        oid: u64,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+}
+
+/// [`Venue`] submitting signed orders to the exchange
+#[cfg(feature = "exchange-signing")]
+pub struct ExchangeVenue {
+    exchange: ExchangeClient,
+    wallet: Wallet,
+    vault_address: Option<String>,
+    /// Asset index and size decimals per coin
+    assets: HashMap<String, (u32, u32)>,
+}
+
+#[cfg(feature = "exchange-signing")]
+impl ExchangeVenue {
+    /// Create a venue for the perp assets in `meta`
+    pub fn new(exchange: ExchangeClient, wallet: Wallet, meta: &Meta) -> Self {
+        let assets = meta
+            .universe
+            .iter()
+            .enumerate()
+            .map(|(index, asset)| {
+                (
+                    asset.name.clone(),
+                    (index as u32, asset.szDecimals.max(0) as u32),
+                )
+            })
+            .collect();
+        Self {
+            exchange,
+            wallet,
+            vault_address: None,
+            assets,
+        }
+    }
+
+    /// Trade on behalf of a vault or subaccount
+    pub fn with_vault_address(mut self, vault_address: impl Into<String>) -> Self {
+        self.vault_address = Some(vault_address.into());
+        self
+    }
+
+    /// Asset index and size decimals of `coin`
+    pub(crate) fn asset(&self, coin: &str) -> Result<(u32, u32), HyperliquidError> {
+        self.assets
+            .get(coin)
+            .copied()
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown coin: {}", coin)))
+    }
+
+    /// Sign `action` with the venue's wallet and send it
+    pub(crate) async fn post_action(&self, action: Value) -> Result<Value, HyperliquidError> {
+        self.exchange
+            .post_signed_action(action, &self.wallet, self.vault_address.as_deref())
+            .await
+    }
+}
+
+/// Round a perp price to 5 significant figures and `6 - sz_decimals` decimals
+pub fn round_px(px: f64, sz_decimals: u32) -> f64 {
+    if px <= 0.0 {
+        return 0.0;
+    }
+    let magnitude = px.log10().floor() as i32;
+    let sig_decimals = (4 - magnitude).max(0);
+    let decimals = sig_decimals.min(6 - sz_decimals.min(6) as i32);
+    let scale = 10f64.powi(decimals);
+    (px * scale).round() / scale
+}
+
+/// Round a size down to `sz_decimals`
+pub fn round_sz(sz: f64, sz_decimals: u32) -> f64 {
+    let scale = 10f64.powi(sz_decimals as i32);
+    // Nudge before flooring so 0.3 doesn't become 0.29999
+    ((sz * scale) + 1e-9).floor() / scale
+}
+
+#[cfg(feature = "exchange-signing")]
+fn wire(value: Result<String, PrecisionError>) -> Result<String, HyperliquidError> {
+    value.map_err(|e| HyperliquidError::Validation(e.to_string()))
+}
+
+#[cfg(feature = "exchange-signing")]
+impl Venue for ExchangeVenue {
+    async fn place(&self, order: &ChildOrder) -> Result<ChildResult, HyperliquidError> {
+        let (asset, sz_decimals) = self.asset(&order.coin)?;
+        let sz = round_sz(order.sz, sz_decimals);
+        if sz <= 0.0 {
+            return Ok(ChildResult::Rejected("size rounds to zero".to_string()));
+        }
+        let format = WireFormat::perp(sz_decimals);
+        let action = json!({
+            "type": "order",
+            "orders": [{
+                "a": asset,
+                "b": order.is_buy,
+                "p": wire(format.price_f64(round_px(order.limit_px, sz_decimals)))?,
+                "s": wire(format.size_f64(sz))?,
+                "r": false,
+                "t": {"limit": {"tif": order.tif}},
+            }],
+            "grouping": "na",
+        });
+
+        let response = self
+            .exchange
+            .post_signed_action(action, &self.wallet, self.vault_address.as_deref())
+            .await?;
+        if response.get("status").and_then(Value::as_str) == Some("err") {
+            let reason = response
+                .get("response")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Ok(ChildResult::Rejected(reason.to_string()));
+        }
+
+        let status = response
+            .pointer("/response/data/statuses/0")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let number = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        Ok(if let Some(filled) = status.get("filled") {
+            ChildResult::Filled {
+                sz: number(filled.get("totalSz")),
+                avg_px: number(filled.get("avgPx")),
+            }
+        } else if let Some(oid) = status.pointer("/resting/oid").and_then(Value::as_u64) {
+            ChildResult::Resting { oid }
+        } else {
+            let reason = status
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("missing order status");
+            ChildResult::Rejected(reason.to_string())
+        })
+    }
+
+    async fn cancel(&self, coin: &str, oid: u64) -> Result<(), HyperliquidError> {
+        let (asset, _) = self.asset(coin)?;
+        let action = json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]});
+        self.exchange
+            .post_signed_action(action, &self.wallet, self.vault_address.as_deref())
+            .await?;
+        Ok(())
+    }
+}
+
+/// Where an execution is in its lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionState {
+    Running,
+    Paused,
+    /// Schedule finished or size fully filled
+    Completed,
+    Canceled,
+    Failed(String),
+}
+
+impl ExecutionState {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            ExecutionState::Completed | ExecutionState::Canceled | ExecutionState::Failed(_)
+        )
+    }
+}
+
+/// Progress of an execution
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport {
+    pub state: ExecutionState,
+    pub target_sz: f64,
+    pub filled_sz: f64,
+    /// Volume-weighted average fill price (0 until the first fill)
+    pub avg_px: f64,
+    pub children_sent: u32,
+    pub children_rejected: u32,
+}
+
+impl ExecutionReport {
+    pub fn remaining_sz(&self) -> f64 {
+        (self.target_sz - self.filled_sz).max(0.0)
+    }
+
+    fn record_fill(&mut self, sz: f64, px: f64) {
+        let total = self.filled_sz + sz;
+        if total > 0.0 {
+            self.avg_px = (self.avg_px * self.filled_sz + px * sz) / total;
+        }
+        self.filled_sz = total;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Run,
+    Pause,
+    Cancel,
+}
+
+/// Controls for a running execution
+pub struct ExecutionHandle {
+    control: watch::Sender<Control>,
+    report: watch::Receiver<ExecutionReport>,
+    task: JoinHandle<()>,
+}
+
+impl ExecutionHandle {
+    /// Stop sending children (and pull a resting iceberg child) until resumed
+    pub fn pause(&self) {
+        self.control.send_if_modified(|control| {
+            let changed = *control == Control::Run;
+            if changed {
+                *control = Control::Pause;
+            }
+            changed
+        });
+    }
+
+    pub fn resume(&self) {
+        self.control.send_if_modified(|control| {
+            let changed = *control == Control::Pause;
+            if changed {
+                *control = Control::Run;
+            }
+            changed
+        });
+    }
+
+    /// Stop the execution, cancelling any resting child
+    pub fn cancel(&self) {
+        let _ = self.control.send(Control::Cancel);
+    }
+
+    /// Current progress
+    pub fn report(&self) -> ExecutionReport {
+        self.report.borrow().clone()
+    }
+
+    /// Wait for the execution to finish and return the final report
+    pub async fn wait(self) -> ExecutionReport {
+        if let Err(e) = self.task.await {
+            warn!("Execution task failed: {}", e);
+        }
+        self.report.borrow().clone()
+    }
+}
+
+/// Runs parent orders against a venue
+pub struct Executor<V> {
+    venue: Arc<V>,
+    quotes: watch::Receiver<Option<Quote>>,
+    oms: Option<OrderManager>,
+    kill_switches: Option<KillSwitchRegistry>,
+}
+
+impl<V: Venue> Executor<V> {
+    pub fn new(venue: V, quotes: watch::Receiver<Option<Quote>>) -> Self {
+        Self {
+            venue: Arc::new(venue),
+            quotes,
+            oms: None,
+            kill_switches: None,
+        }
+    }
+
+    /// Follow resting children through `oms`; required for iceberg orders
+    pub fn with_order_manager(mut self, oms: OrderManager) -> Self {
+        self.oms = Some(oms);
+        self
+    }
+
+    /// Refuse to start, and fail running executions, while the coin is
+    /// disabled in `switches`
+    pub fn with_kill_switches(mut self, switches: KillSwitchRegistry) -> Self {
+        self.kill_switches = Some(switches);
+        self
+    }
+
+    /// Start working `parent` in the background
+    pub fn start(&self, parent: ParentOrder) -> Result<ExecutionHandle, HyperliquidError> {
+        parent.validate()?;
+        if let Some(switches) = &self.kill_switches {
+            switches.check(&parent.coin)?;
+        }
+        if matches!(parent.algo, Algo::Iceberg { .. }) && self.oms.is_none() {
+            return Err(HyperliquidError::Config(
+                "iceberg execution needs an order manager".to_string(),
+            ));
+        }
+
+        let (control_tx, control_rx) = watch::channel(Control::Run);
+        let (report_tx, report_rx) = watch::channel(ExecutionReport {
+            state: ExecutionState::Running,
+            target_sz: parent.sz,
+            filled_sz: 0.0,
+            avg_px: 0.0,
+            children_sent: 0,
+            children_rejected: 0,
+        });
+        let run = Run {
+            venue: Arc::clone(&self.venue),
+            quotes: self.quotes.clone(),
+            oms: self.oms.clone(),
+            kill_switches: self.kill_switches.clone(),
+            control: control_rx,
+            report: report_tx,
+            parent,
+        };
+        let task = tokio::spawn(run.execute());
+
+        Ok(ExecutionHandle {
+            control: control_tx,
+            report: report_rx,
+            task,
+        })
+    }
+}
+
+/// Why a run stopped early
+enum Stop {
+    Canceled,
+    Failed(String),
+}
+
+/// State of one execution task
+struct Run<V> {
+    venue: Arc<V>,
+    quotes: watch::Receiver<Option<Quote>>,
+    oms: Option<OrderManager>,
+    kill_switches: Option<KillSwitchRegistry>,
+    control: watch::Receiver<Control>,
+    report: watch::Sender<ExecutionReport>,
+    parent: ParentOrder,
+}
+
+impl<V: Venue> Run<V> {
+    async fn execute(mut self) {
+        let result = match self.parent.algo {
+            Algo::Iceberg { display_sz } => self.iceberg(display_sz).await,
+            _ => self.scheduled().await,
+        };
+        let state = match result {
+            Ok(()) => ExecutionState::Completed,
+            Err(Stop::Canceled) => ExecutionState::Canceled,
+            Err(Stop::Failed(reason)) => ExecutionState::Failed(reason),
+        };
+        self.report.send_modify(|report| report.state = state);
+    }
+
+    fn remaining(&self) -> f64 {
+        self.report.borrow().remaining_sz()
+    }
+
+    /// Block while paused; fails once canceled
+    async fn checkpoint(&mut self) -> Result<(), Stop> {
+        loop {
+            let control = *self.control.borrow_and_update();
+            match control {
+                Control::Run => {
+                    self.set_state(ExecutionState::Running);
+                    return Ok(());
+                }
+                Control::Cancel => return Err(Stop::Canceled),
+                Control::Pause => {
+                    self.set_state(ExecutionState::Paused);
+                    if self.control.changed().await.is_err() {
+                        // Handle dropped while paused
+                        return Err(Stop::Canceled);
+                    }
+                }
+            }
+        }
+    }
+
+    fn set_state(&self, state: ExecutionState) {
+        self.report.send_if_modified(|report| {
+            let changed = report.state != state;
+            report.state = state;
+            changed
+        });
+    }
+
+    /// Price for an IOC child: through the quote by the slippage, capped at
+    /// the limit
+    fn ioc_price(&self) -> f64 {
+        let parent = &self.parent;
+        match *self.quotes.borrow() {
+            Some(quote) if parent.is_buy => {
+                (quote.ask * (1.0 + parent.slippage)).min(parent.limit_px)
+            }
+            Some(quote) => (quote.bid * (1.0 - parent.slippage)).max(parent.limit_px),
+            None => parent.limit_px,
+        }
+    }
+
+    /// Fails once the coin is disabled by a kill switch
+    fn check_enabled(&self, coin: &str) -> Result<(), Stop> {
+        match &self.kill_switches {
+            Some(switches) => switches
+                .check(coin)
+                .map_err(|e| Stop::Failed(e.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    async fn send(&mut self, child: ChildOrder) -> Result<ChildResult, Stop> {
+        self.check_enabled(&child.coin)?;
+        self.report.send_modify(|report| report.children_sent += 1);
+        match self.venue.place(&child).await {
+            Ok(result) => Ok(result),
+            Err(e) => {
+                warn!("Child order for {} failed: {}", child.coin, e);
+                Ok(ChildResult::Rejected(e.to_string()))
+            }
+        }
+    }
+
+    fn record(&mut self, result: &ChildResult, rejections: &mut u32) -> Result<(), Stop> {
+        match result {
+            ChildResult::Filled { sz, avg_px } => {
+                *rejections = 0;
+                self.report
+                    .send_modify(|report| report.record_fill(*sz, *avg_px));
+            }
+            ChildResult::Resting { .. } => *rejections = 0,
+            ChildResult::Rejected(reason) => {
+                debug!("Child order rejected: {}", reason);
+                self.report
+                    .send_modify(|report| report.children_rejected += 1);
+                *rejections += 1;
+                if *rejections >= MAX_CONSECUTIVE_REJECTIONS {
+                    return Err(Stop::Failed(reason.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// TWAP and VWAP: IOC slices on a fixed schedule
+    async fn scheduled(&mut self) -> Result<(), Stop> {
+        let (duration, fractions) = self.parent.schedule();
+        let interval = duration / fractions.len() as u32;
+        let start = Instant::now();
+        let mut rejections = 0;
+
+        for (index, fraction) in fractions.into_iter().enumerate() {
+            let due = start + interval * index as u32;
+            tokio::select! {
+                _ = sleep_until(due) => {}
+                _ = self.control.changed() => {}
+            }
+            self.checkpoint().await?;
+            // A control change may have woken us early
+            sleep_until(due).await;
+            self.checkpoint().await?;
+
+            let filled = self.report.borrow().filled_sz;
+            let sz = self.parent.sz * fraction - filled;
+            if sz <= SIZE_EPSILON {
+                continue;
+            }
+            let child = ChildOrder {
+                coin: self.parent.coin.clone(),
+                is_buy: self.parent.is_buy,
+                sz,
+                limit_px: self.ioc_price(),
+                tif: "Ioc",
+            };
+            let result = self.send(child).await?;
+            self.record(&result, &mut rejections)?;
+            if self.remaining() <= SIZE_EPSILON {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Iceberg: one resting child at a time until the size is filled
+    async fn iceberg(&mut self, display_sz: f64) -> Result<(), Stop> {
+        let oms = self.oms.clone().expect("checked in Executor::start");
+        let mut rejections = 0;
+
+        while self.remaining() > SIZE_EPSILON {
+            self.checkpoint().await?;
+
+            let sz = display_sz.min(self.remaining());
+            let child = ChildOrder {
+                coin: self.parent.coin.clone(),
+                is_buy: self.parent.is_buy,
+                sz,
+                limit_px: self.parent.limit_px,
+                tif: "Gtc",
+            };
+            self.check_enabled(&child.coin)?;
+            let mut events = oms.events();
+            let key = oms.record_submission(&child.coin, child.is_buy, sz, child.limit_px, None);
+            let result = self.send(child).await?;
+            oms.record_status(key, &result.to_status());
+
+            let ChildResult::Resting { oid } = result else {
+                self.record(&result, &mut rejections)?;
+                continue;
+            };
+            rejections = 0;
+
+            // Wait for the child to finish, pulling it on pause or cancel
+            let mut control_open = true;
+            let mut pulled_at = None;
+            loop {
+                let order = oms.get(key);
+                if order.as_ref().map_or(true, |o| o.state.is_terminal()) {
+                    break;
+                }
+                let confirm_by = pulled_at.map(|at: Instant| at + CANCEL_CONFIRM_TIMEOUT);
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    },
+                    changed = self.control.changed(), if control_open && pulled_at.is_none() => {
+                        if changed.is_err() {
+                            control_open = false;
+                        } else if *self.control.borrow() != Control::Run {
+                            if let Err(e) = self.venue.cancel(&self.parent.coin, oid).await {
+                                warn!("Failed to cancel iceberg child {}: {}", oid, e);
+                            }
+                            pulled_at = Some(Instant::now());
+                        }
+                    }
+                    _ = sleep_until(confirm_by.unwrap_or_else(Instant::now)), if confirm_by.is_some() => {
+                        warn!("No cancel confirmation for iceberg child {}", oid);
+                        break;
+                    }
+                }
+            }
+
+            if let Some(order) = oms.get(key) {
+                if order.filled_sz > 0.0 {
+                    self.report.send_modify(|report| {
+                        report.record_fill(order.filled_sz, order.avg_fill_px)
+                    });
+                }
+                if order.state == OrderState::Rejected {
+                    self.record(
+                        &ChildResult::Rejected(order.reason.unwrap_or_default()),
+                        &mut rejections,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+//! Client-side expiry of resting orders
+//!
+//! Hyperliquid has no good-till-date orders. An [`OrderExpiry`] gives the
+//! orders of an [`OrderManager`] a deadline and cancels them through a
+//! [`KillSwitch`] once it passes, turning GTC and ALO orders into GTD ones.
+//!
+//! - A deadline counts from when the order is first seen: its `Submitted`
+//!   event, or the first check that finds it open. It is the order's
+//!   [`Expiry`] if set with [`OrderExpiry::set_expiry`], else the coin's or
+//!   the default TTL; orders with neither never expire.
+//! - TTL deadlines get a random delay of up to the jitter (500ms by
+//!   default), so orders placed together aren't all canceled in one burst.
+//! - Only orders the exchange reported resting are canceled. An IOC order
+//!   never rests, and an order still waiting for its acknowledgement is
+//!   canceled as soon as it rests if its deadline has passed.
+//!
+//! A cancel that doesn't take is resent after 5s while the order stays open.
+//!
+//! ```no_run
+//! # fn example(venue: hyperliquid_core::execution::ExchangeVenue, oms: hyperliquid_core::oms::OrderManager) {
+//! use std::time::Duration;
+//! use hyperliquid_core::expiry::OrderExpiry;
+//!
+//! let expiry = OrderExpiry::new(venue, oms)
+//!     .with_default_ttl(Duration::from_secs(30))
+//!     .with_coin_ttl("ETH", Duration::from_secs(10))
+//!     .with_jitter(Duration::from_secs(2));
+//! let _job = expiry.start(Duration::from_millis(250));
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::oms::{OrderEvent, OrderEventKind, OrderKey, OrderManager, OrderState, TrackedOrder};
+use crate::watchdog::KillSwitch;
+
+/// Default upper bound of the random delay added to TTL deadlines
+pub const DEFAULT_JITTER: Duration = Duration::from_millis(500);
+
+/// Time after which a cancel is resent if the order is still open
+const CANCEL_RETRY: Duration = Duration::from_secs(5);
+
+/// When an order expires
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expiry {
+    /// Good till canceled, whatever the TTLs
+    Never,
+    /// This long after the order was first seen, plus jitter
+    After(Duration),
+    /// At this time (ms), without jitter
+    At(u64),
+}
+
+#[derive(Debug)]
+struct Tracked {
+    /// When the order was first seen (ms)
+    seen_at: u64,
+    /// `None` if the order never expires
+    deadline: Option<u64>,
+    /// When a cancel was last sent (ms)
+    canceled_at: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    orders: HashMap<OrderKey, Tracked>,
+    expired: u64,
+}
+
+/// Cancels orders of an [`OrderManager`] once they outlive their TTL
+pub struct OrderExpiry<K> {
+    venue: Arc<K>,
+    orders: OrderManager,
+    state: Arc<Mutex<State>>,
+    clock: Arc<dyn Clock>,
+    default_ttl: Option<Duration>,
+    coin_ttls: HashMap<String, Duration>,
+    jitter: Duration,
+    /// Serializes checks so an expired order is canceled once
+    checking: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<K> Clone for OrderExpiry<K> {
+    fn clone(&self) -> Self {
+        Self {
+            venue: Arc::clone(&self.venue),
+            orders: self.orders.clone(),
+            state: Arc::clone(&self.state),
+            clock: Arc::clone(&self.clock),
+            default_ttl: self.default_ttl,
+            coin_ttls: self.coin_ttls.clone(),
+            jitter: self.jitter,
+            checking: Arc::clone(&self.checking),
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for OrderExpiry<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("OrderExpiry")
+            .field("default_ttl", &self.default_ttl)
+            .field("coin_ttls", &self.coin_ttls)
+            .field("jitter", &self.jitter)
+            .field("tracked", &state.orders.len())
+            .field("expired", &state.expired)
+            .finish()
+    }
+}
+
+impl<K> OrderExpiry<K> {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<K: KillSwitch> OrderExpiry<K> {
+    /// Expire orders of `orders`, canceling them through `venue`
+    ///
+    /// Without a TTL nothing expires until one is set.
+    pub fn new(venue: K, orders: OrderManager) -> Self {
+        Self {
+            venue: Arc::new(venue),
+            orders,
+            state: Arc::new(Mutex::new(State::default())),
+            clock: clock::system(),
+            default_ttl: None,
+            coin_ttls: HashMap::new(),
+            jitter: DEFAULT_JITTER,
+            checking: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// TTL of orders of coins without their own
+    pub fn with_default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// TTL of orders of `coin`
+    pub fn with_coin_ttl(mut self, coin: impl Into<String>, ttl: Duration) -> Self {
+        self.coin_ttls.insert(coin.into(), ttl);
+        self
+    }
+
+    /// Upper bound of the random delay added to TTL deadlines (default
+    /// 500ms); zero disables it
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn ttl(&self, coin: &str) -> Option<Duration> {
+        self.coin_ttls.get(coin).copied().or(self.default_ttl)
+    }
+
+    fn deadline(&self, seen_at: u64, expiry: Expiry) -> Option<u64> {
+        match expiry {
+            Expiry::Never => None,
+            Expiry::After(ttl) => {
+                let jitter = (self.jitter.as_millis() as f64 * rand::random::<f64>()) as u64;
+                Some(seen_at + ttl.as_millis() as u64 + jitter)
+            }
+            Expiry::At(time) => Some(time),
+        }
+    }
+
+    /// Start tracking `order` if it is new
+    fn see(&self, state: &mut State, order: &TrackedOrder, now: u64) {
+        if state.orders.contains_key(&order.key) {
+            return;
+        }
+        let expiry = self.ttl(&order.coin).map_or(Expiry::Never, Expiry::After);
+        state.orders.insert(
+            order.key,
+            Tracked {
+                seen_at: now,
+                deadline: self.deadline(now, expiry),
+                canceled_at: None,
+            },
+        );
+    }
+
+    /// Override when the open order `key` expires
+    ///
+    /// Typically called right after [`OrderManager::record_submission`].
+    /// Does nothing if the order is unknown or no longer open.
+    pub fn set_expiry(&self, key: OrderKey, expiry: Expiry) {
+        let Some(order) = self.orders.get(key).filter(|order| order.state.is_open()) else {
+            return;
+        };
+        let now = self.clock.now_ms();
+        let mut state = self.lock();
+        self.see(&mut state, &order, now);
+        let seen_at = state.orders[&key].seen_at;
+        let deadline = self.deadline(seen_at, expiry);
+        if let Some(tracked) = state.orders.get_mut(&key) {
+            tracked.deadline = deadline;
+        }
+    }
+
+    /// When the order `key` expires (ms), if it is tracked and expires
+    pub fn deadline_of(&self, key: OrderKey) -> Option<u64> {
+        self.lock()
+            .orders
+            .get(&key)
+            .and_then(|tracked| tracked.deadline)
+    }
+
+    /// Orders canceled for expiring so far
+    pub fn expired(&self) -> u64 {
+        self.lock().expired
+    }
+
+    /// Start the clock of a submitted order
+    pub fn handle_event(&self, event: &OrderEvent) {
+        if matches!(event.kind, OrderEventKind::Submitted) {
+            let now = self.clock.now_ms();
+            self.see(&mut self.lock(), &event.order, now);
+        }
+    }
+
+    /// Track newly open orders, forget closed ones and return the resting
+    /// orders due a cancel
+    fn due(&self, now: u64) -> Vec<TrackedOrder> {
+        let open = self.orders.open_orders();
+        let mut state = self.lock();
+        let keys: HashSet<OrderKey> = open.iter().map(|order| order.key).collect();
+        state.orders.retain(|key, _| keys.contains(key));
+        for order in &open {
+            self.see(&mut state, order, now);
+        }
+        let retry = CANCEL_RETRY.as_millis() as u64;
+        open.into_iter()
+            .filter(|order| {
+                let resting =
+                    matches!(order.state, OrderState::Acked | OrderState::PartiallyFilled);
+                resting
+                    && state.orders.get(&order.key).is_some_and(|tracked| {
+                        tracked.deadline.is_some_and(|deadline| deadline <= now)
+                            && !tracked
+                                .canceled_at
+                                .is_some_and(|at| now.saturating_sub(at) < retry)
+                    })
+            })
+            .collect()
+    }
+
+    /// Cancel the resting orders whose deadline has passed
+    ///
+    /// Returns the keys of the orders canceled. On `Err` the cancel failed
+    /// and is retried by the next check.
+    pub async fn check(&self) -> Result<Vec<OrderKey>, HyperliquidError> {
+        let _checking = self.checking.lock().await;
+        let now = self.clock.now_ms();
+        let due = self.due(now);
+        if due.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.venue.cancel_orders(&due).await?;
+        let mut guard = self.lock();
+        let state = &mut *guard;
+        for order in &due {
+            if let Some(tracked) = state.orders.get_mut(&order.key) {
+                if tracked.canceled_at.is_none() {
+                    info!("Order {:?} on {} expired", order.key, order.coin);
+                    crate::telemetry::counter!("hyperliquid_orders_expired_total", "coin" => order.coin.clone())
+                        .increment(1);
+                    state.expired += 1;
+                }
+                tracked.canceled_at = Some(now);
+            }
+        }
+        Ok(due.iter().map(|order| order.key).collect())
+    }
+
+    /// Follow the order manager's events and check every `period` in the
+    /// background until the handle is aborted
+    pub fn start(&self, period: Duration) -> JoinHandle<()> {
+        let expiry = self.clone();
+        let mut events = self.orders.events();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    event = events.recv() => match event {
+                        Ok(event) => expiry.handle_event(&event),
+                        // Missed orders are picked up by the next check
+                        Err(RecvError::Lagged(_)) => {}
+                        Err(RecvError::Closed) => return,
+                    },
+                    _ = interval.tick() => {
+                        if let Err(e) = expiry.check().await {
+                            warn!("Failed to cancel expired orders: {}", e);
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
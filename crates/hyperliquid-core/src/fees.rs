@@ -0,0 +1,508 @@
+//! Expected trading fees and fee reconciliation
+//!
+//! A [`FeeModel`] gives the fee rate a fill should have been charged.
+//! [`TieredFeeModel`] follows the exchange's schedule: the volume tier sets
+//! the perp or spot taker and maker rates, referral and staking discounts
+//! reduce positive fees, and a maker rebate replaces the maker rate.
+//! [`FixedRates`] charges flat rates, e.g. those reported by `userFees`.
+//!
+//! A [`FeeReconciler`] compares each fill's actual fee with the model and
+//! with the builder rate, and keeps the fills that disagree as
+//! [`FeeDiscrepancy`]s. The actual `fee` of a fill includes its
+//! `builderFee`; the two parts are checked separately. Spot fees paid in the
+//! base token are converted to USDC at the fill price.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::fees::{FeeReconciler, TieredFeeModel};
+//!
+//! let model = TieredFeeModel::hyperliquid()
+//!     .with_volume(30_000_000.0)
+//!     .with_referral_discount(0.04);
+//! let mut reconciler = FeeReconciler::new(model);
+//! reconciler
+//!     .fetch_user(&client, "0x0000000000000000000000000000000000000000", 1_700_000_000_000..1_701_000_000_000)
+//!     .await?;
+//! let summary = reconciler.summary();
+//! println!("taker rate {:?}, {} discrepancies", summary.taker_rate(), summary.discrepancies);
+//! reconciler.write_csv(std::io::stdout())?;
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::io::Write;
+use std::ops::Range;
+use std::pin::pin;
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::info::TimePaginator;
+use crate::types::{UserFeesResponse, UserFill};
+
+/// Fee differences below this (in USDC) are rounding
+const MIN_DISCREPANCY: f64 = 1e-6;
+
+/// Default relative difference from the expected fee that is reported
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// Taker and maker rates as fractions of notional; negative is a rebate
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeRates {
+    pub taker: f64,
+    pub maker: f64,
+}
+
+impl FeeRates {
+    pub fn new(taker: f64, maker: f64) -> Self {
+        Self { taker, maker }
+    }
+
+    pub fn rate(&self, crossed: bool) -> f64 {
+        if crossed {
+            self.taker
+        } else {
+            self.maker
+        }
+    }
+}
+
+/// One volume tier of a fee schedule
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// 14-day weighted volume (USDC) from which the tier applies
+    pub min_volume: f64,
+    pub perp: FeeRates,
+    pub spot: FeeRates,
+}
+
+/// Fill facts a fee depends on
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeFill {
+    pub coin: String,
+    pub px: f64,
+    pub sz: f64,
+    /// Whether the fill took liquidity
+    pub crossed: bool,
+    pub time: u64,
+    pub tid: u64,
+    pub oid: u64,
+    /// Total fee charged, including the builder fee, in USDC
+    pub fee: f64,
+    /// Builder fee charged, in USDC
+    pub builder_fee: f64,
+}
+
+impl FeeFill {
+    pub fn notional(&self) -> f64 {
+        self.px * self.sz
+    }
+
+    /// Spot pairs are named `@<index>` or `BASE/QUOTE`
+    pub fn is_spot(&self) -> bool {
+        self.coin.starts_with('@') || self.coin.contains('/')
+    }
+
+    /// Fee kept by the exchange
+    pub fn exchange_fee(&self) -> f64 {
+        self.fee - self.builder_fee
+    }
+}
+
+impl From<&UserFill> for FeeFill {
+    fn from(fill: &UserFill) -> Self {
+        let parse = |value: Option<&str>| value.and_then(|s| s.parse().ok()).unwrap_or(0.0);
+        let px = parse(Some(&fill.px));
+        // Spot buys pay fees in the base token
+        let to_usdc = match fill.fee_token.as_deref() {
+            None | Some("USDC") => 1.0,
+            Some(_) => px,
+        };
+        Self {
+            coin: fill.coin.clone(),
+            px,
+            sz: parse(Some(&fill.sz)),
+            crossed: fill.crossed,
+            time: fill.time,
+            tid: fill.tid,
+            oid: fill.oid,
+            fee: parse(fill.fee.as_deref()) * to_usdc,
+            builder_fee: parse(fill.builder_fee.as_deref()) * to_usdc,
+        }
+    }
+}
+
+/// Expected exchange fee rate of a fill
+pub trait FeeModel: Send + Sync + Debug {
+    /// Fraction of notional charged by the exchange, builder fee excluded
+    fn rate(&self, fill: &FeeFill) -> f64;
+
+    fn expected_fee(&self, fill: &FeeFill) -> f64 {
+        fill.notional() * self.rate(fill)
+    }
+}
+
+/// Same rates for every fill
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedRates(pub FeeRates);
+
+impl FixedRates {
+    /// Rates of a `userFees` response, which are in basis points
+    pub fn from_user_fees(fees: &UserFeesResponse) -> Result<Self, HyperliquidError> {
+        let bps = |value: &str| {
+            value
+                .parse::<f64>()
+                .map(|bps| bps / 10_000.0)
+                .map_err(|_| HyperliquidError::Validation(format!("invalid fee rate: {}", value)))
+        };
+        Ok(Self(FeeRates::new(
+            bps(&fees.taker_fee)?,
+            bps(&fees.maker_fee)?,
+        )))
+    }
+}
+
+impl FeeModel for FixedRates {
+    fn rate(&self, fill: &FeeFill) -> f64 {
+        self.0.rate(fill.crossed)
+    }
+}
+
+/// Volume-tiered schedule with discounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TieredFeeModel {
+    tiers: Vec<FeeTier>,
+    volume: f64,
+    referral_discount: f64,
+    staking_discount: f64,
+    maker_rebate: Option<f64>,
+}
+
+impl TieredFeeModel {
+    /// Schedule with `tiers`, in any order
+    pub fn new(mut tiers: Vec<FeeTier>) -> Self {
+        tiers.sort_by(|a, b| a.min_volume.total_cmp(&b.min_volume));
+        Self {
+            tiers,
+            volume: 0.0,
+            referral_discount: 0.0,
+            staking_discount: 0.0,
+            maker_rebate: None,
+        }
+    }
+
+    /// The exchange's published base schedule
+    pub fn hyperliquid() -> Self {
+        let tier = |min_volume: f64, perp: (f64, f64), spot: (f64, f64)| FeeTier {
+            min_volume,
+            perp: FeeRates::new(perp.0 / 100.0, perp.1 / 100.0),
+            spot: FeeRates::new(spot.0 / 100.0, spot.1 / 100.0),
+        };
+        // Rates in percent
+        Self::new(vec![
+            tier(0.0, (0.045, 0.015), (0.070, 0.040)),
+            tier(5e6, (0.040, 0.012), (0.060, 0.030)),
+            tier(25e6, (0.035, 0.008), (0.050, 0.020)),
+            tier(100e6, (0.030, 0.004), (0.040, 0.010)),
+            tier(500e6, (0.028, 0.0), (0.035, 0.0)),
+            tier(2e9, (0.026, 0.0), (0.030, 0.0)),
+            tier(7e9, (0.024, 0.0), (0.025, 0.0)),
+        ])
+    }
+
+    /// 14-day weighted volume the tier is picked by
+    pub fn with_volume(mut self, volume: f64) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Discount on positive fees for referred users, as a fraction
+    pub fn with_referral_discount(mut self, discount: f64) -> Self {
+        self.referral_discount = discount;
+        self
+    }
+
+    /// Discount on positive fees from staking, as a fraction
+    pub fn with_staking_discount(mut self, discount: f64) -> Self {
+        self.staking_discount = discount;
+        self
+    }
+
+    /// Rebate (as a positive fraction) paid on maker fills instead of a fee
+    pub fn with_maker_rebate(mut self, rebate: f64) -> Self {
+        self.maker_rebate = Some(rebate);
+        self
+    }
+
+    /// Tier for the configured volume
+    pub fn tier(&self) -> Option<&FeeTier> {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_volume <= self.volume)
+    }
+
+    /// Effective rates after discounts and rebates
+    pub fn rates(&self, spot: bool) -> FeeRates {
+        let base = match self.tier() {
+            Some(tier) if spot => tier.spot,
+            Some(tier) => tier.perp,
+            None => FeeRates::new(0.0, 0.0),
+        };
+        let discount = (1.0 - self.referral_discount) * (1.0 - self.staking_discount);
+        let discounted = |rate: f64| if rate > 0.0 { rate * discount } else { rate };
+        FeeRates {
+            taker: discounted(base.taker),
+            maker: match self.maker_rebate {
+                Some(rebate) => -rebate,
+                None => discounted(base.maker),
+            },
+        }
+    }
+}
+
+impl FeeModel for TieredFeeModel {
+    fn rate(&self, fill: &FeeFill) -> f64 {
+        self.rates(fill.is_spot()).rate(fill.crossed)
+    }
+}
+
+/// Fill charged other than expected
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FeeDiscrepancy {
+    pub time: u64,
+    pub coin: String,
+    pub tid: u64,
+    pub oid: u64,
+    pub crossed: bool,
+    pub notional: f64,
+    pub expected_fee: f64,
+    pub actual_fee: f64,
+    pub expected_builder_fee: f64,
+    pub actual_builder_fee: f64,
+}
+
+impl FeeDiscrepancy {
+    /// Exchange fee rate actually charged
+    pub fn actual_rate(&self) -> f64 {
+        if self.notional > 0.0 {
+            self.actual_fee / self.notional
+        } else {
+            0.0
+        }
+    }
+
+    /// Actual minus expected, exchange and builder fees together
+    pub fn difference(&self) -> f64 {
+        (self.actual_fee + self.actual_builder_fee)
+            - (self.expected_fee + self.expected_builder_fee)
+    }
+}
+
+/// Totals over the reconciled fills
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FeeSummary {
+    pub fills: u64,
+    pub taker_notional: f64,
+    pub maker_notional: f64,
+    /// Exchange fees actually charged on taker and maker fills
+    pub taker_fee: f64,
+    pub maker_fee: f64,
+    pub expected_fee: f64,
+    pub actual_fee: f64,
+    pub expected_builder_fee: f64,
+    pub actual_builder_fee: f64,
+    pub discrepancies: usize,
+}
+
+impl FeeSummary {
+    /// Average taker rate charged, if there were taker fills
+    pub fn taker_rate(&self) -> Option<f64> {
+        (self.taker_notional > 0.0).then(|| self.taker_fee / self.taker_notional)
+    }
+
+    /// Average maker rate charged, if there were maker fills
+    pub fn maker_rate(&self) -> Option<f64> {
+        (self.maker_notional > 0.0).then(|| self.maker_fee / self.maker_notional)
+    }
+}
+
+/// Checks fills' fees against a [`FeeModel`]
+#[derive(Debug)]
+pub struct FeeReconciler {
+    model: Box<dyn FeeModel>,
+    builder_rate: f64,
+    tolerance: f64,
+    seen: HashSet<(u64, u64)>,
+    summary: FeeSummary,
+    discrepancies: Vec<FeeDiscrepancy>,
+}
+
+impl FeeReconciler {
+    pub fn new(model: impl FeeModel + 'static) -> Self {
+        Self {
+            model: Box::new(model),
+            builder_rate: 0.0,
+            tolerance: DEFAULT_TOLERANCE,
+            seen: HashSet::new(),
+            summary: FeeSummary::default(),
+            discrepancies: Vec::new(),
+        }
+    }
+
+    /// Builder fee rate, as a fraction, expected on fills that paid one
+    ///
+    /// Fills without a builder fee were not routed through a builder and are
+    /// expected to pay none.
+    pub fn with_builder_rate(mut self, rate: f64) -> Self {
+        self.builder_rate = rate;
+        self
+    }
+
+    /// Relative difference from the expected fee that is reported (default 1%)
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn differs(&self, expected: f64, actual: f64) -> bool {
+        (actual - expected).abs() > (self.tolerance * expected.abs()).max(MIN_DISCREPANCY)
+    }
+
+    /// Check one fill; returns its discrepancy, if any
+    ///
+    /// Fills already seen (same `tid` and `oid`) are skipped.
+    pub fn reconcile(&mut self, fill: &FeeFill) -> Option<FeeDiscrepancy> {
+        if !self.seen.insert((fill.tid, fill.oid)) {
+            return None;
+        }
+        let notional = fill.notional();
+        let expected_fee = self.model.expected_fee(fill);
+        let expected_builder_fee = if fill.builder_fee != 0.0 {
+            notional * self.builder_rate
+        } else {
+            0.0
+        };
+
+        let summary = &mut self.summary;
+        summary.fills += 1;
+        if fill.crossed {
+            summary.taker_notional += notional;
+            summary.taker_fee += fill.exchange_fee();
+        } else {
+            summary.maker_notional += notional;
+            summary.maker_fee += fill.exchange_fee();
+        }
+        summary.expected_fee += expected_fee;
+        summary.actual_fee += fill.exchange_fee();
+        summary.expected_builder_fee += expected_builder_fee;
+        summary.actual_builder_fee += fill.builder_fee;
+
+        if !self.differs(expected_fee, fill.exchange_fee())
+            && !self.differs(expected_builder_fee, fill.builder_fee)
+        {
+            return None;
+        }
+        let discrepancy = FeeDiscrepancy {
+            time: fill.time,
+            coin: fill.coin.clone(),
+            tid: fill.tid,
+            oid: fill.oid,
+            crossed: fill.crossed,
+            notional,
+            expected_fee,
+            actual_fee: fill.exchange_fee(),
+            expected_builder_fee,
+            actual_builder_fee: fill.builder_fee,
+        };
+        self.summary.discrepancies += 1;
+        self.discrepancies.push(discrepancy.clone());
+        Some(discrepancy)
+    }
+
+    /// Check a fill as returned by `userFills`/`userFillsByTime`
+    ///
+    /// Returns whether it was counted; malformed fills are skipped.
+    pub fn ingest_fill(&mut self, fill: &Value) -> bool {
+        match UserFill::deserialize(fill) {
+            Ok(fill) => {
+                let fill = FeeFill::from(&fill);
+                let counted = !self.seen.contains(&(fill.tid, fill.oid));
+                self.reconcile(&fill);
+                counted
+            }
+            Err(e) => {
+                warn!("Ignoring malformed fill: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Check the fills of a `userFills` stream payload; returns how many counted
+    pub fn ingest_user_fills(&mut self, data: &Value) -> usize {
+        let Some(fills) = data.get("fills").and_then(Value::as_array) else {
+            warn!("Ignoring malformed userFills payload");
+            return 0;
+        };
+        fills.iter().filter(|fill| self.ingest_fill(fill)).count()
+    }
+
+    /// Fetch and check `user`'s fills with a time (ms) in `range`
+    ///
+    /// Pages through `userFillsByTime`, which returns at most
+    /// [`USER_FILLS_PAGE_LIMIT`](crate::info::USER_FILLS_PAGE_LIMIT) fills per request. Returns how many counted.
+    pub async fn fetch_user(
+        &mut self,
+        client: &HttpClient,
+        user: &str,
+        range: Range<u64>,
+    ) -> Result<usize, HyperliquidError> {
+        let mut fills = pin!(TimePaginator::user_fills(client.clone(), user, range).stream());
+        let mut counted = 0;
+        while let Some(fill) = fills.next().await {
+            counted += usize::from(self.ingest_fill(&fill?));
+        }
+        Ok(counted)
+    }
+
+    pub fn discrepancies(&self) -> &[FeeDiscrepancy] {
+        &self.discrepancies
+    }
+
+    pub fn summary(&self) -> FeeSummary {
+        self.summary.clone()
+    }
+
+    /// Write the discrepancies as CSV
+    ///
+    /// Columns: `time,coin,tid,oid,crossed,notional,expected_fee,actual_fee,
+    /// expected_builder_fee,actual_builder_fee`.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), HyperliquidError> {
+        writeln!(
+            writer,
+            "time,coin,tid,oid,crossed,notional,expected_fee,actual_fee,expected_builder_fee,actual_builder_fee"
+        )?;
+        for d in &self.discrepancies {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{},{},{}",
+                d.time,
+                d.coin,
+                d.tid,
+                d.oid,
+                d.crossed,
+                d.notional,
+                d.expected_fee,
+                d.actual_fee,
+                d.expected_builder_fee,
+                d.actual_builder_fee
+            )?;
+        }
+        Ok(())
+    }
+}
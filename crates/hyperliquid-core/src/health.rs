@@ -0,0 +1,422 @@
+//! Exchange-wide health signal
+//!
+//! An [`ExchangeHealth`] tracks whether Hyperliquid is usable from two
+//! sources and publishes the worse of them:
+//!
+//! - the `exchangeStatus` info endpoint, polled with
+//!   [`ExchangeHealth::poll`], [`ExchangeHealth::start`] or
+//!   [`Job::exchange_status`](crate::scheduler::Job::exchange_status); a
+//!   special status is reported as [`HealthStatus::Maintenance`]. Other
+//!   sources, such as a status page, report through
+//!   [`ExchangeHealth::report`];
+//! - the outcome of requests: consecutive network errors, timeouts and 5xx
+//!   responses make the exchange [`Degraded`](HealthStatus::Degraded), then
+//!   [`Down`](HealthStatus::Down). Any success clears them.
+//!
+//! Components given the signal back off together while it is unavailable:
+//!
+//! - [`HttpClient::with_health`] is the circuit breaker. It fails requests
+//!   with [`HyperliquidError::ExchangeUnavailable`] without sending them,
+//!   except for one probe per cooldown while the exchange is down;
+//! - [`Scheduler::with_health`](crate::scheduler::Scheduler::with_health)
+//!   skips job runs;
+//! - with [`ExchangeHealth::with_kill_switches`] the global kill switch is
+//!   disabled, and enabled again on recovery unless someone else disabled
+//!   it meanwhile.
+//!
+//! ```no_run
+//! # fn example(client: hyperliquid_core::HttpClient, switches: hyperliquid_core::killswitch::KillSwitchRegistry) {
+//! use std::time::Duration;
+//! use hyperliquid_core::health::ExchangeHealth;
+//!
+//! let health = ExchangeHealth::new().with_kill_switches(switches);
+//! let client = client.with_health(health.clone());
+//! let _job = health.start(client.clone(), Duration::from_secs(10));
+//! # }
+//! ```
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::client::HttpClient;
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::killswitch::{KillSwitchRegistry, Scope};
+
+/// Consecutive failures after which the exchange counts as degraded
+pub const DEFAULT_DEGRADED_AFTER: u32 = 3;
+/// Consecutive failures after which the exchange counts as down
+pub const DEFAULT_DOWN_AFTER: u32 = 10;
+/// Default time between probes while the exchange is down
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Prefix of the reason of kill switch halts set by the health signal
+const HALT_PREFIX: &str = "exchange health: ";
+
+const CHANNEL_CAPACITY: usize = 64;
+
+/// How usable the exchange is, from best to worst
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    #[default]
+    Operational,
+    /// Requests are failing but still sent
+    Degraded,
+    /// Requests keep failing; only probes are sent
+    Down,
+    /// The exchange reported a special status
+    Maintenance,
+}
+
+impl HealthStatus {
+    /// Whether requests should be sent
+    pub fn is_available(&self) -> bool {
+        matches!(self, HealthStatus::Operational | HealthStatus::Degraded)
+    }
+
+    /// Metric label
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthStatus::Operational => "operational",
+            HealthStatus::Degraded => "degraded",
+            HealthStatus::Down => "down",
+            HealthStatus::Maintenance => "maintenance",
+        }
+    }
+}
+
+impl std::fmt::Display for HealthStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// A change of the published status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HealthChange {
+    pub previous: HealthStatus,
+    pub status: HealthStatus,
+    pub reason: String,
+    /// When the status changed (ms)
+    pub time: u64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    reported: HealthStatus,
+    reported_reason: String,
+    inferred: HealthStatus,
+    last_error: String,
+    failures: u32,
+    /// Published status
+    status: HealthStatus,
+    reason: String,
+    /// When a request was last let through while down (ms)
+    probed_at: u64,
+}
+
+impl State {
+    fn effective(&self) -> (HealthStatus, &str) {
+        if self.reported >= self.inferred {
+            (self.reported, &self.reported_reason)
+        } else {
+            (self.inferred, &self.last_error)
+        }
+    }
+}
+
+/// Shared health signal of the exchange
+///
+/// Cheap to clone; clones share state.
+#[derive(Clone)]
+pub struct ExchangeHealth {
+    state: Arc<Mutex<State>>,
+    changes: broadcast::Sender<HealthChange>,
+    clock: Arc<dyn Clock>,
+    kill_switches: Option<KillSwitchRegistry>,
+    degraded_after: u32,
+    down_after: u32,
+    cooldown: Duration,
+}
+
+impl Default for ExchangeHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ExchangeHealth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("ExchangeHealth")
+            .field("status", &state.status)
+            .field("reason", &state.reason)
+            .field("failures", &state.failures)
+            .field("degraded_after", &self.degraded_after)
+            .field("down_after", &self.down_after)
+            .field("cooldown", &self.cooldown)
+            .finish()
+    }
+}
+
+impl ExchangeHealth {
+    /// Operational signal with the default thresholds
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            changes: broadcast::channel(CHANNEL_CAPACITY).0,
+            clock: clock::system(),
+            kill_switches: None,
+            degraded_after: DEFAULT_DEGRADED_AFTER,
+            down_after: DEFAULT_DOWN_AFTER,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Consecutive failures after which the exchange is degraded (default
+    /// 3) and down (default 10)
+    pub fn with_thresholds(mut self, degraded_after: u32, down_after: u32) -> Self {
+        self.degraded_after = degraded_after.max(1);
+        self.down_after = down_after.max(self.degraded_after);
+        self
+    }
+
+    /// Time between probes while the exchange is down (default 30s)
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Halt all trading on `switches` while the exchange is unavailable
+    pub fn with_kill_switches(mut self, switches: KillSwitchRegistry) -> Self {
+        self.kill_switches = Some(switches);
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Status changes from now on
+    pub fn changes(&self) -> broadcast::Receiver<HealthChange> {
+        self.changes.subscribe()
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        self.lock().status
+    }
+
+    /// Why the exchange has its status; empty while operational
+    pub fn reason(&self) -> String {
+        self.lock().reason.clone()
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.status().is_available()
+    }
+
+    /// Fail with [`HyperliquidError::ExchangeUnavailable`] if a request
+    /// should not be sent
+    ///
+    /// While down, one caller per cooldown is let through to probe.
+    pub fn allow_request(&self) -> Result<(), HyperliquidError> {
+        let now = self.clock.now_ms();
+        let mut state = self.lock();
+        match state.status {
+            HealthStatus::Operational | HealthStatus::Degraded => return Ok(()),
+            HealthStatus::Down
+                if now.saturating_sub(state.probed_at) >= self.cooldown.as_millis() as u64 =>
+            {
+                state.probed_at = now;
+                return Ok(());
+            }
+            HealthStatus::Down | HealthStatus::Maintenance => {}
+        }
+        Err(HyperliquidError::ExchangeUnavailable {
+            status: state.status.to_string(),
+            reason: state.reason.clone(),
+        })
+    }
+
+    /// Record a request the exchange answered
+    pub fn record_success(&self) {
+        let change = {
+            let mut state = self.lock();
+            state.failures = 0;
+            state.inferred = HealthStatus::Operational;
+            state.last_error.clear();
+            self.update(&mut state)
+        };
+        self.publish(change);
+    }
+
+    /// Record a failed request
+    ///
+    /// Only network errors, timeouts and 5xx responses count; rate limits
+    /// and rejected requests say nothing about the exchange's health.
+    pub fn record_error(&self, error: &HyperliquidError) {
+        if !error.is_retryable() || error.is_rate_limited() {
+            return;
+        }
+        let change = {
+            let mut state = self.lock();
+            state.failures = state.failures.saturating_add(1);
+            state.inferred = if state.failures >= self.down_after {
+                HealthStatus::Down
+            } else if state.failures >= self.degraded_after {
+                HealthStatus::Degraded
+            } else {
+                HealthStatus::Operational
+            };
+            state.last_error = format!("{} consecutive failures, last: {}", state.failures, error);
+            self.update(&mut state)
+        };
+        self.publish(change);
+    }
+
+    /// Report the status from an external source
+    ///
+    /// It stands until the next report; [`HealthStatus::Operational`]
+    /// clears it.
+    pub fn report(&self, status: HealthStatus, reason: impl Into<String>) {
+        let change = {
+            let mut state = self.lock();
+            state.reported = status;
+            state.reported_reason = match status {
+                HealthStatus::Operational => String::new(),
+                _ => reason.into(),
+            };
+            self.update(&mut state)
+        };
+        self.publish(change);
+    }
+
+    /// Publish the effective status if it changed
+    fn update(&self, state: &mut State) -> Option<HealthChange> {
+        let (status, reason) = state.effective();
+        let reason = reason.to_string();
+        if status == state.status {
+            state.reason = reason;
+            return None;
+        }
+        let now = self.clock.now_ms();
+        if status == HealthStatus::Down {
+            // The first probe waits a full cooldown
+            state.probed_at = now;
+        }
+        let change = HealthChange {
+            previous: state.status,
+            status,
+            reason: reason.clone(),
+            time: now,
+        };
+        state.status = status;
+        state.reason = reason;
+        Some(change)
+    }
+
+    fn publish(&self, change: Option<HealthChange>) {
+        let Some(change) = change else {
+            return;
+        };
+        if change.status.is_available() {
+            info!("Exchange {} (was {})", change.status, change.previous);
+        } else {
+            warn!("Exchange {}: {}", change.status, change.reason);
+        }
+        crate::telemetry::counter!("hyperliquid_exchange_health_changes_total", "status" => change.status.label())
+            .increment(1);
+        for status in [
+            HealthStatus::Operational,
+            HealthStatus::Degraded,
+            HealthStatus::Down,
+            HealthStatus::Maintenance,
+        ] {
+            crate::telemetry::gauge!("hyperliquid_exchange_health", "status" => status.label())
+                .set(if status == change.status { 1.0 } else { 0.0 });
+        }
+        if let Some(switches) = &self.kill_switches {
+            self.apply_kill_switch(switches, &change);
+        }
+        let _ = self.changes.send(change);
+    }
+
+    fn apply_kill_switch(&self, switches: &KillSwitchRegistry, change: &HealthChange) {
+        let ours = switches
+            .halts()
+            .into_iter()
+            .find(|halt| halt.scope == Scope::All)
+            .map(|halt| halt.reason.starts_with(HALT_PREFIX));
+        let result = match (change.status.is_available(), ours) {
+            // Leave a halt someone else set alone
+            (_, Some(false)) => Ok(()),
+            (false, _) => switches.disable(
+                Scope::All,
+                format!("{}{} ({})", HALT_PREFIX, change.status, change.reason),
+            ),
+            (true, Some(true)) => switches.enable(&Scope::All),
+            (true, None) => Ok(()),
+        };
+        if let Err(e) = result {
+            warn!("Failed to update the exchange health kill switch: {}", e);
+        }
+    }
+
+    /// Fetch `exchangeStatus` and report it
+    ///
+    /// The request bypasses the client's circuit breaker, so polling goes on
+    /// while the exchange is unavailable. A failed poll counts as a failed
+    /// request.
+    pub async fn poll(&self, client: &HttpClient) -> Result<HealthStatus, HyperliquidError> {
+        let client = client.clone().without_health();
+        let response: Value = match client
+            .post("/info", &json!({"type": "exchangeStatus"}))
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_error(&e);
+                return Err(e);
+            }
+        };
+        self.record_success();
+        let (status, reason) = match response.get("specialStatuses") {
+            None | Some(Value::Null) => (HealthStatus::Operational, String::new()),
+            Some(Value::Array(statuses)) if statuses.is_empty() => {
+                (HealthStatus::Operational, String::new())
+            }
+            Some(statuses) => (HealthStatus::Maintenance, statuses.to_string()),
+        };
+        self.report(status, reason);
+        Ok(status)
+    }
+
+    /// Poll `exchangeStatus` every `period` in the background until the
+    /// handle is aborted
+    pub fn start(&self, client: HttpClient, period: Duration) -> JoinHandle<()> {
+        let health = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = health.poll(&client).await {
+                    warn!("Failed to poll exchange status: {}", e);
+                }
+            }
+        })
+    }
+}
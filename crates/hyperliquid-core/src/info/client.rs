@@ -1,18 +1,31 @@
 //! Info API client implementation
 
+use super::meta_cache::{MetaCache, DEFAULT_META_TTL};
+use super::paginate::TimePaginator;
 use crate::client::HttpClient;
 use crate::error::HyperliquidError;
 use crate::types::*;
+use futures::TryStreamExt;
 use serde_json::{json, Value};
 use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+/// Most fills returned by one `userFills`/`userFillsByTime` request
+pub const USER_FILLS_PAGE_LIMIT: usize = 2000;
 
 /// Client for accessing Hyperliquid Info API
 #[derive(Clone)]
 pub struct InfoClient {
     client: HttpClient,
-    coin_to_asset: HashMap<String, u32>,
+    coin_to_asset: HashMap<SymbolId, u32>,
+    asset_to_coin: HashMap<u32, SymbolId>,
     name_to_coin: HashMap<String, String>,
     asset_to_sz_decimals: HashMap<u32, u32>,
+    spot: Option<Arc<SpotUniverse>>,
+    meta_cache: Arc<MetaCache>,
 }
 
 impl InfoClient {
@@ -21,11 +34,22 @@ impl InfoClient {
         Self {
             client,
             coin_to_asset: HashMap::new(),
+            asset_to_coin: HashMap::new(),
             name_to_coin: HashMap::new(),
             asset_to_sz_decimals: HashMap::new(),
+            spot: None,
+            meta_cache: Arc::new(MetaCache::new(DEFAULT_META_TTL)),
         }
     }
 
+    /// Refresh cached `meta` responses older than `ttl` (default 60s)
+    ///
+    /// Starts a new cache; clones made before this call keep sharing the old one.
+    pub fn with_meta_ttl(mut self, ttl: Duration) -> Self {
+        self.meta_cache = Arc::new(MetaCache::new(ttl));
+        self
+    }
+
     /// Create an Info client with default configuration
     pub async fn with_default_config(base_url: &str) -> Result<Self, HyperliquidError> {
         let client = HttpClient::with_default_config(base_url)?;
@@ -34,13 +58,63 @@ impl InfoClient {
 
     /// Get exchange metadata including universe of assets
     pub async fn meta(&self, dex: &str) -> Result<Meta, HyperliquidError> {
-        let request_body = json!({
-            "type": "meta",
-            "dex": dex
-        });
+        fetch_meta(&self.client, dex).await
+    }
 
-        let response: Meta = self.client.post("/info", &request_body).await?;
-        Ok(response)
+    /// Cached metadata for `dex`, without waiting on the network
+    ///
+    /// Returns `None` until the first fetch for `dex` completes. A missing or
+    /// stale entry starts a background refresh (when called inside a Tokio
+    /// runtime); the stale entry is returned meanwhile.
+    pub fn meta_cached(&self, dex: &str) -> Option<Arc<Meta>> {
+        let cached = self.meta_cache.get(dex);
+        if cached.as_ref().map_or(true, |(_, stale)| *stale) {
+            self.spawn_meta_refresh(dex);
+        }
+        cached.map(|(meta, _)| meta)
+    }
+
+    /// Cached metadata for `dex`, fetching it only if nothing is cached yet
+    ///
+    /// A stale entry is returned immediately and refreshed in the background.
+    pub async fn meta_cached_or_fetch(&self, dex: &str) -> Result<Arc<Meta>, HyperliquidError> {
+        if let Some((meta, stale)) = self.meta_cache.get(dex) {
+            if stale {
+                self.spawn_meta_refresh(dex);
+            }
+            return Ok(meta);
+        }
+        self.refresh_meta(dex).await
+    }
+
+    /// Fetch metadata for `dex` and update the cache
+    pub async fn refresh_meta(&self, dex: &str) -> Result<Arc<Meta>, HyperliquidError> {
+        let result = fetch_meta(&self.client, dex).await;
+        self.meta_cache.store(dex, result)
+    }
+
+    /// Drop the cached metadata for `dex`, e.g. after a listing
+    pub fn invalidate_meta(&self, dex: &str) {
+        self.meta_cache.invalidate(dex);
+    }
+
+    fn spawn_meta_refresh(&self, dex: &str) {
+        if !self.meta_cache.begin_refresh(dex) {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            self.meta_cache.abandon_refresh(dex);
+            return;
+        };
+        let client = self.client.clone();
+        let cache = self.meta_cache.clone();
+        let dex = dex.to_string();
+        runtime.spawn(async move {
+            let result = fetch_meta(&client, &dex).await;
+            if let Err(e) = cache.store(&dex, result) {
+                warn!("Background meta refresh for dex {:?} failed: {}", dex, e);
+            }
+        });
     }
 
     /// Get exchange metadata for mainnet (default)
@@ -259,23 +333,41 @@ impl InfoClient {
         self.open_orders(address, "").await
     }
 
-    /// Get user's frontend open orders
-    pub async fn frontend_open_orders(&self, address: &str, dex: &str) -> Result<Vec<NewOrder>, HyperliquidError> {
+    /// Get user's frontend open orders, with trigger details and TP/SL children
+    pub async fn frontend_open_orders(&self, address: &str, dex: &str) -> Result<Vec<FrontendOrder>, HyperliquidError> {
         let request_body = json!({
             "type": "frontendOpenOrders",
             "user": address,
             "dex": dex
         });
 
-        let response: Vec<NewOrder> = self.client.post("/info", &request_body).await?;
+        let response: Vec<FrontendOrder> = self.client.post("/info", &request_body).await?;
         Ok(response)
     }
 
     /// Get user's frontend open orders for mainnet (default)
-    pub async fn frontend_open_orders_mainnet(&self, address: &str) -> Result<Vec<NewOrder>, HyperliquidError> {
+    pub async fn frontend_open_orders_mainnet(&self, address: &str) -> Result<Vec<FrontendOrder>, HyperliquidError> {
         self.frontend_open_orders(address, "").await
     }
 
+    /// Get user's open orders from `frontendOpenOrders` and `openOrders`, merged by oid
+    ///
+    /// Both requests are sent concurrently; see [`OpenOrders`] for how they
+    /// are reconciled and filtered.
+    pub async fn user_open_orders(&self, address: &str, dex: &str) -> Result<OpenOrders, HyperliquidError> {
+        let basic_request = json!({
+            "type": "openOrders",
+            "user": address,
+            "dex": dex
+        });
+
+        let (frontend, basic) = tokio::try_join!(
+            self.frontend_open_orders(address, dex),
+            self.client.post::<_, Vec<BasicOpenOrder>>("/info", &basic_request)
+        )?;
+        Ok(OpenOrders::merge(frontend, basic))
+    }
+
     /// Get user's fill history
     pub async fn user_fills(&self, address: &str) -> Result<Vec<WithFee>, HyperliquidError> {
         let request_body = json!({
@@ -305,6 +397,24 @@ impl InfoClient {
         Ok(response)
     }
 
+    /// Get user's fills in `range` (ms) grouped by order
+    ///
+    /// Pages through `userFillsByTime`, which returns at most
+    /// [`USER_FILLS_PAGE_LIMIT`] fills per request, until the range is
+    /// covered.
+    pub async fn user_fills_aggregated(
+        &self,
+        address: &str,
+        range: Range<i64>,
+    ) -> Result<Vec<OrderExecution>, HyperliquidError> {
+        let range = range.start.max(0) as u64..range.end.max(0) as u64;
+        let fills: Vec<UserFill> = TimePaginator::user_fills(self.client.clone(), address, range)
+            .stream()
+            .try_collect()
+            .await?;
+        Ok(OrderExecution::aggregate(&fills))
+    }
+
     /// Get user's fee information
     pub async fn user_fees(&self, address: &str) -> Result<UserFeesResponse, HyperliquidError> {
         let request_body = json!({
@@ -391,6 +501,16 @@ impl InfoClient {
         Ok(response)
     }
 
+    /// Get the spot pairs with their tokens
+    pub async fn spot_universe(&self) -> Result<SpotUniverse, HyperliquidError> {
+        let request_body = json!({
+            "type": "spotMeta"
+        });
+
+        let response: Value = self.client.post("/info", &request_body).await?;
+        SpotUniverse::from_spot_meta(&response)
+    }
+
     /// Get spot metadata with asset contexts
     pub async fn spot_meta_and_asset_ctxs(&self) -> Result<(SpotMeta, HashMap<String, u32>), HyperliquidError> {
         let spot_meta = self.spot_meta().await?;
@@ -468,18 +588,24 @@ impl InfoClient {
     // Utility methods for asset management
 
     /// Initialize asset mappings from metadata
+    ///
+    /// Uses the cached metadata for `dex` when there is one; see
+    /// [`meta_cached_or_fetch`](Self::meta_cached_or_fetch).
     pub async fn initialize_assets(&mut self, dex: &str) -> Result<(), HyperliquidError> {
-        let meta = self.meta(dex).await?;
+        let meta = self.meta_cached_or_fetch(dex).await?;
 
         // Clear existing mappings
         self.coin_to_asset.clear();
+        self.asset_to_coin.clear();
         self.name_to_coin.clear();
         self.asset_to_sz_decimals.clear();
 
         // Map assets (starting from index 0 for perp assets)
         for (index, asset) in meta.universe.iter().enumerate() {
             let asset_index = index as u32;
-            self.coin_to_asset.insert(asset.name.clone(), asset_index);
+            let symbol = SymbolId::intern(&asset.name);
+            self.coin_to_asset.insert(symbol, asset_index);
+            self.asset_to_coin.insert(asset_index, symbol);
             self.name_to_coin.insert(asset.name.clone(), asset.name.clone());
             self.asset_to_sz_decimals.insert(asset_index, asset.sz_decimals as u32);
         }
@@ -487,17 +613,42 @@ impl InfoClient {
         Ok(())
     }
 
-    /// Get asset index for a coin name
+    /// Initialize spot pair mappings from `spotMeta`
+    ///
+    /// Afterwards [`asset_for_coin`](Self::asset_for_coin) and
+    /// [`sz_decimals_for_coin`](Self::sz_decimals_for_coin) also resolve spot
+    /// pairs, by wire name (`"@107"`) or `BASE/QUOTE`.
+    pub async fn initialize_spot_assets(&mut self) -> Result<(), HyperliquidError> {
+        self.spot = Some(Arc::new(self.spot_universe().await?));
+        Ok(())
+    }
+
+    /// Spot pairs loaded by [`initialize_spot_assets`](Self::initialize_spot_assets)
+    pub fn spot_pairs(&self) -> Option<&SpotUniverse> {
+        self.spot.as_deref()
+    }
+
+    /// Get asset index for a coin name, or the asset id of a spot pair
     pub fn asset_for_coin(&self, coin: &str) -> Option<u32> {
-        self.coin_to_asset.get(coin).copied()
+        global_symbols()
+            .lookup(coin)
+            .and_then(|symbol| self.asset_for_symbol(symbol))
+            .or_else(|| self.spot.as_ref()?.asset_id(coin))
+    }
+
+    /// Get asset index for an interned coin
+    pub fn asset_for_symbol(&self, symbol: SymbolId) -> Option<u32> {
+        self.coin_to_asset.get(&symbol).copied()
+    }
+
+    /// Get the interned coin for an asset index
+    pub fn symbol_for_asset(&self, asset: u32) -> Option<SymbolId> {
+        self.asset_to_coin.get(&asset).copied()
     }
 
     /// Get coin name for an asset index
-    pub fn coin_for_asset(&self, asset: u32) -> Option<&str> {
-        self.coin_to_asset
-            .iter()
-            .find(|(_, &v)| v == asset)
-            .map(|(k, _)| k.as_str())
+    pub fn coin_for_asset(&self, asset: u32) -> Option<&'static str> {
+        self.symbol_for_asset(asset).and_then(|symbol| symbol.name())
     }
 
     /// Get size decimals for an asset
@@ -509,16 +660,22 @@ impl InfoClient {
     pub fn sz_decimals_for_coin(&self, coin: &str) -> Option<u32> {
         self.asset_for_coin(coin)
             .and_then(|asset| self.sz_decimals_for_asset(asset))
+            .or_else(|| self.spot.as_ref()?.sz_decimals(coin))
     }
 
     /// Get all known coins
-    pub fn all_coins(&self) -> Vec<&String> {
-        self.coin_to_asset.keys().collect()
+    pub fn all_coins(&self) -> Vec<&'static str> {
+        self.coin_to_asset.keys().filter_map(|symbol| symbol.name()).collect()
+    }
+
+    /// Get all known coins as interned symbols
+    pub fn all_symbols(&self) -> Vec<SymbolId> {
+        self.coin_to_asset.keys().copied().collect()
     }
 
     /// Check if a coin is known
     pub fn is_known_coin(&self, coin: &str) -> bool {
-        self.coin_to_asset.contains_key(coin)
+        self.asset_for_coin(coin).is_some()
     }
 
     /// Get user's staking summary including total delegated and rewards
@@ -708,6 +865,7 @@ impl InfoClient {
     /// Returns:
     ///     Comprehensive ledger updates including deposits, withdrawals, transfers,
     ///     liquidations, and other account activities excluding funding payments.
+    ///     Delta types without a typed variant come back as `LedgerUpdate::Other`.
     ///
     /// Example:
     ///     ```rust
@@ -722,7 +880,7 @@ impl InfoClient {
         user: &str,
         start_time: i64,
         end_time: Option<i64>,
-    ) -> Result<Value, HyperliquidError> {
+    ) -> Result<Vec<UserLedgerUpdate>, HyperliquidError> {
         let mut request_body = json!({
             "type": "userNonFundingLedgerUpdates",
             "user": user,
@@ -735,7 +893,7 @@ impl InfoClient {
                 .insert("endTime".to_string(), serde_json::Value::Number(end_time.into()));
         }
 
-        let response: Value = self.client.post("/info", &request_body).await?;
+        let response: Vec<UserLedgerUpdate> = self.client.post("/info", &request_body).await?;
         Ok(response)
     }
 
@@ -745,7 +903,7 @@ impl InfoClient {
         user: &str,
         start_time: i64,
         end_time: Option<i64>,
-    ) -> Result<Value, HyperliquidError> {
+    ) -> Result<Vec<UserLedgerUpdate>, HyperliquidError> {
         self.user_non_funding_ledger_updates(user, start_time, end_time).await
     }
 
@@ -960,6 +1118,16 @@ impl InfoClient {
     }
 }
 
+async fn fetch_meta(client: &HttpClient, dex: &str) -> Result<Meta, HyperliquidError> {
+    let request_body = json!({
+        "type": "meta",
+        "dex": dex
+    });
+
+    let response: Meta = client.post("/info", &request_body).await?;
+    Ok(response)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1023,10 +1191,14 @@ mod tests {
         assert_eq!(info_client.sz_decimals_for_coin("BTC"), None);
 
         // Add manual mappings for testing
-        info_client.coin_to_asset.insert("BTC".to_string(), 0);
+        let btc = SymbolId::intern("BTC");
+        info_client.coin_to_asset.insert(btc, 0);
+        info_client.asset_to_coin.insert(0, btc);
         info_client.asset_to_sz_decimals.insert(0, 8);
 
         assert_eq!(info_client.asset_for_coin("BTC"), Some(0));
+        assert_eq!(info_client.asset_for_symbol(btc), Some(0));
+        assert_eq!(info_client.coin_for_asset(0), Some("BTC"));
         assert_eq!(info_client.sz_decimals_for_coin("BTC"), Some(8));
         assert!(info_client.is_known_coin("BTC"));
         assert!(!info_client.is_known_coin("ETH"));
@@ -1049,6 +1221,12 @@ mod tests {
                     ctx: 1,
                 },
             ],
+            universe: vec![SpotPair {
+                name: "BTC/USDC".to_string(),
+                tokens: [0, 1],
+                index: 0,
+                is_canonical: true,
+            }],
         };
 
         // Serialize to JSON
@@ -1067,6 +1245,7 @@ mod tests {
             assert_eq!(original.token, deserialized.token);
             assert_eq!(original.ctx, deserialized.ctx);
         }
+        assert_eq!(spot_meta.universe, deserialized.universe);
     }
 
     #[tokio::test]
@@ -0,0 +1,100 @@
+//! Per-dex cache of `meta` responses
+//!
+//! Entries older than the TTL are still served while a refresh runs in the
+//! background (stale-while-revalidate), so callers on the trading path only
+//! wait on `/info` the first time a dex is requested.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::HyperliquidError;
+use crate::types::Meta;
+
+/// Default age after which a cached `Meta` is refreshed
+pub(crate) const DEFAULT_META_TTL: Duration = Duration::from_secs(60);
+
+/// Minimum time between background refreshes of a dex after one failed
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<String, (Arc<Meta>, Instant)>,
+    refreshing: HashSet<String>,
+    failed: HashMap<String, Instant>,
+}
+
+#[derive(Debug)]
+pub(crate) struct MetaCache {
+    ttl: Duration,
+    state: Mutex<State>,
+}
+
+impl MetaCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Cached meta for `dex` and whether it is past the TTL
+    pub(crate) fn get(&self, dex: &str) -> Option<(Arc<Meta>, bool)> {
+        self.lock()
+            .entries
+            .get(dex)
+            .map(|(meta, fetched_at)| (meta.clone(), fetched_at.elapsed() >= self.ttl))
+    }
+
+    /// Claim the background refresh of `dex`
+    ///
+    /// Fails if one is already running or the last one failed recently.
+    pub(crate) fn begin_refresh(&self, dex: &str) -> bool {
+        let mut state = self.lock();
+        if state
+            .failed
+            .get(dex)
+            .is_some_and(|at| at.elapsed() < RETRY_DELAY)
+        {
+            return false;
+        }
+        state.refreshing.insert(dex.to_string())
+    }
+
+    /// Record the outcome of a fetch of `dex`
+    pub(crate) fn store(
+        &self,
+        dex: &str,
+        result: Result<Meta, HyperliquidError>,
+    ) -> Result<Arc<Meta>, HyperliquidError> {
+        let mut state = self.lock();
+        state.refreshing.remove(dex);
+        match result {
+            Ok(meta) => {
+                let meta = Arc::new(meta);
+                state.failed.remove(dex);
+                state
+                    .entries
+                    .insert(dex.to_string(), (meta.clone(), Instant::now()));
+                Ok(meta)
+            }
+            Err(e) => {
+                state.failed.insert(dex.to_string(), Instant::now());
+                Err(e)
+            }
+        }
+    }
+
+    /// Release a claimed refresh that couldn't be started
+    pub(crate) fn abandon_refresh(&self, dex: &str) {
+        self.lock().refreshing.remove(dex);
+    }
+
+    pub(crate) fn invalidate(&self, dex: &str) {
+        self.lock().entries.remove(dex);
+    }
+}
@@ -4,5 +4,11 @@
 //! informational endpoints of the Hyperliquid API.
 
 pub mod client;
+mod meta_cache;
+pub mod paginate;
 
-pub use client::InfoClient;
\ No newline at end of file
+pub use client::{InfoClient, USER_FILLS_PAGE_LIMIT};
+pub use paginate::{
+    PageCursor, TimePaginator, CANDLE_PAGE_LIMIT, FUNDING_HISTORY_PAGE_LIMIT,
+    USER_FUNDING_PAGE_LIMIT,
+};
\ No newline at end of file
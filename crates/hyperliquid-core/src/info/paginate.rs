@@ -0,0 +1,357 @@
+//! Paging through time-windowed Info endpoints
+//!
+//! `userFillsByTime`, `userFunding`, `fundingHistory` and `candleSnapshot`
+//! return at most a fixed number of items per request, oldest first.
+//! [`TimePaginator`] requests successive windows, each starting at the last
+//! item's time, until a page comes back short, and yields the items as a
+//! `Stream`:
+//!
+//! - pages overlap on the boundary millisecond; items already yielded there
+//!   are dropped;
+//! - with a [`RateLimiter`] every request first takes its
+//!   [`info_weight`], plus the exchange's extra weight per 20 items returned,
+//!   and a 429 backs off and retries instead of ending the stream;
+//! - with a [`StateStore`] the cursor is saved under `paginate/<name>` once a
+//!   page has been consumed, so a restarted sync resumes where it stopped.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> hyperliquid_core::Result<()> {
+//! use futures::TryStreamExt;
+//! use hyperliquid_core::info::TimePaginator;
+//! use hyperliquid_core::types::UserFill;
+//!
+//! let user = "0x0000000000000000000000000000000000000000";
+//! let fills: Vec<UserFill> = TimePaginator::user_fills(client, user, 0..u64::MAX)
+//!     .stream()
+//!     .try_collect()
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::ops::Range;
+use std::sync::Arc;
+
+use futures::stream::{self, Stream};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::USER_FILLS_PAGE_LIMIT;
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::scheduler::{info_weight, rate_limit_retry_after, RateLimiter};
+use crate::store::{self, StateStore};
+
+/// Most entries returned by one `userFunding` request
+pub const USER_FUNDING_PAGE_LIMIT: usize = 500;
+/// Most entries returned by one `fundingHistory` request
+pub const FUNDING_HISTORY_PAGE_LIMIT: usize = 500;
+/// Most candles returned by one `candleSnapshot` request
+pub const CANDLE_PAGE_LIMIT: usize = 5000;
+
+/// Items per unit of extra weight charged on large responses
+const ITEMS_PER_WEIGHT: usize = 20;
+
+/// Where a paginated sync continues from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageCursor {
+    /// Start time (ms) of the next request
+    pub start_time: u64,
+    /// Items already yielded at `start_time`, as JSON
+    #[serde(default)]
+    pub boundary: Vec<String>,
+}
+
+/// Pages through a time-windowed `/info` request
+#[derive(Clone)]
+pub struct TimePaginator {
+    client: HttpClient,
+    request: Value,
+    /// Pointer to the object holding `startTime`/`endTime`
+    window: &'static str,
+    time_field: String,
+    range: Range<u64>,
+    page_limit: usize,
+    limiter: Option<RateLimiter>,
+    store: Option<(Arc<dyn StateStore>, String)>,
+}
+
+impl std::fmt::Debug for TimePaginator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimePaginator")
+            .field("request", &self.request)
+            .field("time_field", &self.time_field)
+            .field("range", &self.range)
+            .field("page_limit", &self.page_limit)
+            .field("store", &self.store.as_ref().map(|(_, key)| key))
+            .finish()
+    }
+}
+
+impl TimePaginator {
+    /// Page `request` over `range` (ms), reading each item's time from
+    /// `time_field`
+    ///
+    /// `startTime` and `endTime` are set on `request` for every page; a page
+    /// of fewer than `page_limit` items ends the stream.
+    pub fn new(
+        client: HttpClient,
+        request: Value,
+        time_field: impl Into<String>,
+        range: Range<u64>,
+        page_limit: usize,
+    ) -> Self {
+        Self {
+            client,
+            request,
+            window: "",
+            time_field: time_field.into(),
+            range,
+            page_limit: page_limit.max(1),
+            limiter: None,
+            store: None,
+        }
+    }
+
+    /// `user`'s fills from `userFillsByTime`
+    pub fn user_fills(client: HttpClient, user: &str, range: Range<u64>) -> Self {
+        Self::new(
+            client,
+            json!({"type": "userFillsByTime", "user": user}),
+            "time",
+            range,
+            USER_FILLS_PAGE_LIMIT,
+        )
+    }
+
+    /// `user`'s funding payments from `userFunding`
+    pub fn user_funding(client: HttpClient, user: &str, range: Range<u64>) -> Self {
+        Self::new(
+            client,
+            json!({"type": "userFunding", "user": user}),
+            "time",
+            range,
+            USER_FUNDING_PAGE_LIMIT,
+        )
+    }
+
+    /// `coin`'s settled funding rates from `fundingHistory`
+    pub fn funding_history(client: HttpClient, coin: &str, range: Range<u64>) -> Self {
+        Self::new(
+            client,
+            json!({"type": "fundingHistory", "coin": coin}),
+            "time",
+            range,
+            FUNDING_HISTORY_PAGE_LIMIT,
+        )
+    }
+
+    /// `coin`'s candles of `interval` from `candleSnapshot`, by open time
+    pub fn candles(client: HttpClient, coin: &str, interval: &str, range: Range<u64>) -> Self {
+        let mut paginator = Self::new(
+            client,
+            json!({"type": "candleSnapshot", "req": {"coin": coin, "interval": interval}}),
+            "t",
+            range,
+            CANDLE_PAGE_LIMIT,
+        );
+        paginator.window = "/req";
+        paginator
+    }
+
+    /// Take each request's weight from `limiter` and back off on 429s
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Save the cursor in `store` under `paginate/<name>` and resume from it
+    pub fn with_store(mut self, store: Arc<dyn StateStore>, name: &str) -> Self {
+        self.store = Some((store, format!("paginate/{}", name)));
+        self
+    }
+
+    /// Cursor the stream starts from: the saved one, if later than the range
+    /// start
+    pub fn cursor(&self) -> Result<PageCursor, HyperliquidError> {
+        let saved = match &self.store {
+            Some((store, key)) => store::get_json::<PageCursor>(store.as_ref(), key)?,
+            None => None,
+        };
+        Ok(saved
+            .filter(|cursor| cursor.start_time >= self.range.start)
+            .unwrap_or(PageCursor {
+                start_time: self.range.start,
+                boundary: Vec::new(),
+            }))
+    }
+
+    /// Items in the range, oldest first
+    ///
+    /// The stream ends after the first error.
+    pub fn stream<T>(self) -> impl Stream<Item = Result<T, HyperliquidError>> + Send + 'static
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (cursor, error) = match self.cursor() {
+            Ok(cursor) => (cursor, None),
+            Err(e) => (PageCursor::default(), Some(e)),
+        };
+        let pager = Pager {
+            paginator: self,
+            cursor,
+            error,
+            items: VecDeque::new(),
+            unsaved: None,
+            done: false,
+        };
+        stream::unfold(pager, |mut pager| async move {
+            let item = pager.next().await?;
+            Some((
+                item.and_then(|item| Ok(serde_json::from_value(item)?)),
+                pager,
+            ))
+        })
+    }
+}
+
+struct Pager {
+    paginator: TimePaginator,
+    /// Cursor of the next request
+    cursor: PageCursor,
+    /// Error loading the saved cursor, reported first
+    error: Option<HyperliquidError>,
+    items: VecDeque<Value>,
+    /// Cursor to save once `items` is consumed
+    unsaved: Option<PageCursor>,
+    done: bool,
+}
+
+impl Pager {
+    async fn next(&mut self) -> Option<Result<Value, HyperliquidError>> {
+        if let Some(e) = self.error.take() {
+            return self.fail(e);
+        }
+        loop {
+            if let Some(item) = self.items.pop_front() {
+                return Some(Ok(item));
+            }
+            if let Some(cursor) = self.unsaved.take() {
+                if let Err(e) = self.save(&cursor) {
+                    return self.fail(e);
+                }
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(e) = self.fetch().await {
+                return self.fail(e);
+            }
+        }
+    }
+
+    fn fail(&mut self, error: HyperliquidError) -> Option<Result<Value, HyperliquidError>> {
+        self.done = true;
+        self.items.clear();
+        Some(Err(error))
+    }
+
+    fn save(&self, cursor: &PageCursor) -> Result<(), HyperliquidError> {
+        match &self.paginator.store {
+            Some((store, key)) => store::put_json(store.as_ref(), key, cursor),
+            None => Ok(()),
+        }
+    }
+
+    async fn fetch(&mut self) -> Result<(), HyperliquidError> {
+        let start = self.cursor.start_time;
+        if start >= self.paginator.range.end {
+            self.done = true;
+            return Ok(());
+        }
+        let page = self.request(start).await?;
+
+        let paginator = &self.paginator;
+        let time = |item: &Value| item.get(&paginator.time_field).and_then(Value::as_u64);
+        let full = page.len() >= paginator.page_limit;
+        let last = page.iter().filter_map(time).max();
+        let seen: HashSet<&str> = self.cursor.boundary.iter().map(String::as_str).collect();
+        let mut boundary = Vec::new();
+        let mut items = VecDeque::new();
+        for item in page {
+            let Some(item_time) = time(&item) else {
+                continue;
+            };
+            if item_time == start && seen.contains(item.to_string().as_str()) {
+                continue;
+            }
+            if Some(item_time) == last {
+                boundary.push(item.to_string());
+            }
+            if paginator.range.contains(&item_time) {
+                items.push_back(item);
+            }
+        }
+
+        match last {
+            Some(last) if last >= start => {
+                if last == start {
+                    boundary.extend(self.cursor.boundary.drain(..));
+                }
+                self.cursor = PageCursor {
+                    start_time: last,
+                    boundary,
+                };
+            }
+            _ => {}
+        }
+        // A full page that ends where it started can't be paged past
+        self.done = !matches!(last, Some(last) if full && last > start);
+        self.items = items;
+        self.unsaved = Some(self.cursor.clone());
+        Ok(())
+    }
+
+    async fn request(&self, start: u64) -> Result<Vec<Value>, HyperliquidError> {
+        let paginator = &self.paginator;
+        let mut body = paginator.request.clone();
+        if let Some(window) = body
+            .pointer_mut(paginator.window)
+            .and_then(Value::as_object_mut)
+        {
+            window.insert("startTime".to_string(), start.into());
+            if paginator.range.end < u64::MAX {
+                let end = paginator.range.end.min(i64::MAX as u64);
+                window.insert("endTime".to_string(), end.into());
+            }
+        }
+        let request_type = body.get("type").and_then(Value::as_str).unwrap_or_default();
+        let weight = info_weight(request_type);
+
+        loop {
+            if let Some(limiter) = &paginator.limiter {
+                limiter.acquire(weight).await;
+            }
+            match paginator.client.post::<_, Vec<Value>>("/info", &body).await {
+                Ok(page) => {
+                    if let Some(limiter) = &paginator.limiter {
+                        limiter.record_success();
+                        let extra = (page.len() / ITEMS_PER_WEIGHT) as u32;
+                        if extra > 0 {
+                            limiter.acquire(extra).await;
+                        }
+                    }
+                    return Ok(page);
+                }
+                Err(e) => match (&paginator.limiter, rate_limit_retry_after(&e)) {
+                    (Some(limiter), Some(retry_after)) => {
+                        limiter.back_off(retry_after);
+                    }
+                    _ => return Err(e),
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Append-only journal of order events for crash recovery
+//!
+//! An [`OrderManager`](crate::oms::OrderManager) with a journal attached
+//! writes every submission before it is sent, and every placement status,
+//! `orderUpdates` payload and `userFills` payload as it is applied, one JSON
+//! line each. Replaying the journal on restart rebuilds the book exactly as
+//! it was, including orders that were submitted but never answered; see
+//! [`OrderManager::with_journal`](crate::oms::OrderManager::with_journal) and
+//! [`OrderManager::recover_in_flight`](crate::oms::OrderManager::recover_in_flight).
+//!
+//! A process killed mid-write leaves a partial last line. It is dropped when
+//! the journal is read or reopened; every earlier line is intact.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+use crate::oms::OrderKey;
+
+/// Order event as journaled
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEntry {
+    /// Order about to be sent
+    Submitted {
+        key: OrderKey,
+        coin: String,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        cloid: Option<String>,
+    },
+    /// Entry of `response.data.statuses` for an order
+    Response { key: OrderKey, status: Value },
+    /// `data` payload of the `orderUpdates` channel
+    OrderUpdates { data: Value },
+    /// `data` payload of the `userFills` channel
+    UserFills { data: Value },
+}
+
+/// One line of the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalRecord {
+    /// Position in the journal, starting at 0
+    pub seq: u64,
+    /// Record time in milliseconds since the epoch
+    pub time: i64,
+    #[serde(flatten)]
+    pub entry: JournalEntry,
+}
+
+struct JournalState {
+    file: File,
+    next_seq: u64,
+}
+
+/// JSONL order event journal
+pub struct OrderJournal {
+    path: PathBuf,
+    sync: bool,
+    state: Mutex<JournalState>,
+}
+
+impl OrderJournal {
+    /// Open (or create) a journal, appending after its existing records
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HyperliquidError> {
+        let path = path.as_ref().to_path_buf();
+        let next_seq = if path.exists() {
+            truncate_partial_line(&path)?;
+            read_journal(&path)?.last().map_or(0, |last| last.seq + 1)
+        } else {
+            0
+        };
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| journal_error(&path, e))?;
+
+        Ok(Self {
+            path,
+            sync: false,
+            state: Mutex::new(JournalState { file, next_seq }),
+        })
+    }
+
+    /// fsync after every record
+    ///
+    /// Without it records survive a crash of the process but not of the
+    /// machine.
+    pub fn with_sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Append an entry, returning its sequence number
+    pub fn append(&self, entry: JournalEntry) -> Result<u64, HyperliquidError> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let record = JournalRecord {
+            seq: state.next_seq,
+            time: chrono::Utc::now().timestamp_millis(),
+            entry,
+        };
+
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+        state
+            .file
+            .write_all(&line)
+            .map_err(|e| journal_error(&self.path, e))?;
+        if self.sync {
+            state
+                .file
+                .sync_data()
+                .map_err(|e| journal_error(&self.path, e))?;
+        }
+
+        state.next_seq += 1;
+        Ok(record.seq)
+    }
+
+    /// Every record in the journal
+    pub fn records(&self) -> Result<Vec<JournalRecord>, HyperliquidError> {
+        read_journal(&self.path)
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of records in the journal
+    pub fn len(&self) -> u64 {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .next_seq
+    }
+
+    /// Check if the journal has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl std::fmt::Debug for OrderJournal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrderJournal")
+            .field("path", &self.path)
+            .field("records", &self.len())
+            .finish()
+    }
+}
+
+/// Read every record of a journal, skipping a partial last line
+pub fn read_journal(path: impl AsRef<Path>) -> Result<Vec<JournalRecord>, HyperliquidError> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|e| journal_error(path, e))?;
+    let lines = BufReader::new(file)
+        .lines()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| journal_error(path, e))?;
+
+    let mut records = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<JournalRecord>(line) {
+            Ok(record) => records.push(record),
+            Err(e) if index + 1 == lines.len() => {
+                warn!(
+                    "Skipping partial last line of journal {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+            Err(e) => {
+                return Err(HyperliquidError::Validation(format!(
+                    "journal line {} is malformed: {}",
+                    index + 1,
+                    e
+                )))
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Cut a partial last line so new records start on a line of their own
+fn truncate_partial_line(path: &Path) -> Result<(), HyperliquidError> {
+    let contents = std::fs::read(path).map_err(|e| journal_error(path, e))?;
+    let complete = contents
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |pos| pos + 1);
+    if complete < contents.len() {
+        warn!(
+            "Dropping {} bytes of partial record from journal {}",
+            contents.len() - complete,
+            path.display()
+        );
+        OpenOptions::new()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_len(complete as u64))
+            .map_err(|e| journal_error(path, e))?;
+    }
+    Ok(())
+}
+
+fn journal_error(path: &Path, e: std::io::Error) -> HyperliquidError {
+    HyperliquidError::Config(format!("journal {}: {}", path.display(), e))
+}
@@ -0,0 +1,282 @@
+//! Runtime trading kill switches
+//!
+//! A [`KillSwitchRegistry`] holds switches per coin and one for everything.
+//! Components given the registry refuse new orders in a disabled scope with
+//! [`HyperliquidError::TradingDisabled`]:
+//!
+//! - [`ExchangeClient::with_kill_switches`](crate::exchange::ExchangeClient::with_kill_switches)
+//!   for orders placed by coin and for signed `order`, `modify` and
+//!   `batchModify` actions, whose asset indices are mapped to coins through
+//!   [`ExchangeClient::with_assets`](crate::exchange::ExchangeClient::with_assets);
+//! - [`Executor::with_kill_switches`](crate::execution::Executor::with_kill_switches)
+//!   for child orders, failing the execution.
+//!
+//! Reduce-only orders are let through so positions can still be closed.
+//! [`KillSwitchRegistry::disable_and_cancel`] also cancels the open orders in
+//! the scope through a [`KillSwitch`].
+//!
+//! With a [`StateStore`] the switches are written through under
+//! `killswitch/` and survive a restart. Processes sharing the store, such
+//! as a bot and the `hl trading` command over one SQLite database, pick up
+//! each other's changes on [`KillSwitchRegistry::reload`], which
+//! [`KillSwitchRegistry::start_reload`] runs on an interval.
+//!
+//! ```
+//! use hyperliquid_core::killswitch::{KillSwitchRegistry, Scope};
+//!
+//! let switches = KillSwitchRegistry::new();
+//! switches.disable(Scope::coin("BTC"), "exchange incident").unwrap();
+//! assert!(switches.check("BTC").is_err());
+//! assert!(switches.check("ETH").is_ok());
+//! switches.enable(&Scope::coin("BTC")).unwrap();
+//! assert!(switches.is_enabled("BTC"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::oms::OrderManager;
+use crate::store::{self, StateStore};
+use crate::watchdog::KillSwitch;
+
+const STORE_PREFIX: &str = "killswitch/";
+
+/// What a switch covers
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    /// Every coin
+    All,
+    /// A single coin
+    Coin(String),
+}
+
+impl Scope {
+    /// Scope covering only `coin`
+    pub fn coin(coin: impl Into<String>) -> Self {
+        Scope::Coin(coin.into())
+    }
+
+    /// Whether the scope covers `coin`
+    pub fn covers(&self, coin: &str) -> bool {
+        match self {
+            Scope::All => true,
+            Scope::Coin(scope) => scope == coin,
+        }
+    }
+
+    fn store_key(&self) -> String {
+        match self {
+            Scope::All => format!("{}all", STORE_PREFIX),
+            Scope::Coin(coin) => format!("{}coin/{}", STORE_PREFIX, coin),
+        }
+    }
+}
+
+impl std::fmt::Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::All => f.write_str("all coins"),
+            Scope::Coin(coin) => f.write_str(coin),
+        }
+    }
+}
+
+/// A disabled scope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Halt {
+    pub scope: Scope,
+    pub reason: String,
+    /// When trading was disabled (ms)
+    pub since: u64,
+}
+
+impl Halt {
+    pub(crate) fn error(&self) -> HyperliquidError {
+        HyperliquidError::TradingDisabled {
+            scope: self.scope.to_string(),
+            reason: self.reason.clone(),
+        }
+    }
+}
+
+/// Per-coin and global trading switches
+///
+/// Cheap to clone; clones share state.
+#[derive(Clone)]
+pub struct KillSwitchRegistry {
+    halts: Arc<Mutex<BTreeMap<Scope, Halt>>>,
+    store: Option<Arc<dyn StateStore>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for KillSwitchRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for KillSwitchRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KillSwitchRegistry")
+            .field("halts", &self.halts())
+            .field("persistent", &self.store.is_some())
+            .finish()
+    }
+}
+
+impl KillSwitchRegistry {
+    /// Registry with every scope enabled
+    pub fn new() -> Self {
+        Self {
+            halts: Arc::new(Mutex::new(BTreeMap::new())),
+            store: None,
+            clock: clock::system(),
+        }
+    }
+
+    /// Write switches through to `store`, loading the ones saved there
+    pub fn with_store(mut self, store: Arc<dyn StateStore>) -> Result<Self, HyperliquidError> {
+        self.store = Some(store);
+        self.reload()?;
+        Ok(self)
+    }
+
+    /// Stamp halts with `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<Scope, Halt>> {
+        self.halts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replace the switches with the ones in the store
+    pub fn reload(&self) -> Result<(), HyperliquidError> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let halts = store::scan_json::<Halt>(store.as_ref(), STORE_PREFIX)?
+            .into_iter()
+            .map(|(_, halt)| (halt.scope.clone(), halt))
+            .collect();
+        *self.lock() = halts;
+        Ok(())
+    }
+
+    /// Reload from the store every `period` in the background until the
+    /// handle is aborted
+    pub fn start_reload(&self, period: Duration) -> JoinHandle<()> {
+        let switches = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = switches.reload() {
+                    warn!("Failed to reload kill switches: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Refuse new orders in `scope`
+    ///
+    /// Disabling a scope that is already disabled keeps the original time
+    /// and replaces the reason.
+    pub fn disable(&self, scope: Scope, reason: impl Into<String>) -> Result<(), HyperliquidError> {
+        let mut halts = self.lock();
+        let since = halts
+            .get(&scope)
+            .map(|halt| halt.since)
+            .unwrap_or_else(|| self.clock.now_ms());
+        let halt = Halt {
+            scope: scope.clone(),
+            reason: reason.into(),
+            since,
+        };
+        if let Some(store) = &self.store {
+            store::put_json(store.as_ref(), &scope.store_key(), &halt)?;
+        }
+        warn!("Trading disabled for {}: {}", scope, halt.reason);
+        crate::telemetry::gauge!("hyperliquid_trading_disabled", "scope" => scope.to_string())
+            .set(1.0);
+        halts.insert(scope, halt);
+        Ok(())
+    }
+
+    /// Accept orders in `scope` again
+    ///
+    /// A coin stays disabled while the global switch is.
+    pub fn enable(&self, scope: &Scope) -> Result<(), HyperliquidError> {
+        let mut halts = self.lock();
+        if let Some(store) = &self.store {
+            store.delete(&scope.store_key())?;
+        }
+        if halts.remove(scope).is_some() {
+            warn!("Trading enabled for {}", scope);
+            crate::telemetry::gauge!("hyperliquid_trading_disabled", "scope" => scope.to_string())
+                .set(0.0);
+        }
+        Ok(())
+    }
+
+    /// Switch that stops `coin` from trading, the global one first
+    pub fn halt(&self, coin: &str) -> Option<Halt> {
+        let halts = self.lock();
+        halts
+            .get(&Scope::All)
+            .or_else(|| halts.get(&Scope::Coin(coin.to_string())))
+            .cloned()
+    }
+
+    /// Whether `coin` may trade, with no global or coin switch engaged
+    pub fn is_enabled(&self, coin: &str) -> bool {
+        self.halt(coin).is_none()
+    }
+
+    /// Fail with [`HyperliquidError::TradingDisabled`] if `coin` is disabled
+    pub fn check(&self, coin: &str) -> Result<(), HyperliquidError> {
+        match self.halt(coin) {
+            Some(halt) => Err(halt.error()),
+            None => Ok(()),
+        }
+    }
+
+    /// Every disabled scope, the global one first
+    pub fn halts(&self) -> Vec<Halt> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// Disable `scope` and cancel the open orders of `orders` in it
+    ///
+    /// Returns the number of orders cancelled. The scope stays disabled if
+    /// the cancel fails.
+    pub async fn disable_and_cancel<K: KillSwitch>(
+        &self,
+        scope: Scope,
+        reason: impl Into<String>,
+        switch: &K,
+        orders: &OrderManager,
+    ) -> Result<usize, HyperliquidError> {
+        self.disable(scope.clone(), reason)?;
+        let open: Vec<_> = orders
+            .open_orders()
+            .into_iter()
+            .filter(|order| scope.covers(&order.coin))
+            .collect();
+        if !open.is_empty() {
+            switch.cancel_orders(&open).await?;
+        }
+        Ok(open.len())
+    }
+}
@@ -2,36 +2,96 @@
 //!
 //! This crate provides the core functionality for interacting with the Hyperliquid
 //! exchange API with a focus on performance, reliability, and security.
+//!
+//! Heavy dependencies sit behind cargo features; `ws`, `exchange-signing`
+//! and `metrics` are on by default:
+//!
+//! - `ws`: WebSocket client and the `attach` helpers that subscribe through it
+//! - `exchange-signing`: wallets, action signing and the Exchange client
+//! - `metrics`: counters and histograms through the `metrics` facade
+//! - `recorder`: market data recorder
+//! - `python-compat`: everything the Python bindings wrap
+//!
+//! With `default-features = false` the crate is the Info REST client, types
+//! and the streaming state machines, fed by hand.
 
 pub mod client;
 pub mod types;
+#[cfg(feature = "exchange-signing")]
 pub mod crypto;
 pub mod info;
+#[cfg(feature = "exchange-signing")]
 pub mod exchange;
 pub mod stream;
 pub mod error;
 pub mod runtime;
 pub mod logging;
 pub mod config;
+pub mod clock;
 pub mod memory;
+mod telemetry;
+pub mod oms;
+pub mod journal;
+pub mod positions;
+pub mod execution;
+pub mod fees;
+pub mod quoter;
+pub mod analytics;
+pub mod margin;
+pub mod reconcile;
+pub mod state_diff;
+pub mod equity;
+pub mod vaults;
+pub mod rebalance;
+pub mod alerts;
+pub mod watchdog;
+pub mod killswitch;
+pub mod ack;
+pub mod expiry;
+pub mod scheduler;
+pub mod health;
+pub mod symbols;
+#[cfg(all(feature = "ws", feature = "exchange-signing"))]
+pub mod accounts;
+#[cfg(all(feature = "ws", feature = "exchange-signing"))]
+pub mod runner;
+pub mod store;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "data")]
+pub mod data;
+#[cfg(feature = "evm")]
+pub mod evm;
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+#[cfg(feature = "sinks")]
+pub mod sinks;
+#[cfg(feature = "axum")]
+pub mod service;
 
 pub use client::{HttpClient, HttpClientConfig, RetryPolicy, StatsSummary};
 pub use info::InfoClient;
+#[cfg(feature = "exchange-signing")]
 pub use exchange::ExchangeClient;
+#[cfg(feature = "exchange-signing")]
 pub use exchange::ExchangeClientConfig;
-pub use types::{Address, Environment, MarketType, Subscription, BaseResponse, ErrorResponse, ApiResponse, Meta, AssetMeta, ExchangeMeta, VaultMeta, UserState, MarginSummary, CrossMarginSummary, Position, PositionDetails, AssetPosition, BuilderInfo, L2BookSnapshot, OrderLevel, Trade, Bbo, BboLevel, Candle, MidPrice, UserEvent, Cleared, ClosedPnl, Deposit, FundingPayment, Liquidation, NewOrder, OrderStatus, PositionUpdate, PnlAnnihilation, Trigger, FilledOrder, Funding, LedgerUpdate, UserLedgerUpdate, ExchangeFill, Fill, OpenOrder, OrderAction, Cancel, BatchCancel, CancelByCloid, BatchCancelByCloid, Modify, BatchModify, Order, Limit, TriggerType, TpSl, TriggerPx, TriggerPxType, Cloid, WsMsg, AllMidsMsg, L2BookMsg, TradesMsg, BboMsg, CandleMsg, PongMsg, UserEventsMsg, UserFillsMsg, OrderUpdatesMsg, UserFundingsMsg, UserNonFundingLedgerUpdatesMsg, WebData2Msg, ActiveAssetCtxMsg, ActiveSpotAssetCtxMsg, ActiveAssetDataMsg, OtherWsMsg, OtherMsg, PerpDexSchemaInput, FundingHistoryRequest, FundingHistoryResponse, UserFeesResponse, parse_response, parse_success_response, parse_error_response, wrap_success, wrap_error, is_error_response, extract_status, extract_nested_data};
-pub use memory::{ArenaAllocator, StringInterner, ZeroCopyValue, ObjectPool, MemoryProfiler, AllocationStats, StringInternStats, PoolStats};
-pub use error::HyperliquidError;
+pub use types::{Address, Environment, MarketType, Subscription, BaseResponse, ErrorResponse, ApiResponse, Meta, AssetMeta, ExchangeMeta, VaultMeta, UserState, MarginSummary, CrossMarginSummary, Position, PositionDetails, AssetPosition, BuilderInfo, L2BookSnapshot, OrderLevel, Trade, Bbo, BboLevel, Candle, MidPrice, UserEvent, Cleared, ClosedPnl, Deposit, FundingPayment, Liquidation, NewOrder, FrontendOrder, FrontendOrderType, WebData2, OrderStatus, PositionUpdate, PnlAnnihilation, Trigger, FilledOrder, Funding, LedgerUpdate, UserLedgerUpdate, ExchangeFill, Fill, OpenOrder, OrderAction, Cancel, BatchCancel, CancelByCloid, BatchCancelByCloid, Modify, BatchModify, Order, Limit, TriggerType, TpSl, TriggerPx, TriggerPxType, Cloid, WsMsg, AllMidsMsg, L2BookMsg, TradesMsg, BboMsg, CandleMsg, PongMsg, UserEventsMsg, UserFillsMsg, OrderUpdatesMsg, UserFundingsMsg, UserNonFundingLedgerUpdatesMsg, WebData2Msg, ActiveAssetCtxMsg, ActiveSpotAssetCtxMsg, ActiveAssetDataMsg, OtherWsMsg, OtherMsg, PerpDexSchemaInput, FundingHistoryRequest, FundingHistoryResponse, UserFeesResponse, parse_response, parse_success_response, parse_error_response, wrap_success, wrap_error, is_error_response, extract_status, extract_nested_data};
+pub use memory::{ArenaAllocator, StringInterner, ZeroCopyValue, ObjectPool, MemoryProfiler, MemoryLeakAlert, MemorySample, AllocationStats, StringInternStats, PoolStats};
+pub use error::{HyperliquidError, ResultExt};
 pub use runtime::{
-    RuntimeConfig, ConfiguredRuntime,
+    RuntimeConfig, ConfiguredRuntime, RuntimeMetricsSnapshot, ThreadPriority, ThreadRole,
+    apply_thread_placement, spawn_role_thread,
     create_default_runtime, create_high_throughput_runtime,
     create_low_latency_runtime, create_single_threaded_runtime,
 };
 pub use logging::{
-    LoggingConfig, init_tracing, generate_trace_id, request_span,
-    log_request, log_response, log_error, log_retry,
+    LoggingConfig, RedactionConfig, RedactingMakeWriter, RequestTrace, TRACE_ID_HEADER, init_tracing, shutdown_tracing,
+    generate_trace_id, request_span, log_request, log_response, log_error, log_retry,
 };
-pub use config::{Config, EnvironmentConfig, HttpClientConfig as ConfiguredHttpClientConfig, WebSocketConfig, RuntimeConfig as ConfiguredRuntimeConfig, LoggingConfig as ConfigLoggingConfig, SecurityConfig, MetricsConfig};
+pub use config::{Config, EnvironmentConfig, HttpClientConfig as ConfiguredHttpClientConfig, WebSocketConfig, RuntimeConfig as ConfiguredRuntimeConfig, LoggingConfig as ConfigLoggingConfig, SecurityConfig, MetricsConfig, StrategyConfig};
+#[cfg(feature = "prometheus")]
+pub use prometheus::{start_prometheus_exporter, PrometheusExporter, PrometheusServer};
+#[cfg(feature = "exchange-signing")]
 pub use crypto::{MultiSigEnvelope, MultiSigUser, MultiSigSignature, sign_multi_sig_envelope, create_multi_sig_envelope, verify_multi_sig_envelope};
 
 /// Result type alias using HyperliquidError
@@ -2,14 +2,25 @@
 //!
 //! This module provides initialization of tracing subscribers with structured logging,
 //! request/response logging, trace ID generation, and configurable log formats.
+//!
+//! With the `otel` feature and an `otlp_endpoint` configured, spans are also
+//! exported over OTLP and trace ids come from the OpenTelemetry context, so
+//! they match the `traceparent` header sent with each request.
+//!
+//! All formatted output passes through [`RedactionConfig`], which masks private
+//! keys, signatures and addresses before anything reaches stdout or log files.
 
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::io;
+use serde::{Deserialize, Serialize};
+use tracing_subscriber::fmt::MakeWriter;
 use tracing_subscriber::{
     fmt,
     fmt::{time::UtcTime, Layer},
     layer::SubscriberExt,
     util::SubscriberInitExt,
-    EnvFilter,
+    EnvFilter, Layer as _,
 };
 use tracing::{Level, Span};
 use tracing_appender::{rolling, non_blocking::NonBlocking};
@@ -36,6 +47,14 @@ pub struct LoggingConfig {
     pub max_file_size_mb: u64,
     /// Maximum number of log files to keep
     pub max_files: u32,
+    /// OTLP collector endpoint for span export (requires the `otel` feature)
+    pub otlp_endpoint: Option<String>,
+    /// Service name reported with exported spans
+    pub service_name: String,
+    /// Fraction of new traces to sample (0.0 - 1.0)
+    pub trace_sample_ratio: f64,
+    /// Masking of secrets and identifiers in log output
+    pub redaction: RedactionConfig,
 }
 
 impl Default for LoggingConfig {
@@ -50,6 +69,10 @@ impl Default for LoggingConfig {
             log_file: "hyperliquid.log".to_string(),
             max_file_size_mb: 100,
             max_files: 5,
+            otlp_endpoint: None,
+            service_name: "hyperliquid-rs".to_string(),
+            trace_sample_ratio: 1.0,
+            redaction: RedactionConfig::default(),
         }
     }
 }
@@ -78,8 +101,175 @@ impl LoggingConfig {
             ..Default::default()
         }
     }
+
+    /// Export spans to an OTLP collector
+    pub fn with_otlp_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.otlp_endpoint = Some(endpoint.into());
+        self
+    }
+}
+
+/// Masking applied to all formatted log output
+///
+/// Hex tokens are classified by length: 64 hex digits are treated as private
+/// keys, 130 as signatures and `0x`-prefixed 40 as addresses. 32-byte hashes
+/// share the private key length and are masked too; that is the safe side to
+/// err on.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    /// Enable redaction
+    pub enabled: bool,
+    /// Mask 32-byte hex values (private keys)
+    pub private_keys: bool,
+    /// Mask 65-byte hex values (signatures)
+    pub signatures: bool,
+    /// Shorten addresses to a prefix and suffix
+    pub addresses: bool,
+    /// Characters of an address to keep, including `0x`
+    pub address_prefix: usize,
+    /// Trailing characters of an address to keep
+    pub address_suffix: usize,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            private_keys: true,
+            signatures: true,
+            addresses: true,
+            address_prefix: 6,
+            address_suffix: 4,
+        }
+    }
+}
+
+impl RedactionConfig {
+    /// Disable all redaction
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Default::default()
+        }
+    }
+
+    /// Redact secrets and identifiers in `input`
+    pub fn redact<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(input);
+        }
+
+        let bytes = input.as_bytes();
+        let mut output: Option<String> = None;
+        let mut copied = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            // Tokens must start at a word boundary
+            if i > 0 && is_word_byte(bytes[i - 1]) {
+                i += 1;
+                continue;
+            }
+
+            let has_prefix = bytes[i] == b'0' && matches!(bytes.get(i + 1), Some(b'x' | b'X'));
+            let start = if has_prefix { i + 2 } else { i };
+            let end = start + bytes[start..].iter().take_while(|b| b.is_ascii_hexdigit()).count();
+            if end == start || bytes.get(end).map_or(false, |b| is_word_byte(*b)) {
+                i = end.max(i + 1);
+                continue;
+            }
+
+            let replacement = match end - start {
+                130 if self.signatures => Some(Cow::Borrowed("[REDACTED_SIGNATURE]")),
+                64 if self.private_keys => Some(Cow::Borrowed("[REDACTED_KEY]")),
+                40 if has_prefix && self.addresses => Some(Cow::Owned(self.mask_address(&input[i..end]))),
+                _ => None,
+            };
+
+            if let Some(replacement) = replacement {
+                let out = output.get_or_insert_with(|| String::with_capacity(input.len()));
+                out.push_str(&input[copied..i]);
+                out.push_str(&replacement);
+                copied = end;
+            }
+            i = end;
+        }
+
+        match output {
+            Some(mut out) => {
+                out.push_str(&input[copied..]);
+                Cow::Owned(out)
+            }
+            None => Cow::Borrowed(input),
+        }
+    }
+
+    fn mask_address(&self, address: &str) -> String {
+        let prefix = self.address_prefix.min(address.len());
+        let suffix = self.address_suffix.min(address.len() - prefix);
+        format!("{}...{}", &address[..prefix], &address[address.len() - suffix..])
+    }
+}
+
+fn is_word_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// `MakeWriter` that redacts everything written through it
+#[derive(Clone, Debug)]
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+    redaction: RedactionConfig,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    /// Wrap a writer factory
+    pub fn new(inner: M, redaction: RedactionConfig) -> Self {
+        Self { inner, redaction }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<'a, M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+            redaction: &self.redaction,
+        }
+    }
 }
 
+/// Writer produced by [`RedactingMakeWriter`]
+///
+/// Formatters write each event as a single buffer, so tokens are never split
+/// across calls.
+pub struct RedactingWriter<'a, W> {
+    inner: W,
+    redaction: &'a RedactionConfig,
+}
+
+impl<W: io::Write> io::Write for RedactingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self.inner.write_all(self.redaction.redact(text).as_bytes())?,
+            Err(_) => self.inner.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Header carrying the trace id on outgoing requests
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+
+/// Boxed layer used to assemble the subscriber
+type BoxedLayer = Box<dyn tracing_subscriber::Layer<tracing_subscriber::Registry> + Send + Sync>;
+
 /// Initialize global tracing subscriber with structured logging
 pub fn init_tracing(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Error>> {
     // Parse environment filter from config level or RUST_LOG
@@ -87,28 +277,30 @@ pub fn init_tracing(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Er
         .or_else(|_| EnvFilter::try_new(&config.level))
         .unwrap_or_else(|_| EnvFilter::new("info"));
 
+    let mut layers: Vec<BoxedLayer> = Vec::new();
+
     // Create base layer for stdout
-    let stdout_layer = create_stdout_layer(config)?.with_filter(filter.clone());
+    layers.push(create_stdout_layer(config)?.with_filter(filter.clone()).boxed());
 
     // Create file layer if file logging is enabled
-    let file_layer = if config.file_logging {
-        Some(create_file_layer(config)?.with_filter(filter))
-    } else {
-        None
-    };
+    if config.file_logging {
+        layers.push(create_file_layer(config)?.with_filter(filter.clone()).boxed());
+    }
 
-    // Build subscriber
-    let subscriber = tracing_subscriber::registry()
-        .with(stdout_layer);
+    // Export spans over OTLP if an endpoint is configured
+    if let Some(endpoint) = &config.otlp_endpoint {
+        #[cfg(feature = "otel")]
+        layers.push(otel::create_layer(config, endpoint)?.with_filter(filter).boxed());
 
-    let subscriber = if let Some(file_layer) = file_layer {
-        subscriber.with(file_layer)
-    } else {
-        subscriber
-    };
+        #[cfg(not(feature = "otel"))]
+        eprintln!(
+            "otlp_endpoint {} ignored: hyperliquid-core was built without the `otel` feature",
+            endpoint
+        );
+    }
 
     // Initialize global subscriber
-    subscriber.init();
+    tracing_subscriber::registry().with(layers).init();
 
     tracing::info!(
         level = "startup",
@@ -128,10 +320,13 @@ fn create_stdout_layer(config: &LoggingConfig) -> Result<impl Layer<tracing_subs
         fmt::time::LocalTime::rfc_3339()
     };
 
+    let writer = RedactingMakeWriter::new(io::stdout, config.redaction.clone());
+
     let layer = match config.format.as_str() {
         "json" => {
             fmt::layer()
                 .json()
+                .with_writer(writer)
                 .with_timer(timer)
                 .with_thread_ids(true)
                 .with_thread_names(true)
@@ -144,6 +339,7 @@ fn create_stdout_layer(config: &LoggingConfig) -> Result<impl Layer<tracing_subs
         "pretty" => {
             fmt::layer()
                 .pretty()
+                .with_writer(writer)
                 .with_timer(timer)
                 .with_thread_ids(true)
                 .with_thread_names(true)
@@ -155,6 +351,7 @@ fn create_stdout_layer(config: &LoggingConfig) -> Result<impl Layer<tracing_subs
         _ => {
             fmt::layer()
                 .json()
+                .with_writer(writer)
                 .with_timer(timer)
                 .with_thread_ids(true)
                 .with_thread_names(true)
@@ -191,7 +388,7 @@ fn create_file_layer(config: &LoggingConfig) -> Result<impl Layer<tracing_subscr
 
     Ok(fmt::layer()
         .json()
-        .with_writer(non_blocking)
+        .with_writer(RedactingMakeWriter::new(non_blocking, config.redaction.clone()))
         .with_timer(timer)
         .with_thread_ids(true)
         .with_thread_names(true)
@@ -201,11 +398,175 @@ fn create_file_layer(config: &LoggingConfig) -> Result<impl Layer<tracing_subscr
         .with_span_events(fmt::format::FmtSpan::NEW | fmt::format::FmtSpan::CLOSE))
 }
 
+/// Flush pending spans and shut down the OTLP exporter
+///
+/// Call before process exit; spans are exported in batches and may otherwise be lost.
+pub fn shutdown_tracing() {
+    #[cfg(feature = "otel")]
+    opentelemetry::global::shutdown_tracer_provider();
+}
+
 /// Create a new trace ID for request tracking
 pub fn generate_trace_id() -> String {
     Uuid::new_v4().to_string()
 }
 
+/// Span and trace id for one logical request
+///
+/// The trace id is shared by every retry attempt and echoed to the server in
+/// [`TRACE_ID_HEADER`] (plus W3C `traceparent` when OpenTelemetry is active),
+/// so a request can be followed across retries and WebSocket post round-trips.
+#[derive(Clone, Debug)]
+pub struct RequestTrace {
+    span: Span,
+    trace_id: String,
+}
+
+impl RequestTrace {
+    /// Start tracing an HTTP request
+    pub fn http(method: &str, url: &str) -> Self {
+        Self::from_span(tracing::info_span!(
+            "http_request",
+            trace_id = tracing::field::Empty,
+            method = method,
+            url = url,
+            user_agent = "hyperliquid-rs/1.0.0"
+        ))
+    }
+
+    /// Start tracing a WebSocket post request
+    pub fn ws_post(post_id: u64, request_type: &str) -> Self {
+        Self::from_span(tracing::info_span!(
+            "ws_post",
+            trace_id = tracing::field::Empty,
+            post_id = post_id,
+            request_type = request_type
+        ))
+    }
+
+    /// Wrap a span declaring an empty `trace_id` field, recording the trace id on it
+    pub fn from_span(span: Span) -> Self {
+        let trace_id = otel_trace_id(&span).unwrap_or_else(generate_trace_id);
+        span.record("trace_id", trace_id.as_str());
+        Self { span, trace_id }
+    }
+
+    /// Get the request span
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Get the trace id
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// Create a child span for one attempt of the request
+    pub fn attempt_span(&self, attempt: u32) -> Span {
+        tracing::info_span!(
+            parent: &self.span,
+            "attempt",
+            trace_id = self.trace_id.as_str(),
+            attempt = attempt
+        )
+    }
+
+    /// Headers propagating this trace to the server
+    ///
+    /// `span` should be the current attempt span so the server-side parent is
+    /// the attempt rather than the whole request.
+    pub fn headers(&self, span: &Span) -> HashMap<String, String> {
+        let mut headers = HashMap::new();
+        headers.insert(TRACE_ID_HEADER.to_string(), self.trace_id.clone());
+
+        #[cfg(feature = "otel")]
+        otel::inject(span, &mut headers);
+        #[cfg(not(feature = "otel"))]
+        let _ = span;
+
+        headers
+    }
+}
+
+/// Trace id from the OpenTelemetry context, if spans are being exported
+fn otel_trace_id(span: &Span) -> Option<String> {
+    #[cfg(feature = "otel")]
+    {
+        otel::trace_id(span)
+    }
+    #[cfg(not(feature = "otel"))]
+    {
+        let _ = span;
+        None
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+    use std::collections::HashMap;
+
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use opentelemetry::{global, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::{self as sdktrace, Sampler};
+    use opentelemetry_sdk::{runtime, Resource};
+    use tracing::Span;
+    use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+    use tracing_subscriber::Registry;
+
+    use super::LoggingConfig;
+
+    /// Build the OTLP export layer and install the global propagator
+    pub(super) fn create_layer(
+        config: &LoggingConfig,
+        endpoint: &str,
+    ) -> Result<OpenTelemetryLayer<Registry, sdktrace::Tracer>, Box<dyn std::error::Error>> {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.trace_sample_ratio.clamp(0.0, 1.0),
+        )));
+
+        let provider = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(
+                sdktrace::Config::default()
+                    .with_sampler(sampler)
+                    .with_resource(Resource::new(vec![KeyValue::new(
+                        "service.name",
+                        config.service_name.clone(),
+                    )])),
+            )
+            .install_batch(runtime::Tokio)?;
+
+        let tracer = provider.tracer("hyperliquid-rs");
+        global::set_tracer_provider(provider);
+
+        Ok(tracing_opentelemetry::layer().with_tracer(tracer))
+    }
+
+    /// Hex trace id of the span's OpenTelemetry context
+    pub(super) fn trace_id(span: &Span) -> Option<String> {
+        let context = span.context();
+        let span_context = context.span().span_context().clone();
+        span_context
+            .is_valid()
+            .then(|| span_context.trace_id().to_string())
+    }
+
+    /// Inject W3C trace context headers for the span
+    pub(super) fn inject(span: &Span, headers: &mut HashMap<String, String>) {
+        let context = span.context();
+        global::get_text_map_propagator(|propagator| propagator.inject_context(&context, headers));
+    }
+}
+
 /// Create a span with trace ID for request tracking
 pub fn request_span(trace_id: &str, method: &str, url: &str) -> Span {
     tracing::info_span!(
@@ -307,6 +668,72 @@ mod tests {
         assert_eq!(config.file_logging, true);
     }
 
+    #[test]
+    fn test_redaction() {
+        let redaction = RedactionConfig::default();
+        let key = "a".repeat(64);
+        let signature = format!("0x{}", "b".repeat(130));
+        let address = "0x1234567890abcdef1234567890abcdef12345678";
+
+        let line = format!("key={} sig=\"{}\" user={} nonce=42", key, signature, address);
+        assert_eq!(
+            redaction.redact(&line),
+            "key=[REDACTED_KEY] sig=\"[REDACTED_SIGNATURE]\" user=0x1234...5678 nonce=42"
+        );
+
+        // Hex embedded in a longer token is left alone
+        let embedded = format!("id_{}", key);
+        assert_eq!(redaction.redact(&embedded), embedded.as_str());
+
+        assert!(matches!(redaction.redact("nothing to hide"), Cow::Borrowed(_)));
+        assert_eq!(RedactionConfig::disabled().redact(&line), line.as_str());
+    }
+
+    #[test]
+    fn test_redacting_writer() {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let capture = Capture::default();
+        let sink = capture.clone();
+        let make_writer = RedactingMakeWriter::new(move || sink.clone(), RedactionConfig::default());
+
+        let key = format!("0x{}", "c".repeat(64));
+        write!(make_writer.make_writer(), "private_key={}", key).unwrap();
+        let written = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(written, "private_key=[REDACTED_KEY]");
+    }
+
+    #[test]
+    fn test_request_trace_headers() {
+        let trace = RequestTrace::http("POST", "https://api.hyperliquid.xyz/info");
+        assert!(!trace.trace_id().is_empty());
+
+        // Every attempt carries the same trace id
+        for attempt in 1..=3 {
+            let span = trace.attempt_span(attempt);
+            let headers = trace.headers(&span);
+            assert_eq!(headers.get(TRACE_ID_HEADER).map(String::as_str), Some(trace.trace_id()));
+        }
+
+        let other = RequestTrace::ws_post(7, "info");
+        assert_ne!(other.trace_id(), trace.trace_id());
+    }
+
     #[test]
     fn test_generate_trace_id() {
         let trace_id = generate_trace_id();
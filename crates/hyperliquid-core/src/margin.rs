@@ -0,0 +1,518 @@
+//! Maintenance margin and liquidation distance
+//!
+//! [`MarginCalculator`] takes an account's `clearinghouseState` and reprices
+//! it as mark prices move, computing maintenance margin requirements, the
+//! liquidation price of every position and how far the mark is from it.
+//! Marks come from the `activeAssetCtx` stream ([`MarginCalculator::attach`])
+//! or from [`MarginCalculator::update_mark`], and every change publishes a
+//! fresh [`MarginReport`].
+//!
+//! The maintenance margin rate of a coin is half the initial margin at its
+//! maximum leverage. Liquidation prices follow the exchange's formula
+//!
+//! ```text
+//! liq_px = mark - side * margin_available / |szi| / (1 - mm_rate * side)
+//! ```
+//!
+//! where `margin_available` is equity above maintenance margin: the cross
+//! account's for cross positions (holding other positions' marks fixed), or
+//! the position's own for isolated ones. Tiered margin tables are not
+//! modelled, so large positions may be liquidated earlier than reported.
+//!
+//! [`MarginCalculator::check_order`] uses the same cached state to tell,
+//! before submitting, whether an order's initial margin (`notional /
+//! leverage` of the size it adds) fits in the cross account's free margin,
+//! and fails with [`HyperliquidError::InsufficientMargin`] when it doesn't.
+//! Margin reserved by resting orders is not tracked.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+use crate::types::Meta;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the report update channel
+const UPDATE_CAPACITY: usize = 256;
+
+/// Leverage assumed for coins with no known maximum
+const DEFAULT_MAX_LEVERAGE: f64 = 50.0;
+
+/// Leverage assumed for coins without a setting, capped at their maximum
+const DEFAULT_LEVERAGE: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginMode {
+    Cross,
+    Isolated,
+}
+
+/// Margin figures for one position at the current mark
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionRisk {
+    pub coin: String,
+    /// Signed size; positive is long
+    pub szi: f64,
+    pub mode: MarginMode,
+    pub entry_px: f64,
+    pub mark_px: f64,
+    pub notional: f64,
+    pub maintenance_margin: f64,
+    /// `None` when the position cannot be liquidated by price alone
+    pub liquidation_px: Option<f64>,
+    /// Relative move of the mark to reach the liquidation price
+    pub distance_to_liquidation: Option<f64>,
+}
+
+/// Margin an order needs and the margin free for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderMargin {
+    pub coin: String,
+    /// Initial margin of the size the order adds
+    pub required: f64,
+    /// Cross account value less the initial margin of cross positions
+    pub available: f64,
+    pub leverage: f64,
+}
+
+/// Margin state of the account at current marks
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MarginReport {
+    /// Equity of the cross margin account
+    pub cross_account_value: f64,
+    /// Maintenance margin required by cross positions
+    pub cross_maintenance_margin: f64,
+    /// Cross maintenance margin over cross equity; liquidation at 1
+    pub cross_margin_ratio: Option<f64>,
+    /// Positions sorted by coin
+    pub positions: Vec<PositionRisk>,
+}
+
+impl MarginReport {
+    /// The position closest to liquidation
+    pub fn nearest_liquidation(&self) -> Option<&PositionRisk> {
+        self.positions
+            .iter()
+            .filter(|p| p.distance_to_liquidation.is_some())
+            .min_by(|a, b| {
+                a.distance_to_liquidation
+                    .unwrap_or(f64::INFINITY)
+                    .total_cmp(&b.distance_to_liquidation.unwrap_or(f64::INFINITY))
+            })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsClearinghouseState {
+    #[serde(default)]
+    asset_positions: Vec<WsAssetPosition>,
+    #[serde(default)]
+    cross_margin_summary: Option<WsMarginSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsAssetPosition {
+    position: WsPosition,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsPosition {
+    coin: String,
+    szi: String,
+    #[serde(default)]
+    entry_px: Option<String>,
+    position_value: String,
+    #[serde(default)]
+    margin_used: Option<String>,
+    #[serde(default)]
+    leverage: Option<WsLeverage>,
+    #[serde(default)]
+    max_leverage: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsLeverage {
+    #[serde(rename = "type")]
+    type_: String,
+    #[serde(default)]
+    value: Option<f64>,
+    #[serde(default)]
+    raw_usd: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsMarginSummary {
+    account_value: String,
+}
+
+/// Position as of the last loaded state
+#[derive(Debug, Clone)]
+struct Held {
+    szi: f64,
+    mode: MarginMode,
+    entry_px: f64,
+    /// Mark implied by the state's position value
+    state_mark: f64,
+    /// Isolated collateral net of entry notional; equity is `raw_usd + szi * mark`
+    raw_usd: f64,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    positions: HashMap<String, Held>,
+    /// Cross account value as of the loaded state
+    cross_account_value: f64,
+    marks: HashMap<String, f64>,
+    max_leverage: HashMap<String, f64>,
+    /// Leverage setting per coin
+    leverage: HashMap<String, f64>,
+}
+
+impl State {
+    fn mark(&self, coin: &str, held: &Held) -> f64 {
+        self.marks.get(coin).copied().unwrap_or(held.state_mark)
+    }
+
+    fn max_leverage(&self, coin: &str) -> f64 {
+        self.max_leverage
+            .get(coin)
+            .copied()
+            .filter(|l| *l > 0.0)
+            .unwrap_or(DEFAULT_MAX_LEVERAGE)
+    }
+
+    fn mm_rate(&self, coin: &str) -> f64 {
+        1.0 / (2.0 * self.max_leverage(coin))
+    }
+
+    fn leverage(&self, coin: &str) -> f64 {
+        self.leverage
+            .get(coin)
+            .copied()
+            .filter(|l| *l > 0.0)
+            .unwrap_or(DEFAULT_LEVERAGE)
+            .min(self.max_leverage(coin))
+    }
+
+    /// Cross account value at current marks, less cross initial margin
+    fn available_margin(&self) -> f64 {
+        self.positions
+            .iter()
+            .filter(|(_, held)| held.mode == MarginMode::Cross)
+            .map(|(coin, held)| {
+                let mark = self.mark(coin, held);
+                held.szi * (mark - held.state_mark) - held.szi.abs() * mark / self.leverage(coin)
+            })
+            .sum::<f64>()
+            + self.cross_account_value
+    }
+
+    fn report(&self) -> MarginReport {
+        let cross: Vec<(&String, &Held)> = self
+            .positions
+            .iter()
+            .filter(|(_, held)| held.mode == MarginMode::Cross)
+            .collect();
+        let cross_account_value = self.cross_account_value
+            + cross
+                .iter()
+                .map(|(coin, held)| held.szi * (self.mark(coin, held) - held.state_mark))
+                .sum::<f64>();
+        let cross_maintenance_margin: f64 = cross
+            .iter()
+            .map(|(coin, held)| held.szi.abs() * self.mark(coin, held) * self.mm_rate(coin))
+            .sum();
+
+        let mut positions: Vec<PositionRisk> = self
+            .positions
+            .iter()
+            .map(|(coin, held)| {
+                let mark = self.mark(coin, held);
+                let mm_rate = self.mm_rate(coin);
+                let notional = held.szi.abs() * mark;
+                let maintenance_margin = notional * mm_rate;
+                let margin_available = match held.mode {
+                    MarginMode::Cross => cross_account_value - cross_maintenance_margin,
+                    MarginMode::Isolated => held.raw_usd + held.szi * mark - maintenance_margin,
+                };
+                let side = held.szi.signum();
+                let liquidation_px =
+                    Some(mark - side * margin_available / held.szi.abs() / (1.0 - mm_rate * side))
+                        .filter(|px| px.is_finite() && *px > 0.0);
+                PositionRisk {
+                    coin: coin.clone(),
+                    szi: held.szi,
+                    mode: held.mode,
+                    entry_px: held.entry_px,
+                    mark_px: mark,
+                    notional,
+                    maintenance_margin,
+                    liquidation_px,
+                    distance_to_liquidation: liquidation_px.map(|px| (mark - px).abs() / mark),
+                }
+            })
+            .collect();
+        positions.sort_by(|a, b| a.coin.cmp(&b.coin));
+
+        MarginReport {
+            cross_account_value,
+            cross_maintenance_margin,
+            cross_margin_ratio: (cross_account_value > 0.0)
+                .then(|| cross_maintenance_margin / cross_account_value),
+            positions,
+        }
+    }
+}
+
+fn parse(value: Option<&str>) -> f64 {
+    value.and_then(|s| s.parse().ok()).unwrap_or(0.0)
+}
+
+/// Reprices an account's margin as marks move
+#[derive(Debug, Clone)]
+pub struct MarginCalculator {
+    state: Arc<Mutex<State>>,
+    updates: broadcast::Sender<MarginReport>,
+}
+
+impl Default for MarginCalculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarginCalculator {
+    pub fn new() -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(State::default())),
+            updates,
+        }
+    }
+
+    /// Take maximum leverage per coin from `meta`
+    ///
+    /// States usually carry each position's maximum leverage; this covers
+    /// those that don't.
+    pub fn with_meta(self, meta: &Meta) -> Self {
+        {
+            let mut state = self.lock();
+            for asset in &meta.universe {
+                state
+                    .max_leverage
+                    .insert(asset.name.clone(), asset.maxLeverage as f64);
+            }
+        }
+        self
+    }
+
+    /// Reports published on every state or mark change
+    pub fn updates(&self) -> broadcast::Receiver<MarginReport> {
+        self.updates.subscribe()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn publish(&self, report: MarginReport) {
+        let _ = self.updates.send(report);
+    }
+
+    /// Replace positions and account value from a `clearinghouseState` response
+    pub fn load_state(&self, data: &Value) -> Result<(), HyperliquidError> {
+        let message: WsClearinghouseState = serde_json::from_value(data.clone())?;
+        let report = {
+            let mut state = self.lock();
+            state.cross_account_value = parse(
+                message
+                    .cross_margin_summary
+                    .as_ref()
+                    .map(|summary| summary.account_value.as_str()),
+            );
+            state.positions.clear();
+            for entry in message.asset_positions {
+                let position = entry.position;
+                let szi = parse(Some(&position.szi));
+                if szi == 0.0 {
+                    continue;
+                }
+                let (mode, raw_usd) = match &position.leverage {
+                    Some(leverage) if leverage.type_ == "isolated" => {
+                        let raw_usd = match &leverage.raw_usd {
+                            Some(raw) => parse(Some(raw)),
+                            // Without rawUsd, treat the margin as the equity at the state's mark
+                            None => {
+                                parse(position.margin_used.as_deref())
+                                    - parse(Some(&position.position_value)) * szi.signum()
+                            }
+                        };
+                        (MarginMode::Isolated, raw_usd)
+                    }
+                    _ => (MarginMode::Cross, 0.0),
+                };
+                if let Some(leverage) = position.leverage.as_ref().and_then(|l| l.value) {
+                    state.leverage.insert(position.coin.clone(), leverage);
+                }
+                if let Some(max_leverage) = position.max_leverage {
+                    state
+                        .max_leverage
+                        .insert(position.coin.clone(), max_leverage);
+                }
+                state.positions.insert(
+                    position.coin,
+                    Held {
+                        szi,
+                        mode,
+                        entry_px: parse(position.entry_px.as_deref()),
+                        state_mark: parse(Some(&position.position_value)) / szi.abs(),
+                        raw_usd,
+                    },
+                );
+            }
+            // The state is priced at its own marks
+            state.marks.clear();
+            state.report()
+        };
+        self.publish(report);
+        Ok(())
+    }
+
+    /// Fetch `user`'s `clearinghouseState` and load it
+    pub async fn refresh(&self, client: &HttpClient, user: &str) -> Result<(), HyperliquidError> {
+        let state: Value = client
+            .post(
+                "/info",
+                &json!({"type": "clearinghouseState", "user": user}),
+            )
+            .await?;
+        self.load_state(&state)
+    }
+
+    /// Reprice `coin` at `mark_px`
+    pub fn update_mark(&self, coin: &str, mark_px: f64) {
+        let report = {
+            let mut state = self.lock();
+            state.marks.insert(coin.to_string(), mark_px);
+            if !state.positions.contains_key(coin) {
+                return;
+            }
+            state.report()
+        };
+        self.publish(report);
+    }
+
+    /// Apply an `activeAssetCtx` stream payload
+    pub fn handle_active_asset_ctx(&self, data: &Value) {
+        let coin = data.get("coin").and_then(Value::as_str);
+        let mark = data
+            .pointer("/ctx/markPx")
+            .and_then(Value::as_str)
+            .and_then(|px| px.parse::<f64>().ok());
+        match (coin, mark) {
+            (Some(coin), Some(mark)) => self.update_mark(coin, mark),
+            _ => warn!("Ignoring malformed activeAssetCtx payload"),
+        }
+    }
+
+    /// Subscribe `ws` to the asset contexts of `coins` and follow their marks
+    ///
+    /// Registers the handlers for the `activeAssetCtx` subscriptions,
+    /// replacing any existing handlers for them.
+    #[cfg(feature = "ws")]
+    pub async fn attach(
+        &self,
+        ws: &WebSocketClient,
+        coins: &[&str],
+    ) -> Result<(), HyperliquidError> {
+        for coin in coins {
+            let subscription = Subscription::ActiveAssetCtx {
+                coin: coin.to_string(),
+            };
+            let calculator = self.clone();
+            let coin = coin.to_string();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if response.channel == "activeAssetCtx"
+                    && response.data.get("coin").and_then(Value::as_str) == Some(&coin)
+                {
+                    calculator.handle_active_asset_ctx(&response.data);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Use `leverage` for orders in `coin`, as set with `updateLeverage`
+    ///
+    /// Loaded states overwrite the setting of coins with a position.
+    pub fn set_leverage(&self, coin: &str, leverage: f64) {
+        self.lock().leverage.insert(coin.to_string(), leverage);
+    }
+
+    /// Check that an order fits in the account's free margin
+    ///
+    /// `px` defaults to the coin's mark. Only size that grows the position
+    /// needs margin; reducing orders always pass.
+    pub fn check_order(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        px: Option<f64>,
+    ) -> Result<OrderMargin, HyperliquidError> {
+        let state = self.lock();
+        let held = state.positions.get(coin);
+        let px = px
+            .or_else(|| state.marks.get(coin).copied())
+            .or_else(|| held.map(|held| held.state_mark))
+            .filter(|px| *px > 0.0)
+            .ok_or_else(|| HyperliquidError::Validation(format!("no price for {}", coin)))?;
+        let szi = held.map_or(0.0, |held| held.szi);
+        let new_szi = if is_buy { szi + sz } else { szi - sz };
+        let added = (new_szi.abs() - szi.abs()).max(0.0);
+        let leverage = state.leverage(coin);
+        let margin = OrderMargin {
+            coin: coin.to_string(),
+            required: added * px / leverage,
+            available: state.available_margin().max(0.0),
+            leverage,
+        };
+        if margin.required > margin.available {
+            return Err(HyperliquidError::InsufficientMargin {
+                coin: margin.coin,
+                required: margin.required,
+                available: margin.available,
+            });
+        }
+        Ok(margin)
+    }
+
+    /// Margin state at current marks
+    pub fn report(&self) -> MarginReport {
+        self.lock().report()
+    }
+
+    /// Risk figures for the position in `coin`
+    pub fn position(&self, coin: &str) -> Option<PositionRisk> {
+        self.report().positions.into_iter().find(|p| p.coin == coin)
+    }
+}
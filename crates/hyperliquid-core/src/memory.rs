@@ -55,6 +55,28 @@ pub struct AllocationStats {
     string_cache_misses: usize,
 }
 
+impl AllocationStats {
+    /// Total number of allocations
+    pub fn total_allocations(&self) -> usize {
+        self.total_allocations
+    }
+
+    /// Total bytes allocated over the lifetime of the allocator
+    pub fn total_bytes_allocated(&self) -> usize {
+        self.total_bytes_allocated
+    }
+
+    /// Bytes currently retained
+    pub fn current_memory_usage(&self) -> usize {
+        self.current_memory_usage
+    }
+
+    /// Highest retained byte count observed
+    pub fn peak_memory_usage(&self) -> usize {
+        self.peak_memory_usage
+    }
+}
+
 /// String interner for deduplicating frequently used strings
 /// Especially useful for symbol names (BTC, ETH, etc.)
 #[derive(Debug)]
@@ -100,11 +122,13 @@ where
     T: Default + Clone,
 {
     /// Available objects
-    pool: Mutex<Vec<T>>,
+    pool: Arc<Mutex<Vec<T>>>,
     /// Maximum pool size
     max_size: usize,
     /// Statistics
     stats: Arc<Mutex<PoolStats>>,
+    /// Resets a returned object before it is reused
+    reset: fn(&mut T),
 }
 
 #[derive(Debug, Clone)]
@@ -190,6 +214,37 @@ impl ArenaAllocator {
         }
     }
 
+    /// Copy a slice of plain values into the arena
+    ///
+    /// Only `Copy` types are accepted because the arena never runs destructors.
+    pub fn allocate_slice<T: Copy>(&mut self, items: &[T]) -> *mut [T] {
+        let size = std::mem::size_of_val(items);
+        let align = std::mem::align_of::<T>();
+
+        if items.is_empty() {
+            return std::ptr::slice_from_raw_parts_mut(std::ptr::NonNull::<T>::dangling().as_ptr(), 0);
+        }
+
+        let ptr = self.allocate_aligned(size, align) as *mut T;
+
+        if !ptr.is_null() {
+            unsafe {
+                std::ptr::copy_nonoverlapping(items.as_ptr(), ptr, items.len());
+            }
+
+            {
+                let mut stats = self.stats.lock().unwrap();
+                stats.total_allocations += 1;
+                stats.total_bytes_allocated += size;
+                stats.current_memory_usage += size;
+                stats.arena_allocations += 1;
+                stats.peak_memory_usage = stats.peak_memory_usage.max(stats.current_memory_usage);
+            }
+        }
+
+        std::ptr::slice_from_raw_parts_mut(ptr, items.len())
+    }
+
     /// Allocate raw bytes in the arena
     pub fn allocate_bytes(&mut self, bytes: &[u8]) -> *mut u8 {
         let ptr = self.allocate_aligned(bytes.len(), 1);
@@ -275,6 +330,11 @@ impl ArenaAllocator {
         self.stats.lock().unwrap().clone()
     }
 
+    /// Get a shared handle to the live statistics (e.g. for `MemoryProfiler`)
+    pub fn stats_handle(&self) -> Arc<Mutex<AllocationStats>> {
+        Arc::clone(&self.stats)
+    }
+
     /// Get total allocated bytes
     pub fn total_allocated(&self) -> usize {
         self.total_allocated
@@ -376,6 +436,11 @@ impl StringInterner {
         self.stats.lock().unwrap().clone()
     }
 
+    /// Get a shared handle to the live statistics (e.g. for `MemoryProfiler`)
+    pub fn stats_handle(&self) -> Arc<Mutex<StringInternStats>> {
+        Arc::clone(&self.stats)
+    }
+
     /// Get the number of unique strings interned
     pub fn len(&self) -> usize {
         self.id_to_string.len()
@@ -473,9 +538,19 @@ where
     T: Default + Clone,
 {
     /// Create a new object pool with the specified maximum size
+    ///
+    /// Returned objects are replaced with `T::default()`.
     pub fn new(max_size: usize) -> Self {
+        Self::with_reset(max_size, |obj| *obj = T::default())
+    }
+
+    /// Create a new object pool with a custom reset function
+    ///
+    /// Use this to keep heap capacity across reuses, e.g. `Vec::clear`
+    /// instead of replacing the buffer.
+    pub fn with_reset(max_size: usize, reset: fn(&mut T)) -> Self {
         Self {
-            pool: Mutex::new(Vec::new()),
+            pool: Arc::new(Mutex::new(Vec::with_capacity(max_size))),
             max_size,
             stats: Arc::new(Mutex::new(PoolStats {
                 total_allocations: 0,
@@ -484,6 +559,7 @@ where
                 pool_misses: 0,
                 max_pool_size: 0,
             })),
+            reset,
         }
     }
 
@@ -494,41 +570,26 @@ where
 
         stats.total_allocations += 1;
 
-        if let Some(obj) = pool.pop() {
-            stats.pool_hits += 1;
-            stats.max_pool_size = stats.max_pool_size.max(pool.len());
-            PooledObject {
-                obj: Some(obj),
-                pool: Arc::downgrade(&self.pool),
-                stats: Arc::downgrade(&self.stats),
+        let obj = match pool.pop() {
+            Some(obj) => {
+                stats.pool_hits += 1;
+                obj
             }
-        } else {
-            stats.pool_misses += 1;
-            PooledObject {
-                obj: Some(T::default()),
-                pool: Arc::downgrade(&self.pool),
-                stats: Arc::downgrade(&self.stats),
+            None => {
+                stats.pool_misses += 1;
+                T::default()
             }
+        };
+
+        PooledObject {
+            obj: Some(obj),
+            pool: Arc::downgrade(&self.pool),
+            stats: Arc::downgrade(&self.stats),
+            max_size: self.max_size,
+            reset: self.reset,
         }
     }
 
-    /// Return an object to the pool
-    fn return_to_pool(&self, mut obj: T) {
-        let mut pool = self.pool.lock().unwrap();
-        let mut stats = self.stats.lock().unwrap();
-
-        stats.total_releases += 1;
-
-        // Reset the object to default state if it implements Default
-        obj = T::default();
-
-        if pool.len() < self.max_size {
-            pool.push(obj);
-            stats.max_pool_size = stats.max_pool_size.max(pool.len());
-        }
-        // If pool is full, just drop the object
-    }
-
     /// Get pool statistics
     pub fn get_stats(&self) -> PoolStats {
         self.stats.lock().unwrap().clone()
@@ -550,6 +611,18 @@ where
     obj: Option<T>,
     pool: std::sync::Weak<Mutex<Vec<T>>>,
     stats: std::sync::Weak<Mutex<PoolStats>>,
+    max_size: usize,
+    reset: fn(&mut T),
+}
+
+impl<T> PooledObject<T>
+where
+    T: Default + Clone,
+{
+    /// Take the object out of the pool permanently
+    pub fn detach(mut self) -> T {
+        self.obj.take().unwrap()
+    }
 }
 
 impl<T> std::ops::Deref for PooledObject<T>
@@ -577,20 +650,19 @@ where
     T: Default + Clone,
 {
     fn drop(&mut self) {
-        if let (Some(obj), Some(pool), Some(stats)) = (
+        if let (Some(mut obj), Some(pool), Some(stats)) = (
             self.obj.take(),
             self.pool.upgrade(),
             self.stats.upgrade(),
         ) {
-            let pool = pool.lock().unwrap();
+            let mut pool = pool.lock().unwrap();
             let mut stats = stats.lock().unwrap();
             stats.total_releases += 1;
 
-            // Reset object to default state
-            let reset_obj = T::default();
-
-            if pool.len() < pool.capacity() {
-                pool.push(reset_obj);
+            if pool.len() < self.max_size {
+                // Reset object state before it is handed out again
+                (self.reset)(&mut obj);
+                pool.push(obj);
                 stats.max_pool_size = stats.max_pool_size.max(pool.len());
             }
             // If pool is full, just drop the object
@@ -598,83 +670,189 @@ where
     }
 }
 
-/// Memory profiler for tracking allocations in real-time
+/// Memory profiler that periodically snapshots registered allocation sources
+///
+/// Sources are registered with `with_allocation_source`/`with_intern_source`
+/// and sampled on a background thread once `start` is called. Each sample's
+/// delta is logged, and an optional leak watchdog fires when retained memory
+/// grows past a threshold relative to the first sample.
 pub struct MemoryProfiler {
     /// Start time for the profiler
     start_time: Instant,
-    /// Memory usage samples
-    samples: Mutex<Vec<MemorySample>>,
+    /// State shared with the sampling thread
+    shared: Arc<ProfilerShared>,
     /// Sample interval
     sample_interval: Duration,
-    /// Running flag
-    running: std::sync::atomic::AtomicBool,
     /// Profiling thread handle
     profiler_handle: Mutex<Option<std::thread::JoinHandle<()>>>,
 }
 
+/// Named source of allocation statistics
+type AllocationSource = (String, Box<dyn Fn() -> AllocationStats + Send + Sync>);
+
+/// Named source of string intern statistics
+type InternSource = (String, Box<dyn Fn() -> StringInternStats + Send + Sync>);
+
+/// Callback fired by the leak watchdog
+type LeakCallback = Box<dyn Fn(&MemoryLeakAlert) + Send + Sync>;
+
+/// Profiler state shared with the sampling thread
+struct ProfilerShared {
+    samples: Mutex<Vec<MemorySample>>,
+    running: std::sync::atomic::AtomicBool,
+    allocation_sources: Vec<AllocationSource>,
+    intern_sources: Vec<InternSource>,
+    watchdog: Option<(usize, LeakCallback)>,
+    /// Retained bytes of the first sample, used as the watchdog baseline
+    baseline_bytes: Mutex<Option<usize>>,
+    /// Set while retained memory is above the watchdog threshold
+    alert_active: std::sync::atomic::AtomicBool,
+    max_samples: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct MemorySample {
     pub timestamp: Duration,
     pub allocated_bytes: usize,
     pub peak_bytes: usize,
     pub active_objects: usize,
+    /// Unique interned strings across all intern sources
+    pub interned_strings: usize,
+}
+
+/// Alert raised when retained memory grows beyond the watchdog threshold
+#[derive(Debug, Clone)]
+pub struct MemoryLeakAlert {
+    /// Retained bytes at the first sample
+    pub baseline_bytes: usize,
+    /// Retained bytes at the current sample
+    pub current_bytes: usize,
+    /// Growth over the baseline
+    pub growth_bytes: usize,
+    /// Configured threshold
+    pub threshold_bytes: usize,
+    /// Sample that triggered the alert
+    pub sample: MemorySample,
 }
 
+/// Default cap on retained samples so long-running processes stay bounded
+const DEFAULT_MAX_SAMPLES: usize = 10_000;
+
 impl MemoryProfiler {
     /// Create a new memory profiler
     pub fn new(sample_interval: Duration) -> Self {
         Self {
             start_time: Instant::now(),
-            samples: Mutex::new(Vec::new()),
+            shared: Arc::new(ProfilerShared {
+                samples: Mutex::new(Vec::new()),
+                running: std::sync::atomic::AtomicBool::new(false),
+                allocation_sources: Vec::new(),
+                intern_sources: Vec::new(),
+                watchdog: None,
+                baseline_bytes: Mutex::new(None),
+                alert_active: std::sync::atomic::AtomicBool::new(false),
+                max_samples: DEFAULT_MAX_SAMPLES,
+            }),
             sample_interval,
-            running: std::sync::atomic::AtomicBool::new(false),
             profiler_handle: Mutex::new(None),
         }
     }
 
+    /// Register a source of allocation statistics
+    ///
+    /// Must be called before `start`; e.g.
+    /// `let handle = arena.stats_handle(); move || handle.lock().unwrap().clone()`.
+    pub fn with_allocation_source<F>(mut self, name: impl Into<String>, source: F) -> Self
+    where
+        F: Fn() -> AllocationStats + Send + Sync + 'static,
+    {
+        self.shared_mut()
+            .allocation_sources
+            .push((name.into(), Box::new(source)));
+        self
+    }
+
+    /// Register a source of string intern statistics
+    pub fn with_intern_source<F>(mut self, name: impl Into<String>, source: F) -> Self
+    where
+        F: Fn() -> StringInternStats + Send + Sync + 'static,
+    {
+        self.shared_mut()
+            .intern_sources
+            .push((name.into(), Box::new(source)));
+        self
+    }
+
+    /// Fire `callback` when retained memory grows more than `threshold_bytes`
+    /// above the first sample
+    ///
+    /// The watchdog re-arms once retained memory drops back below the threshold.
+    pub fn with_leak_watchdog<F>(mut self, threshold_bytes: usize, callback: F) -> Self
+    where
+        F: Fn(&MemoryLeakAlert) + Send + Sync + 'static,
+    {
+        self.shared_mut().watchdog = Some((threshold_bytes, Box::new(callback)));
+        self
+    }
+
+    /// Limit the number of retained samples (oldest are discarded)
+    pub fn with_max_samples(mut self, max_samples: usize) -> Self {
+        self.shared_mut().max_samples = max_samples.max(1);
+        self
+    }
+
+    fn shared_mut(&mut self) -> &mut ProfilerShared {
+        Arc::get_mut(&mut self.shared).expect("profiler must be configured before start")
+    }
+
     /// Start profiling memory usage
     pub fn start(&self) {
-        if self.running.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        if self
+            .shared
+            .running
+            .swap(true, std::sync::atomic::Ordering::SeqCst)
+        {
             return; // Already running
         }
 
         let sample_interval = self.sample_interval;
-        let samples = self.samples.clone();
-        let running = self.running.clone();
-
-        let handle = std::thread::spawn(move || {
-            while running.load(std::sync::atomic::Ordering::SeqCst) {
-                // Sample memory usage (this is a simplified implementation)
-                // In a real implementation, you'd integrate with system memory APIs
-                let sample = MemorySample {
-                    timestamp: Instant::now() - sample_interval,
-                    allocated_bytes: 0, // Would need integration with allocator
-                    peak_bytes: 0,
-                    active_objects: 0,
-                };
-
-                samples.lock().unwrap().push(sample);
-                std::thread::sleep(sample_interval);
-            }
-        });
+        let shared = Arc::clone(&self.shared);
+        let start_time = self.start_time;
+
+        let handle = std::thread::Builder::new()
+            .name("hyperliquid-mem-profiler".to_string())
+            .spawn(move || {
+                while shared.running.load(std::sync::atomic::Ordering::SeqCst) {
+                    shared.record_sample(start_time.elapsed());
+                    std::thread::sleep(sample_interval);
+                }
+            })
+            .expect("failed to spawn memory profiler thread");
 
         *self.profiler_handle.lock().unwrap() = Some(handle);
     }
 
+    /// Take a sample immediately, independent of the background thread
+    pub fn sample_now(&self) -> MemorySample {
+        self.shared.record_sample(self.start_time.elapsed())
+    }
+
     /// Stop profiling and return samples
     pub fn stop(&self) -> Vec<MemorySample> {
-        self.running.store(false, std::sync::atomic::Ordering::SeqCst);
+        self.shared
+            .running
+            .store(false, std::sync::atomic::Ordering::SeqCst);
 
         if let Some(handle) = self.profiler_handle.lock().unwrap().take() {
             let _ = handle.join();
         }
 
-        std::mem::take(&mut *self.samples.lock().unwrap())
+        std::mem::take(&mut *self.shared.samples.lock().unwrap())
     }
 
     /// Get current samples
     pub fn get_samples(&self) -> Vec<MemorySample> {
-        self.samples.lock().unwrap().clone()
+        self.shared.samples.lock().unwrap().clone()
     }
 
     /// Get profiling duration
@@ -683,6 +861,86 @@ impl MemoryProfiler {
     }
 }
 
+impl ProfilerShared {
+    /// Snapshot all sources, log the delta and run the watchdog
+    fn record_sample(&self, timestamp: Duration) -> MemorySample {
+        let mut sample = MemorySample {
+            timestamp,
+            allocated_bytes: 0,
+            peak_bytes: 0,
+            active_objects: 0,
+            interned_strings: 0,
+        };
+
+        for (_, source) in &self.allocation_sources {
+            let stats = source();
+            sample.allocated_bytes += stats.current_memory_usage;
+            sample.peak_bytes += stats.peak_memory_usage;
+            sample.active_objects += stats.total_allocations;
+        }
+        for (_, source) in &self.intern_sources {
+            sample.interned_strings += source().unique_strings;
+        }
+
+        {
+            let mut samples = self.samples.lock().unwrap();
+            if let Some(previous) = samples.last() {
+                tracing::debug!(
+                    allocated_bytes = sample.allocated_bytes,
+                    allocated_delta = sample.allocated_bytes as i64 - previous.allocated_bytes as i64,
+                    interned_delta = sample.interned_strings as i64 - previous.interned_strings as i64,
+                    "Memory profiler sample"
+                );
+            }
+            if samples.len() >= self.max_samples {
+                samples.remove(0);
+            }
+            samples.push(sample.clone());
+        }
+
+        self.check_watchdog(&sample);
+        sample
+    }
+
+    fn check_watchdog(&self, sample: &MemorySample) {
+        let (threshold_bytes, callback) = match &self.watchdog {
+            Some((threshold, callback)) => (*threshold, callback),
+            None => return,
+        };
+
+        let baseline_bytes = *self
+            .baseline_bytes
+            .lock()
+            .unwrap()
+            .get_or_insert(sample.allocated_bytes);
+        let growth_bytes = sample.allocated_bytes.saturating_sub(baseline_bytes);
+
+        if growth_bytes > threshold_bytes {
+            if !self
+                .alert_active
+                .swap(true, std::sync::atomic::Ordering::SeqCst)
+            {
+                tracing::warn!(
+                    baseline_bytes,
+                    current_bytes = sample.allocated_bytes,
+                    threshold_bytes,
+                    "Retained memory exceeded watchdog threshold"
+                );
+                callback(&MemoryLeakAlert {
+                    baseline_bytes,
+                    current_bytes: sample.allocated_bytes,
+                    growth_bytes,
+                    threshold_bytes,
+                    sample: sample.clone(),
+                });
+            }
+        } else {
+            self.alert_active
+                .store(false, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -770,6 +1028,25 @@ mod tests {
         assert_eq!(pool.pool_size(), 1);
     }
 
+    #[test]
+    fn test_object_pool_with_reset_keeps_capacity() {
+        let pool: ObjectPool<Vec<u8>> = ObjectPool::with_reset(2, Vec::clear);
+
+        {
+            let mut buf = pool.get();
+            buf.extend_from_slice(&[0u8; 256]);
+        }
+
+        let buf = pool.get();
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 256);
+        assert_eq!(pool.get_stats().pool_hits, 1);
+
+        // Detached objects never return to the pool
+        let _owned = buf.detach();
+        assert_eq!(pool.pool_size(), 0);
+    }
+
     #[test]
     fn test_memory_profiler() {
         let profiler = MemoryProfiler::new(Duration::from_millis(100));
@@ -781,4 +1058,36 @@ mod tests {
         assert!(samples.len() > 0);
         assert!(profiler.duration() >= Duration::from_millis(250));
     }
+
+    #[test]
+    fn test_memory_profiler_sources_and_watchdog() {
+        let arena = Arc::new(Mutex::new(ArenaAllocator::new(1024)));
+        let arena_stats = arena.lock().unwrap().stats_handle();
+        let mut interner = StringInterner::new();
+        interner.intern("BTC");
+        let intern_stats = interner.stats_handle();
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let alerts_clone = Arc::clone(&alerts);
+
+        let profiler = MemoryProfiler::new(Duration::from_millis(10))
+            .with_allocation_source("arena", move || arena_stats.lock().unwrap().clone())
+            .with_intern_source("symbols", move || intern_stats.lock().unwrap().clone())
+            .with_leak_watchdog(512, move |alert| {
+                alerts_clone.lock().unwrap().push(alert.clone())
+            });
+
+        let baseline = profiler.sample_now();
+        assert_eq!(baseline.allocated_bytes, 0);
+        assert_eq!(baseline.interned_strings, 1);
+
+        arena.lock().unwrap().allocate_bytes(&[0u8; 1024]);
+        profiler.sample_now();
+        // Still above the threshold, so the watchdog must not fire again
+        profiler.sample_now();
+
+        let alerts = alerts.lock().unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].growth_bytes > 512);
+        assert_eq!(alerts[0].baseline_bytes, 0);
+    }
 }
\ No newline at end of file
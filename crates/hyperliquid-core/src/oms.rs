@@ -0,0 +1,816 @@
+//! Order lifecycle management
+//!
+//! [`OrderManager`] tracks the orders this process submits through
+//! `New → Acked → PartiallyFilled → Filled / Canceled / Rejected`, driven by
+//! the exchange's responses and the `orderUpdates` and `userFills` streams.
+//! Every transition and fill is published as an [`OrderEvent`].
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::oms::OrderManager;
+//!
+//! let oms = OrderManager::new();
+//! oms.attach(&ws, "0x1234...").await?;
+//! let mut events = oms.events();
+//!
+//! let key = oms.record_submission("BTC", true, 0.01, 65000.0, Some("0x0000000000000000000000000000abcd"));
+//! // ... submit the order, then hand the response back:
+//! // oms.record_response(&[key], &response);
+//!
+//! while let Ok(event) = events.recv().await {
+//!     println!("{:?} -> {:?}", event.order.cloid, event.kind);
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! Orders are matched to updates by cloid when they have one, otherwise by
+//! oid once the exchange has assigned it. Fills and updates that arrive for an
+//! oid before the placement response are held briefly and applied when the
+//! oid becomes known.
+//!
+//! With a [`StateStore`] attached through [`OrderManager::with_store`], every
+//! order is written through on each change and reloaded on startup, so a
+//! restarted process keeps following the orders it left resting.
+//!
+//! With an [`OrderJournal`] attached through [`OrderManager::with_journal`]
+//! instead, every submission is journaled before it is sent and every
+//! response, update and fill as it is applied. On restart the journal is
+//! replayed to rebuild the book, including orders whose placement response
+//! never arrived, and [`OrderManager::recover_in_flight`] asks the exchange
+//! what became of those.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::journal::{JournalEntry, OrderJournal};
+use crate::store::{self, StateStore};
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::{UserEvent, UserEventKind, WebSocketResponse};
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the event channel; slow receivers miss older events
+const EVENT_CAPACITY: usize = 1024;
+
+/// Number of unmatched oids whose updates are held for a later ack
+const PENDING_CAPACITY: usize = 1024;
+
+/// Fill sizes within this of the order size count as fully filled
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Store key prefix of tracked orders
+const ORDER_PREFIX: &str = "oms/order/";
+
+/// Store key of a tracked order; zero-padded so scans return key order
+fn store_key(key: OrderKey) -> String {
+    format!("{}{:020}", ORDER_PREFIX, key.0)
+}
+
+/// Lifecycle state of a tracked order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderState {
+    /// Submitted, no response yet
+    New,
+    /// Resting on the book
+    Acked,
+    /// Resting with part of its size filled
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected,
+}
+
+impl OrderState {
+    /// Whether the order can still change
+    pub fn is_open(self) -> bool {
+        matches!(
+            self,
+            OrderState::New | OrderState::Acked | OrderState::PartiallyFilled
+        )
+    }
+
+    /// Whether the order has reached a final state
+    pub fn is_terminal(self) -> bool {
+        !self.is_open()
+    }
+}
+
+/// Local handle for an order returned by [`OrderManager::record_submission`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrderKey(u64);
+
+/// Snapshot of a tracked order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedOrder {
+    pub key: OrderKey,
+    pub coin: String,
+    pub is_buy: bool,
+    pub limit_px: f64,
+    pub sz: f64,
+    pub cloid: Option<String>,
+    /// Exchange order id, once acknowledged
+    pub oid: Option<u64>,
+    pub state: OrderState,
+    pub filled_sz: f64,
+    /// Volume-weighted average fill price (0 until the first fill)
+    pub avg_fill_px: f64,
+    /// Rejection reason or final exchange status (e.g. `marginCanceled`)
+    pub reason: Option<String>,
+}
+
+impl TrackedOrder {
+    /// Size still open
+    pub fn remaining_sz(&self) -> f64 {
+        (self.sz - self.filled_sz).max(0.0)
+    }
+}
+
+/// What happened to an order
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderEventKind {
+    Submitted,
+    Acked,
+    /// A single execution; `order` reflects the cumulative fill
+    Fill {
+        px: f64,
+        sz: f64,
+    },
+    PartiallyFilled,
+    Filled,
+    Canceled,
+    Rejected {
+        reason: String,
+    },
+}
+
+/// Lifecycle event with the order state after it was applied
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderEvent {
+    pub kind: OrderEventKind,
+    pub order: TrackedOrder,
+}
+
+/// Entry of the `orderUpdates` stream
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsOrderUpdate {
+    order: WsOrder,
+    status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsOrder {
+    oid: u64,
+    #[serde(default)]
+    cloid: Option<String>,
+}
+
+/// `userFills` message payload
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsUserFills {
+    #[serde(default)]
+    fills: Vec<WsFill>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WsFill {
+    oid: u64,
+    px: String,
+    sz: String,
+    #[serde(default)]
+    tid: Option<u64>,
+}
+
+/// Updates for an oid that no tracked order claims yet
+#[derive(Debug, Default)]
+struct Pending {
+    status: Option<String>,
+    fills: Vec<WsFill>,
+}
+
+#[derive(Debug, Default)]
+struct Book {
+    next_key: u64,
+    orders: HashMap<OrderKey, TrackedOrder>,
+    by_cloid: HashMap<String, OrderKey>,
+    by_oid: HashMap<u64, OrderKey>,
+    seen_fills: HashSet<u64>,
+    pending: HashMap<u64, Pending>,
+    pending_order: VecDeque<u64>,
+}
+
+/// Tracks submitted orders and publishes their lifecycle events
+///
+/// Cheap to clone; clones share state.
+#[derive(Debug, Clone)]
+pub struct OrderManager {
+    book: Arc<Mutex<Book>>,
+    events: broadcast::Sender<OrderEvent>,
+    store: Option<Arc<dyn StateStore>>,
+    journal: Option<Arc<OrderJournal>>,
+}
+
+impl Default for OrderManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrderManager {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            book: Arc::new(Mutex::new(Book::default())),
+            events,
+            store: None,
+            journal: None,
+        }
+    }
+
+    /// Write orders through to `store`, first loading the ones it holds
+    ///
+    /// Loaded orders keep their keys, cloids and oids, so updates for them
+    /// are matched as before the restart.
+    pub fn with_store(mut self, store: Arc<dyn StateStore>) -> Result<Self, HyperliquidError> {
+        let orders: Vec<(String, TrackedOrder)> = store::scan_json(store.as_ref(), ORDER_PREFIX)?;
+        {
+            let mut book = self.lock();
+            for (_, order) in orders {
+                book.next_key = book.next_key.max(order.key.0);
+                if let Some(cloid) = &order.cloid {
+                    book.by_cloid.insert(cloid.clone(), order.key);
+                }
+                if let Some(oid) = order.oid {
+                    book.by_oid.insert(oid, order.key);
+                }
+                book.orders.insert(order.key, order);
+            }
+        }
+        self.store = Some(store);
+        Ok(self)
+    }
+
+    /// Replay `journal` and append to it from now on
+    ///
+    /// Replaying applies the journaled submissions, statuses, updates and
+    /// fills in their original order, so the book ends up as it was when the
+    /// process stopped. Use either this or [`with_store`](Self::with_store),
+    /// not both: fills replayed over stored orders would be counted twice.
+    pub fn with_journal(mut self, journal: Arc<OrderJournal>) -> Result<Self, HyperliquidError> {
+        for record in journal.records()? {
+            match record.entry {
+                JournalEntry::Submitted {
+                    key,
+                    coin,
+                    is_buy,
+                    sz,
+                    limit_px,
+                    cloid,
+                } => {
+                    if !self.lock().orders.contains_key(&key) {
+                        self.insert_submission(key, &coin, is_buy, sz, limit_px, cloid.as_deref());
+                    }
+                }
+                JournalEntry::Response { key, status } => self.apply_order_status(key, &status),
+                JournalEntry::OrderUpdates { data } => self.apply_order_updates(&data),
+                JournalEntry::UserFills { data } => self.apply_user_fills(&data),
+            }
+        }
+        self.journal = Some(journal);
+        Ok(self)
+    }
+
+    /// Append to the journal, if any
+    ///
+    /// Journal failures are logged rather than failing the order path.
+    fn journal(&self, entry: JournalEntry) {
+        if let Some(journal) = &self.journal {
+            if let Err(e) = journal.append(entry) {
+                warn!("Failed to journal order event: {}", e);
+            }
+        }
+    }
+
+    fn persist(&self, order: &TrackedOrder) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store::put_json(store.as_ref(), &store_key(order.key), order) {
+                warn!("Failed to persist order {:?}: {}", order.key, e);
+            }
+        }
+    }
+
+    /// Receive lifecycle events from now on
+    pub fn events(&self) -> broadcast::Receiver<OrderEvent> {
+        self.events.subscribe()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Book> {
+        self.book.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn emit(&self, kind: OrderEventKind, order: &TrackedOrder) {
+        self.persist(order);
+        // No receivers is fine
+        let _ = self.events.send(OrderEvent {
+            kind,
+            order: order.clone(),
+        });
+    }
+
+    /// Start tracking an order about to be submitted
+    pub fn record_submission(
+        &self,
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        cloid: Option<&str>,
+    ) -> OrderKey {
+        let key = {
+            let mut book = self.lock();
+            book.next_key += 1;
+            OrderKey(book.next_key)
+        };
+        self.journal(JournalEntry::Submitted {
+            key,
+            coin: coin.to_string(),
+            is_buy,
+            sz,
+            limit_px,
+            cloid: cloid.map(str::to_string),
+        });
+        self.insert_submission(key, coin, is_buy, sz, limit_px, cloid);
+        key
+    }
+
+    fn insert_submission(
+        &self,
+        key: OrderKey,
+        coin: &str,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        cloid: Option<&str>,
+    ) {
+        let mut book = self.lock();
+        book.next_key = book.next_key.max(key.0);
+        let order = TrackedOrder {
+            key,
+            coin: coin.to_string(),
+            is_buy,
+            limit_px,
+            sz,
+            cloid: cloid.map(str::to_string),
+            oid: None,
+            state: OrderState::New,
+            filled_sz: 0.0,
+            avg_fill_px: 0.0,
+            reason: None,
+        };
+        if let Some(cloid) = cloid {
+            book.by_cloid.insert(cloid.to_string(), key);
+        }
+        book.orders.insert(key, order.clone());
+        drop(book);
+
+        self.emit(OrderEventKind::Submitted, &order);
+    }
+
+    /// Apply an order placement response, `keys` in the order they were sent
+    ///
+    /// Accepts the full exchange response; a whole-request error rejects every
+    /// order.
+    pub fn record_response(&self, keys: &[OrderKey], response: &Value) {
+        if response.get("status").and_then(Value::as_str) == Some("err") {
+            let reason = match response.get("response") {
+                Some(Value::String(reason)) => reason.clone(),
+                Some(other) => other.to_string(),
+                None => "unknown error".to_string(),
+            };
+            for key in keys {
+                self.record_status(*key, &json!({ "error": reason }));
+            }
+            return;
+        }
+
+        let statuses = response
+            .pointer("/response/data/statuses")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        for (index, key) in keys.iter().enumerate() {
+            match statuses.get(index) {
+                Some(status) => self.record_status(*key, status),
+                None => self.record_status(*key, &json!({"error": "missing status in response"})),
+            }
+        }
+    }
+
+    /// Apply one entry of `response.data.statuses`
+    pub fn record_status(&self, key: OrderKey, status: &Value) {
+        self.journal(JournalEntry::Response {
+            key,
+            status: status.clone(),
+        });
+        self.apply_order_status(key, status);
+    }
+
+    fn apply_order_status(&self, key: OrderKey, status: &Value) {
+        if let Some(reason) = status.get("error").and_then(Value::as_str) {
+            self.reject(key, reason);
+            return;
+        }
+
+        let (oid, filled) = if let Some(resting) = status.get("resting") {
+            (resting.get("oid").and_then(Value::as_u64), false)
+        } else if let Some(filled) = status.get("filled") {
+            (filled.get("oid").and_then(Value::as_u64), true)
+        } else {
+            debug!("Ignoring unrecognized order status: {}", status);
+            return;
+        };
+
+        if let Some(oid) = oid {
+            self.bind_oid(key, oid);
+        }
+        let state = if filled {
+            OrderState::Filled
+        } else {
+            OrderState::Acked
+        };
+        self.transition(key, state, None);
+    }
+
+    fn reject(&self, key: OrderKey, reason: &str) {
+        self.transition(key, OrderState::Rejected, Some(reason.to_string()));
+    }
+
+    /// Associate `oid` with `key` and replay anything held for it
+    fn bind_oid(&self, key: OrderKey, oid: u64) {
+        let pending = {
+            let mut book = self.lock();
+            let Some(order) = book.orders.get_mut(&key) else {
+                return;
+            };
+            order.oid = Some(oid);
+            let order = order.clone();
+            book.by_oid.insert(oid, key);
+            book.pending_order.retain(|pending| *pending != oid);
+            let pending = book.pending.remove(&oid);
+            drop(book);
+            self.persist(&order);
+            pending
+        };
+
+        if let Some(pending) = pending {
+            for fill in pending.fills {
+                self.apply_fill(key, &fill);
+            }
+            if let Some(status) = pending.status {
+                self.apply_status(key, &status);
+            }
+        }
+    }
+
+    /// Move an order to `state`, emitting an event if it changed
+    fn transition(&self, key: OrderKey, state: OrderState, reason: Option<String>) {
+        let order = {
+            let mut book = self.lock();
+            let Some(order) = book.orders.get_mut(&key) else {
+                return;
+            };
+            // Terminal states are final, and a fill can't be un-done by a
+            // late ack
+            let regress = order.state == OrderState::PartiallyFilled && state == OrderState::Acked;
+            if order.state == state || order.state.is_terminal() || regress {
+                return;
+            }
+            order.state = state;
+            if reason.is_some() {
+                order.reason = reason.clone();
+            }
+            order.clone()
+        };
+
+        let kind = match state {
+            OrderState::New => OrderEventKind::Submitted,
+            OrderState::Acked => OrderEventKind::Acked,
+            OrderState::PartiallyFilled => OrderEventKind::PartiallyFilled,
+            OrderState::Filled => OrderEventKind::Filled,
+            OrderState::Canceled => OrderEventKind::Canceled,
+            OrderState::Rejected => OrderEventKind::Rejected {
+                reason: reason.unwrap_or_default(),
+            },
+        };
+        self.emit(kind, &order);
+    }
+
+    /// Find the tracked order for an update, or hold the update for later
+    fn resolve(&self, oid: u64, cloid: Option<&str>) -> Option<OrderKey> {
+        let mut book = self.lock();
+        if let Some(key) = book.by_oid.get(&oid) {
+            return Some(*key);
+        }
+        if let Some(key) = cloid.and_then(|cloid| book.by_cloid.get(cloid)).copied() {
+            if let Some(order) = book.orders.get_mut(&key) {
+                order.oid = Some(oid);
+            }
+            book.by_oid.insert(oid, key);
+            return Some(key);
+        }
+        None
+    }
+
+    fn hold(&self, oid: u64) -> MutexGuard<'_, Book> {
+        let mut book = self.lock();
+        if !book.pending.contains_key(&oid) {
+            if book.pending_order.len() >= PENDING_CAPACITY {
+                if let Some(oldest) = book.pending_order.pop_front() {
+                    book.pending.remove(&oldest);
+                }
+            }
+            book.pending_order.push_back(oid);
+            book.pending.insert(oid, Pending::default());
+        }
+        book
+    }
+
+    /// Apply a `data` payload from the `orderUpdates` channel
+    pub fn handle_order_updates(&self, data: &Value) {
+        self.journal(JournalEntry::OrderUpdates { data: data.clone() });
+        self.apply_order_updates(data);
+    }
+
+    fn apply_order_updates(&self, data: &Value) {
+        let updates: Vec<WsOrderUpdate> = match serde_json::from_value(data.clone()) {
+            Ok(updates) => updates,
+            Err(e) => {
+                warn!("Failed to parse orderUpdates message: {}", e);
+                return;
+            }
+        };
+
+        for update in updates {
+            let oid = update.order.oid;
+            match self.resolve(oid, update.order.cloid.as_deref()) {
+                Some(key) => self.apply_status(key, &update.status),
+                None => {
+                    let mut book = self.hold(oid);
+                    if let Some(pending) = book.pending.get_mut(&oid) {
+                        pending.status = Some(update.status);
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply_status(&self, key: OrderKey, status: &str) {
+        match status {
+            "open" | "triggered" => {
+                let partially_filled = self
+                    .lock()
+                    .orders
+                    .get(&key)
+                    .is_some_and(|order| order.filled_sz > 0.0);
+                let state = if partially_filled {
+                    OrderState::PartiallyFilled
+                } else {
+                    OrderState::Acked
+                };
+                self.transition(key, state, None);
+            }
+            "filled" => self.transition(key, OrderState::Filled, None),
+            "rejected" => self.reject(key, status),
+            other if other.ends_with("Rejected") => self.reject(key, other),
+            // "canceled", "marginCanceled", "reduceOnlyCanceled", ...
+            other if other.ends_with("anceled") => {
+                self.transition(key, OrderState::Canceled, Some(other.to_string()))
+            }
+            other => debug!("Ignoring order status {}", other),
+        }
+    }
+
+    /// Apply a `data` payload from the `userFills` channel
+    ///
+    /// Fills are deduplicated by trade id, so replaying the snapshot sent on
+    /// (re)subscription is harmless.
+    pub fn handle_user_fills(&self, data: &Value) {
+        self.journal(JournalEntry::UserFills { data: data.clone() });
+        self.apply_user_fills(data);
+    }
+
+    fn apply_user_fills(&self, data: &Value) {
+        let message: WsUserFills = match serde_json::from_value(data.clone()) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Failed to parse userFills message: {}", e);
+                return;
+            }
+        };
+
+        for fill in message.fills {
+            if let Some(tid) = fill.tid {
+                if !self.lock().seen_fills.insert(tid) {
+                    continue;
+                }
+            }
+            match self.resolve(fill.oid, None) {
+                Some(key) => self.apply_fill(key, &fill),
+                None => {
+                    let mut book = self.hold(fill.oid);
+                    if let Some(pending) = book.pending.get_mut(&fill.oid) {
+                        pending.fills.push(fill);
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply_fill(&self, key: OrderKey, fill: &WsFill) {
+        let (Ok(px), Ok(sz)) = (fill.px.parse::<f64>(), fill.sz.parse::<f64>()) else {
+            warn!("Ignoring fill with malformed px/sz: {:?}", fill);
+            return;
+        };
+
+        let (order, complete) = {
+            let mut book = self.lock();
+            let Some(order) = book.orders.get_mut(&key) else {
+                return;
+            };
+            let total = order.filled_sz + sz;
+            if total > 0.0 {
+                order.avg_fill_px = (order.avg_fill_px * order.filled_sz + px * sz) / total;
+            }
+            order.filled_sz = total;
+            (order.clone(), total + SIZE_EPSILON >= order.sz)
+        };
+
+        self.emit(OrderEventKind::Fill { px, sz }, &order);
+        let state = if complete {
+            OrderState::Filled
+        } else {
+            OrderState::PartiallyFilled
+        };
+        self.transition(key, state, None);
+    }
+
+    /// Apply an event from a [`UserStream`](crate::stream::UserStream)
+    ///
+    /// Use instead of [`attach`](Self::attach) so fills and updates missed
+    /// during websocket outages are applied too.
+    pub fn handle_user_event(&self, event: &UserEvent) {
+        if event.recovered {
+            debug!("Applying recovered user event at {}", event.time);
+        }
+        match &event.kind {
+            UserEventKind::Fill(fill) => self.handle_user_fills(&json!({"fills": [fill]})),
+            UserEventKind::OrderUpdate(update) => self.handle_order_updates(&json!([update])),
+        }
+    }
+
+    /// Subscribe `ws` to `user`'s order updates and fills and feed them in
+    ///
+    /// Registers the handlers for the `orderUpdates` and `userFills`
+    /// subscriptions, replacing any existing handlers for them.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient, user: &str) -> Result<(), HyperliquidError> {
+        for channel in ["orderUpdates", "userFills"] {
+            let subscription: Subscription =
+                serde_json::from_value(json!({"type": channel, "user": user}))?;
+            let oms = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if !response.channel.starts_with(channel) {
+                    return;
+                }
+                if channel == "orderUpdates" {
+                    oms.handle_order_updates(&response.data);
+                } else {
+                    oms.handle_user_fills(&response.data);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Ask the exchange about every open order and apply its answer
+    ///
+    /// Meant for after a restart: orders still `New` were submitted without
+    /// a response being seen and are looked up by cloid, the rest by oid.
+    /// An order the exchange has never heard of is rejected. Returns the
+    /// keys of orders that couldn't be looked up because they have neither.
+    ///
+    /// Only the order status is recovered; fills missed while the process
+    /// was down come through the `userFills` snapshot on resubscription or a
+    /// [`UserStream`](crate::stream::UserStream).
+    pub async fn recover_in_flight(
+        &self,
+        client: &HttpClient,
+        user: &str,
+    ) -> Result<Vec<OrderKey>, HyperliquidError> {
+        let mut unresolved = Vec::new();
+        for order in self.open_orders() {
+            let request = match (&order.oid, &order.cloid) {
+                (Some(oid), _) => json!({"type": "orderStatus", "user": user, "oid": oid}),
+                // `oid` takes a cloid as well
+                (None, Some(cloid)) => json!({"type": "orderStatus", "user": user, "oid": cloid}),
+                (None, None) => {
+                    warn!("Order {:?} has no oid or cloid to look up", order.key);
+                    unresolved.push(order.key);
+                    continue;
+                }
+            };
+            let response: Value = client.post("/info", &request).await?;
+            match response.get("status").and_then(Value::as_str) {
+                Some("order") => match response.get("order") {
+                    Some(update) => self.handle_order_updates(&json!([update])),
+                    None => warn!("orderStatus for {:?} has no order", order.key),
+                },
+                Some("unknownOid") if order.state == OrderState::New => {
+                    self.record_status(order.key, &json!({"error": "unknownOid"}))
+                }
+                other => debug!("orderStatus for {:?} returned {:?}", order.key, other),
+            }
+        }
+        Ok(unresolved)
+    }
+
+    /// Snapshot of an order
+    pub fn get(&self, key: OrderKey) -> Option<TrackedOrder> {
+        self.lock().orders.get(&key).cloned()
+    }
+
+    /// Snapshot of the order with this client order id
+    pub fn order(&self, cloid: &str) -> Option<TrackedOrder> {
+        let book = self.lock();
+        book.by_cloid
+            .get(cloid)
+            .and_then(|key| book.orders.get(key))
+            .cloned()
+    }
+
+    /// Snapshot of the order with this exchange order id
+    pub fn order_by_oid(&self, oid: u64) -> Option<TrackedOrder> {
+        let book = self.lock();
+        book.by_oid
+            .get(&oid)
+            .and_then(|key| book.orders.get(key))
+            .cloned()
+    }
+
+    /// Orders that have not reached a terminal state
+    pub fn open_orders(&self) -> Vec<TrackedOrder> {
+        let mut orders: Vec<_> = self
+            .lock()
+            .orders
+            .values()
+            .filter(|order| order.state.is_open())
+            .cloned()
+            .collect();
+        orders.sort_by_key(|order| order.key.0);
+        orders
+    }
+
+    /// Stop tracking orders in a terminal state, returning how many were removed
+    pub fn prune_closed(&self) -> usize {
+        let mut book = self.lock();
+        let closed: Vec<TrackedOrder> = book
+            .orders
+            .values()
+            .filter(|order| order.state.is_terminal())
+            .cloned()
+            .collect();
+        for order in &closed {
+            if let Some(store) = &self.store {
+                if let Err(e) = store.delete(&store_key(order.key)) {
+                    warn!(
+                        "Failed to remove order {:?} from the store: {}",
+                        order.key, e
+                    );
+                }
+            }
+            book.orders.remove(&order.key);
+            if let Some(cloid) = &order.cloid {
+                book.by_cloid.remove(cloid);
+            }
+            if let Some(oid) = order.oid {
+                book.by_oid.remove(&oid);
+            }
+        }
+        closed.len()
+    }
+}
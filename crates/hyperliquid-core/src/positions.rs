@@ -0,0 +1,556 @@
+//! Local position and PnL tracking
+//!
+//! [`PositionTracker`] keeps per-coin position size, average entry, realized
+//! PnL, fees and cumulative funding from the `userFills` and `userFundings`
+//! streams (or from fills passed in directly), and computes unrealized PnL
+//! against mark prices supplied by the caller.
+//!
+//! Local state can drift from the exchange (missed messages, manual trades
+//! from another session), so [`PositionTracker::reconcile`] compares it with
+//! the account's `clearinghouseState` and publishes a [`Divergence`] for every
+//! coin that disagrees.
+//!
+//! Streamed fill snapshots (`isSnapshot: true`) contain historical fills and
+//! are skipped; seed the tracker with [`PositionTracker::sync_from_state`]
+//! instead.
+//!
+//! [`PositionTracker::with_store`] writes positions through to a
+//! [`StateStore`] and reloads them on startup, keeping realized PnL, fees and
+//! funding across restarts.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::store::{self, StateStore};
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the divergence alert channel
+const ALERT_CAPACITY: usize = 256;
+
+/// Default tolerance when comparing sizes and prices with the exchange
+const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// Store key prefix of positions
+const POSITION_PREFIX: &str = "positions/";
+
+/// Default number of recent fill trade ids remembered for deduplication
+pub const DEFAULT_SEEN_FILLS_CAPACITY: usize = 10_000;
+
+/// Position in one coin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub coin: String,
+    /// Signed size; positive is long
+    pub szi: f64,
+    /// Average entry price of the open size (0 when flat)
+    pub entry_px: f64,
+    /// PnL realized by closing trades, before fees and funding
+    pub realized_pnl: f64,
+    /// Trading fees paid
+    pub fees: f64,
+    /// Funding received (positive) or paid (negative)
+    pub cumulative_funding: f64,
+    /// Last mark price passed to [`PositionTracker::update_mark`]
+    pub mark_px: Option<f64>,
+}
+
+impl Position {
+    fn new(coin: &str) -> Self {
+        Self {
+            coin: coin.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_flat(&self) -> bool {
+        self.szi.abs() < DEFAULT_TOLERANCE
+    }
+
+    /// Unrealized PnL at `mark_px`
+    pub fn unrealized_pnl_at(&self, mark_px: f64) -> f64 {
+        self.szi * (mark_px - self.entry_px)
+    }
+
+    /// Unrealized PnL at the last mark price, if one is known
+    pub fn unrealized_pnl(&self) -> Option<f64> {
+        self.mark_px.map(|mark| self.unrealized_pnl_at(mark))
+    }
+
+    /// Realized PnL net of fees, plus funding
+    pub fn net_realized_pnl(&self) -> f64 {
+        self.realized_pnl - self.fees + self.cumulative_funding
+    }
+
+    /// Apply an execution of `sz` at `px`
+    fn apply_fill(&mut self, is_buy: bool, px: f64, sz: f64, fee: f64) {
+        let signed = if is_buy { sz } else { -sz };
+        self.fees += fee;
+
+        if self.is_flat() || self.szi.signum() == signed.signum() {
+            // Opening or adding: blend the entry price
+            let open = self.szi.abs();
+            self.entry_px = (self.entry_px * open + px * sz) / (open + sz);
+            self.szi += signed;
+            return;
+        }
+
+        // Reducing, closing or flipping
+        let closed = sz.min(self.szi.abs());
+        self.realized_pnl += closed * (px - self.entry_px) * self.szi.signum();
+        self.szi += signed;
+        if self.is_flat() {
+            self.szi = 0.0;
+            self.entry_px = 0.0;
+        } else if self.szi.signum() == signed.signum() {
+            // Flipped; the remainder was opened at this fill's price
+            self.entry_px = px;
+        }
+    }
+}
+
+/// Local state disagreeing with the exchange for one coin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub coin: String,
+    pub local_szi: f64,
+    pub exchange_szi: f64,
+    pub local_entry_px: f64,
+    pub exchange_entry_px: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsUserFills {
+    #[serde(default)]
+    is_snapshot: bool,
+    #[serde(default)]
+    fills: Vec<WsFill>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsFill {
+    coin: String,
+    px: String,
+    sz: String,
+    side: String,
+    #[serde(default)]
+    fee: Option<String>,
+    #[serde(default)]
+    tid: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsUserFundings {
+    #[serde(default)]
+    is_snapshot: bool,
+    #[serde(default)]
+    fundings: Vec<WsFunding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WsFunding {
+    coin: String,
+    usdc: String,
+}
+
+/// Trade ids of the most recent fills, oldest evicted first
+#[derive(Debug)]
+struct SeenFills {
+    capacity: usize,
+    ids: HashSet<u64>,
+    /// Trade ids in arrival order, for eviction
+    order: VecDeque<u64>,
+}
+
+impl SeenFills {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ids: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Remember `tid`, returning `false` if it is already remembered
+    fn insert(&mut self, tid: u64) -> bool {
+        if !self.ids.insert(tid) {
+            return false;
+        }
+        self.order.push_back(tid);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    positions: HashMap<String, Position>,
+    seen_fills: SeenFills,
+}
+
+/// Tracks positions and PnL for one account
+///
+/// Cheap to clone; clones share state.
+#[derive(Debug, Clone)]
+pub struct PositionTracker {
+    state: Arc<Mutex<State>>,
+    alerts: broadcast::Sender<Divergence>,
+    tolerance: f64,
+    store: Option<Arc<dyn StateStore>>,
+}
+
+impl Default for PositionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PositionTracker {
+    pub fn new() -> Self {
+        let (alerts, _) = broadcast::channel(ALERT_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(State {
+                positions: HashMap::new(),
+                seen_fills: SeenFills::new(DEFAULT_SEEN_FILLS_CAPACITY),
+            })),
+            alerts,
+            tolerance: DEFAULT_TOLERANCE,
+            store: None,
+        }
+    }
+
+    /// Write positions through to `store`, first loading the ones it holds
+    pub fn with_store(mut self, store: Arc<dyn StateStore>) -> Result<Self, HyperliquidError> {
+        let positions: Vec<(String, Position)> = store::scan_json(store.as_ref(), POSITION_PREFIX)?;
+        {
+            let mut state = self.lock();
+            for (_, position) in positions {
+                state.positions.insert(position.coin.clone(), position);
+            }
+        }
+        self.store = Some(store);
+        Ok(self)
+    }
+
+    fn persist(&self, positions: &[Position]) {
+        let Some(store) = &self.store else {
+            return;
+        };
+        for position in positions {
+            let key = format!("{}{}", POSITION_PREFIX, position.coin);
+            if let Err(e) = store::put_json(store.as_ref(), &key, position) {
+                warn!("Failed to persist {} position: {}", position.coin, e);
+            }
+        }
+    }
+
+    /// Remember the trade ids of the last `capacity` fills for deduplication
+    ///
+    /// A redelivered fill older than that is applied again.
+    pub fn with_seen_fills_capacity(self, capacity: usize) -> Self {
+        self.lock().seen_fills = SeenFills::new(capacity);
+        self
+    }
+
+    /// Absolute difference in size or entry price tolerated by reconciliation
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Receive divergence alerts raised by reconciliation
+    pub fn alerts(&self) -> broadcast::Receiver<Divergence> {
+        self.alerts.subscribe()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Apply a single execution
+    pub fn apply_fill(&self, coin: &str, is_buy: bool, px: f64, sz: f64, fee: f64) {
+        let position = {
+            let mut state = self.lock();
+            let position = state
+                .positions
+                .entry(coin.to_string())
+                .or_insert_with(|| Position::new(coin));
+            position.apply_fill(is_buy, px, sz, fee);
+            position.clone()
+        };
+        self.persist(&[position]);
+    }
+
+    /// Record a funding payment (`usdc` positive when received)
+    pub fn apply_funding(&self, coin: &str, usdc: f64) {
+        let position = {
+            let mut state = self.lock();
+            let position = state
+                .positions
+                .entry(coin.to_string())
+                .or_insert_with(|| Position::new(coin));
+            position.cumulative_funding += usdc;
+            position.clone()
+        };
+        self.persist(&[position]);
+    }
+
+    /// Set the mark price used for unrealized PnL
+    pub fn update_mark(&self, coin: &str, mark_px: f64) {
+        if let Some(position) = self.lock().positions.get_mut(coin) {
+            position.mark_px = Some(mark_px);
+        }
+    }
+
+    /// Apply a `data` payload from the `userFills` channel
+    ///
+    /// Fills are deduplicated by trade id among the last
+    /// [`DEFAULT_SEEN_FILLS_CAPACITY`] fills, or the capacity set with
+    /// [`with_seen_fills_capacity`](Self::with_seen_fills_capacity).
+    pub fn handle_user_fills(&self, data: &Value) {
+        let message: WsUserFills = match serde_json::from_value(data.clone()) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Failed to parse userFills message: {}", e);
+                return;
+            }
+        };
+        if message.is_snapshot {
+            return;
+        }
+
+        for fill in message.fills {
+            if let Some(tid) = fill.tid {
+                if !self.lock().seen_fills.insert(tid) {
+                    continue;
+                }
+            }
+            let (Ok(px), Ok(sz)) = (fill.px.parse::<f64>(), fill.sz.parse::<f64>()) else {
+                warn!("Ignoring fill with malformed px/sz for {}", fill.coin);
+                continue;
+            };
+            let fee = fill
+                .fee
+                .as_deref()
+                .and_then(|fee| fee.parse().ok())
+                .unwrap_or(0.0);
+            self.apply_fill(&fill.coin, fill.side == "B", px, sz, fee);
+        }
+    }
+
+    /// Apply a `data` payload from the `userFundings` channel
+    pub fn handle_user_fundings(&self, data: &Value) {
+        let message: WsUserFundings = match serde_json::from_value(data.clone()) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Failed to parse userFundings message: {}", e);
+                return;
+            }
+        };
+        if message.is_snapshot {
+            return;
+        }
+
+        for funding in message.fundings {
+            match funding.usdc.parse::<f64>() {
+                Ok(usdc) => self.apply_funding(&funding.coin, usdc),
+                Err(_) => warn!("Ignoring malformed funding for {}", funding.coin),
+            }
+        }
+    }
+
+    /// Subscribe `ws` to `user`'s fills and funding payments and feed them in
+    ///
+    /// Registers the handlers for the `userFills` and `userFundings`
+    /// subscriptions, replacing any existing handlers for them.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient, user: &str) -> Result<(), HyperliquidError> {
+        for channel in ["userFills", "userFundings"] {
+            let subscription: Subscription =
+                serde_json::from_value(json!({"type": channel, "user": user}))?;
+            let tracker = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if !response.channel.starts_with(channel) {
+                    return;
+                }
+                if channel == "userFills" {
+                    tracker.handle_user_fills(&response.data);
+                } else {
+                    tracker.handle_user_fundings(&response.data);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Sizes and entry prices from a `clearinghouseState` response
+    fn exchange_positions(state: &Value) -> HashMap<String, (f64, f64)> {
+        let parse = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        state
+            .get("assetPositions")
+            .and_then(Value::as_array)
+            .map(|positions| {
+                positions
+                    .iter()
+                    .filter_map(|entry| {
+                        let position = entry.get("position")?;
+                        let coin = position.get("coin")?.as_str()?;
+                        Some((
+                            coin.to_string(),
+                            (parse(position.get("szi")), parse(position.get("entryPx"))),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Overwrite sizes and entry prices with a `clearinghouseState` response
+    ///
+    /// Realized PnL, fees and funding are kept. Use this to seed the tracker
+    /// at startup or when polling instead of streaming.
+    pub fn sync_from_state(&self, state: &Value) {
+        let exchange = Self::exchange_positions(state);
+        let mut local = self.lock();
+        for position in local.positions.values_mut() {
+            if !exchange.contains_key(&position.coin) {
+                position.szi = 0.0;
+                position.entry_px = 0.0;
+            }
+        }
+        for (coin, (szi, entry_px)) in exchange {
+            let position = local
+                .positions
+                .entry(coin.clone())
+                .or_insert_with(|| Position::new(&coin));
+            position.szi = szi;
+            position.entry_px = entry_px;
+        }
+        let positions: Vec<Position> = local.positions.values().cloned().collect();
+        drop(local);
+        self.persist(&positions);
+    }
+
+    /// Compare local positions with a `clearinghouseState` response
+    ///
+    /// Returns the coins that disagree beyond the tolerance and publishes each
+    /// on [`alerts`](Self::alerts). Local state is left unchanged.
+    pub fn reconcile_state(&self, state: &Value) -> Vec<Divergence> {
+        let exchange = Self::exchange_positions(state);
+        let local = self.lock();
+
+        let mut coins: Vec<&String> = exchange.keys().chain(local.positions.keys()).collect();
+        coins.sort();
+        coins.dedup();
+
+        let divergences: Vec<Divergence> = coins
+            .into_iter()
+            .filter_map(|coin| {
+                let (local_szi, local_entry_px) = local
+                    .positions
+                    .get(coin)
+                    .map(|p| (p.szi, p.entry_px))
+                    .unwrap_or_default();
+                let (exchange_szi, exchange_entry_px) =
+                    exchange.get(coin).copied().unwrap_or_default();
+                let size_differs = (local_szi - exchange_szi).abs() > self.tolerance;
+                // Entry prices only matter while a position is open
+                let entry_differs = exchange_szi.abs() > self.tolerance
+                    && (local_entry_px - exchange_entry_px).abs()
+                        > self.tolerance * exchange_entry_px.abs().max(1.0);
+                (size_differs || entry_differs).then(|| Divergence {
+                    coin: coin.clone(),
+                    local_szi,
+                    exchange_szi,
+                    local_entry_px,
+                    exchange_entry_px,
+                })
+            })
+            .collect();
+        drop(local);
+
+        for divergence in &divergences {
+            warn!(
+                "Position divergence for {}: local {} @ {}, exchange {} @ {}",
+                divergence.coin,
+                divergence.local_szi,
+                divergence.local_entry_px,
+                divergence.exchange_szi,
+                divergence.exchange_entry_px
+            );
+            let _ = self.alerts.send(divergence.clone());
+        }
+        divergences
+    }
+
+    /// Fetch `user`'s `clearinghouseState` and reconcile against it
+    pub async fn reconcile(
+        &self,
+        client: &HttpClient,
+        user: &str,
+    ) -> Result<Vec<Divergence>, HyperliquidError> {
+        let state: Value = client
+            .post(
+                "/info",
+                &json!({"type": "clearinghouseState", "user": user}),
+            )
+            .await?;
+        Ok(self.reconcile_state(&state))
+    }
+
+    /// Snapshot of the position in `coin`
+    pub fn position(&self, coin: &str) -> Option<Position> {
+        self.lock().positions.get(coin).cloned()
+    }
+
+    /// Snapshot of every coin traded, sorted by coin
+    pub fn positions(&self) -> Vec<Position> {
+        let mut positions: Vec<Position> = self.lock().positions.values().cloned().collect();
+        positions.sort_by(|a, b| a.coin.cmp(&b.coin));
+        positions
+    }
+
+    /// Realized PnL across coins, net of fees and including funding
+    pub fn total_net_realized_pnl(&self) -> f64 {
+        self.lock()
+            .positions
+            .values()
+            .map(Position::net_realized_pnl)
+            .sum()
+    }
+
+    /// Unrealized PnL across coins with a known mark price
+    pub fn total_unrealized_pnl(&self) -> f64 {
+        self.lock()
+            .positions
+            .values()
+            .filter_map(Position::unrealized_pnl)
+            .sum()
+    }
+}
@@ -0,0 +1,281 @@
+//! Prometheus metrics endpoint
+//!
+//! The SDK records HTTP, WebSocket, exchange and runtime metrics through the
+//! `metrics` facade under the `hyperliquid_` prefix. This module installs a
+//! Prometheus recorder for the facade and serves the rendered metrics over a
+//! small hyper server at the configured path, renaming the prefix to the
+//! configured namespace.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tracing::{debug, info};
+
+use crate::config::MetricsConfig;
+use crate::error::HyperliquidError;
+
+/// Prefix used by every metric the SDK records
+const METRIC_PREFIX: &str = "hyperliquid_";
+
+/// Prometheus text exposition content type
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Install the Prometheus recorder once per process
+fn recorder_handle() -> Result<PrometheusHandle, HyperliquidError> {
+    static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+    if let Some(handle) = HANDLE.get() {
+        return Ok(handle.clone());
+    }
+    let handle = PrometheusBuilder::new().install_recorder().map_err(|e| {
+        HyperliquidError::Config(format!("failed to install Prometheus recorder: {}", e))
+    })?;
+    Ok(HANDLE.get_or_init(|| handle).clone())
+}
+
+/// Renders recorded metrics in the Prometheus text format
+#[derive(Clone)]
+pub struct PrometheusExporter {
+    handle: PrometheusHandle,
+    path: Arc<str>,
+    namespace: Arc<str>,
+}
+
+impl PrometheusExporter {
+    /// Install the Prometheus recorder as the global `metrics` recorder
+    ///
+    /// Fails if a different recorder is already installed.
+    pub fn install(config: &MetricsConfig) -> Result<Self, HyperliquidError> {
+        Ok(Self {
+            handle: recorder_handle()?,
+            path: config.endpoint.as_str().into(),
+            namespace: config.namespace.as_str().into(),
+        })
+    }
+
+    /// Render all metrics with the configured namespace
+    pub fn render(&self) -> String {
+        self.handle.run_upkeep();
+        apply_namespace(&self.handle.render(), &self.namespace)
+    }
+
+    /// Path metrics are served at
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Bind `addr` and serve metrics until the returned server is shut down
+    pub async fn serve(self, addr: SocketAddr) -> Result<PrometheusServer, HyperliquidError> {
+        let listener = TcpListener::bind(addr).await.map_err(|e| {
+            HyperliquidError::Config(format!("failed to bind metrics endpoint {}: {}", addr, e))
+        })?;
+        let local_addr = listener.local_addr().map_err(|e| {
+            HyperliquidError::Config(format!("failed to read metrics endpoint address: {}", e))
+        })?;
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+        info!(addr = %local_addr, path = %self.path, "Serving Prometheus metrics");
+
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            debug!("Metrics endpoint accept failed: {}", e);
+                            continue;
+                        }
+                    },
+                    _ = &mut shutdown_rx => break,
+                };
+
+                let exporter = self.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| {
+                        let response = exporter.respond(&request);
+                        async move { Ok::<_, Infallible>(response) }
+                    });
+                    if let Err(e) = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await
+                    {
+                        debug!("Metrics connection error: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(PrometheusServer {
+            local_addr,
+            shutdown_tx: Some(shutdown_tx),
+            task,
+        })
+    }
+
+    fn respond(&self, request: &Request<Incoming>) -> Response<Full<Bytes>> {
+        let (status, body) = if request.uri().path() != &*self.path {
+            (StatusCode::NOT_FOUND, String::new())
+        } else if request.method() != Method::GET {
+            (StatusCode::METHOD_NOT_ALLOWED, String::new())
+        } else {
+            (StatusCode::OK, self.render())
+        };
+
+        let mut response = Response::new(Full::new(Bytes::from(body)));
+        *response.status_mut() = status;
+        response.headers_mut().insert(
+            hyper::header::CONTENT_TYPE,
+            hyper::header::HeaderValue::from_static(CONTENT_TYPE),
+        );
+        response
+    }
+}
+
+impl std::fmt::Debug for PrometheusExporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrometheusExporter")
+            .field("path", &self.path)
+            .field("namespace", &self.namespace)
+            .finish()
+    }
+}
+
+/// Running metrics endpoint
+#[derive(Debug)]
+pub struct PrometheusServer {
+    local_addr: SocketAddr,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl PrometheusServer {
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stop accepting connections and wait for the accept loop to exit
+    pub async fn shutdown(mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+/// Start the Prometheus endpoint if enabled in `config`
+///
+/// Returns `None` when metrics or Prometheus export are disabled.
+pub async fn start_prometheus_exporter(
+    config: &MetricsConfig,
+) -> Result<Option<PrometheusServer>, HyperliquidError> {
+    if !config.enabled || !config.enable_prometheus {
+        return Ok(None);
+    }
+
+    let addr: SocketAddr = config.listen_addr.parse().map_err(|e| {
+        HyperliquidError::Config(format!(
+            "invalid metrics listen address {}: {}",
+            config.listen_addr, e
+        ))
+    })?;
+
+    let server = PrometheusExporter::install(config)?.serve(addr).await?;
+    Ok(Some(server))
+}
+
+/// Replace the `hyperliquid_` prefix of metric names with `namespace`
+fn apply_namespace(rendered: &str, namespace: &str) -> String {
+    if namespace == METRIC_PREFIX.trim_end_matches('_') {
+        return rendered.to_string();
+    }
+
+    let replacement = if namespace.is_empty() {
+        String::new()
+    } else {
+        format!("{}_", namespace)
+    };
+    let rename = |name: &str| match name.strip_prefix(METRIC_PREFIX) {
+        Some(rest) => format!("{}{}", replacement, rest),
+        None => name.to_string(),
+    };
+
+    let mut output = String::with_capacity(rendered.len());
+    for line in rendered.lines() {
+        if let Some(comment) = line.strip_prefix("# ") {
+            // "# HELP name ..." / "# TYPE name ..."
+            let mut parts = comment.splitn(3, ' ');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(kind), Some(name), rest) => {
+                    output.push_str("# ");
+                    output.push_str(kind);
+                    output.push(' ');
+                    output.push_str(&rename(name));
+                    if let Some(rest) = rest {
+                        output.push(' ');
+                        output.push_str(rest);
+                    }
+                }
+                _ => output.push_str(line),
+            }
+        } else {
+            output.push_str(&rename(line));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_namespace() {
+        let rendered = "# TYPE hyperliquid_http_requests_total counter\n\
+                        hyperliquid_http_requests_total{method=\"POST\"} 3\n\
+                        other_metric 1\n";
+
+        let renamed = apply_namespace(rendered, "trading");
+        assert_eq!(
+            renamed,
+            "# TYPE trading_http_requests_total counter\n\
+             trading_http_requests_total{method=\"POST\"} 3\n\
+             other_metric 1\n"
+        );
+
+        assert_eq!(apply_namespace(rendered, "hyperliquid"), rendered);
+        assert!(apply_namespace(rendered, "").starts_with("# TYPE http_requests_total"));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_exporter_is_not_started() {
+        let config = MetricsConfig {
+            enable_prometheus: false,
+            ..Default::default()
+        };
+        assert!(start_prometheus_exporter(&config).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_listen_addr() {
+        let config = MetricsConfig {
+            listen_addr: "not-an-address".to_string(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            start_prometheus_exporter(&config).await,
+            Err(HyperliquidError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,571 @@
+//! Throttled two-sided quoting
+//!
+//! A [`Quoter`] takes the quotes a strategy wants resting, per coin and side,
+//! and keeps the book in line with them using as few actions as possible:
+//!
+//! - a side whose price and size are unchanged, or whose price moved less
+//!   than the tolerance, is left alone;
+//! - a resting order is moved with a modify rather than a cancel and place;
+//! - updates arriving faster than actions can be sent are coalesced, so only
+//!   the latest target of each side is ever acted on.
+//!
+//! Actions are sent by [`Quoter::flush`], at most `max_actions_per_sec` per
+//! second, cancels first. Whatever does not fit waits for the next flush;
+//! [`Quoter::start`] flushes on an interval.
+//!
+//! ```no_run
+//! # async fn example(venue: hyperliquid_core::execution::ExchangeVenue) {
+//! use std::time::Duration;
+//! use hyperliquid_core::quoter::{QuoteLevel, Quoter};
+//!
+//! let quoter = Quoter::new(venue, 5).with_price_tolerance_bps(1.0);
+//! let _job = quoter.start(Duration::from_millis(100));
+//! quoter.set_quote(
+//!     "BTC",
+//!     Some(QuoteLevel::new(64_990.0, 0.1)),
+//!     Some(QuoteLevel::new(65_010.0, 0.1)),
+//! );
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+#[cfg(feature = "exchange-signing")]
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+#[cfg(feature = "exchange-signing")]
+use crate::execution::{round_px, round_sz, ExchangeVenue};
+#[cfg(feature = "exchange-signing")]
+use crate::types::precision::{PrecisionError, WireFormat};
+
+/// Sizes closer than this are equal
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Price and size to rest on one side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteLevel {
+    pub px: f64,
+    pub sz: f64,
+}
+
+impl QuoteLevel {
+    pub fn new(px: f64, sz: f64) -> Self {
+        Self { px, sz }
+    }
+}
+
+/// Order the quoter has resting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveQuote {
+    pub oid: u64,
+    pub px: f64,
+    pub sz: f64,
+}
+
+/// Action needed to bring one side to its target
+#[derive(Debug, Clone, PartialEq)]
+pub enum QuoteAction {
+    Place {
+        coin: String,
+        is_buy: bool,
+        px: f64,
+        sz: f64,
+    },
+    Modify {
+        coin: String,
+        oid: u64,
+        is_buy: bool,
+        px: f64,
+        sz: f64,
+    },
+    Cancel {
+        coin: String,
+        oid: u64,
+        is_buy: bool,
+    },
+}
+
+impl QuoteAction {
+    pub fn coin(&self) -> &str {
+        match self {
+            QuoteAction::Place { coin, .. }
+            | QuoteAction::Modify { coin, .. }
+            | QuoteAction::Cancel { coin, .. } => coin,
+        }
+    }
+
+    pub fn is_buy(&self) -> bool {
+        match self {
+            QuoteAction::Place { is_buy, .. }
+            | QuoteAction::Modify { is_buy, .. }
+            | QuoteAction::Cancel { is_buy, .. } => *is_buy,
+        }
+    }
+
+    /// Send order: cancels, then modifies, then places
+    fn priority(&self) -> u8 {
+        match self {
+            QuoteAction::Cancel { .. } => 0,
+            QuoteAction::Modify { .. } => 1,
+            QuoteAction::Place { .. } => 2,
+        }
+    }
+}
+
+/// Outcome of one [`QuoteAction`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionResult {
+    /// Placed or modified order resting under `oid`
+    Resting {
+        oid: u64,
+    },
+    /// Order filled on arrival, or canceled
+    Done,
+    Rejected(String),
+}
+
+/// Where quote actions are sent
+pub trait QuoteVenue: Send + Sync + 'static {
+    /// Send `actions`, returning one result per action in the same order
+    fn submit(
+        &self,
+        actions: &[QuoteAction],
+    ) -> impl Future<Output = Result<Vec<ActionResult>, HyperliquidError>> + Send;
+}
+
+#[cfg(feature = "exchange-signing")]
+fn wire(value: Result<String, PrecisionError>) -> Result<String, HyperliquidError> {
+    value.map_err(|e| HyperliquidError::Validation(e.to_string()))
+}
+
+/// Result of each entry of `response.data.statuses`, or of every entry
+/// when the whole request was rejected
+#[cfg(feature = "exchange-signing")]
+fn statuses(response: &Value, count: usize) -> Vec<ActionResult> {
+    if response.get("status").and_then(Value::as_str) != Some("ok") {
+        let reason = response
+            .get("response")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return vec![ActionResult::Rejected(reason.to_string()); count];
+    }
+    let statuses = response
+        .pointer("/response/data/statuses")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    (0..count)
+        .map(|index| match statuses.get(index) {
+            Some(Value::Object(status)) => {
+                if let Some(error) = status.get("error").and_then(Value::as_str) {
+                    ActionResult::Rejected(error.to_string())
+                } else if let Some(oid) = status
+                    .get("resting")
+                    .and_then(|resting| resting.get("oid"))
+                    .and_then(Value::as_u64)
+                {
+                    ActionResult::Resting { oid }
+                } else {
+                    ActionResult::Done
+                }
+            }
+            // Cancels and modifies answer "success"
+            Some(_) => ActionResult::Done,
+            None => ActionResult::Rejected("missing status".to_string()),
+        })
+        .collect()
+}
+
+/// Quotes are post-only (`Alo`); the venue sends at most one cancel, one
+/// `batchModify` and one order request per call.
+#[cfg(feature = "exchange-signing")]
+impl QuoteVenue for ExchangeVenue {
+    async fn submit(&self, actions: &[QuoteAction]) -> Result<Vec<ActionResult>, HyperliquidError> {
+        let mut results = vec![ActionResult::Rejected("not sent".to_string()); actions.len()];
+        let mut cancels = (Vec::new(), Vec::new());
+        let mut modifies = (Vec::new(), Vec::new());
+        let mut places = (Vec::new(), Vec::new());
+
+        for (index, action) in actions.iter().enumerate() {
+            let (asset, sz_decimals) = match self.asset(action.coin()) {
+                Ok(asset) => asset,
+                Err(e) => {
+                    results[index] = ActionResult::Rejected(e.to_string());
+                    continue;
+                }
+            };
+            let format = WireFormat::perp(sz_decimals);
+            let order = |is_buy: bool, px: f64, sz: f64| -> Result<Value, HyperliquidError> {
+                Ok(json!({
+                    "a": asset,
+                    "b": is_buy,
+                    "p": wire(format.price_f64(round_px(px, sz_decimals)))?,
+                    "s": wire(format.size_f64(round_sz(sz, sz_decimals)))?,
+                    "r": false,
+                    "t": {"limit": {"tif": "Alo"}},
+                }))
+            };
+            match action {
+                QuoteAction::Cancel { oid, .. } => {
+                    cancels.0.push(index);
+                    cancels.1.push(json!({"a": asset, "o": oid}));
+                }
+                QuoteAction::Modify {
+                    oid,
+                    is_buy,
+                    px,
+                    sz,
+                    ..
+                } => {
+                    modifies.0.push(index);
+                    modifies
+                        .1
+                        .push(json!({"oid": oid, "order": order(*is_buy, *px, *sz)?}));
+                }
+                QuoteAction::Place { is_buy, px, sz, .. } => {
+                    places.0.push(index);
+                    places.1.push(order(*is_buy, *px, *sz)?);
+                }
+            }
+        }
+
+        let requests: [(_, fn(Value) -> Value); 3] = [
+            (
+                cancels,
+                |entries| json!({"type": "cancel", "cancels": entries}),
+            ),
+            (
+                modifies,
+                |entries| json!({"type": "batchModify", "modifies": entries}),
+            ),
+            (
+                places,
+                |entries| json!({"type": "order", "orders": entries, "grouping": "na"}),
+            ),
+        ];
+        for ((indices, entries), action) in requests {
+            if indices.is_empty() {
+                continue;
+            }
+            let response = self.post_action(action(Value::Array(entries))).await?;
+            let statuses = statuses(&response, indices.len());
+            for (index, result) in indices.into_iter().zip(statuses) {
+                results[index] = result;
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Counters since the quoter was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuoterStats {
+    /// Calls to [`Quoter::set_quote`] that changed a target
+    pub updates: u64,
+    /// Targets replaced before they were acted on
+    pub coalesced: u64,
+    pub actions_sent: u64,
+    pub rejected: u64,
+}
+
+#[derive(Debug, Default)]
+struct Side {
+    target: Option<QuoteLevel>,
+    live: Option<LiveQuote>,
+    /// Target changed since the last flush that acted on this side
+    dirty: bool,
+    /// The last attempt at the target was rejected; wait for a new target
+    settled: bool,
+}
+
+struct Throttle {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Throttle {
+    fn take_available(&mut self) -> usize {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.updated = now;
+        self.tokens.floor() as usize
+    }
+}
+
+struct State {
+    /// Bid and ask by coin
+    sides: BTreeMap<String, [Side; 2]>,
+    throttle: Throttle,
+    stats: QuoterStats,
+}
+
+fn side_index(is_buy: bool) -> usize {
+    if is_buy {
+        0
+    } else {
+        1
+    }
+}
+
+/// Coalesces quote updates into throttled order actions
+pub struct Quoter<V> {
+    venue: Arc<V>,
+    state: Arc<Mutex<State>>,
+    price_tolerance: f64,
+    /// Serializes flushes so results are applied in the order actions were sent
+    flushing: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<V> Clone for Quoter<V> {
+    fn clone(&self) -> Self {
+        Self {
+            venue: Arc::clone(&self.venue),
+            state: Arc::clone(&self.state),
+            price_tolerance: self.price_tolerance,
+            flushing: Arc::clone(&self.flushing),
+        }
+    }
+}
+
+impl<V> std::fmt::Debug for Quoter<V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("Quoter")
+            .field("coins", &state.sides.keys().collect::<Vec<_>>())
+            .field("max_actions_per_sec", &state.throttle.rate)
+            .field("price_tolerance", &self.price_tolerance)
+            .field("stats", &state.stats)
+            .finish()
+    }
+}
+
+impl<V> Quoter<V> {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<V: QuoteVenue> Quoter<V> {
+    /// Quote through `venue`, sending at most `max_actions_per_sec` actions a second
+    pub fn new(venue: V, max_actions_per_sec: u32) -> Self {
+        let rate = f64::from(max_actions_per_sec.max(1));
+        Self {
+            venue: Arc::new(venue),
+            state: Arc::new(Mutex::new(State {
+                sides: BTreeMap::new(),
+                throttle: Throttle {
+                    rate,
+                    tokens: rate,
+                    updated: Instant::now(),
+                },
+                stats: QuoterStats::default(),
+            })),
+            price_tolerance: 0.0,
+            flushing: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Leave a resting quote alone while its price is within `bps` of the target
+    ///
+    /// Size changes always move the order.
+    pub fn with_price_tolerance_bps(mut self, bps: f64) -> Self {
+        self.price_tolerance = bps.max(0.0) / 10_000.0;
+        self
+    }
+
+    /// Set the quotes wanted on `coin`; `None` pulls that side
+    pub fn set_quote(&self, coin: &str, bid: Option<QuoteLevel>, ask: Option<QuoteLevel>) {
+        let mut state = self.lock();
+        let State { sides, stats, .. } = &mut *state;
+        let entry = sides.entry(coin.to_string()).or_default();
+        for (side, target) in entry.iter_mut().zip([bid, ask]) {
+            // Non-positive sizes mean no quote
+            let target = target.filter(|level| level.sz > SIZE_EPSILON && level.px > 0.0);
+            if side.target == target {
+                continue;
+            }
+            stats.updates += 1;
+            if side.dirty {
+                stats.coalesced += 1;
+            }
+            side.target = target;
+            side.dirty = true;
+            side.settled = false;
+        }
+    }
+
+    /// Pull both sides of `coin`
+    pub fn pull(&self, coin: &str) {
+        self.set_quote(coin, None, None);
+    }
+
+    /// Pull every quote
+    pub fn pull_all(&self) {
+        let coins: Vec<String> = self.lock().sides.keys().cloned().collect();
+        for coin in coins {
+            self.pull(&coin);
+        }
+    }
+
+    /// Forget a resting quote that filled or was canceled elsewhere
+    ///
+    /// Feed fills and cancels of the quoter's orders here, e.g. from an
+    /// [`OrderManager`](crate::oms::OrderManager); the next flush places a
+    /// fresh order if the side still has a target.
+    pub fn order_done(&self, oid: u64) {
+        let mut state = self.lock();
+        for side in state.sides.values_mut().flatten() {
+            if side.live.is_some_and(|live| live.oid == oid) {
+                side.live = None;
+                side.dirty = true;
+                side.settled = false;
+            }
+        }
+    }
+
+    /// Resting quote on one side of `coin`
+    pub fn live(&self, coin: &str, is_buy: bool) -> Option<LiveQuote> {
+        self.lock()
+            .sides
+            .get(coin)
+            .and_then(|sides| sides[side_index(is_buy)].live)
+    }
+
+    pub fn stats(&self) -> QuoterStats {
+        self.lock().stats
+    }
+
+    /// Actions that would bring every side to its target, cancels first
+    pub fn plan(&self) -> Vec<QuoteAction> {
+        let state = self.lock();
+        let mut actions: Vec<QuoteAction> = state
+            .sides
+            .iter()
+            .flat_map(|(coin, sides)| {
+                sides
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(index, side)| self.side_action(coin, index == 0, side))
+            })
+            .collect();
+        actions.sort_by_key(QuoteAction::priority);
+        actions
+    }
+
+    fn side_action(&self, coin: &str, is_buy: bool, side: &Side) -> Option<QuoteAction> {
+        let coin = coin.to_string();
+        match (side.target, side.live) {
+            (None, Some(live)) => Some(QuoteAction::Cancel {
+                coin,
+                oid: live.oid,
+                is_buy,
+            }),
+            (Some(_), _) if side.settled => None,
+            (Some(target), None) => Some(QuoteAction::Place {
+                coin,
+                is_buy,
+                px: target.px,
+                sz: target.sz,
+            }),
+            (Some(target), Some(live)) => {
+                let moved = (target.px - live.px).abs() > live.px * self.price_tolerance;
+                let resized = (target.sz - live.sz).abs() > SIZE_EPSILON;
+                (moved || resized).then_some(QuoteAction::Modify {
+                    coin,
+                    oid: live.oid,
+                    is_buy,
+                    px: target.px,
+                    sz: target.sz,
+                })
+            }
+            (None, None) => None,
+        }
+    }
+
+    /// Send as many planned actions as the rate allows
+    ///
+    /// Returns the number of actions sent. On `Err` the venue could not be
+    /// reached and the book is left as it was last known.
+    pub async fn flush(&self) -> Result<usize, HyperliquidError> {
+        let _flushing = self.flushing.lock().await;
+        let mut actions = self.plan();
+        {
+            let mut state = self.lock();
+            let available = state.throttle.take_available();
+            let deferred = actions.split_off(available.min(actions.len()));
+            state.throttle.tokens -= actions.len() as f64;
+            state.stats.actions_sent += actions.len() as u64;
+            // Every target is acted on or needs nothing, except the deferred
+            for side in state.sides.values_mut().flatten() {
+                side.dirty = false;
+            }
+            for action in &deferred {
+                if let Some(sides) = state.sides.get_mut(action.coin()) {
+                    sides[side_index(action.is_buy())].dirty = true;
+                }
+            }
+        }
+        if actions.is_empty() {
+            return Ok(0);
+        }
+
+        let results = self.venue.submit(&actions).await?;
+        let mut state = self.lock();
+        for (action, result) in actions.iter().zip(results) {
+            let Some(sides) = state.sides.get_mut(action.coin()) else {
+                continue;
+            };
+            let side = &mut sides[side_index(action.is_buy())];
+            let rejected = matches!(result, ActionResult::Rejected(_));
+            if let ActionResult::Rejected(reason) = &result {
+                warn!("Quote action on {} rejected: {}", action.coin(), reason);
+            }
+            match (action, result) {
+                (QuoteAction::Cancel { .. }, _) => side.live = None,
+                (
+                    QuoteAction::Place { px, sz, .. } | QuoteAction::Modify { px, sz, .. },
+                    ActionResult::Resting { oid },
+                ) => {
+                    side.live = Some(LiveQuote {
+                        oid,
+                        px: *px,
+                        sz: *sz,
+                    })
+                }
+                (QuoteAction::Place { .. }, ActionResult::Done) => side.live = None,
+                // A modified order that fills on arrival is gone
+                (QuoteAction::Modify { .. }, ActionResult::Done) => side.live = None,
+                // A rejected modify leaves the original resting
+                (_, ActionResult::Rejected(_)) => side.settled = true,
+            }
+            if rejected {
+                state.stats.rejected += 1;
+            }
+        }
+        Ok(actions.len())
+    }
+
+    /// Flush every `period` in the background until the handle is aborted
+    pub fn start(&self, period: Duration) -> JoinHandle<()> {
+        let quoter = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = quoter.flush().await {
+                    warn!("Quote flush failed: {}", e);
+                }
+            }
+        })
+    }
+}
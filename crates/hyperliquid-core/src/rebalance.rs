@@ -0,0 +1,319 @@
+//! Target-weight portfolio rebalancing
+//!
+//! A [`Rebalancer`] holds a target weight per coin (signed notional as a
+//! fraction of account equity; negative is short). Given the current
+//! [`Holdings`] and prices it plans the orders that bring every coin whose
+//! weight has drifted outside its tolerance band back to target, leaving
+//! coins inside their band alone. Held coins without a target are closed.
+//!
+//! A plan can be inspected on its own or worked through the [`execution`]
+//! module: each leg becomes a TWAP [`ParentOrder`] on that coin's
+//! [`Executor`], with reducing legs worked before the ones adding exposure.
+//!
+//! ```no_run
+//! # async fn example(state: hyperliquid_core::types::ClearinghouseState, prices: std::collections::HashMap<String, f64>, executors: std::collections::HashMap<String, hyperliquid_core::execution::Executor<hyperliquid_core::execution::ExchangeVenue>>) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::rebalance::{Holdings, Rebalancer};
+//!
+//! let rebalancer = Rebalancer::new([("BTC", 0.5), ("ETH", 0.3)]).with_tolerance(0.02);
+//! let plan = rebalancer.plan(&Holdings::from(&state), &prices)?;
+//! for leg in &plan.legs {
+//!     println!("{} {} {}", if leg.is_buy { "buy" } else { "sell" }, leg.sz, leg.coin);
+//! }
+//! let reports = rebalancer.execute(&plan, &executors).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! [`execution`]: crate::execution
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::time::Duration;
+
+use crate::error::HyperliquidError;
+use crate::execution::{ExecutionReport, Executor, ParentOrder, Venue};
+use crate::types::ClearinghouseState;
+
+/// Default drift from target, in weight, before a coin is traded
+const DEFAULT_TOLERANCE: f64 = 0.01;
+
+/// Smallest order value the exchange accepts
+const DEFAULT_MIN_NOTIONAL: f64 = 10.0;
+
+const DEFAULT_DURATION: Duration = Duration::from_secs(60);
+const DEFAULT_SLICES: u32 = 6;
+const DEFAULT_MAX_SLIPPAGE: f64 = 0.01;
+
+/// Account equity and signed position sizes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Holdings {
+    pub equity: f64,
+    /// Signed size per coin; positive is long
+    pub positions: HashMap<String, f64>,
+}
+
+impl Holdings {
+    pub fn new(equity: f64) -> Self {
+        Self {
+            equity,
+            positions: HashMap::new(),
+        }
+    }
+
+    pub fn with_position(mut self, coin: impl Into<String>, szi: f64) -> Self {
+        self.positions.insert(coin.into(), szi);
+        self
+    }
+}
+
+impl From<&ClearinghouseState> for Holdings {
+    fn from(state: &ClearinghouseState) -> Self {
+        Self {
+            equity: state.margin_summary.accountValue.parse().unwrap_or(0.0),
+            positions: state
+                .asset_positions
+                .iter()
+                .map(|entry| &entry.position)
+                .filter(|position| position.szi() != 0.0)
+                .map(|position| (position.coin.clone(), position.szi()))
+                .collect(),
+        }
+    }
+}
+
+/// Order bringing one coin back to its target
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalanceLeg {
+    pub coin: String,
+    pub is_buy: bool,
+    /// Unsigned size to trade
+    pub sz: f64,
+    /// Price the leg was sized at
+    pub px: f64,
+    pub current_weight: f64,
+    pub target_weight: f64,
+}
+
+impl RebalanceLeg {
+    pub fn notional(&self) -> f64 {
+        self.sz * self.px
+    }
+
+    /// Whether the leg shrinks the position rather than adding to it
+    pub fn is_reducing(&self) -> bool {
+        self.target_weight.abs() < self.current_weight.abs()
+            && self.target_weight * self.current_weight >= 0.0
+    }
+}
+
+/// Orders needed to reach the targets
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebalancePlan {
+    pub equity: f64,
+    /// Reducing legs first, then by coin
+    pub legs: Vec<RebalanceLeg>,
+}
+
+impl RebalancePlan {
+    pub fn is_empty(&self) -> bool {
+        self.legs.is_empty()
+    }
+
+    /// Total value traded
+    pub fn turnover(&self) -> f64 {
+        self.legs.iter().map(RebalanceLeg::notional).sum()
+    }
+}
+
+/// Plans and executes moves to target weights
+#[derive(Debug, Clone)]
+pub struct Rebalancer {
+    targets: BTreeMap<String, f64>,
+    tolerance: f64,
+    bands: HashMap<String, f64>,
+    min_notional: f64,
+    duration: Duration,
+    slices: u32,
+    max_slippage: f64,
+}
+
+impl Rebalancer {
+    pub fn new<S: Into<String>>(targets: impl IntoIterator<Item = (S, f64)>) -> Self {
+        Self {
+            targets: targets
+                .into_iter()
+                .map(|(coin, weight)| (coin.into(), weight))
+                .collect(),
+            tolerance: DEFAULT_TOLERANCE,
+            bands: HashMap::new(),
+            min_notional: DEFAULT_MIN_NOTIONAL,
+            duration: DEFAULT_DURATION,
+            slices: DEFAULT_SLICES,
+            max_slippage: DEFAULT_MAX_SLIPPAGE,
+        }
+    }
+
+    /// Drift from target, in weight, tolerated before trading a coin
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Tolerance for one coin, overriding [`with_tolerance`](Self::with_tolerance)
+    pub fn with_band(mut self, coin: impl Into<String>, tolerance: f64) -> Self {
+        self.bands.insert(coin.into(), tolerance);
+        self
+    }
+
+    /// Legs worth less than this are dropped
+    pub fn with_min_notional(mut self, min_notional: f64) -> Self {
+        self.min_notional = min_notional;
+        self
+    }
+
+    /// TWAP schedule each leg is worked over
+    pub fn with_schedule(mut self, duration: Duration, slices: u32) -> Self {
+        self.duration = duration;
+        self.slices = slices;
+        self
+    }
+
+    /// How far from the planned price a leg may fill, as a fraction
+    pub fn with_max_slippage(mut self, max_slippage: f64) -> Self {
+        self.max_slippage = max_slippage;
+        self
+    }
+
+    pub fn targets(&self) -> &BTreeMap<String, f64> {
+        &self.targets
+    }
+
+    fn validate(&self) -> Result<(), HyperliquidError> {
+        let invalid = |message: String| Err(HyperliquidError::Validation(message));
+        if let Some((coin, _)) = self.targets.iter().find(|(_, w)| !w.is_finite()) {
+            return invalid(format!("target weight of {} must be finite", coin));
+        }
+        if !(self.tolerance >= 0.0) || self.bands.values().any(|band| !(*band >= 0.0)) {
+            return invalid("tolerance must be non-negative".to_string());
+        }
+        if !(0.0..1.0).contains(&self.max_slippage) {
+            return invalid("max slippage must be in [0, 1)".to_string());
+        }
+        if self.slices == 0 {
+            return invalid("schedule needs at least one slice".to_string());
+        }
+        Ok(())
+    }
+
+    /// Legs that bring every out-of-band coin back to its target weight
+    ///
+    /// `prices` needs an entry for every coin held or with a non-zero target.
+    pub fn plan(
+        &self,
+        holdings: &Holdings,
+        prices: &HashMap<String, f64>,
+    ) -> Result<RebalancePlan, HyperliquidError> {
+        self.validate()?;
+        if !(holdings.equity > 0.0) {
+            return Err(HyperliquidError::Validation(
+                "account equity must be positive".to_string(),
+            ));
+        }
+
+        let coins: BTreeSet<&String> = self
+            .targets
+            .keys()
+            .chain(holdings.positions.keys())
+            .collect();
+
+        let mut legs = Vec::new();
+        for coin in coins {
+            let szi = holdings.positions.get(coin).copied().unwrap_or(0.0);
+            let target_weight = self.targets.get(coin).copied().unwrap_or(0.0);
+            if szi == 0.0 && target_weight == 0.0 {
+                continue;
+            }
+            let px = prices
+                .get(coin)
+                .copied()
+                .filter(|px| *px > 0.0)
+                .ok_or_else(|| HyperliquidError::Validation(format!("no price for {}", coin)))?;
+            let current_weight = szi * px / holdings.equity;
+            let tolerance = self.bands.get(coin).copied().unwrap_or(self.tolerance);
+            if (target_weight - current_weight).abs() <= tolerance {
+                continue;
+            }
+
+            // Close exactly rather than leaving dust from the price
+            let delta = if target_weight == 0.0 {
+                -szi
+            } else {
+                target_weight * holdings.equity / px - szi
+            };
+            if delta.abs() * px < self.min_notional {
+                continue;
+            }
+            legs.push(RebalanceLeg {
+                coin: coin.clone(),
+                is_buy: delta > 0.0,
+                sz: delta.abs(),
+                px,
+                current_weight,
+                target_weight,
+            });
+        }
+        // Stable, so coins stay sorted within each group
+        legs.sort_by_key(|leg| !leg.is_reducing());
+
+        Ok(RebalancePlan {
+            equity: holdings.equity,
+            legs,
+        })
+    }
+
+    /// TWAP parent order working `leg`
+    pub fn parent_order(&self, leg: &RebalanceLeg) -> ParentOrder {
+        let limit_px = if leg.is_buy {
+            leg.px * (1.0 + self.max_slippage)
+        } else {
+            leg.px * (1.0 - self.max_slippage)
+        };
+        ParentOrder::twap(
+            &leg.coin,
+            leg.is_buy,
+            leg.sz,
+            limit_px,
+            self.duration,
+            self.slices,
+        )
+    }
+
+    /// Work `plan` through the executor of each leg's coin
+    ///
+    /// Reducing legs run (concurrently) to completion before the rest start,
+    /// so freed margin is available to them. Returns one report per leg, in
+    /// plan order; a failed leg does not stop the others.
+    pub async fn execute<V: Venue>(
+        &self,
+        plan: &RebalancePlan,
+        executors: &HashMap<String, Executor<V>>,
+    ) -> Result<Vec<ExecutionReport>, HyperliquidError> {
+        let mut orders = Vec::with_capacity(plan.legs.len());
+        for leg in &plan.legs {
+            let executor = executors.get(&leg.coin).ok_or_else(|| {
+                HyperliquidError::Validation(format!("no executor for {}", leg.coin))
+            })?;
+            orders.push((executor, leg.is_reducing(), self.parent_order(leg)));
+        }
+
+        let mut reports = Vec::with_capacity(orders.len());
+        for reducing in [true, false] {
+            let mut handles = Vec::new();
+            for (executor, _, parent) in orders.iter().filter(|(_, r, _)| *r == reducing) {
+                handles.push(executor.start(parent.clone())?);
+            }
+            for handle in handles {
+                reports.push(handle.wait().await);
+            }
+        }
+        Ok(reports)
+    }
+}
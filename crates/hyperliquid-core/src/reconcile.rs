@@ -0,0 +1,391 @@
+//! Account reconciliation: fills vs ledger vs positions
+//!
+//! A [`Reconciler`] keeps a journal of the fills the application has
+//! processed and periodically checks it, and an optional
+//! [`PositionTracker`], against the exchange's record:
+//!
+//! - `userFillsByTime` for fills the journal never saw ([`ReconcileEvent::MissingFill`]),
+//!   fills it saw that the exchange doesn't report ([`ReconcileEvent::UnknownFill`])
+//!   and fills processed more than once ([`ReconcileEvent::DuplicateFill`]);
+//! - the sum of `closedPnl - fee` over those fills against the tracker's
+//!   realized PnL net of fees ([`ReconcileEvent::PnlDrift`]);
+//! - `clearinghouseState` against the tracker's positions ([`ReconcileEvent::PositionDrift`]);
+//! - `userNonFundingLedgerUpdates` for deposits, withdrawals and transfers
+//!   that change equity without a fill ([`ReconcileEvent::LedgerChange`]).
+//!
+//! Checks cover the window from the reconciler's start time; start it
+//! together with a freshly seeded tracker so both count PnL from the same
+//! point. Fills newer than the grace period are left for the next run since
+//! they may still be in flight on the stream.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient, tracker: hyperliquid_core::positions::PositionTracker) {
+//! use std::time::Duration;
+//! use hyperliquid_core::reconcile::Reconciler;
+//!
+//! let reconciler = Reconciler::new("0xabc").with_position_tracker(tracker);
+//! let mut events = reconciler.events();
+//! // Call reconciler.record_fills(&payload) wherever userFills payloads are handled
+//! let _job = reconciler.spawn(client, Duration::from_secs(60));
+//! while let Ok(event) = events.recv().await {
+//!     println!("{:?}", event);
+//! }
+//! # }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+use crate::positions::{Divergence, PositionTracker};
+use crate::types::UserLedgerUpdate;
+
+/// Capacity of the event channel
+const EVENT_CAPACITY: usize = 1024;
+
+/// Default age below which fills are not yet expected on both sides
+const DEFAULT_GRACE: Duration = Duration::from_secs(10);
+
+/// Default PnL difference reported as drift
+const DEFAULT_PNL_TOLERANCE: f64 = 0.01;
+
+/// Discrepancy found by a reconciliation run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ReconcileEvent {
+    /// The exchange reports a fill that was never processed locally
+    MissingFill {
+        coin: String,
+        oid: u64,
+        tid: u64,
+        time: u64,
+    },
+    /// A fill was processed locally more than once
+    DuplicateFill { tid: u64, count: u32 },
+    /// A fill was processed locally that the exchange doesn't report
+    UnknownFill { tid: u64, time: u64 },
+    /// Local realized PnL net of fees differs from the exchange's
+    PnlDrift {
+        local: f64,
+        exchange: f64,
+        difference: f64,
+    },
+    /// A position disagrees with the exchange
+    PositionDrift {
+        coin: String,
+        local_szi: f64,
+        exchange_szi: f64,
+    },
+    /// Equity changed without a fill (deposit, withdrawal, transfer, ...)
+    LedgerChange {
+        time: u64,
+        hash: String,
+        kind: String,
+        usdc: Option<f64>,
+    },
+}
+
+impl From<Divergence> for ReconcileEvent {
+    fn from(divergence: Divergence) -> Self {
+        ReconcileEvent::PositionDrift {
+            coin: divergence.coin,
+            local_szi: divergence.local_szi,
+            exchange_szi: divergence.exchange_szi,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsUserFills {
+    #[serde(default)]
+    is_snapshot: bool,
+    #[serde(default)]
+    fills: Vec<WsFill>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WsFill {
+    coin: String,
+    oid: u64,
+    tid: u64,
+    time: u64,
+    #[serde(default)]
+    fee: Option<String>,
+    #[serde(default)]
+    closed_pnl: Option<String>,
+}
+
+impl WsFill {
+    fn net_pnl(&self) -> f64 {
+        let parse = |value: &Option<String>| {
+            value
+                .as_deref()
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        parse(&self.closed_pnl) - parse(&self.fee)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Journal {
+    /// Times each fill was processed, with its time
+    fills: HashMap<u64, (u32, u64)>,
+    /// Duplicates already reported, with the count reported
+    reported_duplicates: HashMap<u64, u32>,
+    /// Missing or unknown fills already reported
+    reported_fills: HashSet<u64>,
+    seen_ledger: HashSet<String>,
+}
+
+/// Cross-checks processed fills and tracked positions with the exchange
+#[derive(Debug, Clone)]
+pub struct Reconciler {
+    user: String,
+    start_time: u64,
+    grace: Duration,
+    pnl_tolerance: f64,
+    tracker: Option<PositionTracker>,
+    journal: Arc<Mutex<Journal>>,
+    events: broadcast::Sender<ReconcileEvent>,
+}
+
+impl Reconciler {
+    /// Reconcile `user`'s account from now on
+    pub fn new(user: impl Into<String>) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            user: user.into(),
+            start_time: now_millis(),
+            grace: DEFAULT_GRACE,
+            pnl_tolerance: DEFAULT_PNL_TOLERANCE,
+            tracker: None,
+            journal: Arc::new(Mutex::new(Journal::default())),
+            events,
+        }
+    }
+
+    /// Check fills and ledger updates from `start_time` (ms) instead of now
+    pub fn with_start_time(mut self, start_time: u64) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Leave fills younger than `grace` for the next run
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    pub fn with_pnl_tolerance(mut self, tolerance: f64) -> Self {
+        self.pnl_tolerance = tolerance;
+        self
+    }
+
+    /// Also check `tracker`'s positions and realized PnL
+    pub fn with_position_tracker(mut self, tracker: PositionTracker) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Discrepancies found by each run
+    pub fn events(&self) -> broadcast::Receiver<ReconcileEvent> {
+        self.events.subscribe()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Journal> {
+        self.journal.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record a processed `userFills` payload
+    ///
+    /// Call this each time the application applies a payload, so repeated
+    /// processing shows up as duplicates. Snapshots are ignored.
+    pub fn record_fills(&self, data: &Value) {
+        let message: WsUserFills = match serde_json::from_value(data.clone()) {
+            Ok(message) => message,
+            Err(e) => {
+                warn!("Ignoring malformed userFills payload: {}", e);
+                return;
+            }
+        };
+        if message.is_snapshot {
+            return;
+        }
+        let mut journal = self.lock();
+        for fill in message.fills {
+            journal.fills.entry(fill.tid).or_insert((0, fill.time)).0 += 1;
+        }
+    }
+
+    /// Compare the journal and tracker with exchange responses
+    ///
+    /// `fills` is a `userFillsByTime` response, `ledger` a
+    /// `userNonFundingLedgerUpdates` response and `state` an optional
+    /// `clearinghouseState` response, all covering the window from the start
+    /// time to `now` (ms). Each discrepancy is reported once; returns the new
+    /// ones, which are also published on [`events`](Self::events).
+    pub fn reconcile_responses(
+        &self,
+        fills: &Value,
+        ledger: &Value,
+        state: Option<&Value>,
+        now: u64,
+    ) -> Result<Vec<ReconcileEvent>, HyperliquidError> {
+        let fills: Vec<WsFill> = serde_json::from_value(fills.clone())?;
+        let ledger: Vec<UserLedgerUpdate> = serde_json::from_value(ledger.clone())?;
+        let settled_before = now.saturating_sub(self.grace.as_millis() as u64);
+        let fills: Vec<WsFill> = fills
+            .into_iter()
+            .filter(|fill| fill.time >= self.start_time)
+            .collect();
+        let mut events = Vec::new();
+
+        {
+            let mut journal = self.lock();
+            let exchange_tids: HashSet<u64> = fills.iter().map(|fill| fill.tid).collect();
+            for fill in &fills {
+                if fill.time < settled_before
+                    && !journal.fills.contains_key(&fill.tid)
+                    && journal.reported_fills.insert(fill.tid)
+                {
+                    events.push(ReconcileEvent::MissingFill {
+                        coin: fill.coin.clone(),
+                        oid: fill.oid,
+                        tid: fill.tid,
+                        time: fill.time,
+                    });
+                }
+            }
+
+            let mut local: Vec<(u64, u32, u64)> = journal
+                .fills
+                .iter()
+                .map(|(tid, (count, time))| (*tid, *count, *time))
+                .collect();
+            local.sort_unstable();
+            for (tid, count, time) in local {
+                if count > 1 && journal.reported_duplicates.get(&tid) != Some(&count) {
+                    journal.reported_duplicates.insert(tid, count);
+                    events.push(ReconcileEvent::DuplicateFill { tid, count });
+                }
+                if time >= self.start_time
+                    && time < settled_before
+                    && !exchange_tids.contains(&tid)
+                    && journal.reported_fills.insert(tid)
+                {
+                    events.push(ReconcileEvent::UnknownFill { tid, time });
+                }
+            }
+
+            for update in ledger {
+                if update.time < self.start_time || !journal.seen_ledger.insert(update.hash.clone())
+                {
+                    continue;
+                }
+                events.push(ReconcileEvent::LedgerChange {
+                    time: update.time,
+                    kind: update.delta.kind().to_string(),
+                    usdc: update.delta.usdc(),
+                    hash: update.hash,
+                });
+            }
+        }
+
+        if let Some(tracker) = &self.tracker {
+            let exchange: f64 = fills.iter().map(WsFill::net_pnl).sum();
+            let local: f64 = tracker
+                .positions()
+                .iter()
+                .map(|position| position.realized_pnl - position.fees)
+                .sum();
+            let difference = local - exchange;
+            if difference.abs() > self.pnl_tolerance {
+                events.push(ReconcileEvent::PnlDrift {
+                    local,
+                    exchange,
+                    difference,
+                });
+            }
+            if let Some(state) = state {
+                events.extend(
+                    tracker
+                        .reconcile_state(state)
+                        .into_iter()
+                        .map(ReconcileEvent::from),
+                );
+            }
+        }
+
+        for event in &events {
+            warn!("Reconciliation: {:?}", event);
+            let _ = self.events.send(event.clone());
+        }
+        Ok(events)
+    }
+
+    /// Fetch the exchange's records for the window and reconcile against them
+    pub async fn run_once(
+        &self,
+        client: &HttpClient,
+    ) -> Result<Vec<ReconcileEvent>, HyperliquidError> {
+        let now = now_millis();
+        let fills: Value = client
+            .post(
+                "/info",
+                &json!({"type": "userFillsByTime", "user": self.user, "startTime": self.start_time}),
+            )
+            .await?;
+        let ledger: Value = client
+            .post(
+                "/info",
+                &json!({
+                    "type": "userNonFundingLedgerUpdates",
+                    "user": self.user,
+                    "startTime": self.start_time,
+                }),
+            )
+            .await?;
+        let state: Option<Value> = match self.tracker {
+            Some(_) => Some(
+                client
+                    .post(
+                        "/info",
+                        &json!({"type": "clearinghouseState", "user": self.user}),
+                    )
+                    .await?,
+            ),
+            None => None,
+        };
+        self.reconcile_responses(&fills, &ledger, state.as_ref(), now)
+    }
+
+    /// Run [`run_once`](Self::run_once) every `period` until the task is aborted
+    pub fn spawn(&self, client: HttpClient, period: Duration) -> JoinHandle<()> {
+        let reconciler = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = reconciler.run_once(&client).await {
+                    warn!("Reconciliation run failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
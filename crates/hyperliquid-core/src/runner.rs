@@ -0,0 +1,530 @@
+//! Config-driven strategy harness
+//!
+//! A [`Runner`] takes a [`Config`] and a [`Strategy`] and does everything a
+//! bot's `main` otherwise repeats. On startup, in order:
+//!
+//! 1. builds the HTTP, info and exchange clients from the `[environment]`
+//!    and `[security]` sections, signing with `security.private_key` and
+//!    checking transfers against `security.withdrawal_allowlist`;
+//! 2. loads the perp universe and the account's margin state; orders are
+//!    checked against the [`KillSwitchRegistry`] and [`MarginCalculator`]
+//!    before they are sent;
+//! 3. connects the websocket and subscribes the [`OrderManager`] to the
+//!    account's order updates and fills, and a [`LocalBook`] per coin of
+//!    `[strategy] coins`;
+//! 4. calls [`Strategy::on_start`], then feeds the strategy book updates,
+//!    fills and a timer every `strategy.timer_interval_ms`.
+//!
+//! Callbacks run one at a time; a callback that fails is logged and the
+//! strategy carries on. On Ctrl-C or [`ShutdownHandle::shutdown`] the
+//! runner stops dispatching, calls [`Strategy::on_stop`], cancels the
+//! orders still open if `strategy.cancel_on_shutdown` is set, and closes
+//! the websocket. Only orders placed through [`StrategyContext::place`] are
+//! tracked, and so cancelled. Logging is left to the binary.
+//!
+//! ```no_run
+//! use hyperliquid_core::oms::TrackedOrder;
+//! use hyperliquid_core::runner::{Runner, Strategy, StrategyContext};
+//! use hyperliquid_core::stream::LocalBook;
+//! use hyperliquid_core::{Config, HyperliquidError};
+//!
+//! struct Logger;
+//!
+//! impl Strategy for Logger {
+//!     async fn on_book(&mut self, _ctx: &StrategyContext, book: &LocalBook) -> Result<(), HyperliquidError> {
+//!         println!("{} best bid {:?}", book.coin(), book.bids().first());
+//!         Ok(())
+//!     }
+//!
+//!     async fn on_fill(&mut self, _ctx: &StrategyContext, order: &TrackedOrder, px: f64, sz: f64) -> Result<(), HyperliquidError> {
+//!         println!("filled {} {} at {}", sz, order.coin, px);
+//!         Ok(())
+//!     }
+//!
+//!     async fn on_timer(&mut self, ctx: &StrategyContext) -> Result<(), HyperliquidError> {
+//!         println!("{} open orders", ctx.orders().open_orders().len());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn main() -> Result<(), HyperliquidError> {
+//!     let config = Config::load("bot.toml")?;
+//!     Runner::new(config, Logger)?.run_blocking()
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::client::HttpClient;
+use crate::config::{Config, StrategyConfig};
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::exchange::{ExchangeClient, ExchangeClientConfig, WithdrawalAllowlist};
+use crate::execution::{ChildOrder, ChildResult, ExchangeVenue, Venue};
+use crate::info::InfoClient;
+use crate::killswitch::KillSwitchRegistry;
+use crate::margin::MarginCalculator;
+use crate::oms::{OrderEvent, OrderEventKind, OrderKey, OrderManager, TrackedOrder};
+use crate::runtime::{ConfiguredRuntime, RuntimeConfig};
+use crate::stream::{
+    LocalBook, WebSocketClient, WebSocketClientConfig, WebSocketError, WebSocketResponse,
+};
+use crate::types::{Environment, Subscription};
+use crate::watchdog::KillSwitch;
+
+/// Book update notices queued for the strategy; a full queue drops them,
+/// the book itself is always current
+const BOOK_QUEUE_CAPACITY: usize = 1024;
+
+/// Interval between refreshes of the account's margin state
+const MARGIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Callbacks of a trading strategy
+///
+/// Every callback gets the [`StrategyContext`] holding the clients, books
+/// and order manager.
+pub trait Strategy: Send + 'static {
+    /// Called once everything is connected, before any other callback;
+    /// failing aborts the run
+    fn on_start(
+        &mut self,
+        _ctx: &StrategyContext,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// Called when the book of a configured coin changes
+    fn on_book(
+        &mut self,
+        ctx: &StrategyContext,
+        book: &LocalBook,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+
+    /// Called for each execution of a tracked order; `order` includes it
+    fn on_fill(
+        &mut self,
+        ctx: &StrategyContext,
+        order: &TrackedOrder,
+        px: f64,
+        sz: f64,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+
+    /// Called every `strategy.timer_interval_ms`
+    fn on_timer(
+        &mut self,
+        ctx: &StrategyContext,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+
+    /// Called once on shutdown, before open orders are cancelled
+    fn on_stop(
+        &mut self,
+        _ctx: &StrategyContext,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Everything a strategy trades with
+pub struct StrategyContext {
+    settings: StrategyConfig,
+    user: String,
+    info: InfoClient,
+    exchange: ExchangeClient,
+    venue: Arc<ExchangeVenue>,
+    oms: OrderManager,
+    margin: MarginCalculator,
+    kill_switches: KillSwitchRegistry,
+    books: BTreeMap<String, LocalBook>,
+}
+
+impl std::fmt::Debug for StrategyContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyContext")
+            .field("user", &self.user)
+            .field("coins", &self.books.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+impl StrategyContext {
+    /// Address of the traded account
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn info(&self) -> &InfoClient {
+        &self.info
+    }
+
+    /// Exchange client, with the kill switch and margin checks
+    pub fn exchange(&self) -> &ExchangeClient {
+        &self.exchange
+    }
+
+    /// Venue for an [`Executor`](crate::execution::Executor) or
+    /// [`Quoter`](crate::quoter::Quoter)
+    pub fn venue(&self) -> &Arc<ExchangeVenue> {
+        &self.venue
+    }
+
+    pub fn orders(&self) -> &OrderManager {
+        &self.oms
+    }
+
+    pub fn margin(&self) -> &MarginCalculator {
+        &self.margin
+    }
+
+    pub fn kill_switches(&self) -> &KillSwitchRegistry {
+        &self.kill_switches
+    }
+
+    /// Book of a configured coin
+    pub fn book(&self, coin: &str) -> Option<&LocalBook> {
+        self.books.get(coin)
+    }
+
+    /// The `[strategy.params]` table as `T`
+    pub fn params<T: DeserializeOwned>(&self) -> Result<T, HyperliquidError> {
+        self.settings.params()
+    }
+
+    /// Check, send and track an order
+    ///
+    /// Fails without sending if the coin is disabled or the order doesn't
+    /// fit the free margin. The order is tracked by the [`OrderManager`]
+    /// under the returned key whether or not the exchange accepts it.
+    pub async fn place(
+        &self,
+        order: &ChildOrder,
+    ) -> Result<(OrderKey, ChildResult), HyperliquidError> {
+        self.kill_switches.check(&order.coin)?;
+        self.margin
+            .check_order(&order.coin, order.is_buy, order.sz, Some(order.limit_px))?;
+        let key =
+            self.oms
+                .record_submission(&order.coin, order.is_buy, order.sz, order.limit_px, None);
+        match self.venue.place(order).await {
+            Ok(result) => {
+                self.oms.record_status(key, &result.to_status());
+                Ok((key, result))
+            }
+            Err(e) => {
+                self.oms
+                    .record_status(key, &json!({"error": e.to_string()}));
+                Err(e)
+            }
+        }
+    }
+
+    /// Cancel a tracked order resting on the book
+    pub async fn cancel(&self, key: OrderKey) -> Result<(), HyperliquidError> {
+        let order = self
+            .oms
+            .get(key)
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown order {:?}", key)))?;
+        let oid = order.oid.ok_or_else(|| {
+            HyperliquidError::Validation(format!("order {:?} has no oid yet", key))
+        })?;
+        self.venue.cancel(&order.coin, oid).await
+    }
+}
+
+/// Stops a [`Runner`] from another task
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle(Arc<watch::Sender<bool>>);
+
+impl ShutdownHandle {
+    pub fn shutdown(&self) {
+        self.0.send_replace(true);
+    }
+
+    pub fn is_shutdown(&self) -> bool {
+        *self.0.borrow()
+    }
+}
+
+/// Runs one [`Strategy`] against the exchange
+pub struct Runner<S> {
+    config: Config,
+    strategy: S,
+    oms: OrderManager,
+    kill_switches: KillSwitchRegistry,
+    shutdown: ShutdownHandle,
+}
+
+impl<S> std::fmt::Debug for Runner<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Runner")
+            .field("strategy", &self.config.strategy)
+            .field("kill_switches", &self.kill_switches)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Strategy> Runner<S> {
+    /// Validate `config` for running `strategy`
+    ///
+    /// Fails with [`HyperliquidError::Config`] if the configuration is
+    /// invalid or has no signing key.
+    pub fn new(config: Config, strategy: S) -> Result<Self, HyperliquidError> {
+        config.validate()?;
+        if config.security.private_key.is_none() {
+            return Err(HyperliquidError::Config(
+                "no signing key configured; set security.private_key".to_string(),
+            ));
+        }
+        Ok(Self {
+            config,
+            strategy,
+            oms: OrderManager::new(),
+            kill_switches: KillSwitchRegistry::new(),
+            shutdown: ShutdownHandle(Arc::new(watch::channel(false).0)),
+        })
+    }
+
+    /// Track orders in `oms`, e.g. one backed by a store
+    pub fn with_order_manager(mut self, oms: OrderManager) -> Self {
+        self.oms = oms;
+        self
+    }
+
+    /// Check orders against `switches`, e.g. ones shared with `hl trading`
+    pub fn with_kill_switches(mut self, switches: KillSwitchRegistry) -> Self {
+        self.kill_switches = switches;
+        self
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Run on a runtime built from the `[runtime]` section until shut down
+    pub fn run_blocking(self) -> Result<(), HyperliquidError> {
+        let settings = &self.config.runtime;
+        let mut config = RuntimeConfig::default();
+        // 0 keeps the default of one thread per core
+        if settings.worker_threads > 0 {
+            config.worker_threads = settings.worker_threads;
+        }
+        config.max_blocking_threads = settings.max_blocking_threads;
+        config.thread_stack_size = settings.thread_stack_size;
+        config.global_queue_interval = settings.global_queue_interval;
+        config.shutdown_timeout_secs = settings.shutdown_timeout_secs;
+        config.enable_metrics = settings.enable_metrics;
+        config.metrics_interval_ms = settings.metrics_interval_ms;
+
+        let runtime = ConfiguredRuntime::new(config)?;
+        let result = runtime.block_on(self.run());
+        runtime.shutdown()?;
+        result
+    }
+
+    /// Start up, run the strategy until shut down, then tear down
+    pub async fn run(self) -> Result<(), HyperliquidError> {
+        let Runner {
+            config,
+            mut strategy,
+            oms,
+            kill_switches,
+            shutdown,
+        } = self;
+        let ws_error = |e: WebSocketError| HyperliquidError::WebSocket(e.to_string());
+
+        // Clients
+        let key = config
+            .security
+            .private_key
+            .as_ref()
+            .ok_or_else(|| HyperliquidError::Config("no signing key configured".to_string()))?;
+        let mainnet = config.get_environment() == Environment::Mainnet;
+        let wallet = Wallet::new(key.expose(), mainnet)?;
+        let user = wallet.address();
+        let account = user
+            .parse()
+            .map_err(|_| HyperliquidError::Signing("invalid wallet address".to_string()))?;
+        let base_url = config.get_base_url();
+        let http = HttpClient::with_default_config(base_url.clone())?;
+        let mut info = InfoClient::new(http.clone());
+        info.initialize_assets("").await?;
+
+        // Risk state
+        let meta = info.meta("").await?;
+        let margin = MarginCalculator::new().with_meta(&meta);
+        margin.refresh(&http, &user).await?;
+        let mut exchange_config = if mainnet {
+            ExchangeClientConfig::mainnet(account)
+        } else {
+            ExchangeClientConfig::testnet(account)
+        };
+        exchange_config.base_url = base_url;
+        let mut exchange = ExchangeClient::new(exchange_config)
+            .with_kill_switches(kill_switches.clone())
+            .with_margin_check(margin.clone())
+            .with_assets(info.clone());
+        if let Some(allowlist) = WithdrawalAllowlist::from_config(&config.security)? {
+            exchange = exchange.with_withdrawal_allowlist(allowlist);
+        }
+        let venue = Arc::new(ExchangeVenue::new(exchange.clone(), wallet, &meta));
+
+        // Streams
+        let mut ws = WebSocketClient::with_config(
+            WebSocketClientConfig {
+                url: config.get_websocket_url(),
+                ..Default::default()
+            }
+            .with_settings(&config.websocket),
+        )
+        .map_err(ws_error)?;
+        ws.connect().await.map_err(ws_error)?;
+        let mut orders = oms.events();
+        let (book_updates, mut book_rx) = mpsc::channel(BOOK_QUEUE_CAPACITY);
+        let settings = config.strategy;
+        let mut books = BTreeMap::new();
+        let streams = async {
+            oms.attach(&ws, &user).await?;
+            let coins: Vec<&str> = settings.coins.iter().map(String::as_str).collect();
+            margin.attach(&ws, &coins).await?;
+            for coin in &settings.coins {
+                let book = LocalBook::new(coin.clone());
+                let subscription = Subscription::L2Book { coin: coin.clone() };
+                let (handler_book, updates) = (book.clone(), book_updates.clone());
+                ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                    // Unrouted messages are broadcast to every handler
+                    if response.channel == "l2Book" && handler_book.apply(&response.data) {
+                        let _ = updates.try_send(handler_book.coin().to_string());
+                    }
+                })
+                .await;
+                ws.subscribe(subscription).await.map_err(ws_error)?;
+                books.insert(coin.clone(), book);
+            }
+            Ok::<_, HyperliquidError>(())
+        };
+        if let Err(e) = streams.await {
+            let _ = ws.shutdown().await;
+            return Err(e);
+        }
+
+        let refresh = spawn_margin_refresh(margin.clone(), http, user.clone());
+        let ctrl_c = spawn_ctrl_c(shutdown.clone());
+        let timer_interval = Duration::from_millis(settings.timer_interval_ms);
+        let cancel_on_shutdown = settings.cancel_on_shutdown;
+        let ctx = StrategyContext {
+            settings,
+            user,
+            info,
+            exchange,
+            venue,
+            oms,
+            margin,
+            kill_switches,
+            books,
+        };
+
+        // Strategy
+        info!("Starting strategy for {}", ctx.user);
+        let mut stop = shutdown.0.subscribe();
+        let result = match strategy.on_start(&ctx).await {
+            Ok(()) => {
+                let mut timer = tokio::time::interval(timer_interval);
+                timer.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                loop {
+                    let (callback, result) = tokio::select! {
+                        _ = stop.wait_for(|stop| *stop) => break,
+                        Some(coin) = book_rx.recv() => match ctx.books.get(&coin) {
+                            Some(book) => ("book", strategy.on_book(&ctx, book).await),
+                            None => continue,
+                        },
+                        event = orders.recv() => match event {
+                            Ok(OrderEvent {
+                                kind: OrderEventKind::Fill { px, sz },
+                                order,
+                            }) => ("fill", strategy.on_fill(&ctx, &order, px, sz).await),
+                            Ok(_) => continue,
+                            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                                warn!("Strategy missed {} order events", missed);
+                                continue;
+                            }
+                            // The order manager holds the sender
+                            Err(broadcast::error::RecvError::Closed) => break,
+                        },
+                        _ = timer.tick() => ("timer", strategy.on_timer(&ctx).await),
+                    };
+                    if let Err(e) = result {
+                        warn!("Strategy {} callback failed: {}", callback, e);
+                        crate::telemetry::counter!(
+                            "hyperliquid_strategy_errors_total",
+                            "callback" => callback
+                        )
+                        .increment(1);
+                    }
+                }
+                Ok(())
+            }
+            Err(e) => Err(e),
+        };
+
+        // Teardown
+        info!("Stopping strategy");
+        if result.is_ok() {
+            if let Err(e) = strategy.on_stop(&ctx).await {
+                warn!("Strategy stop callback failed: {}", e);
+            }
+        }
+        if cancel_on_shutdown {
+            let open = ctx.oms.open_orders();
+            if !open.is_empty() {
+                info!("Cancelling {} open orders", open.len());
+                if let Err(e) = ctx.venue.cancel_orders(&open).await {
+                    warn!("Failed to cancel open orders on shutdown: {}", e);
+                }
+            }
+        }
+        refresh.abort();
+        ctrl_c.abort();
+        if let Err(e) = ws.shutdown().await {
+            warn!("Failed to close websocket: {}", e);
+        }
+        result
+    }
+}
+
+/// Reload `user`'s margin state in the background so fills are reflected
+fn spawn_margin_refresh(
+    margin: MarginCalculator,
+    http: HttpClient,
+    user: String,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(MARGIN_REFRESH_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The state was just loaded
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(e) = margin.refresh(&http, &user).await {
+                warn!("Failed to refresh margin state: {}", e);
+            }
+        }
+    })
+}
+
+fn spawn_ctrl_c(shutdown: ShutdownHandle) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("Received Ctrl-C, shutting down");
+            shutdown.shutdown();
+        }
+    })
+}
@@ -4,11 +4,50 @@
 //! worker thread configuration, blocking pool management, and graceful
 //! shutdown handling.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::runtime::{Builder, Runtime};
+use tokio::runtime::{Builder, Handle, Runtime};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
+/// Role of a thread, used for naming so profilers and `top -H` show intent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThreadRole {
+    /// General tokio worker
+    Worker,
+    /// WebSocket reader / message hand-off
+    WsReader,
+    /// Action signing
+    Signer,
+    /// HTTP request execution
+    Http,
+}
+
+impl ThreadRole {
+    /// Thread name used for this role
+    pub fn thread_name(&self) -> &'static str {
+        match self {
+            ThreadRole::Worker => "hyperliquid-worker",
+            ThreadRole::WsReader => "hyperliquid-ws-reader",
+            ThreadRole::Signer => "hyperliquid-signer",
+            ThreadRole::Http => "hyperliquid-http",
+        }
+    }
+}
+
+/// Scheduling priority requested for runtime threads
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum ThreadPriority {
+    /// Leave the OS default untouched
+    #[default]
+    Normal,
+    /// Raise priority via a negative nice value (needs CAP_SYS_NICE or root)
+    High,
+    /// Request `SCHED_FIFO`, falling back to `High` when not permitted
+    Realtime,
+}
+
 /// Configuration for Tokio async runtime
 #[derive(Clone, Debug)]
 pub struct RuntimeConfig {
@@ -26,6 +65,16 @@ pub struct RuntimeConfig {
     pub global_queue_interval: u32,
     /// Shutdown timeout in seconds
     pub shutdown_timeout_secs: u64,
+    /// Periodically publish runtime metrics through the `metrics` facade
+    pub enable_metrics: bool,
+    /// Runtime metrics publish interval in milliseconds
+    pub metrics_interval_ms: u64,
+    /// Role used to name the worker threads
+    pub thread_role: ThreadRole,
+    /// CPU cores to pin worker threads to, assigned round-robin (empty = no pinning)
+    pub core_ids: Vec<usize>,
+    /// Scheduling priority for worker threads
+    pub thread_priority: ThreadPriority,
 }
 
 impl Default for RuntimeConfig {
@@ -40,6 +89,11 @@ impl Default for RuntimeConfig {
             enable_time: true,
             global_queue_interval: 61, // Prime number to reduce collisions
             shutdown_timeout_secs: 30,
+            enable_metrics: false,
+            metrics_interval_ms: 1000,
+            thread_role: ThreadRole::Worker,
+            core_ids: Vec::new(),
+            thread_priority: ThreadPriority::Normal,
         }
     }
 }
@@ -85,6 +139,24 @@ impl RuntimeConfig {
         }
     }
 
+    /// Pin worker threads to the given cores (round-robin)
+    pub fn with_core_ids(mut self, core_ids: Vec<usize>) -> Self {
+        self.core_ids = core_ids;
+        self
+    }
+
+    /// Set the scheduling priority of worker threads
+    pub fn with_thread_priority(mut self, priority: ThreadPriority) -> Self {
+        self.thread_priority = priority;
+        self
+    }
+
+    /// Set the role used to name worker threads
+    pub fn with_thread_role(mut self, role: ThreadRole) -> Self {
+        self.thread_role = role;
+        self
+    }
+
     /// Create a configuration for single-threaded runtime
     pub fn single_threaded() -> Self {
         Self {
@@ -104,6 +176,8 @@ pub struct ConfiguredRuntime {
     config: RuntimeConfig,
     /// Whether the runtime is currently running
     is_running: bool,
+    /// Background task publishing runtime metrics
+    metrics_task: Option<JoinHandle<()>>,
 }
 
 impl ConfiguredRuntime {
@@ -114,6 +188,13 @@ impl ConfiguredRuntime {
             config.worker_threads, config.max_blocking_threads
         );
 
+        // Blocking-pool threads also run on_thread_start, so pinning only the
+        // first `worker_threads` starts keeps spawn_blocking off the pinned cores.
+        let core_ids = Arc::new(config.core_ids.clone());
+        let priority = config.thread_priority;
+        let worker_threads = config.worker_threads;
+        let started = Arc::new(AtomicUsize::new(0));
+
         let runtime = Builder::new_multi_thread()
             .worker_threads(config.worker_threads)
             .max_blocking_threads(config.max_blocking_threads)
@@ -121,8 +202,17 @@ impl ConfiguredRuntime {
             .enable_io(config.enable_io)
             .enable_time(config.enable_time)
             .global_queue_interval(config.global_queue_interval)
-            .thread_name("hyperliquid-worker")
-            .on_thread_start(|| {
+            .thread_name(config.thread_role.thread_name())
+            .on_thread_start(move || {
+                let index = started.fetch_add(1, Ordering::SeqCst);
+                if index < worker_threads {
+                    let core = if core_ids.is_empty() {
+                        None
+                    } else {
+                        Some(core_ids[index % core_ids.len()])
+                    };
+                    apply_thread_placement(core, priority);
+                }
                 debug!("Tokio worker thread started");
             })
             .on_thread_stop(|| {
@@ -132,13 +222,40 @@ impl ConfiguredRuntime {
 
         info!("Tokio runtime created successfully");
 
+        let metrics_task = if config.enable_metrics && config.enable_time {
+            let interval = Duration::from_millis(config.metrics_interval_ms.max(1));
+            Some(runtime.spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                loop {
+                    ticker.tick().await;
+                    RuntimeMetricsSnapshot::capture(&Handle::current()).record();
+                }
+            }))
+        } else {
+            if config.enable_metrics {
+                warn!("Runtime metrics require the time driver; metrics reporting disabled");
+            }
+            None
+        };
+
         Ok(Self {
             runtime,
             config,
             is_running: true,
+            metrics_task,
         })
     }
 
+    /// Capture a snapshot of the runtime metrics
+    pub fn metrics(&self) -> RuntimeMetricsSnapshot {
+        RuntimeMetricsSnapshot::capture(self.runtime.handle())
+    }
+
+    /// Check if runtime metrics are being published
+    pub fn is_reporting_metrics(&self) -> bool {
+        self.metrics_task.is_some()
+    }
+
     /// Get a reference to the underlying Tokio runtime
     pub fn inner(&self) -> &Runtime {
         &self.runtime
@@ -166,6 +283,10 @@ impl ConfiguredRuntime {
             self.config.shutdown_timeout_secs
         );
 
+        if let Some(task) = &self.metrics_task {
+            task.abort();
+        }
+
         // Drop the runtime to initiate shutdown
         drop(self.runtime);
 
@@ -189,6 +310,217 @@ impl Drop for ConfiguredRuntime {
     }
 }
 
+/// Pin the current thread to `core` and apply `priority`
+///
+/// Failures (unsupported platform, missing privileges) are logged and
+/// reported through the return value; they never abort the thread.
+pub fn apply_thread_placement(core: Option<usize>, priority: ThreadPriority) -> bool {
+    let mut ok = true;
+
+    if let Some(core) = core {
+        if let Err(e) = pin_current_thread(core) {
+            warn!("Failed to pin thread to core {}: {}", core, e);
+            ok = false;
+        }
+    }
+
+    if let Err(e) = set_current_thread_priority(priority) {
+        warn!("Failed to set thread priority {:?}: {}", priority, e);
+        ok = false;
+    }
+
+    ok
+}
+
+/// Spawn a named OS thread for a role, optionally pinned and prioritized
+pub fn spawn_role_thread<F, T>(
+    role: ThreadRole,
+    core: Option<usize>,
+    priority: ThreadPriority,
+    f: F,
+) -> std::io::Result<std::thread::JoinHandle<T>>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    std::thread::Builder::new()
+        .name(role.thread_name().to_string())
+        .spawn(move || {
+            apply_thread_placement(core, priority);
+            f()
+        })
+}
+
+#[cfg(target_os = "linux")]
+fn pin_current_thread(core: usize) -> std::io::Result<()> {
+    // Safety: cpu_set_t is plain data and only passed to sched_setaffinity
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(core, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_current_thread(_core: usize) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "core pinning is only supported on Linux",
+    ))
+}
+
+#[cfg(unix)]
+fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    match priority {
+        ThreadPriority::Normal => Ok(()),
+        ThreadPriority::High => set_nice(-10),
+        ThreadPriority::Realtime => {
+            // Safety: plain libc calls on the current thread
+            let result = unsafe {
+                let param = libc::sched_param {
+                    sched_priority: libc::sched_get_priority_min(libc::SCHED_FIFO),
+                };
+                libc::pthread_setschedparam(libc::pthread_self(), libc::SCHED_FIFO, &param)
+            };
+            if result == 0 {
+                Ok(())
+            } else {
+                debug!("SCHED_FIFO not permitted, falling back to nice priority");
+                set_nice(-10)
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn set_nice(nice: i32) -> std::io::Result<()> {
+    // On Linux, PRIO_PROCESS with who=0 applies to the calling thread
+    // Safety: plain libc call
+    if unsafe { libc::setpriority(libc::PRIO_PROCESS as _, 0, nice) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_current_thread_priority(priority: ThreadPriority) -> std::io::Result<()> {
+    match priority {
+        ThreadPriority::Normal => Ok(()),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "thread priorities are only supported on Unix",
+        )),
+    }
+}
+
+/// Point-in-time view of tokio runtime metrics
+///
+/// Worker park/unpark and busy time help tell whether latency spikes come from
+/// the runtime (saturated workers, deep queues) or from the network. Blocking
+/// pool and poll-time metrics are only available when built with
+/// `--cfg tokio_unstable` and are `None` otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct RuntimeMetricsSnapshot {
+    /// Number of worker threads
+    pub workers: usize,
+    /// Number of tasks currently alive
+    pub alive_tasks: usize,
+    /// Tasks waiting in the global injection queue
+    pub global_queue_depth: usize,
+    /// Total times workers parked, summed over workers
+    pub worker_park_count: u64,
+    /// Total park/unpark transitions, summed over workers
+    pub worker_park_unpark_count: u64,
+    /// Total time workers spent busy, summed over workers
+    pub worker_busy_duration: Duration,
+    /// Threads in the blocking pool
+    pub blocking_threads: Option<usize>,
+    /// Idle threads in the blocking pool
+    pub idle_blocking_threads: Option<usize>,
+    /// Tasks waiting for a blocking thread
+    pub blocking_queue_depth: Option<usize>,
+    /// Mean task poll time, averaged over workers
+    pub mean_poll_time: Option<Duration>,
+}
+
+impl RuntimeMetricsSnapshot {
+    /// Capture metrics from a runtime handle
+    pub fn capture(handle: &Handle) -> Self {
+        let metrics = handle.metrics();
+        let workers = metrics.num_workers();
+
+        let mut snapshot = Self {
+            workers,
+            alive_tasks: metrics.num_alive_tasks(),
+            global_queue_depth: metrics.global_queue_depth(),
+            ..Default::default()
+        };
+
+        #[cfg(target_has_atomic = "64")]
+        for worker in 0..workers {
+            snapshot.worker_park_count += metrics.worker_park_count(worker);
+            snapshot.worker_park_unpark_count += metrics.worker_park_unpark_count(worker);
+            snapshot.worker_busy_duration += metrics.worker_total_busy_duration(worker);
+        }
+
+        #[cfg(tokio_unstable)]
+        {
+            snapshot.blocking_threads = Some(metrics.num_blocking_threads());
+            snapshot.idle_blocking_threads = Some(metrics.num_idle_blocking_threads());
+            snapshot.blocking_queue_depth = Some(metrics.blocking_queue_depth());
+            if workers > 0 {
+                let total: Duration = (0..workers).map(|w| metrics.worker_mean_poll_time(w)).sum();
+                snapshot.mean_poll_time = Some(total / workers as u32);
+            }
+        }
+
+        snapshot
+    }
+
+    /// Number of blocking threads currently running tasks
+    pub fn busy_blocking_threads(&self) -> Option<usize> {
+        match (self.blocking_threads, self.idle_blocking_threads) {
+            (Some(total), Some(idle)) => Some(total.saturating_sub(idle)),
+            _ => None,
+        }
+    }
+
+    /// Publish the snapshot as `hyperliquid_runtime_*` gauges
+    pub fn record(&self) {
+        crate::telemetry::gauge!("hyperliquid_runtime_workers").set(self.workers as f64);
+        crate::telemetry::gauge!("hyperliquid_runtime_alive_tasks").set(self.alive_tasks as f64);
+        crate::telemetry::gauge!("hyperliquid_runtime_global_queue_depth").set(self.global_queue_depth as f64);
+        crate::telemetry::counter!("hyperliquid_runtime_worker_park_total").absolute(self.worker_park_count);
+        crate::telemetry::counter!("hyperliquid_runtime_worker_park_unpark_total")
+            .absolute(self.worker_park_unpark_count);
+        crate::telemetry::gauge!("hyperliquid_runtime_worker_busy_seconds")
+            .set(self.worker_busy_duration.as_secs_f64());
+
+        if let Some(threads) = self.blocking_threads {
+            crate::telemetry::gauge!("hyperliquid_runtime_blocking_threads").set(threads as f64);
+        }
+        if let Some(busy) = self.busy_blocking_threads() {
+            crate::telemetry::gauge!("hyperliquid_runtime_blocking_threads_busy").set(busy as f64);
+        }
+        if let Some(depth) = self.blocking_queue_depth {
+            crate::telemetry::gauge!("hyperliquid_runtime_blocking_queue_depth").set(depth as f64);
+        }
+        if let Some(poll_time) = self.mean_poll_time {
+            crate::telemetry::gauge!("hyperliquid_runtime_mean_poll_seconds").set(poll_time.as_secs_f64());
+        }
+
+        debug!(
+            workers = self.workers,
+            alive_tasks = self.alive_tasks,
+            global_queue_depth = self.global_queue_depth,
+            "Recorded runtime metrics"
+        );
+    }
+}
+
 /// Create a default configured runtime
 pub fn create_default_runtime() -> std::io::Result<ConfiguredRuntime> {
     ConfiguredRuntime::new(RuntimeConfig::default())
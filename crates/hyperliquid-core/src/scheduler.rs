@@ -0,0 +1,590 @@
+//! Rate-limit-aware polling
+//!
+//! A [`Scheduler`] runs registered [`Job`]s on fixed intervals while keeping
+//! their combined request weight inside Hyperliquid's per-IP budget:
+//!
+//! - every run first takes its weight from a shared [`RateLimiter`], so jobs
+//!   wait rather than burst when the budget is spent;
+//! - start times are jittered so jobs with the same interval do not fire in
+//!   lockstep;
+//! - a 429 from any job pauses every job (and anything else sharing the
+//!   limiter) with exponential backoff, honouring `Retry-After` when given;
+//! - with an [`ExchangeHealth`], runs are skipped while the exchange is
+//!   unavailable, except for jobs polling the health itself.
+//!
+//! The limiter is cheap to clone; pass [`Scheduler::limiter`] to code issuing
+//! other requests so they draw from the same budget. [`Scheduler::start`]
+//! refuses job sets whose planned weight would take more than
+//! [`with_max_share`](Scheduler::with_max_share) of it.
+//!
+//! ```no_run
+//! # fn example(client: hyperliquid_core::HttpClient) -> hyperliquid_core::Result<()> {
+//! use hyperliquid_core::scheduler::{Job, RateLimiter, Scheduler};
+//!
+//! let user = "0x0000000000000000000000000000000000000000";
+//! let scheduler = Scheduler::new(RateLimiter::hyperliquid())
+//!     .with_job(Job::user_state(client.clone(), user, |state| println!("{}", state)))
+//!     .with_job(Job::meta(client.clone(), |meta| println!("{}", meta)))
+//!     .with_job(Job::fills(client, user, |fills| println!("{}", fills)));
+//! let _handle = scheduler.start()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::health::ExchangeHealth;
+
+/// Request weight allowed per IP per minute
+pub const IP_WEIGHT_PER_MINUTE: u32 = 1200;
+/// Weight of an exchange action without batching
+pub const EXCHANGE_WEIGHT: u32 = 1;
+
+const DEFAULT_JITTER: f64 = 0.5;
+const DEFAULT_MAX_SHARE: f64 = 0.75;
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Weight of an `/info` request of the given `type`
+///
+/// Responses that return many items (fills, candles, ...) are charged extra
+/// weight by the exchange per 20 items; that part is not known up front and
+/// is not included.
+pub fn info_weight(request_type: &str) -> u32 {
+    match request_type {
+        "l2Book"
+        | "allMids"
+        | "clearinghouseState"
+        | "orderStatus"
+        | "spotClearinghouseState"
+        | "exchangeStatus" => 2,
+        "userRole" => 60,
+        _ => 20,
+    }
+}
+
+struct Bucket {
+    capacity: f64,
+    tokens: f64,
+    /// Tokens added per second
+    refill: f64,
+    updated: Instant,
+    paused_until: Option<Instant>,
+    consecutive_limits: u32,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill).min(self.capacity);
+        self.updated = now;
+    }
+}
+
+/// Weight-based token bucket shared by everything issuing requests
+#[derive(Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl std::fmt::Debug for RateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bucket = self.lock();
+        f.debug_struct("RateLimiter")
+            .field("capacity", &bucket.capacity)
+            .field("tokens", &bucket.tokens)
+            .field("paused_until", &bucket.paused_until)
+            .finish()
+    }
+}
+
+impl RateLimiter {
+    /// Allow `weight_per_minute`, starting with a full bucket
+    pub fn new(weight_per_minute: u32) -> Self {
+        let capacity = weight_per_minute.max(1) as f64;
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                tokens: capacity,
+                refill: capacity / 60.0,
+                updated: Instant::now(),
+                paused_until: None,
+                consecutive_limits: 0,
+            })),
+        }
+    }
+
+    /// Hyperliquid's per-IP budget ([`IP_WEIGHT_PER_MINUTE`])
+    pub fn hyperliquid() -> Self {
+        Self::new(IP_WEIGHT_PER_MINUTE)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Weight allowed per minute
+    pub fn capacity(&self) -> u32 {
+        self.lock().capacity as u32
+    }
+
+    /// Weight that can be spent right now
+    pub fn available(&self) -> f64 {
+        let mut bucket = self.lock();
+        bucket.refill(Instant::now());
+        bucket.tokens
+    }
+
+    /// Take `weight` if available, otherwise return how long to wait
+    ///
+    /// Weights above the capacity are charged as the full capacity.
+    pub fn try_acquire(&self, weight: u32) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut bucket = self.lock();
+        if let Some(until) = bucket.paused_until {
+            if until > now {
+                return Err(until - now);
+            }
+            bucket.paused_until = None;
+        }
+        bucket.refill(now);
+        let weight = (weight as f64).min(bucket.capacity);
+        if bucket.tokens >= weight {
+            bucket.tokens -= weight;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (weight - bucket.tokens) / bucket.refill,
+            ))
+        }
+    }
+
+    /// Wait until `weight` is available and take it
+    pub async fn acquire(&self, weight: u32) {
+        while let Err(wait) = self.try_acquire(weight) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Remaining global pause after a 429, if any
+    pub fn paused_for(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.lock()
+            .paused_until
+            .filter(|until| *until > now)
+            .map(|until| until - now)
+    }
+
+    /// Pause all callers after a 429 and return the pause length
+    ///
+    /// Without `retry_after` the pause doubles with each consecutive 429,
+    /// from one second up to a minute. The bucket is emptied so callers ramp
+    /// back up at the refill rate once the pause ends.
+    pub fn back_off(&self, retry_after: Option<Duration>) -> Duration {
+        let now = Instant::now();
+        let mut bucket = self.lock();
+        bucket.consecutive_limits = bucket.consecutive_limits.saturating_add(1);
+        let exponent = (bucket.consecutive_limits - 1).min(16);
+        let pause =
+            retry_after.unwrap_or_else(|| (BASE_BACKOFF * 2u32.pow(exponent)).min(MAX_BACKOFF));
+        let until = now + pause;
+        if bucket.paused_until.map_or(true, |current| current < until) {
+            bucket.paused_until = Some(until);
+        }
+        bucket.tokens = 0.0;
+        bucket.updated = until;
+        pause
+    }
+
+    /// Reset the backoff after a successful request
+    pub fn record_success(&self) {
+        self.lock().consecutive_limits = 0;
+    }
+}
+
+/// `Retry-After` of a rate-limit error, or `None` if `error` is not a 429
+pub fn rate_limit_retry_after(error: &HyperliquidError) -> Option<Option<Duration>> {
+    error.is_rate_limited().then(|| error.retry_after())
+}
+
+/// Boxed future returned by a job run
+pub type JobFuture = Pin<Box<dyn Future<Output = Result<(), HyperliquidError>> + Send>>;
+
+/// A polling job
+#[derive(Clone)]
+pub struct Job {
+    name: String,
+    interval: Duration,
+    weight: u32,
+    /// Runs whatever the exchange health
+    exempt: bool,
+    run: Arc<dyn Fn() -> JobFuture + Send + Sync>,
+}
+
+impl std::fmt::Debug for Job {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Job")
+            .field("name", &self.name)
+            .field("interval", &self.interval)
+            .field("weight", &self.weight)
+            .field("exempt", &self.exempt)
+            .finish()
+    }
+}
+
+impl Job {
+    /// Run `f`, costing `weight`, every `interval`
+    pub fn new<F, Fut>(name: impl Into<String>, interval: Duration, weight: u32, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), HyperliquidError>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            interval: interval.max(Duration::from_millis(1)),
+            weight,
+            exempt: false,
+            run: Arc::new(move || Box::pin(f())),
+        }
+    }
+
+    /// POST `request` to `/info` every `interval` and pass the response to
+    /// `handler`, weighted by [`info_weight`]
+    pub fn info<H>(
+        name: impl Into<String>,
+        client: HttpClient,
+        interval: Duration,
+        request: Value,
+        handler: H,
+    ) -> Self
+    where
+        H: Fn(Value) + Send + Sync + 'static,
+    {
+        let weight = info_weight(
+            request
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+        );
+        let handler = Arc::new(handler);
+        Self::new(name, interval, weight, move || {
+            let client = client.clone();
+            let request = request.clone();
+            let handler = handler.clone();
+            async move {
+                let response: Value = client.post("/info", &request).await?;
+                handler(response);
+                Ok(())
+            }
+        })
+    }
+
+    /// Poll `user`'s `clearinghouseState` every second
+    pub fn user_state<H>(client: HttpClient, user: &str, handler: H) -> Self
+    where
+        H: Fn(Value) + Send + Sync + 'static,
+    {
+        Self::info(
+            "user_state",
+            client,
+            Duration::from_secs(1),
+            json!({"type": "clearinghouseState", "user": user}),
+            handler,
+        )
+    }
+
+    /// Poll perp `meta` every five minutes
+    pub fn meta<H>(client: HttpClient, handler: H) -> Self
+    where
+        H: Fn(Value) + Send + Sync + 'static,
+    {
+        Self::info(
+            "meta",
+            client,
+            Duration::from_secs(300),
+            json!({"type": "meta"}),
+            handler,
+        )
+    }
+
+    /// Poll `user`'s `userFills` every two seconds
+    pub fn fills<H>(client: HttpClient, user: &str, handler: H) -> Self
+    where
+        H: Fn(Value) + Send + Sync + 'static,
+    {
+        Self::info(
+            "fills",
+            client,
+            Duration::from_secs(2),
+            json!({"type": "userFills", "user": user}),
+            handler,
+        )
+    }
+
+    /// Poll `exchangeStatus` into `health` every ten seconds
+    ///
+    /// Runs while the exchange is unavailable, so the scheduler notices when
+    /// it is back.
+    pub fn exchange_status(client: HttpClient, health: ExchangeHealth) -> Self {
+        let weight = info_weight("exchangeStatus");
+        Self::new(
+            "exchange_status",
+            Duration::from_secs(10),
+            weight,
+            move || {
+                let client = client.clone();
+                let health = health.clone();
+                async move { health.poll(&client).await.map(|_| ()) }
+            },
+        )
+        .exempt_from_health()
+    }
+
+    /// Run even while the scheduler's [`ExchangeHealth`] reports the
+    /// exchange unavailable
+    pub fn exempt_from_health(mut self) -> Self {
+        self.exempt = true;
+        self
+    }
+
+    /// Use a different polling interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Override the weight charged per run
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// Weight this job spends per minute when running on schedule
+    pub fn weight_per_minute(&self) -> f64 {
+        self.weight as f64 * 60.0 / self.interval.as_secs_f64()
+    }
+}
+
+/// Counters for one job
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JobStats {
+    pub runs: u64,
+    pub failures: u64,
+    pub rate_limited: u64,
+    /// Runs skipped while the exchange was unavailable
+    pub skipped: u64,
+    /// Last completed run in ms since the epoch
+    pub last_run: Option<i64>,
+}
+
+/// Runs [`Job`]s against a shared [`RateLimiter`]
+#[derive(Debug)]
+pub struct Scheduler {
+    limiter: RateLimiter,
+    jobs: Vec<Job>,
+    jitter: f64,
+    max_share: f64,
+    clock: Arc<dyn Clock>,
+    health: Option<ExchangeHealth>,
+}
+
+impl Scheduler {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter,
+            jobs: Vec::new(),
+            jitter: DEFAULT_JITTER,
+            max_share: DEFAULT_MAX_SHARE,
+            clock: clock::system(),
+            health: None,
+        }
+    }
+
+    /// Clock used to stamp [`JobStats::last_run`]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Skip runs while `health` reports the exchange unavailable
+    pub fn with_health(mut self, health: ExchangeHealth) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    pub fn with_job(mut self, job: Job) -> Self {
+        self.jobs.push(job);
+        self
+    }
+
+    /// Delay each job's first run by a random fraction of its interval, up
+    /// to `jitter` (0 starts every job immediately)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fraction of the limiter's budget the jobs may plan to use, leaving
+    /// the rest for orders and ad-hoc requests
+    pub fn with_max_share(mut self, max_share: f64) -> Self {
+        self.max_share = max_share.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Limiter the jobs draw from
+    pub fn limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    /// Combined weight per minute of all jobs running on schedule
+    pub fn planned_weight_per_minute(&self) -> f64 {
+        self.jobs.iter().map(Job::weight_per_minute).sum()
+    }
+
+    /// Spawn one task per job
+    ///
+    /// Fails with [`HyperliquidError::Config`] if the planned weight exceeds
+    /// the allowed share of the limiter's budget, or if two jobs share a name.
+    pub fn start(self) -> Result<SchedulerHandle, HyperliquidError> {
+        let planned = self.planned_weight_per_minute();
+        let allowed = self.limiter.capacity() as f64 * self.max_share;
+        if planned > allowed {
+            return Err(HyperliquidError::Config(format!(
+                "scheduled jobs need {:.0} weight/min but only {:.0} of {} is allowed",
+                planned,
+                allowed,
+                self.limiter.capacity()
+            )));
+        }
+
+        let stats: Arc<Mutex<HashMap<String, JobStats>>> = Arc::default();
+        for job in &self.jobs {
+            let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+            if stats
+                .insert(job.name.clone(), JobStats::default())
+                .is_some()
+            {
+                return Err(HyperliquidError::Config(format!(
+                    "duplicate job name: {}",
+                    job.name
+                )));
+            }
+        }
+
+        let tasks = self
+            .jobs
+            .into_iter()
+            .map(|job| {
+                let offset = job.interval.mul_f64(self.jitter * rand::random::<f64>());
+                tokio::spawn(run_job(
+                    job,
+                    offset,
+                    self.limiter.clone(),
+                    self.clock.clone(),
+                    self.health.clone(),
+                    stats.clone(),
+                ))
+            })
+            .collect();
+        Ok(SchedulerHandle { tasks, stats })
+    }
+}
+
+async fn run_job(
+    job: Job,
+    offset: Duration,
+    limiter: RateLimiter,
+    clock: Arc<dyn Clock>,
+    health: Option<ExchangeHealth>,
+    stats: Arc<Mutex<HashMap<String, JobStats>>>,
+) {
+    let mut ticks = tokio::time::interval_at(Instant::now() + offset, job.interval);
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        ticks.tick().await;
+        if !job.exempt && health.as_ref().is_some_and(|health| !health.is_available()) {
+            let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+            stats.entry(job.name.clone()).or_default().skipped += 1;
+            continue;
+        }
+        limiter.acquire(job.weight).await;
+
+        let result = (job.run)().await;
+        let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = stats.entry(job.name.clone()).or_default();
+        match result {
+            Ok(()) => {
+                limiter.record_success();
+                entry.runs += 1;
+                entry.last_run = Some(clock.now_ms() as i64);
+            }
+            Err(e) => match rate_limit_retry_after(&e) {
+                Some(retry_after) => {
+                    let pause = limiter.back_off(retry_after);
+                    entry.rate_limited += 1;
+                    warn!(
+                        "Job {} rate limited; pausing all jobs for {:?}",
+                        job.name, pause
+                    );
+                }
+                None => {
+                    entry.failures += 1;
+                    warn!("Job {} failed: {}", job.name, e);
+                }
+            },
+        }
+    }
+}
+
+/// Running jobs; dropping the handle stops them
+#[derive(Debug)]
+pub struct SchedulerHandle {
+    tasks: Vec<JoinHandle<()>>,
+    stats: Arc<Mutex<HashMap<String, JobStats>>>,
+}
+
+impl SchedulerHandle {
+    /// Counters by job name
+    pub fn stats(&self) -> HashMap<String, JobStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Stop all jobs
+    pub fn shutdown(self) {}
+}
+
+impl Drop for SchedulerHandle {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
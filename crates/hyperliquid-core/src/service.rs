@@ -0,0 +1,411 @@
+//! Embedding SDK clients in an axum service
+//!
+//! An [`SdkState`] bundles the clients a service shares between handlers
+//! (an [`InfoClient`], an [`ExchangeClient`], a [`WebSocketClient`] and its
+//! [`FeedLatencyTracker`]) with named [`ReadinessGate`]s. Then:
+//!
+//! - [`SdkState::layer`] makes it available to handlers, which extract it
+//!   directly or through the [`Info`] and [`Exchange`] extractors;
+//! - [`SdkRouterExt::with_sdk`] adds `/health`, reporting the stream's
+//!   connectivity, and `/ready`, which answers 503 until the stream is
+//!   connected and every gate is open;
+//! - [`SdkState::require_ready`] is a tower layer answering 503 to every
+//!   request while the service isn't ready.
+//!
+//! ```no_run
+//! # async fn example(info: hyperliquid_core::InfoClient, ws: hyperliquid_core::stream::WebSocketClient) {
+//! use std::sync::Arc;
+//! use axum::routing::get;
+//! use axum::Router;
+//! use hyperliquid_core::service::{Info, SdkRouterExt, SdkState};
+//!
+//! async fn meta(Info(info): Info) -> String {
+//!     format!("{} assets", info.meta("").await.map(|m| m.universe.len()).unwrap_or(0))
+//! }
+//!
+//! let state = SdkState::new().with_info(info).with_websocket(Arc::new(ws));
+//! let warmed_up = state.gate("warmup");
+//! let app = Router::new()
+//!     .route("/meta", get(meta).layer(state.require_ready()))
+//!     .with_sdk(state);
+//! warmed_up.open();
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
+//! axum::serve(listener, app).await.unwrap();
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+
+use axum::extract::{FromRequestParts, Request};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Extension, Json, Router};
+use futures::future::BoxFuture;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tower::{Layer, Service};
+
+use crate::exchange::ExchangeClient;
+use crate::info::InfoClient;
+use crate::stream::{BufferStats, FeedLatencyTracker, LatencyStats, WebSocketClient};
+
+/// Path of the health endpoint added by [`SdkRouterExt::with_sdk`]
+pub const HEALTH_PATH: &str = "/health";
+
+/// Path of the readiness endpoint added by [`SdkRouterExt::with_sdk`]
+pub const READY_PATH: &str = "/ready";
+
+type Gates = Arc<Mutex<BTreeMap<String, bool>>>;
+
+fn lock(gates: &Gates) -> MutexGuard<'_, BTreeMap<String, bool>> {
+    gates.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Named condition the service waits on before reporting ready
+#[derive(Clone)]
+pub struct ReadinessGate {
+    name: String,
+    gates: Gates,
+}
+
+impl std::fmt::Debug for ReadinessGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReadinessGate")
+            .field("name", &self.name)
+            .field("open", &self.is_open())
+            .finish()
+    }
+}
+
+impl ReadinessGate {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn open(&self) {
+        lock(&self.gates).insert(self.name.clone(), true);
+    }
+
+    pub fn close(&self) {
+        lock(&self.gates).insert(self.name.clone(), false);
+    }
+
+    pub fn is_open(&self) -> bool {
+        lock(&self.gates).get(&self.name).copied().unwrap_or(false)
+    }
+}
+
+/// State of the websocket stream
+#[derive(Debug, Clone)]
+pub struct StreamStats {
+    pub connected: bool,
+    pub subscriptions: usize,
+    pub pending_posts: usize,
+    /// Present when the circular buffer is enabled
+    pub buffer: Option<BufferStats>,
+    /// Feed latency by channel, when a tracker is attached
+    pub latency: BTreeMap<String, LatencyStats>,
+}
+
+impl StreamStats {
+    fn to_json(&self) -> Value {
+        let latency: BTreeMap<_, _> = self
+            .latency
+            .iter()
+            .map(|(channel, stats)| {
+                (
+                    channel.clone(),
+                    json!({
+                        "count": stats.count,
+                        "last_ms": stats.last.as_secs_f64() * 1000.0,
+                        "mean_ms": stats.mean.as_secs_f64() * 1000.0,
+                        "max_ms": stats.max.as_secs_f64() * 1000.0,
+                    }),
+                )
+            })
+            .collect();
+        json!({
+            "connected": self.connected,
+            "subscriptions": self.subscriptions,
+            "pending_posts": self.pending_posts,
+            "buffered": self.buffer.as_ref().map(|buffer| buffer.current_size),
+            "dropped": self.buffer.as_ref().map(|buffer| buffer.messages_dropped),
+            "latency": latency,
+        })
+    }
+}
+
+/// Whether the service can take traffic, and why not
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    /// Stream connectivity, when a websocket client is attached
+    pub websocket: Option<bool>,
+    pub gates: BTreeMap<String, bool>,
+}
+
+/// SDK clients shared by a service's handlers
+#[derive(Clone, Default)]
+pub struct SdkState {
+    info: Option<InfoClient>,
+    exchange: Option<ExchangeClient>,
+    websocket: Option<Arc<WebSocketClient>>,
+    latency: Option<FeedLatencyTracker>,
+    gates: Gates,
+}
+
+impl std::fmt::Debug for SdkState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SdkState")
+            .field("info", &self.info.is_some())
+            .field("exchange", &self.exchange.is_some())
+            .field("websocket", &self.websocket.is_some())
+            .field("gates", &*lock(&self.gates))
+            .finish()
+    }
+}
+
+impl SdkState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_info(mut self, info: InfoClient) -> Self {
+        self.info = Some(info);
+        self
+    }
+
+    pub fn with_exchange(mut self, exchange: ExchangeClient) -> Self {
+        self.exchange = Some(exchange);
+        self
+    }
+
+    /// Stream whose connectivity gates readiness
+    pub fn with_websocket(mut self, websocket: Arc<WebSocketClient>) -> Self {
+        self.websocket = Some(websocket);
+        self
+    }
+
+    /// Tracker whose per-channel latency is reported by `/health`
+    pub fn with_latency_tracker(mut self, tracker: FeedLatencyTracker) -> Self {
+        self.latency = Some(tracker);
+        self
+    }
+
+    pub fn info(&self) -> Option<&InfoClient> {
+        self.info.as_ref()
+    }
+
+    pub fn exchange(&self) -> Option<&ExchangeClient> {
+        self.exchange.as_ref()
+    }
+
+    pub fn websocket(&self) -> Option<&Arc<WebSocketClient>> {
+        self.websocket.as_ref()
+    }
+
+    /// Register a gate, closed until opened, that readiness waits on
+    ///
+    /// Registering an existing name returns a handle to the same gate.
+    pub fn gate(&self, name: impl Into<String>) -> ReadinessGate {
+        let name = name.into();
+        lock(&self.gates).entry(name.clone()).or_insert(false);
+        ReadinessGate {
+            name,
+            gates: Arc::clone(&self.gates),
+        }
+    }
+
+    /// Stream state, if a websocket client is attached
+    pub async fn stream_stats(&self) -> Option<StreamStats> {
+        let websocket = self.websocket.as_ref()?;
+        Some(StreamStats {
+            connected: websocket.is_connected().await,
+            subscriptions: websocket.subscriptions().await.len(),
+            pending_posts: websocket.pending_post_count(),
+            buffer: websocket.buffer_stats(),
+            latency: self
+                .latency
+                .as_ref()
+                .map(FeedLatencyTracker::all)
+                .unwrap_or_default(),
+        })
+    }
+
+    pub async fn readiness(&self) -> Readiness {
+        let websocket = match &self.websocket {
+            Some(websocket) => Some(websocket.is_connected().await),
+            None => None,
+        };
+        let gates = lock(&self.gates).clone();
+        Readiness {
+            ready: websocket.unwrap_or(true) && gates.values().all(|open| *open),
+            websocket,
+            gates,
+        }
+    }
+
+    /// Layer making the state available to handlers
+    pub fn layer(&self) -> Extension<SdkState> {
+        Extension(self.clone())
+    }
+
+    /// Layer answering 503 while the service isn't ready
+    pub fn require_ready(&self) -> RequireReadyLayer {
+        RequireReadyLayer {
+            state: self.clone(),
+        }
+    }
+}
+
+fn unavailable(message: &str) -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(json!({ "error": message })),
+    )
+        .into_response()
+}
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for SdkState {
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<SdkState>()
+            .cloned()
+            .ok_or((StatusCode::INTERNAL_SERVER_ERROR, "SdkState layer missing"))
+    }
+}
+
+/// Extracts the shared [`InfoClient`]; 503 when none is configured
+#[derive(Clone)]
+pub struct Info(pub InfoClient);
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Info {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let sdk = SdkState::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        sdk.info
+            .map(Info)
+            .ok_or_else(|| unavailable("info client not configured"))
+    }
+}
+
+/// Extracts the shared [`ExchangeClient`]; 503 when none is configured
+#[derive(Clone)]
+pub struct Exchange(pub ExchangeClient);
+
+#[axum::async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for Exchange {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let sdk = SdkState::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        sdk.exchange
+            .map(Exchange)
+            .ok_or_else(|| unavailable("exchange client not configured"))
+    }
+}
+
+async fn health(sdk: SdkState) -> Json<Value> {
+    let stream = sdk.stream_stats().await;
+    let status = match &stream {
+        Some(stream) if !stream.connected => "degraded",
+        _ => "ok",
+    };
+    Json(json!({
+        "status": status,
+        "websocket": stream.as_ref().map(StreamStats::to_json),
+    }))
+}
+
+async fn ready(sdk: SdkState) -> (StatusCode, Json<Readiness>) {
+    let readiness = sdk.readiness().await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
+
+/// Mounting SDK state and its health endpoints on a router
+pub trait SdkRouterExt {
+    /// Add [`HEALTH_PATH`] and [`READY_PATH`] and layer `state` over the router
+    fn with_sdk(self, state: SdkState) -> Self;
+}
+
+impl<S> SdkRouterExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn with_sdk(self, state: SdkState) -> Self {
+        self.route(HEALTH_PATH, get(health))
+            .route(READY_PATH, get(ready))
+            .layer(state.layer())
+    }
+}
+
+/// Tower layer of [`RequireReady`]
+#[derive(Debug, Clone)]
+pub struct RequireReadyLayer {
+    state: SdkState,
+}
+
+impl<S> Layer<S> for RequireReadyLayer {
+    type Service = RequireReady<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireReady {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Service answering 503 instead of calling `inner` while not ready
+#[derive(Debug, Clone)]
+pub struct RequireReady<S> {
+    inner: S,
+    state: SdkState,
+}
+
+impl<S> Service<Request> for RequireReady<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the instance that was polled ready and leave a fresh clone
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let state = self.state.clone();
+        Box::pin(async move {
+            if !state.readiness().await.ready {
+                return Ok(unavailable("not ready"));
+            }
+            inner.call(request).await
+        })
+    }
+}
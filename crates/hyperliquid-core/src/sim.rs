@@ -0,0 +1,655 @@
+//! Paper-trading simulator
+//!
+//! [`SimExchange`] accepts the same wire-format actions as
+//! [`ExchangeClient::post_signed_action`](crate::ExchangeClient::post_signed_action)
+//! and answers with the same response shapes, but matches orders locally
+//! against an L2 book instead of sending them to the exchange. Books come
+//! from the live `l2Book` stream ([`SimExchange::attach`]) or from recorded
+//! snapshots passed to [`SimExchange::apply_l2_book`].
+//!
+//! The matching model is deliberately simple:
+//!
+//! - Taking orders walk the book up to their limit price and consume the
+//!   liquidity they take until the next snapshot replaces it.
+//! - Resting orders fill at their own price, as maker, once a later snapshot
+//!   trades through them. Queue position is not modelled.
+//! - Every action is delayed by [`SimConfig::latency`] before it is matched.
+//!
+//! Fills and order updates are published in the `userFills` and
+//! `orderUpdates` stream formats, so an [`OrderManager`](crate::oms::OrderManager)
+//! or [`PositionTracker`](crate::positions::PositionTracker) can follow the
+//! simulated account the same way it follows a real one. The simulator also
+//! implements [`Venue`], so execution algorithms run against it unchanged.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
+
+use crate::clock::{self, Clock};
+use crate::crypto::Wallet;
+use crate::error::HyperliquidError;
+use crate::execution::{ChildOrder, ChildResult, Venue};
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketClient;
+use crate::stream::WebSocketResponse;
+use crate::types::precision::float_to_wire;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+use crate::types::{L2BookSnapshot, Meta, OrderLevel};
+
+/// Capacity of the fill and order update channels
+const EVENT_CAPACITY: usize = 1024;
+
+/// Sizes below this are treated as zero
+const SIZE_EPSILON: f64 = 1e-9;
+
+/// Account reported in simulated stream payloads unless overridden
+const DEFAULT_USER: &str = "0x0000000000000000000000000000000000000000";
+
+/// Simulator settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimConfig {
+    /// Delay applied to every action before it is matched
+    pub latency: Duration,
+    /// Fee rate charged on taking fills
+    pub taker_fee: f64,
+    /// Fee rate charged on resting fills
+    pub maker_fee: f64,
+}
+
+impl Default for SimConfig {
+    /// No latency and the base-tier perp fee schedule
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            taker_fee: 0.00045,
+            maker_fee: 0.00015,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Set the action latency
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Set the taker and maker fee rates
+    pub fn with_fees(mut self, taker_fee: f64, maker_fee: f64) -> Self {
+        self.taker_fee = taker_fee;
+        self.maker_fee = maker_fee;
+        self
+    }
+}
+
+/// Order resting in the simulator
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimOrder {
+    pub oid: u64,
+    pub coin: String,
+    pub is_buy: bool,
+    pub limit_px: f64,
+    /// Unfilled size
+    pub sz: f64,
+    pub orig_sz: f64,
+    pub cloid: Option<String>,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Level {
+    px: f64,
+    sz: f64,
+}
+
+/// Both sides of a book, best level first
+#[derive(Debug, Default)]
+struct Book {
+    bids: Vec<Level>,
+    asks: Vec<Level>,
+}
+
+impl Book {
+    /// Levels a buy (`is_buy`) or sell takes from
+    fn opposite(&mut self, is_buy: bool) -> &mut Vec<Level> {
+        if is_buy {
+            &mut self.asks
+        } else {
+            &mut self.bids
+        }
+    }
+}
+
+/// Whether `px` is at or through `limit_px` for the given side
+fn crosses(is_buy: bool, px: f64, limit_px: f64) -> bool {
+    if is_buy {
+        px <= limit_px
+    } else {
+        px >= limit_px
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    books: HashMap<String, Book>,
+    resting: BTreeMap<u64, SimOrder>,
+    next_oid: u64,
+    next_tid: u64,
+    fees_paid: f64,
+}
+
+/// Order action entry in wire format
+#[derive(Debug, Deserialize)]
+struct WireOrder {
+    a: u32,
+    b: bool,
+    p: String,
+    s: String,
+    #[serde(default)]
+    c: Option<String>,
+    t: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireCancel {
+    #[serde(alias = "asset")]
+    a: u32,
+    #[serde(default)]
+    o: Option<u64>,
+    #[serde(default)]
+    cloid: Option<String>,
+}
+
+/// Local exchange matching orders against streamed or replayed books
+#[derive(Debug, Clone)]
+pub struct SimExchange {
+    coins: Arc<Vec<String>>,
+    config: SimConfig,
+    user: String,
+    state: Arc<Mutex<State>>,
+    fills: broadcast::Sender<Value>,
+    order_updates: broadcast::Sender<Value>,
+    clock: Arc<dyn Clock>,
+}
+
+impl SimExchange {
+    /// Create a simulator for the perp assets in `meta`
+    pub fn new(meta: &Meta) -> Self {
+        let (fills, _) = broadcast::channel(EVENT_CAPACITY);
+        let (order_updates, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            coins: Arc::new(meta.universe.iter().map(|a| a.name.clone()).collect()),
+            config: SimConfig::default(),
+            user: DEFAULT_USER.to_string(),
+            state: Arc::new(Mutex::new(State {
+                books: HashMap::new(),
+                resting: BTreeMap::new(),
+                next_oid: 1,
+                next_tid: 1,
+                fees_paid: 0.0,
+            })),
+            fills,
+            order_updates,
+            clock: clock::system(),
+        }
+    }
+
+    /// Set latency and fees
+    pub fn with_config(mut self, config: SimConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the account reported in simulated stream payloads
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = user.into();
+        self
+    }
+
+    /// Clock stamping fills and order updates; replays pass one that
+    /// follows the recorded data
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn config(&self) -> &SimConfig {
+        &self.config
+    }
+
+    /// Fills in `userFills` stream format
+    pub fn user_fills(&self) -> broadcast::Receiver<Value> {
+        self.fills.subscribe()
+    }
+
+    /// Order status changes in `orderUpdates` stream format
+    pub fn order_updates(&self) -> broadcast::Receiver<Value> {
+        self.order_updates.subscribe()
+    }
+
+    /// Orders currently resting, oldest first
+    pub fn open_orders(&self) -> Vec<SimOrder> {
+        self.lock().resting.values().cloned().collect()
+    }
+
+    /// Total fees charged so far
+    pub fn fees_paid(&self) -> f64 {
+        self.lock().fees_paid
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn coin(&self, asset: u32) -> Option<&str> {
+        self.coins.get(asset as usize).map(String::as_str)
+    }
+
+    /// Replace a coin's book with an `l2Book` payload and fill resting orders
+    /// it trades through
+    pub fn apply_l2_book(&self, data: &Value) {
+        let snapshot: L2BookSnapshot = match serde_json::from_value(data.clone()) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("Ignoring malformed l2Book payload: {}", e);
+                return;
+            }
+        };
+        let side = |levels: &[OrderLevel]| -> Vec<Level> {
+            levels
+                .iter()
+                .filter_map(|level| {
+                    Some(Level {
+                        px: level.px.parse().ok()?,
+                        sz: level.sz.parse().ok()?,
+                    })
+                })
+                .collect()
+        };
+        let book = Book {
+            bids: side(&snapshot.levels[0][..]),
+            asks: side(&snapshot.levels[1][..]),
+        };
+
+        let mut state = self.lock();
+        state.books.insert(snapshot.coin.clone(), book);
+        self.match_resting(&mut state, &snapshot.coin);
+    }
+
+    /// Feed the simulator from `coin`'s live `l2Book` stream
+    ///
+    /// Registers the handler for the subscription, replacing any existing
+    /// handler for it.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient, coin: &str) -> Result<(), HyperliquidError> {
+        let subscription = Subscription::L2Book {
+            coin: coin.to_string(),
+        };
+        let sim = self.clone();
+        let coin = coin.to_string();
+        ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            if response.channel == "l2Book"
+                && response.data.get("coin").and_then(Value::as_str) == Some(&coin)
+            {
+                sim.apply_l2_book(&response.data);
+            }
+        })
+        .await;
+        ws.subscribe(subscription)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))
+    }
+
+    /// Handle a wire-format action as the exchange would
+    ///
+    /// Mirrors [`ExchangeClient::post_signed_action`](crate::ExchangeClient::post_signed_action);
+    /// nothing is signed. Supports `order`, `cancel` and `cancelByCloid`;
+    /// other actions get an error response.
+    pub async fn post_signed_action(
+        &self,
+        action: Value,
+        _wallet: &Wallet,
+        _vault_address: Option<&str>,
+    ) -> Result<Value, HyperliquidError> {
+        self.submit(action).await
+    }
+
+    async fn submit(&self, action: Value) -> Result<Value, HyperliquidError> {
+        if !self.config.latency.is_zero() {
+            tokio::time::sleep(self.config.latency).await;
+        }
+        let action_type = action.get("type").and_then(Value::as_str).ok_or_else(|| {
+            HyperliquidError::Validation("action has no `type` field".to_string())
+        })?;
+
+        match action_type {
+            "order" => {
+                let orders: Vec<WireOrder> = serde_json::from_value(
+                    action.get("orders").cloned().unwrap_or_else(|| json!([])),
+                )?;
+                let statuses = orders.iter().map(|order| self.place(order)).collect();
+                Ok(ok_response("order", statuses))
+            }
+            "cancel" | "cancelByCloid" => {
+                let cancels: Vec<WireCancel> = serde_json::from_value(
+                    action.get("cancels").cloned().unwrap_or_else(|| json!([])),
+                )?;
+                let statuses = cancels.iter().map(|cancel| self.cancel(cancel)).collect();
+                Ok(ok_response("cancel", statuses))
+            }
+            other => Ok(json!({
+                "status": "err",
+                "response": format!("Action not supported by the simulator: {}", other),
+            })),
+        }
+    }
+
+    /// Match one order and return its `statuses` entry
+    fn place(&self, order: &WireOrder) -> Value {
+        let Some(coin) = self.coin(order.a).map(str::to_string) else {
+            return json!({"error": format!("Unknown asset: {}", order.a)});
+        };
+        let (Ok(limit_px), Ok(sz)) = (order.p.parse::<f64>(), order.s.parse::<f64>()) else {
+            return json!({"error": "Invalid price or size"});
+        };
+        if !(limit_px > 0.0 && sz > 0.0) {
+            return json!({"error": "Order has invalid price or size"});
+        }
+        let Some(tif) = order.t.pointer("/limit/tif").and_then(Value::as_str) else {
+            return json!({"error": "Only limit orders are simulated"});
+        };
+
+        let mut state = self.lock();
+        let oid = state.next_oid;
+        state.next_oid += 1;
+        let best = state
+            .books
+            .get_mut(&coin)
+            .and_then(|book| book.opposite(order.b).first().map(|level| level.px));
+        let crossing = best.is_some_and(|px| crosses(order.b, px, limit_px));
+
+        if tif == "Alo" && crossing {
+            return json!({"error": format!(
+                "Post only order would have immediately matched, bbo was {}. asset={}",
+                wire(best.unwrap_or_default()),
+                order.a
+            )});
+        }
+
+        let mut sim_order = SimOrder {
+            oid,
+            coin: coin.clone(),
+            is_buy: order.b,
+            limit_px,
+            sz,
+            orig_sz: sz,
+            cloid: order.c.clone(),
+            timestamp: self.clock.now_ms(),
+        };
+        let (filled, notional) = self.take(&mut state, &mut sim_order);
+
+        if sim_order.sz <= SIZE_EPSILON {
+            self.publish_update(&sim_order, "filled");
+            return filled_status(oid, filled, notional);
+        }
+        if tif == "Ioc" {
+            if filled <= SIZE_EPSILON {
+                return json!({"error": format!(
+                    "Order could not immediately match against any resting orders. asset={}",
+                    order.a
+                )});
+            }
+            self.publish_update(&sim_order, "canceled");
+            return filled_status(oid, filled, notional);
+        }
+
+        self.publish_update(&sim_order, "open");
+        state.resting.insert(oid, sim_order);
+        json!({"resting": {"oid": oid}})
+    }
+
+    /// Take liquidity for an incoming order, returning filled size and notional
+    fn take(&self, state: &mut State, order: &mut SimOrder) -> (f64, f64) {
+        let Some(book) = state.books.get_mut(&order.coin) else {
+            return (0.0, 0.0);
+        };
+        let levels = book.opposite(order.is_buy);
+        let mut fills = Vec::new();
+        while order.sz > SIZE_EPSILON {
+            let Some(level) = levels.first_mut() else {
+                break;
+            };
+            if !crosses(order.is_buy, level.px, order.limit_px) {
+                break;
+            }
+            let sz = level.sz.min(order.sz);
+            fills.push((level.px, sz));
+            order.sz -= sz;
+            level.sz -= sz;
+            if level.sz <= SIZE_EPSILON {
+                levels.remove(0);
+            }
+        }
+
+        let (mut filled, mut notional) = (0.0, 0.0);
+        let payload: Vec<Value> = fills
+            .into_iter()
+            .map(|(px, sz)| {
+                filled += sz;
+                notional += px * sz;
+                self.fill(state, order, px, sz, true)
+            })
+            .collect();
+        if !payload.is_empty() {
+            let _ = self
+                .fills
+                .send(json!({"user": self.user, "fills": payload}));
+        }
+        (filled, notional)
+    }
+
+    /// Fill resting orders on `coin` that the current book trades through
+    fn match_resting(&self, state: &mut State, coin: &str) {
+        let oids: Vec<u64> = state
+            .resting
+            .values()
+            .filter(|order| order.coin == coin)
+            .map(|order| order.oid)
+            .collect();
+        let mut payload = Vec::new();
+
+        for oid in oids {
+            let Some(mut order) = state.resting.remove(&oid) else {
+                continue;
+            };
+            let mut filled = 0.0;
+            if let Some(book) = state.books.get_mut(coin) {
+                let levels = book.opposite(order.is_buy);
+                while order.sz - filled > SIZE_EPSILON {
+                    let Some(level) = levels.first_mut() else {
+                        break;
+                    };
+                    if !crosses(order.is_buy, level.px, order.limit_px) {
+                        break;
+                    }
+                    let sz = level.sz.min(order.sz - filled);
+                    filled += sz;
+                    level.sz -= sz;
+                    if level.sz <= SIZE_EPSILON {
+                        levels.remove(0);
+                    }
+                }
+            }
+
+            if filled > SIZE_EPSILON {
+                // Makers fill at their own price
+                payload.push(self.fill(state, &order, order.limit_px, filled, false));
+                order.sz -= filled;
+            }
+            if order.sz <= SIZE_EPSILON {
+                debug!("Simulated order {} filled", oid);
+                self.publish_update(&order, "filled");
+            } else {
+                state.resting.insert(oid, order);
+            }
+        }
+
+        if !payload.is_empty() {
+            let _ = self
+                .fills
+                .send(json!({"user": self.user, "fills": payload}));
+        }
+    }
+
+    /// Charge the fee for a fill and build its `userFills` entry
+    fn fill(&self, state: &mut State, order: &SimOrder, px: f64, sz: f64, crossed: bool) -> Value {
+        let rate = if crossed {
+            self.config.taker_fee
+        } else {
+            self.config.maker_fee
+        };
+        let fee = px * sz * rate;
+        state.fees_paid += fee;
+        let tid = state.next_tid;
+        state.next_tid += 1;
+        json!({
+            "coin": order.coin,
+            "px": wire(px),
+            "sz": wire(sz),
+            "side": if order.is_buy { "B" } else { "A" },
+            "time": self.clock.now_ms(),
+            "oid": order.oid,
+            "tid": tid,
+            "crossed": crossed,
+            "fee": wire(fee),
+            "feeToken": "USDC",
+            "closedPnl": "0",
+        })
+    }
+
+    /// Cancel one order and return its `statuses` entry
+    fn cancel(&self, cancel: &WireCancel) -> Value {
+        let coin = self.coin(cancel.a);
+        let mut state = self.lock();
+        let oid = state
+            .resting
+            .values()
+            .find(|order| {
+                Some(order.coin.as_str()) == coin
+                    && (cancel.o == Some(order.oid)
+                        || (cancel.cloid.is_some() && cancel.cloid == order.cloid))
+            })
+            .map(|order| order.oid);
+
+        match oid.and_then(|oid| state.resting.remove(&oid)) {
+            Some(order) => {
+                self.publish_update(&order, "canceled");
+                json!("success")
+            }
+            None => json!({"error": "Order was never placed, already canceled, or filled."}),
+        }
+    }
+
+    fn publish_update(&self, order: &SimOrder, status: &str) {
+        let _ = self.order_updates.send(json!([{
+            "order": {
+                "coin": order.coin,
+                "side": if order.is_buy { "B" } else { "A" },
+                "limitPx": wire(order.limit_px),
+                "sz": wire(order.sz.max(0.0)),
+                "oid": order.oid,
+                "timestamp": order.timestamp,
+                "origSz": wire(order.orig_sz),
+                "cloid": order.cloid,
+            },
+            "status": status,
+            "statusTimestamp": self.clock.now_ms(),
+        }]));
+    }
+}
+
+impl Venue for SimExchange {
+    async fn place(&self, order: &ChildOrder) -> Result<ChildResult, HyperliquidError> {
+        let asset = self
+            .coins
+            .iter()
+            .position(|coin| *coin == order.coin)
+            .ok_or_else(|| HyperliquidError::Validation(format!("unknown coin: {}", order.coin)))?;
+        let action = json!({
+            "type": "order",
+            "orders": [{
+                "a": asset,
+                "b": order.is_buy,
+                "p": wire(order.limit_px),
+                "s": wire(order.sz),
+                "r": false,
+                "t": {"limit": {"tif": order.tif}},
+            }],
+            "grouping": "na",
+        });
+        let response = self.submit(action).await?;
+        let status = response
+            .pointer("/response/data/statuses/0")
+            .cloned()
+            .unwrap_or(Value::Null);
+        let number = |value: Option<&Value>| {
+            value
+                .and_then(Value::as_str)
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        Ok(if let Some(filled) = status.get("filled") {
+            ChildResult::Filled {
+                sz: number(filled.get("totalSz")),
+                avg_px: number(filled.get("avgPx")),
+            }
+        } else if let Some(oid) = status.pointer("/resting/oid").and_then(Value::as_u64) {
+            ChildResult::Resting { oid }
+        } else {
+            let reason = status
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            ChildResult::Rejected(reason.to_string())
+        })
+    }
+
+    async fn cancel(&self, coin: &str, oid: u64) -> Result<(), HyperliquidError> {
+        let asset = self
+            .coins
+            .iter()
+            .position(|c| c == coin)
+            .unwrap_or(usize::MAX);
+        let action = json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]});
+        self.submit(action).await?;
+        Ok(())
+    }
+}
+
+fn ok_response(kind: &str, statuses: Vec<Value>) -> Value {
+    json!({
+        "status": "ok",
+        "response": {"type": kind, "data": {"statuses": statuses}},
+    })
+}
+
+fn filled_status(oid: u64, filled: f64, notional: f64) -> Value {
+    json!({"filled": {
+        "oid": oid,
+        "totalSz": wire(filled),
+        "avgPx": wire(notional / filled),
+    }})
+}
+
+/// Format a number for the wire, rounded to 8 decimals
+fn wire(value: f64) -> String {
+    let rounded = (value * 1e8).round() / 1e8;
+    float_to_wire(rounded).unwrap_or_else(|_| rounded.to_string())
+}
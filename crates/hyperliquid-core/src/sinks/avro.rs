@@ -0,0 +1,109 @@
+//! Avro schemas and encoding for [`SinkEvent`]s
+//!
+//! Each event kind has its own record schema, so a topic (one per kind)
+//! carries a single schema that can be registered as-is.
+
+use std::sync::OnceLock;
+
+use apache_avro::{to_avro_datum, Schema};
+
+use super::event::SinkEvent;
+use crate::error::HyperliquidError;
+
+/// Schema of `trades` payloads
+pub const TRADE_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "Trade",
+    "namespace": "xyz.hyperliquid",
+    "fields": [
+        {"name": "coin", "type": "string"},
+        {"name": "side", "type": {"type": "enum", "name": "Side", "symbols": ["buy", "sell"]}},
+        {"name": "px", "type": "double"},
+        {"name": "sz", "type": "double"},
+        {"name": "time", "type": "long"},
+        {"name": "tid", "type": "long"}
+    ]
+}"#;
+
+/// Schema of `book` payloads
+pub const BOOK_DELTA_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "BookDelta",
+    "namespace": "xyz.hyperliquid",
+    "fields": [
+        {"name": "coin", "type": "string"},
+        {"name": "time", "type": "long"},
+        {"name": "snapshot", "type": "boolean"},
+        {"name": "bids", "type": {"type": "array", "items": {
+            "type": "record",
+            "name": "LevelChange",
+            "fields": [
+                {"name": "px", "type": "double"},
+                {"name": "sz", "type": "double"}
+            ]
+        }}},
+        {"name": "asks", "type": {"type": "array", "items": "LevelChange"}}
+    ]
+}"#;
+
+/// Schema of `fills` payloads
+pub const FILL_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "Fill",
+    "namespace": "xyz.hyperliquid",
+    "fields": [
+        {"name": "user", "type": "string"},
+        {"name": "coin", "type": "string"},
+        {"name": "side", "type": {"type": "enum", "name": "Side", "symbols": ["buy", "sell"]}},
+        {"name": "px", "type": "double"},
+        {"name": "sz", "type": "double"},
+        {"name": "fee", "type": "double"},
+        {"name": "closed_pnl", "type": "double"},
+        {"name": "crossed", "type": "boolean"},
+        {"name": "oid", "type": "long"},
+        {"name": "tid", "type": "long"},
+        {"name": "time", "type": "long"}
+    ]
+}"#;
+
+struct Schemas {
+    trade: Schema,
+    book_delta: Schema,
+    fill: Schema,
+}
+
+fn schemas() -> &'static Schemas {
+    static SCHEMAS: OnceLock<Schemas> = OnceLock::new();
+    SCHEMAS.get_or_init(|| Schemas {
+        trade: Schema::parse_str(TRADE_SCHEMA).expect("valid trade schema"),
+        book_delta: Schema::parse_str(BOOK_DELTA_SCHEMA).expect("valid book delta schema"),
+        fill: Schema::parse_str(FILL_SCHEMA).expect("valid fill schema"),
+    })
+}
+
+/// Schema of the payloads for `event`'s kind
+pub fn schema_for(event: &SinkEvent) -> &'static Schema {
+    let schemas = schemas();
+    match event {
+        SinkEvent::Trade(_) => &schemas.trade,
+        SinkEvent::BookDelta(_) => &schemas.book_delta,
+        SinkEvent::Fill(_) => &schemas.fill,
+    }
+}
+
+/// Avro binary encoding of `event`, without any container header
+pub fn encode(event: &SinkEvent) -> Result<Vec<u8>, HyperliquidError> {
+    let value = match event {
+        SinkEvent::Trade(e) => apache_avro::to_value(e),
+        SinkEvent::BookDelta(e) => apache_avro::to_value(e),
+        SinkEvent::Fill(e) => apache_avro::to_value(e),
+    }
+    .map_err(avro_error)?;
+    let schema = schema_for(event);
+    let value = value.resolve(schema).map_err(avro_error)?;
+    to_avro_datum(schema, value).map_err(avro_error)
+}
+
+fn avro_error(e: apache_avro::Error) -> HyperliquidError {
+    HyperliquidError::Validation(format!("Avro encoding failed: {}", e))
+}
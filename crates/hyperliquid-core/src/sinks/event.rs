@@ -0,0 +1,249 @@
+//! Normalized events published by sinks
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::stream::WebSocketResponse;
+
+/// Trade side from the aggressor's point of view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+impl Side {
+    fn from_wire(side: &str) -> Self {
+        if side == "B" {
+            Side::Buy
+        } else {
+            Side::Sell
+        }
+    }
+}
+
+/// Public trade
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeEvent {
+    pub coin: String,
+    pub side: Side,
+    pub px: f64,
+    pub sz: f64,
+    /// Exchange time in ms
+    pub time: u64,
+    pub tid: u64,
+}
+
+/// Price level; `sz` is 0 when the level was removed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LevelChange {
+    pub px: f64,
+    pub sz: f64,
+}
+
+/// Levels of a book that changed since the previous update
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookDeltaEvent {
+    pub coin: String,
+    pub time: u64,
+    /// Whether this is the first update seen for the coin, carrying every level
+    pub snapshot: bool,
+    pub bids: Vec<LevelChange>,
+    pub asks: Vec<LevelChange>,
+}
+
+/// Fill of the subscribed account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FillEvent {
+    pub user: String,
+    pub coin: String,
+    pub side: Side,
+    pub px: f64,
+    pub sz: f64,
+    pub fee: f64,
+    pub closed_pnl: f64,
+    /// Whether the fill took liquidity
+    pub crossed: bool,
+    pub oid: u64,
+    pub tid: u64,
+    pub time: u64,
+}
+
+/// Event published to a sink
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SinkEvent {
+    Trade(TradeEvent),
+    BookDelta(BookDeltaEvent),
+    Fill(FillEvent),
+}
+
+impl SinkEvent {
+    /// Kind used in topic names: `trades`, `book` or `fills`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SinkEvent::Trade(_) => "trades",
+            SinkEvent::BookDelta(_) => "book",
+            SinkEvent::Fill(_) => "fills",
+        }
+    }
+
+    pub fn coin(&self) -> &str {
+        match self {
+            SinkEvent::Trade(e) => &e.coin,
+            SinkEvent::BookDelta(e) => &e.coin,
+            SinkEvent::Fill(e) => &e.coin,
+        }
+    }
+}
+
+/// Sizes by price of one side, keyed by the price's bit pattern
+type SideLevels = BTreeMap<u64, (f64, f64)>;
+
+/// Turns websocket messages into [`SinkEvent`]s
+///
+/// Keeps the last book per coin to compute deltas.
+#[derive(Debug, Default)]
+pub struct Normalizer {
+    books: HashMap<String, (SideLevels, SideLevels)>,
+}
+
+impl Normalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events in a `trades`, `l2Book` or `userFills` message
+    ///
+    /// Other channels and fill snapshots (historical fills sent on
+    /// subscription) yield nothing.
+    pub fn normalize(&mut self, response: &WebSocketResponse) -> Vec<SinkEvent> {
+        match response.channel.as_str() {
+            "trades" => trades(&response.data),
+            "l2Book" => self.book(&response.data).into_iter().collect(),
+            "userFills" => fills(&response.data),
+            _ => Vec::new(),
+        }
+    }
+
+    fn book(&mut self, data: &Value) -> Option<SinkEvent> {
+        let coin = data.get("coin")?.as_str()?.to_string();
+        let time = data.get("time").and_then(Value::as_u64).unwrap_or_default();
+        let side = |index: usize| -> SideLevels {
+            data.pointer(&format!("/levels/{}", index))
+                .and_then(Value::as_array)
+                .map(|levels| {
+                    levels
+                        .iter()
+                        .filter_map(|level| {
+                            let px = level.get("px")?.as_str()?.parse::<f64>().ok()?;
+                            let sz = level.get("sz")?.as_str()?.parse::<f64>().ok()?;
+                            Some((px.to_bits(), (px, sz)))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let (bids, asks) = (side(0), side(1));
+
+        let previous = self
+            .books
+            .insert(coin.clone(), (bids.clone(), asks.clone()));
+        let snapshot = previous.is_none();
+        let (prev_bids, prev_asks) = previous.unwrap_or_default();
+        let mut bid_changes = diff(&prev_bids, &bids);
+        let mut ask_changes = diff(&prev_asks, &asks);
+        bid_changes.sort_by(|a, b| b.px.total_cmp(&a.px));
+        ask_changes.sort_by(|a, b| a.px.total_cmp(&b.px));
+        if !snapshot && bid_changes.is_empty() && ask_changes.is_empty() {
+            return None;
+        }
+        Some(SinkEvent::BookDelta(BookDeltaEvent {
+            coin,
+            time,
+            snapshot,
+            bids: bid_changes,
+            asks: ask_changes,
+        }))
+    }
+}
+
+fn diff(previous: &SideLevels, current: &SideLevels) -> Vec<LevelChange> {
+    let mut changes: Vec<LevelChange> = current
+        .iter()
+        .filter(|(key, (_, sz))| previous.get(key).map(|(_, prev)| prev) != Some(sz))
+        .map(|(_, (px, sz))| LevelChange { px: *px, sz: *sz })
+        .collect();
+    changes.extend(
+        previous
+            .iter()
+            .filter(|(key, _)| !current.contains_key(key))
+            .map(|(_, (px, _))| LevelChange { px: *px, sz: 0.0 }),
+    );
+    changes
+}
+
+fn parse(value: Option<&Value>) -> Option<f64> {
+    value?.as_str()?.parse().ok()
+}
+
+fn trades(data: &Value) -> Vec<SinkEvent> {
+    let Some(trades) = data.as_array() else {
+        warn!("Ignoring malformed trades payload");
+        return Vec::new();
+    };
+    trades
+        .iter()
+        .filter_map(|trade| {
+            Some(SinkEvent::Trade(TradeEvent {
+                coin: trade.get("coin")?.as_str()?.to_string(),
+                side: Side::from_wire(trade.get("side")?.as_str()?),
+                px: parse(trade.get("px"))?,
+                sz: parse(trade.get("sz"))?,
+                time: trade.get("time")?.as_u64()?,
+                tid: trade.get("tid").and_then(Value::as_u64).unwrap_or_default(),
+            }))
+        })
+        .collect()
+}
+
+fn fills(data: &Value) -> Vec<SinkEvent> {
+    if data.get("isSnapshot").and_then(Value::as_bool) == Some(true) {
+        return Vec::new();
+    }
+    let user = data
+        .get("user")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let fills = data
+        .get("fills")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    fills
+        .iter()
+        .filter_map(|fill| {
+            Some(SinkEvent::Fill(FillEvent {
+                user: user.clone(),
+                coin: fill.get("coin")?.as_str()?.to_string(),
+                side: Side::from_wire(fill.get("side")?.as_str()?),
+                px: parse(fill.get("px"))?,
+                sz: parse(fill.get("sz"))?,
+                fee: parse(fill.get("fee")).unwrap_or_default(),
+                closed_pnl: parse(fill.get("closedPnl")).unwrap_or_default(),
+                crossed: fill
+                    .get("crossed")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default(),
+                oid: fill.get("oid")?.as_u64()?,
+                tid: fill.get("tid").and_then(Value::as_u64).unwrap_or_default(),
+                time: fill.get("time")?.as_u64()?,
+            }))
+        })
+        .collect()
+}
@@ -0,0 +1,83 @@
+//! [`Sink`] producing to Kafka
+
+use std::time::Duration;
+
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+
+use super::{Sink, SinkFuture};
+use crate::error::HyperliquidError;
+
+/// Produces each event as a Kafka record
+#[derive(Clone)]
+pub struct KafkaSink {
+    producer: FutureProducer,
+    queue_timeout: Duration,
+}
+
+impl std::fmt::Debug for KafkaSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaSink")
+            .field("queue_timeout", &self.queue_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KafkaSink {
+    /// Producer for a comma-separated list of brokers
+    ///
+    /// Uses idempotent delivery so retried records are neither duplicated
+    /// nor reordered within a partition.
+    pub fn new(brokers: &str) -> Result<Self, HyperliquidError> {
+        let mut config = ClientConfig::new();
+        config
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .set("linger.ms", "5");
+        Self::from_config(&config)
+    }
+
+    /// Producer from a complete client configuration (TLS, SASL, ...)
+    pub fn from_config(config: &ClientConfig) -> Result<Self, HyperliquidError> {
+        let producer = config
+            .create()
+            .map_err(|e| HyperliquidError::Config(format!("Kafka producer: {}", e)))?;
+        Ok(Self {
+            producer,
+            queue_timeout: Duration::ZERO,
+        })
+    }
+
+    /// How long to wait for room in the producer queue when it's full
+    /// (default: fail immediately)
+    pub fn with_queue_timeout(mut self, timeout: Duration) -> Self {
+        self.queue_timeout = timeout;
+        self
+    }
+
+    /// Wait for queued records to be delivered
+    pub fn flush(&self, timeout: Duration) -> Result<(), HyperliquidError> {
+        self.producer
+            .flush(timeout)
+            .map_err(|e| HyperliquidError::Network(format!("Kafka flush: {}", e)))
+    }
+}
+
+impl Sink for KafkaSink {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    fn publish<'a>(&'a self, topic: &'a str, key: &'a str, payload: Vec<u8>) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let record = FutureRecord::to(topic).key(key).payload(&payload);
+            self.producer
+                .send(record, self.queue_timeout)
+                .await
+                .map_err(|(e, _)| {
+                    HyperliquidError::Network(format!("Kafka delivery to {}: {}", topic, e))
+                })?;
+            Ok(())
+        })
+    }
+}
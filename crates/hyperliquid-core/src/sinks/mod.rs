@@ -0,0 +1,268 @@
+//! Publishing websocket events to message buses
+//!
+//! A [`Publisher`] turns `trades`, `l2Book` and `userFills` messages into
+//! [`SinkEvent`]s and hands the encoded payloads to a [`Sink`]:
+//! `KafkaSink` with the `kafka` feature, `NatsSink` with the `nats` feature,
+//! or any other implementation of the trait.
+//!
+//! Events go to one topic per kind (`hyperliquid.trades`,
+//! `hyperliquid.book`, `hyperliquid.fills`), optionally suffixed with the
+//! coin, and are keyed by coin (by user for fills) so a partitioned bus keeps
+//! each book's updates in order. Payloads are JSON, or Avro with the `avro`
+//! feature, optionally framed with a schema registry id.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::error::HyperliquidError;
+use crate::stream::{WebSocketClient, WebSocketResponse};
+use crate::types::Subscription;
+
+#[cfg(feature = "avro")]
+pub mod avro;
+mod event;
+#[cfg(feature = "kafka")]
+pub mod kafka;
+#[cfg(feature = "nats")]
+pub mod nats;
+
+pub use event::{BookDeltaEvent, FillEvent, LevelChange, Normalizer, Side, SinkEvent, TradeEvent};
+#[cfg(feature = "kafka")]
+pub use kafka::KafkaSink;
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+
+/// Future returned by [`Sink::publish`]
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<(), HyperliquidError>> + Send + 'a>>;
+
+/// Destination for encoded events
+pub trait Sink: Send + Sync + 'static {
+    /// Name used in publish failure logs
+    fn name(&self) -> &str;
+
+    fn publish<'a>(&'a self, topic: &'a str, key: &'a str, payload: Vec<u8>) -> SinkFuture<'a>;
+}
+
+/// Payload encoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// The event serialized as JSON, tagged with its `type`
+    #[default]
+    Json,
+    /// Avro binary datum of the kind's schema (see [`avro`])
+    ///
+    /// With a `schema_id`, the datum is prefixed with the Confluent wire
+    /// format header (a zero byte and the big-endian id).
+    #[cfg(feature = "avro")]
+    Avro { schema_id: Option<u32> },
+}
+
+impl Encoding {
+    pub fn encode(&self, event: &SinkEvent) -> Result<Vec<u8>, HyperliquidError> {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec(event)?),
+            #[cfg(feature = "avro")]
+            Encoding::Avro { schema_id } => {
+                let datum = avro::encode(event)?;
+                Ok(match schema_id {
+                    Some(id) => {
+                        let mut framed = Vec::with_capacity(datum.len() + 5);
+                        framed.push(0);
+                        framed.extend_from_slice(&id.to_be_bytes());
+                        framed.extend_from_slice(&datum);
+                        framed
+                    }
+                    None => datum,
+                })
+            }
+        }
+    }
+}
+
+/// Normalizes websocket messages and publishes them to a [`Sink`]
+#[derive(Clone)]
+pub struct Publisher {
+    sink: Arc<dyn Sink>,
+    encoding: Encoding,
+    topic_prefix: String,
+    per_coin_topics: bool,
+    normalizer: Arc<Mutex<Normalizer>>,
+}
+
+impl std::fmt::Debug for Publisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Publisher")
+            .field("sink", &self.sink.name())
+            .field("encoding", &self.encoding)
+            .field("topic_prefix", &self.topic_prefix)
+            .field("per_coin_topics", &self.per_coin_topics)
+            .finish()
+    }
+}
+
+impl Publisher {
+    pub fn new(sink: impl Sink) -> Self {
+        Self {
+            sink: Arc::new(sink),
+            encoding: Encoding::default(),
+            topic_prefix: "hyperliquid".to_string(),
+            per_coin_topics: false,
+            normalizer: Arc::new(Mutex::new(Normalizer::new())),
+        }
+    }
+
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// First component of every topic (default `hyperliquid`)
+    pub fn with_topic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.topic_prefix = prefix.into();
+        self
+    }
+
+    /// Publish each coin to its own topic, e.g. `hyperliquid.trades.BTC`
+    pub fn with_per_coin_topics(mut self, enabled: bool) -> Self {
+        self.per_coin_topics = enabled;
+        self
+    }
+
+    /// Topic `event` is published to
+    ///
+    /// Characters other than ASCII alphanumerics, `-` and `_` in the coin
+    /// (spot names such as `PURR/USDC` or `@107`) are replaced with `_`, as
+    /// neither Kafka topics nor NATS subjects accept all of them.
+    pub fn topic(&self, event: &SinkEvent) -> String {
+        if self.per_coin_topics {
+            let coin: String = event
+                .coin()
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            format!("{}.{}.{}", self.topic_prefix, event.kind(), coin)
+        } else {
+            format!("{}.{}", self.topic_prefix, event.kind())
+        }
+    }
+
+    /// Events in a websocket message; see [`Normalizer::normalize`]
+    pub fn normalize(&self, response: &WebSocketResponse) -> Vec<SinkEvent> {
+        self.normalizer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .normalize(response)
+    }
+
+    pub async fn publish_event(&self, event: &SinkEvent) -> Result<(), HyperliquidError> {
+        let payload = self.encoding.encode(event)?;
+        let key = match event {
+            SinkEvent::Fill(fill) => fill.user.as_str(),
+            _ => event.coin(),
+        };
+        self.sink.publish(&self.topic(event), key, payload).await
+    }
+
+    /// Normalize and publish a websocket message
+    ///
+    /// Every event is attempted; the first failure is returned.
+    pub async fn handle(&self, response: &WebSocketResponse) -> Result<(), HyperliquidError> {
+        let mut result = Ok(());
+        for event in self.normalize(response) {
+            if let Err(e) = self.publish_event(&event).await {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Subscribe to `subscriptions` and publish their messages
+    ///
+    /// Only `Trades`, `L2Book` and `UserFills` are accepted. Messages are
+    /// published in arrival order by a background task, which logs failures
+    /// and runs until the websocket client is dropped.
+    pub async fn forward(
+        &self,
+        ws: &WebSocketClient,
+        subscriptions: Vec<Subscription>,
+    ) -> Result<JoinHandle<()>, HyperliquidError> {
+        if let Some(unsupported) = subscriptions.iter().find(|s| {
+            !matches!(
+                s,
+                Subscription::Trades { .. }
+                    | Subscription::L2Book { .. }
+                    | Subscription::UserFills { .. }
+            )
+        }) {
+            return Err(HyperliquidError::Config(format!(
+                "Cannot publish {:?}: only trades, l2Book and userFills are supported",
+                unsupported
+            )));
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<WebSocketResponse>();
+        for subscription in subscriptions {
+            let tx = tx.clone();
+            let filter = subscription.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if is_for(&filter, &response) {
+                    let _ = tx.send(response);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+
+        let publisher = self.clone();
+        Ok(tokio::spawn(async move {
+            while let Some(response) = rx.recv().await {
+                if let Err(e) = publisher.handle(&response).await {
+                    warn!(
+                        "Failed to publish {} message to {}: {}",
+                        response.channel,
+                        publisher.sink.name(),
+                        e
+                    );
+                }
+            }
+        }))
+    }
+}
+
+/// Whether `response` is a message of `subscription`
+fn is_for(subscription: &Subscription, response: &WebSocketResponse) -> bool {
+    let data = &response.data;
+    match subscription {
+        Subscription::Trades { coin } => {
+            response.channel == "trades"
+                && data.pointer("/0/coin").and_then(|c| c.as_str()) == Some(coin)
+        }
+        Subscription::L2Book { coin } => {
+            response.channel == "l2Book" && data.get("coin").and_then(|c| c.as_str()) == Some(coin)
+        }
+        Subscription::UserFills { user } => {
+            response.channel == "userFills"
+                && data
+                    .get("user")
+                    .and_then(|u| u.as_str())
+                    .is_some_and(|u| u.eq_ignore_ascii_case(&user.to_hex()))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,55 @@
+//! [`Sink`] publishing to NATS subjects
+
+use async_nats::HeaderMap;
+
+use super::{Sink, SinkFuture};
+use crate::error::HyperliquidError;
+
+/// Header carrying the event key, as NATS messages have none of their own
+pub const KEY_HEADER: &str = "Hyperliquid-Key";
+
+/// Publishes each event to the subject named after its topic
+#[derive(Debug, Clone)]
+pub struct NatsSink {
+    client: async_nats::Client,
+}
+
+impl NatsSink {
+    /// Connect to a server, e.g. `nats://localhost:4222`
+    pub async fn connect(url: &str) -> Result<Self, HyperliquidError> {
+        let client = async_nats::connect(url)
+            .await
+            .map_err(|e| HyperliquidError::Network(format!("NATS connect: {}", e)))?;
+        Ok(Self::from_client(client))
+    }
+
+    /// Use an already connected client
+    pub fn from_client(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+
+    /// Wait for buffered messages to be written to the server
+    pub async fn flush(&self) -> Result<(), HyperliquidError> {
+        self.client
+            .flush()
+            .await
+            .map_err(|e| HyperliquidError::Network(format!("NATS flush: {}", e)))
+    }
+}
+
+impl Sink for NatsSink {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    fn publish<'a>(&'a self, topic: &'a str, key: &'a str, payload: Vec<u8>) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let mut headers = HeaderMap::new();
+            headers.insert(KEY_HEADER, key);
+            self.client
+                .publish_with_headers(topic.to_string(), headers, payload.into())
+                .await
+                .map_err(|e| HyperliquidError::Network(format!("NATS publish to {}: {}", topic, e)))
+        })
+    }
+}
@@ -0,0 +1,240 @@
+//! Change events from successive account snapshots
+//!
+//! `webData2` pushes, and `clearinghouseState` polls, deliver the whole
+//! account every time. A [`StateDiffer`] remembers the previous snapshot and
+//! turns each new one into the [`StateChange`]s between them: positions
+//! opened, closed or resized, and changes to the margin in use and the
+//! withdrawable balance. The first snapshot only sets the baseline.
+//!
+//! ```no_run
+//! # fn example(snapshots: Vec<hyperliquid_core::types::WebData2>) {
+//! use hyperliquid_core::state_diff::{StateChange, StateDiffer};
+//!
+//! let mut differ = StateDiffer::new().with_min_change(1.0);
+//! for snapshot in &snapshots {
+//!     for change in differ.diff_web_data(snapshot) {
+//!         if let StateChange::PositionClosed { coin, .. } = change {
+//!             println!("{} closed", coin);
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::types::{ClearinghouseState, UserState, WebData2};
+
+/// Change between two account snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum StateChange {
+    /// A position appeared; a position that flips side closes and reopens
+    PositionOpened {
+        coin: String,
+        szi: f64,
+        entry_px: Option<f64>,
+    },
+    PositionClosed {
+        coin: String,
+        previous_szi: f64,
+    },
+    /// Size changed without crossing zero
+    PositionResized {
+        coin: String,
+        previous_szi: f64,
+        szi: f64,
+        entry_px: Option<f64>,
+    },
+    /// Total margin in use changed
+    MarginChanged {
+        previous: f64,
+        current: f64,
+        account_value: f64,
+    },
+    WithdrawableChanged {
+        previous: f64,
+        current: f64,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+struct Snapshot {
+    /// Signed size and entry price by coin
+    positions: BTreeMap<String, (f64, Option<f64>)>,
+    account_value: f64,
+    margin_used: f64,
+    withdrawable: f64,
+}
+
+fn parse(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+fn parse_opt(value: Option<&String>) -> Option<f64> {
+    value.and_then(|value| value.parse().ok())
+}
+
+impl From<&ClearinghouseState> for Snapshot {
+    fn from(state: &ClearinghouseState) -> Self {
+        Self {
+            positions: state
+                .asset_positions
+                .iter()
+                .map(|entry| &entry.position)
+                .filter(|position| position.szi() != 0.0)
+                .map(|position| {
+                    (
+                        position.coin.clone(),
+                        (position.szi(), parse_opt(position.entry_px.as_ref())),
+                    )
+                })
+                .collect(),
+            account_value: parse(&state.margin_summary.accountValue),
+            margin_used: parse(&state.margin_summary.totalMarginUsed),
+            withdrawable: parse(&state.withdrawable),
+        }
+    }
+}
+
+impl From<&UserState> for Snapshot {
+    fn from(state: &UserState) -> Self {
+        Self {
+            positions: state
+                .positions
+                .iter()
+                .map(|position| {
+                    (
+                        position.coin.clone(),
+                        (
+                            parse(&position.position.szi),
+                            parse_opt(position.position.entryPx.as_ref()),
+                        ),
+                    )
+                })
+                .filter(|(_, (szi, _))| *szi != 0.0)
+                .collect(),
+            account_value: parse(&state.marginSummary.accountValue),
+            margin_used: parse(&state.marginSummary.totalMarginUsed),
+            withdrawable: parse(&state.withdrawable),
+        }
+    }
+}
+
+/// Diffs successive snapshots of one account
+#[derive(Debug, Clone, Default)]
+pub struct StateDiffer {
+    previous: Option<Snapshot>,
+    /// Smallest USD move reported for margin and withdrawable
+    min_change: f64,
+}
+
+impl StateDiffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ignore margin and withdrawable moves smaller than `usd` (default 0)
+    ///
+    /// Moves are measured from the last reported value, so slow drifts are
+    /// still reported once they add up.
+    pub fn with_min_change(mut self, usd: f64) -> Self {
+        self.min_change = usd.max(0.0);
+        self
+    }
+
+    /// Changes since the previous `clearinghouseState`
+    pub fn diff(&mut self, state: &ClearinghouseState) -> Vec<StateChange> {
+        self.apply(Snapshot::from(state))
+    }
+
+    /// Changes since the previous `webData2` message
+    pub fn diff_web_data(&mut self, data: &WebData2) -> Vec<StateChange> {
+        self.diff(&data.clearinghouse_state)
+    }
+
+    /// Changes since the previous [`UserState`]
+    pub fn diff_user_state(&mut self, state: &UserState) -> Vec<StateChange> {
+        self.apply(Snapshot::from(state))
+    }
+
+    /// Forget the baseline; the next snapshot sets a new one
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    fn apply(&mut self, mut current: Snapshot) -> Vec<StateChange> {
+        let Some(previous) = self.previous.take() else {
+            self.previous = Some(current);
+            return Vec::new();
+        };
+        let mut changes = Vec::new();
+
+        for (coin, &(previous_szi, _)) in &previous.positions {
+            match current.positions.get(coin) {
+                None => changes.push(StateChange::PositionClosed {
+                    coin: coin.clone(),
+                    previous_szi,
+                }),
+                Some(&(szi, entry_px)) if szi.signum() != previous_szi.signum() => {
+                    changes.push(StateChange::PositionClosed {
+                        coin: coin.clone(),
+                        previous_szi,
+                    });
+                    changes.push(StateChange::PositionOpened {
+                        coin: coin.clone(),
+                        szi,
+                        entry_px,
+                    });
+                }
+                Some(&(szi, entry_px)) if szi != previous_szi => {
+                    changes.push(StateChange::PositionResized {
+                        coin: coin.clone(),
+                        previous_szi,
+                        szi,
+                        entry_px,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        for (coin, &(szi, entry_px)) in &current.positions {
+            if !previous.positions.contains_key(coin) {
+                changes.push(StateChange::PositionOpened {
+                    coin: coin.clone(),
+                    szi,
+                    entry_px,
+                });
+            }
+        }
+
+        // Unreported moves stay in the baseline until they reach the threshold
+        if self.moved(previous.margin_used, current.margin_used) {
+            changes.push(StateChange::MarginChanged {
+                previous: previous.margin_used,
+                current: current.margin_used,
+                account_value: current.account_value,
+            });
+        } else {
+            current.margin_used = previous.margin_used;
+        }
+        if self.moved(previous.withdrawable, current.withdrawable) {
+            changes.push(StateChange::WithdrawableChanged {
+                previous: previous.withdrawable,
+                current: current.withdrawable,
+            });
+        } else {
+            current.withdrawable = previous.withdrawable;
+        }
+
+        self.previous = Some(current);
+        changes
+    }
+
+    fn moved(&self, previous: f64, current: f64) -> bool {
+        let delta = (current - previous).abs();
+        delta > 0.0 && delta >= self.min_change
+    }
+}
@@ -0,0 +1,136 @@
+//! Persistent key-value state
+//!
+//! [`StateStore`] is the storage the stateful components write through to so
+//! a bot can restart without losing in-flight state:
+//!
+//! - [`OrderManager::with_store`](crate::oms::OrderManager::with_store) keeps
+//!   every tracked order under `oms/order/`;
+//! - [`PositionTracker::with_store`](crate::positions::PositionTracker::with_store)
+//!   keeps positions and PnL under `positions/`;
+//! - [`NonceManager::with_store`](crate::crypto::NonceManager::with_store)
+//!   keeps the last nonce per signer under `nonce/`.
+//!
+//! Backends: [`MemoryStore`] (tests, or a process that doesn't need to
+//! survive restarts), `SledStore` with the `sled` feature and `SqliteStore`
+//! with the `sqlite` feature. Values are opaque bytes; the components store
+//! JSON through [`put_json`] and [`get_json`].
+
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::HyperliquidError;
+
+#[cfg(feature = "sled")]
+pub mod sled;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(feature = "sled")]
+pub use self::sled::SledStore;
+#[cfg(feature = "sqlite")]
+pub use self::sqlite::SqliteStore;
+
+/// Ordered key-value storage
+///
+/// Keys are `/`-separated paths; [`scan`](StateStore::scan) returns entries
+/// in key order. Writes should be durable once [`flush`](StateStore::flush)
+/// returns.
+pub trait StateStore: Send + Sync + std::fmt::Debug {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), HyperliquidError>;
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, HyperliquidError>;
+
+    fn delete(&self, key: &str) -> Result<(), HyperliquidError>;
+
+    /// Entries whose key starts with `prefix`, in key order
+    fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, HyperliquidError>;
+
+    /// Persist buffered writes
+    fn flush(&self) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+}
+
+/// Serialize `value` as JSON and store it under `key`
+pub fn put_json<T: Serialize + ?Sized>(
+    store: &dyn StateStore,
+    key: &str,
+    value: &T,
+) -> Result<(), HyperliquidError> {
+    store.put(key, &serde_json::to_vec(value)?)
+}
+
+/// Load and deserialize the JSON stored under `key`
+pub fn get_json<T: DeserializeOwned>(
+    store: &dyn StateStore,
+    key: &str,
+) -> Result<Option<T>, HyperliquidError> {
+    match store.get(key)? {
+        Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+        None => Ok(None),
+    }
+}
+
+/// Load and deserialize every JSON value under `prefix`, in key order
+pub fn scan_json<T: DeserializeOwned>(
+    store: &dyn StateStore,
+    prefix: &str,
+) -> Result<Vec<(String, T)>, HyperliquidError> {
+    store
+        .scan(prefix)?
+        .into_iter()
+        .map(|(key, bytes)| Ok((key, serde_json::from_slice(&bytes)?)))
+        .collect()
+}
+
+/// In-process [`StateStore`]
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<BTreeMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Vec<u8>>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+}
+
+impl StateStore for MemoryStore {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), HyperliquidError> {
+        self.lock().insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, HyperliquidError> {
+        Ok(self.lock().get(key).cloned())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), HyperliquidError> {
+        self.lock().remove(key);
+        Ok(())
+    }
+
+    fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, HyperliquidError> {
+        Ok(self
+            .lock()
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+}
@@ -0,0 +1,71 @@
+//! [`StateStore`] backed by an embedded sled database
+
+use std::path::Path;
+
+use super::StateStore;
+use crate::error::HyperliquidError;
+
+/// Name of the tree used when opening a database by path
+const DEFAULT_TREE: &str = "hyperliquid";
+
+/// Store kept in a sled tree
+#[derive(Debug, Clone)]
+pub struct SledStore {
+    tree: sled::Tree,
+}
+
+impl SledStore {
+    /// Open (or create) the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HyperliquidError> {
+        let db = sled::open(path).map_err(storage_error)?;
+        Self::from_db(&db, DEFAULT_TREE)
+    }
+
+    /// Use tree `name` of an already open database
+    pub fn from_db(db: &sled::Db, name: &str) -> Result<Self, HyperliquidError> {
+        Ok(Self {
+            tree: db.open_tree(name).map_err(storage_error)?,
+        })
+    }
+}
+
+impl StateStore for SledStore {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), HyperliquidError> {
+        self.tree.insert(key, value).map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, HyperliquidError> {
+        Ok(self
+            .tree
+            .get(key)
+            .map_err(storage_error)?
+            .map(|value| value.to_vec()))
+    }
+
+    fn delete(&self, key: &str) -> Result<(), HyperliquidError> {
+        self.tree.remove(key).map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, HyperliquidError> {
+        self.tree
+            .scan_prefix(prefix)
+            .map(|entry| {
+                let (key, value) = entry.map_err(storage_error)?;
+                let key = String::from_utf8(key.to_vec())
+                    .map_err(|e| HyperliquidError::Storage(format!("non-UTF-8 key: {}", e)))?;
+                Ok((key, value.to_vec()))
+            })
+            .collect()
+    }
+
+    fn flush(&self) -> Result<(), HyperliquidError> {
+        self.tree.flush().map_err(storage_error)?;
+        Ok(())
+    }
+}
+
+fn storage_error(e: sled::Error) -> HyperliquidError {
+    HyperliquidError::Storage(e.to_string())
+}
@@ -0,0 +1,100 @@
+//! [`StateStore`] backed by a SQLite database
+
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::StateStore;
+use crate::error::HyperliquidError;
+
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS state (
+    key TEXT PRIMARY KEY NOT NULL,
+    value BLOB NOT NULL
+) WITHOUT ROWID";
+
+/// Store kept in a `state` table of a SQLite database
+#[derive(Debug)]
+pub struct SqliteStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    /// Open (or create) the database at `path`
+    ///
+    /// The database is switched to WAL mode so writes don't block readers in
+    /// other processes inspecting it.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, HyperliquidError> {
+        let connection = Connection::open(path).map_err(storage_error)?;
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(storage_error)?;
+        Self::from_connection(connection)
+    }
+
+    /// Database that lives only as long as the store
+    pub fn in_memory() -> Result<Self, HyperliquidError> {
+        Self::from_connection(Connection::open_in_memory().map_err(storage_error)?)
+    }
+
+    /// Use an already open connection, creating the table if needed
+    pub fn from_connection(connection: Connection) -> Result<Self, HyperliquidError> {
+        connection.execute(SCHEMA, []).map_err(storage_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl StateStore for SqliteStore {
+    fn put(&self, key: &str, value: &[u8]) -> Result<(), HyperliquidError> {
+        self.lock()
+            .execute(
+                "INSERT INTO state (key, value) VALUES (?1, ?2)
+                 ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+                params![key, value],
+            )
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, HyperliquidError> {
+        self.lock()
+            .query_row("SELECT value FROM state WHERE key = ?1", [key], |row| {
+                row.get(0)
+            })
+            .optional()
+            .map_err(storage_error)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), HyperliquidError> {
+        self.lock()
+            .execute("DELETE FROM state WHERE key = ?1", [key])
+            .map_err(storage_error)?;
+        Ok(())
+    }
+
+    fn scan(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, HyperliquidError> {
+        // substr() rather than LIKE, which would treat `%` and `_` in the
+        // prefix as wildcards
+        let connection = self.lock();
+        let mut statement = connection
+            .prepare(
+                "SELECT key, value FROM state
+                 WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key",
+            )
+            .map_err(storage_error)?;
+        let rows = statement
+            .query_map([prefix], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(storage_error)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(storage_error)
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> HyperliquidError {
+    HyperliquidError::Storage(e.to_string())
+}
@@ -0,0 +1,441 @@
+//! Arena-backed decoding for hot-path market data
+//!
+//! This module decodes `l2Book` and `trades` WebSocket messages directly into
+//! arena-allocated structs. Coin symbols are looked up in the process-wide
+//! registry so the decoded values carry the same 4-byte `SymbolId` used by the
+//! Info and Exchange clients instead of an owned `String`. Coins the registry
+//! doesn't know are rejected rather than added, so a stream can't grow it.
+//!
+//! Decoded values borrow from the decoder and are invalidated by the next call
+//! to [`ArenaMessageDecoder::decode`] or [`ArenaMessageDecoder::reset`], which
+//! keeps steady-state memory usage constant regardless of message volume.
+
+use crate::memory::{AllocationStats, ArenaAllocator, ZeroCopyValue};
+use crate::types::{global_symbols, OptimizedTrade, OrderSide, SymbolId, SymbolRegistry};
+
+use super::error::WebSocketError;
+
+/// Default arena chunk size used by the decoder (64KB)
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Single price level of an arena-decoded order book
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArenaLevel {
+    /// Level price
+    pub px: f64,
+    /// Aggregated size at this level
+    pub sz: f64,
+    /// Number of orders at this level
+    pub n: u32,
+}
+
+/// L2 order book snapshot whose levels live in the decoder arena
+#[derive(Debug, Clone, Copy)]
+pub struct ArenaL2Book<'a> {
+    /// Interned coin symbol
+    pub symbol_id: SymbolId,
+    /// Exchange timestamp in milliseconds
+    pub time: i64,
+    /// Bid levels, best first
+    pub bids: &'a [ArenaLevel],
+    /// Ask levels, best first
+    pub asks: &'a [ArenaLevel],
+}
+
+/// Message decoded through the arena path
+#[derive(Debug, Clone, Copy)]
+pub enum ArenaMessage<'a> {
+    /// Order book snapshot (`l2Book` channel)
+    L2Book(ArenaL2Book<'a>),
+    /// Batch of trades (`trades` channel)
+    Trades(&'a [OptimizedTrade]),
+}
+
+impl<'a> ArenaMessage<'a> {
+    /// Get the interned symbol of the message, if any
+    pub fn symbol_id(&self) -> Option<SymbolId> {
+        match self {
+            ArenaMessage::L2Book(book) => Some(book.symbol_id),
+            ArenaMessage::Trades(trades) => trades.first().map(|t| t.symbol_id),
+        }
+    }
+}
+
+/// Callback invoked for every arena-decoded message
+pub type ArenaMessageHandler = Box<dyn FnMut(ArenaMessage<'_>) + Send + 'static>;
+
+/// Decoder that turns raw WebSocket text into arena-allocated market data
+pub struct ArenaMessageDecoder {
+    /// Arena backing decoded levels and trades
+    arena: ArenaAllocator,
+    /// Scratch buffers reused across messages
+    bids: Vec<ArenaLevel>,
+    asks: Vec<ArenaLevel>,
+    trades: Vec<OptimizedTrade>,
+    /// Number of messages decoded
+    decoded: u64,
+}
+
+impl ArenaMessageDecoder {
+    /// Create a new decoder with the default arena chunk size
+    pub fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Create a new decoder with a custom arena chunk size
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            arena: ArenaAllocator::new(chunk_size),
+            bids: Vec::new(),
+            asks: Vec::new(),
+            trades: Vec::new(),
+            decoded: 0,
+        }
+    }
+
+    /// Check whether a channel is handled by the arena path
+    pub fn supports_channel(channel: &str) -> bool {
+        matches!(channel, "l2Book" | "trades")
+    }
+
+    /// Decode a raw WebSocket message
+    ///
+    /// Returns `Ok(None)` when the message belongs to a channel that is not
+    /// handled by the arena path. The arena is reset before decoding, so any
+    /// previously returned message is no longer reachable.
+    pub fn decode<'a>(
+        &'a mut self,
+        text: &str,
+    ) -> Result<Option<ArenaMessage<'a>>, WebSocketError> {
+        self.arena.reset();
+
+        let value = ZeroCopyValue::from_json_str(text)
+            .map_err(|e| WebSocketError::Deserialization(e.to_string()))?;
+
+        let (channel, data) = match &value {
+            ZeroCopyValue::Object(map) => {
+                let channel = map.get("channel").and_then(|c| c.as_str());
+                (channel, map.get("data"))
+            }
+            _ => (None, None),
+        };
+
+        let (channel, data) = match (channel, data) {
+            (Some(channel), Some(data)) if Self::supports_channel(channel) => (channel, data),
+            _ => return Ok(None),
+        };
+
+        let message = match channel {
+            "l2Book" => self.decode_l2_book(data)?,
+            _ => self.decode_trades(data)?,
+        };
+
+        self.decoded += 1;
+        Ok(Some(message))
+    }
+
+    /// Decode the `data` payload of an `l2Book` message
+    fn decode_l2_book<'a>(
+        &'a mut self,
+        data: &ZeroCopyValue<'_>,
+    ) -> Result<ArenaMessage<'a>, WebSocketError> {
+        let map = match data {
+            ZeroCopyValue::Object(map) => map,
+            _ => {
+                return Err(WebSocketError::Deserialization(
+                    "l2Book data is not an object".to_string(),
+                ))
+            }
+        };
+
+        let coin = map
+            .get("coin")
+            .and_then(|c| c.as_str())
+            .ok_or_else(|| WebSocketError::Deserialization("l2Book missing coin".to_string()))?;
+        let time = map.get("time").and_then(|t| t.as_number()).unwrap_or(0.0) as i64;
+
+        let sides = match map.get("levels") {
+            Some(ZeroCopyValue::Array(sides)) if sides.len() == 2 => sides,
+            _ => {
+                return Err(WebSocketError::Deserialization(
+                    "l2Book levels must have two sides".to_string(),
+                ))
+            }
+        };
+
+        self.bids.clear();
+        self.asks.clear();
+        collect_levels(&sides[0], &mut self.bids)?;
+        collect_levels(&sides[1], &mut self.asks)?;
+
+        let symbol_id = lookup_symbol(coin)?;
+        let bids = self.arena.allocate_slice(&self.bids);
+        let asks = self.arena.allocate_slice(&self.asks);
+
+        if bids.is_null() || asks.is_null() {
+            return Err(WebSocketError::Deserialization(
+                "arena allocation failed".to_string(),
+            ));
+        }
+
+        // Safety: the slices live in arena chunks that are only recycled by
+        // `reset`, which requires `&mut self` and therefore ends this borrow.
+        let (bids, asks) = unsafe { (&*bids, &*asks) };
+
+        Ok(ArenaMessage::L2Book(ArenaL2Book {
+            symbol_id,
+            time,
+            bids,
+            asks,
+        }))
+    }
+
+    /// Decode the `data` payload of a `trades` message
+    fn decode_trades<'a>(
+        &'a mut self,
+        data: &ZeroCopyValue<'_>,
+    ) -> Result<ArenaMessage<'a>, WebSocketError> {
+        let items = match data {
+            ZeroCopyValue::Array(items) => items,
+            _ => {
+                return Err(WebSocketError::Deserialization(
+                    "trades data is not an array".to_string(),
+                ))
+            }
+        };
+
+        self.trades.clear();
+        for item in items {
+            let map = match item {
+                ZeroCopyValue::Object(map) => map,
+                _ => {
+                    return Err(WebSocketError::Deserialization(
+                        "trade is not an object".to_string(),
+                    ))
+                }
+            };
+
+            let coin = map
+                .get("coin")
+                .and_then(|c| c.as_str())
+                .ok_or_else(|| WebSocketError::Deserialization("trade missing coin".to_string()))?;
+            let side = match map.get("side").and_then(|s| s.as_str()) {
+                Some("B") => OrderSide::Buy,
+                Some("A") => OrderSide::Sell,
+                other => {
+                    return Err(WebSocketError::Deserialization(format!(
+                        "invalid trade side: {:?}",
+                        other
+                    )));
+                }
+            };
+
+            self.trades.push(OptimizedTrade {
+                symbol_id: lookup_symbol(coin)?,
+                price: parse_decimal(map.get("px"), "px")?,
+                size: parse_decimal(map.get("sz"), "sz")?,
+                timestamp: map.get("time").and_then(|t| t.as_number()).unwrap_or(0.0) as i64,
+                side,
+            });
+        }
+
+        let trades = self.arena.allocate_slice(&self.trades);
+        if trades.is_null() {
+            return Err(WebSocketError::Deserialization(
+                "arena allocation failed".to_string(),
+            ));
+        }
+
+        // Safety: see `decode_l2_book`.
+        Ok(ArenaMessage::Trades(unsafe { &*trades }))
+    }
+
+    /// Release all arena memory held by previously decoded messages
+    pub fn reset(&mut self) {
+        self.arena.reset();
+    }
+
+    /// Resolve an interned symbol back to its coin name
+    pub fn symbol(&self, id: SymbolId) -> Option<&'static str> {
+        id.name()
+    }
+
+    /// Get the symbol registry used by this decoder
+    pub fn symbols(&self) -> &'static SymbolRegistry {
+        global_symbols()
+    }
+
+    /// Get the number of messages decoded so far
+    pub fn decoded_count(&self) -> u64 {
+        self.decoded
+    }
+
+    /// Get arena allocation statistics
+    pub fn arena_stats(&self) -> AllocationStats {
+        self.arena.get_stats()
+    }
+}
+
+/// Resolve a coin through the process-wide registry without registering it
+fn lookup_symbol(coin: &str) -> Result<SymbolId, WebSocketError> {
+    global_symbols()
+        .lookup(coin)
+        .ok_or_else(|| WebSocketError::Deserialization(format!("unknown coin: {}", coin)))
+}
+
+impl Default for ArenaMessageDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse one side of an `l2Book` levels array into `out`
+fn collect_levels(
+    side: &ZeroCopyValue<'_>,
+    out: &mut Vec<ArenaLevel>,
+) -> Result<(), WebSocketError> {
+    let levels = match side {
+        ZeroCopyValue::Array(levels) => levels,
+        _ => {
+            return Err(WebSocketError::Deserialization(
+                "l2Book side is not an array".to_string(),
+            ))
+        }
+    };
+
+    for level in levels {
+        let map = match level {
+            ZeroCopyValue::Object(map) => map,
+            _ => {
+                return Err(WebSocketError::Deserialization(
+                    "l2Book level is not an object".to_string(),
+                ))
+            }
+        };
+
+        out.push(ArenaLevel {
+            px: parse_decimal(map.get("px"), "px")?,
+            sz: parse_decimal(map.get("sz"), "sz")?,
+            n: map.get("n").and_then(|n| n.as_number()).unwrap_or(0.0) as u32,
+        });
+    }
+
+    Ok(())
+}
+
+/// Parse a decimal string field (e.g. `"px": "50000.5"`)
+fn parse_decimal(value: Option<&ZeroCopyValue<'_>>, field: &str) -> Result<f64, WebSocketError> {
+    match value {
+        Some(ZeroCopyValue::Number(n)) => Ok(*n),
+        Some(v) => v
+            .as_str()
+            .and_then(|s| s.parse::<f64>().ok())
+            .ok_or_else(|| WebSocketError::Deserialization(format!("invalid {} value", field))),
+        None => Err(WebSocketError::Deserialization(format!(
+            "missing {} field",
+            field
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const L2_BOOK: &str = r#"{"channel":"l2Book","data":{"coin":"BTC","time":1700000000000,"levels":[[{"px":"50000.0","sz":"1.5","n":3}],[{"px":"50001.0","sz":"0.5","n":1},{"px":"50002.0","sz":"2.0","n":4}]]}}"#;
+    const TRADES: &str = r#"{"channel":"trades","data":[{"coin":"ETH","side":"B","px":"3000.5","sz":"0.1","time":1700000000001,"hash":"0xabc","tid":1},{"coin":"ETH","side":"A","px":"3000.0","sz":"0.2","time":1700000000002,"hash":"0xdef","tid":2}]}"#;
+
+    /// Register the coins of the fixtures, as loading the asset map would
+    fn decoder() -> ArenaMessageDecoder {
+        SymbolId::intern("BTC");
+        SymbolId::intern("ETH");
+        ArenaMessageDecoder::new()
+    }
+
+    #[test]
+    fn test_decode_l2_book() {
+        let mut decoder = decoder();
+
+        let message = decoder.decode(L2_BOOK).unwrap().unwrap();
+        let book = match message {
+            ArenaMessage::L2Book(book) => book,
+            other => panic!("expected l2Book, got {:?}", other),
+        };
+
+        assert_eq!(book.time, 1700000000000);
+        assert_eq!(book.bids.len(), 1);
+        assert_eq!(book.asks.len(), 2);
+        assert_eq!(
+            book.bids[0],
+            ArenaLevel {
+                px: 50000.0,
+                sz: 1.5,
+                n: 3
+            }
+        );
+        assert_eq!(book.asks[1].px, 50002.0);
+
+        let symbol_id = book.symbol_id;
+        assert_eq!(decoder.symbol(symbol_id), Some("BTC"));
+    }
+
+    #[test]
+    fn test_decode_trades_resolves_symbols() {
+        let mut decoder = decoder();
+
+        let first = match decoder.decode(TRADES).unwrap().unwrap() {
+            ArenaMessage::Trades(trades) => {
+                assert_eq!(trades.len(), 2);
+                assert_eq!(trades[0].side, OrderSide::Buy);
+                assert_eq!(trades[1].side, OrderSide::Sell);
+                assert_eq!(trades[0].symbol_id, trades[1].symbol_id);
+                trades[0].symbol_id
+            }
+            other => panic!("expected trades, got {:?}", other),
+        };
+
+        let second = decoder
+            .decode(TRADES)
+            .unwrap()
+            .unwrap()
+            .symbol_id()
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(Some(first), global_symbols().lookup("ETH"));
+        assert_eq!(decoder.decoded_count(), 2);
+    }
+
+    #[test]
+    fn test_unknown_coin_is_not_registered() {
+        let mut decoder = decoder();
+        let book = L2_BOOK.replace("\"BTC\"", "\"NOTLISTED\"");
+        let trades = TRADES.replace("\"ETH\"", "\"NOTLISTED\"");
+
+        assert!(decoder.decode(&book).is_err());
+        assert!(decoder.decode(&trades).is_err());
+        assert_eq!(global_symbols().lookup("NOTLISTED"), None);
+        assert_eq!(decoder.decoded_count(), 0);
+    }
+
+    #[test]
+    fn test_unsupported_channel_is_skipped() {
+        let mut decoder = decoder();
+        let result = decoder
+            .decode(r#"{"channel":"allMids","data":{"mids":{"BTC":"50000"}}}"#)
+            .unwrap();
+        assert!(result.is_none());
+        assert_eq!(decoder.decoded_count(), 0);
+    }
+
+    #[test]
+    fn test_arena_memory_is_recycled() {
+        let mut decoder = decoder();
+        for _ in 0..1000 {
+            decoder.decode(L2_BOOK).unwrap();
+            decoder.decode(TRADES).unwrap();
+        }
+        decoder.reset();
+        assert_eq!(decoder.decoded_count(), 2000);
+        assert!(decoder.symbols().lookup("BTC").is_some());
+    }
+}
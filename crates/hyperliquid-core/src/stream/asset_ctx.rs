@@ -0,0 +1,81 @@
+//! Typed asset context and mark price streams
+//!
+//! [`WebSocketClient::asset_ctx_stream`] subscribes to a coin's
+//! `activeAssetCtx` and yields each update as an [`AssetCtxUpdate`], perp or
+//! spot; [`WebSocketClient::mark_price_stream`] narrows that to the mark.
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! let mut marks = ws.mark_price_stream("BTC").await?;
+//! while let Some(mark) = marks.recv().await {
+//!     println!("BTC mark {}", mark);
+//! }
+//! # Ok(()) }
+//! ```
+
+use rust_decimal::Decimal;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use super::{WebSocketClient, WebSocketResponse};
+use crate::error::HyperliquidError;
+use crate::types::{AssetCtxUpdate, Subscription};
+
+impl WebSocketClient {
+    /// Subscribe to `coin`'s `activeAssetCtx` and call `f` with each update
+    async fn attach_asset_ctx<F>(&self, coin: &str, f: F) -> Result<(), HyperliquidError>
+    where
+        F: Fn(AssetCtxUpdate) + Send + Sync + 'static,
+    {
+        let subscription = Subscription::ActiveAssetCtx {
+            coin: coin.to_string(),
+        };
+        let coin = coin.to_string();
+        self.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            match AssetCtxUpdate::parse(&response.channel, &response.data) {
+                Ok(Some(update)) if update.coin() == coin => f(update),
+                Ok(_) => {}
+                Err(e) => warn!("Ignoring malformed {} payload: {}", response.channel, e),
+            }
+        })
+        .await;
+        self.subscribe(subscription)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))
+    }
+
+    /// Typed context updates of a perp or spot `coin`
+    ///
+    /// Registers the handler for the `activeAssetCtx` subscription, replacing
+    /// any existing handler for it. The receiver ends when the handler is
+    /// replaced or unregistered.
+    pub async fn asset_ctx_stream(
+        &self,
+        coin: &str,
+    ) -> Result<mpsc::UnboundedReceiver<AssetCtxUpdate>, HyperliquidError> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.attach_asset_ctx(coin, move |update| {
+            let _ = tx.send(update);
+        })
+        .await?;
+        Ok(rx)
+    }
+
+    /// Mark prices of a perp or spot `coin`, one per context update
+    ///
+    /// Shares the subscription and handler of
+    /// [`asset_ctx_stream`](Self::asset_ctx_stream), so only one of the two
+    /// can be open per coin.
+    pub async fn mark_price_stream(
+        &self,
+        coin: &str,
+    ) -> Result<mpsc::UnboundedReceiver<Decimal>, HyperliquidError> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.attach_asset_ctx(coin, move |update| {
+            let _ = tx.send(update.mark_px());
+        })
+        .await?;
+        Ok(rx)
+    }
+}
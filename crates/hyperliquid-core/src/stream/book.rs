@@ -0,0 +1,410 @@
+//! Locally maintained L2 books and their verification against REST snapshots
+//!
+//! [`LocalBook`] keeps the latest state of a coin's book from the `l2Book`
+//! channel. A book that silently stops matching the exchange (a dropped or
+//! misrouted message, a handler that ran against stale state) looks healthy
+//! from the inside, so [`BookVerifier`] periodically fetches the `l2Book`
+//! snapshot over REST for every tracked book that is still being updated and
+//! compares the two:
+//!
+//! - the divergence of each check is published as the
+//!   `hyperliquid_book_divergence` gauge, labelled by coin;
+//! - past the tolerance the local book is replaced by the snapshot and
+//!   `hyperliquid_book_resyncs_total` is incremented.
+//!
+//! The snapshot and the local book are never taken at exactly the same
+//! time, so some divergence is expected on active markets; the tolerance
+//! absorbs it.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient, ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::time::Duration;
+//! use hyperliquid_core::stream::{BookVerifier, LocalBook};
+//!
+//! let book = LocalBook::new("BTC");
+//! book.attach(&ws).await?;
+//! let verifier = BookVerifier::new(client)
+//!     .with_interval(Duration::from_secs(15))
+//!     .with_tolerance(0.02);
+//! verifier.track(book.clone());
+//! verifier.spawn();
+//! # Ok(()) }
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+#[cfg(feature = "ws")]
+use super::WebSocketClient;
+use super::WebSocketResponse;
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Default time between two checks of a book
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default divergence above which a book is resynced
+const DEFAULT_TOLERANCE: f64 = 0.05;
+
+/// Default time without updates after which a book is no longer checked
+const DEFAULT_STALE_AFTER: Duration = Duration::from_secs(60);
+
+/// Price level of a [`LocalBook`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookLevel {
+    pub px: f64,
+    pub sz: f64,
+    /// Number of orders at the level
+    pub n: u64,
+}
+
+/// Outcome of comparing a [`LocalBook`] with a REST snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookDivergence {
+    pub coin: String,
+    /// Exchange time of the local book in ms
+    pub local_time: u64,
+    /// Exchange time of the snapshot in ms
+    pub snapshot_time: u64,
+    /// Levels whose size differs, or that only one side has
+    pub mismatched_levels: usize,
+    /// Size difference summed over the compared levels, relative to the
+    /// snapshot's size over the same levels
+    pub divergence: f64,
+    /// Whether the local book was replaced by the snapshot
+    pub resynced: bool,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    bids: Vec<BookLevel>,
+    asks: Vec<BookLevel>,
+    /// Exchange time of the applied payload
+    time: u64,
+    last_update: Option<Instant>,
+}
+
+/// Latest L2 book of one coin
+#[derive(Debug, Clone)]
+pub struct LocalBook {
+    coin: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl LocalBook {
+    pub fn new(coin: impl Into<String>) -> Self {
+        Self {
+            coin: coin.into(),
+            state: Arc::new(Mutex::new(State::default())),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    /// Apply a `data` payload from the `l2Book` channel
+    ///
+    /// Returns whether it was applied; payloads of another coin and payloads
+    /// older than the current book are ignored.
+    pub fn apply(&self, data: &Value) -> bool {
+        if data.get("coin").and_then(Value::as_str) != Some(self.coin.as_str()) {
+            return false;
+        }
+        let Some((bids, asks, time)) = parse_book(data) else {
+            warn!("Ignoring malformed l2Book payload for {}", self.coin);
+            return false;
+        };
+        let mut state = self.lock();
+        if state.last_update.is_some() && time < state.time {
+            return false;
+        }
+        state.bids = bids;
+        state.asks = asks;
+        state.time = time;
+        state.last_update = Some(Instant::now());
+        true
+    }
+
+    /// Bids, best first
+    pub fn bids(&self) -> Vec<BookLevel> {
+        self.lock().bids.clone()
+    }
+
+    /// Asks, best first
+    pub fn asks(&self) -> Vec<BookLevel> {
+        self.lock().asks.clone()
+    }
+
+    /// Exchange time of the book in ms, 0 before the first update
+    pub fn time(&self) -> u64 {
+        self.lock().time
+    }
+
+    /// Whether the book was updated within `window`
+    pub fn is_active(&self, window: Duration) -> bool {
+        self.lock()
+            .last_update
+            .is_some_and(|last| last.elapsed() <= window)
+    }
+
+    /// Compare the book with an `l2Book` snapshot, without changing it
+    pub fn compare(&self, snapshot: &Value) -> Option<BookDivergence> {
+        let (bids, asks, snapshot_time) = parse_book(snapshot)?;
+        let state = self.lock();
+        let (bid_mismatches, bid_diff, bid_size) = compare_side(&state.bids, &bids, true);
+        let (ask_mismatches, ask_diff, ask_size) = compare_side(&state.asks, &asks, false);
+        let size = bid_size + ask_size;
+        let diff = bid_diff + ask_diff;
+        let divergence = if size > 0.0 {
+            diff / size
+        } else if diff > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        };
+        Some(BookDivergence {
+            coin: self.coin.clone(),
+            local_time: state.time,
+            snapshot_time,
+            mismatched_levels: bid_mismatches + ask_mismatches,
+            divergence,
+            resynced: false,
+        })
+    }
+
+    /// Subscribe `ws` to the coin's book and feed it in
+    ///
+    /// Registers the handler for the `l2Book` subscription, replacing any
+    /// existing handler for it.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        let subscription = Subscription::L2Book {
+            coin: self.coin.clone(),
+        };
+        let book = self.clone();
+        ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+            // Unrouted messages are broadcast to every handler
+            if response.channel == "l2Book" {
+                book.apply(&response.data);
+            }
+        })
+        .await;
+        ws.subscribe(subscription)
+            .await
+            .map_err(|e| HyperliquidError::WebSocket(e.to_string()))
+    }
+
+    /// Replace the book with a snapshot regardless of its time
+    fn resync(&self, snapshot: &Value) {
+        if let Some((bids, asks, time)) = parse_book(snapshot) {
+            let mut state = self.lock();
+            state.bids = bids;
+            state.asks = asks;
+            state.time = time;
+        }
+    }
+}
+
+/// Periodically checks tracked [`LocalBook`]s against REST snapshots
+#[derive(Clone)]
+pub struct BookVerifier {
+    client: HttpClient,
+    books: Arc<Mutex<Vec<LocalBook>>>,
+    interval: Duration,
+    tolerance: f64,
+    stale_after: Duration,
+}
+
+impl std::fmt::Debug for BookVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BookVerifier")
+            .field("interval", &self.interval)
+            .field("tolerance", &self.tolerance)
+            .field("stale_after", &self.stale_after)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BookVerifier {
+    pub fn new(client: HttpClient) -> Self {
+        Self {
+            client,
+            books: Arc::new(Mutex::new(Vec::new())),
+            interval: DEFAULT_INTERVAL,
+            tolerance: DEFAULT_TOLERANCE,
+            stale_after: DEFAULT_STALE_AFTER,
+        }
+    }
+
+    /// Time between two checks (default 30s)
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Divergence above which a book is resynced (default 0.05)
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Skip books not updated for this long (default 60s)
+    pub fn with_stale_after(mut self, stale_after: Duration) -> Self {
+        self.stale_after = stale_after;
+        self
+    }
+
+    fn books(&self) -> MutexGuard<'_, Vec<LocalBook>> {
+        self.books.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Check `book` on every run, replacing a tracked book of the same coin
+    pub fn track(&self, book: LocalBook) {
+        let mut books = self.books();
+        books.retain(|tracked| tracked.coin != book.coin);
+        books.push(book);
+    }
+
+    /// Stop checking the book of `coin`
+    pub fn untrack(&self, coin: &str) {
+        self.books().retain(|book| book.coin != coin);
+    }
+
+    /// Fetch the snapshot of `book`, compare and resync if beyond tolerance
+    pub async fn verify(&self, book: &LocalBook) -> Result<BookDivergence, HyperliquidError> {
+        let snapshot: Value = self
+            .client
+            .post("/info", &json!({"type": "l2Book", "coin": book.coin}))
+            .await?;
+        let mut divergence = book.compare(&snapshot).ok_or_else(|| {
+            HyperliquidError::Validation(format!("Malformed l2Book snapshot for {}", book.coin))
+        })?;
+
+        crate::telemetry::gauge!("hyperliquid_book_divergence", "coin" => book.coin.clone())
+            .set(divergence.divergence);
+        if divergence.divergence > self.tolerance {
+            warn!(
+                "Book of {} diverged from the snapshot by {:.4} ({} levels), resyncing",
+                book.coin, divergence.divergence, divergence.mismatched_levels
+            );
+            book.resync(&snapshot);
+            crate::telemetry::counter!("hyperliquid_book_resyncs_total", "coin" => book.coin.clone())
+                .increment(1);
+            divergence.resynced = true;
+        } else {
+            debug!(
+                "Book of {} within tolerance: divergence {:.4}",
+                book.coin, divergence.divergence
+            );
+        }
+        Ok(divergence)
+    }
+
+    /// Check every tracked book that is still being updated
+    ///
+    /// Books whose snapshot can't be fetched are logged and skipped.
+    pub async fn verify_all(&self) -> Vec<BookDivergence> {
+        let books: Vec<LocalBook> = self
+            .books()
+            .iter()
+            .filter(|book| book.is_active(self.stale_after))
+            .cloned()
+            .collect();
+        let mut results = Vec::with_capacity(books.len());
+        for book in books {
+            match self.verify(&book).await {
+                Ok(divergence) => results.push(divergence),
+                Err(e) => warn!("Failed to verify the book of {}: {}", book.coin, e),
+            }
+        }
+        results
+    }
+
+    /// Run [`verify_all`](Self::verify_all) every interval until aborted
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let verifier = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(verifier.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                verifier.verify_all().await;
+            }
+        })
+    }
+}
+
+fn parse_book(data: &Value) -> Option<(Vec<BookLevel>, Vec<BookLevel>, u64)> {
+    let side = |index: usize| -> Option<Vec<BookLevel>> {
+        data.pointer(&format!("/levels/{}", index))?
+            .as_array()?
+            .iter()
+            .map(|level| {
+                Some(BookLevel {
+                    px: level.get("px")?.as_str()?.parse().ok()?,
+                    sz: level.get("sz")?.as_str()?.parse().ok()?,
+                    n: level.get("n").and_then(Value::as_u64).unwrap_or_default(),
+                })
+            })
+            .collect()
+    };
+    let time = data.get("time").and_then(Value::as_u64).unwrap_or_default();
+    Some((side(0)?, side(1)?, time))
+}
+
+/// Mismatched levels, absolute size difference and snapshot size of one side
+///
+/// Only the price range both books cover is compared: each carries a fixed
+/// number of levels, so a level beyond the other book's deepest price is
+/// missing there by construction rather than by drift.
+fn compare_side(local: &[BookLevel], snapshot: &[BookLevel], bids: bool) -> (usize, f64, f64) {
+    let deepest = |levels: &[BookLevel]| levels.last().map(|level| level.px);
+    let within = match (deepest(local), deepest(snapshot)) {
+        (Some(a), Some(b)) if bids => a.max(b),
+        (Some(a), Some(b)) => a.min(b),
+        // One side is empty: every level of the other is a mismatch
+        _ => {
+            if bids {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }
+        }
+    };
+    let in_range = |px: f64| if bids { px >= within } else { px <= within };
+
+    let mut sizes: BTreeMap<u64, (f64, f64)> = BTreeMap::new();
+    for level in local.iter().filter(|level| in_range(level.px)) {
+        sizes.entry(level.px.to_bits()).or_default().0 += level.sz;
+    }
+    for level in snapshot.iter().filter(|level| in_range(level.px)) {
+        sizes.entry(level.px.to_bits()).or_default().1 += level.sz;
+    }
+
+    let mut mismatched = 0;
+    let mut diff = 0.0;
+    let mut total = 0.0;
+    for (local_sz, snapshot_sz) in sizes.values() {
+        let delta = (local_sz - snapshot_sz).abs();
+        if delta > f64::EPSILON * snapshot_sz.abs().max(1.0) {
+            mismatched += 1;
+            diff += delta;
+        }
+        total += snapshot_sz;
+    }
+    (mismatched, diff, total)
+}
@@ -333,6 +333,8 @@ mod tests {
         WebSocketResponse {
             channel: channel.to_string(),
             data: serde_json::json!({"test": "data"}),
+            time: None,
+            received_at: Instant::now(),
         }
     }
 
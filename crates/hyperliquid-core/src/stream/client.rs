@@ -1,18 +1,70 @@
-use std::time::Duration;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
 use tokio::time;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 use futures_util::{SinkExt, StreamExt};
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, warn, Instrument};
+use serde::Serialize;
 use serde_json::json;
 use rand;
 
+use crate::logging::RequestTrace;
 use crate::types::{Environment, Subscription};
 use super::error::WebSocketError;
-use super::message::{WebSocketMessage, WebSocketRequest, WebSocketResponse};
+use super::limits::{SubscriptionLimits, SubscriptionUsage};
+use super::message::{
+    PostRequestType, WebSocketMessage, WebSocketPostRequest, WebSocketPostResponse,
+    WebSocketRequest, WebSocketResponse,
+};
 use super::router::MessageRouter;
 use super::buffer::CircularBuffer;
+use super::spsc::{spsc_channel, BufferMode, SpscProducer, SpscWaitStrategy};
+use super::arena::{ArenaMessage, ArenaMessageDecoder, ArenaMessageHandler};
+use super::compression::{self, CompressionCounters, CompressionStats, DeflateParams, InflaterInput};
+use super::decode::{DecodeFilter, RawMessage};
+
+/// Arena decoder paired with the handler that consumes its output
+type ArenaPath = Arc<std::sync::Mutex<Option<(ArenaMessageDecoder, ArenaMessageHandler)>>>;
+
+/// Post requests awaiting a response, keyed by post id
+type PendingPosts = Arc<std::sync::Mutex<HashMap<u64, PendingPost>>>;
+
+/// A post request awaiting its response on the `post` channel
+struct PendingPost {
+    tx: oneshot::Sender<Result<serde_json::Value, WebSocketError>>,
+    trace: RequestTrace,
+    sent_at: Instant,
+}
+
+/// Text messages inflated off the read loop
+type Inflated = mpsc::UnboundedReceiver<Result<String, WebSocketError>>;
+
+/// Connection established by [`WebSocketClient::connect`]
+enum Connection {
+    Plain(tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>),
+    /// Offered `permessage-deflate`, with the parameters if accepted
+    Deflate(compression::DeflateWebSocket, Option<DeflateParams>),
+}
+
+/// Message queued for the writer half of the connection
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum Outbound {
+    Request(WebSocketRequest),
+    Post(WebSocketPostRequest),
+}
+
+impl Outbound {
+    fn method(&self) -> &str {
+        match self {
+            Outbound::Request(request) => &request.method,
+            Outbound::Post(request) => &request.method,
+        }
+    }
+}
 
 /// Configuration for WebSocket client
 #[derive(Clone, Debug)]
@@ -37,6 +89,16 @@ pub struct WebSocketClientConfig {
     pub buffer_capacity: usize,
     /// Enable circular buffer for burst handling
     pub enable_buffer: bool,
+    /// Buffer used for the reader to router hand-off
+    pub buffer_mode: BufferMode,
+    /// Timeout for post request round-trips in seconds
+    pub post_timeout_secs: u64,
+    /// Subscription caps checked before subscribing
+    pub subscription_limits: SubscriptionLimits,
+    /// Offer permessage-deflate and inflate compressed messages
+    pub enable_compression: bool,
+    /// Channels decoded into responses; others are passed through raw
+    pub decode_filter: DecodeFilter,
 }
 
 impl Default for WebSocketClientConfig {
@@ -52,6 +114,11 @@ impl Default for WebSocketClientConfig {
             enable_heartbeat: true,
             buffer_capacity: 1000,
             enable_buffer: true,
+            buffer_mode: BufferMode::Circular,
+            post_timeout_secs: 30,
+            subscription_limits: SubscriptionLimits::default(),
+            enable_compression: false,
+            decode_filter: DecodeFilter::All,
         }
     }
 }
@@ -80,6 +147,24 @@ impl WebSocketClientConfig {
             ..Default::default()
         }
     }
+
+    /// Apply the `[websocket]` section of the application configuration
+    pub fn with_settings(mut self, settings: &crate::config::WebSocketConfig) -> Self {
+        self.connection_timeout_secs = (settings.connect_timeout_ms / 1000).max(1);
+        self.heartbeat_interval_secs = (settings.ping_interval_ms / 1000).max(1);
+        self.max_reconnection_attempts = settings.max_reconnect_attempts;
+        self.reconnection_delay_base_ms = settings.reconnect_delay_ms;
+        self.buffer_capacity = settings.buffer_size;
+        self.buffer_mode = settings.buffer_mode;
+        self.enable_compression = settings.enable_compression;
+        self
+    }
+
+    /// Decode only the channels `filter` lets through
+    pub fn with_decode_filter(mut self, filter: DecodeFilter) -> Self {
+        self.decode_filter = filter;
+        self
+    }
 }
 
 /// WebSocket client state
@@ -115,6 +200,8 @@ pub enum WebSocketEvent {
     Disconnected,
     /// Data received
     Data(WebSocketResponse),
+    /// Message on a channel excluded from decoding
+    Raw(RawMessage),
     /// Error occurred
     Error(WebSocketError),
     /// Heartbeat received
@@ -134,13 +221,21 @@ pub struct WebSocketClient {
     /// Event receiver for internal use
     event_rx: Arc<Mutex<mpsc::UnboundedReceiver<WebSocketEvent>>>,
     /// Message sender for sending requests to WebSocket
-    message_tx: mpsc::UnboundedSender<WebSocketRequest>,
+    message_tx: mpsc::UnboundedSender<Outbound>,
     /// Message router for dispatching messages to handlers
     message_router: MessageRouter,
     /// Circular buffer for burst handling
     buffer: Option<Arc<CircularBuffer>>,
     /// Buffer consumer task handle
     buffer_consumer_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Arena-backed decode path for l2Book/trades (bypasses JSON values)
+    arena_path: ArenaPath,
+    /// Post requests awaiting a response
+    pending_posts: PendingPosts,
+    /// Next post request id
+    next_post_id: Arc<AtomicU64>,
+    /// permessage-deflate byte counters
+    compression: Arc<CompressionCounters>,
     /// Shutdown signal
     shutdown_tx: mpsc::Sender<()>,
 }
@@ -157,8 +252,11 @@ impl WebSocketClient {
         let (message_tx, _) = mpsc::unbounded_channel();
         let (shutdown_tx, _) = mpsc::channel(1);
 
-        // Initialize circular buffer if enabled
-        let buffer = if config.enable_buffer && config.buffer_capacity > 0 {
+        // Initialize circular buffer if enabled (SPSC rings are created per connection)
+        let buffer = if config.enable_buffer
+            && config.buffer_capacity > 0
+            && config.buffer_mode == BufferMode::Circular
+        {
             Some(Arc::new(CircularBuffer::new(config.buffer_capacity)))
         } else {
             None
@@ -173,6 +271,10 @@ impl WebSocketClient {
             message_router: MessageRouter::new(),
             buffer,
             buffer_consumer_handle: None,
+            arena_path: Arc::new(std::sync::Mutex::new(None)),
+            pending_posts: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            next_post_id: Arc::new(AtomicU64::new(1)),
+            compression: Arc::new(CompressionCounters::default()),
             shutdown_tx,
         })
     }
@@ -188,22 +290,40 @@ impl WebSocketClient {
         info!("Connecting to WebSocket: {}", self.config.url);
 
         // Attempt to establish WebSocket connection
+        let connecting = async {
+            if self.config.enable_compression {
+                let (ws_stream, params) = compression::connect(&self.config.url).await?;
+                Ok(Connection::Deflate(ws_stream, params))
+            } else {
+                let (ws_stream, _) = connect_async(&self.config.url)
+                    .await
+                    .map_err(|e| WebSocketError::Connection(e.to_string()))?;
+                Ok(Connection::Plain(ws_stream))
+            }
+        };
         match time::timeout(
             Duration::from_secs(self.config.connection_timeout_secs),
-            connect_async(&self.config.url),
+            connecting,
         ).await {
-            Ok(Ok((ws_stream, _))) => {
+            Ok(Ok(connection)) => {
                 info!("WebSocket connection established");
 
                 // Update state
                 state.is_connected = true;
                 state.reconnection_attempt = 0;
+                crate::telemetry::gauge!("hyperliquid_ws_connected").set(1.0);
 
                 // Send connected event
                 let _ = self.event_tx.send(WebSocketEvent::Connected);
 
                 // Start message handling in background task
-                self.start_message_handler(ws_stream).await?;
+                match connection {
+                    Connection::Plain(ws_stream) => self.start_message_handler(ws_stream, None).await?,
+                    Connection::Deflate(ws_stream, params) => {
+                        self.compression.set_negotiated(params.is_some());
+                        self.start_message_handler(ws_stream, params).await?
+                    }
+                }
 
                 // Start heartbeat if enabled
                 if self.config.enable_heartbeat {
@@ -227,7 +347,7 @@ impl WebSocketClient {
             }
             Ok(Err(e)) => {
                 error!("WebSocket connection failed: {}", e);
-                Err(WebSocketError::Connection(e.to_string()))
+                Err(e)
             }
             Err(_) => {
                 error!("WebSocket connection timeout");
@@ -237,12 +357,17 @@ impl WebSocketClient {
     }
 
     /// Start the message handler in a background task
-    async fn start_message_handler(
+    ///
+    /// With `compression` negotiated, text messages are handed to an inflater
+    /// thread and read back in order.
+    async fn start_message_handler<S>(
         &mut self,
-        ws_stream: tokio_tungstenite::WebSocketStream<
-            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-        >,
-    ) -> Result<(), WebSocketError> {
+        ws_stream: tokio_tungstenite::WebSocketStream<S>,
+        compression: Option<DeflateParams>,
+    ) -> Result<(), WebSocketError>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
         // Split the WebSocket stream
         let (write, read) = ws_stream.split();
 
@@ -256,47 +381,46 @@ impl WebSocketClient {
         let config = self.config.clone();
         let message_router = self.message_router.clone();
         let buffer = self.buffer.clone();
+        let arena_path = self.arena_path.clone();
+        let pending_posts = self.pending_posts.clone();
+        let mut spsc_producer = match self.config.buffer_mode {
+            BufferMode::Spsc { wait_strategy } if self.config.enable_buffer => {
+                Some(self.start_spsc_consumer(wait_strategy)?)
+            }
+            _ => None,
+        };
+        let (inflate_tx, mut inflated_rx) = match compression {
+            Some(params) => {
+                let (tx, rx) = compression::spawn_inflater(params, self.compression.clone())?;
+                (Some(tx), Some(rx))
+            }
+            None => (None, None),
+        };
         let mut shutdown_rx = self.shutdown_tx.subscribe();
 
         tokio::spawn(async move {
             let (mut write, mut read) = (write, read);
 
             loop {
-                tokio::select! {
+                let text = tokio::select! {
                     // Handle incoming messages
                     msg = read.next() => {
                         match msg {
                             Some(Ok(Message::Text(text))) => {
-                                debug!("Received WebSocket message: {}", text);
-
-                                // Try to parse as WebSocketResponse
-                                match WebSocketResponse::try_from(text.as_str()) {
-                                    Ok(response) => {
-                                        // If buffer is enabled, insert message into buffer
-                                        if let Some(buffer) = &buffer {
-                                            let evicted = buffer.insert(response.clone());
-                                            if evicted {
-                                                debug!("Buffer full, evicted oldest message");
-                                            }
-                                        } else {
-                                            // No buffer, route directly
-                                            message_router.route_message(response.clone()).await;
-                                            // Also send as event for backward compatibility
-                                            let _ = event_tx.send(WebSocketEvent::Data(response));
-                                        }
-                                    }
-                                    Err(e) => {
-                                        warn!("Failed to parse WebSocket message: {}", e);
-                                        // Check if it's a ping/pong
-                                        if text == "ping" {
-                                            let _ = event_tx.send(WebSocketEvent::Heartbeat);
-                                            // Send pong response
-                                            if let Err(e) = write.send(Message::Text("pong".to_string())).await {
-                                                error!("Failed to send pong: {}", e);
-                                            }
-                                        }
+                                match &inflate_tx {
+                                    // Queue behind compressed messages to keep arrival order
+                                    Some(inflate_tx) => {
+                                        let _ = inflate_tx.send(InflaterInput::Text(text));
+                                        continue;
                                     }
+                                    None => text,
+                                }
+                            }
+                            Some(Ok(Message::Binary(data))) if inflate_tx.is_some() => {
+                                if let Some(inflate_tx) = &inflate_tx {
+                                    let _ = inflate_tx.send(InflaterInput::Compressed(data));
                                 }
+                                continue;
                             }
                             Some(Ok(Message::Ping(data))) => {
                                 debug!("Received WebSocket ping");
@@ -306,10 +430,12 @@ impl WebSocketClient {
                                 if let Err(e) = write.send(Message::Pong(data)).await {
                                     error!("Failed to send pong response: {}", e);
                                 }
+                                continue;
                             }
                             Some(Ok(Message::Pong(_))) => {
                                 debug!("Received WebSocket pong");
                                 let _ = event_tx.send(WebSocketEvent::Heartbeat);
+                                continue;
                             }
                             Some(Ok(Message::Close(_))) => {
                                 info!("WebSocket connection closed by server");
@@ -342,6 +468,23 @@ impl WebSocketClient {
                             }
                             _ => {
                                 // Ignore other message types
+                                continue;
+                            }
+                        }
+                    }
+
+                    // Messages back from the inflater thread
+                    Some(inflated) = Self::recv_inflated(&mut inflated_rx) => {
+                        match inflated {
+                            Ok(text) => text,
+                            Err(e) => {
+                                // The shared deflate window is lost, so start over
+                                error!("Failed to inflate WebSocket message: {}", e);
+                                let _ = event_tx.send(WebSocketEvent::Error(e));
+                                if config.auto_reconnect {
+                                    let _ = event_tx.send(WebSocketEvent::Reconnecting(1));
+                                }
+                                break;
                             }
                         }
                     }
@@ -351,7 +494,7 @@ impl WebSocketClient {
                         match msg {
                             Some(request) => {
                                 // Check if this is a ping request
-                                if request.method == "ping" {
+                                if request.method() == "ping" {
                                     // Send WebSocket protocol ping frame (empty payload)
                                     debug!("Sending WebSocket protocol ping");
                                     if let Err(e) = write.send(Message::Ping(vec![])).await {
@@ -380,6 +523,7 @@ impl WebSocketClient {
                                         }
                                     }
                                 }
+                                continue;
                             }
                             None => {
                                 // Message channel closed
@@ -393,9 +537,72 @@ impl WebSocketClient {
                         info!("WebSocket client shutting down");
                         break;
                     }
+                };
+
+                debug!("Received WebSocket message: {}", text);
+                crate::telemetry::counter!("hyperliquid_ws_messages_received_total").increment(1);
+
+                // Hot-path market data goes through the arena decoder when enabled
+                if Self::dispatch_arena(&arena_path, &text) {
+                    continue;
+                }
+
+                // Channels excluded from decoding are passed through as text
+                if let Some(channel) = config.decode_filter.skipped_channel(&text) {
+                    crate::telemetry::counter!("hyperliquid_ws_messages_raw_total").increment(1);
+                    let _ = event_tx.send(WebSocketEvent::Raw(RawMessage::new(channel, text)));
+                    continue;
+                }
+
+                // Try to parse as WebSocketResponse
+                match WebSocketResponse::try_from(text.as_str()) {
+                    Ok(response) if response.channel == "post" => {
+                        Self::resolve_post(&pending_posts, response.data);
+                    }
+                    Ok(response) => {
+                        if let Some(latency) = response.latency() {
+                            crate::telemetry::histogram!("hyperliquid_ws_message_latency_seconds", "channel" => response.channel.clone())
+                                .record(latency.as_secs_f64());
+                        }
+
+                        // SPSC mode hands off to the dedicated consumer thread
+                        if let Some(producer) = &mut spsc_producer {
+                            if producer.try_push(response).is_err() {
+                                crate::telemetry::counter!("hyperliquid_ws_messages_dropped_total").increment(1);
+                                debug!("SPSC buffer full, dropped newest message");
+                            }
+                        } else if let Some(buffer) = &buffer {
+                            // If buffer is enabled, insert message into buffer
+                            let evicted = buffer.insert(response.clone());
+                            if evicted {
+                                crate::telemetry::counter!("hyperliquid_ws_messages_dropped_total").increment(1);
+                                debug!("Buffer full, evicted oldest message");
+                            }
+                        } else {
+                            // No buffer, route directly
+                            message_router.route_message(response.clone()).await;
+                            // Also send as event for backward compatibility
+                            let _ = event_tx.send(WebSocketEvent::Data(response));
+                        }
+                    }
+                    Err(e) => {
+                        warn!("Failed to parse WebSocket message: {}", e);
+                        // Check if it's a ping/pong
+                        if text == "ping" {
+                            let _ = event_tx.send(WebSocketEvent::Heartbeat);
+                            // Send pong response
+                            if let Err(e) = write.send(Message::Text("pong".to_string())).await {
+                                error!("Failed to send pong: {}", e);
+                            }
+                        }
+                    }
                 }
             }
 
+            // Fail outstanding posts; their responses can't arrive on a new connection
+            pending_posts.lock().unwrap_or_else(|e| e.into_inner()).clear();
+            crate::telemetry::gauge!("hyperliquid_ws_connected").set(0.0);
+
             // Update state
             if let Ok(mut state) = state.write().await {
                 state.is_connected = false;
@@ -405,6 +612,14 @@ impl WebSocketClient {
         Ok(())
     }
 
+    /// Next message from the inflater, or never without one
+    async fn recv_inflated(inflated: &mut Option<Inflated>) -> Option<Result<String, WebSocketError>> {
+        match inflated {
+            Some(inflated) => inflated.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Start the buffer consumer task
     async fn start_buffer_consumer(&mut self) -> Result<(), WebSocketError> {
         let buffer = match &self.buffer {
@@ -439,6 +654,35 @@ impl WebSocketClient {
         Ok(())
     }
 
+    /// Start the SPSC consumer thread and return the producer half
+    ///
+    /// The consumer runs on a dedicated OS thread so busy-spinning never stalls
+    /// the tokio workers; routing is driven through the current runtime handle.
+    /// The thread exits once the producer is dropped with the read task.
+    fn start_spsc_consumer(
+        &self,
+        wait_strategy: SpscWaitStrategy,
+    ) -> Result<SpscProducer<WebSocketResponse>, WebSocketError> {
+        let (producer, mut consumer) = spsc_channel(self.config.buffer_capacity, wait_strategy);
+        let message_router = self.message_router.clone();
+        let event_tx = self.event_tx.clone();
+        let runtime = tokio::runtime::Handle::current();
+
+        std::thread::Builder::new()
+            .name("hyperliquid-spsc-consumer".to_string())
+            .spawn(move || {
+                while let Some(message) = consumer.pop_blocking() {
+                    runtime.block_on(message_router.route_message(message.clone()));
+                    // Also send as event for backward compatibility
+                    let _ = event_tx.send(WebSocketEvent::Data(message));
+                }
+                debug!("SPSC consumer shutting down");
+            })
+            .map_err(|e| WebSocketError::Connection(format!("Failed to spawn SPSC consumer: {}", e)))?;
+
+        Ok(producer)
+    }
+
     /// Get buffer statistics if buffer is enabled
     pub fn buffer_stats(&self) -> Option<super::buffer::BufferStats> {
         self.buffer.as_ref().map(|buffer| buffer.stats())
@@ -451,7 +695,19 @@ impl WebSocketClient {
 
     /// Check if buffer is enabled
     pub fn is_buffer_enabled(&self) -> bool {
-        self.buffer.is_some()
+        self.buffer.is_some() || self.is_spsc_enabled()
+    }
+
+    /// Get permessage-deflate statistics, if compression is enabled
+    pub fn compression_stats(&self) -> Option<CompressionStats> {
+        self.config
+            .enable_compression
+            .then(|| self.compression.stats())
+    }
+
+    /// Check if the lock-free SPSC hand-off is enabled
+    pub fn is_spsc_enabled(&self) -> bool {
+        self.config.enable_buffer && matches!(self.config.buffer_mode, BufferMode::Spsc { .. })
     }
 
     /// Clear the buffer (remove all messages)
@@ -498,6 +754,7 @@ impl WebSocketClient {
 
         // Send reconnecting event
         let _ = self.event_tx.send(WebSocketEvent::Reconnecting(attempt));
+        crate::telemetry::counter!("hyperliquid_ws_reconnects_total").increment(1);
 
         // Release the lock before sleeping
         drop(state);
@@ -606,7 +863,7 @@ impl WebSocketClient {
                                 };
 
                                 // Try to send ping request through message channel
-                                if let Err(e) = message_tx.send(ping_request) {
+                                if let Err(e) = message_tx.send(Outbound::Request(ping_request)) {
                                     warn!("Failed to send ping request: {}", e);
                                 }
                             }
@@ -624,10 +881,17 @@ impl WebSocketClient {
     }
 
     /// Subscribe to a channel
+    ///
+    /// Fails with [`WebSocketError::SubscriptionLimit`] if a new subscription
+    /// would exceed the configured caps, since the exchange would otherwise
+    /// drop it silently.
     pub async fn subscribe(&self, subscription: Subscription) -> Result<(), WebSocketError> {
         // Add to subscriptions list
         {
             let mut state = self.state.write().await;
+            self.config
+                .subscription_limits
+                .check(&state.subscriptions, &subscription)?;
             if !state.subscriptions.contains(&subscription) {
                 state.subscriptions.push(subscription.clone());
             }
@@ -668,6 +932,11 @@ impl WebSocketClient {
 
     /// Send a WebSocket request
     async fn send_request(&self, request: WebSocketRequest) -> Result<(), WebSocketError> {
+        self.send_outbound(Outbound::Request(request)).await
+    }
+
+    /// Queue a message for the writer
+    async fn send_outbound(&self, message: Outbound) -> Result<(), WebSocketError> {
         // Check if connected
         let state = self.state.read().await;
         if !state.is_connected {
@@ -675,12 +944,115 @@ impl WebSocketClient {
         }
 
         // Send request through message channel
-        self.message_tx.send(request)
+        self.message_tx.send(message)
             .map_err(|e| WebSocketError::Send(e.to_string()))?;
 
         Ok(())
     }
 
+    /// Send an info query or signed action over the WebSocket and await the response
+    ///
+    /// The round-trip runs in a `ws_post` span; its trace id is logged with the
+    /// request and the matching response so it can be correlated with other traffic.
+    pub async fn post(
+        &self,
+        request_type: PostRequestType,
+        payload: serde_json::Value,
+    ) -> Result<serde_json::Value, WebSocketError> {
+        self.post_with_sent_hook(request_type, payload, || {}).await
+    }
+
+    /// Like [`post`](Self::post), calling `on_sent` once the request has been
+    /// queued for the socket, before the response is awaited
+    pub async fn post_with_sent_hook(
+        &self,
+        request_type: PostRequestType,
+        payload: serde_json::Value,
+        on_sent: impl FnOnce() + Send,
+    ) -> Result<serde_json::Value, WebSocketError> {
+        let id = self.next_post_id.fetch_add(1, Ordering::Relaxed);
+        let trace = RequestTrace::ws_post(id, request_type.as_str());
+        let (tx, rx) = oneshot::channel();
+
+        self.pending_posts.lock().unwrap_or_else(|e| e.into_inner()).insert(
+            id,
+            PendingPost {
+                tx,
+                trace: trace.clone(),
+                sent_at: Instant::now(),
+            },
+        );
+
+        let timeout = Duration::from_secs(self.config.post_timeout_secs);
+        let result = async {
+            debug!(trace_id = trace.trace_id(), post_id = id, "Sending WebSocket post");
+            self.send_outbound(Outbound::Post(WebSocketPostRequest::new(id, request_type, payload)))
+                .await?;
+            on_sent();
+
+            match time::timeout(timeout, rx).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(_)) => Err(WebSocketError::ChannelClosed),
+                Err(_) => Err(WebSocketError::Timeout(format!("post {} timed out", id))),
+            }
+        }
+        .instrument(trace.span().clone())
+        .await;
+
+        if result.is_err() {
+            self.pending_posts.lock().unwrap_or_else(|e| e.into_inner()).remove(&id);
+        }
+        result
+    }
+
+    /// Complete the pending post matching a `post` channel message
+    fn resolve_post(pending_posts: &PendingPosts, data: serde_json::Value) {
+        let response: WebSocketPostResponse = match serde_json::from_value(data) {
+            Ok(response) => response,
+            Err(e) => {
+                warn!("Failed to parse post response: {}", e);
+                return;
+            }
+        };
+
+        let pending = pending_posts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&response.id);
+        let Some(pending) = pending else {
+            debug!(post_id = response.id, "Post response without a pending request");
+            return;
+        };
+
+        let result = if response.response.type_ == "error" {
+            let message = match response.response.payload {
+                serde_json::Value::String(message) => message,
+                other => other.to_string(),
+            };
+            Err(WebSocketError::Protocol(message))
+        } else {
+            Ok(response.response.payload)
+        };
+
+        crate::telemetry::histogram!("hyperliquid_ws_post_duration_seconds")
+            .record(pending.sent_at.elapsed().as_secs_f64());
+        pending.trace.span().in_scope(|| {
+            debug!(
+                trace_id = pending.trace.trace_id(),
+                post_id = response.id,
+                latency_ms = pending.sent_at.elapsed().as_millis() as u64,
+                ok = result.is_ok(),
+                "WebSocket post completed"
+            );
+        });
+        let _ = pending.tx.send(result);
+    }
+
+    /// Number of post requests awaiting a response
+    pub fn pending_post_count(&self) -> usize {
+        self.pending_posts.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
     /// Check if client is connected
     pub async fn is_connected(&self) -> bool {
         let state = self.state.read().await;
@@ -693,6 +1065,24 @@ impl WebSocketClient {
         state.subscriptions.clone()
     }
 
+    /// Current usage against the subscription caps
+    pub async fn subscription_usage(&self) -> SubscriptionUsage {
+        let state = self.state.read().await;
+        SubscriptionUsage::of(&state.subscriptions)
+    }
+
+    /// Whether `subscription` fits within the caps of this connection
+    ///
+    /// Lets a caller holding several connections pick one with room before
+    /// subscribing.
+    pub async fn can_subscribe(&self, subscription: &Subscription) -> bool {
+        let state = self.state.read().await;
+        self.config
+            .subscription_limits
+            .check(&state.subscriptions, subscription)
+            .is_ok()
+    }
+
     /// Get next event from the event stream
     pub async fn next_event(&self) -> Option<WebSocketEvent> {
         let mut event_rx = self.event_rx.lock().await;
@@ -722,6 +1112,57 @@ impl WebSocketClient {
         self.message_router.has_handler(subscription).await
     }
 
+    /// Route l2Book and trades messages through an arena-backed decoder
+    ///
+    /// Matching messages are decoded into arena-allocated structs with interned
+    /// symbols and passed to `handler` instead of the router, buffer and event
+    /// stream. The arena is recycled after every message, so the handler must
+    /// copy out anything it needs to keep. Messages for coins missing from the
+    /// symbol registry fall back to the JSON path.
+    pub fn set_arena_handler<F>(&self, handler: F)
+    where
+        F: FnMut(ArenaMessage<'_>) + Send + 'static,
+    {
+        let mut arena_path = self.arena_path.lock().unwrap_or_else(|e| e.into_inner());
+        *arena_path = Some((ArenaMessageDecoder::new(), Box::new(handler)));
+    }
+
+    /// Disable the arena decode path
+    pub fn clear_arena_handler(&self) {
+        let mut arena_path = self.arena_path.lock().unwrap_or_else(|e| e.into_inner());
+        *arena_path = None;
+    }
+
+    /// Check if the arena decode path is enabled
+    pub fn has_arena_handler(&self) -> bool {
+        self.arena_path.lock().map(|p| p.is_some()).unwrap_or(false)
+    }
+
+    /// Try to decode a message through the arena path, returning true if handled
+    fn dispatch_arena(arena_path: &ArenaPath, text: &str) -> bool {
+        let mut guard = match arena_path.lock() {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+        let (decoder, handler) = match guard.as_mut() {
+            Some(path) => (&mut path.0, &mut path.1),
+            None => return false,
+        };
+
+        match decoder.decode(text) {
+            Ok(Some(message)) => {
+                handler(message);
+                decoder.reset();
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!("Arena decode failed, falling back to JSON path: {}", e);
+                false
+            }
+        }
+    }
+
     /// Shutdown the WebSocket client
     pub async fn shutdown(&self) -> Result<(), WebSocketError> {
         let _ = self.shutdown_tx.send(()).await;
@@ -737,6 +1178,13 @@ impl Clone for WebSocketClient {
             event_tx: self.event_tx.clone(),
             event_rx: self.event_rx.clone(),
             message_tx: self.message_tx.clone(),
+            message_router: self.message_router.clone(),
+            buffer: self.buffer.clone(),
+            buffer_consumer_handle: None,
+            arena_path: self.arena_path.clone(),
+            pending_posts: self.pending_posts.clone(),
+            next_post_id: self.next_post_id.clone(),
+            compression: self.compression.clone(),
             shutdown_tx: self.shutdown_tx.clone(),
         }
     }
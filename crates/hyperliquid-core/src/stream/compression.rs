@@ -0,0 +1,476 @@
+//! permessage-deflate (RFC 7692) for the WebSocket client
+//!
+//! With [`WebSocketClientConfig::enable_compression`](super::WebSocketClientConfig)
+//! set, the handshake offers `permessage-deflate` and, if the server accepts,
+//! compressed messages are inflated on a dedicated thread so the read loop
+//! only moves bytes. tungstenite rejects frames with the RSV1 bit set, so a
+//! thin [`DeflateStream`] under it clears the bit and hands compressed text
+//! messages over as binary ones, which are then inflated here. The API only
+//! sends text, so every binary message on a compressed connection is taken
+//! to be compressed.
+//!
+//! Plain text messages go through the same thread, keeping messages in
+//! arrival order whether or not the server compressed them.
+
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use flate2::{Decompress, FlushDecompress};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{header::SEC_WEBSOCKET_EXTENSIONS, HeaderValue};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{debug, info};
+
+use super::error::WebSocketError;
+
+/// Name of the extension in `Sec-WebSocket-Extensions`
+pub const PERMESSAGE_DEFLATE: &str = "permessage-deflate";
+
+/// Extension offered in the handshake
+const OFFER: &str = "permessage-deflate; client_max_window_bits";
+
+/// Tail stripped from every compressed message by the sender
+const DEFLATE_TRAILER: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Spare output capacity kept while inflating
+const MIN_SPARE: usize = 4096;
+
+const RSV1: u8 = 0x40;
+const OPCODE_MASK: u8 = 0x0f;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+
+/// Parameters of an accepted `permessage-deflate` extension
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeflateParams {
+    /// The server resets its compressor after every message
+    pub server_no_context_takeover: bool,
+    /// We may not reuse our compressor across messages
+    pub client_no_context_takeover: bool,
+    /// Window size the server compresses with
+    pub server_max_window_bits: Option<u8>,
+    /// Window size we may compress with
+    pub client_max_window_bits: Option<u8>,
+}
+
+impl DeflateParams {
+    /// Parse the server's `Sec-WebSocket-Extensions` response header
+    ///
+    /// Returns `None` unless `permessage-deflate` was accepted.
+    pub fn from_header(value: &str) -> Option<Self> {
+        value.split(',').find_map(|extension| {
+            let mut parts = extension.split(';').map(str::trim);
+            if parts.next()? != PERMESSAGE_DEFLATE {
+                return None;
+            }
+            let mut params = Self::default();
+            for part in parts {
+                let (name, value) = match part.split_once('=') {
+                    Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                    None => (part, None),
+                };
+                let bits = value.and_then(|bits| bits.parse().ok());
+                match name {
+                    "server_no_context_takeover" => params.server_no_context_takeover = true,
+                    "client_no_context_takeover" => params.client_no_context_takeover = true,
+                    "server_max_window_bits" => params.server_max_window_bits = bits,
+                    "client_max_window_bits" => params.client_max_window_bits = bits,
+                    _ => {}
+                }
+            }
+            Some(params)
+        })
+    }
+}
+
+/// Compression statistics of a connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompressionStats {
+    /// Whether the server accepted `permessage-deflate`
+    pub negotiated: bool,
+    /// Compressed messages inflated
+    pub messages: u64,
+    /// Bytes received compressed
+    pub compressed_bytes: u64,
+    /// Bytes those messages inflated to
+    pub decompressed_bytes: u64,
+    /// Messages that failed to inflate
+    pub errors: u64,
+}
+
+impl CompressionStats {
+    /// Decompressed bytes per compressed byte received
+    pub fn ratio(&self) -> f64 {
+        if self.compressed_bytes == 0 {
+            return 1.0;
+        }
+        self.decompressed_bytes as f64 / self.compressed_bytes as f64
+    }
+}
+
+/// Counters behind [`CompressionStats`], shared with the inflater thread
+#[derive(Debug, Default)]
+pub(crate) struct CompressionCounters {
+    negotiated: AtomicBool,
+    messages: AtomicU64,
+    compressed_bytes: AtomicU64,
+    decompressed_bytes: AtomicU64,
+    errors: AtomicU64,
+}
+
+impl CompressionCounters {
+    pub(crate) fn set_negotiated(&self, negotiated: bool) {
+        self.negotiated.store(negotiated, Ordering::Relaxed);
+    }
+
+    fn record(&self, compressed: usize, decompressed: usize) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.compressed_bytes
+            .fetch_add(compressed as u64, Ordering::Relaxed);
+        self.decompressed_bytes
+            .fetch_add(decompressed as u64, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_ws_compressed_bytes_total")
+            .increment(compressed as u64);
+        crate::telemetry::counter!("hyperliquid_ws_decompressed_bytes_total")
+            .increment(decompressed as u64);
+    }
+
+    fn record_error(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        crate::telemetry::counter!("hyperliquid_ws_decompression_errors_total").increment(1);
+    }
+
+    pub(crate) fn stats(&self) -> CompressionStats {
+        CompressionStats {
+            negotiated: self.negotiated.load(Ordering::Relaxed),
+            messages: self.messages.load(Ordering::Relaxed),
+            compressed_bytes: self.compressed_bytes.load(Ordering::Relaxed),
+            decompressed_bytes: self.decompressed_bytes.load(Ordering::Relaxed),
+            errors: self.errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Inflates the messages of one `permessage-deflate` connection
+pub struct Inflater {
+    decompress: Decompress,
+    reset_per_message: bool,
+}
+
+impl Inflater {
+    pub fn new(params: DeflateParams) -> Self {
+        Self {
+            // Raw deflate; a 15-bit window decodes any smaller one too
+            decompress: Decompress::new(false),
+            reset_per_message: params.server_no_context_takeover,
+        }
+    }
+
+    /// Inflate the payload of one compressed text message
+    pub fn inflate(&mut self, payload: &[u8]) -> Result<String, WebSocketError> {
+        let mut out = Vec::with_capacity(payload.len() * 4);
+        self.feed(payload, &mut out)?;
+        self.feed(&DEFLATE_TRAILER, &mut out)?;
+        if self.reset_per_message {
+            self.decompress.reset(false);
+        }
+        String::from_utf8(out).map_err(|e| {
+            WebSocketError::Deserialization(format!("inflated message is not UTF-8: {}", e))
+        })
+    }
+
+    fn feed(&mut self, mut input: &[u8], out: &mut Vec<u8>) -> Result<(), WebSocketError> {
+        loop {
+            if out.capacity() - out.len() < MIN_SPARE {
+                out.reserve(MIN_SPARE.max(input.len() * 2));
+            }
+            let total_in = self.decompress.total_in();
+            let total_out = self.decompress.total_out();
+            self.decompress
+                .decompress_vec(input, out, FlushDecompress::Sync)
+                .map_err(|e| WebSocketError::Protocol(format!("permessage-deflate: {}", e)))?;
+            let consumed = (self.decompress.total_in() - total_in) as usize;
+            let produced = self.decompress.total_out() - total_out;
+            input = &input[consumed..];
+
+            // Output left over means everything pending has been flushed
+            if input.is_empty() && out.len() < out.capacity() {
+                return Ok(());
+            }
+            if consumed == 0 && produced == 0 {
+                return Err(WebSocketError::Protocol(
+                    "permessage-deflate: inflater made no progress".to_string(),
+                ));
+            }
+        }
+    }
+}
+
+/// Message handed to the inflater thread
+pub(crate) enum InflaterInput {
+    /// Payload of a compressed message
+    Compressed(Vec<u8>),
+    /// Uncompressed text, passed through to keep arrival order
+    Text(String),
+}
+
+/// Inflate messages on a dedicated thread
+///
+/// Returns the sender feeding the thread and the receiver of its text
+/// messages, in the order they were sent. The thread exits once the sender
+/// is dropped with the read task.
+pub(crate) fn spawn_inflater(
+    params: DeflateParams,
+    counters: Arc<CompressionCounters>,
+) -> Result<
+    (
+        std::sync::mpsc::Sender<InflaterInput>,
+        mpsc::UnboundedReceiver<Result<String, WebSocketError>>,
+    ),
+    WebSocketError,
+> {
+    let (input_tx, input_rx) = std::sync::mpsc::channel();
+    let (output_tx, output_rx) = mpsc::unbounded_channel();
+
+    std::thread::Builder::new()
+        .name("hyperliquid-ws-inflate".to_string())
+        .spawn(move || {
+            let mut inflater = Inflater::new(params);
+            while let Ok(input) = input_rx.recv() {
+                let text = match input {
+                    InflaterInput::Text(text) => Ok(text),
+                    InflaterInput::Compressed(payload) => {
+                        let inflated = inflater.inflate(&payload);
+                        match &inflated {
+                            Ok(text) => counters.record(payload.len(), text.len()),
+                            Err(_) => counters.record_error(),
+                        }
+                        inflated
+                    }
+                };
+                if output_tx.send(text).is_err() {
+                    break;
+                }
+            }
+            debug!("WebSocket inflater shutting down");
+        })
+        .map_err(|e| {
+            WebSocketError::Connection(format!("Failed to spawn WebSocket inflater: {}", e))
+        })?;
+
+    Ok((input_tx, output_rx))
+}
+
+/// Tracks frame boundaries of the incoming byte stream
+#[derive(Debug, Default)]
+struct FrameMarker {
+    /// Bytes of the `\r\n\r\n` ending the handshake response matched so far
+    handshake_matched: u8,
+    handshake_done: bool,
+    header: [u8; 14],
+    header_len: usize,
+    payload_left: u64,
+}
+
+impl FrameMarker {
+    /// Rewrite the first byte of compressed frames in `bytes`
+    fn scan(&mut self, bytes: &mut [u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            if !self.handshake_done {
+                self.handshake_matched = match (self.handshake_matched, bytes[i]) {
+                    (0 | 2, b'\r') | (1 | 3, b'\n') => self.handshake_matched + 1,
+                    (_, b'\r') => 1,
+                    _ => 0,
+                };
+                self.handshake_done = self.handshake_matched == 4;
+                i += 1;
+                continue;
+            }
+            if self.payload_left > 0 {
+                let skip = self.payload_left.min((bytes.len() - i) as u64);
+                self.payload_left -= skip;
+                i += skip as usize;
+                continue;
+            }
+
+            if self.header_len == 0 && bytes[i] & RSV1 != 0 {
+                // Compressed text arrives as binary for the inflater
+                let opcode = match bytes[i] & OPCODE_MASK {
+                    OPCODE_TEXT => OPCODE_BINARY,
+                    opcode => opcode,
+                };
+                bytes[i] = (bytes[i] & !RSV1 & !OPCODE_MASK) | opcode;
+            }
+            self.header[self.header_len] = bytes[i];
+            self.header_len += 1;
+            i += 1;
+            if let Some(payload_len) = self.payload_len() {
+                self.payload_left = payload_len;
+                self.header_len = 0;
+            }
+        }
+    }
+
+    /// Payload length once the whole frame header has been read
+    fn payload_len(&self) -> Option<u64> {
+        if self.header_len < 2 {
+            return None;
+        }
+        let len = self.header[1] & 0x7f;
+        let extended = match len {
+            126 => 2,
+            127 => 8,
+            _ => 0,
+        };
+        let mask = if self.header[1] & 0x80 != 0 { 4 } else { 0 };
+        if self.header_len < 2 + extended + mask {
+            return None;
+        }
+        Some(match len {
+            126 => u16::from_be_bytes([self.header[2], self.header[3]]) as u64,
+            127 => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(&self.header[2..10]);
+                u64::from_be_bytes(bytes)
+            }
+            len => len as u64,
+        })
+    }
+}
+
+/// Stream under tungstenite clearing the RSV1 bit of compressed frames
+#[derive(Debug)]
+pub(crate) struct DeflateStream<S> {
+    inner: S,
+    marker: FrameMarker,
+}
+
+impl<S> DeflateStream<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner,
+            marker: FrameMarker::default(),
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for DeflateStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.marker.scan(&mut buf.filled_mut()[start..]);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for DeflateStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Connection offering `permessage-deflate`
+pub(crate) type DeflateWebSocket = WebSocketStream<DeflateStream<MaybeTlsStream<TcpStream>>>;
+
+/// Connect to `url`, offering `permessage-deflate`
+///
+/// Returns the parameters the server accepted, if it did. Only `ws://` URLs
+/// are supported, like the uncompressed path without a TLS backend.
+pub(crate) async fn connect(
+    url: &str,
+) -> Result<(DeflateWebSocket, Option<DeflateParams>), WebSocketError> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| WebSocketError::Connection(e.to_string()))?;
+    request
+        .headers_mut()
+        .insert(SEC_WEBSOCKET_EXTENSIONS, HeaderValue::from_static(OFFER));
+
+    let uri = request.uri();
+    let host = uri
+        .host()
+        .ok_or_else(|| WebSocketError::Connection(format!("no host in {}", url)))?
+        .to_string();
+    if uri.scheme_str() != Some("ws") {
+        return Err(WebSocketError::Connection(format!(
+            "compressed connections need a ws:// URL, got {}",
+            url
+        )));
+    }
+    let port = uri.port_u16().unwrap_or(80);
+
+    let tcp = TcpStream::connect((host.as_str(), port))
+        .await
+        .map_err(|e| WebSocketError::Connection(e.to_string()))?;
+    let _ = tcp.set_nodelay(true);
+    let (ws, response) =
+        tokio_tungstenite::client_async(request, DeflateStream::new(MaybeTlsStream::Plain(tcp)))
+            .await
+            .map_err(|e| WebSocketError::Connection(e.to_string()))?;
+
+    let params = response
+        .headers()
+        .get_all(SEC_WEBSOCKET_EXTENSIONS)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .find_map(DeflateParams::from_header);
+    match &params {
+        Some(params) => info!("Negotiated permessage-deflate: {:?}", params),
+        None => info!("Server declined permessage-deflate"),
+    }
+    Ok((ws, params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_rewrites_compressed_text_frames() {
+        let mut bytes = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n".to_vec();
+        let start = bytes.len();
+        // Compressed text, plain text, compressed text with a 16-bit length, ping
+        bytes.extend([0xc1, 0x02, 0xc1, 0x01]);
+        bytes.extend([0x81, 0x01, 0xc1]);
+        bytes.extend([0xc1, 0x7e, 0x00, 0x7e]);
+        bytes.extend(std::iter::repeat(0xc1).take(126));
+        bytes.extend([0x89, 0x00]);
+
+        // Split inside a header to carry state across reads
+        let mut marker = FrameMarker::default();
+        let (first, second) = bytes.split_at_mut(start + 9);
+        marker.scan(first);
+        marker.scan(second);
+        let frames = &bytes[start..];
+
+        assert_eq!(&frames[..4], &[0x82, 0x02, 0xc1, 0x01]);
+        assert_eq!(&frames[4..7], &[0x81, 0x01, 0xc1]);
+        assert_eq!(&frames[7..9], &[0x82, 0x7e]);
+        assert!(frames[11..137].iter().all(|&byte| byte == 0xc1));
+        assert_eq!(&frames[137..], &[0x89, 0x00]);
+    }
+}
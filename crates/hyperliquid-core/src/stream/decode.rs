@@ -0,0 +1,117 @@
+//! Choosing which channels are decoded
+//!
+//! Every message is normally parsed into a [`WebSocketResponse`] with a
+//! `serde_json::Value` payload. A [`DecodeFilter`] in
+//! [`WebSocketClientConfig`](super::WebSocketClientConfig) limits that to
+//! the channels a process uses: messages on other channels only have their
+//! channel name read and are passed through, as the text received, in
+//! [`WebSocketEvent::Raw`](super::WebSocketEvent::Raw) events. They are not
+//! routed to handlers. `post` responses are always decoded.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), hyperliquid_core::stream::WebSocketError> {
+//! use hyperliquid_core::stream::{DecodeFilter, WebSocketClient, WebSocketClientConfig};
+//!
+//! // Trades only; webData2 blobs on the same connection stay as text
+//! let config = WebSocketClientConfig::mainnet().with_decode_filter(DecodeFilter::only(["trades"]));
+//! let mut client = WebSocketClient::with_config(config)?;
+//! client.connect().await?;
+//! # Ok(()) }
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+use super::WebSocketResponse;
+
+/// Channels that are decoded regardless of the filter
+const ALWAYS_DECODED: &[&str] = &["post"];
+
+/// Which channels are decoded into [`WebSocketResponse`]s
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DecodeFilter {
+    /// Decode every channel
+    #[default]
+    All,
+    /// Decode only these channels
+    Only(HashSet<String>),
+    /// Decode every channel but these
+    Except(HashSet<String>),
+}
+
+impl DecodeFilter {
+    pub fn only<S: Into<String>>(channels: impl IntoIterator<Item = S>) -> Self {
+        DecodeFilter::Only(channels.into_iter().map(Into::into).collect())
+    }
+
+    pub fn except<S: Into<String>>(channels: impl IntoIterator<Item = S>) -> Self {
+        DecodeFilter::Except(channels.into_iter().map(Into::into).collect())
+    }
+
+    /// Whether messages on `channel` are decoded
+    ///
+    /// Channels are matched by name, ignoring any `.<coin>` suffix.
+    pub fn decodes(&self, channel: &str) -> bool {
+        let name = channel.split('.').next().unwrap_or(channel);
+        if ALWAYS_DECODED.contains(&name) {
+            return true;
+        }
+        match self {
+            DecodeFilter::All => true,
+            DecodeFilter::Only(channels) => channels.contains(name),
+            DecodeFilter::Except(channels) => !channels.contains(name),
+        }
+    }
+
+    /// Channel of `text` if the message is to be passed through undecoded
+    ///
+    /// Only the channel name is parsed. Messages without one (e.g. plain
+    /// `ping`) are left to the normal decode path.
+    pub fn skipped_channel(&self, text: &str) -> Option<String> {
+        if *self == DecodeFilter::All {
+            return None;
+        }
+        #[derive(Deserialize)]
+        struct Envelope<'a> {
+            #[serde(borrow)]
+            channel: Cow<'a, str>,
+        }
+        let envelope: Envelope<'_> = serde_json::from_str(text).ok()?;
+        (!self.decodes(&envelope.channel)).then(|| envelope.channel.into_owned())
+    }
+}
+
+/// Message on a channel excluded by the [`DecodeFilter`]
+#[derive(Debug, Clone)]
+pub struct RawMessage {
+    pub channel: String,
+    /// Message as received
+    pub text: Arc<str>,
+    /// Local monotonic time the message was read
+    pub received_at: Instant,
+}
+
+impl RawMessage {
+    pub fn new(channel: impl Into<String>, text: impl Into<Arc<str>>) -> Self {
+        Self {
+            channel: channel.into(),
+            text: text.into(),
+            received_at: Instant::now(),
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /// Decode the message after all, keeping its receive time
+    pub fn decode(&self) -> Result<WebSocketResponse, serde_json::Error> {
+        let mut response = WebSocketResponse::try_from(&*self.text)?;
+        response.received_at = self.received_at;
+        Ok(response)
+    }
+}
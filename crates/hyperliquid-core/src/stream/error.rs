@@ -23,6 +23,9 @@ pub enum WebSocketError {
     #[error("WebSocket subscription error: {0}")]
     Subscription(String),
 
+    #[error("WebSocket subscription limit reached: {0}")]
+    SubscriptionLimit(String),
+
     #[error("WebSocket serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
 
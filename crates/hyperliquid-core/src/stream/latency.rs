@@ -0,0 +1,136 @@
+//! Per-channel feed latency
+//!
+//! Every [`WebSocketResponse`] carries its exchange timestamp and a local
+//! monotonic receive time. A [`FeedLatencyTracker`] fed those responses
+//! keeps, per channel, how far behind the exchange the feed arrives; the
+//! client also records it in the `hyperliquid_ws_message_latency_seconds`
+//! histogram with a `channel` label.
+//!
+//! ```no_run
+//! # async fn example(ws: hyperliquid_core::stream::WebSocketClient) {
+//! use std::time::Duration;
+//! use hyperliquid_core::stream::FeedLatencyTracker;
+//! use hyperliquid_core::Subscription;
+//!
+//! let tracker = FeedLatencyTracker::new();
+//! let feed = tracker.clone();
+//! ws.register_handler(Subscription::L2Book { coin: "BTC".into() }, move |response| {
+//!     feed.record(&response);
+//!     if response.is_stale(Duration::from_millis(500)) {
+//!         return;
+//!     }
+//!     // ... use the book
+//! })
+//! .await;
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::WebSocketResponse;
+use crate::clock::{self, Clock};
+
+/// Latency summary of one channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Messages with an exchange timestamp
+    pub count: u64,
+    pub last: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+}
+
+#[derive(Debug, Default)]
+struct ChannelState {
+    count: u64,
+    last: Duration,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+/// Exchange-to-receipt latency by channel
+#[derive(Clone)]
+pub struct FeedLatencyTracker {
+    channels: Arc<Mutex<HashMap<String, ChannelState>>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for FeedLatencyTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeedLatencyTracker")
+            .field("channels", &self.lock().len())
+            .finish()
+    }
+}
+
+impl Default for FeedLatencyTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeedLatencyTracker {
+    pub fn new() -> Self {
+        Self {
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            clock: clock::system(),
+        }
+    }
+
+    /// Clock the receive times are converted to wall-clock time with
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, ChannelState>> {
+        self.channels.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record `response` and return its latency
+    ///
+    /// Messages without an exchange timestamp are not counted.
+    pub fn record(&self, response: &WebSocketResponse) -> Option<Duration> {
+        let latency = response.latency_at(self.clock.as_ref())?;
+        let mut channels = self.lock();
+        let state = channels.entry(response.channel.clone()).or_default();
+        if state.count == 0 || latency < state.min {
+            state.min = latency;
+        }
+        state.max = state.max.max(latency);
+        state.last = latency;
+        state.total += latency;
+        state.count += 1;
+        Some(latency)
+    }
+
+    pub fn stats(&self, channel: &str) -> Option<LatencyStats> {
+        self.lock().get(channel).map(summarize)
+    }
+
+    /// Stats of every channel seen, by channel name
+    pub fn all(&self) -> BTreeMap<String, LatencyStats> {
+        self.lock()
+            .iter()
+            .map(|(channel, state)| (channel.clone(), summarize(state)))
+            .collect()
+    }
+
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+}
+
+fn summarize(state: &ChannelState) -> LatencyStats {
+    LatencyStats {
+        count: state.count,
+        last: state.last,
+        min: state.min,
+        max: state.max,
+        mean: state.total / state.count.max(1) as u32,
+    }
+}
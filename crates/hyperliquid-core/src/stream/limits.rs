@@ -0,0 +1,122 @@
+//! Client-side accounting of WebSocket subscription caps
+//!
+//! The exchange caps the number of subscriptions and the number of distinct
+//! users across user-specific subscriptions. Past either cap it drops new
+//! subscriptions without an error, so [`WebSocketClient::subscribe`] checks
+//! them before sending anything.
+//!
+//! [`WebSocketClient::subscribe`]: super::WebSocketClient::subscribe
+
+use std::collections::HashSet;
+
+use super::error::WebSocketError;
+use crate::types::{Address, Subscription};
+
+/// Documented cap on subscriptions
+pub const MAX_SUBSCRIPTIONS: usize = 1000;
+
+/// Documented cap on distinct users across user-specific subscriptions
+pub const MAX_UNIQUE_USERS: usize = 10;
+
+/// Subscription caps enforced by a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscriptionLimits {
+    /// Maximum number of active subscriptions
+    pub max_subscriptions: usize,
+    /// Maximum number of distinct users across user-specific subscriptions
+    pub max_unique_users: usize,
+}
+
+impl Default for SubscriptionLimits {
+    fn default() -> Self {
+        Self {
+            max_subscriptions: MAX_SUBSCRIPTIONS,
+            max_unique_users: MAX_UNIQUE_USERS,
+        }
+    }
+}
+
+impl SubscriptionLimits {
+    /// No client-side caps
+    pub fn unlimited() -> Self {
+        Self {
+            max_subscriptions: usize::MAX,
+            max_unique_users: usize::MAX,
+        }
+    }
+
+    /// Check that `subscription` can be added to `active`
+    ///
+    /// A subscription already in `active` always passes, so re-subscribing
+    /// after a reconnect is never rejected.
+    pub fn check(
+        &self,
+        active: &[Subscription],
+        subscription: &Subscription,
+    ) -> Result<(), WebSocketError> {
+        if active.contains(subscription) {
+            return Ok(());
+        }
+        let usage = SubscriptionUsage::of(active);
+        if usage.subscriptions >= self.max_subscriptions {
+            return Err(WebSocketError::SubscriptionLimit(format!(
+                "{} of {} subscriptions in use",
+                usage.subscriptions, self.max_subscriptions
+            )));
+        }
+        if let Some(user) = subscription_user(subscription) {
+            let users = unique_users(active);
+            if !users.contains(user) && users.len() >= self.max_unique_users {
+                return Err(WebSocketError::SubscriptionLimit(format!(
+                    "{} of {} users in use, cannot add {}",
+                    users.len(),
+                    self.max_unique_users,
+                    user
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Snapshot of subscription usage on a connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubscriptionUsage {
+    /// Number of active subscriptions
+    pub subscriptions: usize,
+    /// Number of distinct users across user-specific subscriptions
+    pub unique_users: usize,
+}
+
+impl SubscriptionUsage {
+    pub fn of(active: &[Subscription]) -> Self {
+        Self {
+            subscriptions: active.len(),
+            unique_users: unique_users(active).len(),
+        }
+    }
+}
+
+/// User a subscription is specific to, if any
+fn subscription_user(subscription: &Subscription) -> Option<&Address> {
+    match subscription {
+        Subscription::UserEvents { user }
+        | Subscription::UserFills { user }
+        | Subscription::OrderUpdates { user }
+        | Subscription::UserFundings { user }
+        | Subscription::UserNonFundingLedgerUpdates { user }
+        | Subscription::WebData2 { user }
+        | Subscription::ActiveAssetData { user, .. }
+        | Subscription::Notification { user } => Some(user),
+        Subscription::AllMids
+        | Subscription::L2Book { .. }
+        | Subscription::Trades { .. }
+        | Subscription::Bbo { .. }
+        | Subscription::Candle { .. }
+        | Subscription::ActiveAssetCtx { .. } => None,
+    }
+}
+
+fn unique_users(active: &[Subscription]) -> HashSet<&Address> {
+    active.iter().filter_map(subscription_user).collect()
+}
@@ -0,0 +1,503 @@
+//! Per-coin stream of candles and trades with gap backfill
+//!
+//! [`MarketStream`] merges a coin's `candle` and `trades` channels into one
+//! ordered sequence of [`MarketEvent`]s. Two kinds of gaps are backfilled
+//! over REST:
+//!
+//! - a live candle opening more than one interval after the previous one
+//!   means candles were skipped; `candleSnapshot` is queried from the
+//!   previous candle on, so its final values are recovered too;
+//! - after a reconnect, candles since the last one seen and trades since the
+//!   disconnect are fetched from `candleSnapshot` and `recentTrades`.
+//!
+//! Recovered events are flagged `recovered` and emitted ahead of the live
+//! messages that arrive meanwhile. Trade ids are not contiguous per coin, so
+//! trade gaps are only detected through disconnects, and `recentTrades` only
+//! returns the latest trades: a long outage can't be recovered in full.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient, ws: hyperliquid_core::stream::WebSocketClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::stream::MarketStream;
+//!
+//! let stream = MarketStream::new(client, "BTC", "1m");
+//! stream.attach(&ws).await?;
+//! let mut events = stream.events();
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         println!("{:?}", event);
+//!     }
+//! });
+//! while let Some(event) = ws.next_event().await {
+//!     stream.handle_ws_event(&event).await?;
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use super::WebSocketResponse;
+#[cfg(feature = "ws")]
+use super::{WebSocketClient, WebSocketEvent};
+use crate::client::HttpClient;
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the event channel; slow receivers miss older events
+const EVENT_CAPACITY: usize = 4096;
+
+/// Number of delivered trade ids remembered for deduplication
+const SEEN_CAPACITY: usize = 8192;
+
+/// Default lookback before the disconnect, covering messages lost in flight
+const DEFAULT_OVERLAP: Duration = Duration::from_secs(5);
+
+/// Length of a candle interval such as `"15m"` in milliseconds
+///
+/// `None` for unknown intervals and for `"1M"`, whose length varies; gaps
+/// between live candles are then not detected.
+pub fn interval_millis(interval: &str) -> Option<u64> {
+    const MINUTE: u64 = 60_000;
+    let minutes = match interval {
+        "1m" => 1,
+        "3m" => 3,
+        "5m" => 5,
+        "15m" => 15,
+        "30m" => 30,
+        "1h" => 60,
+        "2h" => 2 * 60,
+        "4h" => 4 * 60,
+        "8h" => 8 * 60,
+        "12h" => 12 * 60,
+        "1d" => 24 * 60,
+        "3d" => 3 * 24 * 60,
+        "1w" => 7 * 24 * 60,
+        _ => return None,
+    };
+    Some(minutes * MINUTE)
+}
+
+/// What a [`MarketEvent`] carries
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketEventKind {
+    /// A `candle` message; the open candle is sent again on every update
+    Candle(Value),
+    /// One element of a `trades` message
+    Trade(Value),
+}
+
+/// Candle or trade of the streamed coin
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketEvent {
+    pub kind: MarketEventKind,
+    /// Candle open time or trade time in ms
+    pub time: u64,
+    /// Whether the event was missed by the websocket and fetched over REST
+    pub recovered: bool,
+}
+
+#[derive(Debug)]
+struct State {
+    seen_trades: HashSet<u64>,
+    seen_order: VecDeque<u64>,
+    /// Open time of the latest candle delivered
+    last_candle: Option<u64>,
+    /// Start of the candle window to recover
+    candle_gap: Option<u64>,
+    /// Start of the trade window to recover, set on disconnect
+    trade_gap: Option<u64>,
+    recovering: bool,
+    /// Live events held back until the pending windows are recovered
+    held: Option<Vec<MarketEvent>>,
+}
+
+impl State {
+    /// Whether the trade hasn't been delivered yet, remembering it
+    fn is_new_trade(&mut self, tid: u64) -> bool {
+        if !self.seen_trades.insert(tid) {
+            return false;
+        }
+        if self.seen_order.len() >= SEEN_CAPACITY {
+            if let Some(oldest) = self.seen_order.pop_front() {
+                self.seen_trades.remove(&oldest);
+            }
+        }
+        self.seen_order.push_back(tid);
+        true
+    }
+}
+
+/// Ordered candles and trades of one coin, backfilled across gaps
+#[derive(Clone)]
+pub struct MarketStream {
+    client: HttpClient,
+    coin: String,
+    interval: String,
+    interval_ms: Option<u64>,
+    overlap: Duration,
+    state: Arc<Mutex<State>>,
+    events: broadcast::Sender<MarketEvent>,
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for MarketStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MarketStream")
+            .field("coin", &self.coin)
+            .field("interval", &self.interval)
+            .field("overlap", &self.overlap)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MarketStream {
+    /// Stream `coin`'s trades and its candles of `interval` (e.g. `"1m"`)
+    pub fn new(client: HttpClient, coin: impl Into<String>, interval: impl Into<String>) -> Self {
+        let interval = interval.into();
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            client,
+            coin: coin.into(),
+            interval_ms: interval_millis(&interval),
+            interval,
+            overlap: DEFAULT_OVERLAP,
+            state: Arc::new(Mutex::new(State {
+                seen_trades: HashSet::new(),
+                seen_order: VecDeque::new(),
+                last_candle: None,
+                candle_gap: None,
+                trade_gap: None,
+                recovering: false,
+                held: None,
+            })),
+            events,
+            clock: clock::system(),
+        }
+    }
+
+    /// How far before the disconnect to look for missed trades (default 5s)
+    pub fn with_overlap(mut self, overlap: Duration) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Clock that disconnects and recovery windows are timed with
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn coin(&self) -> &str {
+        &self.coin
+    }
+
+    pub fn interval(&self) -> &str {
+        &self.interval
+    }
+
+    pub fn events(&self) -> broadcast::Receiver<MarketEvent> {
+        self.events.subscribe()
+    }
+
+    /// Emit `event`, or hold it back while a gap is pending
+    fn deliver(&self, state: &mut State, event: MarketEvent) {
+        match state.held.as_mut() {
+            Some(held) => held.push(event),
+            None => {
+                let _ = self.events.send(event);
+            }
+        }
+    }
+
+    /// Apply a `data` payload from the `candle` channel
+    ///
+    /// Returns whether the candle revealed a gap; it is then held back until
+    /// [`recover`](Self::recover) has emitted the missing candles.
+    pub fn handle_candle(&self, data: &Value) -> bool {
+        let Some(time) = data.get("t").and_then(Value::as_u64) else {
+            warn!("Ignoring malformed candle message");
+            return false;
+        };
+        let mut state = self.lock();
+        let gap = match (state.last_candle, self.interval_ms) {
+            (Some(last), _) if time < last => {
+                // Already superseded, e.g. by a recovered candle
+                return false;
+            }
+            (Some(last), Some(interval)) if time > last + interval => Some(last),
+            _ => None,
+        };
+        if let Some(last) = gap {
+            state.candle_gap = Some(state.candle_gap.map_or(last, |start| start.min(last)));
+            state.held.get_or_insert_with(Vec::new);
+        }
+        state.last_candle = Some(time);
+        self.deliver(&mut state, candle_event(data.clone(), false));
+        gap.is_some()
+    }
+
+    /// Apply a `data` payload from the `trades` channel
+    pub fn handle_trades(&self, data: &Value) {
+        let Some(trades) = data.as_array() else {
+            warn!("Ignoring malformed trades message");
+            return;
+        };
+        let mut state = self.lock();
+        for trade in trades {
+            if let Some(tid) = trade.get("tid").and_then(Value::as_u64) {
+                if !state.is_new_trade(tid) {
+                    continue;
+                }
+            }
+            self.deliver(&mut state, trade_event(trade.clone(), false));
+        }
+    }
+
+    /// Track connectivity and backfill after a reconnect
+    ///
+    /// Feed every event from [`WebSocketClient::next_event`]. On
+    /// [`WebSocketEvent::Connected`] following a disconnect this returns once
+    /// the missed events have been emitted. If the REST queries fail, the
+    /// windows are kept and retried on the next reconnect or
+    /// [`recover`](Self::recover) call.
+    #[cfg(feature = "ws")]
+    pub async fn handle_ws_event(&self, event: &WebSocketEvent) -> Result<usize, HyperliquidError> {
+        match event {
+            WebSocketEvent::Disconnected | WebSocketEvent::Reconnecting(_) => {
+                let start = self
+                    .clock
+                    .now_ms()
+                    .saturating_sub(self.overlap.as_millis() as u64);
+                let mut state = self.lock();
+                let candle_start = state.last_candle.unwrap_or(start);
+                state.candle_gap.get_or_insert(candle_start);
+                state.trade_gap.get_or_insert(start);
+                Ok(0)
+            }
+            WebSocketEvent::Connected => self.recover().await,
+            _ => Ok(0),
+        }
+    }
+
+    /// Fetch and emit the events of every pending gap
+    ///
+    /// Does nothing if there is no gap. Returns the number of recovered
+    /// events.
+    pub async fn recover(&self) -> Result<usize, HyperliquidError> {
+        {
+            let mut state = self.lock();
+            if state.recovering {
+                // The running recovery picks up any window added meanwhile
+                return Ok(0);
+            }
+            state.recovering = true;
+            state.held.get_or_insert_with(Vec::new);
+        }
+
+        let mut recovered = 0;
+        let result = loop {
+            let (candle_start, trade_start) = {
+                let mut state = self.lock();
+                (state.candle_gap.take(), state.trade_gap.take())
+            };
+            if candle_start.is_none() && trade_start.is_none() {
+                break Ok(recovered);
+            }
+            let end = self.clock.now_ms();
+
+            match self.fetch(candle_start, trade_start, end).await {
+                Ok(events) => {
+                    let mut state = self.lock();
+                    // Live candles held back are newer than anything fetched
+                    let held_candle = state.held.iter().flatten().find_map(|event| {
+                        matches!(event.kind, MarketEventKind::Candle(_)).then_some(event.time)
+                    });
+                    let mut count = 0;
+                    for event in events {
+                        match &event.kind {
+                            MarketEventKind::Candle(_) => {
+                                if held_candle.is_some_and(|held| event.time >= held) {
+                                    continue;
+                                }
+                                let last = state.last_candle.get_or_insert(event.time);
+                                *last = (*last).max(event.time);
+                            }
+                            MarketEventKind::Trade(trade) => {
+                                if let Some(tid) = trade.get("tid").and_then(Value::as_u64) {
+                                    if !state.is_new_trade(tid) {
+                                        continue;
+                                    }
+                                }
+                            }
+                        }
+                        // Sent under the lock, ahead of the held live events
+                        let _ = self.events.send(event);
+                        count += 1;
+                    }
+                    drop(state);
+                    info!(
+                        "Recovered {} missed market events for {} up to {}",
+                        count, self.coin, end
+                    );
+                    recovered += count;
+                }
+                Err(e) => {
+                    let mut state = self.lock();
+                    let keep = |pending: Option<u64>, failed: Option<u64>| match (pending, failed) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                    state.candle_gap = keep(state.candle_gap, candle_start);
+                    state.trade_gap = keep(state.trade_gap, trade_start);
+                    break Err(e);
+                }
+            }
+        };
+
+        let mut state = self.lock();
+        state.recovering = false;
+        for event in state.held.take().unwrap_or_default() {
+            let _ = self.events.send(event);
+        }
+        result
+    }
+
+    /// Candles since `candle_start` and trades since `trade_start`, in time order
+    async fn fetch(
+        &self,
+        candle_start: Option<u64>,
+        trade_start: Option<u64>,
+        end: u64,
+    ) -> Result<Vec<MarketEvent>, HyperliquidError> {
+        let mut events = Vec::new();
+        if let Some(start) = candle_start {
+            let candles: Value = self
+                .client
+                .post(
+                    "/info",
+                    &json!({
+                        "type": "candleSnapshot",
+                        "req": {
+                            "coin": self.coin,
+                            "interval": self.interval,
+                            "startTime": start,
+                            "endTime": end,
+                        },
+                    }),
+                )
+                .await?;
+            events.extend(
+                candles
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|candle| candle_event(candle.clone(), true))
+                    .filter(|event| event.time >= start),
+            );
+        }
+        if let Some(start) = trade_start {
+            let trades: Value = self
+                .client
+                .post("/info", &json!({"type": "recentTrades", "coin": self.coin}))
+                .await?;
+            let mut trades: Vec<MarketEvent> = trades
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|trade| trade_event(trade.clone(), true))
+                .filter(|event| event.time >= start && event.time <= end)
+                .collect();
+            // Newest first on the wire
+            trades.sort_by_key(|event| (event.time, trade_id(&event.kind)));
+            events.extend(trades);
+        }
+        // Stable, so candles stay ahead of the trades of their minute
+        events.sort_by_key(|event| event.time);
+        Ok(events)
+    }
+
+    /// Subscribe `ws` to the coin's candles and trades and feed them in
+    ///
+    /// Registers the handlers for the `candle` and `trades` subscriptions,
+    /// replacing any existing handlers for them. Candle gaps found by the
+    /// handler are recovered on a spawned task.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        let subscriptions = [
+            (
+                "candle",
+                Subscription::Candle {
+                    coin: self.coin.clone(),
+                    interval: self.interval.clone(),
+                },
+            ),
+            (
+                "trades",
+                Subscription::Trades {
+                    coin: self.coin.clone(),
+                },
+            ),
+        ];
+        for (channel, subscription) in subscriptions {
+            let stream = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if !response.channel.starts_with(channel) {
+                    return;
+                }
+                if channel == "trades" {
+                    stream.handle_trades(&response.data);
+                } else if stream.handle_candle(&response.data) {
+                    let stream = stream.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = stream.recover().await {
+                            warn!("Failed to backfill candles for {}: {}", stream.coin, e);
+                        }
+                    });
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+fn trade_id(kind: &MarketEventKind) -> u64 {
+    match kind {
+        MarketEventKind::Trade(trade) => trade.get("tid").and_then(Value::as_u64).unwrap_or(0),
+        MarketEventKind::Candle(_) => 0,
+    }
+}
+
+fn candle_event(candle: Value, recovered: bool) -> MarketEvent {
+    MarketEvent {
+        time: candle.get("t").and_then(Value::as_u64).unwrap_or_default(),
+        kind: MarketEventKind::Candle(candle),
+        recovered,
+    }
+}
+
+fn trade_event(trade: Value, recovered: bool) -> MarketEvent {
+    MarketEvent {
+        time: trade
+            .get("time")
+            .and_then(Value::as_u64)
+            .unwrap_or_default(),
+        kind: MarketEventKind::Trade(trade),
+        recovered,
+    }
+}
@@ -1,4 +1,9 @@
+use std::time::{Duration, Instant};
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::clock::{Clock, SystemClock};
 use crate::types::Subscription;
 
 /// WebSocket request message
@@ -10,6 +15,68 @@ pub struct WebSocketRequest {
     pub subscription: Subscription,
 }
 
+/// Kind of request carried by a WebSocket post
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostRequestType {
+    /// Info query, same payload as `POST /info`
+    Info,
+    /// Signed action, same payload as `POST /exchange`
+    Action,
+}
+
+impl PostRequestType {
+    /// Wire name of the request type
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostRequestType::Info => "info",
+            PostRequestType::Action => "action",
+        }
+    }
+}
+
+/// Request sent over the WebSocket with `method: "post"`
+///
+/// The server answers on the `post` channel with the same `id`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebSocketPostRequest {
+    /// Always "post"
+    pub method: String,
+    /// Client-chosen id echoed in the response
+    pub id: u64,
+    /// Wrapped request
+    pub request: PostRequestBody,
+}
+
+/// Body of a WebSocket post request
+#[derive(Debug, Clone, Serialize)]
+pub struct PostRequestBody {
+    /// Request kind
+    #[serde(rename = "type")]
+    pub type_: PostRequestType,
+    /// Request payload
+    pub payload: serde_json::Value,
+}
+
+/// Data of a `post` channel message
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebSocketPostResponse {
+    /// Id of the originating request
+    pub id: u64,
+    /// Wrapped response
+    pub response: PostResponseBody,
+}
+
+/// Body of a WebSocket post response
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostResponseBody {
+    /// "info", "action" or "error"
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// Response payload (an error message string for "error")
+    pub payload: serde_json::Value,
+}
+
 /// WebSocket response message
 #[derive(Debug, Clone, Deserialize)]
 pub struct WebSocketResponse {
@@ -19,6 +86,65 @@ pub struct WebSocketResponse {
     pub data: serde_json::Value,
     /// Timestamp
     pub time: Option<i64>,
+    /// Local monotonic time the message was parsed
+    #[serde(skip, default = "Instant::now")]
+    pub received_at: Instant,
+}
+
+impl WebSocketResponse {
+    /// Response received now
+    pub fn new(channel: impl Into<String>, data: Value, time: Option<i64>) -> Self {
+        Self {
+            channel: channel.into(),
+            data,
+            time,
+            received_at: Instant::now(),
+        }
+    }
+
+    /// Exchange timestamp in ms
+    ///
+    /// Taken from `data.time`, the `time` of the first element of an array
+    /// payload (trades, fills), or the message's own `time`, in that order.
+    pub fn exchange_time(&self) -> Option<u64> {
+        self.data
+            .get("time")
+            .or_else(|| self.data.pointer("/0/time"))
+            .and_then(Value::as_u64)
+            .or_else(|| self.time.and_then(|time| u64::try_from(time).ok()))
+    }
+
+    /// Time since the message was received
+    pub fn age(&self) -> Duration {
+        self.received_at.elapsed()
+    }
+
+    /// Whether the message was received more than `max_age` ago
+    pub fn is_stale(&self, max_age: Duration) -> bool {
+        self.age() > max_age
+    }
+
+    /// Time from the exchange timestamp to receipt, by the system clock
+    pub fn latency(&self) -> Option<Duration> {
+        self.latency_at(&SystemClock)
+    }
+
+    /// [`latency`](Self::latency) by `clock`
+    ///
+    /// The receive time is `clock`'s current time less the message's
+    /// [`age`](Self::age). Exchange timestamps ahead of it (clock skew) give
+    /// zero.
+    pub fn latency_at(&self, clock: &dyn Clock) -> Option<Duration> {
+        let received = clock.now_ms().saturating_sub(self.age().as_millis() as u64);
+        self.exchange_time()
+            .map(|time| Duration::from_millis(received.saturating_sub(time)))
+    }
+
+    /// Time since the exchange timestamp by `clock`, receipt delay included
+    pub fn exchange_age(&self, clock: &dyn Clock) -> Option<Duration> {
+        self.exchange_time()
+            .map(|time| Duration::from_millis(clock.now_ms().saturating_sub(time)))
+    }
 }
 
 /// WebSocket message type
@@ -60,6 +186,17 @@ impl WebSocketRequest {
     }
 }
 
+impl WebSocketPostRequest {
+    /// Create a post request
+    pub fn new(id: u64, type_: PostRequestType, payload: serde_json::Value) -> Self {
+        Self {
+            method: "post".to_string(),
+            id,
+            request: PostRequestBody { type_, payload },
+        }
+    }
+}
+
 impl TryFrom<&str> for WebSocketResponse {
     type Error = serde_json::Error;
 
@@ -3,14 +3,41 @@
 //! This module provides a WebSocket client for subscribing to real-time market data
 //! from the Hyperliquid exchange, including order books, trades, candles, and user events.
 
+mod arena;
+#[cfg(feature = "ws")]
+mod asset_ctx;
+mod book;
 mod buffer;
+#[cfg(feature = "ws")]
 mod client;
+#[cfg(feature = "ws")]
+mod compression;
+mod decode;
 mod error;
+mod latency;
+mod limits;
+mod market;
 mod message;
 mod router;
+mod spsc;
+mod user;
 
+pub use arena::{ArenaL2Book, ArenaLevel, ArenaMessage, ArenaMessageDecoder, ArenaMessageHandler};
+pub use book::{BookDivergence, BookLevel, BookVerifier, LocalBook};
 pub use buffer::{CircularBuffer, BufferStats};
+#[cfg(feature = "ws")]
 pub use client::{WebSocketClient, WebSocketClientConfig, WebSocketEvent};
+#[cfg(feature = "ws")]
+pub use compression::{CompressionStats, DeflateParams, Inflater, PERMESSAGE_DEFLATE};
+pub use decode::{DecodeFilter, RawMessage};
 pub use error::WebSocketError;
-pub use message::{WebSocketMessage, WebSocketRequest, WebSocketResponse};
-pub use router::{MessageRouter, MessageHandler};
\ No newline at end of file
+pub use latency::{FeedLatencyTracker, LatencyStats};
+pub use limits::{SubscriptionLimits, SubscriptionUsage, MAX_SUBSCRIPTIONS, MAX_UNIQUE_USERS};
+pub use market::{interval_millis, MarketEvent, MarketEventKind, MarketStream};
+pub use message::{
+    PostRequestBody, PostRequestType, PostResponseBody, WebSocketMessage, WebSocketPostRequest,
+    WebSocketPostResponse, WebSocketRequest, WebSocketResponse,
+};
+pub use router::{MessageRouter, MessageHandler};
+pub use spsc::{spsc_channel, BufferMode, SpscConsumer, SpscProducer, SpscStats, SpscWaitStrategy};
+pub use user::{UserEvent, UserEventKind, UserStream};
\ No newline at end of file
@@ -140,7 +140,9 @@ impl MessageRouter {
                 "userFundings" => Some(Subscription::UserFundings { user: identifier.to_string() }),
                 "userNonFundingLedgerUpdates" => Some(Subscription::UserNonFundingLedgerUpdates { user: identifier.to_string() }),
                 "webData2" => Some(Subscription::WebData2 { user: identifier.to_string() }),
-                "activeAssetCtx" => Some(Subscription::ActiveAssetCtx { coin: identifier.to_string() }),
+                "notification" => Some(Subscription::Notification { user: identifier.to_string() }),
+                // Spot pairs subscribed with activeAssetCtx answer on their own channel
+                "activeAssetCtx" | "activeSpotAssetCtx" => Some(Subscription::ActiveAssetCtx { coin: identifier.to_string() }),
                 "activeAssetData" => {
                     if parts.len() >= 3 {
                         Some(Subscription::ActiveAssetData {
@@ -0,0 +1,401 @@
+//! Lock-free single-producer/single-consumer ring buffer
+//!
+//! This module provides an SPSC ring buffer for handing WebSocket messages from
+//! the reader task to the router with minimal latency. Unlike [`CircularBuffer`],
+//! it never evicts: when full, new messages are rejected and counted so the
+//! producer never has to touch the consumer's slots.
+//!
+//! The consumer waits for messages using a configurable [`SpscWaitStrategy`]:
+//! - `BusySpin` keeps a core hot and gives sub-microsecond hand-off
+//! - `Park` sleeps the consumer thread until the producer unparks it
+//!
+//! [`CircularBuffer`]: super::CircularBuffer
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::ops::Deref;
+use std::sync::atomic::{fence, AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::{self, Thread};
+
+use serde::{Deserialize, Serialize};
+
+/// How the consumer waits when the ring buffer is empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpscWaitStrategy {
+    /// Spin on the ring buffer (lowest latency, burns a core)
+    BusySpin,
+    /// Park the consumer thread until the producer signals
+    #[default]
+    Park,
+}
+
+/// Buffer used for the reader to router hand-off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum BufferMode {
+    /// Evicting circular buffer consumed by a tokio task
+    #[default]
+    Circular,
+    /// Lock-free SPSC ring buffer consumed by a dedicated thread
+    Spsc {
+        /// Consumer wait strategy
+        #[serde(default)]
+        wait_strategy: SpscWaitStrategy,
+    },
+}
+
+/// Statistics for the SPSC ring buffer
+#[derive(Debug, Clone)]
+pub struct SpscStats {
+    /// Total messages pushed by the producer
+    pub messages_pushed: u64,
+    /// Total messages popped by the consumer
+    pub messages_popped: u64,
+    /// Messages rejected because the ring was full
+    pub messages_rejected: u64,
+    /// Current number of messages in the ring
+    pub current_size: usize,
+    /// Ring capacity (always a power of two)
+    pub capacity: usize,
+}
+
+/// Pads a value to its own cache line to avoid false sharing
+#[repr(align(64))]
+struct CachePadded<T>(T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Shared state between producer and consumer
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    mask: usize,
+    /// Next position to read (written by consumer only)
+    head: CachePadded<AtomicUsize>,
+    /// Next position to write (written by producer only)
+    tail: CachePadded<AtomicUsize>,
+    /// Set when the producer is dropped
+    closed: AtomicBool,
+    /// Set while the consumer is parked (or about to park)
+    parked: AtomicBool,
+    /// Consumer thread, registered on first park
+    consumer_thread: OnceLock<Thread>,
+    rejected: AtomicU64,
+    wait_strategy: SpscWaitStrategy,
+}
+
+// Safety: each slot is accessed by exactly one side at a time, handed over
+// through the acquire/release pair on `head` and `tail`.
+unsafe impl<T: Send> Send for Ring<T> {}
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    fn len(&self) -> usize {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+
+    fn stats(&self) -> SpscStats {
+        let tail = self.tail.load(Ordering::Acquire);
+        let head = self.head.load(Ordering::Acquire);
+        SpscStats {
+            messages_pushed: tail as u64,
+            messages_popped: head as u64,
+            messages_rejected: self.rejected.load(Ordering::Relaxed),
+            current_size: tail.wrapping_sub(head),
+            capacity: self.mask + 1,
+        }
+    }
+
+    fn wake_consumer(&self) {
+        if self.parked.swap(false, Ordering::SeqCst) {
+            if let Some(thread) = self.consumer_thread.get() {
+                thread.unpark();
+            }
+        }
+    }
+}
+
+impl<T> Drop for Ring<T> {
+    fn drop(&mut self) {
+        let tail = *self.tail.0.get_mut();
+        let mut head = *self.head.0.get_mut();
+        while head != tail {
+            // Safety: slots between head and tail are initialized
+            unsafe { (*self.slots[head & self.mask].get()).assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// Create a new SPSC ring buffer
+///
+/// The capacity is rounded up to the next power of two (minimum 2).
+pub fn spsc_channel<T: Send>(
+    capacity: usize,
+    wait_strategy: SpscWaitStrategy,
+) -> (SpscProducer<T>, SpscConsumer<T>) {
+    let capacity = capacity.max(2).next_power_of_two();
+    let slots = (0..capacity)
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect::<Vec<_>>()
+        .into_boxed_slice();
+
+    let ring = Arc::new(Ring {
+        slots,
+        mask: capacity - 1,
+        head: CachePadded(AtomicUsize::new(0)),
+        tail: CachePadded(AtomicUsize::new(0)),
+        closed: AtomicBool::new(false),
+        parked: AtomicBool::new(false),
+        consumer_thread: OnceLock::new(),
+        rejected: AtomicU64::new(0),
+        wait_strategy,
+    });
+
+    (
+        SpscProducer {
+            ring: Arc::clone(&ring),
+        },
+        SpscConsumer { ring },
+    )
+}
+
+/// Producer half of the SPSC ring buffer
+pub struct SpscProducer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T> SpscProducer<T> {
+    /// Push a message without blocking
+    ///
+    /// Returns the message back if the ring is full.
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        let ring = &*self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let head = ring.head.load(Ordering::Acquire);
+
+        if tail.wrapping_sub(head) > ring.mask {
+            ring.rejected.fetch_add(1, Ordering::Relaxed);
+            return Err(item);
+        }
+
+        // Safety: the slot at `tail` is free and only the producer writes to it
+        unsafe { (*ring.slots[tail & ring.mask].get()).write(item) };
+        ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+
+        if ring.wait_strategy == SpscWaitStrategy::Park {
+            fence(Ordering::SeqCst);
+            ring.wake_consumer();
+        }
+
+        Ok(())
+    }
+
+    /// Get the current number of messages in the ring
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Check if the ring is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get ring statistics
+    pub fn stats(&self) -> SpscStats {
+        self.ring.stats()
+    }
+}
+
+impl<T> Drop for SpscProducer<T> {
+    fn drop(&mut self) {
+        self.ring.closed.store(true, Ordering::SeqCst);
+        self.ring.wake_consumer();
+    }
+}
+
+/// Consumer half of the SPSC ring buffer
+pub struct SpscConsumer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+impl<T> SpscConsumer<T> {
+    /// Pop a message without blocking
+    pub fn try_pop(&mut self) -> Option<T> {
+        let ring = &*self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        let tail = ring.tail.load(Ordering::Acquire);
+
+        if head == tail {
+            return None;
+        }
+
+        // Safety: the slot at `head` was published by the producer's release store
+        let item = unsafe { (*ring.slots[head & ring.mask].get()).assume_init_read() };
+        ring.head.store(head.wrapping_add(1), Ordering::Release);
+
+        Some(item)
+    }
+
+    /// Pop a message, waiting with the configured strategy
+    ///
+    /// Returns `None` once the producer has been dropped and the ring is drained.
+    /// This blocks the calling thread and must not be used inside async tasks.
+    pub fn pop_blocking(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.try_pop() {
+                return Some(item);
+            }
+            if self.ring.closed.load(Ordering::Acquire) {
+                // Drain anything pushed just before the producer closed
+                return self.try_pop();
+            }
+
+            match self.ring.wait_strategy {
+                SpscWaitStrategy::BusySpin => std::hint::spin_loop(),
+                SpscWaitStrategy::Park => self.park(),
+            }
+        }
+    }
+
+    fn park(&self) {
+        let ring = &*self.ring;
+        ring.consumer_thread.get_or_init(thread::current);
+        ring.parked.store(true, Ordering::SeqCst);
+
+        // Re-check after announcing so a concurrent push cannot be missed
+        if ring.len() == 0 && !ring.closed.load(Ordering::SeqCst) {
+            thread::park();
+        }
+        ring.parked.store(false, Ordering::SeqCst);
+    }
+
+    /// Get the current number of messages in the ring
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// Check if the ring is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the producer has been dropped
+    pub fn is_closed(&self) -> bool {
+        self.ring.closed.load(Ordering::Acquire)
+    }
+
+    /// Get ring statistics
+    pub fn stats(&self) -> SpscStats {
+        self.ring.stats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_rounds_to_power_of_two() {
+        let (producer, _consumer) = spsc_channel::<u32>(1000, SpscWaitStrategy::Park);
+        assert_eq!(producer.stats().capacity, 1024);
+    }
+
+    #[test]
+    fn test_push_pop_and_full() {
+        let (mut producer, mut consumer) = spsc_channel(4, SpscWaitStrategy::BusySpin);
+
+        for i in 0..4 {
+            producer.try_push(i).unwrap();
+        }
+        assert_eq!(producer.try_push(4), Err(4));
+        assert_eq!(producer.stats().messages_rejected, 1);
+
+        for i in 0..4 {
+            assert_eq!(consumer.try_pop(), Some(i));
+        }
+        assert_eq!(consumer.try_pop(), None);
+        assert!(consumer.is_empty());
+    }
+
+    #[test]
+    fn test_pop_blocking_returns_none_after_close() {
+        let (mut producer, mut consumer) = spsc_channel(8, SpscWaitStrategy::Park);
+        producer.try_push("last".to_string()).unwrap();
+        drop(producer);
+
+        assert!(consumer.is_closed());
+        assert_eq!(consumer.pop_blocking().as_deref(), Some("last"));
+        assert_eq!(consumer.pop_blocking(), None);
+    }
+
+    #[test]
+    fn test_cross_thread_ordering() {
+        for strategy in [SpscWaitStrategy::BusySpin, SpscWaitStrategy::Park] {
+            let (mut producer, mut consumer) = spsc_channel(64, strategy);
+
+            let handle = thread::spawn(move || {
+                let mut received = Vec::new();
+                while let Some(item) = consumer.pop_blocking() {
+                    received.push(item);
+                }
+                received
+            });
+
+            for i in 0..10_000u32 {
+                let mut item = i;
+                while let Err(rejected) = producer.try_push(item) {
+                    item = rejected;
+                    std::hint::spin_loop();
+                }
+            }
+            drop(producer);
+
+            let received = handle.join().unwrap();
+            assert_eq!(received, (0..10_000).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_remaining_items_are_dropped() {
+        let marker = Arc::new(());
+        let (mut producer, consumer) = spsc_channel(4, SpscWaitStrategy::Park);
+        producer.try_push(Arc::clone(&marker)).unwrap();
+        producer.try_push(Arc::clone(&marker)).unwrap();
+        assert_eq!(Arc::strong_count(&marker), 3);
+
+        drop(producer);
+        drop(consumer);
+        assert_eq!(Arc::strong_count(&marker), 1);
+    }
+
+    #[test]
+    fn test_buffer_mode_serde() {
+        let mode: BufferMode =
+            serde_json::from_str(r#"{"mode":"spsc","wait_strategy":"busy_spin"}"#).unwrap();
+        assert_eq!(
+            mode,
+            BufferMode::Spsc {
+                wait_strategy: SpscWaitStrategy::BusySpin
+            }
+        );
+
+        let mode: BufferMode = serde_json::from_str(r#"{"mode":"spsc"}"#).unwrap();
+        assert_eq!(
+            mode,
+            BufferMode::Spsc {
+                wait_strategy: SpscWaitStrategy::Park
+            }
+        );
+        assert_eq!(BufferMode::default(), BufferMode::Circular);
+    }
+}
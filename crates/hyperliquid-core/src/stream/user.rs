@@ -0,0 +1,430 @@
+//! Per-user stream of fills and order updates with reconnect gap-fill
+//!
+//! [`UserStream`] merges a user's `userFills` and `orderUpdates` channels
+//! into one ordered sequence of [`UserEvent`]s. Messages sent while the
+//! websocket was down are lost, so after a reconnect the stream queries
+//! `userFillsByTime` and `historicalOrders` for the outage window and injects
+//! whatever it hasn't already delivered, flagged `recovered`, ahead of the
+//! live messages that arrive in the meantime.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient, ws: hyperliquid_core::stream::WebSocketClient, oms: hyperliquid_core::oms::OrderManager) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::stream::UserStream;
+//!
+//! let stream = UserStream::new(client, "0x1234...");
+//! stream.attach(&ws).await?;
+//! let mut events = stream.events();
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         oms.handle_user_event(&event);
+//!     }
+//! });
+//! while let Some(event) = ws.next_event().await {
+//!     stream.handle_ws_event(&event).await?;
+//! }
+//! # Ok(()) }
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use super::WebSocketResponse;
+#[cfg(feature = "ws")]
+use super::{WebSocketClient, WebSocketEvent};
+use crate::client::HttpClient;
+use crate::error::HyperliquidError;
+#[cfg(feature = "ws")]
+use crate::types::Subscription;
+
+/// Capacity of the event channel; slow receivers miss older events
+const EVENT_CAPACITY: usize = 4096;
+
+/// Number of delivered fills and order updates remembered for deduplication
+const SEEN_CAPACITY: usize = 8192;
+
+/// Most fills `userFillsByTime` returns per request
+const FILLS_PAGE_SIZE: usize = 2000;
+
+/// Default lookback before the disconnect, covering messages lost in flight
+const DEFAULT_OVERLAP: Duration = Duration::from_secs(5);
+
+/// What a [`UserEvent`] carries
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserEventKind {
+    /// One element of a `userFills` message's `fills`
+    Fill(Value),
+    /// One element of an `orderUpdates` message
+    OrderUpdate(Value),
+}
+
+/// Fill or order update of the streamed user
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserEvent {
+    pub kind: UserEventKind,
+    /// Fill time or status timestamp in ms
+    pub time: u64,
+    /// Whether the event was missed by the websocket and fetched over REST
+    pub recovered: bool,
+}
+
+impl UserEvent {
+    /// The event as a payload of its websocket channel
+    ///
+    /// Lets the existing channel handlers, such as
+    /// [`OrderManager::handle_user_fills`](crate::oms::OrderManager::handle_user_fills),
+    /// consume recovered events unchanged.
+    pub fn to_channel_payload(&self, user: &str) -> (&'static str, Value) {
+        match &self.kind {
+            UserEventKind::Fill(fill) => ("userFills", json!({"user": user, "fills": [fill]})),
+            UserEventKind::OrderUpdate(update) => ("orderUpdates", json!([update])),
+        }
+    }
+}
+
+/// Set that forgets its oldest entries past a capacity
+#[derive(Debug)]
+struct Seen<T> {
+    set: HashSet<T>,
+    order: VecDeque<T>,
+}
+
+impl<T: Eq + Hash + Clone> Seen<T> {
+    fn new() -> Self {
+        Self {
+            set: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// Whether `value` is new, remembering it
+    fn insert(&mut self, value: T) -> bool {
+        if !self.set.insert(value.clone()) {
+            return false;
+        }
+        if self.order.len() >= SEEN_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.set.remove(&oldest);
+            }
+        }
+        self.order.push_back(value);
+        true
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    seen_fills: Seen<u64>,
+    seen_updates: Seen<(u64, String, u64)>,
+    /// Start of the window to recover, set on disconnect
+    gap_start: Option<u64>,
+    /// Live events held back while a recovery is in progress
+    held: Option<Vec<UserEvent>>,
+}
+
+/// Ordered fills and order updates of one user, gap-filled after reconnects
+#[derive(Clone)]
+pub struct UserStream {
+    client: HttpClient,
+    user: String,
+    overlap: Duration,
+    state: Arc<Mutex<State>>,
+    events: broadcast::Sender<UserEvent>,
+}
+
+impl std::fmt::Debug for UserStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserStream")
+            .field("user", &self.user)
+            .field("overlap", &self.overlap)
+            .finish_non_exhaustive()
+    }
+}
+
+impl UserStream {
+    pub fn new(client: HttpClient, user: impl Into<String>) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        Self {
+            client,
+            user: user.into(),
+            overlap: DEFAULT_OVERLAP,
+            state: Arc::new(Mutex::new(State {
+                seen_fills: Seen::new(),
+                seen_updates: Seen::new(),
+                gap_start: None,
+                held: None,
+            })),
+            events,
+        }
+    }
+
+    /// How far before the disconnect to look for missed events (default 5s)
+    pub fn with_overlap(mut self, overlap: Duration) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    pub fn events(&self) -> broadcast::Receiver<UserEvent> {
+        self.events.subscribe()
+    }
+
+    /// Emit `event` unless already delivered; held back during a recovery
+    fn deliver(&self, event: UserEvent) {
+        let mut state = self.lock();
+        if !is_new(&mut state, &event) {
+            return;
+        }
+        if let Some(held) = state.held.as_mut() {
+            held.push(event);
+            return;
+        }
+        drop(state);
+        let _ = self.events.send(event);
+    }
+
+    /// Apply a `data` payload from the `userFills` channel
+    ///
+    /// The snapshot sent on (re)subscription is skipped; fills it holds that
+    /// were missed are recovered over REST instead.
+    pub fn handle_user_fills(&self, data: &Value) {
+        if data.get("isSnapshot").and_then(Value::as_bool) == Some(true) {
+            return;
+        }
+        let Some(fills) = data.get("fills").and_then(Value::as_array) else {
+            warn!("Ignoring malformed userFills message");
+            return;
+        };
+        for fill in fills {
+            self.deliver(fill_event(fill.clone(), false));
+        }
+    }
+
+    /// Apply a `data` payload from the `orderUpdates` channel
+    pub fn handle_order_updates(&self, data: &Value) {
+        let Some(updates) = data.as_array() else {
+            warn!("Ignoring malformed orderUpdates message");
+            return;
+        };
+        for update in updates {
+            self.deliver(order_update_event(update.clone(), false));
+        }
+    }
+
+    /// Track connectivity and gap-fill after a reconnect
+    ///
+    /// Feed every event from [`WebSocketClient::next_event`]. On
+    /// [`WebSocketEvent::Connected`] following a disconnect this returns once
+    /// the missed events have been emitted. If the REST queries fail, the
+    /// window is kept and retried on the next reconnect or
+    /// [`recover`](Self::recover) call.
+    #[cfg(feature = "ws")]
+    pub async fn handle_ws_event(&self, event: &WebSocketEvent) -> Result<usize, HyperliquidError> {
+        match event {
+            WebSocketEvent::Disconnected | WebSocketEvent::Reconnecting(_) => {
+                let start = now_millis().saturating_sub(self.overlap.as_millis() as u64);
+                self.lock().gap_start.get_or_insert(start);
+                Ok(0)
+            }
+            WebSocketEvent::Connected => self.recover().await,
+            _ => Ok(0),
+        }
+    }
+
+    /// Fetch and emit events missed since the last disconnect
+    ///
+    /// Does nothing if there was no disconnect. Returns the number of
+    /// recovered events.
+    pub async fn recover(&self) -> Result<usize, HyperliquidError> {
+        let start = {
+            let mut state = self.lock();
+            let Some(start) = state.gap_start else {
+                return Ok(0);
+            };
+            if state.held.is_some() {
+                // Another recovery is in progress and will cover the window
+                return Ok(0);
+            }
+            state.held = Some(Vec::new());
+            start
+        };
+        let end = now_millis();
+
+        let fetched = self.fetch(start, end).await;
+        let mut state = self.lock();
+        let held = state.held.take().unwrap_or_default();
+        let mut out = Vec::new();
+        let recovered = match fetched {
+            Ok(events) => {
+                // Keep a window opened by a disconnect during the queries
+                if state.gap_start == Some(start) {
+                    state.gap_start = None;
+                }
+                let mut count = 0;
+                for event in events {
+                    if is_new(&mut state, &event) {
+                        out.push(event);
+                        count += 1;
+                    }
+                }
+                Ok(count)
+            }
+            Err(e) => Err(e),
+        };
+        out.extend(held);
+        // Sent under the lock so no live event can overtake them
+        for event in out {
+            let _ = self.events.send(event);
+        }
+        drop(state);
+
+        if let Ok(count) = &recovered {
+            info!(
+                "Recovered {} missed events for {} between {} and {}",
+                count, self.user, start, end
+            );
+        }
+        recovered
+    }
+
+    /// Fills and order updates between `start` and `end` (ms), in time order
+    async fn fetch(&self, start: u64, end: u64) -> Result<Vec<UserEvent>, HyperliquidError> {
+        let mut events = Vec::new();
+        let mut page_start = start;
+        loop {
+            let fills: Value = self
+                .client
+                .post(
+                    "/info",
+                    &json!({
+                        "type": "userFillsByTime",
+                        "user": self.user,
+                        "startTime": page_start,
+                        "endTime": end,
+                    }),
+                )
+                .await?;
+            let fills = fills.as_array().cloned().unwrap_or_default();
+            let page_len = fills.len();
+            let mut last_time = page_start;
+            for fill in fills {
+                let event = fill_event(fill, true);
+                last_time = last_time.max(event.time);
+                events.push(event);
+            }
+            // Fills sharing the page's last timestamp are refetched and deduplicated
+            if page_len < FILLS_PAGE_SIZE || last_time == page_start {
+                break;
+            }
+            page_start = last_time;
+        }
+
+        let orders: Value = self
+            .client
+            .post(
+                "/info",
+                &json!({"type": "historicalOrders", "user": self.user}),
+            )
+            .await?;
+        events.extend(
+            orders
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|update| order_update_event(update.clone(), true))
+                .filter(|event| event.time >= start && event.time <= end),
+        );
+
+        // Stable, so a fill stays ahead of the status change it caused
+        events.sort_by_key(|event| event.time);
+        Ok(events)
+    }
+
+    /// Subscribe `ws` to the user's fills and order updates and feed them in
+    ///
+    /// Registers the handlers for the `userFills` and `orderUpdates`
+    /// subscriptions, replacing any existing handlers for them.
+    #[cfg(feature = "ws")]
+    pub async fn attach(&self, ws: &WebSocketClient) -> Result<(), HyperliquidError> {
+        for channel in ["userFills", "orderUpdates"] {
+            let subscription: Subscription =
+                serde_json::from_value(json!({"type": channel, "user": self.user}))?;
+            let stream = self.clone();
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                // Unrouted messages are broadcast to every handler
+                if !response.channel.starts_with(channel) {
+                    return;
+                }
+                if channel == "userFills" {
+                    stream.handle_user_fills(&response.data);
+                } else {
+                    stream.handle_order_updates(&response.data);
+                }
+            })
+            .await;
+            ws.subscribe(subscription)
+                .await
+                .map_err(|e| HyperliquidError::WebSocket(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `event` hasn't been delivered yet, remembering it
+///
+/// Fills without a trade id and updates without an oid can't be deduplicated
+/// and always count as new.
+fn is_new(state: &mut State, event: &UserEvent) -> bool {
+    match &event.kind {
+        UserEventKind::Fill(fill) => match fill.get("tid").and_then(Value::as_u64) {
+            Some(tid) => state.seen_fills.insert(tid),
+            None => true,
+        },
+        UserEventKind::OrderUpdate(update) => {
+            let Some(oid) = update.pointer("/order/oid").and_then(Value::as_u64) else {
+                return true;
+            };
+            let status = update
+                .get("status")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            state.seen_updates.insert((oid, status, event.time))
+        }
+    }
+}
+
+fn fill_event(fill: Value, recovered: bool) -> UserEvent {
+    UserEvent {
+        time: fill.get("time").and_then(Value::as_u64).unwrap_or_default(),
+        kind: UserEventKind::Fill(fill),
+        recovered,
+    }
+}
+
+fn order_update_event(update: Value, recovered: bool) -> UserEvent {
+    UserEvent {
+        time: update
+            .get("statusTimestamp")
+            .and_then(Value::as_u64)
+            .unwrap_or_default(),
+        kind: UserEventKind::OrderUpdate(update),
+        recovered,
+    }
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
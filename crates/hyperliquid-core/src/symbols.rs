@@ -0,0 +1,261 @@
+//! Canonical instrument symbols
+//!
+//! Hyperliquid names perps by their base (`"BTC"`) and spot pairs by a wire
+//! name (`"PURR/USDC"` or `"@107"`). A [`SymbolMap`] maps those coins to an
+//! exchange-neutral [`Instrument`] and back, so a multi-exchange framework can
+//! address this SDK with the symbols it uses everywhere else. Symbols follow
+//! the unified `BASE/QUOTE` (spot) and `BASE/QUOTE:SETTLE` (linear perp)
+//! convention.
+//!
+//! Hyperliquid's own names can differ from other venues' (the spot token
+//! `UBTC` is bitcoin, the perp `kPEPE` is a thousand PEPE); asset aliases
+//! rename them in the canonical form. Symbol aliases add further names that
+//! resolve to a coin, such as another exchange's ticker.
+//!
+//! ```no_run
+//! # async fn example(info: hyperliquid_core::InfoClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use hyperliquid_core::symbols::SymbolMap;
+//!
+//! let symbols = SymbolMap::load(&info)
+//!     .await?
+//!     .with_asset_alias("UBTC", "BTC")
+//!     .with_alias("XBTUSD", "BTC");
+//! assert_eq!(symbols.coin("BTC/USDC:USDC"), Some("BTC"));
+//! assert_eq!(symbols.coin("XBTUSD"), Some("BTC"));
+//! let spot = symbols.coin("BTC/USDC").unwrap();
+//! println!("{} trades as {}", symbols.symbol(spot).unwrap(), spot);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::HyperliquidError;
+use crate::info::InfoClient;
+use crate::types::{Meta, SpotUniverse};
+
+/// Quote and settlement asset of Hyperliquid perps
+pub const PERP_QUOTE: &str = "USDC";
+
+/// Kind of contract an instrument is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContractType {
+    Spot,
+    /// Linear perpetual settled in the quote
+    Perpetual,
+}
+
+/// Exchange-neutral identity of a market
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Instrument {
+    pub base: String,
+    pub quote: String,
+    pub contract: ContractType,
+}
+
+impl Instrument {
+    pub fn spot(base: impl Into<String>, quote: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            quote: quote.into(),
+            contract: ContractType::Spot,
+        }
+    }
+
+    pub fn perpetual(base: impl Into<String>, quote: impl Into<String>) -> Self {
+        Self {
+            base: base.into(),
+            quote: quote.into(),
+            contract: ContractType::Perpetual,
+        }
+    }
+}
+
+impl std::fmt::Display for Instrument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.contract {
+            ContractType::Spot => write!(f, "{}/{}", self.base, self.quote),
+            ContractType::Perpetual => write!(f, "{}/{}:{}", self.base, self.quote, self.quote),
+        }
+    }
+}
+
+impl FromStr for Instrument {
+    type Err = HyperliquidError;
+
+    /// Parse `BASE/QUOTE` or `BASE/QUOTE:SETTLE`
+    fn from_str(symbol: &str) -> Result<Self, Self::Err> {
+        let invalid = || HyperliquidError::Validation(format!("invalid symbol: {}", symbol));
+        let (pair, settle) = match symbol.split_once(':') {
+            Some((pair, settle)) => (pair, Some(settle)),
+            None => (symbol, None),
+        };
+        let (base, quote) = pair.split_once('/').ok_or_else(invalid)?;
+        if base.is_empty() || quote.is_empty() {
+            return Err(invalid());
+        }
+        match settle {
+            None => Ok(Self::spot(base, quote)),
+            Some(settle) if settle == quote => Ok(Self::perpetual(base, quote)),
+            // Only linear contracts are listed
+            Some(_) => Err(invalid()),
+        }
+    }
+}
+
+/// Two-way mapping between Hyperliquid coins and canonical instruments
+#[derive(Debug, Clone, Default)]
+pub struct SymbolMap {
+    /// Instruments by coin, in Hyperliquid's asset names
+    markets: BTreeMap<String, Instrument>,
+    /// Canonical name by Hyperliquid asset name
+    asset_aliases: HashMap<String, String>,
+    /// Coin by extra name
+    aliases: HashMap<String, String>,
+    by_coin: HashMap<String, Instrument>,
+    by_instrument: HashMap<Instrument, String>,
+}
+
+impl SymbolMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map the perps and spot pairs currently listed on the first perp dex
+    pub async fn load(info: &InfoClient) -> Result<Self, HyperliquidError> {
+        let (meta, spot) = tokio::try_join!(info.meta(""), info.spot_universe())?;
+        Ok(Self::new().with_perps(&meta).with_spot(&spot))
+    }
+
+    /// Add the perps of `meta`, quoted and settled in [`PERP_QUOTE`]
+    pub fn with_perps(mut self, meta: &Meta) -> Self {
+        for asset in &meta.universe {
+            self.markets.insert(
+                asset.name.clone(),
+                Instrument::perpetual(asset.name.clone(), PERP_QUOTE),
+            );
+        }
+        self.rebuild();
+        self
+    }
+
+    /// Add the spot pairs of `universe`, under their wire names
+    pub fn with_spot(mut self, universe: &SpotUniverse) -> Self {
+        for pair in universe.pairs() {
+            let (Some(base), Some(quote)) =
+                (universe.token(pair.base()), universe.token(pair.quote()))
+            else {
+                continue;
+            };
+            self.markets.insert(
+                pair.name.clone(),
+                Instrument::spot(base.name.clone(), quote.name.clone()),
+            );
+        }
+        self.rebuild();
+        self
+    }
+
+    /// Add or replace one market, named in Hyperliquid's assets
+    pub fn with_market(mut self, coin: impl Into<String>, instrument: Instrument) -> Self {
+        self.markets.insert(coin.into(), instrument);
+        self.rebuild();
+        self
+    }
+
+    /// Call Hyperliquid's asset `name` `canonical` in instruments, e.g.
+    /// `UBTC` as `BTC`
+    pub fn with_asset_alias(
+        mut self,
+        name: impl Into<String>,
+        canonical: impl Into<String>,
+    ) -> Self {
+        self.asset_aliases.insert(name.into(), canonical.into());
+        self.rebuild();
+        self
+    }
+
+    /// Resolve `alias` to `coin` as well
+    pub fn with_alias(mut self, alias: impl Into<String>, coin: impl Into<String>) -> Self {
+        self.aliases.insert(alias.into(), coin.into());
+        self
+    }
+
+    fn canonical(&self, asset: &str) -> String {
+        self.asset_aliases
+            .get(asset)
+            .cloned()
+            .unwrap_or_else(|| asset.to_string())
+    }
+
+    fn rebuild(&mut self) {
+        self.by_coin.clear();
+        self.by_instrument.clear();
+        for (coin, raw) in &self.markets {
+            let instrument = Instrument {
+                base: self.canonical(&raw.base),
+                quote: self.canonical(&raw.quote),
+                contract: raw.contract,
+            };
+            // Canonical pairs sort after `@` wire names; let them win
+            let named = coin.contains('/');
+            match self.by_instrument.get(&instrument) {
+                Some(existing) if existing.contains('/') || !named => {}
+                _ => {
+                    self.by_instrument.insert(instrument.clone(), coin.clone());
+                }
+            }
+            self.by_coin.insert(coin.clone(), instrument);
+        }
+    }
+
+    /// Canonical instrument of `coin`
+    pub fn instrument(&self, coin: &str) -> Option<&Instrument> {
+        self.by_coin.get(coin)
+    }
+
+    /// Canonical symbol of `coin`, e.g. `"BTC/USDC:USDC"` for `"BTC"`
+    pub fn symbol(&self, coin: &str) -> Option<String> {
+        self.instrument(coin).map(Instrument::to_string)
+    }
+
+    /// Coin trading `instrument`
+    pub fn coin_for(&self, instrument: &Instrument) -> Option<&str> {
+        self.by_instrument.get(instrument).map(String::as_str)
+    }
+
+    /// Coin named by `name`: an alias, a canonical symbol or a coin itself
+    pub fn coin<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        if let Some(coin) = self.aliases.get(name) {
+            return self
+                .by_coin
+                .get_key_value(coin.as_str())
+                .map(|(coin, _)| coin.as_str());
+        }
+        if let Ok(instrument) = name.parse::<Instrument>() {
+            if let Some(coin) = self.coin_for(&instrument) {
+                return Some(coin);
+            }
+        }
+        self.by_coin.contains_key(name).then_some(name)
+    }
+
+    /// Every coin and its instrument, by coin
+    pub fn instruments(&self) -> impl Iterator<Item = (&str, &Instrument)> {
+        self.markets
+            .keys()
+            .filter_map(|coin| Some((coin.as_str(), self.by_coin.get(coin)?)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.markets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.markets.is_empty()
+    }
+}
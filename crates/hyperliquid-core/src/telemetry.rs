@@ -0,0 +1,59 @@
+//! Metric macros used across the crate
+//!
+//! With the `metrics` feature these are the [`metrics`](::metrics) facade's
+//! own macros. Without it they expand to a no-op handle, so instrumented code
+//! builds unchanged and the facade is not linked.
+
+#[cfg(feature = "metrics")]
+pub(crate) use ::metrics::{counter, gauge, histogram};
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) use noop::{counter, gauge, histogram};
+
+#[cfg(not(feature = "metrics"))]
+pub(crate) mod noop {
+    /// Handle that discards every update
+    pub(crate) struct Noop;
+
+    impl Noop {
+        pub(crate) fn increment<T>(&self, _value: T) {}
+
+        pub(crate) fn absolute<T>(&self, _value: T) {}
+
+        pub(crate) fn set<T>(&self, _value: T) {}
+
+        pub(crate) fn record<T>(&self, _value: T) {}
+    }
+
+    // Labels are captured in a closure that never runs, so values computed
+    // only for a label don't warn as unused and cost nothing
+    macro_rules! discard {
+        ($name:expr $(, $key:expr => $value:expr)* $(,)?) => {{
+            let _ = || ($name, $(($key, $value)),*);
+            $crate::telemetry::noop::Noop
+        }};
+    }
+
+    macro_rules! counter {
+        ($($arg:tt)*) => {
+            $crate::telemetry::noop::discard!($($arg)*)
+        };
+    }
+
+    macro_rules! gauge {
+        ($($arg:tt)*) => {
+            $crate::telemetry::noop::discard!($($arg)*)
+        };
+    }
+
+    macro_rules! histogram {
+        ($($arg:tt)*) => {
+            $crate::telemetry::noop::discard!($($arg)*)
+        };
+    }
+
+    pub(crate) use counter;
+    pub(crate) use discard;
+    pub(crate) use gauge;
+    pub(crate) use histogram;
+}
@@ -1,6 +1,9 @@
 //! Address validation and type for Ethereum-style addresses
 
+#[cfg(feature = "exchange-signing")]
+use k256::ecdsa::{SigningKey, VerifyingKey};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha3::{Digest, Keccak256};
 use std::fmt;
 use std::str::FromStr;
 
@@ -11,6 +14,7 @@ use std::str::FromStr;
 /// - Contains exactly 20 bytes (40 hex characters + 0x prefix)
 /// - Uses lowercase hex representation
 /// - Validates hex characters only
+/// - Mixed-case input must carry a valid EIP-55 checksum
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Address {
     /// Internal storage as 20 bytes (Ethereum address size)
@@ -18,6 +22,14 @@ pub struct Address {
 }
 
 impl Address {
+    /// The zero address
+    pub const ZERO: Address = Address { bytes: [0u8; 20] };
+
+    /// Create an Address from its 20 bytes
+    pub const fn from_bytes(bytes: [u8; 20]) -> Self {
+        Address { bytes }
+    }
+
     /// Create a new Address from a hex string
     ///
     /// # Arguments
@@ -59,7 +71,89 @@ impl Address {
                 .map_err(|e| format!("Failed to parse hex: {}", e))?;
         }
 
-        Ok(Address { bytes })
+        // All-lowercase and all-uppercase input carries no checksum
+        let address = Address { bytes };
+        let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase())
+            && hex.chars().any(|c| c.is_ascii_uppercase());
+        if mixed_case && address.to_checksum()[2..] != *hex {
+            return Err(format!(
+                "Invalid EIP-55 checksum: expected {}",
+                address.to_checksum()
+            ));
+        }
+
+        Ok(address)
+    }
+
+    /// Derive the address of a secp256k1 public key
+    ///
+    /// The address is the last 20 bytes of the keccak256 hash of the
+    /// uncompressed key without its 0x04 prefix.
+    #[cfg(feature = "exchange-signing")]
+    pub fn from_public_key(key: &VerifyingKey) -> Self {
+        let point = key.to_encoded_point(false);
+        let hash = Keccak256::digest(&point.as_bytes()[1..]);
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(&hash[12..]);
+        Address { bytes }
+    }
+
+    /// Derive the address of a SEC1-encoded public key, compressed or not
+    #[cfg(feature = "exchange-signing")]
+    pub fn from_public_key_bytes(key: &[u8]) -> Result<Self, String> {
+        VerifyingKey::from_sec1_bytes(key)
+            .map(|key| Self::from_public_key(&key))
+            .map_err(|e| format!("Invalid public key: {}", e))
+    }
+
+    /// Derive the address controlled by a private key
+    #[cfg(feature = "exchange-signing")]
+    pub fn from_private_key(key: &SigningKey) -> Self {
+        Self::from_public_key(key.verifying_key())
+    }
+
+    /// Whether this is the zero address
+    pub fn is_zero(&self) -> bool {
+        self.bytes == [0u8; 20]
+    }
+
+    /// Compare in constant time, for authentication paths
+    ///
+    /// `==` may return as soon as a byte differs, leaking how much of an
+    /// address matched through timing.
+    pub fn ct_eq(&self, other: &Address) -> bool {
+        let diff = self
+            .bytes
+            .iter()
+            .zip(other.bytes.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        std::hint::black_box(diff) == 0
+    }
+
+    /// Get the address in EIP-55 mixed-case checksum form
+    ///
+    /// # Examples
+    /// ```
+    /// use hyperliquid_core::types::Address;
+    ///
+    /// let addr: Address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+    /// assert_eq!(addr.to_checksum(), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    /// ```
+    pub fn to_checksum(&self) -> String {
+        let lower = hex::encode(self.bytes);
+        let hash = Keccak256::digest(lower.as_bytes());
+        let mut out = String::with_capacity(42);
+        out.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            // Uppercase letters whose hash nibble is 8 or more
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                out.push(c.to_ascii_uppercase());
+            } else {
+                out.push(c);
+            }
+        }
+        out
     }
 
     /// Get the address as bytes
@@ -97,6 +191,14 @@ impl fmt::Display for Address {
     }
 }
 
+impl From<[u8; 20]> for Address {
+    fn from(bytes: [u8; 20]) -> Self {
+        Address { bytes }
+    }
+}
+
+/// Serialized as lowercase hex, the form the exchange API uses;
+/// deserialization accepts lowercase, uppercase and checksummed input
 impl Serialize for Address {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -210,6 +312,63 @@ mod tests {
         assert!(validate_address("0x123456789012345678901234567890123456789g").is_err());
     }
 
+    #[test]
+    fn test_checksum() {
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let lower = checksummed.to_lowercase();
+            let addr = Address::from_str(&lower).unwrap();
+            assert_eq!(addr.to_checksum(), checksummed);
+            assert_eq!(Address::from_str(checksummed).unwrap(), addr);
+            assert_eq!(Address::from_str(&checksummed.to_uppercase()[2..]).unwrap(), addr);
+
+            // Checksummed input deserializes, and serializes back as lowercase
+            let json = format!("\"{}\"", checksummed);
+            let parsed: Address = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&parsed).unwrap(), format!("\"{}\"", lower));
+        }
+
+        let wrong = Address::from_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD");
+        assert!(wrong.unwrap_err().contains("checksum"));
+        let json = r#""0x5AaEB6053F3E94C9b9A09f33669435E7Ef1BeAed""#;
+        assert!(serde_json::from_str::<Address>(json).is_err());
+    }
+
+    #[cfg(feature = "exchange-signing")]
+    #[test]
+    fn test_derivation() {
+        let mut key = [0u8; 32];
+        key[31] = 1;
+        let signing_key = SigningKey::from_bytes(&key.into()).unwrap();
+        let expected = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+        let from_private = Address::from_private_key(&signing_key);
+        assert_eq!(from_private.to_checksum(), expected);
+        let compressed = signing_key.verifying_key().to_encoded_point(true);
+        let uncompressed = signing_key.verifying_key().to_encoded_point(false);
+        assert_eq!(Address::from_public_key_bytes(compressed.as_bytes()).unwrap(), from_private);
+        assert_eq!(Address::from_public_key_bytes(uncompressed.as_bytes()).unwrap(), from_private);
+        assert!(Address::from_public_key_bytes(&[0x02; 33]).is_err());
+    }
+
+    #[test]
+    fn test_zero_and_ct_eq() {
+        assert!(Address::ZERO.is_zero());
+        assert!(Address::from_str("0x0000000000000000000000000000000000000000").unwrap().is_zero());
+        let addr = Address::from_bytes([0x11; 20]);
+        assert!(!addr.is_zero());
+
+        assert!(addr.ct_eq(&Address::from([0x11; 20])));
+        let mut bytes = [0x11; 20];
+        bytes[19] = 0x10;
+        assert!(!addr.ct_eq(&Address::from(bytes)));
+        assert!(!addr.ct_eq(&Address::ZERO));
+    }
+
     #[test]
     fn test_hash_and_equality() {
         let addr1 = Address::from_str("0x1234567890123456789012345678901234567890").unwrap();
@@ -0,0 +1,158 @@
+//! Messages of the `activeAssetCtx` WebSocket subscription
+//!
+//! Subscribing to `activeAssetCtx` for a perp yields `activeAssetCtx`
+//! messages; for a spot pair (`"PURR/USDC"`, `"@107"`) the exchange answers
+//! on `activeSpotAssetCtx` with a different context. [`AssetCtxUpdate`]
+//! parses either from a channel and its `data`.
+//!
+//! Prices and volumes are [`Decimal`]s, parsed from the wire strings without
+//! going through `f64`.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Context of a perp
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpAssetCtx {
+    pub day_ntl_vlm: Decimal,
+    pub prev_day_px: Decimal,
+    pub mark_px: Decimal,
+    /// `None` while the book is one-sided
+    #[serde(default)]
+    pub mid_px: Option<Decimal>,
+    /// Rate for the current funding interval
+    pub funding: Decimal,
+    pub open_interest: Decimal,
+    pub oracle_px: Decimal,
+    #[serde(default)]
+    pub premium: Option<Decimal>,
+    /// Bid and ask impact prices
+    #[serde(default)]
+    pub impact_pxs: Option<Vec<Decimal>>,
+    #[serde(default)]
+    pub day_base_vlm: Option<Decimal>,
+}
+
+/// Context of a spot pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotAssetCtx {
+    pub day_ntl_vlm: Decimal,
+    pub prev_day_px: Decimal,
+    pub mark_px: Decimal,
+    #[serde(default)]
+    pub mid_px: Option<Decimal>,
+    pub circulating_supply: Decimal,
+    #[serde(default)]
+    pub total_supply: Option<Decimal>,
+    #[serde(default)]
+    pub day_base_vlm: Option<Decimal>,
+}
+
+/// `data` of an `activeAssetCtx` message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveAssetCtxMsg {
+    pub coin: String,
+    pub ctx: PerpAssetCtx,
+}
+
+/// `data` of an `activeSpotAssetCtx` message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveSpotAssetCtxMsg {
+    pub coin: String,
+    pub ctx: SpotAssetCtx,
+}
+
+/// Perp or spot context update
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssetCtxUpdate {
+    Perp(ActiveAssetCtxMsg),
+    Spot(ActiveSpotAssetCtxMsg),
+}
+
+impl AssetCtxUpdate {
+    /// Parse the `data` of a message on `channel`
+    ///
+    /// Returns `Ok(None)` for channels other than `activeAssetCtx` and
+    /// `activeSpotAssetCtx`.
+    pub fn parse(channel: &str, data: &Value) -> Result<Option<Self>, serde_json::Error> {
+        match channel {
+            "activeAssetCtx" => Ok(Some(AssetCtxUpdate::Perp(ActiveAssetCtxMsg::deserialize(
+                data,
+            )?))),
+            "activeSpotAssetCtx" => Ok(Some(AssetCtxUpdate::Spot(
+                ActiveSpotAssetCtxMsg::deserialize(data)?,
+            ))),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn coin(&self) -> &str {
+        match self {
+            AssetCtxUpdate::Perp(msg) => &msg.coin,
+            AssetCtxUpdate::Spot(msg) => &msg.coin,
+        }
+    }
+
+    pub fn is_spot(&self) -> bool {
+        matches!(self, AssetCtxUpdate::Spot(_))
+    }
+
+    pub fn mark_px(&self) -> Decimal {
+        match self {
+            AssetCtxUpdate::Perp(msg) => msg.ctx.mark_px,
+            AssetCtxUpdate::Spot(msg) => msg.ctx.mark_px,
+        }
+    }
+
+    pub fn mid_px(&self) -> Option<Decimal> {
+        match self {
+            AssetCtxUpdate::Perp(msg) => msg.ctx.mid_px,
+            AssetCtxUpdate::Spot(msg) => msg.ctx.mid_px,
+        }
+    }
+
+    pub fn prev_day_px(&self) -> Decimal {
+        match self {
+            AssetCtxUpdate::Perp(msg) => msg.ctx.prev_day_px,
+            AssetCtxUpdate::Spot(msg) => msg.ctx.prev_day_px,
+        }
+    }
+
+    pub fn day_ntl_vlm(&self) -> Decimal {
+        match self {
+            AssetCtxUpdate::Perp(msg) => msg.ctx.day_ntl_vlm,
+            AssetCtxUpdate::Spot(msg) => msg.ctx.day_ntl_vlm,
+        }
+    }
+
+    /// Oracle price; perps only
+    pub fn oracle_px(&self) -> Option<Decimal> {
+        match self {
+            AssetCtxUpdate::Perp(msg) => Some(msg.ctx.oracle_px),
+            AssetCtxUpdate::Spot(_) => None,
+        }
+    }
+
+    /// Current funding rate; perps only
+    pub fn funding(&self) -> Option<Decimal> {
+        match self {
+            AssetCtxUpdate::Perp(msg) => Some(msg.ctx.funding),
+            AssetCtxUpdate::Spot(_) => None,
+        }
+    }
+}
+
+impl From<ActiveAssetCtxMsg> for AssetCtxUpdate {
+    fn from(msg: ActiveAssetCtxMsg) -> Self {
+        AssetCtxUpdate::Perp(msg)
+    }
+}
+
+impl From<ActiveSpotAssetCtxMsg> for AssetCtxUpdate {
+    fn from(msg: ActiveSpotAssetCtxMsg) -> Self {
+        AssetCtxUpdate::Spot(msg)
+    }
+}
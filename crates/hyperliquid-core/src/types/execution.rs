@@ -0,0 +1,155 @@
+//! Fills aggregated by order
+//!
+//! [`OrderExecution::aggregate`] folds the individual fills of a
+//! `userFills`/`userFillsByTime` response into one summary per order: total
+//! size, size-weighted average price, fees and realized PnL, and the time
+//! span of the fills. Fills carrying a cloid are grouped by it, so an order
+//! that was modified (and got a new oid) stays one execution; other fills
+//! are grouped by oid.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Fill as reported by `userFills` and `userFillsByTime`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFill {
+    pub coin: String,
+    pub px: String,
+    pub sz: String,
+    /// "B" for a buy, "A" for a sell
+    pub side: String,
+    /// Time in milliseconds
+    pub time: u64,
+    pub oid: u64,
+    /// Trade id
+    pub tid: u64,
+    #[serde(default)]
+    pub fee: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_token: Option<String>,
+    #[serde(default)]
+    pub closed_pnl: Option<String>,
+    /// Whether the fill took liquidity
+    #[serde(default)]
+    pub crossed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloid: Option<String>,
+    /// Part of `fee` paid to the order's builder
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder_fee: Option<String>,
+}
+
+impl UserFill {
+    pub fn is_buy(&self) -> bool {
+        self.side == "B"
+    }
+}
+
+/// Fills of one order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderExecution {
+    pub coin: String,
+    /// Oids of the fills, in fill order; more than one if the order was modified
+    pub oids: Vec<u64>,
+    pub cloid: Option<String>,
+    pub is_buy: bool,
+    /// Total filled size
+    pub total_sz: f64,
+    /// Size-weighted average fill price
+    pub avg_px: f64,
+    /// Sum of the fees, in each fill's fee token
+    pub total_fee: f64,
+    /// Sum of the realized PnL of the fills
+    pub closed_pnl: f64,
+    /// Time of the first fill in milliseconds
+    pub first_fill_time: u64,
+    /// Time of the last fill in milliseconds
+    pub last_fill_time: u64,
+    pub fill_count: usize,
+}
+
+#[derive(Hash, PartialEq, Eq)]
+enum OrderKey {
+    Cloid(String),
+    Oid(u64),
+}
+
+impl OrderExecution {
+    /// Group fills by order, ordered by first fill time
+    ///
+    /// Fills with an unparsable price or size are skipped.
+    pub fn aggregate<'a>(fills: impl IntoIterator<Item = &'a UserFill>) -> Vec<OrderExecution> {
+        let mut fills: Vec<&UserFill> = fills.into_iter().collect();
+        fills.sort_by_key(|fill| (fill.time, fill.tid));
+
+        let mut index: HashMap<(String, OrderKey), usize> = HashMap::new();
+        let mut executions: Vec<OrderExecution> = Vec::new();
+        // Notional per execution, for the average price
+        let mut notionals: Vec<f64> = Vec::new();
+        let parse = |value: &Option<String>| {
+            value
+                .as_deref()
+                .and_then(|s| s.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+        for fill in fills {
+            let (Ok(px), Ok(sz)) = (fill.px.parse::<f64>(), fill.sz.parse::<f64>()) else {
+                continue;
+            };
+            let key = match &fill.cloid {
+                Some(cloid) => OrderKey::Cloid(cloid.clone()),
+                None => OrderKey::Oid(fill.oid),
+            };
+            let slot = *index.entry((fill.coin.clone(), key)).or_insert_with(|| {
+                executions.push(OrderExecution {
+                    coin: fill.coin.clone(),
+                    oids: Vec::new(),
+                    cloid: fill.cloid.clone(),
+                    is_buy: fill.is_buy(),
+                    total_sz: 0.0,
+                    avg_px: 0.0,
+                    total_fee: 0.0,
+                    closed_pnl: 0.0,
+                    first_fill_time: fill.time,
+                    last_fill_time: fill.time,
+                    fill_count: 0,
+                });
+                notionals.push(0.0);
+                executions.len() - 1
+            });
+
+            let execution = &mut executions[slot];
+            if !execution.oids.contains(&fill.oid) {
+                execution.oids.push(fill.oid);
+            }
+            execution.total_sz += sz;
+            notionals[slot] += px * sz;
+            if execution.total_sz > 0.0 {
+                execution.avg_px = notionals[slot] / execution.total_sz;
+            }
+            execution.total_fee += parse(&fill.fee);
+            execution.closed_pnl += parse(&fill.closed_pnl);
+            execution.last_fill_time = fill.time;
+            execution.fill_count += 1;
+        }
+        executions
+    }
+
+    /// Filled size times average price
+    pub fn notional(&self) -> f64 {
+        self.total_sz * self.avg_px
+    }
+
+    /// Realized PnL net of fees
+    pub fn net_pnl(&self) -> f64 {
+        self.closed_pnl - self.total_fee
+    }
+}
@@ -0,0 +1,277 @@
+//! Open orders as returned by the `frontendOpenOrders` info request
+//!
+//! Unlike `openOrders`, these carry the trigger details and the TP/SL orders
+//! attached to a parent (`children`), which only become live once the parent
+//! fills.
+
+use serde::{Deserialize, Serialize};
+
+/// Order type as displayed by the frontend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrontendOrderType {
+    #[serde(rename = "Limit")]
+    Limit,
+    #[serde(rename = "Market")]
+    Market,
+    #[serde(rename = "Stop Market")]
+    StopMarket,
+    #[serde(rename = "Stop Limit")]
+    StopLimit,
+    #[serde(rename = "Take Profit Market")]
+    TakeProfitMarket,
+    #[serde(rename = "Take Profit Limit")]
+    TakeProfitLimit,
+    /// A type this version doesn't know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl FrontendOrderType {
+    pub fn is_take_profit(self) -> bool {
+        matches!(
+            self,
+            FrontendOrderType::TakeProfitMarket | FrontendOrderType::TakeProfitLimit
+        )
+    }
+
+    pub fn is_stop_loss(self) -> bool {
+        matches!(
+            self,
+            FrontendOrderType::StopMarket | FrontendOrderType::StopLimit
+        )
+    }
+
+    /// Whether the order executes as a market order once triggered
+    pub fn is_market(self) -> bool {
+        matches!(
+            self,
+            FrontendOrderType::Market
+                | FrontendOrderType::StopMarket
+                | FrontendOrderType::TakeProfitMarket
+        )
+    }
+}
+
+/// Resting order with its trigger details and attached TP/SL orders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct FrontendOrder {
+    pub coin: String,
+    /// `B` for bids, `A` for asks
+    pub side: String,
+    pub limit_px: String,
+    /// Remaining size; `0.0` for TP/SL orders sized to the position
+    pub sz: String,
+    pub oid: u64,
+    pub timestamp: u64,
+    pub orig_sz: String,
+    /// Human-readable condition, e.g. `Price above 30000`, or `N/A`
+    #[serde(default)]
+    pub trigger_condition: String,
+    #[serde(default)]
+    pub is_trigger: bool,
+    #[serde(default)]
+    pub trigger_px: String,
+    /// TP/SL orders placed with this one as their parent
+    #[serde(default)]
+    pub children: Vec<FrontendOrder>,
+    /// Whether this is a TP/SL on the whole position rather than one order
+    #[serde(default)]
+    pub is_position_tpsl: bool,
+    #[serde(default)]
+    pub reduce_only: bool,
+    pub order_type: FrontendOrderType,
+    #[serde(default)]
+    pub tif: Option<String>,
+    #[serde(default)]
+    pub cloid: Option<String>,
+}
+
+impl FrontendOrder {
+    pub fn is_buy(&self) -> bool {
+        self.side == "B"
+    }
+
+    /// Trigger condition, or `None` for plain orders
+    pub fn trigger_condition(&self) -> Option<&str> {
+        if self.is_trigger && self.trigger_condition != "N/A" {
+            Some(&self.trigger_condition)
+        } else {
+            None
+        }
+    }
+
+    /// Trigger price, or `None` for plain orders
+    pub fn trigger_px(&self) -> Option<f64> {
+        if !self.is_trigger {
+            return None;
+        }
+        self.trigger_px.parse().ok()
+    }
+
+    /// Whether this is a take-profit or stop-loss order
+    pub fn is_tpsl(&self) -> bool {
+        self.order_type.is_take_profit() || self.order_type.is_stop_loss()
+    }
+
+    /// Attached take-profit order, if any
+    pub fn take_profit(&self) -> Option<&FrontendOrder> {
+        self.children
+            .iter()
+            .find(|child| child.order_type.is_take_profit())
+    }
+
+    /// Attached stop-loss order, if any
+    pub fn stop_loss(&self) -> Option<&FrontendOrder> {
+        self.children
+            .iter()
+            .find(|child| child.order_type.is_stop_loss())
+    }
+
+    /// This order followed by its children, each paired with its parent's oid
+    pub fn flatten(&self) -> Vec<(Option<u64>, &FrontendOrder)> {
+        let mut orders = vec![(None, self)];
+        let mut index = 0;
+        while index < orders.len() {
+            let (_, order) = orders[index];
+            orders.extend(order.children.iter().map(|child| (Some(order.oid), child)));
+            index += 1;
+        }
+        orders
+    }
+}
+
+/// Entry of the `openOrders` info request
+///
+/// Carries no trigger details or TP/SL children; see [`FrontendOrder`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BasicOpenOrder {
+    pub coin: String,
+    pub side: String,
+    pub limit_px: String,
+    pub sz: String,
+    pub oid: u64,
+    pub timestamp: u64,
+    #[serde(default)]
+    pub orig_sz: Option<String>,
+    #[serde(default)]
+    pub cloid: Option<String>,
+}
+
+impl From<BasicOpenOrder> for FrontendOrder {
+    /// Order of unknown type without trigger details
+    fn from(order: BasicOpenOrder) -> Self {
+        FrontendOrder {
+            orig_sz: order.orig_sz.unwrap_or_else(|| order.sz.clone()),
+            coin: order.coin,
+            side: order.side,
+            limit_px: order.limit_px,
+            sz: order.sz,
+            oid: order.oid,
+            timestamp: order.timestamp,
+            trigger_condition: "N/A".to_string(),
+            is_trigger: false,
+            trigger_px: String::new(),
+            children: Vec::new(),
+            is_position_tpsl: false,
+            reduce_only: false,
+            order_type: FrontendOrderType::Unknown,
+            tif: None,
+            cloid: order.cloid,
+        }
+    }
+}
+
+/// A user's open orders from `frontendOpenOrders` and `openOrders`, one per oid
+///
+/// The two requests are answered separately, so an order placed or
+/// cancelled in between shows up in only one of them. Merging keeps every
+/// order either one returned, preferring the richer `frontendOpenOrders`
+/// entry. Orders are newest first unless reordered.
+#[derive(Debug, Clone, Default)]
+pub struct OpenOrders {
+    orders: Vec<FrontendOrder>,
+}
+
+impl OpenOrders {
+    /// Merge both responses, deduplicating by oid
+    pub fn merge(frontend: Vec<FrontendOrder>, basic: Vec<BasicOpenOrder>) -> Self {
+        let mut seen: std::collections::HashSet<u64> =
+            frontend.iter().map(|order| order.oid).collect();
+        let mut orders = Vec::with_capacity(frontend.len() + basic.len());
+        orders.extend(frontend);
+        orders.extend(
+            basic
+                .into_iter()
+                .filter(|order| seen.insert(order.oid))
+                .map(FrontendOrder::from),
+        );
+        Self { orders }.newest_first()
+    }
+
+    /// Only the orders on `coin`
+    pub fn by_coin(mut self, coin: &str) -> Self {
+        self.orders.retain(|order| order.coin == coin);
+        self
+    }
+
+    /// Only the bids (`is_buy`) or only the asks
+    pub fn by_side(mut self, is_buy: bool) -> Self {
+        self.orders.retain(|order| order.is_buy() == is_buy);
+        self
+    }
+
+    /// Sort by placement time, oldest first
+    pub fn oldest_first(mut self) -> Self {
+        self.orders
+            .sort_by_key(|order| (order.timestamp, order.oid));
+        self
+    }
+
+    /// Sort by placement time, newest first
+    pub fn newest_first(mut self) -> Self {
+        self.orders
+            .sort_by_key(|order| std::cmp::Reverse((order.timestamp, order.oid)));
+        self
+    }
+
+    pub fn get(&self, oid: u64) -> Option<&FrontendOrder> {
+        self.orders.iter().find(|order| order.oid == oid)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FrontendOrder> {
+        self.orders.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    pub fn into_vec(self) -> Vec<FrontendOrder> {
+        self.orders
+    }
+}
+
+impl IntoIterator for OpenOrders {
+    type Item = FrontendOrder;
+    type IntoIter = std::vec::IntoIter<FrontendOrder>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.orders.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a OpenOrders {
+    type Item = &'a FrontendOrder;
+    type IntoIter = std::slice::Iter<'a, FrontendOrder>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.orders.iter()
+    }
+}
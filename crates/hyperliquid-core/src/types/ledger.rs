@@ -0,0 +1,173 @@
+//! Non-funding ledger updates
+//!
+//! Entries of the `userNonFundingLedgerUpdates` info response and WebSocket
+//! channel: every change to an account's balances other than funding
+//! payments. Delta types this crate does not know yet parse as
+//! [`LedgerUpdate::Other`] rather than failing the whole response.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Entry of `userNonFundingLedgerUpdates`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserLedgerUpdate {
+    /// Time in milliseconds
+    pub time: u64,
+    /// Hash of the transaction
+    pub hash: String,
+    pub delta: LedgerUpdate,
+}
+
+/// Position closed by a liquidation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidatedPosition {
+    pub coin: String,
+    /// Signed size before the liquidation
+    pub szi: String,
+}
+
+/// Balance change recorded in the ledger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LedgerUpdate {
+    /// USDC bridged in
+    Deposit {
+        usdc: String,
+    },
+    /// USDC bridged out
+    Withdraw {
+        usdc: String,
+        nonce: u64,
+        fee: String,
+    },
+    /// USDC sent to another account
+    #[serde(rename_all = "camelCase")]
+    InternalTransfer {
+        usdc: String,
+        user: String,
+        destination: String,
+        fee: String,
+    },
+    /// USDC moved between a master account and a sub-account
+    #[serde(rename_all = "camelCase")]
+    SubAccountTransfer {
+        usdc: String,
+        user: String,
+        destination: String,
+    },
+    /// Spot token sent to another account
+    #[serde(rename_all = "camelCase")]
+    SpotTransfer {
+        token: String,
+        amount: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        usdc_value: Option<String>,
+        user: String,
+        destination: String,
+        fee: String,
+    },
+    /// USDC moved between the spot and perp balances
+    #[serde(rename_all = "camelCase")]
+    AccountClassTransfer {
+        usdc: String,
+        to_perp: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultCreate {
+        vault: String,
+        usdc: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fee: Option<String>,
+    },
+    VaultDeposit {
+        vault: String,
+        usdc: String,
+    },
+    /// Profit share paid out by a vault
+    VaultDistribution {
+        vault: String,
+        usdc: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultWithdraw {
+        vault: String,
+        user: String,
+        requested_usd: String,
+        commission: String,
+        closing_cost: String,
+        basis: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        net_withdrawn_usd: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Liquidation {
+        /// Notional of the liquidated positions
+        liquidated_ntl_pos: String,
+        account_value: String,
+        leverage_type: String,
+        liquidated_positions: Vec<LiquidatedPosition>,
+    },
+    /// Spot tokens credited at genesis
+    SpotGenesis {
+        token: String,
+        amount: String,
+    },
+    /// Delta of a type not modelled here, as received
+    #[serde(untagged)]
+    Other(Value),
+}
+
+impl LedgerUpdate {
+    /// Wire name of the delta type, e.g. `"vaultDeposit"`
+    pub fn kind(&self) -> &str {
+        match self {
+            LedgerUpdate::Deposit { .. } => "deposit",
+            LedgerUpdate::Withdraw { .. } => "withdraw",
+            LedgerUpdate::InternalTransfer { .. } => "internalTransfer",
+            LedgerUpdate::SubAccountTransfer { .. } => "subAccountTransfer",
+            LedgerUpdate::SpotTransfer { .. } => "spotTransfer",
+            LedgerUpdate::AccountClassTransfer { .. } => "accountClassTransfer",
+            LedgerUpdate::VaultCreate { .. } => "vaultCreate",
+            LedgerUpdate::VaultDeposit { .. } => "vaultDeposit",
+            LedgerUpdate::VaultDistribution { .. } => "vaultDistribution",
+            LedgerUpdate::VaultWithdraw { .. } => "vaultWithdraw",
+            LedgerUpdate::Liquidation { .. } => "liquidation",
+            LedgerUpdate::SpotGenesis { .. } => "spotGenesis",
+            LedgerUpdate::Other(value) => value
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown"),
+        }
+    }
+
+    /// USDC amount of the update, for the types that move USDC
+    ///
+    /// The amount is unsigned; whether it was credited or debited depends on
+    /// the type and, for transfers, on which side the account is.
+    pub fn usdc(&self) -> Option<f64> {
+        let usdc = match self {
+            LedgerUpdate::Deposit { usdc }
+            | LedgerUpdate::Withdraw { usdc, .. }
+            | LedgerUpdate::InternalTransfer { usdc, .. }
+            | LedgerUpdate::SubAccountTransfer { usdc, .. }
+            | LedgerUpdate::AccountClassTransfer { usdc, .. }
+            | LedgerUpdate::VaultCreate { usdc, .. }
+            | LedgerUpdate::VaultDeposit { usdc, .. }
+            | LedgerUpdate::VaultDistribution { usdc, .. } => usdc.as_str(),
+            LedgerUpdate::Other(value) => value.get("usdc").and_then(Value::as_str)?,
+            _ => return None,
+        };
+        usdc.parse().ok()
+    }
+
+    /// Whether this is a deposit to, withdrawal from or payout of a vault
+    pub fn is_vault(&self) -> bool {
+        matches!(
+            self,
+            LedgerUpdate::VaultCreate { .. }
+                | LedgerUpdate::VaultDeposit { .. }
+                | LedgerUpdate::VaultDistribution { .. }
+                | LedgerUpdate::VaultWithdraw { .. }
+        )
+    }
+}
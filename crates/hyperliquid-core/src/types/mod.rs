@@ -8,15 +8,15 @@ pub use address::Address;
 
 pub mod precision;
 pub use precision::{
-    OrderWireBuilder, PegPriceType, PrecisionError, TriggerCondition,
-    float_to_int, float_to_int_for_hashing, float_to_usd_int, float_to_wire,
+    OrderWireBuilder, PegPriceType, PrecisionError, TriggerCondition, WireFormat,
+    float_to_int, float_to_int_for_hashing, float_to_usd_int, float_to_wire, to_wire_string,
     validate_price_precision, validate_quantity_precision, validate_usd_precision
 };
 
 pub mod timestamp;
 pub use timestamp::{
-    add_millis, add_seconds, format_timestamp, get_timestamp_ms, get_timestamp_seconds,
-    is_valid_timestamp, millis_to_seconds, seconds_to_millis, time_diff_ms, time_diff_seconds,
+    add_millis, add_seconds, format_timestamp, get_timestamp_ms, get_timestamp_ms_at,
+    get_timestamp_seconds, get_timestamp_seconds_at, is_valid_timestamp, millis_to_seconds, seconds_to_millis, time_diff_ms, time_diff_seconds,
     TimestampError, validate_future_timestamp, validate_past_timestamp
 };
 
@@ -26,7 +26,30 @@ pub use perp_schema::{
 };
 
 pub mod optimized;
-pub use optimized::{SymbolInterner, SymbolId, OptimizedOrder, OrderSide, OrderType, OptimizedPosition, OptimizedL2Book, OptimizedTrade, OptimizedUserState, TradingObjectPool, TradingAllocator, TradingAllocatorStats};
+pub use optimized::{global_symbols, SymbolRegistry, SymbolInterner, SymbolId, OptimizedOrder, OrderSide, OrderType, OptimizedPosition, OptimizedL2Book, OptimizedTrade, OptimizedUserState, TradingObjectPool, TradingAllocator, TradingAllocatorStats};
+
+pub mod frontend_order;
+pub use frontend_order::{BasicOpenOrder, FrontendOrder, FrontendOrderType, OpenOrders};
+
+pub mod web_data;
+pub use web_data::{ClearinghouseState, CumFunding, PerpAssetPosition, PerpPosition, PositionLeverage, WebData2};
+
+pub mod ledger;
+pub use ledger::{LedgerUpdate, LiquidatedPosition, UserLedgerUpdate};
+
+pub mod execution;
+pub use execution::{OrderExecution, UserFill};
+
+pub mod notification;
+pub use notification::{Notification, NotificationMsg};
+
+pub mod asset_ctx;
+pub use asset_ctx::{
+    ActiveAssetCtxMsg, ActiveSpotAssetCtxMsg, AssetCtxUpdate, PerpAssetCtx, SpotAssetCtx,
+};
+
+pub mod spot;
+pub use spot::{SpotPair, SpotTokenInfo, SpotUniverse, SPOT_ASSET_OFFSET};
 
 pub mod response_utils;
 pub use response_utils::{ApiResponse, parse_response, parse_success_response, parse_error_response, wrap_success, wrap_error, is_error_response, extract_status, extract_nested_data};
@@ -278,7 +301,7 @@ pub enum MarketType {
 }
 
 /// Subscription types for WebSocket
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Subscription {
     #[serde(rename = "allMids")]
@@ -307,6 +330,8 @@ pub enum Subscription {
     ActiveAssetCtx { coin: String },
     #[serde(rename = "activeAssetData")]
     ActiveAssetData { user: Address, coin: String },
+    #[serde(rename = "notification")]
+    Notification { user: Address },
 }
 
 /// Base response structure for API calls
@@ -480,6 +505,7 @@ pub mod response_utils {
 
 /// Meta information about assets
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Meta {
     pub universe: Vec<AssetMeta>,
     pub exchange: Option<ExchangeMeta>,
@@ -487,6 +513,7 @@ pub struct Meta {
 
 /// Asset metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct AssetMeta {
     pub name: String,
     pub onlyIsolated: bool,
@@ -508,12 +535,14 @@ pub struct AssetMeta {
 
 /// Exchange metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ExchangeMeta {
     pub vaults: Vec<VaultMeta>,
 }
 
 /// Vault metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct VaultMeta {
     pub vault: Address,
     pub name: String,
@@ -526,6 +555,7 @@ pub struct VaultMeta {
 
 /// User state information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct UserState {
     pub marginSummary: MarginSummary,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -555,6 +585,7 @@ pub struct UserState {
 /// assert_eq!(summary.total_margin_used(), "2000.0");
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct MarginSummary {
     pub accountValue: String,
     pub totalMarginUsed: String,
@@ -925,6 +956,7 @@ impl MarginSummary {
 
 /// Cross margin summary
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CrossMarginSummary {
     pub accountValue: String,
     pub totalMarginUsed: String,
@@ -934,6 +966,7 @@ pub struct CrossMarginSummary {
 
 /// Position information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Position {
     pub coin: String,
     pub position: PositionDetails,
@@ -941,6 +974,7 @@ pub struct Position {
 
 /// Detailed position information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct PositionDetails {
     pub szi: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -975,6 +1009,7 @@ pub struct PositionDetails {
 
 /// Asset position
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct AssetPosition {
     pub time: i64,
     pub token: String,
@@ -1001,16 +1036,46 @@ pub struct BuilderInfo {
     pub fee_in_tenths_bps: u32,
 }
 
+/// Inline capacity for one side of an L2 book
+///
+/// Hyperliquid publishes 20 levels per side by default.
+pub const BOOK_LEVELS_INLINE: usize = 20;
+
+/// Inline capacity for bulk order/cancel batches
+pub const BATCH_INLINE: usize = 8;
+
+/// Storage for one side of an L2 book
+///
+/// With the `smallvec` feature, shallow books are stored inline without a
+/// heap allocation; otherwise this is a plain `Vec`.
+#[cfg(feature = "smallvec")]
+pub type BookLevels = smallvec::SmallVec<[OrderLevel; BOOK_LEVELS_INLINE]>;
+/// Storage for one side of an L2 book
+#[cfg(not(feature = "smallvec"))]
+pub type BookLevels = Vec<OrderLevel>;
+
+/// Storage for bulk order/cancel batches
+///
+/// With the `smallvec` feature, small batches are stored inline without a
+/// heap allocation; otherwise this is a plain `Vec`.
+#[cfg(feature = "smallvec")]
+pub type BatchVec<T> = smallvec::SmallVec<[T; BATCH_INLINE]>;
+/// Storage for bulk order/cancel batches
+#[cfg(not(feature = "smallvec"))]
+pub type BatchVec<T> = Vec<T>;
+
 /// L2 order book snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct L2BookSnapshot {
     pub coin: String,
-    pub levels: [Vec<OrderLevel>; 2],
+    pub levels: [BookLevels; 2],
     pub time: i64,
 }
 
 /// Order book level
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct OrderLevel {
     pub px: String,
     pub sz: String,
@@ -1020,6 +1085,7 @@ pub struct OrderLevel {
 
 /// Trade information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Trade {
     pub coin: String,
     pub side: String,
@@ -1048,6 +1114,7 @@ pub struct BboLevel {
 
 /// Candle information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Candle {
     pub coin: String,
     pub interval: String,
@@ -1129,6 +1196,7 @@ pub struct Deposit {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct FundingPayment {
     pub coin: String,
     pub fundingPayment: String,
@@ -1468,6 +1536,9 @@ pub struct SpotMeta {
     pub onlyIsolated: bool,
     pub type_: Option<String>,
     pub tokens: Vec<SpotAssetInfo>,
+    /// Tradable pairs
+    #[serde(default)]
+    pub universe: Vec<SpotPair>,
 }
 
 /// Order wire format for API serialization
@@ -1563,6 +1634,12 @@ pub enum WsMsg {
     OrderUpdatesMsg(OrderUpdatesMsg),
     #[serde(rename = "userFundings")]
     UserFundingsMsg(UserFundingsMsg),
+    #[serde(rename = "notification")]
+    NotificationMsg(NotificationMsg),
+    #[serde(rename = "activeAssetCtx")]
+    ActiveAssetCtxMsg(ActiveAssetCtxMsg),
+    #[serde(rename = "activeSpotAssetCtx")]
+    ActiveSpotAssetCtxMsg(ActiveSpotAssetCtxMsg),
     #[serde(rename = "pong")]
     PongMsg(PongMsg),
     #[serde(other)]
@@ -1633,6 +1710,7 @@ pub struct PongMsg {
 /// Fill trade record for perpetual trades
 /// Represents a single trade execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Fill {
     /// Coin being traded
     pub coin: String,
@@ -1715,6 +1793,9 @@ impl WsMsg {
             WsMsg::UserFillsMsg(_) => None,
             WsMsg::OrderUpdatesMsg(_) => None,
             WsMsg::UserFundingsMsg(_) => None,
+            WsMsg::NotificationMsg(_) => None,
+            WsMsg::ActiveAssetCtxMsg(_) => None,
+            WsMsg::ActiveSpotAssetCtxMsg(_) => None,
             WsMsg::PongMsg(_) => None,
             WsMsg::OtherWsMsg(_) => None,
         }
@@ -1736,6 +1817,11 @@ impl WsMsg {
             WsMsg::UserFillsMsg(_) => Some("userFills".to_string()),
             WsMsg::OrderUpdatesMsg(_) => Some("orderUpdates".to_string()),
             WsMsg::UserFundingsMsg(_) => Some("userFundings".to_string()),
+            WsMsg::NotificationMsg(_) => Some("notification".to_string()),
+            WsMsg::ActiveAssetCtxMsg(msg) => Some(format!("activeAssetCtx.{}", msg.coin)),
+            WsMsg::ActiveSpotAssetCtxMsg(msg) => {
+                Some(format!("activeSpotAssetCtx.{}", msg.coin))
+            }
             WsMsg::PongMsg(_) => Some("pong".to_string()),
             WsMsg::OtherWsMsg(_) => None,
         }
@@ -1867,6 +1953,21 @@ mod ws_msg_and_fill_tests {
         assert_eq!(ws_msg.timestamp(), Some(1234567890));
     }
 
+    #[test]
+    fn test_book_levels_and_batches_serialize_as_arrays() {
+        let json = r#"{"coin":"ETH","levels":[[{"px":"3000.0","sz":"1.0","n":2,"numLevels":null}],[]],"time":1}"#;
+        let book: L2BookSnapshot = serde_json::from_str(json).unwrap();
+        assert_eq!(book.levels[0].len(), 1);
+        assert!(book.levels[1].is_empty());
+        assert_eq!(serde_json::to_string(&book).unwrap(), json);
+
+        #[cfg(feature = "smallvec")]
+        assert!(!book.levels[0].spilled());
+
+        let cancels: BulkCancelRequest = serde_json::from_str(r#"{"cancels":[]}"#).unwrap();
+        assert!(cancels.cancels.is_empty());
+    }
+
     #[test]
     fn test_ws_msg_l2_book_serialization() {
         // Test L2BookMsg serialization/deserialization
@@ -1878,13 +1979,15 @@ mod ws_msg_and_fill_tests {
                     sz: "1.0".to_string(),
                     n: 1,
                     numLevels: None,
-                }],
+                }]
+                .into(),
                 vec![OrderLevel {
                     px: "49999.0".to_string(),
                     sz: "0.5".to_string(),
                     n: 1,
                     numLevels: None,
-                }],
+                }]
+                .into(),
             ],
             time: 1234567890,
         };
@@ -2958,6 +3061,7 @@ pub struct OpenOrdersResponse {
 /// Individual open order
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct OpenOrder {
     pub coin: String,
     pub limit_px: String,
@@ -2990,6 +3094,7 @@ pub struct FundingHistoryRequest {
 
 /// Funding history response
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct FundingHistoryResponse {
     /// Coin for which funding history is requested
     pub coin: String,
@@ -3079,13 +3184,13 @@ impl FundingHistoryRequest {
 /// Bulk order request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkOrderRequest {
-    pub orders: Vec<OrderRequest>,
+    pub orders: BatchVec<OrderRequest>,
 }
 
 /// Bulk cancel request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BulkCancelRequest {
-    pub cancels: Vec<CancelRequest>,
+    pub cancels: BatchVec<CancelRequest>,
 }
 
 /// Transaction signature information
@@ -3198,7 +3303,7 @@ mod exchange_api_tests {
         };
 
         let bulk_request = BulkOrderRequest {
-            orders: vec![order1],
+            orders: vec![order1].into(),
         };
 
         let json = serde_json::to_string(&bulk_request).unwrap();
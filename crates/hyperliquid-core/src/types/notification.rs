@@ -0,0 +1,81 @@
+//! Messages of the `notification` WebSocket channel
+//!
+//! The channel pushes free text to a user: liquidation warnings and notices
+//! about the exchange itself. The payload has no type field, so
+//! [`Notification`] classifies each message from its text; messages that
+//! match nothing known are kept as [`Notification::Other`].
+
+use serde::{Deserialize, Serialize};
+
+/// `data` of a `notification` channel message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationMsg {
+    pub notification: String,
+}
+
+/// Words marking a notice about the exchange rather than the account
+const SYSTEM_KEYWORDS: [&str; 6] = [
+    "maintenance",
+    "upgrade",
+    "downtime",
+    "outage",
+    "halted",
+    "network",
+];
+
+/// Notification pushed to a user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Notification {
+    /// A position is close to, or has reached, liquidation
+    LiquidationWarning {
+        message: String,
+    },
+    /// Maintenance, upgrades and other exchange-wide notices
+    SystemNotice {
+        message: String,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl Notification {
+    /// Classify a notification text
+    pub fn parse(text: &str) -> Self {
+        let message = text.to_string();
+        let lower = text.to_lowercase();
+        if lower.contains("liquidat") {
+            Notification::LiquidationWarning { message }
+        } else if SYSTEM_KEYWORDS.iter().any(|word| lower.contains(word)) {
+            Notification::SystemNotice { message }
+        } else {
+            Notification::Other { message }
+        }
+    }
+
+    /// Text as sent by the exchange
+    pub fn message(&self) -> &str {
+        match self {
+            Notification::LiquidationWarning { message }
+            | Notification::SystemNotice { message }
+            | Notification::Other { message } => message,
+        }
+    }
+
+    pub fn is_liquidation_warning(&self) -> bool {
+        matches!(self, Notification::LiquidationWarning { .. })
+    }
+}
+
+impl From<&NotificationMsg> for Notification {
+    fn from(msg: &NotificationMsg) -> Self {
+        Notification::parse(&msg.notification)
+    }
+}
+
+impl From<NotificationMsg> for Notification {
+    fn from(msg: NotificationMsg) -> Self {
+        Notification::parse(&msg.notification)
+    }
+}
@@ -38,6 +38,11 @@ impl SymbolInterner {
     pub fn stats(&self) -> crate::memory::StringInternStats {
         self.interner.get_stats()
     }
+
+    /// Get a shared handle to the live intern statistics
+    pub fn stats_handle(&self) -> std::sync::Arc<std::sync::Mutex<crate::memory::StringInternStats>> {
+        self.interner.stats_handle()
+    }
 }
 
 /// Interned symbol ID (4 bytes instead of variable-length string)
@@ -54,6 +59,118 @@ impl SymbolId {
     pub fn raw(&self) -> u32 {
         self.0
     }
+
+    /// Intern a coin in the process-wide registry
+    pub fn intern(symbol: &str) -> Self {
+        global_symbols().intern(symbol)
+    }
+
+    /// Resolve this ID through the process-wide registry
+    ///
+    /// IDs produced by a local `SymbolInterner` are not known to the registry.
+    pub fn name(&self) -> Option<&'static str> {
+        global_symbols().get(*self)
+    }
+}
+
+impl std::fmt::Display for SymbolId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => write!(f, "#{}", self.0),
+        }
+    }
+}
+
+/// Serializes as the coin name, so `SymbolId`s can be used directly in wire types
+impl Serialize for SymbolId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.name() {
+            Some(name) => serializer.serialize_str(name),
+            None => Err(serde::ser::Error::custom(format!(
+                "symbol id {} is not registered",
+                self.0
+            ))),
+        }
+    }
+}
+
+/// Deserializes from the name of a coin already in the process-wide registry
+///
+/// Unknown names are an error rather than being interned, since interning
+/// leaks the name; coins are registered when metadata is loaded with
+/// [`InfoClient::initialize_assets`](crate::info::InfoClient::initialize_assets).
+impl<'de> Deserialize<'de> for SymbolId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let symbol = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        global_symbols()
+            .lookup(&symbol)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown symbol: {}", symbol)))
+    }
+}
+
+/// Process-wide symbol registry shared by the Info, Exchange and Stream clients
+///
+/// Coin names are leaked into `&'static str`; the set of listed coins is small
+/// and bounded, and this lets lookups hand out names without allocating.
+#[derive(Debug, Default)]
+pub struct SymbolRegistry {
+    inner: std::sync::RwLock<SymbolRegistryInner>,
+}
+
+#[derive(Debug, Default)]
+struct SymbolRegistryInner {
+    ids: HashMap<&'static str, SymbolId>,
+    names: Vec<&'static str>,
+}
+
+impl SymbolRegistry {
+    /// Intern a coin, returning its stable ID
+    pub fn intern(&self, symbol: &str) -> SymbolId {
+        if let Some(id) = self.lookup(symbol) {
+            return id;
+        }
+
+        let mut inner = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        // Another thread may have inserted it between the read and write lock
+        if let Some(&id) = inner.ids.get(symbol) {
+            return id;
+        }
+
+        let name: &'static str = Box::leak(symbol.to_string().into_boxed_str());
+        let id = SymbolId(inner.names.len() as u32);
+        inner.names.push(name);
+        inner.ids.insert(name, id);
+        id
+    }
+
+    /// Look up a coin without interning it
+    pub fn lookup(&self, symbol: &str) -> Option<SymbolId> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.ids.get(symbol).copied()
+    }
+
+    /// Resolve an ID to its coin name
+    pub fn get(&self, id: SymbolId) -> Option<&'static str> {
+        let inner = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        inner.names.get(id.0 as usize).copied()
+    }
+
+    /// Number of registered symbols
+    pub fn len(&self) -> usize {
+        self.inner.read().unwrap_or_else(|e| e.into_inner()).names.len()
+    }
+
+    /// Check if no symbols are registered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Get the process-wide symbol registry
+pub fn global_symbols() -> &'static SymbolRegistry {
+    static REGISTRY: std::sync::OnceLock<SymbolRegistry> = std::sync::OnceLock::new();
+    REGISTRY.get_or_init(SymbolRegistry::default)
 }
 
 /// Optimized order with interned symbols and zero-copy values
@@ -114,7 +231,7 @@ pub struct OptimizedL2Book {
 }
 
 /// Optimized trade with interned symbols
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct OptimizedTrade {
     /// Interned symbol ID
     pub symbol_id: SymbolId,
@@ -222,14 +339,33 @@ pub struct TradingAllocator {
 impl TradingAllocator {
     /// Create a new trading allocator with default settings
     pub fn new() -> Self {
+        let arena = crate::memory::ArenaAllocator::new(64 * 1024); // 64KB chunks
+        let symbols = SymbolInterner::new();
+
+        let arena_stats = arena.stats_handle();
+        let symbol_stats = symbols.stats_handle();
+        let profiler = crate::memory::MemoryProfiler::new(std::time::Duration::from_secs(1))
+            .with_allocation_source("arena", move || arena_stats.lock().unwrap().clone())
+            .with_intern_source("symbols", move || symbol_stats.lock().unwrap().clone());
+
         Self {
-            arena: crate::memory::ArenaAllocator::new(64 * 1024), // 64KB chunks
-            symbols: SymbolInterner::new(),
+            arena,
+            symbols,
             pools: TradingObjectPool::new(),
-            profiler: crate::memory::MemoryProfiler::new(std::time::Duration::from_secs(1)),
+            profiler,
         }
     }
 
+    /// Fire `callback` when arena memory retained during profiling grows by
+    /// more than `threshold_bytes`
+    pub fn with_leak_watchdog<F>(mut self, threshold_bytes: usize, callback: F) -> Self
+    where
+        F: Fn(&crate::memory::MemoryLeakAlert) + Send + Sync + 'static,
+    {
+        self.profiler = self.profiler.with_leak_watchdog(threshold_bytes, callback);
+        self
+    }
+
     /// Intern a trading symbol
     pub fn intern_symbol(&mut self, symbol: &str) -> SymbolId {
         self.symbols.intern_symbol(symbol)
@@ -333,6 +469,39 @@ impl Default for TradingAllocator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_global_symbol_registry() {
+        let btc = SymbolId::intern("REGISTRY_TEST_BTC");
+        let eth = global_symbols().intern("REGISTRY_TEST_ETH");
+
+        assert_eq!(btc, global_symbols().intern("REGISTRY_TEST_BTC"));
+        assert_ne!(btc, eth);
+        assert_eq!(btc.name(), Some("REGISTRY_TEST_BTC"));
+        assert_eq!(global_symbols().lookup("REGISTRY_TEST_ETH"), Some(eth));
+        assert_eq!(global_symbols().lookup("REGISTRY_TEST_UNKNOWN"), None);
+        assert_eq!(btc.to_string(), "REGISTRY_TEST_BTC");
+
+        // Shared across threads
+        let from_thread = std::thread::spawn(|| SymbolId::intern("REGISTRY_TEST_BTC"))
+            .join()
+            .unwrap();
+        assert_eq!(from_thread, btc);
+    }
+
+    #[test]
+    fn test_symbol_id_serde_uses_coin_name() {
+        let id = SymbolId::intern("REGISTRY_TEST_SOL");
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"REGISTRY_TEST_SOL\"");
+
+        let parsed: SymbolId = serde_json::from_str("\"REGISTRY_TEST_SOL\"").unwrap();
+        assert_eq!(parsed, id);
+        // Unknown names are not interned
+        assert!(serde_json::from_str::<SymbolId>("\"REGISTRY_TEST_UNLISTED\"").is_err());
+        assert_eq!(global_symbols().lookup("REGISTRY_TEST_UNLISTED"), None);
+
+        assert!(serde_json::to_string(&SymbolId::from_raw(u32::MAX)).is_err());
+    }
+
     #[test]
     fn test_symbol_interner() {
         let mut interner = SymbolInterner::new();
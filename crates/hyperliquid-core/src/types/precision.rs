@@ -9,8 +9,13 @@
 //! - USD values are rounded to 6 decimal places for hashing
 //! - The conversion validates that no precision is lost
 //! - Results are normalized to remove trailing zeros
+//!
+//! [`to_wire_string`] produces the exchange's canonical form of a decimal and
+//! [`WireFormat`] additionally checks a price or size against the tick and
+//! lot rules of an asset, since the exchange rejects anything else.
 
 use std::str::FromStr;
+use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use thiserror::Error;
 
@@ -23,8 +28,19 @@ pub enum PrecisionError {
     DecimalError { source: rust_decimal::Error },
     #[error("Overflow during integer conversion: {value}")]
     OverflowError { value: f64 },
+    #[error("{value} is not accepted by the exchange: {reason}")]
+    WireFormatError { value: String, reason: String },
 }
 
+/// Significant figures allowed in a non-integer price
+pub const MAX_PRICE_SIG_FIGS: u32 = 5;
+
+/// Decimals allowed in a perp price before subtracting `szDecimals`
+pub const PERP_MAX_DECIMALS: u32 = 6;
+
+/// Decimals allowed in a spot price before subtracting `szDecimals`
+pub const SPOT_MAX_DECIMALS: u32 = 8;
+
 /// Convert a float to wire format string with precision handling
 ///
 /// This function:
@@ -66,9 +82,147 @@ pub fn float_to_wire(value: f64) -> Result<String, PrecisionError> {
     let decimal = Decimal::from_f64(rounded)
         .ok_or_else(|| PrecisionError::RoundingError { value })?;
 
-    // Normalize and convert to string
-    let normalized = decimal.normalize();
-    Ok(normalized.to_string())
+    Ok(to_wire_string(decimal))
+}
+
+/// Canonical wire representation of a decimal
+///
+/// The exchange hashes prices and sizes as strings, so `"1.50"` and `"1.5"`
+/// are different values to it. The canonical form has no trailing zeros, no
+/// trailing decimal point, no exponent and no negative zero.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use rust_decimal::Decimal;
+/// use hyperliquid_core::types::precision::to_wire_string;
+///
+/// assert_eq!(to_wire_string(Decimal::from_str("50000.0").unwrap()), "50000");
+/// assert_eq!(to_wire_string(Decimal::from_str("0.0012300").unwrap()), "0.00123");
+/// assert_eq!(to_wire_string(Decimal::from_str("-0.00").unwrap()), "0");
+/// ```
+pub fn to_wire_string(value: Decimal) -> String {
+    let normalized = value.normalize();
+    if normalized.is_zero() {
+        return "0".to_string();
+    }
+    normalized.to_string()
+}
+
+/// Number of significant figures of a decimal, ignoring trailing zeros
+fn significant_figures(value: Decimal) -> u32 {
+    let mantissa = value.normalize().mantissa().unsigned_abs();
+    if mantissa == 0 {
+        0
+    } else {
+        mantissa.ilog10() + 1
+    }
+}
+
+/// Price and size rules of one asset
+///
+/// Prices may have at most [`MAX_PRICE_SIG_FIGS`] significant figures
+/// (integers are always allowed) and at most `max_decimals - sz_decimals`
+/// decimals; sizes at most `sz_decimals` decimals.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use rust_decimal::Decimal;
+/// use hyperliquid_core::types::precision::WireFormat;
+///
+/// // BTC perp, szDecimals 5
+/// let btc = WireFormat::perp(5);
+/// assert_eq!(btc.price(Decimal::from_str("60123.0").unwrap()).unwrap(), "60123");
+/// assert!(btc.price(Decimal::from_str("60123.5").unwrap()).is_err());
+/// assert_eq!(btc.size(Decimal::from_str("0.00100").unwrap()).unwrap(), "0.001");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WireFormat {
+    pub sz_decimals: u32,
+    /// [`PERP_MAX_DECIMALS`] or [`SPOT_MAX_DECIMALS`]
+    pub max_decimals: u32,
+}
+
+impl WireFormat {
+    /// Rules of a perp asset
+    pub fn perp(sz_decimals: u32) -> Self {
+        Self {
+            sz_decimals,
+            max_decimals: PERP_MAX_DECIMALS,
+        }
+    }
+
+    /// Rules of a spot asset
+    pub fn spot(sz_decimals: u32) -> Self {
+        Self {
+            sz_decimals,
+            max_decimals: SPOT_MAX_DECIMALS,
+        }
+    }
+
+    /// Decimals allowed in a price
+    pub fn price_decimals(&self) -> u32 {
+        self.max_decimals.saturating_sub(self.sz_decimals)
+    }
+
+    /// Canonical wire string of `px`, or an error if the exchange would reject it
+    pub fn price(&self, px: Decimal) -> Result<String, PrecisionError> {
+        let px = px.normalize();
+        let reject = |reason: String| PrecisionError::WireFormatError {
+            value: px.to_string(),
+            reason,
+        };
+        if px.is_sign_negative() && !px.is_zero() {
+            return Err(reject("price is negative".to_string()));
+        }
+        if px.scale() > self.price_decimals() {
+            return Err(reject(format!(
+                "more than {} decimals",
+                self.price_decimals()
+            )));
+        }
+        if px.scale() > 0 && significant_figures(px) > MAX_PRICE_SIG_FIGS {
+            return Err(reject(format!(
+                "more than {} significant figures",
+                MAX_PRICE_SIG_FIGS
+            )));
+        }
+        Ok(to_wire_string(px))
+    }
+
+    /// Canonical wire string of `sz`, or an error if the exchange would reject it
+    pub fn size(&self, sz: Decimal) -> Result<String, PrecisionError> {
+        let sz = sz.normalize();
+        let reject = |reason: String| PrecisionError::WireFormatError {
+            value: sz.to_string(),
+            reason,
+        };
+        if sz.is_sign_negative() && !sz.is_zero() {
+            return Err(reject("size is negative".to_string()));
+        }
+        if sz.scale() > self.sz_decimals {
+            return Err(reject(format!("more than {} decimals", self.sz_decimals)));
+        }
+        Ok(to_wire_string(sz))
+    }
+
+    /// [`price`](Self::price) of a float
+    pub fn price_f64(&self, px: f64) -> Result<String, PrecisionError> {
+        self.price(f64_to_decimal(px)?)
+    }
+
+    /// [`size`](Self::size) of a float
+    pub fn size_f64(&self, sz: f64) -> Result<String, PrecisionError> {
+        self.size(f64_to_decimal(sz)?)
+    }
+}
+
+/// Decimal of a float rounded to 8 places, without the binary noise of `f64`
+fn f64_to_decimal(value: f64) -> Result<Decimal, PrecisionError> {
+    Decimal::from_f64(value)
+        .map(|decimal| decimal.round_dp(8))
+        .ok_or(PrecisionError::RoundingError { value })
 }
 
 /// Convert a float to integer for hashing with specified decimal places
@@ -246,14 +400,14 @@ pub fn validate_usd_precision(usd_value: f64) -> bool {
 ///
 /// let order_wire = OrderWireBuilder::new("BTC")
 ///     .buy()
-///     .size(1.5)
-///     .limit_price(50000.0)
+///     .size(1.5).unwrap()
+///     .limit_price(50000.0).unwrap()
 ///     .build()
 ///     .unwrap();
 ///
 /// assert_eq!(order_wire.coin, "BTC");
 /// assert_eq!(order_wire.sz, "1.5");
-/// assert_eq!(order_wire.limit_price, "50000.0");
+/// assert_eq!(order_wire.limit_price, "50000");
 /// assert!(order_wire.is_buy);
 /// ```
 #[derive(Debug, Clone)]
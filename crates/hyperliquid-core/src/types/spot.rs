@@ -0,0 +1,167 @@
+//! Spot pair universe
+//!
+//! `spotMeta` lists tokens and the pairs trading them. A pair is addressed
+//! by its `name` on the wire (`"PURR/USDC"` for canonical pairs, `"@107"`
+//! otherwise) and by `10000 + index` as the asset of an order. The tokens of
+//! a pair are indices into the token list, so [`SpotUniverse`] keeps both to
+//! resolve a pair from any of its names, its index or its asset id.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::HyperliquidError;
+
+/// Offset of spot asset ids: the asset of pair `index` is `10000 + index`
+pub const SPOT_ASSET_OFFSET: u32 = 10_000;
+
+/// Entry of the `universe` array of `spotMeta`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotPair {
+    /// Wire name, e.g. `"PURR/USDC"` or `"@107"`
+    pub name: String,
+    /// Token indices of the base and quote
+    pub tokens: [u32; 2],
+    pub index: u32,
+    /// Whether `name` is the `BASE/QUOTE` form
+    pub is_canonical: bool,
+}
+
+impl SpotPair {
+    /// Asset id used in order actions
+    pub fn asset_id(&self) -> u32 {
+        SPOT_ASSET_OFFSET + self.index
+    }
+
+    /// Token index of the base
+    pub fn base(&self) -> u32 {
+        self.tokens[0]
+    }
+
+    /// Token index of the quote
+    pub fn quote(&self) -> u32 {
+        self.tokens[1]
+    }
+}
+
+/// Token fields of `spotMeta` needed to resolve pairs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotTokenInfo {
+    pub name: String,
+    pub index: u32,
+    /// Size decimals of orders with this token as the base
+    pub sz_decimals: u32,
+}
+
+/// Spot pairs indexed by name, index and asset id
+#[derive(Debug, Clone, Default)]
+pub struct SpotUniverse {
+    pairs: Vec<SpotPair>,
+    tokens: HashMap<u32, SpotTokenInfo>,
+    by_name: HashMap<String, usize>,
+    by_index: HashMap<u32, usize>,
+}
+
+impl SpotUniverse {
+    pub fn new(pairs: Vec<SpotPair>, tokens: Vec<SpotTokenInfo>) -> Self {
+        let tokens: HashMap<u32, SpotTokenInfo> = tokens
+            .into_iter()
+            .map(|token| (token.index, token))
+            .collect();
+        let mut by_name = HashMap::with_capacity(pairs.len() * 2);
+        let mut by_index = HashMap::with_capacity(pairs.len());
+        for (position, pair) in pairs.iter().enumerate() {
+            by_name.insert(pair.name.clone(), position);
+            by_index.insert(pair.index, position);
+        }
+        // `BASE/QUOTE` also resolves non-canonical pairs, without shadowing
+        // a wire name
+        for (position, pair) in pairs.iter().enumerate() {
+            if let (Some(base), Some(quote)) = (tokens.get(&pair.base()), tokens.get(&pair.quote()))
+            {
+                by_name
+                    .entry(format!("{}/{}", base.name, quote.name))
+                    .or_insert(position);
+            }
+        }
+        Self {
+            pairs,
+            tokens,
+            by_name,
+            by_index,
+        }
+    }
+
+    /// Build from a raw `spotMeta` response
+    pub fn from_spot_meta(spot_meta: &Value) -> Result<Self, HyperliquidError> {
+        let field = |name: &str| {
+            spot_meta
+                .get(name)
+                .cloned()
+                .unwrap_or(Value::Array(Vec::new()))
+        };
+        let pairs: Vec<SpotPair> = serde_json::from_value(field("universe"))?;
+        let tokens: Vec<SpotTokenInfo> = serde_json::from_value(field("tokens"))?;
+        Ok(Self::new(pairs, tokens))
+    }
+
+    /// Pairs in `spotMeta` order
+    pub fn pairs(&self) -> &[SpotPair] {
+        &self.pairs
+    }
+
+    /// Pair by wire name or `BASE/QUOTE`
+    pub fn pair(&self, name: &str) -> Option<&SpotPair> {
+        self.by_name
+            .get(name)
+            .map(|&position| &self.pairs[position])
+    }
+
+    pub fn pair_by_index(&self, index: u32) -> Option<&SpotPair> {
+        self.by_index
+            .get(&index)
+            .map(|&position| &self.pairs[position])
+    }
+
+    pub fn pair_by_asset(&self, asset_id: u32) -> Option<&SpotPair> {
+        self.pair_by_index(asset_id.checked_sub(SPOT_ASSET_OFFSET)?)
+    }
+
+    /// Asset id of the pair named `name`
+    pub fn asset_id(&self, name: &str) -> Option<u32> {
+        self.pair(name).map(SpotPair::asset_id)
+    }
+
+    /// Wire name of the pair named `name`, e.g. `"@107"` for `"HYPE/USDC"`
+    pub fn coin(&self, name: &str) -> Option<&str> {
+        self.pair(name).map(|pair| pair.name.as_str())
+    }
+
+    pub fn token(&self, index: u32) -> Option<&SpotTokenInfo> {
+        self.tokens.get(&index)
+    }
+
+    /// `BASE/QUOTE` name of `pair`
+    pub fn display_name(&self, pair: &SpotPair) -> Option<String> {
+        let base = self.token(pair.base())?;
+        let quote = self.token(pair.quote())?;
+        Some(format!("{}/{}", base.name, quote.name))
+    }
+
+    /// Size decimals of orders on the pair named `name`
+    pub fn sz_decimals(&self, name: &str) -> Option<u32> {
+        self.token(self.pair(name)?.base())
+            .map(|token| token.sz_decimals)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
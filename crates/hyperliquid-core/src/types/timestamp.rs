@@ -11,9 +11,10 @@
 //! - Includes validation for reasonable timestamp ranges
 //! - Handles edge cases and overflow protection
 
-use std::time::{SystemTime, UNIX_EPOCH};
 use thiserror::Error;
 
+use crate::clock::{Clock, SystemClock};
+
 /// Timestamp handling errors
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum TimestampError {
@@ -39,12 +40,16 @@ pub enum TimestampError {
 /// assert!(timestamp > 1_000_000_000_000); // Should be after 2001
 /// ```
 pub fn get_timestamp_ms() -> Result<i64, TimestampError> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| TimestampError::SystemTimeError(format!("System time before Unix epoch: {}", e)))?;
+    get_timestamp_ms_at(&SystemClock)
+}
 
-    // Convert to milliseconds
-    let millis = now.as_secs() as i64 * 1000 + now.subsec_millis() as i64;
+/// Get the time of `clock` in Unix milliseconds
+///
+/// Same checks as [`get_timestamp_ms`]; pass a
+/// [`ManualClock`](crate::clock::ManualClock) in tests.
+pub fn get_timestamp_ms_at(clock: &dyn Clock) -> Result<i64, TimestampError> {
+    let millis = i64::try_from(clock.now_ms())
+        .map_err(|_| TimestampError::OverflowError(i64::MAX))?;
 
     // Validate reasonable range (year 2000 to 2100)
     if millis < 946684800000 || millis > 4102444800000 {
@@ -68,12 +73,13 @@ pub fn get_timestamp_ms() -> Result<i64, TimestampError> {
 /// assert!(timestamp > 1_000_000_000); // Should be after 2001
 /// ```
 pub fn get_timestamp_seconds() -> Result<i64, TimestampError> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|e| TimestampError::SystemTimeError(format!("System time before Unix epoch: {}", e)))?;
+    get_timestamp_seconds_at(&SystemClock)
+}
 
-    // Convert to seconds
-    let seconds = now.as_secs() as i64;
+/// Get the time of `clock` in Unix seconds
+pub fn get_timestamp_seconds_at(clock: &dyn Clock) -> Result<i64, TimestampError> {
+    let seconds = i64::try_from(clock.now_ms() / 1000)
+        .map_err(|_| TimestampError::OverflowError(i64::MAX))?;
 
     // Validate reasonable range (year 2000 to 2100)
     if seconds < 946684800 || seconds > 4102444800 {
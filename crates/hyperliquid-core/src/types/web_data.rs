@@ -0,0 +1,150 @@
+//! Account snapshot carried by the `webData2` channel
+//!
+//! One `webData2` message combines what otherwise takes several requests:
+//! `clearinghouseState`, `frontendOpenOrders`, `metaAndAssetCtxs` and the
+//! server time. The exchange pushes a full snapshot on every change, so each
+//! message replaces the previous one.
+//!
+//! Parse the `data` of a `webData2` message with
+//! `serde_json::from_value::<WebData2>(response.data)`.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::frontend_order::FrontendOrder;
+use super::{AssetContext, CrossMarginSummary, LeverageType, MarginSummary, Meta};
+
+/// Leverage of a position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionLeverage {
+    #[serde(rename = "type")]
+    pub type_: LeverageType,
+    pub value: u32,
+    /// USD backing an isolated position
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_usd: Option<String>,
+}
+
+/// Funding paid (positive) or received on a position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CumFunding {
+    pub all_time: String,
+    pub since_open: String,
+    pub since_change: String,
+}
+
+/// Open perp position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerpPosition {
+    pub coin: String,
+    /// Signed size; positive is long
+    pub szi: String,
+    pub leverage: PositionLeverage,
+    pub entry_px: Option<String>,
+    pub position_value: String,
+    pub unrealized_pnl: String,
+    pub return_on_equity: String,
+    pub liquidation_px: Option<String>,
+    pub margin_used: String,
+    pub max_leverage: u32,
+    #[serde(default)]
+    pub cum_funding: Option<CumFunding>,
+}
+
+impl PerpPosition {
+    pub fn szi(&self) -> f64 {
+        self.szi.parse().unwrap_or(0.0)
+    }
+}
+
+/// Entry of `assetPositions`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerpAssetPosition {
+    /// Position mode, `oneWay`
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub position: PerpPosition,
+}
+
+/// Perp margin and positions of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearinghouseState {
+    pub margin_summary: MarginSummary,
+    pub cross_margin_summary: CrossMarginSummary,
+    #[serde(default)]
+    pub cross_maintenance_margin_used: Option<String>,
+    pub withdrawable: String,
+    pub asset_positions: Vec<PerpAssetPosition>,
+    pub time: u64,
+}
+
+impl ClearinghouseState {
+    /// Position in `coin`, if one is open
+    pub fn position(&self, coin: &str) -> Option<&PerpPosition> {
+        self.asset_positions
+            .iter()
+            .map(|entry| &entry.position)
+            .find(|position| position.coin == coin)
+    }
+}
+
+/// Payload of the `webData2` channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebData2 {
+    pub user: String,
+    pub clearinghouse_state: ClearinghouseState,
+    /// Open orders with their trigger details and TP/SL children
+    #[serde(default)]
+    pub open_orders: Vec<FrontendOrder>,
+    pub meta: Meta,
+    /// Contexts of the perp assets, indexed like `meta.universe`
+    #[serde(default)]
+    pub asset_ctxs: Vec<AssetContext>,
+    /// Exchange time in milliseconds
+    pub server_time: u64,
+    #[serde(default)]
+    pub agent_address: Option<String>,
+    #[serde(default)]
+    pub agent_valid_until: Option<u64>,
+    #[serde(default)]
+    pub cum_ledger: Option<String>,
+    #[serde(default)]
+    pub is_vault: bool,
+    #[serde(default)]
+    pub total_vault_equity: Option<String>,
+    /// Spot balances, when the account holds any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spot_state: Option<Value>,
+    /// TWAP orders in progress, as `[id, state]` pairs
+    #[serde(default)]
+    pub twap_states: Vec<Value>,
+}
+
+impl WebData2 {
+    /// Context of the perp `coin`
+    pub fn asset_ctx(&self, coin: &str) -> Option<&AssetContext> {
+        let index = self
+            .meta
+            .universe
+            .iter()
+            .position(|asset| asset.name == coin)?;
+        self.asset_ctxs.get(index)
+    }
+
+    /// Mark price of the perp `coin`
+    pub fn mark_px(&self, coin: &str) -> Option<f64> {
+        self.asset_ctx(coin)?.markPx.as_deref()?.parse().ok()
+    }
+
+    /// Open orders on `coin`
+    pub fn open_orders_for<'a>(&'a self, coin: &'a str) -> impl Iterator<Item = &'a FrontendOrder> {
+        self.open_orders
+            .iter()
+            .filter(move |order| order.coin == coin)
+    }
+}
@@ -0,0 +1,466 @@
+//! Vault operator reports
+//!
+//! [`VaultDetails`] types the `vaultDetails` info response: the vault's
+//! settings, portfolio history and every follower's equity and PnL.
+//! [`VaultReport`] turns one response into follower statistics, the profit
+//! share accrued to the leader, and the leader's PnL split into its own
+//! stake's return and that profit share.
+//!
+//! Hyperliquid charges followers a share of their profit (10% by default)
+//! when they withdraw. The accrual reported here is that share of each
+//! follower's current positive PnL, i.e. what the leader would receive if
+//! every follower withdrew now.
+//!
+//! A [`VaultMonitor`] polls `vaultDetails` and keeps a [`VaultSnapshot`]
+//! per poll in a [`StateStore`] under `vault/<vault>/<time>`, so follower
+//! equity can be followed over time.
+//!
+//! ```no_run
+//! # async fn example(client: hyperliquid_core::HttpClient) -> Result<(), hyperliquid_core::HyperliquidError> {
+//! use std::sync::Arc;
+//! use hyperliquid_core::store::MemoryStore;
+//! use hyperliquid_core::vaults::VaultMonitor;
+//!
+//! let vault = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+//! let monitor = VaultMonitor::new(client, vault, Arc::new(MemoryStore::new()));
+//! let report = monitor.report().await?;
+//! println!(
+//!     "{} followers, {:.2} USDC profit share accrued",
+//!     report.followers.len(),
+//!     report.accrued_profit_share
+//! );
+//! let _job = monitor.start();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
+
+use crate::client::HttpClient;
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+use crate::store::{self, StateStore};
+
+const VAULT_PREFIX: &str = "vault/";
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Share of follower profit paid to the leader on withdrawal
+pub const DEFAULT_PROFIT_SHARE: f64 = 0.10;
+
+fn parse(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+/// Entry of `followers` in `vaultDetails`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultFollower {
+    /// Follower address, or `"Leader"` for the leader's own stake
+    pub user: String,
+    pub vault_equity: String,
+    /// PnL since the follower's entry
+    pub pnl: String,
+    pub all_time_pnl: String,
+    #[serde(default)]
+    pub days_following: u64,
+    #[serde(default)]
+    pub vault_entry_time: u64,
+    #[serde(default)]
+    pub lockup_until: Option<u64>,
+}
+
+/// History for one window of `portfolio`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultPortfolio {
+    /// `(time ms, value)` pairs
+    #[serde(default)]
+    pub account_value_history: Vec<(u64, String)>,
+    #[serde(default)]
+    pub pnl_history: Vec<(u64, String)>,
+    #[serde(default)]
+    pub vlm: Option<String>,
+}
+
+/// `vaultDetails` response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDetails {
+    pub name: String,
+    pub vault_address: String,
+    pub leader: String,
+    #[serde(default)]
+    pub description: String,
+    /// Histories by window (`"day"`, `"week"`, `"month"`, `"allTime"`, ...)
+    #[serde(default)]
+    pub portfolio: Vec<(String, VaultPortfolio)>,
+    #[serde(default)]
+    pub apr: f64,
+    /// Leader's share of the vault's equity
+    #[serde(default)]
+    pub leader_fraction: f64,
+    #[serde(default)]
+    pub leader_commission: f64,
+    #[serde(default)]
+    pub followers: Vec<VaultFollower>,
+    #[serde(default)]
+    pub max_distributable: f64,
+    #[serde(default)]
+    pub max_withdrawable: f64,
+    #[serde(default)]
+    pub is_closed: bool,
+    #[serde(default)]
+    pub allow_deposits: bool,
+}
+
+impl VaultDetails {
+    /// History of the `window` portfolio, e.g. `"allTime"`
+    pub fn portfolio(&self, window: &str) -> Option<&VaultPortfolio> {
+        self.portfolio
+            .iter()
+            .find(|(name, _)| name == window)
+            .map(|(_, portfolio)| portfolio)
+    }
+
+    /// Whether `follower` is the leader's own stake
+    pub fn is_leader(&self, follower: &VaultFollower) -> bool {
+        follower.user == "Leader" || follower.user.eq_ignore_ascii_case(&self.leader)
+    }
+}
+
+/// One follower's position in the vault
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FollowerStats {
+    pub user: String,
+    pub equity: f64,
+    /// PnL since entry
+    pub pnl: f64,
+    pub all_time_pnl: f64,
+    /// Share of the vault's equity
+    pub share: f64,
+    pub days_following: u64,
+    pub entry_time: u64,
+    pub lockup_until: Option<u64>,
+    /// Profit share the leader would take if the follower withdrew now
+    pub accrued_profit_share: f64,
+}
+
+/// Leader PnL by source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LeaderPnl {
+    /// PnL of the leader's own stake
+    pub own_pnl: f64,
+    /// Profit share accrued from followers
+    pub profit_share: f64,
+}
+
+impl LeaderPnl {
+    pub fn total(&self) -> f64 {
+        self.own_pnl + self.profit_share
+    }
+}
+
+/// Follower statistics and profit share of a vault at one time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultReport {
+    pub vault: String,
+    pub name: String,
+    pub leader: String,
+    pub time: u64,
+    /// Sum of all stakes, the leader's included
+    pub total_equity: f64,
+    pub leader_equity: f64,
+    /// Followers other than the leader, largest stake first
+    pub followers: Vec<FollowerStats>,
+    pub follower_equity: f64,
+    pub follower_pnl: f64,
+    pub accrued_profit_share: f64,
+    pub leader_pnl: LeaderPnl,
+    pub apr: f64,
+}
+
+impl VaultReport {
+    /// Build a report at `time` charging `profit_share` of follower profit
+    pub fn from_details(details: &VaultDetails, profit_share: f64, time: u64) -> Self {
+        let total_equity: f64 = details
+            .followers
+            .iter()
+            .map(|follower| parse(&follower.vault_equity))
+            .sum();
+        let leader = details
+            .followers
+            .iter()
+            .find(|follower| details.is_leader(follower));
+        let leader_equity = leader.map_or(total_equity * details.leader_fraction, |leader| {
+            parse(&leader.vault_equity)
+        });
+        let mut followers: Vec<FollowerStats> = details
+            .followers
+            .iter()
+            .filter(|follower| !details.is_leader(follower))
+            .map(|follower| {
+                let equity = parse(&follower.vault_equity);
+                let pnl = parse(&follower.pnl);
+                FollowerStats {
+                    user: follower.user.clone(),
+                    equity,
+                    pnl,
+                    all_time_pnl: parse(&follower.all_time_pnl),
+                    share: if total_equity > 0.0 {
+                        equity / total_equity
+                    } else {
+                        0.0
+                    },
+                    days_following: follower.days_following,
+                    entry_time: follower.vault_entry_time,
+                    lockup_until: follower.lockup_until,
+                    accrued_profit_share: pnl.max(0.0) * profit_share,
+                }
+            })
+            .collect();
+        followers.sort_by(|a, b| b.equity.total_cmp(&a.equity));
+
+        let accrued_profit_share = followers.iter().map(|f| f.accrued_profit_share).sum();
+        Self {
+            vault: details.vault_address.clone(),
+            name: details.name.clone(),
+            leader: details.leader.clone(),
+            time,
+            total_equity,
+            leader_equity,
+            follower_equity: followers.iter().map(|f| f.equity).sum(),
+            follower_pnl: followers.iter().map(|f| f.pnl).sum(),
+            accrued_profit_share,
+            leader_pnl: LeaderPnl {
+                own_pnl: leader.map_or(0.0, |leader| parse(&leader.pnl)),
+                profit_share: accrued_profit_share,
+            },
+            followers,
+            apr: details.apr,
+        }
+    }
+
+    pub fn follower(&self, user: &str) -> Option<&FollowerStats> {
+        self.followers
+            .iter()
+            .find(|follower| follower.user.eq_ignore_ascii_case(user))
+    }
+}
+
+/// Equity of one follower in a [`VaultSnapshot`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FollowerEquity {
+    pub user: String,
+    pub equity: f64,
+    pub pnl: f64,
+}
+
+/// Stored state of a vault at one poll
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaultSnapshot {
+    pub time: u64,
+    pub total_equity: f64,
+    pub leader_equity: f64,
+    pub accrued_profit_share: f64,
+    pub followers: Vec<FollowerEquity>,
+}
+
+impl From<&VaultReport> for VaultSnapshot {
+    fn from(report: &VaultReport) -> Self {
+        Self {
+            time: report.time,
+            total_equity: report.total_equity,
+            leader_equity: report.leader_equity,
+            accrued_profit_share: report.accrued_profit_share,
+            followers: report
+                .followers
+                .iter()
+                .map(|follower| FollowerEquity {
+                    user: follower.user.clone(),
+                    equity: follower.equity,
+                    pnl: follower.pnl,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Vault stakes of `user`: `(vault address, equity)`
+pub async fn user_vault_equities(
+    client: &HttpClient,
+    user: &str,
+) -> Result<Vec<(String, f64)>, HyperliquidError> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct WireEquity {
+        vault_address: String,
+        equity: String,
+    }
+    let equities: Vec<WireEquity> = client
+        .post("/info", &json!({"type": "userVaultEquities", "user": user}))
+        .await?;
+    Ok(equities
+        .into_iter()
+        .map(|entry| (entry.vault_address, parse(&entry.equity)))
+        .collect())
+}
+
+/// Key prefix of `vault`'s snapshots
+fn vault_prefix(vault: &str) -> String {
+    format!("{}{}/", VAULT_PREFIX, vault.to_lowercase())
+}
+
+/// Polls a vault and records its followers in a [`StateStore`]
+#[derive(Clone)]
+pub struct VaultMonitor {
+    client: HttpClient,
+    vault: String,
+    store: Arc<dyn StateStore>,
+    interval: Duration,
+    profit_share: f64,
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for VaultMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VaultMonitor")
+            .field("vault", &self.vault)
+            .field("store", &self.store)
+            .field("interval", &self.interval)
+            .field("profit_share", &self.profit_share)
+            .finish()
+    }
+}
+
+impl VaultMonitor {
+    pub fn new(client: HttpClient, vault: impl Into<String>, store: Arc<dyn StateStore>) -> Self {
+        Self {
+            client,
+            vault: vault.into(),
+            store,
+            interval: DEFAULT_INTERVAL,
+            profit_share: DEFAULT_PROFIT_SHARE,
+            clock: clock::system(),
+        }
+    }
+
+    /// Time between snapshots (default 5 minutes)
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Share of follower profit paid to the leader (default 10%)
+    pub fn with_profit_share(mut self, profit_share: f64) -> Self {
+        self.profit_share = profit_share.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Clock that stamps reports and snapshots
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn vault(&self) -> &str {
+        &self.vault
+    }
+
+    /// Fetch `vaultDetails`
+    pub async fn details(&self) -> Result<VaultDetails, HyperliquidError> {
+        self.client
+            .post(
+                "/info",
+                &json!({"type": "vaultDetails", "vaultAddress": self.vault}),
+            )
+            .await
+    }
+
+    /// Fetch the vault and report on it
+    pub async fn report(&self) -> Result<VaultReport, HyperliquidError> {
+        let details = self.details().await?;
+        Ok(VaultReport::from_details(
+            &details,
+            self.profit_share,
+            self.clock.now_ms(),
+        ))
+    }
+
+    /// Fetch the vault, record a snapshot and return the report
+    pub async fn sample(&self) -> Result<VaultReport, HyperliquidError> {
+        let report = self.report().await?;
+        self.record(&VaultSnapshot::from(&report))?;
+        Ok(report)
+    }
+
+    /// Store `snapshot`
+    pub fn record(&self, snapshot: &VaultSnapshot) -> Result<(), HyperliquidError> {
+        let key = format!("{}{:020}", vault_prefix(&self.vault), snapshot.time);
+        store::put_json(self.store.as_ref(), &key, snapshot)?;
+        self.store.flush()
+    }
+
+    /// Sample every interval in the background until the handle is aborted
+    ///
+    /// Failed samples are logged and skipped.
+    pub fn start(&self) -> JoinHandle<()> {
+        let monitor = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(monitor.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = monitor.sample().await {
+                    warn!(vault = %monitor.vault, "Vault sample failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// Recorded snapshots with a time in `range`, in time order
+    pub fn history(&self, range: Range<u64>) -> Result<Vec<VaultSnapshot>, HyperliquidError> {
+        Ok(
+            store::scan_json::<VaultSnapshot>(self.store.as_ref(), &vault_prefix(&self.vault))?
+                .into_iter()
+                .map(|(_, snapshot)| snapshot)
+                .filter(|snapshot| range.contains(&snapshot.time))
+                .collect(),
+        )
+    }
+
+    /// `(time, equity, pnl)` of `user` in each snapshot in `range` they were in
+    pub fn follower_history(
+        &self,
+        user: &str,
+        range: Range<u64>,
+    ) -> Result<Vec<(u64, f64, f64)>, HyperliquidError> {
+        Ok(self
+            .history(range)?
+            .into_iter()
+            .filter_map(|snapshot| {
+                snapshot
+                    .followers
+                    .iter()
+                    .find(|follower| follower.user.eq_ignore_ascii_case(user))
+                    .map(|follower| (snapshot.time, follower.equity, follower.pnl))
+            })
+            .collect())
+    }
+
+    /// Change in accrued profit share between the first and last snapshot in `range`
+    pub fn profit_share_accrued(&self, range: Range<u64>) -> Result<f64, HyperliquidError> {
+        let history = self.history(range)?;
+        Ok(match (history.first(), history.last()) {
+            (Some(first), Some(last)) => last.accrued_profit_share - first.accrued_profit_share,
+            _ => 0.0,
+        })
+    }
+}
@@ -0,0 +1,404 @@
+//! Heartbeat watchdog for resting orders
+//!
+//! A [`Watchdog`] follows the liveness of the market data feed and, with an
+//! [`OrderLatencyTracker`], how long orders wait for their acknowledgement.
+//! When the process has been blind longer than the threshold it protects the
+//! book through a [`KillSwitch`]:
+//!
+//! - [`TripAction::CancelAll`] cancels every open order of the
+//!   [`OrderManager`] straight away;
+//! - [`TripAction::ScheduleCancel`] arms the exchange's `scheduleCancel`
+//!   dead man's switch instead, so the orders are canceled after a grace
+//!   period unless the feed recovers first, in which case it is disarmed.
+//!
+//! The watchdog trips once per outage. Strategies should check
+//! [`Watchdog::is_tripped`] before quoting, since nothing stops new orders
+//! being placed while it is tripped.
+//!
+//! ```no_run
+//! # async fn example(
+//! #     ws: hyperliquid_core::stream::WebSocketClient,
+//! #     venue: hyperliquid_core::execution::ExchangeVenue,
+//! #     oms: hyperliquid_core::oms::OrderManager,
+//! # ) {
+//! use std::time::Duration;
+//! use hyperliquid_core::watchdog::{TripAction, Watchdog};
+//!
+//! let watchdog = Watchdog::new(venue, oms)
+//!     .with_feed_timeout(Duration::from_secs(3))
+//!     .with_action(TripAction::ScheduleCancel(Duration::from_secs(10)));
+//! let _job = watchdog.start(Duration::from_millis(500));
+//! while let Some(event) = ws.next_event().await {
+//!     watchdog.handle_ws_event(&event);
+//! }
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+#[cfg(feature = "exchange-signing")]
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::clock::{self, Clock};
+use crate::error::HyperliquidError;
+#[cfg(feature = "exchange-signing")]
+use crate::exchange::OrderLatencyTracker;
+#[cfg(feature = "exchange-signing")]
+use crate::execution::ExchangeVenue;
+use crate::oms::{OrderManager, TrackedOrder};
+#[cfg(feature = "ws")]
+use crate::stream::WebSocketEvent;
+
+/// Default silence after which the feed counts as lost
+pub const DEFAULT_FEED_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Earliest the exchange accepts a `scheduleCancel` time after now
+pub const MIN_SCHEDULE_CANCEL_DELAY: Duration = Duration::from_secs(5);
+
+/// What the watchdog does when it trips
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TripAction {
+    /// Cancel every open order
+    CancelAll,
+    /// Arm `scheduleCancel` this long after the trip; raised to
+    /// [`MIN_SCHEDULE_CANCEL_DELAY`]
+    ScheduleCancel(Duration),
+}
+
+/// Why the process is flying blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlindReason {
+    /// The websocket has been down this long
+    Disconnected(Duration),
+    /// No message has arrived for this long
+    FeedSilent(Duration),
+    /// An order has waited this long for its acknowledgement
+    AckOverdue(Duration),
+}
+
+impl BlindReason {
+    /// Metric label
+    pub fn label(&self) -> &'static str {
+        match self {
+            BlindReason::Disconnected(_) => "disconnected",
+            BlindReason::FeedSilent(_) => "feed_silent",
+            BlindReason::AckOverdue(_) => "ack_overdue",
+        }
+    }
+}
+
+impl std::fmt::Display for BlindReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlindReason::Disconnected(down) => write!(f, "websocket down for {:?}", down),
+            BlindReason::FeedSilent(silent) => write!(f, "no market data for {:?}", silent),
+            BlindReason::AckOverdue(waiting) => {
+                write!(f, "order unacknowledged for {:?}", waiting)
+            }
+        }
+    }
+}
+
+/// Where protective cancels are sent
+pub trait KillSwitch: Send + Sync + 'static {
+    /// Cancel `orders`, by oid or else by cloid
+    fn cancel_orders(
+        &self,
+        orders: &[TrackedOrder],
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+
+    /// Cancel every open order at `time` (ms); `None` disarms the schedule
+    fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+    ) -> impl Future<Output = Result<(), HyperliquidError>> + Send;
+}
+
+#[cfg(feature = "exchange-signing")]
+impl KillSwitch for ExchangeVenue {
+    async fn cancel_orders(&self, orders: &[TrackedOrder]) -> Result<(), HyperliquidError> {
+        let mut by_oid = Vec::new();
+        let mut by_cloid = Vec::new();
+        for order in orders {
+            let (asset, _) = self.asset(&order.coin)?;
+            match (order.oid, &order.cloid) {
+                (Some(oid), _) => by_oid.push(json!({"a": asset, "o": oid})),
+                (None, Some(cloid)) => by_cloid.push(json!({"asset": asset, "cloid": cloid})),
+                (None, None) => {}
+            }
+        }
+        if !by_oid.is_empty() {
+            self.post_action(json!({"type": "cancel", "cancels": by_oid}))
+                .await?;
+        }
+        if !by_cloid.is_empty() {
+            self.post_action(json!({"type": "cancelByCloid", "cancels": by_cloid}))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn schedule_cancel(&self, time: Option<u64>) -> Result<(), HyperliquidError> {
+        let mut action = json!({"type": "scheduleCancel"});
+        if let Some(time) = time {
+            action["time"] = time.into();
+        }
+        let response = self.post_action(action).await?;
+        if response.get("status").and_then(Value::as_str) == Some("err") {
+            let reason = response
+                .get("response")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(HyperliquidError::Validation(reason.to_string()));
+        }
+        Ok(())
+    }
+}
+
+struct State {
+    /// Time (ms) of the last message, or of the start
+    last_message: u64,
+    /// Time (ms) the websocket went down
+    disconnected_at: Option<u64>,
+    tripped: bool,
+    trips: u64,
+}
+
+/// Protects resting orders when the data feed or order acks go quiet
+pub struct Watchdog<K> {
+    venue: Arc<K>,
+    orders: OrderManager,
+    state: Arc<Mutex<State>>,
+    clock: Arc<dyn Clock>,
+    feed_timeout: Duration,
+    action: TripAction,
+    #[cfg(feature = "exchange-signing")]
+    acks: Option<(Arc<OrderLatencyTracker>, Duration)>,
+    /// Serializes checks so a trip's action is sent once
+    checking: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl<K> Clone for Watchdog<K> {
+    fn clone(&self) -> Self {
+        Self {
+            venue: Arc::clone(&self.venue),
+            orders: self.orders.clone(),
+            state: Arc::clone(&self.state),
+            clock: Arc::clone(&self.clock),
+            feed_timeout: self.feed_timeout,
+            action: self.action,
+            #[cfg(feature = "exchange-signing")]
+            acks: self.acks.clone(),
+            checking: Arc::clone(&self.checking),
+        }
+    }
+}
+
+impl<K> std::fmt::Debug for Watchdog<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("Watchdog")
+            .field("feed_timeout", &self.feed_timeout)
+            .field("action", &self.action)
+            .field("tripped", &state.tripped)
+            .field("trips", &state.trips)
+            .finish()
+    }
+}
+
+impl<K> Watchdog<K> {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<K: KillSwitch> Watchdog<K> {
+    /// Guard the open orders of `orders` through `venue`
+    ///
+    /// Defaults to [`TripAction::CancelAll`] after [`DEFAULT_FEED_TIMEOUT`]
+    /// of silence.
+    pub fn new(venue: K, orders: OrderManager) -> Self {
+        let clock = clock::system();
+        Self {
+            venue: Arc::new(venue),
+            orders,
+            state: Arc::new(Mutex::new(State {
+                last_message: clock.now_ms(),
+                disconnected_at: None,
+                tripped: false,
+                trips: 0,
+            })),
+            clock,
+            feed_timeout: DEFAULT_FEED_TIMEOUT,
+            action: TripAction::CancelAll,
+            #[cfg(feature = "exchange-signing")]
+            acks: None,
+            checking: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Trip after `timeout` without a message or with the websocket down
+    pub fn with_feed_timeout(mut self, timeout: Duration) -> Self {
+        self.feed_timeout = timeout;
+        self
+    }
+
+    /// Also trip once an order of `tracker` waits `timeout` for its
+    /// acknowledgement
+    #[cfg(feature = "exchange-signing")]
+    pub fn with_ack_timeout(
+        mut self,
+        tracker: Arc<OrderLatencyTracker>,
+        timeout: Duration,
+    ) -> Self {
+        self.acks = Some((tracker, timeout));
+        self
+    }
+
+    pub fn with_action(mut self, action: TripAction) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Measure feed silence and schedule cancels on `clock`
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.lock().last_message = clock.now_ms();
+        self.clock = clock;
+        self
+    }
+
+    /// Note that the feed is alive
+    ///
+    /// Call from the handlers of the subscriptions the strategy relies on.
+    pub fn record_message(&self) {
+        self.lock().last_message = self.clock.now_ms();
+    }
+
+    /// Follow websocket connectivity
+    ///
+    /// Feed every event from
+    /// [`WebSocketClient::next_event`](crate::stream::WebSocketClient::next_event);
+    /// data and heartbeats count as messages.
+    #[cfg(feature = "ws")]
+    pub fn handle_ws_event(&self, event: &WebSocketEvent) {
+        let now = self.clock.now_ms();
+        let mut state = self.lock();
+        match event {
+            WebSocketEvent::Disconnected | WebSocketEvent::Reconnecting(_) => {
+                state.disconnected_at.get_or_insert(now);
+            }
+            WebSocketEvent::Connected => {
+                state.disconnected_at = None;
+                state.last_message = now;
+            }
+            WebSocketEvent::Data(_) | WebSocketEvent::Raw(_) | WebSocketEvent::Heartbeat => {
+                state.last_message = now;
+            }
+            WebSocketEvent::Error(_) => {}
+        }
+    }
+
+    /// Why the process is blind, or `None` while it can see
+    pub fn blind_reason(&self) -> Option<BlindReason> {
+        let now = self.clock.now_ms();
+        let elapsed = |since: u64| Duration::from_millis(now.saturating_sub(since));
+        {
+            let state = self.lock();
+            if let Some(down) = state.disconnected_at.map(elapsed) {
+                if down >= self.feed_timeout {
+                    return Some(BlindReason::Disconnected(down));
+                }
+            }
+            let silent = elapsed(state.last_message);
+            if silent >= self.feed_timeout {
+                return Some(BlindReason::FeedSilent(silent));
+            }
+        }
+        #[cfg(feature = "exchange-signing")]
+        if let Some((tracker, timeout)) = &self.acks {
+            if let Some(waiting) = tracker.oldest_pending() {
+                if waiting >= *timeout {
+                    return Some(BlindReason::AckOverdue(waiting));
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether the watchdog has acted and the process is still blind
+    pub fn is_tripped(&self) -> bool {
+        self.lock().tripped
+    }
+
+    /// Number of times the watchdog has tripped
+    pub fn trips(&self) -> u64 {
+        self.lock().trips
+    }
+
+    /// Trip if blind, or stand down if recovered
+    ///
+    /// Returns the reason while blind. On `Err` the protective action failed
+    /// and is retried by the next check.
+    pub async fn check(&self) -> Result<Option<BlindReason>, HyperliquidError> {
+        let _checking = self.checking.lock().await;
+        let reason = self.blind_reason();
+        let tripped = self.is_tripped();
+        match reason {
+            Some(reason) if !tripped => {
+                warn!("Watchdog tripped: {}", reason);
+                self.trip().await?;
+                crate::telemetry::counter!("hyperliquid_watchdog_trips_total", "reason" => reason.label())
+                    .increment(1);
+                let mut state = self.lock();
+                state.tripped = true;
+                state.trips += 1;
+            }
+            None if tripped => {
+                if let TripAction::ScheduleCancel(_) = self.action {
+                    self.venue.schedule_cancel(None).await?;
+                }
+                info!("Watchdog recovered");
+                self.lock().tripped = false;
+            }
+            _ => {}
+        }
+        Ok(reason)
+    }
+
+    async fn trip(&self) -> Result<(), HyperliquidError> {
+        match self.action {
+            TripAction::CancelAll => {
+                let orders = self.orders.open_orders();
+                if orders.is_empty() {
+                    return Ok(());
+                }
+                self.venue.cancel_orders(&orders).await
+            }
+            TripAction::ScheduleCancel(delay) => {
+                let delay = delay.max(MIN_SCHEDULE_CANCEL_DELAY);
+                let time = self.clock.now_ms() + delay.as_millis() as u64;
+                self.venue.schedule_cancel(Some(time)).await
+            }
+        }
+    }
+
+    /// Check every `period` in the background until the handle is aborted
+    pub fn start(&self, period: Duration) -> JoinHandle<()> {
+        let watchdog = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = watchdog.check().await {
+                    warn!("Watchdog action failed: {}", e);
+                }
+            }
+        })
+    }
+}
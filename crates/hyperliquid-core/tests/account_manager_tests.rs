@@ -0,0 +1,150 @@
+//! Tests for managing several accounts in one process
+
+use hyperliquid_core::accounts::{AccountKind, AccountManager, AccountUsage};
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::{
+    ExchangeClient, ExchangeClientConfig, HttpClient, HttpClientConfig, HyperliquidError,
+};
+use mockito::Matcher;
+use serde_json::json;
+
+const VAULT: &str = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+
+fn cancel() -> serde_json::Value {
+    json!({"type": "cancel", "cancels": [{"a": 0, "o": 1}]})
+}
+
+struct Setup {
+    manager: AccountManager,
+    master: ExchangeClientConfig,
+    url: String,
+}
+
+fn setup(url: String) -> Setup {
+    let master = ExchangeClientConfig::builder()
+        .base_url(url.clone())
+        .wallet(Wallet::generate_testnet().unwrap())
+        .build()
+        .unwrap();
+    let http = HttpClient::new(url.clone(), HttpClientConfig::default()).unwrap();
+    let manager = AccountManager::new(http, ExchangeClient::new(master.clone()));
+    Setup {
+        manager,
+        master,
+        url,
+    }
+}
+
+#[test]
+fn test_accounts_are_validated_and_listed() {
+    let Setup {
+        manager,
+        master,
+        url,
+    } = setup("http://127.0.0.1:3001".to_string());
+    let main = manager
+        .add("main", AccountKind::Master, master.clone())
+        .unwrap();
+    assert_eq!(main.user(), format!("{:?}", master.account));
+    assert!(main.vault_address().is_none());
+
+    let vault = ExchangeClientConfig::builder()
+        .base_url(url.clone())
+        .wallet(master.wallet().unwrap().clone())
+        .vault_address(VAULT.parse().unwrap())
+        .build()
+        .unwrap();
+    let fund = manager.add("fund", AccountKind::Vault, vault).unwrap();
+    assert_eq!(fund.user(), VAULT);
+    assert_eq!(fund.vault_address(), Some(VAULT));
+
+    let agent = ExchangeClientConfig::builder()
+        .base_url(url.clone())
+        .account(master.account)
+        .agent(Wallet::generate_testnet().unwrap())
+        .build()
+        .unwrap();
+    // An agent is not the account's own key
+    assert!(matches!(
+        manager.add("bot", AccountKind::Master, agent.clone()),
+        Err(HyperliquidError::Config(_))
+    ));
+    let bot = manager.add("bot", AccountKind::Agent, agent).unwrap();
+    assert_eq!(bot.user(), main.user());
+
+    // Duplicate labels, missing vaults and other endpoints are refused
+    assert!(manager
+        .add("main", AccountKind::Master, master.clone())
+        .is_err());
+    assert!(manager
+        .add("sub", AccountKind::SubAccount, master.clone())
+        .is_err());
+    let mut elsewhere = master;
+    elsewhere.base_url = "http://127.0.0.1:3002".to_string();
+    assert!(manager
+        .add("other", AccountKind::Master, elsewhere)
+        .is_err());
+
+    assert_eq!(manager.labels(), vec!["bot", "fund", "main"]);
+    assert!(manager.remove("bot").is_some());
+    assert!(manager.get("bot").is_none());
+}
+
+#[tokio::test]
+async fn test_actions_are_routed_and_accounted_per_account() {
+    let mut server = mockito::Server::new_async().await;
+    let vault_order = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({"vaultAddress": VAULT})))
+        .with_body(json!({"status": "ok", "response": {"type": "cancel"}}).to_string())
+        .expect(2)
+        .create_async()
+        .await;
+    let main_order = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({"vaultAddress": null})))
+        .with_body(json!({"status": "ok", "response": {"type": "cancel"}}).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let Setup {
+        manager,
+        master,
+        url,
+    } = setup(server.url());
+    let manager = manager.with_account_limit(600);
+    manager
+        .add("main", AccountKind::Master, master.clone())
+        .unwrap();
+    let vault = ExchangeClientConfig::builder()
+        .base_url(url)
+        .wallet(master.wallet().unwrap().clone())
+        .vault_address(VAULT.parse().unwrap())
+        .build()
+        .unwrap();
+    manager.add("fund", AccountKind::Vault, vault).unwrap();
+
+    manager.post_action("fund", cancel()).await.unwrap();
+    manager.post_action("fund", cancel()).await.unwrap();
+    manager.post_action("main", cancel()).await.unwrap();
+    vault_order.assert_async().await;
+    main_order.assert_async().await;
+
+    let usage = manager.usage();
+    assert_eq!(
+        usage["fund"],
+        AccountUsage {
+            actions: 2,
+            weight: 2,
+            errors: 0,
+            rate_limited: 0,
+        }
+    );
+    assert_eq!(usage["main"].actions, 1);
+
+    assert!(matches!(
+        manager.post_action("missing", cancel()).await,
+        Err(HyperliquidError::Config(_))
+    ));
+}
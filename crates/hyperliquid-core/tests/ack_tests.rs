@@ -0,0 +1,91 @@
+//! Tests for waiting on order acknowledgements
+
+use std::time::Duration;
+
+use hyperliquid_core::ack::{AckSource, AckWaiter};
+use hyperliquid_core::oms::OrderState;
+use hyperliquid_core::{HttpClient, HttpClientConfig, HyperliquidError};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const USER: &str = "0x0000000000000000000000000000000000000001";
+const CLOID: &str = "0x0000000000000000000000000000002a";
+
+fn update(oid: u64, cloid: &str, status: &str) -> Value {
+    json!({
+        "order": {"coin": "BTC", "side": "B", "limitPx": "60000", "sz": "0.01", "oid": oid,
+                  "timestamp": 1_700_000_000_000u64, "origSz": "0.01", "cloid": cloid},
+        "status": status,
+        "statusTimestamp": 1_700_000_000_100u64
+    })
+}
+
+async fn waiter(server: &mockito::Server, poll_interval: Duration) -> AckWaiter {
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    AckWaiter::new(client, USER).with_poll_interval(poll_interval)
+}
+
+#[tokio::test]
+async fn test_stream_update_resolves_the_wait() {
+    let mut server = mockito::Server::new_async().await;
+    let polls = server.mock("POST", "/info").expect(0).create_async().await;
+    let acks = waiter(&server, Duration::from_secs(60)).await;
+
+    let feed = acks.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // Other orders' updates are skipped
+        feed.handle_order_updates(&json!([update(1, "0x01", "open")]));
+        feed.handle_order_updates(&json!([update(2, CLOID, "perpMarginRejected")]));
+    });
+
+    let ack = acks
+        .wait_for_order_ack(CLOID, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(ack.oid, 2);
+    assert_eq!(ack.state, OrderState::Rejected);
+    assert_eq!(ack.status, "perpMarginRejected");
+    assert_eq!(ack.source, AckSource::Stream);
+    assert!(ack.is_terminal());
+    polls.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_falls_back_to_polling() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "orderStatus", "user": USER, "oid": CLOID}),
+        ))
+        .with_body(json!({"status": "order", "order": update(7, CLOID, "open")}).to_string())
+        .create_async()
+        .await;
+    let acks = waiter(&server, Duration::from_millis(10)).await;
+
+    let ack = acks
+        .wait_for_order_ack(CLOID, Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(ack.oid, 7);
+    assert_eq!(ack.state, OrderState::Acked);
+    assert_eq!(ack.source, AckSource::Poll);
+    assert!(!ack.is_terminal());
+}
+
+#[tokio::test]
+async fn test_times_out_while_the_order_is_unknown() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .with_body(json!({"status": "unknownOid"}).to_string())
+        .create_async()
+        .await;
+    let acks = waiter(&server, Duration::from_millis(10)).await;
+
+    let result = acks
+        .wait_for_order_ack(CLOID, Duration::from_millis(50))
+        .await;
+    assert!(matches!(result, Err(HyperliquidError::Timeout(_))));
+}
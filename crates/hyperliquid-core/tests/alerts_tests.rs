@@ -0,0 +1,179 @@
+//! Tests for the alert hooks
+
+use std::time::Duration;
+
+use hyperliquid_core::alerts::{Alert, AlertKind, AlertManager, FnHook, Severity};
+use hyperliquid_core::margin::{MarginMode, MarginReport, PositionRisk};
+use hyperliquid_core::stream::WebSocketEvent;
+use hyperliquid_core::types::{Notification, Subscription, WsMsg};
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+
+fn manager() -> (AlertManager, mpsc::UnboundedReceiver<Alert>) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    let manager = AlertManager::new().with_hook(FnHook::new("test", move |alert: Alert| {
+        let tx = tx.clone();
+        async move {
+            let _ = tx.send(alert);
+            Ok(())
+        }
+    }));
+    (manager, rx)
+}
+
+async fn next(rx: &mut mpsc::UnboundedReceiver<Alert>) -> Option<Alert> {
+    timeout(Duration::from_millis(200), rx.recv())
+        .await
+        .ok()
+        .flatten()
+}
+
+fn report(distance: f64) -> MarginReport {
+    MarginReport {
+        cross_account_value: 10_000.0,
+        cross_maintenance_margin: 1_000.0,
+        cross_margin_ratio: Some(0.1),
+        positions: vec![PositionRisk {
+            coin: "BTC".to_string(),
+            szi: 1.0,
+            mode: MarginMode::Cross,
+            entry_px: 65_000.0,
+            mark_px: 65_000.0,
+            notional: 65_000.0,
+            maintenance_margin: 1_000.0,
+            liquidation_px: Some(65_000.0 * (1.0 - distance)),
+            distance_to_liquidation: Some(distance),
+        }],
+    }
+}
+
+#[tokio::test]
+async fn test_liquidation_risk_rearms() {
+    let (manager, mut rx) = manager();
+    let manager = manager
+        .with_liquidation_threshold(0.1)
+        .with_cooldown(Duration::ZERO);
+
+    manager.check_margin(&report(0.2));
+    assert!(next(&mut rx).await.is_none());
+
+    manager.check_margin(&report(0.04));
+    let alert = next(&mut rx).await.unwrap();
+    assert_eq!(alert.severity, Severity::Critical);
+    assert!(matches!(alert.kind, AlertKind::LiquidationRisk { ref coin, .. } if coin == "BTC"));
+
+    // Still at risk: no repeat until the position recovers past the re-arm level
+    manager.check_margin(&report(0.08));
+    manager.check_margin(&report(0.12));
+    assert!(next(&mut rx).await.is_none());
+    manager.check_margin(&report(0.2));
+    manager.check_margin(&report(0.09));
+    assert_eq!(next(&mut rx).await.unwrap().severity, Severity::Warning);
+}
+
+#[tokio::test]
+async fn test_disconnect_alerts() {
+    let (manager, mut rx) = manager();
+    let manager = manager.with_disconnect_threshold(Duration::from_millis(20));
+
+    manager.handle_ws_event(&WebSocketEvent::Disconnected);
+    manager.check_connection();
+    assert!(next(&mut rx).await.is_none());
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    manager.check_connection();
+    manager.check_connection();
+    let alert = next(&mut rx).await.unwrap();
+    assert!(matches!(alert.kind, AlertKind::WsDisconnected { .. }));
+    assert!(next(&mut rx).await.is_none());
+
+    manager.handle_ws_event(&WebSocketEvent::Connected);
+    let alert = next(&mut rx).await.unwrap();
+    assert!(matches!(alert.kind, AlertKind::WsReconnected { .. }));
+}
+
+#[tokio::test]
+async fn test_severity_and_cooldown_filters() {
+    let (manager, _rx) = manager();
+    let manager = manager.with_min_severity(Severity::Warning);
+    let breaker = || {
+        Alert::new(
+            Severity::Critical,
+            AlertKind::CircuitBreakerOpen {
+                name: "orders".to_string(),
+                reason: "5 rejects in 10s".to_string(),
+            },
+            "Circuit breaker orders opened",
+        )
+    };
+
+    let info = Alert::new(
+        Severity::Info,
+        AlertKind::Custom {
+            name: "note".to_string(),
+        },
+        "hello",
+    );
+    assert_eq!(manager.send(info).await, 0);
+    assert_eq!(manager.send(breaker()).await, 1);
+    assert_eq!(manager.send(breaker()).await, 0);
+
+    let json = serde_json::to_value(breaker()).unwrap();
+    assert_eq!(json["type"], "circuitBreakerOpen");
+    assert_eq!(json["severity"], "critical");
+}
+
+#[test]
+fn test_notification_channel_parsing() {
+    let msg: WsMsg = serde_json::from_value(json!({
+        "type": "notification",
+        "data": {"notification": "Your BTC position is close to liquidation"}
+    }))
+    .unwrap();
+    let WsMsg::NotificationMsg(msg) = msg else {
+        panic!("notification parsed as another message");
+    };
+    let notification = Notification::from(msg);
+    assert!(notification.is_liquidation_warning());
+    assert_eq!(
+        notification.message(),
+        "Your BTC position is close to liquidation"
+    );
+
+    assert!(matches!(
+        Notification::parse("Scheduled network upgrade at 12:00 UTC"),
+        Notification::SystemNotice { .. }
+    ));
+    assert!(matches!(
+        Notification::parse("Welcome"),
+        Notification::Other { .. }
+    ));
+
+    let subscription: Subscription = serde_json::from_value(
+        json!({"type": "notification", "user": "0x0000000000000000000000000000000000000001"}),
+    )
+    .unwrap();
+    assert!(matches!(subscription, Subscription::Notification { .. }));
+}
+
+#[tokio::test]
+async fn test_notification_alert_severity() {
+    let (manager, mut rx) = manager();
+
+    manager.handle_notification(&Notification::parse("Account was liquidated"));
+    let alert = next(&mut rx).await.unwrap();
+    assert_eq!(alert.severity, Severity::Critical);
+    assert_eq!(alert.message, "Account was liquidated");
+    assert!(matches!(
+        alert.kind,
+        AlertKind::Notification { ref notification } if notification.is_liquidation_warning()
+    ));
+
+    manager.handle_notification(&Notification::parse("Exchange maintenance tonight"));
+    assert_eq!(next(&mut rx).await.unwrap().severity, Severity::Warning);
+
+    // Same text within the cooldown
+    manager.handle_notification(&Notification::parse("Exchange maintenance tonight"));
+    assert!(next(&mut rx).await.is_none());
+}
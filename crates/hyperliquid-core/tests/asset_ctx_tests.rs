@@ -0,0 +1,94 @@
+//! Tests for typed activeAssetCtx payloads
+
+use std::str::FromStr;
+use std::time::Instant;
+
+use hyperliquid_core::stream::{MessageRouter, WebSocketResponse};
+use hyperliquid_core::types::{AssetCtxUpdate, Subscription};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn test_parse_perp_ctx() {
+    let data = json!({"coin": "BTC", "ctx": {
+        "dayNtlVlm": "1169046.29406", "prevDayPx": "59000.0", "markPx": "60001.5",
+        "midPx": "60000.5", "funding": "0.0000125", "openInterest": "100.25",
+        "oraclePx": "60000.0", "premium": "0.00031774", "impactPxs": ["60000.0", "60001.0"],
+        "dayBaseVlm": "16.6"
+    }});
+    let update = AssetCtxUpdate::parse("activeAssetCtx", &data)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(update.coin(), "BTC");
+    assert!(!update.is_spot());
+    assert_eq!(update.mark_px(), dec("60001.5"));
+    assert_eq!(update.mid_px(), Some(dec("60000.5")));
+    assert_eq!(update.oracle_px(), Some(dec("60000.0")));
+    assert_eq!(update.funding(), Some(dec("0.0000125")));
+    let AssetCtxUpdate::Perp(msg) = update else {
+        panic!("expected a perp context");
+    };
+    assert_eq!(msg.ctx.open_interest, dec("100.25"));
+    assert_eq!(
+        msg.ctx.impact_pxs,
+        Some(vec![dec("60000.0"), dec("60001.0")])
+    );
+}
+
+#[test]
+fn test_parse_spot_ctx() {
+    let data = json!({"coin": "@107", "ctx": {
+        "dayNtlVlm": "8906.0", "prevDayPx": "0.22916", "markPx": "0.22923",
+        "midPx": null, "circulatingSupply": "598274922.83822763", "coin": "@107",
+        "totalSupply": "999999999.0", "dayBaseVlm": "38879.0"
+    }});
+    let update = AssetCtxUpdate::parse("activeSpotAssetCtx", &data)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(update.coin(), "@107");
+    assert!(update.is_spot());
+    assert_eq!(update.mark_px(), dec("0.22923"));
+    assert_eq!(update.mid_px(), None);
+    assert_eq!(update.oracle_px(), None);
+    assert_eq!(update.funding(), None);
+    let AssetCtxUpdate::Spot(msg) = update else {
+        panic!("expected a spot context");
+    };
+    assert_eq!(msg.ctx.circulating_supply, dec("598274922.83822763"));
+}
+
+#[test]
+fn test_parse_other_and_malformed() {
+    assert_eq!(
+        AssetCtxUpdate::parse("allMids", &json!({"mids": {}})).unwrap(),
+        None
+    );
+    assert!(AssetCtxUpdate::parse("activeAssetCtx", &json!({"coin": "BTC"})).is_err());
+    assert!(AssetCtxUpdate::parse(
+        "activeAssetCtx",
+        &json!({"coin": "BTC", "ctx": {"markPx": "not a number"}})
+    )
+    .is_err());
+}
+
+#[test]
+fn test_spot_channel_routes_to_asset_ctx_subscription() {
+    let response = WebSocketResponse {
+        channel: "activeSpotAssetCtx.@107".to_string(),
+        data: json!({}),
+        time: None,
+        received_at: Instant::now(),
+    };
+    assert_eq!(
+        MessageRouter::channel_to_subscription(&response),
+        Some(Subscription::ActiveAssetCtx {
+            coin: "@107".to_string()
+        })
+    );
+}
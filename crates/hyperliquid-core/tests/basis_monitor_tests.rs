@@ -0,0 +1,57 @@
+//! Tests for the spot/perp basis monitor
+
+use hyperliquid_core::analytics::{BasisMonitor, BasisPair};
+use serde_json::json;
+
+#[test]
+fn test_joins_spot_and_perp() {
+    let monitor = BasisMonitor::new().with_pair(BasisPair::new("HYPE", "@107"));
+    let mut updates = monitor.updates();
+
+    monitor.handle_all_mids(&json!({"mids": {"@107": "40.0", "BTC": "60000.0"}}), 1_000);
+    assert!(updates.try_recv().is_err());
+
+    monitor.handle_active_asset_ctx(
+        &json!({"coin": "HYPE", "ctx": {"markPx": "40.2", "funding": "0.0000125"}}),
+        1_500,
+    );
+    let update = updates.try_recv().unwrap();
+    assert_eq!(update.pair, "HYPE:@107");
+    assert!((update.basis - 0.2).abs() < 1e-9);
+    assert!((update.premium_bps() - 50.0).abs() < 1e-6);
+    assert_eq!(update.funding, Some(0.0000125));
+    assert_eq!(update.time, 1_500);
+}
+
+#[test]
+fn test_threshold_and_skew_are_per_pair() {
+    let monitor = BasisMonitor::new()
+        .with_pair(BasisPair::new("HYPE", "@107").with_min_change_bps(10.0))
+        .with_pair(
+            BasisPair::new("PURR", "PURR/USDC")
+                .with_name("purr")
+                .with_max_skew_ms(100),
+        );
+    let mut updates = monitor.updates();
+
+    monitor.update_spot_mid("@107", 100.0, 0);
+    monitor.update_perp_mark("HYPE", 100.0, None, 0);
+    assert_eq!(updates.try_recv().unwrap().premium, 0.0);
+
+    // 5 bps move is below the HYPE pair's threshold
+    monitor.update_perp_mark("HYPE", 100.05, None, 10);
+    assert!(updates.try_recv().is_err());
+    monitor.update_perp_mark("HYPE", 100.2, None, 20);
+    assert!((updates.try_recv().unwrap().premium_bps() - 20.0).abs() < 1e-6);
+
+    // Prices too far apart in time are not joined
+    monitor.update_spot_mid("PURR/USDC", 0.2, 0);
+    monitor.update_perp_mark("PURR", 0.21, None, 500);
+    assert!(updates.try_recv().is_err());
+    assert!(monitor.basis("purr").is_none());
+    monitor.update_spot_mid("PURR/USDC", 0.2, 450);
+    assert_eq!(updates.try_recv().unwrap().pair, "purr");
+
+    assert!(monitor.remove_pair("purr"));
+    assert_eq!(monitor.pairs().len(), 1);
+}
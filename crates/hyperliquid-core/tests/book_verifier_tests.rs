@@ -0,0 +1,135 @@
+//! Tests for local L2 books and their verification against REST snapshots
+
+use std::time::Duration;
+
+use hyperliquid_core::stream::{BookVerifier, LocalBook};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+fn book(time: u64, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> Value {
+    let side = |levels: &[(&str, &str)]| -> Value {
+        levels
+            .iter()
+            .map(|(px, sz)| json!({"px": px, "sz": sz, "n": 1}))
+            .collect()
+    };
+    json!({"coin": "ETH", "time": time, "levels": [side(bids), side(asks)]})
+}
+
+fn live() -> Value {
+    book(
+        100,
+        &[("3000.0", "1.0"), ("2999.0", "2.0"), ("2998.0", "3.0")],
+        &[("3001.0", "1.0"), ("3002.0", "2.0"), ("3003.0", "3.0")],
+    )
+}
+
+#[test]
+fn test_apply_ignores_other_coins_and_older_payloads() {
+    let local = LocalBook::new("ETH");
+    assert!(!local.is_active(Duration::from_secs(60)));
+    assert!(local.apply(&live()));
+    assert_eq!(local.time(), 100);
+    assert_eq!(local.bids()[0].px, 3000.0);
+    assert_eq!(local.asks()[2].sz, 3.0);
+    assert!(local.is_active(Duration::from_secs(60)));
+
+    assert!(!local.apply(&book(90, &[("1.0", "1.0")], &[])));
+    let mut other = live();
+    other["coin"] = json!("BTC");
+    assert!(!local.apply(&other));
+    assert_eq!(local.bids().len(), 3);
+}
+
+#[test]
+fn test_compare_only_covers_the_shared_price_range() {
+    let local = LocalBook::new("ETH");
+    local.apply(&live());
+
+    assert_eq!(local.compare(&live()).unwrap().mismatched_levels, 0);
+
+    // One level deeper than the local book on both sides: not a mismatch
+    let deeper = book(
+        101,
+        &[
+            ("3000.0", "1.0"),
+            ("2999.0", "2.0"),
+            ("2998.0", "3.0"),
+            ("2997.0", "9.0"),
+        ],
+        &[
+            ("3001.0", "1.0"),
+            ("3002.0", "2.0"),
+            ("3003.0", "3.0"),
+            ("3004.0", "9.0"),
+        ],
+    );
+    let divergence = local.compare(&deeper).unwrap();
+    assert_eq!(divergence.mismatched_levels, 0);
+    assert_eq!(divergence.divergence, 0.0);
+    assert_eq!(divergence.snapshot_time, 101);
+
+    // A changed size and a level the local book lacks
+    let drifted = book(
+        102,
+        &[
+            ("3000.0", "1.0"),
+            ("2999.5", "1.0"),
+            ("2999.0", "2.0"),
+            ("2998.0", "3.0"),
+        ],
+        &[("3001.0", "4.0"), ("3002.0", "2.0"), ("3003.0", "3.0")],
+    );
+    let divergence = local.compare(&drifted).unwrap();
+    assert_eq!(divergence.mismatched_levels, 2);
+    // (1 + 3) / (7 + 9)
+    assert!((divergence.divergence - 0.25).abs() < 1e-9);
+    assert!(!divergence.resynced);
+}
+
+#[tokio::test]
+async fn test_verifier_resyncs_books_beyond_tolerance() {
+    let mut server = mockito::Server::new_async().await;
+    let snapshot = book(
+        200,
+        &[("3000.0", "5.0"), ("2999.0", "2.0"), ("2998.0", "3.0")],
+        &[("3001.0", "1.0"), ("3002.0", "2.0"), ("3003.0", "3.0")],
+    );
+    let mock = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "l2Book", "coin": "ETH"}),
+        ))
+        .with_body(snapshot.to_string())
+        .expect(2)
+        .create_async()
+        .await;
+
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let local = LocalBook::new("ETH");
+    local.apply(&live());
+
+    // 4 / 16 is within a 50% tolerance
+    let lenient = BookVerifier::new(client.clone()).with_tolerance(0.5);
+    let divergence = lenient.verify(&local).await.unwrap();
+    assert!(!divergence.resynced);
+    assert_eq!(local.bids()[0].sz, 1.0);
+
+    let verifier = BookVerifier::new(client).with_tolerance(0.1);
+    verifier.track(local.clone());
+    // Books without updates are not checked
+    verifier.track(LocalBook::new("BTC"));
+    let results = verifier.verify_all().await;
+    mock.assert_async().await;
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].resynced);
+    assert_eq!(results[0].mismatched_levels, 1);
+    assert_eq!(local.bids()[0].sz, 5.0);
+    assert_eq!(local.time(), 200);
+    assert_eq!(local.compare(&snapshot).unwrap().mismatched_levels, 0);
+
+    verifier.untrack("ETH");
+    assert!(verifier.verify_all().await.is_empty());
+}
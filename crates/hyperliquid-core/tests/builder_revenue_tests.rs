@@ -0,0 +1,129 @@
+//! Tests for the builder fee revenue report
+
+use hyperliquid_core::analytics::BuilderRevenue;
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::{Matcher, Server};
+use serde_json::{json, Value};
+
+const BUILDER: &str = "0xB0B0000000000000000000000000000000000001";
+/// 2024-01-01 00:00:00 UTC
+const DAY: u64 = 1_704_067_200_000;
+const HOUR: u64 = 3_600_000;
+
+fn fill(coin: &str, px: &str, sz: &str, builder_fee: Option<&str>, time: u64, tid: u64) -> Value {
+    let mut fill = json!({
+        "coin": coin, "px": px, "sz": sz, "side": "B", "time": time, "oid": tid + 1000,
+        "tid": tid, "fee": "0.1", "crossed": true, "closedPnl": "0.0", "hash": "0x0"
+    });
+    if let Some(fee) = builder_fee {
+        fill["builderFee"] = json!(fee);
+    }
+    fill
+}
+
+#[test]
+fn test_aggregates_by_day_and_coin() {
+    let mut revenue = BuilderRevenue::new(BUILDER);
+    let counted = revenue.ingest_user_fills(&json!({"user": "0xAAA", "fills": [
+        fill("BTC", "60000.0", "0.1", Some("0.6"), DAY + HOUR, 1),
+        fill("BTC", "60000.0", "0.2", Some("1.2"), DAY + 2 * HOUR, 2),
+        fill("ETH", "3000.0", "1.0", Some("0.3"), DAY + 3 * HOUR, 3),
+        // No builder fee
+        fill("ETH", "3000.0", "1.0", None, DAY + 4 * HOUR, 4),
+        fill("BTC", "61000.0", "0.1", Some("0.61"), DAY + 25 * HOUR, 5),
+    ]}));
+    assert_eq!(counted, 4);
+    // Replayed fills are not counted twice
+    revenue.ingest_user_fills(&json!({"user": "0xBBB", "fills": [
+        fill("BTC", "60000.0", "0.1", Some("0.6"), DAY + HOUR, 1),
+        fill("BTC", "60000.0", "0.1", Some("0.6"), DAY + 5 * HOUR, 6),
+    ]}));
+
+    let rows = revenue.rows();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0].day, "2024-01-01");
+    assert_eq!(rows[0].coin, "BTC");
+    assert_eq!(rows[0].fills, 3);
+    assert_eq!(rows[0].users, 2);
+    assert!((rows[0].notional - 24_000.0).abs() < 1e-6);
+    assert!((rows[0].builder_fee - 2.4).abs() < 1e-9);
+    assert_eq!(
+        (rows[1].day.as_str(), rows[1].coin.as_str()),
+        ("2024-01-01", "ETH")
+    );
+    assert_eq!(
+        (rows[2].day.as_str(), rows[2].coin.as_str()),
+        ("2024-01-02", "BTC")
+    );
+
+    assert!((revenue.total_fee() - 3.31).abs() < 1e-9);
+    assert!((revenue.by_coin()["BTC"] - 3.01).abs() < 1e-9);
+    assert!((revenue.by_day()["2024-01-01"] - 2.7).abs() < 1e-9);
+
+    let mut csv = Vec::new();
+    revenue.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "day,coin,fills,users,notional,builder_fee");
+    assert_eq!(lines[2], "2024-01-01,ETH,1,1,3000,0.3");
+    assert_eq!(lines.len(), 4);
+}
+
+#[test]
+fn test_recorded_lines_and_builder_filter() {
+    let mut named = fill("SOL", "100.0", "10.0", Some("0.5"), DAY, 10);
+    named["builder"] = json!(BUILDER.to_lowercase());
+    let mut other = fill("SOL", "100.0", "10.0", Some("0.5"), DAY, 11);
+    other["builder"] = json!("0x0000000000000000000000000000000000000002");
+    let recorded = json!({"channel": "userFills", "time": DAY, "data": {
+        "user": "0xAAA", "fills": [fill("SOL", "100.0", "1.0", Some("0.05"), DAY, 12)]
+    }});
+    let lines = [
+        recorded.to_string(),
+        json!({"channel": "trades", "data": []}).to_string(),
+        json!(["0xCCC", named]).to_string(),
+        other.to_string(),
+        String::new(),
+    ]
+    .join("\n");
+
+    let mut revenue = BuilderRevenue::new(BUILDER).with_range(DAY..DAY + HOUR);
+    assert_eq!(revenue.ingest_jsonl(lines.as_bytes()).unwrap(), 2);
+    let rows = revenue.rows();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].fills, 2);
+    assert_eq!(rows[0].users, 2);
+    assert!((rows[0].builder_fee - 0.55).abs() < 1e-9);
+
+    // Outside the range
+    assert!(!revenue.ingest_fill(
+        &fill("SOL", "1.0", "1.0", Some("1.0"), DAY + HOUR, 13),
+        None
+    ));
+    assert!(revenue.ingest_jsonl("not json".as_bytes()).is_err());
+}
+
+#[tokio::test]
+async fn test_fetch_user_pages_fills_by_time() {
+    let mut server = Server::new_async().await;
+    let page = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({
+            "type": "userFillsByTime", "user": "0xAAA", "startTime": DAY
+        })))
+        .with_body(
+            json!([
+                fill("BTC", "60000.0", "0.1", Some("0.6"), DAY + HOUR, 1),
+                fill("BTC", "60000.0", "0.1", None, DAY + HOUR, 2),
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+
+    let mut revenue = BuilderRevenue::new(BUILDER).with_range(DAY..DAY + 24 * HOUR);
+    assert_eq!(revenue.fetch_user(&client, "0xAAA").await.unwrap(), 1);
+    page.assert_async().await;
+    assert_eq!(revenue.rows()[0].users, 1);
+}
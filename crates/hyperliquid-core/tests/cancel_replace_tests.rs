@@ -0,0 +1,120 @@
+//! Tests for cancel-and-replace through the exchange client
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{LegStatus, OrderRef, ReplaceMethod};
+use hyperliquid_core::{ExchangeClient, ExchangeClientConfig};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+fn client(url: String) -> ExchangeClient {
+    let account = "0x1234567890abcdef1234567890abcdef12345678"
+        .parse()
+        .unwrap();
+    let mut config = ExchangeClientConfig::testnet(account);
+    config.base_url = url;
+    ExchangeClient::new(config)
+}
+
+fn order(asset: u32) -> Value {
+    json!({"a": asset, "b": true, "p": "60000", "s": "0.1", "r": false, "t": {"limit": {"tif": "Gtc"}}})
+}
+
+#[tokio::test]
+async fn test_same_asset_uses_modify_and_keeps_cloid() {
+    let mut server = mockito::Server::new_async().await;
+    let cloid = "0x0000000000000000000000000000abcd";
+    let modify = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({
+            "action": {"type": "modify", "oid": cloid, "order": {"a": 0, "c": cloid}}
+        })))
+        .with_body(json!({"status": "ok", "response": {"type": "default"}}).to_string())
+        .create_async()
+        .await;
+
+    let wallet = Wallet::generate_testnet().unwrap();
+    let result = client(server.url())
+        .cancel_and_replace(
+            0,
+            OrderRef::Cloid(cloid.to_string()),
+            order(0),
+            &wallet,
+            None,
+        )
+        .await
+        .unwrap();
+    modify.assert_async().await;
+    assert_eq!(result.method, ReplaceMethod::Modify);
+    assert!(result.is_success());
+}
+
+#[tokio::test]
+async fn test_failed_cancel_skips_the_place() {
+    let mut server = mockito::Server::new_async().await;
+    let cancel = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(
+            json!({"action": {"type": "cancel", "cancels": [{"a": 0, "o": 42}]}}),
+        ))
+        .with_body(
+            json!({"status": "ok", "response": {"type": "cancel", "data": {"statuses": [
+                {"error": "Order was never placed, already canceled, or filled."}
+            ]}}})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let place = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({"action": {"type": "order"}})))
+        .expect(0)
+        .create_async()
+        .await;
+
+    let wallet = Wallet::generate_testnet().unwrap();
+    let result = client(server.url())
+        .cancel_and_replace(0, 42u64, order(1), &wallet, None)
+        .await
+        .unwrap();
+    cancel.assert_async().await;
+    place.assert_async().await;
+    assert_eq!(result.method, ReplaceMethod::CancelPlace);
+    assert!(matches!(result.cancel, LegStatus::Failed(ref e) if e.contains("already canceled")));
+    assert_eq!(result.place, LegStatus::Skipped);
+    assert!(!result.left_flat());
+}
+
+#[tokio::test]
+async fn test_rejected_place_leaves_flat() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({"action": {"type": "cancel"}})))
+        .with_body(
+            json!({"status": "ok", "response": {"type": "cancel", "data": {"statuses": ["success"]}}})
+                .to_string(),
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(json!({"action": {"type": "order"}})))
+        .with_body(
+            json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+                {"error": "Insufficient margin to place order."}
+            ]}}})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let wallet = Wallet::generate_testnet().unwrap();
+    let result = client(server.url())
+        .cancel_and_replace(0, 42u64, order(1), &wallet, None)
+        .await
+        .unwrap();
+    assert!(result.cancel.is_done());
+    assert!(matches!(result.place, LegStatus::Failed(_)));
+    assert!(result.left_flat());
+    assert_eq!(result.oid(), None);
+}
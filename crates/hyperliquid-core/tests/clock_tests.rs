@@ -0,0 +1,94 @@
+//! Tests for the clock abstraction and the components reading it
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::clock::{Clock, ManualClock, SystemClock};
+use hyperliquid_core::crypto::nonce::verify_nonce_age_at;
+use hyperliquid_core::crypto::NonceManager;
+use hyperliquid_core::scheduler::{Job, RateLimiter, Scheduler};
+use hyperliquid_core::types::{get_timestamp_ms_at, get_timestamp_seconds_at, TimestampError};
+
+const START: u64 = 1_700_000_000_000;
+
+#[test]
+fn test_manual_clock_is_shared_between_clones() {
+    let clock = ManualClock::new(START);
+    let shared: Arc<dyn Clock> = Arc::new(clock.clone());
+    assert_eq!(shared.now_ms(), START);
+    assert_eq!(shared.now_us(), START * 1_000);
+
+    clock.advance(Duration::from_millis(1_500));
+    assert_eq!(shared.now_ms(), START + 1_500);
+    clock.set(START - 10);
+    assert_eq!(shared.now_ms(), START - 10);
+
+    assert!(SystemClock.now_ms() > START);
+}
+
+#[test]
+fn test_nonces_follow_the_clock() {
+    let clock = ManualClock::new(START);
+    let nonces = NonceManager::new().with_clock(Arc::new(clock.clone()));
+
+    assert_eq!(nonces.next().unwrap(), START);
+    // Same millisecond
+    assert_eq!(nonces.next().unwrap(), START + 1);
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(nonces.next().unwrap(), START + 10);
+
+    // A clock stepping backwards never repeats a nonce
+    clock.set(START - 60_000);
+    assert_eq!(nonces.next().unwrap(), START + 11);
+    assert_eq!(nonces.last(), START + 11);
+}
+
+#[test]
+fn test_nonce_age_window() {
+    let clock = ManualClock::new(START);
+    let nonce = START * 1_000;
+    assert!(verify_nonce_age_at(&clock, nonce, Some(60)));
+
+    clock.advance(Duration::from_secs(60));
+    assert!(verify_nonce_age_at(&clock, nonce, Some(60)));
+    clock.advance(Duration::from_secs(1));
+    assert!(!verify_nonce_age_at(&clock, nonce, Some(60)));
+}
+
+#[test]
+fn test_timestamps_from_clock() {
+    let clock = ManualClock::new(START + 999);
+    assert_eq!(get_timestamp_ms_at(&clock).unwrap(), (START + 999) as i64);
+    assert_eq!(
+        get_timestamp_seconds_at(&clock).unwrap(),
+        (START / 1_000) as i64
+    );
+
+    clock.set(0);
+    assert_eq!(
+        get_timestamp_ms_at(&clock),
+        Err(TimestampError::InvalidRange(0))
+    );
+}
+
+#[tokio::test]
+async fn test_scheduler_stamps_runs_with_clock() {
+    let clock = ManualClock::new(START);
+    let handle = Scheduler::new(RateLimiter::hyperliquid())
+        .with_jitter(0.0)
+        .with_clock(Arc::new(clock.clone()))
+        .with_job(Job::new("state", Duration::from_millis(100), 1, || async {
+            Ok(())
+        }))
+        .start()
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(handle.stats()["state"].last_run, Some(START as i64));
+
+    clock.advance(Duration::from_secs(1));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let stats = &handle.stats()["state"];
+    assert_eq!(stats.runs, 2);
+    assert_eq!(stats.last_run, Some((START + 1_000) as i64));
+}
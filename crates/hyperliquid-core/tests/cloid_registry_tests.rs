@@ -0,0 +1,102 @@
+//! Tests for duplicate order suppression by cloid
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{CloidRegistry, DuplicatePolicy};
+use hyperliquid_core::{ExchangeClient, ExchangeClientConfig, HyperliquidError};
+use serde_json::json;
+
+const CLOID: &str = "0x00000000000000000000000000000001";
+
+fn cloids(cloids: &[&str]) -> Vec<String> {
+    cloids.iter().map(|cloid| cloid.to_string()).collect()
+}
+
+#[test]
+fn test_duplicates_are_rejected_until_released_or_expired() {
+    let registry = CloidRegistry::new(Duration::from_millis(50));
+    assert_eq!(registry.claim(&cloids(&[CLOID])).unwrap(), None);
+    assert!(registry.contains(CLOID));
+    assert!(matches!(
+        registry.claim(&cloids(&[CLOID])),
+        Err(HyperliquidError::Validation(_))
+    ));
+
+    // A failed submission can be retried with the same cloid
+    registry.complete(&cloids(&[CLOID]), None);
+    assert!(registry.is_empty());
+    assert_eq!(registry.claim(&cloids(&[CLOID])).unwrap(), None);
+    registry.complete(&cloids(&[CLOID]), Some(r#"{"status":"ok"}"#));
+
+    std::thread::sleep(Duration::from_millis(60));
+    assert!(!registry.contains(CLOID));
+    assert_eq!(registry.claim(&cloids(&[CLOID])).unwrap(), None);
+
+    // Repeating a cloid within one action is a double fire too
+    assert!(registry.claim(&cloids(&["0x02", "0x02"])).is_err());
+    assert!(!registry.contains("0x02"));
+}
+
+#[test]
+fn test_return_original_replays_the_completed_action() {
+    let registry =
+        CloidRegistry::new(Duration::from_secs(60)).with_policy(DuplicatePolicy::ReturnOriginal);
+    let batch = cloids(&["0x01", "0x02"]);
+    assert_eq!(registry.claim(&batch).unwrap(), None);
+
+    // Still in flight: nothing to return yet
+    assert!(registry.claim(&batch).is_err());
+    registry.complete(&batch, Some(r#"{"status":"ok"}"#));
+    assert_eq!(
+        registry.claim(&batch).unwrap().as_deref(),
+        Some(r#"{"status":"ok"}"#)
+    );
+
+    // Overlapping a different action is not a replay
+    assert!(registry.claim(&cloids(&["0x02", "0x03"])).is_err());
+    assert!(registry.claim(&cloids(&["0x01"])).is_err());
+}
+
+#[tokio::test]
+async fn test_client_sends_an_order_once() {
+    let mut server = mockito::Server::new_async().await;
+    let response = json!({"status": "ok", "response": {"type": "order", "data": {
+        "statuses": [{"resting": {"oid": 7}}]
+    }}});
+    let order = server
+        .mock("POST", "/exchange")
+        .with_body(response.to_string())
+        .expect(1)
+        .create_async()
+        .await;
+
+    let wallet = Wallet::generate_testnet().unwrap();
+    let config = ExchangeClientConfig::builder()
+        .base_url(server.url())
+        .wallet(wallet.clone())
+        .build()
+        .unwrap();
+    let registry = Arc::new(
+        CloidRegistry::new(Duration::from_secs(60)).with_policy(DuplicatePolicy::ReturnOriginal),
+    );
+    let exchange = ExchangeClient::new(config).with_cloid_registry(registry.clone());
+    let action = json!({"type": "order", "grouping": "na", "orders": [{
+        "a": 0, "b": true, "p": "50000", "s": "0.01", "r": false,
+        "t": {"limit": {"tif": "Gtc"}}, "c": CLOID
+    }]});
+
+    let first = exchange
+        .post_signed_action(action.clone(), &wallet, None)
+        .await
+        .unwrap();
+    let second = exchange
+        .post_signed_action(action, &wallet, None)
+        .await
+        .unwrap();
+    order.assert_async().await;
+    assert_eq!(first, response);
+    assert_eq!(second, response);
+    assert!(registry.contains(CLOID));
+}
@@ -0,0 +1,112 @@
+//! Tests for closing positions with reduce-only IOC orders
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{close_order_params, CloseAmount, LegStatus};
+use hyperliquid_core::{ExchangeClient, ExchangeClientConfig, HttpClient, HttpClientConfig};
+use mockito::Matcher;
+use serde_json::json;
+
+#[test]
+fn test_close_is_sided_against_the_position() {
+    let (is_buy, sz, px) = close_order_params(0.5, CloseAmount::All, 5, 60000.0, 50);
+    assert!(!is_buy);
+    assert_eq!(sz, 0.5);
+    assert_eq!(px, 59700.0);
+
+    let (is_buy, sz, px) = close_order_params(-2.0, CloseAmount::Fraction(0.25), 2, 3000.0, 100);
+    assert!(is_buy);
+    assert_eq!(sz, 0.5);
+    assert_eq!(px, 3030.0);
+}
+
+#[test]
+fn test_partial_close_never_leaves_dust() {
+    // 0.333 of 1.0 rounds down to the lot size
+    let (_, sz, _) = close_order_params(1.0, CloseAmount::Fraction(0.333), 2, 100.0, 0);
+    assert_eq!(sz, 0.33);
+
+    // Leaving 0.05 at $100 would be under the minimum order value
+    let (_, sz, _) = close_order_params(1.0, CloseAmount::Size(0.95), 2, 100.0, 0);
+    assert_eq!(sz, 1.0);
+
+    // Float noise from a tracked position is rounded onto the size grid
+    let (_, sz, _) = close_order_params(-0.30000000000000004, CloseAmount::All, 3, 100.0, 0);
+    assert_eq!(sz, 0.3);
+
+    let (_, sz, _) = close_order_params(1.0, CloseAmount::Fraction(0.0), 2, 100.0, 0);
+    assert_eq!(sz, 0.0);
+}
+
+#[tokio::test]
+async fn test_close_position_sends_reduce_only_ioc() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "clearinghouseState"})))
+        .with_body(
+            json!({"assetPositions": [
+                {"type": "oneWay", "position": {"coin": "ETH", "szi": "-1.5", "entryPx": "3000"}}
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+        .with_body(
+            json!({"universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 50, "onlyIsolated": false},
+                {"name": "ETH", "szDecimals": 4, "maxLeverage": 50, "onlyIsolated": false}
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "allMids"})))
+        .with_body(json!({"BTC": "60000", "ETH": "3000"}).to_string())
+        .create_async()
+        .await;
+    let order = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(
+            json!({"action": {"type": "order", "orders": [
+                {"a": 1, "b": true, "s": "1.5", "r": true, "t": {"limit": {"tif": "Ioc"}}}
+            ]}}),
+        ))
+        .with_body(
+            json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+                {"filled": {"totalSz": "1.5", "avgPx": "3001.0", "oid": 9}}
+            ]}}})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let account = "0x1234567890abcdef1234567890abcdef12345678"
+        .parse()
+        .unwrap();
+    let mut config = ExchangeClientConfig::testnet(account);
+    config.base_url = server.url();
+    let exchange = ExchangeClient::new(config);
+    let info = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let wallet = Wallet::generate_testnet().unwrap();
+
+    let result = exchange
+        .close_position(&info, "ETH", CloseAmount::All, 30, &wallet, None)
+        .await
+        .unwrap();
+    order.assert_async().await;
+    assert!(result.is_buy);
+    assert_eq!(result.szi, -1.5);
+    assert_eq!(result.leg, LegStatus::Done);
+    assert_eq!(result.filled_sz(), 1.5);
+
+    let flat = exchange
+        .close_position_with_szi(&info, "BTC", 0.0, CloseAmount::All, 30, &wallet, None)
+        .await
+        .unwrap();
+    assert_eq!(flat.leg, LegStatus::Skipped);
+}
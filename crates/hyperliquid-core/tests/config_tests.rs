@@ -78,6 +78,10 @@ fn test_config_file_load() {
         key_rotation_hours = 12
         strict_mode = true
 
+        [security.cert_pinning]
+        domain = "api.hyperliquid.xyz"
+        pins = ["5f1c7b3e8a9d2c4f6b0e1a3d5c7e9f1b2d4a6c8e0f2b4d6a8c0e2f4a6b8d0c2e"]
+
         [metrics]
         enabled = false
         endpoint = "/debug/metrics"
@@ -348,6 +352,10 @@ fn test_config_toml_types() {
         strict_mode = false
         key_rotation_hours = 24
 
+        [security.cert_pinning]
+        domain = "api.hyperliquid.xyz"
+        pins = ["5f1c7b3e8a9d2c4f6b0e1a3d5c7e9f1b2d4a6c8e0f2b4d6a8c0e2f4a6b8d0c2e"]
+
         [metrics]
         enabled = true
         endpoint = "/metrics"
@@ -406,4 +414,39 @@ fn test_config_toml_types() {
     assert_eq!(config.metrics.namespace, "test");
 
     fs::remove_file("types_config.toml").unwrap();
-}
\ No newline at end of file
+}
+#[test]
+fn test_config_secret_references() {
+    env::set_var("HL_TEST_SECRET_KEY", "0x1111111111111111111111111111111111111111111111111111111111111111");
+    fs::write("secrets_key.txt", "  0x2222  \n").unwrap();
+    fs::write("secrets_config.toml", r#"
+        [security]
+        private_key = "env:HL_TEST_SECRET_KEY"
+
+        [logging]
+        service_name = "file:secrets_key.txt"
+    "#).unwrap();
+
+    let config = Config::load_with_profile("secrets_config.toml", None).unwrap();
+    assert_eq!(
+        config.security.private_key.as_ref().map(|k| k.expose()),
+        Some("0x1111111111111111111111111111111111111111111111111111111111111111")
+    );
+    assert_eq!(config.logging.service_name, "0x2222");
+    assert!(!format!("{:?}", config).contains("0x1111"));
+
+    // A plain resolver leaves references untouched
+    let config = Config::load_with_secrets(
+        "secrets_config.toml",
+        None,
+        &hyperliquid_core::config::SecretsResolver::new(),
+    ).unwrap();
+    assert_eq!(config.logging.service_name, "file:secrets_key.txt");
+
+    env::remove_var("HL_TEST_SECRET_KEY");
+    let err = Config::load_with_profile("secrets_config.toml", None).unwrap_err();
+    assert!(err.to_string().contains("security.private_key"));
+
+    fs::remove_file("secrets_config.toml").unwrap();
+    fs::remove_file("secrets_key.txt").unwrap();
+}
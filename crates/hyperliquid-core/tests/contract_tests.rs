@@ -0,0 +1,235 @@
+//! Contract tests replaying recorded API responses through the typed models
+//!
+//! `tests/fixtures/contract/manifest.json` lists the info requests to record.
+//! Each recording lives in a dated directory next to it, holding one sanitized
+//! response per request and a `known_drift.json` naming the fixtures whose
+//! typed decode currently fails, with the reason.
+//!
+//! With `strict-schema` every response type rejects unknown fields, so a
+//! field added by an exchange upgrade shows up here rather than being
+//! silently dropped. The replay fails when a fixture outside the baseline
+//! stops decoding, and when a baseline entry starts decoding again so the
+//! baseline is kept current.
+//!
+//! Record a new version against mainnet with:
+//!
+//! ```text
+//! HYPERLIQUID_FIXTURE_USER=0x... cargo test -p hyperliquid-core \
+//!     --features strict-schema --test contract_tests -- --ignored record_fixtures
+//! ```
+
+#![cfg(feature = "strict-schema")]
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use hyperliquid_core::{
+    Candle, Fill, FundingHistoryResponse, HttpClient, L2BookSnapshot, Meta, OpenOrder, Trade,
+    UserState,
+};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+const MAINNET_URL: &str = "https://api.hyperliquid.xyz";
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+/// Longest array kept when recording, to keep fixtures reviewable
+const MAX_RECORDED_ITEMS: usize = 5;
+
+type Decoder = fn(&Value) -> Result<(), String>;
+
+fn decode<T: DeserializeOwned>(value: &Value) -> Result<(), String> {
+    serde_json::from_value::<T>(value.clone())
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Typed model for each recorded request
+fn decoder(name: &str) -> Option<Decoder> {
+    let decoder: Decoder = match name {
+        "meta" => decode::<Meta>,
+        "l2Book" => decode::<L2BookSnapshot>,
+        "recentTrades" => decode::<Vec<Trade>>,
+        "candleSnapshot" => decode::<Vec<Candle>>,
+        "fundingHistory" => decode::<FundingHistoryResponse>,
+        "clearinghouseState" => decode::<UserState>,
+        "openOrders" => decode::<Vec<OpenOrder>>,
+        "userFills" => decode::<Vec<Fill>>,
+        _ => return None,
+    };
+    Some(decoder)
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/contract")
+}
+
+fn read_json(path: &Path) -> Value {
+    let text = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", path.display(), e));
+    serde_json::from_str(&text)
+        .unwrap_or_else(|e| panic!("failed to parse {}: {}", path.display(), e))
+}
+
+fn manifest() -> BTreeMap<String, Value> {
+    serde_json::from_value(read_json(&fixtures_dir().join("manifest.json"))).unwrap()
+}
+
+fn versions() -> Vec<PathBuf> {
+    let mut versions: Vec<PathBuf> = fs::read_dir(fixtures_dir())
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    versions.sort();
+    versions
+}
+
+/// Decode every fixture in `version`, returning failures by fixture name
+fn replay(version: &Path) -> BTreeMap<String, String> {
+    let mut failures = BTreeMap::new();
+    for name in manifest().keys() {
+        let path = version.join(format!("{}.json", name));
+        if !path.exists() {
+            continue;
+        }
+        let decoder = decoder(name)
+            .unwrap_or_else(|| panic!("no typed model registered for fixture {}", name));
+        if let Err(e) = decoder(&read_json(&path)) {
+            failures.insert(name.clone(), e);
+        }
+    }
+    failures
+}
+
+/// Replace account addresses and truncate long arrays
+fn sanitize(value: Value) -> Value {
+    match value {
+        Value::String(s)
+            if s.len() == 42
+                && s.starts_with("0x")
+                && s[2..].chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            Value::String(ZERO_ADDRESS.to_string())
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .take(MAX_RECORDED_ITEMS)
+                .map(sanitize)
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key, sanitize(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Fill `$USER`, `$START` and `$END` placeholders in a manifest request
+fn substitute(request: &Value, user: &str, start: i64, end: i64) -> Value {
+    match request {
+        Value::String(s) if s == "$USER" => json!(user),
+        Value::String(s) if s == "$START" => json!(start),
+        Value::String(s) if s == "$END" => json!(end),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), substitute(value, user, start, end)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+#[test]
+fn test_every_fixture_has_a_model() {
+    for name in manifest().keys() {
+        assert!(decoder(name).is_some(), "no typed model for {}", name);
+    }
+    assert!(!versions().is_empty(), "no recorded fixture versions");
+}
+
+#[test]
+fn test_replay_matches_known_drift() {
+    for version in versions() {
+        let failures = replay(&version);
+        let known: BTreeMap<String, String> =
+            serde_json::from_value(read_json(&version.join("known_drift.json"))).unwrap();
+
+        let new_drift: Vec<String> = failures
+            .iter()
+            .filter(|(name, _)| !known.contains_key(*name))
+            .map(|(name, error)| format!("{}: {}", name, error))
+            .collect();
+        assert!(
+            new_drift.is_empty(),
+            "schema drift in {}:\n{}",
+            version.display(),
+            new_drift.join("\n")
+        );
+
+        let fixed: Vec<&String> = known
+            .keys()
+            .filter(|name| !failures.contains_key(*name))
+            .collect();
+        assert!(
+            fixed.is_empty(),
+            "fixtures in {} now decode, remove them from known_drift.json: {:?}",
+            version.display(),
+            fixed
+        );
+    }
+}
+
+#[test]
+fn test_sanitize() {
+    let value = sanitize(json!({
+        "user": "0x31ca8395cf837de08b24da3f660e77761dfb974b",
+        "hash": "0x4b3a1f0c2e6d5a7b8c9d0e1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d",
+        "levels": [[1, 2, 3, 4, 5, 6, 7], []],
+    }));
+    assert_eq!(value["user"], ZERO_ADDRESS);
+    assert_ne!(value["hash"], ZERO_ADDRESS);
+    assert_eq!(
+        value["levels"][0].as_array().unwrap().len(),
+        MAX_RECORDED_ITEMS
+    );
+}
+
+/// Record a new fixture version from mainnet and write its drift baseline
+#[tokio::test]
+#[ignore = "hits the live API"]
+async fn record_fixtures() {
+    let user = std::env::var("HYPERLIQUID_FIXTURE_USER").ok();
+    let now = chrono::Utc::now();
+    let end = now.timestamp_millis();
+    let start = end - 24 * 60 * 60 * 1000;
+    let version = fixtures_dir().join(now.format("%Y-%m-%d").to_string());
+    fs::create_dir_all(&version).unwrap();
+
+    let client = HttpClient::with_default_config(MAINNET_URL).unwrap();
+    for (name, request) in manifest() {
+        let needs_user = request.to_string().contains("$USER");
+        let Some(user) = user.as_deref().or((!needs_user).then_some(ZERO_ADDRESS)) else {
+            eprintln!("skipping {}: set HYPERLIQUID_FIXTURE_USER", name);
+            continue;
+        };
+        let request = substitute(&request, user, start, end);
+        let response: Value = client.post("/info", &request).await.unwrap();
+        let text = serde_json::to_string_pretty(&sanitize(response)).unwrap();
+        fs::write(version.join(format!("{}.json", name)), text + "\n").unwrap();
+    }
+
+    let drift = replay(&version);
+    let text = serde_json::to_string_pretty(&drift).unwrap();
+    fs::write(version.join("known_drift.json"), text + "\n").unwrap();
+    eprintln!(
+        "recorded {} with {} drifting fixtures",
+        version.display(),
+        drift.len()
+    );
+}
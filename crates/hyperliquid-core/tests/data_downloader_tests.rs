@@ -0,0 +1,138 @@
+//! Tests for the S3 archive downloader
+
+#![cfg(feature = "data")]
+
+use std::io::Write;
+
+use chrono::NaiveDate;
+use hyperliquid_core::data::downloader::{convert_l2_book, convert_trades};
+use hyperliquid_core::data::{ArchiveDownloader, ArchiveKind, DateRange};
+use serde_json::Value;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, day).unwrap()
+}
+
+fn lz4(data: &[u8]) -> Vec<u8> {
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn temp_root(name: &str) -> std::path::PathBuf {
+    let root = std::env::temp_dir().join(format!(
+        "hyperliquid-data-{}-{}",
+        name,
+        uuid::Uuid::new_v4()
+    ));
+    std::fs::create_dir_all(&root).unwrap();
+    root
+}
+
+#[test]
+fn test_objects_cover_range() {
+    let downloader = ArchiveDownloader::new("data");
+    let range = DateRange::new(date(1), date(2)).unwrap();
+    assert!(DateRange::new(date(2), date(1)).is_err());
+    assert!(downloader
+        .objects(&range, &[ArchiveKind::L2Book], &[])
+        .is_err());
+
+    let objects = downloader
+        .objects(
+            &range,
+            &[ArchiveKind::L2Book, ArchiveKind::AssetCtxs],
+            &["BTC"],
+        )
+        .unwrap();
+    assert_eq!(objects.len(), 2 * 24 + 2);
+    assert_eq!(objects[9].key, "market_data/20240301/9/l2Book/BTC.lz4");
+    assert!(objects[9]
+        .output
+        .ends_with("l2Book/BTC/2024-03-01/09.jsonl"));
+    assert_eq!(objects[24].key, "asset_ctxs/20240301.csv.lz4");
+}
+
+#[test]
+fn test_convert_archives() {
+    let archive = concat!(
+        r#"{"time":"2024-03-01T09:00:00.5","ver_num":1,"raw":{"channel":"l2Book","data":{"coin":"BTC","time":1709283600500,"levels":[[{"px":"61000.0","sz":"1.0","n":2}],[{"px":"61001.0","sz":"0.5","n":1}]]}}}"#,
+        "\n\nnot json\n",
+    );
+    let decoded = lz4_flex::frame::FrameDecoder::new(&lz4(archive.as_bytes())[..]);
+    let mut output = Vec::new();
+    assert_eq!(
+        convert_l2_book(std::io::BufReader::new(decoded), &mut output).unwrap(),
+        1
+    );
+    let message: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(message["channel"], "l2Book");
+    assert_eq!(message["data"]["levels"][0][0]["px"], "61000.0");
+
+    let trades = concat!(
+        r#"{"coin":"BTC","side":"B","time":"2024-03-01T09:00:01.250","px":"61001.0","sz":"0.1","hash":"0xabc","tid":7}"#,
+        "\n",
+        r#"{"coin":"ETH","side":"A","time":"2024-03-01T09:00:02","px":"3400.0","sz":"1.0"}"#,
+        "\n",
+    );
+    let mut output = Vec::new();
+    let coins = vec!["BTC".to_string()];
+    assert_eq!(
+        convert_trades(trades.as_bytes(), &coins, &mut output).unwrap(),
+        1
+    );
+    let message: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(message["data"][0]["time"], 1_709_283_601_250u64);
+}
+
+#[tokio::test]
+async fn test_download_is_resumable() {
+    let mut server = mockito::Server::new_async().await;
+    let csv = "time,coin,funding,open_interest,prev_day_px,day_ntl_vlm,premium,oracle_px,mark_px,mid_px,impact_bid_px,impact_ask_px\n\
+               2024-03-01T00:00:00Z,BTC,0.0000125,1000.0,60000.0,1000000.0,0.0001,61000.0,61005.0,61004.5,61003.0,61006.0\n\
+               2024-03-01T00:00:00Z,ETH,0.00001,5000.0,3300.0,500000.0,0.0001,3400.0,3401.0,3400.5,3400.0,3402.0\n";
+    let archive = server
+        .mock("GET", "/hyperliquid-archive/asset_ctxs/20240301.csv.lz4")
+        .with_status(200)
+        .with_body(lz4(csv.as_bytes()))
+        .expect(1)
+        .create_async()
+        .await;
+    server
+        .mock("GET", "/hyperliquid-archive/asset_ctxs/20240302.csv.lz4")
+        .with_status(404)
+        .create_async()
+        .await;
+
+    let root = temp_root("resume");
+    let downloader = ArchiveDownloader::new(&root).with_endpoint(server.url());
+    let range = DateRange::new(date(1), date(2)).unwrap();
+
+    let report = downloader
+        .download(&range, &[ArchiveKind::AssetCtxs], &["BTC"])
+        .await
+        .unwrap();
+    assert_eq!(report.downloaded, 1);
+    assert_eq!(report.messages, 1);
+    assert_eq!(
+        report.missing,
+        vec!["s3://hyperliquid-archive/asset_ctxs/20240302.csv.lz4".to_string()]
+    );
+
+    let recording = std::fs::read_to_string(root.join("activeAssetCtx/2024-03-01.jsonl")).unwrap();
+    let message: Value = serde_json::from_str(recording.trim()).unwrap();
+    assert_eq!(message["data"]["coin"], "BTC");
+    assert_eq!(message["data"]["ctx"]["markPx"], "61005.0");
+    assert_eq!(message["data"]["ctx"]["impactPxs"][1], "61006.0");
+    assert_eq!(message["time"], 1_709_251_200_000u64);
+
+    // Converted days are skipped on the next run
+    let report = downloader
+        .download(&range, &[ArchiveKind::AssetCtxs], &["BTC"])
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, 1);
+    archive.assert_async().await;
+
+    let _ = std::fs::remove_dir_all(root);
+}
@@ -0,0 +1,126 @@
+//! Tests for rolling order book depth statistics
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::analytics::DepthMonitor;
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::oms::OrderManager;
+use hyperliquid_core::stream::LocalBook;
+use serde_json::{json, Value};
+
+fn levels(levels: &[(&str, &str)]) -> Value {
+    levels
+        .iter()
+        .map(|(px, sz)| json!({"px": px, "sz": sz, "n": 1}))
+        .collect()
+}
+
+fn update(book: &LocalBook, time: u64, bids: &[(&str, &str)], asks: &[(&str, &str)]) {
+    assert!(book.apply(&json!({
+        "coin": book.coin(),
+        "time": time,
+        "levels": [levels(bids), levels(asks)]
+    })));
+}
+
+#[test]
+fn test_averages_and_replenishment() {
+    let clock = ManualClock::new(1_000_000);
+    let monitor = DepthMonitor::new()
+        .with_levels(2)
+        .with_window(Duration::from_secs(10))
+        .with_clock(Arc::new(clock.clone()));
+    let book = LocalBook::new("BTC");
+    monitor.track(book.clone());
+
+    // Nothing to sample before the first update
+    assert_eq!(monitor.sample(), 0);
+    assert!(monitor.snapshot("BTC").is_none());
+
+    update(
+        &book,
+        1,
+        &[("100", "2"), ("99", "3"), ("98", "7")],
+        &[("101", "1"), ("102", "4")],
+    );
+    assert_eq!(monitor.sample(), 1);
+    // Unchanged books aren't sampled twice
+    assert_eq!(monitor.sample(), 0);
+
+    clock.advance(Duration::from_secs(1));
+    // 3 added at 100; 98 is beyond the previous top two levels
+    update(
+        &book,
+        2,
+        &[("100", "5"), ("99", "3"), ("98", "9")],
+        &[("101", "1"), ("102", "2")],
+    );
+    assert_eq!(monitor.sample(), 1);
+
+    let snapshot = monitor.snapshot("BTC").unwrap();
+    assert_eq!(snapshot.samples, 2);
+    assert_eq!(snapshot.avg_bid_sizes, [3.5, 3.0]);
+    assert_eq!(snapshot.avg_ask_sizes, [1.0, 3.0]);
+    assert_eq!(snapshot.bid_depth, 6.5);
+    assert_eq!(snapshot.ask_depth, 4.0);
+    assert!((snapshot.bid_replenish_rate - 3.0).abs() < 1e-9);
+    assert_eq!(snapshot.ask_replenish_rate, 0.0);
+    assert!(snapshot.queue.is_empty());
+
+    // Samples older than the window are dropped
+    clock.advance(Duration::from_secs(20));
+    update(&book, 3, &[("100", "1")], &[("101", "1")]);
+    assert_eq!(monitor.sample(), 1);
+    let snapshot = monitor.snapshot("BTC").unwrap();
+    assert_eq!(snapshot.samples, 1);
+    assert_eq!(snapshot.avg_bid_sizes, [1.0, 0.0]);
+    assert_eq!(snapshot.bid_replenish_rate, 0.0);
+
+    monitor.untrack("BTC");
+    assert!(monitor.snapshot("BTC").is_none());
+}
+
+#[test]
+fn test_queue_position_of_resting_order() {
+    let oms = OrderManager::new();
+    let key = oms.record_submission("BTC", true, 1.0, 100.0, None);
+    oms.record_status(key, &json!({"resting": {"oid": 9}}));
+    // Orders of other coins are ignored
+    let other = oms.record_submission("ETH", true, 1.0, 100.0, None);
+    oms.record_status(other, &json!({"resting": {"oid": 10}}));
+
+    let monitor = DepthMonitor::new().with_orders(oms);
+    let book = LocalBook::new("BTC");
+    let mut snapshots = monitor.snapshots();
+    monitor.track(book.clone());
+
+    // Our 1.0 joined the back of the level
+    update(&book, 1, &[("100", "5")], &[("101", "1")]);
+    let published = monitor.publish();
+    assert_eq!(published.len(), 1);
+    let queue = &snapshots.try_recv().unwrap().queue;
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0].oid, 9);
+    assert_eq!(queue[0].remaining_sz, 1.0);
+    assert_eq!(queue[0].ahead_sz, 4.0);
+
+    // Size ahead traded or canceled
+    update(&book, 2, &[("100", "3")], &[("101", "1")]);
+    monitor.sample();
+    assert_eq!(monitor.snapshot("BTC").unwrap().queue[0].ahead_sz, 2.0);
+
+    // Size added later queues behind us
+    update(&book, 3, &[("100", "10")], &[("101", "1")]);
+    monitor.sample();
+    let position = &monitor.snapshot("BTC").unwrap().queue[0];
+    assert_eq!(position.ahead_sz, 2.0);
+    assert_eq!(position.level_sz, Some(10.0));
+
+    // Off the visible book, the last estimate is kept
+    update(&book, 4, &[("99", "1")], &[("101", "1")]);
+    monitor.sample();
+    let position = &monitor.snapshot("BTC").unwrap().queue[0];
+    assert_eq!(position.ahead_sz, 2.0);
+    assert_eq!(position.level_sz, None);
+}
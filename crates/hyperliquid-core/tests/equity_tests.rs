@@ -0,0 +1,119 @@
+//! Tests for the account equity sampler
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::equity::{max_drawdown, EquitySample, EquitySampler};
+use hyperliquid_core::store::{MemoryStore, StateStore};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const USER: &str = "0x1111111111111111111111111111111111111111";
+
+fn state(time: u64, account_value: &str) -> Value {
+    let summary = json!({"accountValue": account_value, "totalNtlPos": "6000.0",
+                         "totalRawUsd": "4000.0", "totalMarginUsed": "600.0"});
+    json!({
+        "marginSummary": summary,
+        "crossMarginSummary": summary,
+        "withdrawable": "9400.0",
+        "assetPositions": [{"type": "oneWay", "position": {
+            "coin": "BTC", "szi": "0.1", "leverage": {"type": "cross", "value": 10},
+            "entryPx": "60000.0", "positionValue": "6000.0", "unrealizedPnl": "-25.5",
+            "returnOnEquity": "0.0", "liquidationPx": null, "marginUsed": "600.0",
+            "maxLeverage": 50
+        }}],
+        "time": time
+    })
+}
+
+fn sample(time: u64, account_value: f64) -> EquitySample {
+    EquitySample {
+        time,
+        account_value,
+        margin_used: 0.0,
+        withdrawable: account_value,
+        positions: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn test_samples_are_persisted_and_queried() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "clearinghouseState", "user": USER}),
+        ))
+        .with_body(state(1_000, "10000.0").to_string())
+        .create_async()
+        .await;
+
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let sampler = EquitySampler::new(client.clone(), USER, store.clone());
+    let taken = sampler.sample().await.unwrap();
+    assert_eq!(taken.account_value, 10_000.0);
+    assert!((taken.margin_usage() - 0.06).abs() < 1e-12);
+    assert_eq!(taken.positions[0].unrealized_pnl, -25.5);
+
+    sampler.record(&sample(500, 9_000.0)).unwrap();
+    sampler.record(&sample(2_000, 11_000.0)).unwrap();
+
+    // A new sampler on the same store sees the history, in time order
+    let reopened = EquitySampler::new(client, USER, store);
+    let times: Vec<u64> = reopened
+        .history(0..u64::MAX)
+        .unwrap()
+        .iter()
+        .map(|sample| sample.time)
+        .collect();
+    assert_eq!(times, vec![500, 1_000, 2_000]);
+    assert_eq!(reopened.history(600..2_000).unwrap().len(), 1);
+    assert_eq!(reopened.latest().unwrap().unwrap().time, 2_000);
+
+    let mut csv = Vec::new();
+    reopened.export_csv(0..1_001, &mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "time,account_value,margin_used,withdrawable,positions\n\
+         500,9000,0,9000,0\n\
+         1000,10000,600,9400,1\n"
+    );
+}
+
+#[test]
+fn test_max_drawdown() {
+    let samples = vec![
+        sample(1, 100.0),
+        sample(2, 120.0),
+        sample(3, 90.0),
+        sample(4, 130.0),
+        sample(5, 110.0),
+    ];
+    let drawdown = max_drawdown(&samples).unwrap();
+    assert_eq!((drawdown.peak_time, drawdown.trough_time), (2, 3));
+    assert!((drawdown.fraction - 0.25).abs() < 1e-12);
+
+    assert!(max_drawdown(&samples[..2]).is_none());
+}
+
+#[test]
+fn test_retention_drops_old_samples() {
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+    let client = HttpClient::new("http://127.0.0.1:1", HttpClientConfig::default()).unwrap();
+    let sampler =
+        EquitySampler::new(client, USER, store).with_retention(Duration::from_millis(1_000));
+    for time in [1_000, 1_500, 2_000, 2_600] {
+        sampler.record(&sample(time, 100.0)).unwrap();
+    }
+    let times: Vec<u64> = sampler
+        .history(0..u64::MAX)
+        .unwrap()
+        .iter()
+        .map(|sample| sample.time)
+        .collect();
+    assert_eq!(times, vec![2_000, 2_600]);
+    assert!(sampler.drawdown(0..u64::MAX).unwrap().is_none());
+}
@@ -1,3 +1,5 @@
+use std::error::Error;
+
 use hyperliquid_core::error::HyperliquidError;
 use reqwest::StatusCode;
 
@@ -262,4 +264,68 @@ fn test_error_convenience_constructors() {
         retry_after: 30,
     };
     assert!(matches!(rate_limit_retry_error, HyperliquidError::RateLimitWithRetry { .. }));
-}
\ No newline at end of file
+}
+#[test]
+fn test_rate_limit_classification() {
+    let with_retry = HyperliquidError::RateLimitWithRetry {
+        message: "Rate limited".to_string(),
+        retry_after: 30,
+    };
+    assert!(with_retry.is_rate_limited());
+    assert_eq!(with_retry.retry_after(), Some(std::time::Duration::from_secs(30)));
+
+    let too_many = HyperliquidError::Http {
+        status: StatusCode::TOO_MANY_REQUESTS,
+        message: "Too many requests".to_string(),
+        cause: None,
+    };
+    assert!(too_many.is_rate_limited());
+    assert_eq!(too_many.retry_after(), None);
+
+    let server_err = HyperliquidError::Server {
+        status: StatusCode::INTERNAL_SERVER_ERROR,
+        message: "Internal server error".to_string(),
+    };
+    assert!(!server_err.is_rate_limited());
+    assert!(!HyperliquidError::Validation("Invalid order".to_string()).is_rate_limited());
+}
+
+#[test]
+fn test_transport_errors_keep_their_source() {
+    use tokio_tungstenite::tungstenite;
+
+    let closed: HyperliquidError = tungstenite::Error::ConnectionClosed.into();
+    assert!(closed.is_retryable());
+    assert!(closed.source().is_some());
+
+    let utf8: HyperliquidError = tungstenite::Error::Utf8.into();
+    assert!(!utf8.is_retryable());
+}
+
+#[test]
+fn test_context_preserves_classification_and_chain() {
+    use hyperliquid_core::ResultExt;
+
+    let parsed: Result<serde_json::Value, _> = serde_json::from_str("{ invalid json");
+    let err = parsed.context("decoding meta").unwrap_err();
+    assert!(format!("{}", err).starts_with("decoding meta: JSON serialization error:"));
+    assert_eq!(err.chain().count(), 3);
+    assert!(err.root_cause().is::<serde_json::Error>());
+    assert!(!err.is_retryable());
+
+    let limited: Result<(), HyperliquidError> = Err(HyperliquidError::RateLimitWithRetry {
+        message: "Rate limited".to_string(),
+        retry_after: 5,
+    });
+    let err = limited
+        .with_context(|| format!("placing order {}", 42))
+        .unwrap_err()
+        .context("rebalancing");
+    assert!(err.is_retryable());
+    assert!(err.is_rate_limited());
+    assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(5)));
+    assert!(matches!(
+        err.root_cause().downcast_ref::<HyperliquidError>(),
+        Some(HyperliquidError::RateLimitWithRetry { .. })
+    ));
+}
@@ -0,0 +1,125 @@
+//! Tests for the HyperEVM client and bridging helpers
+
+#![cfg(feature = "evm")]
+
+use hyperliquid_core::evm::{
+    system_address, EvmClient, EvmNetwork, LinkedToken, HYPE_SYSTEM_ADDRESS,
+};
+use hyperliquid_core::HyperliquidError;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+fn spot_meta() -> serde_json::Value {
+    json!({
+        "tokens": [
+            {"name": "USDC", "index": 0, "weiDecimals": 8, "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054", "evmContract": null},
+            {"name": "HYPE", "index": 150, "weiDecimals": 8, "tokenId": "0x0d01dc56dcaaca66ad901c959b4011ec", "evmContract": null},
+            {"name": "UBTC", "index": 197, "weiDecimals": 10, "tokenId": "0x8f254b963e8468305d409b33aa137c67",
+             "evmContract": {"address": "0x9FDBdA0A5e284c32744D2f17Ee5c74B284993463", "evm_extra_wei_decimals": -2}}
+        ]
+    })
+}
+
+#[test]
+fn test_linked_tokens_from_spot_meta() {
+    let tokens = LinkedToken::from_spot_meta(&spot_meta());
+    assert_eq!(tokens.len(), 2);
+
+    let hype = &tokens[0];
+    assert!(hype.is_native());
+    assert_eq!(hype.evm_decimals(), 18);
+    assert_eq!(hype.system_address(), HYPE_SYSTEM_ADDRESS);
+
+    let ubtc = &tokens[1];
+    assert_eq!(ubtc.evm_decimals(), 8);
+    assert_eq!(
+        ubtc.system_address(),
+        "0x20000000000000000000000000000000000000c5"
+    );
+    assert_eq!(system_address(197), ubtc.system_address());
+    assert_eq!(ubtc.spot_token(), "UBTC:0x8f254b963e8468305d409b33aa137c67");
+
+    assert_eq!(EvmNetwork::Mainnet.chain_id(), 999);
+    assert_eq!(EvmNetwork::Testnet.chain_id(), 998);
+}
+
+#[test]
+fn test_bridge_amounts() {
+    let tokens = LinkedToken::from_spot_meta(&spot_meta());
+    let (hype, ubtc) = (&tokens[0], &tokens[1]);
+
+    assert_eq!(
+        hype.to_evm_units(dec!(1.5)).unwrap(),
+        1_500_000_000_000_000_000
+    );
+    assert_eq!(
+        hype.from_evm_units(1_500_000_000_000_000_000).unwrap(),
+        dec!(1.5)
+    );
+    assert!(ubtc.to_evm_units(dec!(0.000000001)).is_err());
+
+    let transfer = hype.evm_to_core(dec!(2)).unwrap();
+    assert_eq!(transfer.to, HYPE_SYSTEM_ADDRESS);
+    assert_eq!(transfer.value, 2_000_000_000_000_000_000);
+
+    let transfer = ubtc.evm_to_core(dec!(0.5)).unwrap();
+    assert_eq!(transfer.to, "0x9fdbda0a5e284c32744d2f17ee5c74b284993463");
+    assert_eq!(transfer.value, 0);
+    assert_eq!(
+        transfer.data.unwrap(),
+        "0xa9059cbb\
+         00000000000000000000000020000000000000000000000000000000000000c5\
+         0000000000000000000000000000000000000000000000000000000002faf080"
+    );
+
+    let action = ubtc
+        .core_to_evm_action(dec!(0.25), 1_700_000_000_000)
+        .unwrap();
+    assert_eq!(action["type"], "spotSend");
+    assert_eq!(
+        action["destination"],
+        "0x20000000000000000000000000000000000000c5"
+    );
+    assert_eq!(action["amount"], "0.25");
+    assert!(ubtc.core_to_evm_action(dec!(0), 0).is_err());
+    assert!(ubtc.core_to_evm_action(dec!(0.00000000001), 0).is_err());
+}
+
+#[tokio::test]
+async fn test_rpc_requests() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/")
+        .match_body(mockito::Matcher::PartialJson(
+            json!({"method": "eth_getBalance"}),
+        ))
+        .with_body(r#"{"jsonrpc":"2.0","id":1,"result":"0x1bc16d674ec80000"}"#)
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/")
+        .match_body(mockito::Matcher::PartialJson(
+            json!({"method": "eth_sendRawTransaction"}),
+        ))
+        .with_body(r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"nonce too low"}}"#)
+        .create_async()
+        .await;
+
+    let client = EvmClient::new(EvmNetwork::Testnet).with_rpc_url(server.url());
+    assert_eq!(client.chain_id(), 998);
+
+    let hype = &LinkedToken::from_spot_meta(&spot_meta())[0];
+    let balance = client
+        .token_balance(hype, "0x0000000000000000000000000000000000000001")
+        .await
+        .unwrap();
+    assert_eq!(balance, dec!(2));
+
+    match client.send_raw_transaction("0x02f8").await {
+        Err(HyperliquidError::Client { code, message, .. }) => {
+            assert_eq!(code, -32000);
+            assert_eq!(message, "nonce too low");
+        }
+        other => panic!("expected a JSON-RPC error, got {:?}", other),
+    }
+}
@@ -0,0 +1,173 @@
+//! Tests for the validated ExchangeClientConfig builder
+
+use std::str::FromStr;
+
+use ethers_core::types::Address;
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{SignerConfig, DEFAULT_SLIPPAGE_BPS};
+use hyperliquid_core::types::Environment;
+use hyperliquid_core::{ExchangeClientConfig, HyperliquidError};
+
+const KEY: &str = "0x1111111111111111111111111111111111111111111111111111111111111111";
+const AGENT_KEY: &str = "0x2222222222222222222222222222222222222222222222222222222222222222";
+
+fn address(wallet: &Wallet) -> Address {
+    Address::from_str(&wallet.address()).unwrap()
+}
+
+fn other_account() -> Address {
+    "0x3333333333333333333333333333333333333333"
+        .parse()
+        .unwrap()
+}
+
+fn config_error(result: Result<ExchangeClientConfig, HyperliquidError>) -> String {
+    match result {
+        Err(HyperliquidError::Config(message)) => message,
+        other => panic!(
+            "expected a config error, got {:?}",
+            other.map(|c| c.base_url)
+        ),
+    }
+}
+
+#[test]
+fn test_wallet_signer_defaults_account_and_endpoint() {
+    let wallet = Wallet::mainnet(KEY).unwrap();
+    let config = ExchangeClientConfig::builder()
+        .wallet(wallet.clone())
+        .build()
+        .unwrap();
+
+    assert_eq!(config.base_url, Environment::Mainnet.base_url());
+    assert_eq!(config.account, address(&wallet));
+    assert_eq!(config.default_slippage_bps, DEFAULT_SLIPPAGE_BPS);
+    assert!(matches!(config.signer, Some(SignerConfig::Wallet(_))));
+    assert_eq!(config.wallet().unwrap().address(), wallet.address());
+}
+
+#[test]
+fn test_agent_vault_and_dex() {
+    let agent = Wallet::testnet(AGENT_KEY).unwrap();
+    let vault = "0x4444444444444444444444444444444444444444"
+        .parse()
+        .unwrap();
+    let config = ExchangeClientConfig::builder()
+        .environment(Environment::Testnet)
+        .account(other_account())
+        .agent(agent)
+        .vault_address(vault)
+        .dex("xyz")
+        .default_slippage_bps(25)
+        .timeout(5)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.base_url, Environment::Testnet.base_url());
+    assert_eq!(config.account, other_account());
+    assert_eq!(config.vault_address, Some(vault));
+    assert_eq!(config.dex.as_deref(), Some("xyz"));
+    assert_eq!(config.default_slippage_bps, 25);
+    assert_eq!(config.timeout, Some(5));
+}
+
+#[test]
+fn test_signer_must_match_account() {
+    let wallet = Wallet::mainnet(KEY).unwrap();
+    let message = config_error(
+        ExchangeClientConfig::builder()
+            .account(other_account())
+            .wallet(wallet.clone())
+            .build(),
+    );
+    assert!(message.contains("agent"), "{}", message);
+
+    let message = config_error(
+        ExchangeClientConfig::builder()
+            .account(address(&wallet))
+            .agent(wallet.clone())
+            .build(),
+    );
+    assert!(message.contains("own key"), "{}", message);
+
+    // An agent cannot guess the account it trades for
+    config_error(ExchangeClientConfig::builder().agent(wallet).build());
+    config_error(ExchangeClientConfig::builder().build());
+}
+
+#[test]
+fn test_network_and_url_consistency() {
+    // Testnet key against the mainnet endpoint
+    let message = config_error(
+        ExchangeClientConfig::builder()
+            .wallet(Wallet::testnet(KEY).unwrap())
+            .build(),
+    );
+    assert!(message.contains("testnet key"), "{}", message);
+
+    config_error(
+        ExchangeClientConfig::builder()
+            .environment(Environment::Testnet)
+            .base_url(Environment::Mainnet.base_url())
+            .account(other_account())
+            .build(),
+    );
+    config_error(
+        ExchangeClientConfig::builder()
+            .base_url("ftp://example.com")
+            .account(other_account())
+            .build(),
+    );
+    config_error(
+        ExchangeClientConfig::builder()
+            .base_url("not a url")
+            .account(other_account())
+            .build(),
+    );
+
+    // Custom endpoints accept either network's keys
+    let config = ExchangeClientConfig::builder()
+        .base_url("http://127.0.0.1:3001/")
+        .wallet(Wallet::testnet(KEY).unwrap())
+        .build()
+        .unwrap();
+    assert_eq!(config.base_url, "http://127.0.0.1:3001");
+}
+
+#[test]
+fn test_remote_signer() {
+    let config = ExchangeClientConfig::builder()
+        .account(other_account())
+        .remote_signer("https://signer.internal:8443", other_account())
+        .build()
+        .unwrap();
+    assert!(config.wallet().is_none());
+    assert_eq!(config.signer.unwrap().address().unwrap(), other_account());
+
+    config_error(
+        ExchangeClientConfig::builder()
+            .account(other_account())
+            .remote_signer("signer", other_account())
+            .build(),
+    );
+}
+
+#[test]
+fn test_rejects_bad_vault_dex_and_slippage() {
+    let builder = || ExchangeClientConfig::builder().account(other_account());
+
+    config_error(builder().vault_address(other_account()).build());
+    config_error(builder().dex("").build());
+    config_error(builder().dex("my dex").build());
+    config_error(builder().default_slippage_bps(10_001).build());
+    assert!(builder().default_slippage_bps(10_000).build().is_ok());
+}
+
+#[test]
+fn test_presets_keep_defaults() {
+    let config = ExchangeClientConfig::testnet(other_account());
+    assert!(config.signer.is_none());
+    assert!(config.vault_address.is_none());
+    assert!(config.dex.is_none());
+    assert_eq!(config.default_slippage_bps, DEFAULT_SLIPPAGE_BPS);
+}
@@ -0,0 +1,162 @@
+//! Tests for the exchange health signal
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::health::{ExchangeHealth, HealthStatus};
+use hyperliquid_core::killswitch::{KillSwitchRegistry, Scope};
+use hyperliquid_core::scheduler::{Job, RateLimiter, Scheduler};
+use hyperliquid_core::{HttpClient, HttpClientConfig, HyperliquidError};
+use mockito::{Matcher, Server};
+use serde_json::{json, Value};
+
+fn timeout() -> HyperliquidError {
+    HyperliquidError::Timeout("Request timeout".to_string())
+}
+
+#[test]
+fn test_infers_outage_from_errors() {
+    let clock = ManualClock::new(1_700_000_000_000);
+    let health = ExchangeHealth::new()
+        .with_clock(Arc::new(clock.clone()))
+        .with_thresholds(2, 3)
+        .with_cooldown(Duration::from_secs(5));
+    let mut changes = health.changes();
+
+    health.record_error(&timeout());
+    // Rate limits and rejections don't count
+    health.record_error(&HyperliquidError::RateLimit(
+        "too many requests".to_string(),
+    ));
+    health.record_error(&HyperliquidError::Validation("bad order".to_string()));
+    assert_eq!(health.status(), HealthStatus::Operational);
+    health.record_error(&timeout());
+    assert_eq!(health.status(), HealthStatus::Degraded);
+    assert!(health.allow_request().is_ok());
+
+    health.record_error(&timeout());
+    assert_eq!(health.status(), HealthStatus::Down);
+    assert!(matches!(
+        health.allow_request(),
+        Err(HyperliquidError::ExchangeUnavailable { ref status, .. }) if status == "down"
+    ));
+
+    // One probe per cooldown
+    clock.advance(Duration::from_secs(5));
+    assert!(health.allow_request().is_ok());
+    assert!(health.allow_request().is_err());
+
+    health.record_success();
+    assert!(health.is_available());
+    let seen: Vec<HealthStatus> = std::iter::from_fn(|| changes.try_recv().ok())
+        .map(|change| change.status)
+        .collect();
+    assert_eq!(
+        seen,
+        [
+            HealthStatus::Degraded,
+            HealthStatus::Down,
+            HealthStatus::Operational
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_maintenance_trips_breaker_and_kill_switch() {
+    let mut server = Server::new_async().await;
+    let status = json!({"type": "exchangeStatus"});
+    let maintenance = server
+        .mock("POST", "/info")
+        .match_body(Matcher::Json(status.clone()))
+        .with_body(json!({"time": 1, "specialStatuses": ["upgrade"]}).to_string())
+        .create_async()
+        .await;
+    let meta = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+        .with_body("{}")
+        .expect(1)
+        .create_async()
+        .await;
+
+    let switches = KillSwitchRegistry::new();
+    let health = ExchangeHealth::new().with_kill_switches(switches.clone());
+    let client = HttpClient::new(server.url(), HttpClientConfig::default())
+        .unwrap()
+        .with_health(health.clone());
+
+    assert_eq!(
+        health.poll(&client).await.unwrap(),
+        HealthStatus::Maintenance
+    );
+    assert!(health.reason().contains("upgrade"));
+    assert!(switches.check("BTC").is_err());
+    // Held back without being sent
+    let refused: Result<Value, _> = client.post("/info", &json!({"type": "meta"})).await;
+    assert!(matches!(
+        refused,
+        Err(HyperliquidError::ExchangeUnavailable { .. })
+    ));
+
+    maintenance.remove_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::Json(status))
+        .with_body(json!({"time": 2, "specialStatuses": null}).to_string())
+        .create_async()
+        .await;
+    assert_eq!(
+        health.poll(&client).await.unwrap(),
+        HealthStatus::Operational
+    );
+    assert!(switches.is_enabled("BTC"));
+    let _: Value = client
+        .post("/info", &json!({"type": "meta"}))
+        .await
+        .unwrap();
+    meta.assert_async().await;
+
+    // A halt set by someone else survives recovery
+    switches.disable(Scope::All, "manual").unwrap();
+    health.report(HealthStatus::Maintenance, "status page incident");
+    health.report(HealthStatus::Operational, "");
+    assert_eq!(switches.halt("BTC").unwrap().reason, "manual");
+}
+
+#[tokio::test]
+async fn test_scheduler_skips_runs_while_unavailable() {
+    let health = ExchangeHealth::new();
+    health.report(HealthStatus::Maintenance, "upgrade");
+    let runs = Arc::new(AtomicU32::new(0));
+    let exempt = Arc::new(AtomicU32::new(0));
+
+    let (counter, exempt_counter) = (runs.clone(), exempt.clone());
+    let handle = Scheduler::new(RateLimiter::new(60_000))
+        .with_jitter(0.0)
+        .with_health(health.clone())
+        .with_job(Job::new("poll", Duration::from_millis(10), 1, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        }))
+        .with_job(
+            Job::new("probe", Duration::from_millis(10), 1, move || {
+                exempt_counter.fetch_add(1, Ordering::SeqCst);
+                async { Ok(()) }
+            })
+            .exempt_from_health(),
+        )
+        .start()
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(runs.load(Ordering::SeqCst), 0);
+    assert!(exempt.load(Ordering::SeqCst) > 0);
+    assert!(handle.stats()["poll"].skipped > 0);
+
+    health.report(HealthStatus::Operational, "");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(runs.load(Ordering::SeqCst) > 0);
+    handle.shutdown();
+}
@@ -0,0 +1,183 @@
+//! Tests for the client-side execution algorithms
+//!
+//! Runs parent orders against a scripted venue in place of the exchange.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyperliquid_core::execution::{
+    round_px, round_sz, ChildOrder, ChildResult, ExecutionState, Executor, ParentOrder, Quote,
+    Venue,
+};
+use hyperliquid_core::oms::OrderManager;
+use hyperliquid_core::HyperliquidError;
+use serde_json::json;
+use tokio::sync::watch;
+
+/// Venue answering with scripted results (fully filled when the script runs out)
+#[derive(Clone, Default)]
+struct MockVenue {
+    script: Arc<Mutex<VecDeque<ChildResult>>>,
+    sent: Arc<Mutex<Vec<ChildOrder>>>,
+    canceled: Arc<Mutex<Vec<u64>>>,
+}
+
+impl MockVenue {
+    fn scripted(results: Vec<ChildResult>) -> Self {
+        Self {
+            script: Arc::new(Mutex::new(results.into())),
+            ..Default::default()
+        }
+    }
+}
+
+impl Venue for MockVenue {
+    async fn place(&self, order: &ChildOrder) -> Result<ChildResult, HyperliquidError> {
+        self.sent.lock().unwrap().push(order.clone());
+        Ok(self
+            .script
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or(ChildResult::Filled {
+                sz: order.sz,
+                avg_px: order.limit_px,
+            }))
+    }
+
+    async fn cancel(&self, _coin: &str, oid: u64) -> Result<(), HyperliquidError> {
+        self.canceled.lock().unwrap().push(oid);
+        Ok(())
+    }
+}
+
+fn quotes(bid: f64, ask: f64) -> watch::Receiver<Option<Quote>> {
+    let (tx, rx) = watch::channel(Some(Quote { bid, ask }));
+    // Keep the sender alive for the duration of the test
+    std::mem::forget(tx);
+    rx
+}
+
+#[test]
+fn test_rounding() {
+    assert_eq!(round_px(65432.17, 5), 65432.0);
+    assert_eq!(round_px(1.234567, 2), 1.2346);
+    assert_eq!(round_px(0.0123456, 0), 0.012346);
+    assert_eq!(round_sz(0.3, 1), 0.3);
+    assert_eq!(round_sz(1.239, 2), 1.23);
+}
+
+#[tokio::test]
+async fn test_twap_carries_unfilled_size_forward() {
+    let venue = MockVenue::scripted(vec![ChildResult::Filled {
+        sz: 0.1,
+        avg_px: 100.0,
+    }]);
+    let executor = Executor::new(venue.clone(), quotes(99.0, 100.0));
+    let parent = ParentOrder::twap("ETH", true, 1.0, 101.0, Duration::from_millis(40), 4)
+        .with_slippage(0.05);
+    let report = executor.start(parent).unwrap().wait().await;
+
+    assert_eq!(report.state, ExecutionState::Completed);
+    assert!((report.filled_sz - 1.0).abs() < 1e-9);
+    assert_eq!(report.children_sent, 4);
+
+    let sent = venue.sent.lock().unwrap();
+    let sizes: Vec<f64> = sent.iter().map(|child| child.sz).collect();
+    // First slice filled 0.1 of 0.25, so the second asks for 0.4
+    assert!((sizes[0] - 0.25).abs() < 1e-9);
+    assert!((sizes[1] - 0.4).abs() < 1e-9);
+    // Priced through the ask but capped at the limit
+    assert!(sent
+        .iter()
+        .all(|child| child.limit_px == 101.0 && child.tif == "Ioc"));
+}
+
+#[tokio::test]
+async fn test_vwap_follows_profile_and_fails_on_rejections() {
+    let venue = MockVenue::default();
+    let executor = Executor::new(venue.clone(), quotes(99.0, 100.0));
+    let parent = ParentOrder::vwap(
+        "ETH",
+        false,
+        2.0,
+        90.0,
+        Duration::from_millis(30),
+        vec![1.0, 0.0, 3.0],
+    );
+    let report = executor.start(parent).unwrap().wait().await;
+    assert_eq!(report.state, ExecutionState::Completed);
+    let sizes: Vec<f64> = venue.sent.lock().unwrap().iter().map(|c| c.sz).collect();
+    assert_eq!(sizes.len(), 2);
+    assert!((sizes[0] - 0.5).abs() < 1e-9 && (sizes[1] - 1.5).abs() < 1e-9);
+
+    let rejected = ChildResult::Rejected("Insufficient margin".to_string());
+    let venue = MockVenue::scripted(vec![rejected; 3]);
+    let executor = Executor::new(venue, quotes(99.0, 100.0));
+    let parent = ParentOrder::twap("ETH", true, 1.0, 101.0, Duration::from_millis(30), 5);
+    let report = executor.start(parent).unwrap().wait().await;
+    assert_eq!(
+        report.state,
+        ExecutionState::Failed("Insufficient margin".to_string())
+    );
+    assert_eq!(report.children_rejected, 3);
+}
+
+#[tokio::test]
+async fn test_pause_and_cancel() {
+    let venue = MockVenue::default();
+    let executor = Executor::new(venue.clone(), quotes(99.0, 100.0));
+    let parent = ParentOrder::twap("ETH", true, 1.0, 101.0, Duration::from_secs(60), 10);
+    let handle = executor.start(parent).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    handle.pause();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(handle.report().children_sent, 1);
+    handle.cancel();
+
+    let report = handle.wait().await;
+    assert_eq!(report.state, ExecutionState::Canceled);
+    assert!((report.filled_sz - 0.1).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_iceberg_replaces_filled_child() {
+    let venue = MockVenue::scripted(vec![
+        ChildResult::Resting { oid: 1 },
+        ChildResult::Resting { oid: 2 },
+    ]);
+    let oms = OrderManager::new();
+    let executor =
+        Executor::new(venue.clone(), quotes(99.0, 100.0)).with_order_manager(oms.clone());
+    let handle = executor
+        .start(ParentOrder::iceberg("ETH", true, 1.0, 99.5, 0.4))
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    oms.handle_user_fills(&json!({"user": "0xabc", "fills": [
+        {"coin": "ETH", "px": "99.5", "sz": "0.4", "side": "B", "time": 1, "oid": 1, "tid": 1}
+    ]}));
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert!((handle.report().filled_sz - 0.4).abs() < 1e-9);
+
+    handle.cancel();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(*venue.canceled.lock().unwrap(), vec![2]);
+    oms.handle_order_updates(&json!([
+        {"order": {"coin": "ETH", "side": "B", "limitPx": "99.5", "sz": "0.4", "oid": 2,
+                   "timestamp": 2, "origSz": "0.4"},
+         "status": "canceled", "statusTimestamp": 3}
+    ]));
+    let report = handle.wait().await;
+    assert_eq!(report.state, ExecutionState::Canceled);
+    assert!((report.filled_sz - 0.4).abs() < 1e-9);
+    assert_eq!(*venue.canceled.lock().unwrap(), vec![2]);
+
+    let sent = venue.sent.lock().unwrap();
+    assert!(sent
+        .iter()
+        .all(|child| child.tif == "Gtc" && child.limit_px == 99.5));
+    assert!((sent[0].sz - 0.4).abs() < 1e-9 && (sent[1].sz - 0.4).abs() < 1e-9);
+}
@@ -0,0 +1,136 @@
+//! Tests for client-side order expiry
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::expiry::{Expiry, OrderExpiry};
+use hyperliquid_core::oms::{OrderKey, OrderManager, TrackedOrder};
+use hyperliquid_core::watchdog::KillSwitch;
+use hyperliquid_core::HyperliquidError;
+use serde_json::json;
+
+const START: u64 = 1_700_000_000_000;
+
+/// Records the oids of every cancel
+#[derive(Clone, Default)]
+struct MockSwitch {
+    cancels: Arc<Mutex<Vec<Vec<u64>>>>,
+}
+
+impl MockSwitch {
+    fn cancels(&self) -> Vec<Vec<u64>> {
+        std::mem::take(&mut *self.cancels.lock().unwrap())
+    }
+}
+
+impl KillSwitch for MockSwitch {
+    async fn cancel_orders(&self, orders: &[TrackedOrder]) -> Result<(), HyperliquidError> {
+        let oids = orders.iter().filter_map(|order| order.oid).collect();
+        self.cancels.lock().unwrap().push(oids);
+        Ok(())
+    }
+
+    async fn schedule_cancel(&self, _time: Option<u64>) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+}
+
+fn submit(oms: &OrderManager, coin: &str) -> OrderKey {
+    oms.record_submission(coin, true, 0.01, 60000.0, None)
+}
+
+fn rest(oms: &OrderManager, key: OrderKey, oid: u64) {
+    oms.record_status(key, &json!({"resting": {"oid": oid}}));
+}
+
+#[tokio::test]
+async fn test_cancels_resting_orders_after_ttl() {
+    let clock = ManualClock::new(START);
+    let switch = MockSwitch::default();
+    let oms = OrderManager::new();
+    let expiry = OrderExpiry::new(switch.clone(), oms.clone())
+        .with_clock(Arc::new(clock.clone()))
+        .with_default_ttl(Duration::from_secs(30))
+        .with_coin_ttl("ETH", Duration::from_secs(10))
+        .with_jitter(Duration::ZERO);
+
+    let btc = submit(&oms, "BTC");
+    rest(&oms, btc, 1);
+    let eth = submit(&oms, "ETH");
+    rest(&oms, eth, 2);
+    assert!(expiry.check().await.unwrap().is_empty());
+    assert_eq!(expiry.deadline_of(btc), Some(START + 30_000));
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(expiry.check().await.unwrap(), [eth]);
+    assert_eq!(switch.cancels(), [vec![2]]);
+    // Not resent while the cancel is in flight
+    assert!(expiry.check().await.unwrap().is_empty());
+
+    clock.advance(Duration::from_secs(20));
+    oms.record_status(
+        eth,
+        &json!({"filled": {"oid": 2, "totalSz": "0.01", "avgPx": "60000"}}),
+    );
+    assert_eq!(expiry.check().await.unwrap(), [btc]);
+    assert_eq!(switch.cancels(), [vec![1]]);
+    assert_eq!(expiry.expired(), 2);
+
+    // Still open after the retry interval, so the cancel is resent
+    clock.advance(Duration::from_secs(5));
+    assert_eq!(expiry.check().await.unwrap(), [btc]);
+    assert_eq!(expiry.expired(), 2);
+}
+
+#[tokio::test]
+async fn test_order_expiry_overrides_ttl() {
+    let clock = ManualClock::new(START);
+    let switch = MockSwitch::default();
+    let oms = OrderManager::new();
+    let expiry = OrderExpiry::new(switch.clone(), oms.clone())
+        .with_clock(Arc::new(clock.clone()))
+        .with_default_ttl(Duration::from_secs(1))
+        .with_jitter(Duration::ZERO);
+
+    let gtc = submit(&oms, "BTC");
+    expiry.set_expiry(gtc, Expiry::Never);
+    rest(&oms, gtc, 1);
+    let gtd = submit(&oms, "BTC");
+    expiry.set_expiry(gtd, Expiry::At(START + 5_000));
+    rest(&oms, gtd, 2);
+    // Only canceled once the exchange reports it resting
+    let pending = submit(&oms, "BTC");
+    expiry.check().await.unwrap();
+
+    clock.advance(Duration::from_secs(2));
+    assert!(expiry.check().await.unwrap().is_empty());
+    rest(&oms, pending, 3);
+    assert_eq!(expiry.check().await.unwrap(), [pending]);
+
+    clock.advance(Duration::from_secs(3));
+    assert_eq!(expiry.check().await.unwrap(), [gtd]);
+    assert_eq!(switch.cancels(), [vec![3], vec![2]]);
+    assert_eq!(expiry.deadline_of(gtc), None);
+}
+
+#[test]
+fn test_jitter_spreads_deadlines() {
+    let oms = OrderManager::new();
+    let expiry = OrderExpiry::new(MockSwitch::default(), oms.clone())
+        .with_clock(Arc::new(ManualClock::new(START)))
+        .with_default_ttl(Duration::from_secs(10))
+        .with_jitter(Duration::from_secs(2));
+
+    let deadlines: Vec<u64> = (0..50)
+        .map(|_| {
+            let key = submit(&oms, "BTC");
+            expiry.set_expiry(key, Expiry::After(Duration::from_secs(10)));
+            expiry.deadline_of(key).unwrap()
+        })
+        .collect();
+    assert!(deadlines
+        .iter()
+        .all(|&deadline| (START + 10_000..=START + 12_000).contains(&deadline)));
+    assert!(deadlines.iter().any(|&deadline| deadline != deadlines[0]));
+}
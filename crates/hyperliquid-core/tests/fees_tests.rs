@@ -0,0 +1,163 @@
+//! Tests for fee models and fee reconciliation
+
+use hyperliquid_core::fees::{FeeFill, FeeModel, FeeReconciler, FixedRates, TieredFeeModel};
+use hyperliquid_core::types::UserFill;
+use hyperliquid_core::{HttpClient, HttpClientConfig, UserFeesResponse};
+use mockito::{Matcher, Server};
+use serde_json::{json, Value};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-12
+}
+
+fn fill(tid: u64, coin: &str, px: &str, sz: &str, crossed: bool, fee: &str) -> Value {
+    json!({
+        "coin": coin, "px": px, "sz": sz, "side": "B", "time": 1_700_000_000_000u64 + tid,
+        "oid": 100 + tid, "tid": tid, "fee": fee, "feeToken": "USDC", "crossed": crossed,
+        "closedPnl": "0.0", "dir": "Open Long", "startPosition": "0.0", "hash": "0x0"
+    })
+}
+
+#[test]
+fn test_tiered_rates() {
+    let model = TieredFeeModel::hyperliquid();
+    assert_eq!(model.tier().unwrap().min_volume, 0.0);
+    assert!(close(model.rates(false).taker, 0.00045));
+    assert!(close(model.rates(true).maker, 0.0004));
+
+    let model = model
+        .with_volume(30_000_000.0)
+        .with_referral_discount(0.04)
+        .with_staking_discount(0.1);
+    assert_eq!(model.tier().unwrap().min_volume, 25e6);
+    let rates = model.rates(false);
+    assert!(close(rates.taker, 0.00035 * 0.96 * 0.9));
+    assert!(close(rates.maker, 0.00008 * 0.96 * 0.9));
+
+    // Rebates are not discounted
+    let rates = model.with_maker_rebate(0.00002).rates(false);
+    assert!(close(rates.maker, -0.00002));
+}
+
+#[test]
+fn test_expected_fee_of_user_fill() {
+    let user_fill: UserFill =
+        serde_json::from_value(fill(1, "BTC", "50000.0", "0.1", true, "2.25")).unwrap();
+    let fee_fill = FeeFill::from(&user_fill);
+    assert!(!fee_fill.is_spot());
+    assert!(close(fee_fill.notional(), 5_000.0));
+    assert!(close(
+        TieredFeeModel::hyperliquid().expected_fee(&fee_fill),
+        2.25
+    ));
+}
+
+#[test]
+fn test_fixed_rates_from_user_fees() {
+    let fees = UserFeesResponse {
+        fee_tier: "VIP1".to_string(),
+        volume_30d: "1000000.0".to_string(),
+        maker_fee: "1.5".to_string(),
+        taker_fee: "4.5".to_string(),
+        address: None,
+    };
+    let rates = FixedRates::from_user_fees(&fees).unwrap();
+    assert!(close(rates.0.taker, 0.00045));
+    assert!(close(rates.0.maker, 0.00015));
+
+    let bad = UserFeesResponse {
+        taker_fee: "n/a".to_string(),
+        ..fees
+    };
+    assert!(FixedRates::from_user_fees(&bad).is_err());
+}
+
+#[test]
+fn test_reconcile_reports_discrepancies() {
+    let mut reconciler =
+        FeeReconciler::new(TieredFeeModel::hyperliquid()).with_builder_rate(0.0001);
+
+    assert!(reconciler.ingest_fill(&fill(1, "BTC", "50000.0", "0.1", true, "2.25")));
+    assert!(reconciler.ingest_fill(&fill(2, "BTC", "50000.0", "0.1", false, "0.75")));
+    // Charged 5bps instead of 4.5
+    assert!(reconciler.ingest_fill(&fill(3, "ETH", "2000.0", "1.0", true, "1.0")));
+    // Fee includes a 1bp builder fee
+    let mut routed = fill(4, "ETH", "2000.0", "1.0", true, "1.1");
+    routed["builderFee"] = json!("0.2");
+    assert!(reconciler.ingest_fill(&routed));
+    // Spot buy paying its fee in the base token
+    let mut spot = fill(5, "@107", "20.0", "10.0", true, "0.007");
+    spot["feeToken"] = json!("HYPE");
+    assert!(reconciler.ingest_fill(&spot));
+    // Duplicate and malformed fills are skipped
+    assert!(!reconciler.ingest_fill(&fill(1, "BTC", "50000.0", "0.1", true, "2.25")));
+    assert!(!reconciler.ingest_fill(&json!({"coin": "BTC"})));
+
+    let discrepancies = reconciler.discrepancies();
+    assert_eq!(discrepancies.len(), 1);
+    assert_eq!(discrepancies[0].tid, 3);
+    assert!(close(discrepancies[0].expected_fee, 0.9));
+    assert!(close(discrepancies[0].actual_rate(), 0.0005));
+    assert!((discrepancies[0].difference() - 0.1).abs() < 1e-9);
+
+    let summary = reconciler.summary();
+    assert_eq!(summary.fills, 5);
+    assert_eq!(summary.discrepancies, 1);
+    assert!((summary.actual_builder_fee - 0.2).abs() < 1e-9);
+    assert!((summary.maker_rate().unwrap() - 0.00015).abs() < 1e-12);
+    let taker_fee = 2.25 + 1.0 + 0.9 + 0.14;
+    assert!((summary.taker_rate().unwrap() - taker_fee / 9_200.0).abs() < 1e-9);
+
+    // Builder fee charged at twice the agreed rate
+    let mut overcharged = fill(6, "ETH", "2000.0", "1.0", true, "1.3");
+    overcharged["builderFee"] = json!("0.4");
+    assert_eq!(
+        reconciler.ingest_user_fills(&json!({"user": "0xabc", "fills": [overcharged]})),
+        1
+    );
+    let last = reconciler.discrepancies().last().unwrap();
+    assert_eq!(last.tid, 6);
+    assert!((last.actual_fee - 0.9).abs() < 1e-9);
+    assert!((last.expected_builder_fee - 0.2).abs() < 1e-9);
+
+    let mut csv = Vec::new();
+    reconciler.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("1700000000003,ETH,3,103,true,2000,"));
+}
+
+#[tokio::test]
+async fn test_fetch_user() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "userFillsByTime", "user": "0xabc", "startTime": 0}),
+        ))
+        .with_body(
+            json!([
+                fill(1, "BTC", "50000.0", "0.1", true, "2.25"),
+                fill(2, "SOL", "100.0", "10.0", true, "0.5")
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+
+    let mut reconciler = FeeReconciler::new(TieredFeeModel::hyperliquid());
+    assert_eq!(
+        reconciler
+            .fetch_user(&client, "0xabc", 0..u64::MAX)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(reconciler.discrepancies().len(), 1);
+    assert_eq!(reconciler.discrepancies()[0].coin, "SOL");
+}
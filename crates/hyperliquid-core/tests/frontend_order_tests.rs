@@ -0,0 +1,71 @@
+//! Tests for parsing `frontendOpenOrders` responses
+
+use hyperliquid_core::types::{FrontendOrder, FrontendOrderType};
+use serde_json::json;
+
+#[test]
+fn test_parses_parent_with_tpsl_children() {
+    let response = json!([{
+        "coin": "BTC", "side": "B", "limitPx": "60000.0", "sz": "0.1", "oid": 100,
+        "timestamp": 1_700_000_000_000u64, "triggerCondition": "N/A", "isTrigger": false,
+        "triggerPx": "0.0", "isPositionTpsl": false, "reduceOnly": false,
+        "orderType": "Limit", "origSz": "0.1", "tif": "Gtc", "cloid": null,
+        "children": [
+            {
+                "coin": "BTC", "side": "A", "limitPx": "66000.0", "sz": "0.0", "oid": 101,
+                "timestamp": 1_700_000_000_000u64, "triggerCondition": "Price above 65000",
+                "isTrigger": true, "triggerPx": "65000.0", "children": [],
+                "isPositionTpsl": false, "reduceOnly": true,
+                "orderType": "Take Profit Market", "origSz": "0.0", "tif": null, "cloid": null
+            },
+            {
+                "coin": "BTC", "side": "A", "limitPx": "54000.0", "sz": "0.0", "oid": 102,
+                "timestamp": 1_700_000_000_000u64, "triggerCondition": "Price below 55000",
+                "isTrigger": true, "triggerPx": "55000.0", "children": [],
+                "isPositionTpsl": false, "reduceOnly": true,
+                "orderType": "Stop Market", "origSz": "0.0", "tif": null, "cloid": null
+            }
+        ]
+    }]);
+
+    let orders: Vec<FrontendOrder> = serde_json::from_value(response).unwrap();
+    let parent = &orders[0];
+    assert!(parent.is_buy());
+    assert!(!parent.is_tpsl());
+    assert_eq!(parent.trigger_condition(), None);
+    assert_eq!(parent.trigger_px(), None);
+    assert_eq!(parent.tif.as_deref(), Some("Gtc"));
+
+    let tp = parent.take_profit().unwrap();
+    assert_eq!(tp.oid, 101);
+    assert_eq!(tp.order_type, FrontendOrderType::TakeProfitMarket);
+    assert!(tp.order_type.is_market());
+    assert_eq!(tp.trigger_px(), Some(65000.0));
+    assert_eq!(tp.trigger_condition(), Some("Price above 65000"));
+
+    let sl = parent.stop_loss().unwrap();
+    assert_eq!(sl.oid, 102);
+    assert!(sl.reduce_only);
+
+    let flattened: Vec<(Option<u64>, u64)> = parent
+        .flatten()
+        .into_iter()
+        .map(|(parent, order)| (parent, order.oid))
+        .collect();
+    assert_eq!(
+        flattened,
+        vec![(None, 100), (Some(100), 101), (Some(100), 102)]
+    );
+}
+
+#[test]
+fn test_unknown_order_type_still_parses() {
+    let order: FrontendOrder = serde_json::from_value(json!({
+        "coin": "ETH", "side": "A", "limitPx": "3000.0", "sz": "1.0", "oid": 7,
+        "timestamp": 1, "origSz": "1.0", "orderType": "Scale"
+    }))
+    .unwrap();
+    assert_eq!(order.order_type, FrontendOrderType::Unknown);
+    assert!(order.children.is_empty());
+    assert!(!order.is_trigger);
+}
@@ -0,0 +1,78 @@
+//! Tests for the funding carry analytics
+
+use hyperliquid_core::analytics::funding::HOURS_PER_YEAR;
+use hyperliquid_core::analytics::FundingAnalytics;
+use serde_json::json;
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn analytics() -> FundingAnalytics {
+    let mut analytics = FundingAnalytics::new();
+    let history = json!([
+        {"coin": "BTC", "fundingRate": "0.00001", "premium": "0.0001", "time": 3_600_000},
+        {"coin": "BTC", "fundingRate": "0.00003", "premium": "0.0002", "time": 7_200_000},
+        {"coin": "BTC", "fundingRate": "-0.00001", "premium": "-0.0001", "time": 10_800_000},
+    ]);
+    assert_eq!(analytics.ingest_history(&history).unwrap(), 3);
+    // Overlapping pages don't double count
+    assert_eq!(analytics.ingest_history(&history).unwrap(), 0);
+
+    analytics
+        .ingest_predicted(&json!([
+            ["BTC", [
+                ["BinPerp", {"fundingRate": "0.0002", "nextFundingTime": 1}],
+                ["HlPerp", {"fundingRate": "0.0000125", "nextFundingTime": 1}],
+                ["BybitPerp", null],
+            ]],
+        ]))
+        .unwrap();
+    analytics
+        .ingest_asset_ctxs(&json!([
+            {"universe": [{"name": "BTC", "szDecimals": 5, "maxLeverage": 50}]},
+            [{"funding": "0.00002", "markPx": "65065", "oraclePx": "65000",
+              "openInterest": "100", "dayNtlVlm": "1000000", "premium": "0.0001"}],
+        ]))
+        .unwrap();
+    analytics
+}
+
+#[test]
+fn test_carry_metrics() {
+    let metrics = analytics().metrics("BTC").unwrap();
+    assert_eq!(metrics.samples, 3);
+    assert!(approx(metrics.cumulative, 0.00003));
+    assert!(approx(
+        metrics.mean_annualized.unwrap(),
+        0.00001 * HOURS_PER_YEAR
+    ));
+    assert!(approx(metrics.positive_share.unwrap(), 2.0 / 3.0));
+    assert!(approx(
+        metrics.current_annualized.unwrap(),
+        0.00002 * HOURS_PER_YEAR
+    ));
+    assert!(approx(metrics.basis.unwrap(), 0.001));
+
+    // Binance's 8h rate against Hyperliquid's hourly prediction
+    assert_eq!(metrics.venues.len(), 1);
+    let (venue, spread) = metrics.best_venue_spread().unwrap();
+    assert_eq!(venue, "BinPerp");
+    assert!(approx(spread, (0.0002 / 8.0 - 0.0000125) * HOURS_PER_YEAR));
+}
+
+#[test]
+fn test_series_export() {
+    let analytics = analytics();
+    let series = analytics.series("BTC");
+    assert!(approx(series[1].cumulative, 0.00004));
+
+    let mut csv = Vec::new();
+    analytics.write_csv("BTC", &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "time,rate,annualized,cumulative");
+    assert!(lines[1].starts_with("3600000,0.00001,"));
+    assert!(analytics.series("ETH").is_empty());
+}
@@ -0,0 +1,87 @@
+//! Tests for HttpClient request/response capture
+
+use std::sync::Arc;
+
+use hyperliquid_core::client::capture::{redact_headers, REDACTED};
+use hyperliquid_core::client::HttpCapture;
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{json, Value};
+
+#[tokio::test]
+async fn test_captures_selected_endpoints_only() {
+    let mut server = mockito::Server::new_async().await;
+    let rejection = json!({"status": "err", "response": "Insufficient margin to place order."});
+    server
+        .mock("POST", "/exchange")
+        .with_body(rejection.to_string())
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/info")
+        .with_body("{}")
+        .create_async()
+        .await;
+
+    let capture = Arc::new(HttpCapture::new(2).with_endpoints(["/exchange"]));
+    let client = HttpClient::new(server.url(), HttpClientConfig::default())
+        .unwrap()
+        .with_capture(capture.clone());
+
+    let _: Value = client
+        .post("/info", &json!({"type": "meta"}))
+        .await
+        .unwrap();
+    let body = json!({"action": {"type": "order"}, "nonce": 1});
+    let response: Value = client.post("/exchange", &body).await.unwrap();
+    assert_eq!(response, rejection);
+
+    let captures = capture.captures();
+    assert_eq!(captures.len(), 1);
+    let captured = &captures[0];
+    assert!(captured.url.ends_with("/exchange"));
+    assert_eq!(captured.method, "POST");
+    assert_eq!(captured.attempt, 1);
+    assert_eq!(captured.status, Some(200));
+    assert_eq!(
+        captured.request_body.as_deref(),
+        Some(body.to_string().as_str())
+    );
+    assert_eq!(
+        captured.response_body.as_deref(),
+        Some(rejection.to_string().as_str())
+    );
+
+    // The ring buffer keeps the most recent captures
+    for nonce in 2..5 {
+        let _: Value = client
+            .post("/exchange", &json!({"nonce": nonce}))
+            .await
+            .unwrap();
+    }
+    let captures = capture.captures();
+    assert_eq!(captures.len(), 2);
+    assert_eq!(
+        capture.last().unwrap().request_body.as_deref(),
+        Some(r#"{"nonce":4}"#)
+    );
+}
+
+#[test]
+fn test_sensitive_headers_are_redacted() {
+    let mut headers = HeaderMap::new();
+    headers.insert("Authorization", HeaderValue::from_static("Bearer secret"));
+    headers.insert("X-Api-Key", HeaderValue::from_static("secret"));
+    headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+    let redacted = redact_headers(&headers);
+    let value = |name: &str| {
+        redacted
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_str())
+    };
+    assert_eq!(value("authorization"), Some(REDACTED));
+    assert_eq!(value("x-api-key"), Some(REDACTED));
+    assert_eq!(value("content-type"), Some("application/json"));
+}
@@ -0,0 +1,106 @@
+//! Tests for HttpClient response decompression
+
+use std::io::Write;
+
+use hyperliquid_core::{HttpClient, HttpClientConfig, HyperliquidError};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+fn candles() -> Value {
+    let candles: Vec<Value> = (0..500)
+        .map(|i| json!({"t": i * 60_000, "o": "65000", "c": "65010", "h": "65020", "l": "64990", "v": "1.5"}))
+        .collect();
+    Value::Array(candles)
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn brotli(data: &[u8]) -> Vec<u8> {
+    let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+    encoder.write_all(data).unwrap();
+    encoder.into_inner()
+}
+
+async fn serve(server: &mut mockito::Server, encoding: &str, body: Vec<u8>) -> mockito::Mock {
+    server
+        .mock("POST", "/info")
+        .match_header("accept-encoding", Matcher::Regex("gzip".to_string()))
+        .with_header("content-encoding", encoding)
+        .with_body(body)
+        .create_async()
+        .await
+}
+
+#[tokio::test]
+async fn test_gzip_and_brotli_bodies_are_decoded() {
+    let expected = candles();
+    let raw = expected.to_string().into_bytes();
+    for (encoding, body) in [("gzip", gzip(&raw)), ("br", brotli(&raw))] {
+        let mut server = mockito::Server::new_async().await;
+        let mock = serve(&mut server, encoding, body).await;
+        let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+        let candles: Value = client
+            .post("/info", &json!({"type": "candleSnapshot"}))
+            .await
+            .unwrap();
+        mock.assert_async().await;
+        assert_eq!(candles, expected, "{}", encoding);
+    }
+}
+
+#[tokio::test]
+async fn test_large_bodies_are_decoded_off_the_reactor() {
+    let expected = candles();
+    let mut server = mockito::Server::new_async().await;
+    serve(&mut server, "gzip", gzip(expected.to_string().as_bytes())).await;
+    let config = HttpClientConfig {
+        blocking_decompress_bytes: 0,
+        ..Default::default()
+    };
+    let client = HttpClient::new(server.url(), config).unwrap();
+    let candles: Value = client.post("/info", &json!({})).await.unwrap();
+    assert_eq!(candles, expected);
+}
+
+#[tokio::test]
+async fn test_decompressed_size_is_limited() {
+    let raw = candles().to_string().into_bytes();
+    let compressed = gzip(&raw);
+    let mut server = mockito::Server::new_async().await;
+    serve(&mut server, "gzip", compressed.clone()).await;
+    // The compressed body fits; the decompressed one does not
+    let config = HttpClientConfig {
+        max_response_bytes: compressed.len() * 2,
+        ..Default::default()
+    };
+    assert!(raw.len() > config.max_response_bytes);
+    let client = HttpClient::new(server.url(), config).unwrap();
+    let err = client
+        .post::<_, Value>("/info", &json!({}))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, HyperliquidError::ResponseTooLarge { .. }));
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_compression_can_be_disabled() {
+    let mut server = mockito::Server::new_async().await;
+    let mock = server
+        .mock("POST", "/info")
+        .match_header("accept-encoding", Matcher::Missing)
+        .with_body("{}")
+        .create_async()
+        .await;
+    let config = HttpClientConfig {
+        compression: false,
+        ..Default::default()
+    };
+    let client = HttpClient::new(server.url(), config).unwrap();
+    let _: Value = client.post("/info", &json!({})).await.unwrap();
+    mock.assert_async().await;
+}
@@ -0,0 +1,129 @@
+//! Tests for order journaling and crash-recovery replay
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use hyperliquid_core::journal::OrderJournal;
+use hyperliquid_core::oms::{OrderManager, OrderState};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::Matcher;
+use serde_json::json;
+
+const CLOID_A: &str = "0x0000000000000000000000000000000a";
+const CLOID_B: &str = "0x0000000000000000000000000000000b";
+
+fn temp_journal(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "hyperliquid-journal-{}-{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_replay_rebuilds_in_flight_state() {
+    let path = temp_journal("replay");
+    let journal = Arc::new(OrderJournal::open(&path).unwrap());
+    let oms = OrderManager::new().with_journal(journal).unwrap();
+
+    let acked = oms.record_submission("BTC", true, 0.02, 65000.0, Some(CLOID_A));
+    oms.record_response(
+        &[acked],
+        &json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"resting": {"oid": 77}}]
+        }}}),
+    );
+    oms.handle_user_fills(&json!({"user": "0xabc", "fills": [
+        {"coin": "BTC", "px": "65000", "sz": "0.01", "side": "B", "time": 1, "oid": 77, "tid": 1}
+    ]}));
+    // Crash before the response to this one
+    let in_flight = oms.record_submission("ETH", false, 1.0, 3000.0, Some(CLOID_B));
+    let before = oms.open_orders();
+    drop(oms);
+
+    let journal = Arc::new(OrderJournal::open(&path).unwrap());
+    assert_eq!(journal.len(), 4);
+    let oms = OrderManager::new().with_journal(journal).unwrap();
+    assert_eq!(oms.open_orders(), before);
+    assert_eq!(oms.get(acked).unwrap().state, OrderState::PartiallyFilled);
+    assert_eq!(oms.get(in_flight).unwrap().state, OrderState::New);
+
+    // Keys continue after the replayed ones
+    let next = oms.record_submission("SOL", true, 1.0, 150.0, None);
+    assert_ne!(next, in_flight);
+    assert_ne!(next, acked);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_partial_last_line_is_dropped() {
+    let path = temp_journal("partial");
+    let journal = Arc::new(OrderJournal::open(&path).unwrap());
+    let oms = OrderManager::new().with_journal(journal).unwrap();
+    oms.record_submission("BTC", true, 0.01, 65000.0, Some(CLOID_A));
+    drop(oms);
+
+    let mut contents = std::fs::read_to_string(&path).unwrap();
+    contents.push_str("{\"seq\":1,\"time\":1,\"type\":\"resp");
+    std::fs::write(&path, contents).unwrap();
+
+    let journal = Arc::new(OrderJournal::open(&path).unwrap());
+    assert_eq!(journal.len(), 1);
+    let oms = OrderManager::new().with_journal(journal).unwrap();
+    oms.handle_order_updates(&json!([
+        {"order": {"coin": "BTC", "oid": 5, "cloid": CLOID_A}, "status": "canceled", "statusTimestamp": 2}
+    ]));
+    drop(oms);
+
+    let journal = OrderJournal::open(&path).unwrap();
+    assert_eq!(journal.records().unwrap().len(), 2);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_recover_in_flight_queries_order_status() {
+    let mut server = mockito::Server::new_async().await;
+    let found = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "orderStatus", "oid": CLOID_A}),
+        ))
+        .with_body(
+            json!({"status": "order", "order": {
+                "order": {"coin": "BTC", "side": "B", "limitPx": "65000", "sz": "0.01",
+                          "oid": 88, "timestamp": 1, "origSz": "0.01", "cloid": CLOID_A},
+                "status": "open", "statusTimestamp": 1
+            }})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let unknown = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "orderStatus", "oid": CLOID_B}),
+        ))
+        .with_body(json!({"status": "unknownOid"}).to_string())
+        .create_async()
+        .await;
+
+    let oms = OrderManager::new();
+    let sent = oms.record_submission("BTC", true, 0.01, 65000.0, Some(CLOID_A));
+    let lost = oms.record_submission("ETH", false, 1.0, 3000.0, Some(CLOID_B));
+    let untraceable = oms.record_submission("SOL", true, 1.0, 150.0, None);
+
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let unresolved = oms.recover_in_flight(&client, "0xabc").await.unwrap();
+    found.assert_async().await;
+    unknown.assert_async().await;
+
+    assert_eq!(unresolved, vec![untraceable]);
+    let order = oms.get(sent).unwrap();
+    assert_eq!(order.state, OrderState::Acked);
+    assert_eq!(order.oid, Some(88));
+    assert_eq!(oms.get(lost).unwrap().state, OrderState::Rejected);
+}
@@ -0,0 +1,258 @@
+//! Tests for the trading kill switches
+
+use std::sync::{Arc, Mutex};
+
+use ethers_core::types::Address;
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::killswitch::{KillSwitchRegistry, Scope};
+use hyperliquid_core::oms::{OrderManager, TrackedOrder};
+use hyperliquid_core::store::{MemoryStore, StateStore};
+use hyperliquid_core::types::{ModifyRequest, OrderRequest};
+use hyperliquid_core::watchdog::KillSwitch;
+use hyperliquid_core::{
+    ExchangeClient, ExchangeClientConfig, HttpClient, HttpClientConfig, HyperliquidError,
+    InfoClient,
+};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+/// Records the oids it is asked to cancel
+#[derive(Clone, Default)]
+struct MockSwitch {
+    canceled: Arc<Mutex<Vec<u64>>>,
+}
+
+impl KillSwitch for MockSwitch {
+    async fn cancel_orders(&self, orders: &[TrackedOrder]) -> Result<(), HyperliquidError> {
+        let mut canceled = self.canceled.lock().unwrap();
+        canceled.extend(orders.iter().filter_map(|order| order.oid));
+        Ok(())
+    }
+
+    async fn schedule_cancel(&self, _time: Option<u64>) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+}
+
+fn resting(oms: &OrderManager, coin: &str, oid: u64) {
+    let key = oms.record_submission(coin, true, 1.0, 100.0, None);
+    oms.record_response(
+        &[key],
+        &json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"resting": {"oid": oid}}]
+        }}}),
+    );
+}
+
+#[test]
+fn test_scopes_and_persistence() {
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+    let clock = ManualClock::new(1_000);
+    let switches = KillSwitchRegistry::new()
+        .with_clock(Arc::new(clock))
+        .with_store(store.clone())
+        .unwrap();
+
+    switches.disable(Scope::coin("BTC"), "bad prints").unwrap();
+    assert!(!switches.is_enabled("BTC"));
+    assert!(switches.is_enabled("ETH"));
+    assert!(matches!(
+        switches.check("BTC"),
+        Err(HyperliquidError::TradingDisabled { ref reason, .. }) if reason == "bad prints"
+    ));
+
+    // The global switch covers every coin and is reported first
+    switches.disable(Scope::All, "maintenance").unwrap();
+    assert!(!switches.is_enabled("ETH"));
+    assert_eq!(switches.halt("BTC").unwrap().scope, Scope::All);
+
+    // Another process sharing the store sees the same switches
+    let other = KillSwitchRegistry::new().with_store(store.clone()).unwrap();
+    assert_eq!(other.halts(), switches.halts());
+    assert_eq!(other.halts()[1].since, 1_000);
+
+    other.enable(&Scope::All).unwrap();
+    assert!(!switches.is_enabled("ETH"));
+    switches.reload().unwrap();
+    assert!(switches.is_enabled("ETH"));
+    assert!(!switches.is_enabled("BTC"));
+}
+
+#[tokio::test]
+async fn test_exchange_client_refuses_disabled_coins() {
+    // Nothing listens here, so only a local rejection returns this error
+    let config = ExchangeClientConfig {
+        base_url: "http://127.0.0.1:1".to_string(),
+        ..ExchangeClientConfig::testnet(Address::zero())
+    };
+    let switches = KillSwitchRegistry::new();
+    let exchange = ExchangeClient::new(config).with_kill_switches(switches.clone());
+    switches.disable(Scope::coin("BTC"), "halted").unwrap();
+
+    let result = exchange
+        .order("BTC", true, "1", "50000", None, None, None, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::TradingDisabled { .. })
+    ));
+    // Reduce-only orders and other coins reach the network
+    let result = exchange
+        .order("BTC", false, "1", "50000", None, Some(true), None, None)
+        .await;
+    assert!(!matches!(
+        result,
+        Err(HyperliquidError::TradingDisabled { .. })
+    ));
+    let result = exchange
+        .order("ETH", true, "1", "3000", None, None, None, None)
+        .await;
+    assert!(!matches!(
+        result,
+        Err(HyperliquidError::TradingDisabled { .. })
+    ));
+
+    // Modifying an order re-prices it, so it is checked like a new one
+    let modify = |coin: &str| ModifyRequest {
+        oid: 7,
+        order: OrderRequest {
+            coin: coin.to_string(),
+            is_buy: true,
+            sz: "1".to_string(),
+            limit_px: "50000".to_string(),
+            reduce_only: None,
+            order_type: None,
+            time_in_force: None,
+            trigger_price: None,
+            trail_value: None,
+            close_on_trigger: None,
+        },
+    };
+    let result = exchange.modify_order(modify("BTC"), &[]).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::TradingDisabled { .. })
+    ));
+    let result = exchange.modify_order(modify("ETH"), &[]).await;
+    assert!(!matches!(
+        result,
+        Err(HyperliquidError::TradingDisabled { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_signed_order_actions_respect_switches() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+        .with_body(
+            json!({"universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 50, "onlyIsolated": false},
+                {"name": "ETH", "szDecimals": 4, "maxLeverage": 25, "onlyIsolated": false}
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let mut info =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    info.initialize_assets("").await.unwrap();
+    // Nothing listens here, so only a local rejection returns this error
+    let config = ExchangeClientConfig {
+        base_url: "http://127.0.0.1:1".to_string(),
+        ..ExchangeClientConfig::testnet(Address::zero())
+    };
+    let switches = KillSwitchRegistry::new();
+    let exchange = ExchangeClient::new(config)
+        .with_kill_switches(switches.clone())
+        .with_assets(info);
+    let wallet = Wallet::new(KEY, false).unwrap();
+    let order = |asset: u32, reduce_only: bool| -> Value {
+        json!({"type": "order", "orders": [{
+            "a": asset, "b": true, "p": "50000", "s": "1", "r": reduce_only,
+            "t": {"limit": {"tif": "Gtc"}}
+        }], "grouping": "na"})
+    };
+    let disabled = |result: Result<Value, HyperliquidError>| {
+        matches!(result, Err(HyperliquidError::TradingDisabled { .. }))
+    };
+
+    switches.disable(Scope::coin("BTC"), "halted").unwrap();
+    assert!(disabled(
+        exchange
+            .post_signed_action(order(0, false), &wallet, None)
+            .await
+    ));
+    assert!(!disabled(
+        exchange
+            .post_signed_action(order(0, true), &wallet, None)
+            .await
+    ));
+    assert!(!disabled(
+        exchange
+            .post_signed_action(order(1, false), &wallet, None)
+            .await
+    ));
+    // Modifies and TWAP orders are covered as well
+    let modify = json!({"type": "modify", "oid": 7, "order": {
+        "a": 0, "b": true, "p": "50000", "s": "1", "r": false,
+        "t": {"limit": {"tif": "Gtc"}}
+    }});
+    assert!(disabled(
+        exchange.post_signed_action(modify, &wallet, None).await
+    ));
+    let twap = |asset: u32| -> Value {
+        json!({"type": "twapOrder", "twap": {
+            "a": asset, "b": true, "s": "1", "r": false, "m": 30, "t": false
+        }})
+    };
+    assert!(disabled(
+        exchange.post_signed_action(twap(0), &wallet, None).await
+    ));
+    assert!(!disabled(
+        exchange.post_signed_action(twap(1), &wallet, None).await
+    ));
+    // An asset without a coin can't be checked against the BTC switch
+    assert!(matches!(
+        exchange
+            .post_signed_action(order(7, false), &wallet, None)
+            .await,
+        Err(HyperliquidError::Validation(_))
+    ));
+
+    // The global switch covers every asset
+    switches.disable(Scope::All, "maintenance").unwrap();
+    assert!(disabled(
+        exchange
+            .post_signed_action(order(1, false), &wallet, None)
+            .await
+    ));
+    assert!(disabled(
+        exchange
+            .post_signed_action(order(7, false), &wallet, None)
+            .await
+    ));
+}
+
+#[tokio::test]
+async fn test_disable_and_cancel_only_touches_the_scope() {
+    let oms = OrderManager::new();
+    resting(&oms, "BTC", 1);
+    resting(&oms, "ETH", 2);
+    resting(&oms, "BTC", 3);
+    let switch = MockSwitch::default();
+    let switches = KillSwitchRegistry::new();
+
+    let canceled = switches
+        .disable_and_cancel(Scope::coin("BTC"), "halted", &switch, &oms)
+        .await
+        .unwrap();
+    assert_eq!(canceled, 2);
+    assert_eq!(*switch.canceled.lock().unwrap(), vec![1, 3]);
+    assert!(!switches.is_enabled("BTC"));
+    assert!(switches.is_enabled("ETH"));
+}
@@ -0,0 +1,153 @@
+//! Tests for typed non-funding ledger updates
+
+use hyperliquid_core::types::{LedgerUpdate, LiquidatedPosition, UserLedgerUpdate};
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use mockito::Matcher;
+use serde_json::json;
+
+fn parse(delta: serde_json::Value) -> LedgerUpdate {
+    serde_json::from_value(delta).unwrap()
+}
+
+#[test]
+fn test_known_deltas_are_typed() {
+    assert_eq!(
+        parse(json!({"type": "deposit", "usdc": "1000.0"})),
+        LedgerUpdate::Deposit {
+            usdc: "1000.0".to_string()
+        }
+    );
+    assert_eq!(
+        parse(json!({"type": "withdraw", "usdc": "99.0", "nonce": 1700000000000u64, "fee": "1.0"})),
+        LedgerUpdate::Withdraw {
+            usdc: "99.0".to_string(),
+            nonce: 1700000000000,
+            fee: "1.0".to_string(),
+        }
+    );
+    assert_eq!(
+        parse(json!({"type": "accountClassTransfer", "usdc": "25.5", "toPerp": false})),
+        LedgerUpdate::AccountClassTransfer {
+            usdc: "25.5".to_string(),
+            to_perp: false,
+        }
+    );
+    assert_eq!(
+        parse(json!({"type": "spotGenesis", "token": "PURR", "amount": "1000"})),
+        LedgerUpdate::SpotGenesis {
+            token: "PURR".to_string(),
+            amount: "1000".to_string(),
+        }
+    );
+
+    let transfer = parse(json!({
+        "type": "internalTransfer",
+        "usdc": "10.0",
+        "user": "0x1111111111111111111111111111111111111111",
+        "destination": "0x2222222222222222222222222222222222222222",
+        "fee": "1.0"
+    }));
+    assert_eq!(transfer.kind(), "internalTransfer");
+    assert_eq!(transfer.usdc(), Some(10.0));
+
+    let spot = parse(json!({
+        "type": "spotTransfer",
+        "token": "PURR",
+        "amount": "5",
+        "usdcValue": "0.9",
+        "user": "0x1111111111111111111111111111111111111111",
+        "destination": "0x2222222222222222222222222222222222222222",
+        "fee": "0.0"
+    }));
+    assert_eq!(spot.kind(), "spotTransfer");
+    assert_eq!(spot.usdc(), None);
+}
+
+#[test]
+fn test_vault_and_liquidation_deltas() {
+    let deposit = parse(json!({
+        "type": "vaultDeposit",
+        "vault": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+        "usdc": "100.0"
+    }));
+    assert!(deposit.is_vault());
+    assert_eq!(deposit.usdc(), Some(100.0));
+
+    let withdraw = parse(json!({
+        "type": "vaultWithdraw",
+        "vault": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+        "user": "0x1111111111111111111111111111111111111111",
+        "requestedUsd": "50.0",
+        "commission": "0.5",
+        "closingCost": "0.01",
+        "basis": "45.0",
+        "netWithdrawnUsd": "49.49"
+    }));
+    assert!(withdraw.is_vault());
+    assert_eq!(withdraw.kind(), "vaultWithdraw");
+
+    let liquidation = parse(json!({
+        "type": "liquidation",
+        "liquidatedNtlPos": "3000.0",
+        "accountValue": "120.0",
+        "leverageType": "Cross",
+        "liquidatedPositions": [{"coin": "ETH", "szi": "-1.0"}]
+    }));
+    match &liquidation {
+        LedgerUpdate::Liquidation {
+            liquidated_positions,
+            ..
+        } => assert_eq!(
+            liquidated_positions,
+            &vec![LiquidatedPosition {
+                coin: "ETH".to_string(),
+                szi: "-1.0".to_string()
+            }]
+        ),
+        other => panic!("unexpected delta {:?}", other),
+    }
+    assert!(!liquidation.is_vault());
+}
+
+#[test]
+fn test_unknown_deltas_fall_back_to_other() {
+    let raw = json!({"type": "rewardsClaim", "amount": "3.2"});
+    let delta = parse(raw.clone());
+    assert_eq!(delta, LedgerUpdate::Other(raw.clone()));
+    assert_eq!(delta.kind(), "rewardsClaim");
+    // Serializes back to what was received
+    assert_eq!(serde_json::to_value(&delta).unwrap(), raw);
+
+    let typed = json!({"type": "deposit", "usdc": "1.0"});
+    assert_eq!(serde_json::to_value(parse(typed.clone())).unwrap(), typed);
+}
+
+#[tokio::test]
+async fn test_info_client_returns_typed_updates() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "userNonFundingLedgerUpdates", "startTime": 1}),
+        ))
+        .with_body(
+            json!([
+                {"time": 10, "hash": "0xaa", "delta": {"type": "deposit", "usdc": "500.0"}},
+                {"time": 20, "hash": "0xbb", "delta": {"type": "somethingNew", "value": 1}}
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    let updates: Vec<UserLedgerUpdate> = client
+        .user_non_funding_ledger_updates("0x1111111111111111111111111111111111111111", 1, None)
+        .await
+        .unwrap();
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates[0].hash, "0xaa");
+    assert_eq!(updates[0].delta.usdc(), Some(500.0));
+    assert_eq!(updates[1].delta.kind(), "somethingNew");
+}
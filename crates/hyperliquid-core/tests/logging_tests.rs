@@ -1,6 +1,6 @@
 //! Tests for tracing and structured logging functionality
 
-use hyperliquid_core::{LoggingConfig, init_tracing, generate_trace_id, request_span, log_request, log_response, log_error, log_retry};
+use hyperliquid_core::{LoggingConfig, RedactionConfig, RequestTrace, TRACE_ID_HEADER, init_tracing, generate_trace_id, request_span, log_request, log_response, log_error, log_retry};
 use tracing::{info_span, Instrument};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -174,6 +174,39 @@ fn test_logging_config_validation() {
     assert!(config.max_files <= 30); // Reasonable max files
 }
 
+#[test]
+fn test_logging_config_otlp_endpoint() {
+    let config = LoggingConfig::production().with_otlp_endpoint("http://localhost:4317");
+    assert_eq!(config.otlp_endpoint.as_deref(), Some("http://localhost:4317"));
+    assert_eq!(config.service_name, "hyperliquid-rs");
+    assert_eq!(config.trace_sample_ratio, 1.0);
+    assert!(LoggingConfig::default().otlp_endpoint.is_none());
+}
+
+#[test]
+fn test_request_trace_shared_across_attempts() {
+    let trace = RequestTrace::http("POST", "https://api.hyperliquid.xyz/exchange");
+
+    let first = trace.headers(&trace.attempt_span(1));
+    let retry = trace.headers(&trace.attempt_span(2));
+    assert_eq!(first.get(TRACE_ID_HEADER), retry.get(TRACE_ID_HEADER));
+    assert_eq!(first.get(TRACE_ID_HEADER).map(String::as_str), Some(trace.trace_id()));
+}
+
+#[test]
+fn test_redaction_enabled_by_default() {
+    assert!(LoggingConfig::default().redaction.enabled);
+    assert!(LoggingConfig::debug().redaction.enabled);
+
+    let redaction = RedactionConfig {
+        address_prefix: 4,
+        address_suffix: 2,
+        ..Default::default()
+    };
+    let line = "signer=0xabcdefabcdefabcdefabcdefabcdefabcdefabcd";
+    assert_eq!(redaction.redact(line), "signer=0xab...cd");
+}
+
 #[test]
 fn test_trace_id_uniqueness() {
     let mut trace_ids = std::collections::HashSet::new();
@@ -0,0 +1,289 @@
+//! Tests for the margin and liquidation calculator
+
+use ethers_core::types::Address;
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::margin::{MarginCalculator, MarginMode};
+use hyperliquid_core::types::{ModifyRequest, OrderRequest};
+use hyperliquid_core::{
+    ExchangeClient, ExchangeClientConfig, HttpClient, HttpClientConfig, HyperliquidError,
+    InfoClient,
+};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-6
+}
+
+fn calculator() -> MarginCalculator {
+    let calculator = MarginCalculator::new();
+    calculator
+        .load_state(&json!({
+            "assetPositions": [
+                {"type": "oneWay", "position": {
+                    "coin": "BTC", "szi": "1.0", "entryPx": "58000", "positionValue": "60000",
+                    "marginUsed": "1200", "maxLeverage": 50,
+                    "leverage": {"type": "cross", "value": 50}
+                }},
+                {"type": "oneWay", "position": {
+                    "coin": "ETH", "szi": "-10.0", "entryPx": "3100", "positionValue": "30000",
+                    "marginUsed": "4000", "maxLeverage": 25,
+                    "leverage": {"type": "isolated", "value": 10, "rawUsd": "33000"}
+                }},
+            ],
+            "crossMarginSummary": {"accountValue": "10000", "totalMarginUsed": "1200",
+                                   "totalNtlPos": "60000", "totalRawUsd": "-50000"},
+            "marginSummary": {"accountValue": "14000", "totalMarginUsed": "5200",
+                              "totalNtlPos": "90000", "totalRawUsd": "-17000"},
+            "withdrawable": "8800"
+        }))
+        .unwrap();
+    calculator
+}
+
+#[test]
+fn test_liquidation_prices() {
+    let report = calculator().report();
+    assert!(approx(report.cross_account_value, 10000.0));
+    assert!(approx(report.cross_maintenance_margin, 600.0));
+
+    let btc = &report.positions[0];
+    assert_eq!(btc.mode, MarginMode::Cross);
+    assert!(approx(btc.liquidation_px.unwrap(), 60000.0 - 9400.0 / 0.99));
+
+    let eth = &report.positions[1];
+    assert_eq!(eth.mode, MarginMode::Isolated);
+    assert!(approx(eth.maintenance_margin, 600.0));
+    assert!(approx(eth.liquidation_px.unwrap(), 33000.0 / 10.2));
+}
+
+#[test]
+fn test_marks_from_asset_ctx_stream() {
+    let calculator = calculator();
+    let mut updates = calculator.updates();
+    let liquidation_px = calculator.position("BTC").unwrap().liquidation_px.unwrap();
+
+    calculator.handle_active_asset_ctx(&json!({"coin": "BTC", "ctx": {
+        "markPx": "55000", "oraclePx": "55010", "funding": "0.0000125", "openInterest": "1000",
+        "dayNtlVlm": "1", "prevDayPx": "60000", "premium": "0", "impactPxs": ["54999", "55001"]
+    }}));
+    // Coins without a position don't publish
+    calculator.update_mark("SOL", 150.0);
+
+    let report = updates.try_recv().unwrap();
+    assert!(updates.try_recv().is_err());
+    assert!(approx(report.cross_account_value, 5000.0));
+    let btc = &report.positions[0];
+    // Moving the mark doesn't move the liquidation price
+    assert!(approx(btc.liquidation_px.unwrap(), liquidation_px));
+    assert!(approx(
+        btc.distance_to_liquidation.unwrap(),
+        (55000.0 - liquidation_px) / 55000.0
+    ));
+    assert_eq!(report.nearest_liquidation().unwrap().coin, "ETH");
+}
+
+#[test]
+fn test_check_order_against_free_margin() {
+    let calculator = calculator();
+
+    // 10000 cross value less 1200 margin on BTC at 50x
+    let margin = calculator.check_order("BTC", true, 1.0, None).unwrap();
+    assert!(approx(margin.available, 8800.0));
+    assert!(approx(margin.required, 1200.0));
+    assert!(approx(margin.leverage, 50.0));
+    // Reducing and flipping only pay for the size beyond flat
+    assert!(approx(
+        calculator
+            .check_order("BTC", false, 1.0, None)
+            .unwrap()
+            .required,
+        0.0
+    ));
+    assert!(approx(
+        calculator
+            .check_order("BTC", false, 1.5, None)
+            .unwrap()
+            .required,
+        600.0
+    ));
+
+    match calculator.check_order("BTC", true, 10.0, Some(50000.0)) {
+        Err(HyperliquidError::InsufficientMargin {
+            coin,
+            required,
+            available,
+        }) => {
+            assert_eq!(coin, "BTC");
+            assert!(approx(required, 10000.0));
+            assert!(approx(available, 8800.0));
+        }
+        other => panic!("expected insufficient margin, got {:?}", other),
+    }
+
+    // Unknown coins use the default leverage and need a price
+    assert!(matches!(
+        calculator.check_order("SOL", true, 1.0, None),
+        Err(HyperliquidError::Validation(_))
+    ));
+    calculator.update_mark("SOL", 150.0);
+    assert!(approx(
+        calculator
+            .check_order("SOL", true, 10.0, None)
+            .unwrap()
+            .required,
+        75.0
+    ));
+    calculator.set_leverage("SOL", 5.0);
+    assert!(approx(
+        calculator
+            .check_order("SOL", true, 10.0, None)
+            .unwrap()
+            .required,
+        300.0
+    ));
+    // Settings above the coin's maximum are capped
+    calculator.set_leverage("BTC", 100.0);
+    assert!(approx(
+        calculator
+            .check_order("BTC", true, 1.0, None)
+            .unwrap()
+            .leverage,
+        50.0
+    ));
+}
+
+#[tokio::test]
+async fn test_exchange_client_rejects_orders_locally() {
+    // Nothing listens here, so only a local rejection returns this error
+    let config = ExchangeClientConfig {
+        base_url: "http://127.0.0.1:1".to_string(),
+        ..ExchangeClientConfig::testnet(Address::zero())
+    };
+    let exchange = ExchangeClient::new(config).with_margin_check(calculator());
+
+    let result = exchange
+        .order("BTC", true, "10", "50000", None, None, None, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { .. })
+    ));
+    let result = exchange
+        .order("BTC", true, "abc", "50000", None, None, None, None)
+        .await;
+    assert!(matches!(result, Err(HyperliquidError::Validation(_))));
+    // Reduce-only orders skip the check and reach the network
+    let result = exchange
+        .order("BTC", false, "10", "50000", None, Some(true), None, None)
+        .await;
+    assert!(!matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { .. })
+    ));
+
+    // Typed modifies are checked like orders
+    let modify = ModifyRequest {
+        oid: 1,
+        order: OrderRequest {
+            coin: "BTC".to_string(),
+            is_buy: true,
+            sz: "10".to_string(),
+            limit_px: "50000".to_string(),
+            reduce_only: None,
+            order_type: None,
+            time_in_force: None,
+            trigger_price: None,
+            trail_value: None,
+            close_on_trigger: None,
+        },
+    };
+    let result = exchange.modify_order(modify, &[]).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_signed_order_actions_are_checked() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+        .with_body(
+            json!({"universe": [
+                {"name": "BTC", "szDecimals": 5, "maxLeverage": 50, "onlyIsolated": false},
+                {"name": "ETH", "szDecimals": 4, "maxLeverage": 25, "onlyIsolated": false}
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    let sent = server
+        .mock("POST", "/exchange")
+        .with_body(
+            json!({"status": "ok", "response": {"type": "order", "data": {
+                "statuses": [{"resting": {"oid": 1}}]
+            }}})
+            .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+    let mut info =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    info.initialize_assets("").await.unwrap();
+    let config = ExchangeClientConfig {
+        base_url: server.url(),
+        ..ExchangeClientConfig::testnet(Address::zero())
+    };
+    let exchange = ExchangeClient::new(config)
+        .with_margin_check(calculator())
+        .with_assets(info);
+    let wallet = Wallet::new(KEY, false).unwrap();
+    let order = |asset: u32, sz: &str, reduce_only: bool| -> Value {
+        json!({"type": "order", "orders": [{
+            "a": asset, "b": true, "p": "50000", "s": sz, "r": reduce_only,
+            "t": {"limit": {"tif": "Gtc"}}
+        }], "grouping": "na"})
+    };
+
+    let result = exchange
+        .post_signed_action(order(0, "10", false), &wallet, None)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { ref coin, .. }) if coin == "BTC"
+    ));
+    // Modifies are checked like orders
+    let modify = json!({"type": "modify", "oid": 1, "order": order(0, "10", false)["orders"][0]});
+    let result = exchange.post_signed_action(modify, &wallet, None).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { .. })
+    ));
+    // TWAP orders carry no price, so they are checked at the mark
+    let twap = json!({"type": "twapOrder", "twap": {
+        "a": 0, "b": true, "s": "10", "r": false, "m": 30, "t": false
+    }});
+    let result = exchange.post_signed_action(twap, &wallet, None).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::InsufficientMargin { ref coin, .. }) if coin == "BTC"
+    ));
+    // An asset without a coin can't be checked
+    let result = exchange
+        .post_signed_action(order(7, "0.01", false), &wallet, None)
+        .await;
+    assert!(matches!(result, Err(HyperliquidError::Validation(_))));
+
+    // Reduce-only orders skip the check and are sent
+    exchange
+        .post_signed_action(order(0, "10", true), &wallet, None)
+        .await
+        .unwrap();
+    sent.assert_async().await;
+}
@@ -0,0 +1,161 @@
+//! Tests for the candle and trade stream and its gap backfill
+
+use hyperliquid_core::stream::{interval_millis, MarketEventKind, MarketStream, WebSocketEvent};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const MINUTE: u64 = 60_000;
+
+fn candle(t: u64, close: &str) -> Value {
+    json!({"t": t, "T": t + MINUTE - 1, "s": "BTC", "i": "1m",
+           "o": "60000", "c": close, "h": "60100", "l": "59900", "v": "1.5", "n": 12})
+}
+
+fn trade(tid: u64, time: u64) -> Value {
+    json!({"coin": "BTC", "side": "B", "px": "60000", "sz": "0.1", "time": time,
+           "hash": "0x00", "tid": tid})
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+#[test]
+fn test_interval_lengths() {
+    assert_eq!(interval_millis("1m"), Some(MINUTE));
+    assert_eq!(interval_millis("4h"), Some(240 * MINUTE));
+    assert_eq!(interval_millis("1w"), Some(7 * 24 * 60 * MINUTE));
+    assert_eq!(interval_millis("1M"), None);
+}
+
+#[tokio::test]
+async fn test_skipped_candles_are_backfilled_before_the_live_one() {
+    let mut server = mockito::Server::new_async().await;
+    let snapshot = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({
+            "type": "candleSnapshot",
+            "req": {"coin": "BTC", "interval": "1m", "startTime": MINUTE}
+        })))
+        .with_body(
+            json!([
+                candle(MINUTE, "60050"),
+                candle(2 * MINUTE, "60060"),
+                candle(3 * MINUTE, "60070"),
+                candle(4 * MINUTE, "60080")
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let stream = MarketStream::new(client, "BTC", "1m");
+    let mut events = stream.events();
+
+    assert!(!stream.handle_candle(&candle(0, "60000")));
+    assert!(!stream.handle_candle(&candle(MINUTE, "60010")));
+    assert!(!stream.handle_candle(&candle(MINUTE, "60020")));
+    assert_eq!(events.try_recv().unwrap().time, 0);
+    assert_eq!(events.try_recv().unwrap().time, MINUTE);
+    assert_eq!(events.try_recv().unwrap().time, MINUTE);
+
+    // Two candles were never received
+    assert!(stream.handle_candle(&candle(4 * MINUTE, "60090")));
+    assert!(events.try_recv().is_err());
+    stream.handle_trades(&json!([trade(1, 4 * MINUTE + 10)]));
+    assert!(events.try_recv().is_err());
+
+    assert_eq!(stream.recover().await.unwrap(), 3);
+    snapshot.assert_async().await;
+
+    let recovered: Vec<_> = (0..3).map(|_| events.try_recv().unwrap()).collect();
+    assert!(recovered.iter().all(|event| event.recovered));
+    assert_eq!(
+        recovered.iter().map(|event| event.time).collect::<Vec<_>>(),
+        vec![MINUTE, 2 * MINUTE, 3 * MINUTE]
+    );
+    // The previous candle's final values come from the backfill
+    assert!(matches!(&recovered[0].kind, MarketEventKind::Candle(c) if c["c"] == "60050"));
+
+    let live = events.try_recv().unwrap();
+    assert!(!live.recovered);
+    assert!(matches!(&live.kind, MarketEventKind::Candle(c) if c["c"] == "60090"));
+    assert!(matches!(
+        events.try_recv().unwrap().kind,
+        MarketEventKind::Trade(_)
+    ));
+    assert!(events.try_recv().is_err());
+
+    // Stale candles are dropped
+    assert!(!stream.handle_candle(&candle(3 * MINUTE, "60070")));
+    assert!(events.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_reconnect_recovers_missed_trades_in_order() {
+    let mut server = mockito::Server::new_async().await;
+    let now = now_millis();
+    let minute = now - now % MINUTE - MINUTE;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "candleSnapshot"})))
+        .with_body(json!([candle(minute, "60000")]).to_string())
+        .create_async()
+        .await;
+    let trades = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "recentTrades", "coin": "BTC"}),
+        ))
+        .with_body(
+            json!([
+                trade(3, now - 500),
+                trade(2, now - 1_000),
+                trade(1, now - 2_000),
+                trade(0, now - 3_600_000)
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let stream = MarketStream::new(client, "BTC", "1m");
+    let mut events = stream.events();
+
+    stream.handle_candle(&candle(minute, "59990"));
+    stream.handle_trades(&json!([trade(1, now - 2_000), trade(1, now - 2_000)]));
+    assert!(!events.try_recv().unwrap().recovered);
+    assert!(!events.try_recv().unwrap().recovered);
+    assert!(events.try_recv().is_err());
+
+    stream
+        .handle_ws_event(&WebSocketEvent::Disconnected)
+        .await
+        .unwrap();
+    assert_eq!(
+        stream
+            .handle_ws_event(&WebSocketEvent::Connected)
+            .await
+            .unwrap(),
+        3
+    );
+    trades.assert_async().await;
+
+    let candle_event = events.try_recv().unwrap();
+    assert!(candle_event.recovered);
+    assert!(matches!(&candle_event.kind, MarketEventKind::Candle(c) if c["c"] == "60000"));
+    let tids: Vec<_> = (0..2)
+        .map(|_| match events.try_recv().unwrap().kind {
+            MarketEventKind::Trade(trade) => trade["tid"].as_u64().unwrap(),
+            other => panic!("unexpected event {:?}", other),
+        })
+        .collect();
+    assert_eq!(tids, vec![2, 3]);
+    assert!(events.try_recv().is_err());
+
+    // Nothing left to recover
+    assert_eq!(stream.recover().await.unwrap(), 0);
+}
@@ -11,6 +11,33 @@ use hyperliquid_core::{
     types::{SymbolInterner, SymbolId, OptimizedOrder, OrderSide, OrderType, TradingAllocator},
 };
 
+#[test]
+fn test_memory_profiler_background_watchdog() {
+    let mut allocator = ArenaAllocator::new(4 * 1024);
+    let arena_stats = allocator.stats_handle();
+    let alerts = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let alerts_clone = Arc::clone(&alerts);
+
+    let profiler = MemoryProfiler::new(Duration::from_millis(10))
+        .with_allocation_source("arena", move || arena_stats.lock().unwrap().clone())
+        .with_leak_watchdog(16 * 1024, move |_| {
+            alerts_clone.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+
+    profiler.start();
+    thread::sleep(Duration::from_millis(50));
+
+    // Retain memory well above the watchdog threshold
+    for _ in 0..64 {
+        allocator.allocate_bytes(&[0u8; 1024]);
+    }
+    thread::sleep(Duration::from_millis(50));
+
+    let samples = profiler.stop();
+    assert!(samples.iter().any(|s| s.allocated_bytes >= 64 * 1024));
+    assert_eq!(alerts.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
 #[test]
 fn test_memory_profiling() {
     let profiler = MemoryProfiler::new(Duration::from_millis(100));
@@ -0,0 +1,83 @@
+//! Tests for the per-dex meta cache
+
+use std::time::Duration;
+
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use mockito::Matcher;
+use serde_json::json;
+
+fn meta(coin: &str) -> String {
+    json!({"universe": [{"name": coin, "onlyIsolated": false, "szDecimals": 5, "maxLeverage": 50}]})
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_meta_is_cached_per_dex() {
+    let mut server = mockito::Server::new_async().await;
+    let main = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta", "dex": ""})))
+        .with_body(meta("BTC"))
+        .expect(1)
+        .create_async()
+        .await;
+    let builder = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta", "dex": "xyz"})))
+        .with_body(meta("xyz:TSLA"))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let info = InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    assert_eq!(
+        info.meta_cached_or_fetch("").await.unwrap().universe[0].name,
+        "BTC"
+    );
+    assert_eq!(
+        info.meta_cached_or_fetch("").await.unwrap().universe[0].name,
+        "BTC"
+    );
+    assert_eq!(info.meta_cached("").unwrap().universe[0].name, "BTC");
+    assert_eq!(
+        info.meta_cached_or_fetch("xyz").await.unwrap().universe[0].name,
+        "xyz:TSLA"
+    );
+
+    main.assert_async().await;
+    builder.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_stale_meta_is_served_while_refreshing() {
+    let mut server = mockito::Server::new_async().await;
+    let first = server
+        .mock("POST", "/info")
+        .with_body(meta("BTC"))
+        .expect(1)
+        .create_async()
+        .await;
+
+    let info = InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap())
+        .with_meta_ttl(Duration::ZERO);
+    assert_eq!(info.refresh_meta("").await.unwrap().universe[0].name, "BTC");
+    first.assert_async().await;
+    first.remove_async().await;
+
+    let second = server
+        .mock("POST", "/info")
+        .with_body(meta("ETH"))
+        .expect_at_least(1)
+        .create_async()
+        .await;
+    // Stale, so the old copy comes back and a refresh starts
+    assert_eq!(info.meta_cached("").unwrap().universe[0].name, "BTC");
+    for _ in 0..50 {
+        if info.meta_cached("").unwrap().universe[0].name == "ETH" {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(info.meta_cached("").unwrap().universe[0].name, "ETH");
+    second.assert_async().await;
+}
@@ -0,0 +1,135 @@
+//! Tests for the order lifecycle manager
+//!
+//! Drives `OrderManager` with placement responses and `orderUpdates` /
+//! `userFills` payloads in the exchange's wire format.
+
+use hyperliquid_core::oms::{OrderEventKind, OrderManager, OrderState};
+use serde_json::json;
+
+const CLOID: &str = "0x00000000000000000000000000000001";
+
+#[test]
+fn test_resting_order_fills_in_parts() {
+    let oms = OrderManager::new();
+    let key = oms.record_submission("BTC", true, 0.02, 65000.0, Some(CLOID));
+    assert_eq!(oms.get(key).unwrap().state, OrderState::New);
+
+    oms.record_response(
+        &[key],
+        &json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"resting": {"oid": 77}}]
+        }}}),
+    );
+    assert_eq!(oms.order(CLOID).unwrap().state, OrderState::Acked);
+    assert_eq!(oms.order_by_oid(77).unwrap().key, key);
+
+    oms.handle_user_fills(&json!({"user": "0xabc", "fills": [
+        {"coin": "BTC", "px": "65000", "sz": "0.01", "side": "B", "time": 1, "oid": 77, "tid": 1}
+    ]}));
+    let order = oms.get(key).unwrap();
+    assert_eq!(order.state, OrderState::PartiallyFilled);
+    assert!((order.remaining_sz() - 0.01).abs() < 1e-12);
+
+    // Snapshot replay after a reconnect must not double count
+    oms.handle_user_fills(&json!({"isSnapshot": true, "user": "0xabc", "fills": [
+        {"coin": "BTC", "px": "65000", "sz": "0.01", "side": "B", "time": 1, "oid": 77, "tid": 1}
+    ]}));
+    assert!((oms.get(key).unwrap().filled_sz - 0.01).abs() < 1e-12);
+
+    oms.handle_user_fills(&json!({"user": "0xabc", "fills": [
+        {"coin": "BTC", "px": "64990", "sz": "0.01", "side": "B", "time": 2, "oid": 77, "tid": 2}
+    ]}));
+    let order = oms.get(key).unwrap();
+    assert_eq!(order.state, OrderState::Filled);
+    assert!((order.avg_fill_px - 64995.0).abs() < 1e-9);
+    assert!(oms.open_orders().is_empty());
+}
+
+#[test]
+fn test_updates_before_response_are_applied_on_ack() {
+    let oms = OrderManager::new();
+    let key = oms.record_submission("ETH", false, 1.0, 3000.0, None);
+
+    // IOC fill and the final update race ahead of the placement response
+    oms.handle_user_fills(&json!({"user": "0xabc", "fills": [
+        {"coin": "ETH", "px": "3001", "sz": "1.0", "side": "A", "time": 1, "oid": 9, "tid": 5}
+    ]}));
+    oms.handle_order_updates(&json!([
+        {"order": {"coin": "ETH", "side": "A", "limitPx": "3000", "sz": "0", "oid": 9,
+                   "timestamp": 1, "origSz": "1.0"},
+         "status": "filled", "statusTimestamp": 1}
+    ]));
+    assert_eq!(oms.get(key).unwrap().state, OrderState::New);
+
+    oms.record_response(
+        &[key],
+        &json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"filled": {"oid": 9, "totalSz": "1.0", "avgPx": "3001"}}]
+        }}}),
+    );
+    let order = oms.get(key).unwrap();
+    assert_eq!(order.state, OrderState::Filled);
+    assert!((order.filled_sz - 1.0).abs() < 1e-12);
+    assert_eq!(order.avg_fill_px, 3001.0);
+}
+
+#[test]
+fn test_cancel_and_reject_events() {
+    let oms = OrderManager::new();
+    let mut events = oms.events();
+
+    let resting = oms.record_submission("BTC", true, 0.01, 60000.0, Some(CLOID));
+    let rejected = oms.record_submission("BTC", true, 100.0, 60000.0, None);
+    oms.record_response(
+        &[resting, rejected],
+        &json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+            {"resting": {"oid": 1}},
+            {"error": "Insufficient margin to place order."},
+        ]}}}),
+    );
+    oms.handle_order_updates(&json!([
+        {"order": {"coin": "BTC", "side": "B", "limitPx": "60000", "sz": "0.01", "oid": 1,
+                   "timestamp": 1, "origSz": "0.01", "cloid": CLOID},
+         "status": "marginCanceled", "statusTimestamp": 2}
+    ]));
+
+    let kinds: Vec<_> = std::iter::from_fn(|| events.try_recv().ok())
+        .map(|event| event.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            OrderEventKind::Submitted,
+            OrderEventKind::Submitted,
+            OrderEventKind::Acked,
+            OrderEventKind::Rejected {
+                reason: "Insufficient margin to place order.".to_string()
+            },
+            OrderEventKind::Canceled,
+        ]
+    );
+
+    let canceled = oms.get(resting).unwrap();
+    assert_eq!(canceled.state, OrderState::Canceled);
+    assert_eq!(canceled.reason.as_deref(), Some("marginCanceled"));
+    assert_eq!(oms.prune_closed(), 2);
+    assert!(oms.order(CLOID).is_none());
+}
+
+#[test]
+fn test_whole_request_error_rejects_all() {
+    let oms = OrderManager::new();
+    let keys = [
+        oms.record_submission("BTC", true, 0.01, 60000.0, None),
+        oms.record_submission("BTC", false, 0.01, 70000.0, None),
+    ];
+    oms.record_response(
+        &keys,
+        &json!({"status": "err", "response": "User or API Wallet does not exist."}),
+    );
+    for key in keys {
+        let order = oms.get(key).unwrap();
+        assert_eq!(order.state, OrderState::Rejected);
+        assert!(order.reason.unwrap().contains("does not exist"));
+    }
+}
@@ -0,0 +1,98 @@
+//! Tests for merging, filtering and sorting a user's open orders
+
+use hyperliquid_core::types::{BasicOpenOrder, FrontendOrder, FrontendOrderType, OpenOrders};
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const USER: &str = "0x1111111111111111111111111111111111111111";
+
+fn frontend(coin: &str, side: &str, oid: u64, timestamp: u64) -> Value {
+    json!({
+        "coin": coin, "side": side, "limitPx": "100.0", "sz": "1.0", "oid": oid,
+        "timestamp": timestamp, "triggerCondition": "N/A", "isTrigger": false,
+        "triggerPx": "0.0", "children": [], "isPositionTpsl": false, "reduceOnly": false,
+        "orderType": "Limit", "origSz": "1.0", "tif": "Gtc", "cloid": null
+    })
+}
+
+fn basic(coin: &str, side: &str, oid: u64, timestamp: u64) -> Value {
+    json!({"coin": coin, "side": side, "limitPx": "100.0", "sz": "1.0", "oid": oid,
+           "timestamp": timestamp})
+}
+
+fn merged() -> OpenOrders {
+    let frontend: Vec<FrontendOrder> = serde_json::from_value(json!([
+        frontend("BTC", "B", 1, 1_000),
+        frontend("ETH", "A", 2, 3_000)
+    ]))
+    .unwrap();
+    let basic: Vec<BasicOpenOrder> = serde_json::from_value(json!([
+        basic("BTC", "B", 1, 1_000),
+        basic("ETH", "A", 2, 3_000),
+        // Placed between the two requests
+        basic("BTC", "A", 3, 2_000)
+    ]))
+    .unwrap();
+    OpenOrders::merge(frontend, basic)
+}
+
+fn oids(orders: &OpenOrders) -> Vec<u64> {
+    orders.iter().map(|order| order.oid).collect()
+}
+
+#[test]
+fn test_merge_dedups_by_oid_preferring_frontend_entries() {
+    let orders = merged();
+    assert_eq!(orders.len(), 3);
+    // Newest first by default
+    assert_eq!(oids(&orders), vec![2, 3, 1]);
+
+    assert_eq!(orders.get(1).unwrap().order_type, FrontendOrderType::Limit);
+    let only_basic = orders.get(3).unwrap();
+    assert_eq!(only_basic.order_type, FrontendOrderType::Unknown);
+    assert_eq!(only_basic.orig_sz, "1.0");
+    assert!(only_basic.trigger_px().is_none());
+}
+
+#[test]
+fn test_filters_and_ordering() {
+    assert_eq!(oids(&merged().by_coin("BTC")), vec![3, 1]);
+    assert_eq!(oids(&merged().by_side(false)), vec![2, 3]);
+    assert_eq!(oids(&merged().by_coin("BTC").by_side(true)), vec![1]);
+    assert_eq!(oids(&merged().oldest_first()), vec![1, 3, 2]);
+    assert!(merged().by_coin("SOL").is_empty());
+
+    let coins: Vec<String> = merged().into_iter().map(|order| order.coin).collect();
+    assert_eq!(coins, vec!["ETH", "BTC", "BTC"]);
+}
+
+#[tokio::test]
+async fn test_info_client_merges_both_requests() {
+    let mut server = mockito::Server::new_async().await;
+    let frontend_mock = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "frontendOpenOrders", "user": USER}),
+        ))
+        .with_body(json!([frontend("BTC", "B", 1, 1_000)]).to_string())
+        .create_async()
+        .await;
+    let basic_mock = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "openOrders", "user": USER}),
+        ))
+        .with_body(json!([basic("BTC", "B", 1, 1_000), basic("ETH", "B", 4, 5_000)]).to_string())
+        .create_async()
+        .await;
+
+    let client =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    let orders = client.user_open_orders(USER, "").await.unwrap();
+    frontend_mock.assert_async().await;
+    basic_mock.assert_async().await;
+
+    assert_eq!(oids(&orders), vec![4, 1]);
+    assert_eq!(orders.get(1).unwrap().tif.as_deref(), Some("Gtc"));
+}
@@ -0,0 +1,104 @@
+//! Tests for aggregating user fills by order
+
+use hyperliquid_core::info::USER_FILLS_PAGE_LIMIT;
+use hyperliquid_core::types::{OrderExecution, UserFill};
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+const USER: &str = "0x1111111111111111111111111111111111111111";
+
+fn fill(oid: u64, tid: u64, time: u64, px: &str, sz: &str, cloid: Option<&str>) -> Value {
+    json!({
+        "coin": "BTC", "px": px, "sz": sz, "side": "B", "time": time,
+        "startPosition": "0.0", "dir": "Open Long", "closedPnl": "0.5",
+        "hash": "0x00", "oid": oid, "crossed": true, "fee": "0.1", "tid": tid,
+        "feeToken": "USDC", "cloid": cloid
+    })
+}
+
+fn fills(values: Vec<Value>) -> Vec<UserFill> {
+    serde_json::from_value(Value::Array(values)).unwrap()
+}
+
+#[test]
+fn test_fills_are_grouped_by_cloid_then_oid() {
+    let fills = fills(vec![
+        fill(2, 20, 2_000, "110", "1", None),
+        fill(1, 10, 1_000, "100", "1", Some("0x01")),
+        fill(1, 11, 1_500, "103", "2", Some("0x01")),
+        // Modified order: new oid, same cloid
+        fill(3, 30, 3_000, "106", "1", Some("0x01")),
+    ]);
+    let executions = OrderExecution::aggregate(&fills);
+    assert_eq!(executions.len(), 2);
+
+    let first = &executions[0];
+    assert_eq!(first.cloid.as_deref(), Some("0x01"));
+    assert_eq!(first.oids, vec![1, 3]);
+    assert!(first.is_buy);
+    assert_eq!(first.fill_count, 3);
+    assert_eq!(first.total_sz, 4.0);
+    assert!((first.avg_px - 103.0).abs() < 1e-9);
+    assert!((first.total_fee - 0.3).abs() < 1e-9);
+    assert!((first.net_pnl() - 1.2).abs() < 1e-9);
+    assert_eq!(
+        (first.first_fill_time, first.last_fill_time),
+        (1_000, 3_000)
+    );
+
+    let second = &executions[1];
+    assert_eq!(second.oids, vec![2]);
+    assert_eq!(second.cloid, None);
+    assert_eq!(second.notional(), 110.0);
+}
+
+#[tokio::test]
+async fn test_info_client_pages_through_the_range() {
+    let mut server = mockito::Server::new_async().await;
+    // A full first page; its last fill is repeated on the next one
+    let first_page: Vec<Value> = (0..USER_FILLS_PAGE_LIMIT as u64)
+        .map(|tid| fill(1, tid, 1_000 + tid * 2, "100", "0.01", None))
+        .collect();
+    let last_time = 1_000 + (USER_FILLS_PAGE_LIMIT as u64 - 1) * 2;
+    let first = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({
+            "type": "userFillsByTime", "user": USER, "startTime": 0, "endTime": 100_000
+        })))
+        .with_body(Value::Array(first_page.clone()).to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let second = server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({
+            "type": "userFillsByTime", "startTime": last_time
+        })))
+        .with_body(
+            json!([
+                first_page.last().unwrap().clone(),
+                fill(2, 99_999, last_time + 10, "200", "1", None),
+                // Outside the requested range
+                fill(3, 100_000, 100_000, "300", "1", None)
+            ])
+            .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let client =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    let executions = client
+        .user_fills_aggregated(USER, 0..100_000)
+        .await
+        .unwrap();
+    first.assert_async().await;
+    second.assert_async().await;
+
+    assert_eq!(executions.len(), 2);
+    assert_eq!(executions[0].fill_count, USER_FILLS_PAGE_LIMIT);
+    assert!((executions[0].total_sz - 20.0).abs() < 1e-9);
+    assert_eq!(executions[1].oids, vec![2]);
+}
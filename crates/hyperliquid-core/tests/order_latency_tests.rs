@@ -0,0 +1,175 @@
+//! Tests for order placement latency measurement
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::OrderLatencyTracker;
+use hyperliquid_core::{ExchangeClient, ExchangeClientConfig};
+use serde_json::{json, Value};
+
+fn order_action(orders: &[Value]) -> Value {
+    json!({"type": "order", "orders": orders, "grouping": "na"})
+}
+
+fn update(oid: u64, cloid: Option<&str>, status: &str) -> Value {
+    json!({
+        "order": {"coin": "ETH", "side": "B", "limitPx": "3000", "sz": "1", "oid": oid, "cloid": cloid},
+        "status": status,
+        "statusTimestamp": 1700000000000u64,
+    })
+}
+
+#[test]
+fn test_breakdown_is_matched_by_cloid() {
+    let tracker = OrderLatencyTracker::new(10);
+    let cloid = "0x00000000000000000000000000000001";
+    let id = tracker.start(&order_action(&[json!({"a": 4, "c": cloid})]));
+    tracker.signed(id);
+    tracker.sent(id);
+    tracker.responded(
+        id,
+        &json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+            {"resting": {"oid": 77, "cloid": cloid}}
+        ]}}}),
+    );
+    assert_eq!(tracker.pending_count(), 1);
+    assert!(tracker.completed().is_empty());
+
+    assert_eq!(
+        tracker.handle_order_updates(&json!([update(77, Some(cloid), "open")])),
+        1
+    );
+    assert_eq!(tracker.pending_count(), 0);
+
+    let latency = tracker.last().unwrap();
+    assert_eq!(latency.submission, id);
+    assert_eq!(latency.asset, Some(4));
+    assert_eq!(latency.cloid.as_deref(), Some(cloid));
+    assert_eq!(latency.oid, Some(77));
+    assert!(latency.sign.is_some());
+    assert!(latency.send.is_some());
+    assert!(latency.response.is_some());
+    let ack = latency.ack.unwrap();
+    let total = latency.total.unwrap();
+    assert!(total >= ack);
+    assert!(total >= latency.sign.unwrap());
+
+    // A later update of the same order is not counted again
+    assert_eq!(
+        tracker.handle_order_updates(&json!([update(77, Some(cloid), "filled")])),
+        0
+    );
+    assert_eq!(tracker.completed().len(), 1);
+}
+
+#[test]
+fn test_oid_matching_rejections_and_early_acks() {
+    let tracker = OrderLatencyTracker::new(10);
+    let id = tracker.start(&order_action(&[
+        json!({"a": 1}),
+        json!({"a": 1}),
+        json!({"a": 2}),
+    ]));
+    tracker.signed(id);
+    tracker.sent(id);
+
+    // The ack for the second order beats the placement response
+    assert_eq!(
+        tracker.handle_order_updates(&json!([update(11, None, "open")])),
+        0
+    );
+    tracker.responded(
+        id,
+        &json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+            {"resting": {"oid": 10}},
+            {"resting": {"oid": 11}},
+            {"error": "Order must have minimum value of $10."}
+        ]}}}),
+    );
+
+    let completed = tracker.completed();
+    assert_eq!(completed.len(), 2);
+    assert_eq!(completed[0].oid, Some(11));
+    assert!(completed[0].ack.is_some());
+    assert_eq!(completed[1].asset, Some(2));
+    assert_eq!(completed[1].ack, None);
+    assert_eq!(completed[1].total, None);
+    assert_eq!(tracker.pending_count(), 1);
+
+    assert_eq!(
+        tracker.handle_order_updates(&json!([update(10, None, "open")])),
+        1
+    );
+    assert_eq!(tracker.pending_count(), 0);
+
+    // An action rejected as a whole completes every order
+    let id = tracker.start(&order_action(&[json!({"a": 1})]));
+    tracker.responded(
+        id,
+        &json!({"status": "err", "response": "Insufficient margin"}),
+    );
+    assert_eq!(tracker.pending_count(), 0);
+    assert_eq!(tracker.last().unwrap().oid, None);
+}
+
+#[test]
+fn test_unacknowledged_orders_expire() {
+    let tracker = OrderLatencyTracker::new(1);
+    tracker.start(&order_action(&[json!({"a": 1}), json!({"a": 2})]));
+    assert_eq!(tracker.expire(Duration::from_secs(60)), 0);
+    assert_eq!(tracker.expire(Duration::ZERO), 2);
+    assert_eq!(tracker.pending_count(), 0);
+    assert!(tracker.completed().is_empty());
+}
+
+#[tokio::test]
+async fn test_signed_orders_are_tracked() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/exchange")
+        .with_body(
+            json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+                {"resting": {"oid": 42}}
+            ]}}})
+            .to_string(),
+        )
+        .expect(2)
+        .create_async()
+        .await;
+
+    let account = "0x1234567890abcdef1234567890abcdef12345678"
+        .parse()
+        .unwrap();
+    let mut config = ExchangeClientConfig::testnet(account);
+    config.base_url = server.url();
+    let tracker = Arc::new(OrderLatencyTracker::new(10));
+    let exchange = ExchangeClient::new(config).with_latency_tracker(tracker.clone());
+    let wallet = Wallet::generate_testnet().unwrap();
+
+    let order = json!({"a": 1, "b": true, "p": "3000", "s": "1", "r": false, "t": {"limit": {"tif": "Gtc"}}});
+    exchange
+        .post_signed_action(order_action(&[order]), &wallet, None)
+        .await
+        .unwrap();
+    assert_eq!(tracker.pending_count(), 1);
+    assert_eq!(
+        tracker.handle_order_updates(&json!([update(42, None, "open")])),
+        1
+    );
+    let latency = tracker.last().unwrap();
+    assert_eq!(latency.oid, Some(42));
+    assert!(latency.total.is_some());
+
+    // Only order actions are timed
+    exchange
+        .post_signed_action(
+            json!({"type": "cancel", "cancels": [{"a": 1, "o": 42}]}),
+            &wallet,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(tracker.pending_count(), 0);
+    assert_eq!(tracker.completed().len(), 1);
+}
@@ -0,0 +1,117 @@
+//! Tests for paging through time-windowed Info endpoints
+
+use std::sync::Arc;
+
+use futures::{StreamExt, TryStreamExt};
+use hyperliquid_core::info::{PageCursor, TimePaginator};
+use hyperliquid_core::scheduler::RateLimiter;
+use hyperliquid_core::store::{get_json, MemoryStore, StateStore};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::{Matcher, Mock, Server, ServerGuard};
+use serde_json::{json, Value};
+
+fn entry(time: u64, id: u64) -> Value {
+    json!({"time": time, "id": id})
+}
+
+async fn page(server: &mut ServerGuard, start: u64, entries: Vec<Value>) -> Mock {
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "userFunding", "startTime": start}),
+        ))
+        .with_body(Value::Array(entries).to_string())
+        .expect(1)
+        .create_async()
+        .await
+}
+
+fn paginator(server: &ServerGuard, range: std::ops::Range<u64>) -> TimePaginator {
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    TimePaginator::new(
+        client,
+        json!({"type": "userFunding", "user": "0xabc"}),
+        "time",
+        range,
+        3,
+    )
+}
+
+fn ids(entries: &[Value]) -> Vec<u64> {
+    entries.iter().map(|e| e["id"].as_u64().unwrap()).collect()
+}
+
+#[tokio::test]
+async fn test_pages_until_a_short_page() {
+    let mut server = Server::new_async().await;
+    // Pages restart at the last time; entry 3 is returned twice
+    let first = page(
+        &mut server,
+        100,
+        vec![entry(100, 1), entry(110, 2), entry(120, 3)],
+    )
+    .await;
+    let second = page(&mut server, 120, vec![entry(120, 3), entry(130, 4)]).await;
+
+    let limiter = RateLimiter::new(1200);
+    let entries: Vec<Value> = paginator(&server, 100..1_000)
+        .with_rate_limiter(limiter.clone())
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+    first.assert_async().await;
+    second.assert_async().await;
+    assert_eq!(ids(&entries), vec![1, 2, 3, 4]);
+    // Two requests of weight 20 were drawn from the budget
+    assert!(limiter.available() < 1200.0 - 35.0);
+}
+
+#[tokio::test]
+async fn test_resumes_from_the_saved_cursor() {
+    let mut server = Server::new_async().await;
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+
+    let first = page(&mut server, 0, vec![entry(10, 1), entry(20, 2)]).await;
+    let entries: Vec<Value> = paginator(&server, 0..1_000)
+        .with_store(store.clone(), "funding")
+        .stream()
+        .try_collect()
+        .await
+        .unwrap();
+    first.assert_async().await;
+    assert_eq!(ids(&entries), vec![1, 2]);
+    let cursor: PageCursor = get_json(store.as_ref(), "paginate/funding")
+        .unwrap()
+        .unwrap();
+    assert_eq!(cursor.start_time, 20);
+    assert_eq!(cursor.boundary.len(), 1);
+
+    // A later sync only yields what is new
+    let second = page(&mut server, 20, vec![entry(20, 2), entry(30, 3)]).await;
+    let paginator = paginator(&server, 0..1_000).with_store(store.clone(), "funding");
+    assert_eq!(paginator.cursor().unwrap().start_time, 20);
+    let entries: Vec<Value> = paginator.stream().try_collect().await.unwrap();
+    second.assert_async().await;
+    assert_eq!(ids(&entries), vec![3]);
+}
+
+#[tokio::test]
+async fn test_errors_end_the_stream() {
+    let mut server = Server::new_async().await;
+    // Not retryable, so the error surfaces straight away
+    server
+        .mock("POST", "/info")
+        .with_status(404)
+        .with_body("{}")
+        .create_async()
+        .await;
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+
+    let results: Vec<_> = TimePaginator::user_fills(client, "0xabc", 0..1_000)
+        .stream::<Value>()
+        .collect()
+        .await;
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+}
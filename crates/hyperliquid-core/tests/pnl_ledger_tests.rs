@@ -0,0 +1,129 @@
+//! Tests for the per-trade PnL ledger
+
+use hyperliquid_core::analytics::{CostBasis, PnlLedger, PnlRowKind};
+use hyperliquid_core::oms::OrderManager;
+use serde_json::{json, Value};
+
+const T: u64 = 1_704_067_200_000;
+
+fn fill(side: &str, px: &str, sz: &str, fee: &str, time: u64, tid: u64) -> Value {
+    json!({
+        "coin": "ETH", "px": px, "sz": sz, "side": side, "time": time, "oid": tid + 1000,
+        "tid": tid, "fee": fee, "feeToken": "USDC", "crossed": true, "hash": "0x0"
+    })
+}
+
+/// Two buys, funding over both lots, a partial close and a flip short
+fn ledger(method: CostBasis) -> PnlLedger {
+    let mut ledger = PnlLedger::new().with_method(method);
+    let fills = json!({"isSnapshot": true, "user": "0x0", "fills": [
+        fill("B", "100", "1", "0.1", T, 1),
+        fill("B", "110", "1", "0.1", T + 1, 2),
+        fill("A", "120", "1", "0.5", T + 3, 3),
+        fill("A", "90", "1.5", "0.2", T + 4, 4),
+    ]});
+    assert_eq!(ledger.ingest_user_fills(&fills), 4);
+    assert!(ledger.ingest_funding(&json!({
+        "time": T + 2,
+        "hash": "0x0",
+        "delta": {"type": "funding", "coin": "ETH", "usdc": "2.0", "szi": "2.0", "fundingRate": "0.0001"}
+    })));
+    ledger
+}
+
+#[test]
+fn test_fifo_closes_oldest_lots() {
+    let rows = ledger(CostBasis::Fifo).rows();
+    assert_eq!(rows.len(), 4);
+    assert!(rows.iter().all(|row| row.kind == PnlRowKind::Trade));
+
+    // Opening fills only pay their fee
+    assert_eq!(rows[0].closed_sz, 0.0);
+    assert_eq!(rows[0].net_pnl, -0.1);
+    assert_eq!(rows[1].position, 2.0);
+
+    let close = &rows[2];
+    assert_eq!(close.closed_sz, 1.0);
+    assert_eq!(close.cost_basis, Some(100.0));
+    assert_eq!(close.realized_pnl, 20.0);
+    assert_eq!(close.funding, 1.0);
+    assert_eq!(close.net_pnl, 20.5);
+
+    let flip = &rows[3];
+    assert_eq!(flip.closed_sz, 1.0);
+    assert_eq!(flip.cost_basis, Some(110.0));
+    assert_eq!(flip.realized_pnl, -20.0);
+    assert_eq!(flip.funding, 1.0);
+    assert_eq!(flip.position, -0.5);
+}
+
+#[test]
+fn test_average_cost_and_flat_funding() {
+    let mut ledger = ledger(CostBasis::AverageCost);
+    // Covers the short, then funding settles while flat
+    assert!(ledger.ingest_fill(&fill("B", "80", "0.5", "0", T + 5, 5)));
+    assert_eq!(
+        ledger.ingest_user_fundings(&json!({"isSnapshot": false, "user": "0x0", "fundings": [
+            {"time": T + 6, "coin": "ETH", "usdc": "-0.3", "szi": "0.0", "fundingRate": "0.0001"}
+        ]})),
+        1
+    );
+    let rows = ledger.rows();
+    assert_eq!(rows.len(), 6);
+
+    assert_eq!(rows[2].cost_basis, Some(105.0));
+    assert_eq!(rows[2].realized_pnl, 15.0);
+    assert_eq!(rows[2].funding, 1.0);
+    assert_eq!(rows[3].realized_pnl, -15.0);
+    // Short 0.5 opened at 90, covered at 80
+    assert_eq!(rows[4].cost_basis, Some(90.0));
+    assert_eq!(rows[4].realized_pnl, 5.0);
+    assert_eq!(rows[4].position, 0.0);
+
+    assert_eq!(rows[5].kind, PnlRowKind::Funding);
+    assert_eq!(rows[5].net_pnl, -0.3);
+}
+
+#[test]
+fn test_dedup_and_cloid_join() {
+    let oms = OrderManager::new();
+    let key = oms.record_submission("ETH", true, 1.0, 100.0, Some("0xc1"));
+    oms.record_status(key, &json!({"resting": {"oid": 1001}}));
+
+    let mut ledger = PnlLedger::new().with_orders(oms);
+    assert!(ledger.ingest_fill(&fill("B", "100", "1", "0.1", T, 1)));
+    assert!(!ledger.ingest_fill(&fill("B", "100", "1", "0.1", T, 1)));
+    assert!(!ledger.ingest_fill(&json!({"coin": "ETH"})));
+    // Fees in the bought token are valued at the fill price
+    let mut spot = fill("B", "2", "10", "0.01", T + 1, 2);
+    spot["coin"] = json!("PURR/USDC");
+    spot["feeToken"] = json!("PURR");
+    assert!(ledger.ingest_fill(&spot));
+
+    let rows = ledger.rows();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].cloid.as_deref(), Some("0xc1"));
+    assert_eq!(rows[1].cloid, None);
+    assert_eq!(rows[1].fee, 0.02);
+}
+
+#[test]
+fn test_csv_output() {
+    let mut csv = Vec::new();
+    ledger(CostBasis::Fifo).write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "time,coin,kind,side,px,sz,tid,oid,cloid,closed_sz,cost_basis,\
+         realized_pnl,fee,funding,net_pnl,position"
+    );
+    assert_eq!(lines.len(), 5);
+    assert_eq!(
+        lines[3],
+        format!(
+            "{},ETH,trade,sell,120,1,3,1003,,1,100,20,0.5,1,20.5,1",
+            T + 3
+        )
+    );
+}
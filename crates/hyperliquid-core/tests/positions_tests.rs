@@ -0,0 +1,98 @@
+//! Tests for the position and PnL tracker
+
+use hyperliquid_core::positions::PositionTracker;
+use serde_json::json;
+
+fn approx(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn test_open_add_reduce_and_flip() {
+    let tracker = PositionTracker::new();
+    tracker.apply_fill("BTC", true, 100.0, 1.0, 0.1);
+    tracker.apply_fill("BTC", true, 110.0, 1.0, 0.1);
+    let position = tracker.position("BTC").unwrap();
+    assert!(approx(position.szi, 2.0));
+    assert!(approx(position.entry_px, 105.0));
+
+    // Sell 3: closes 2 at +15 each, opens 1 short at 120
+    tracker.apply_fill("BTC", false, 120.0, 3.0, 0.3);
+    let position = tracker.position("BTC").unwrap();
+    assert!(approx(position.szi, -1.0));
+    assert!(approx(position.entry_px, 120.0));
+    assert!(approx(position.realized_pnl, 30.0));
+    assert!(approx(position.fees, 0.5));
+
+    tracker.update_mark("BTC", 115.0);
+    assert!(approx(tracker.total_unrealized_pnl(), 5.0));
+
+    tracker.apply_fill("BTC", true, 118.0, 1.0, 0.0);
+    let position = tracker.position("BTC").unwrap();
+    assert!(position.is_flat());
+    assert!(approx(position.entry_px, 0.0));
+    assert!(approx(position.realized_pnl, 32.0));
+}
+
+#[test]
+fn test_stream_payloads() {
+    let tracker = PositionTracker::new();
+    let fills = json!({"user": "0xabc", "fills": [
+        {"coin": "ETH", "px": "3000", "sz": "2", "side": "B", "time": 1, "oid": 1, "tid": 10,
+         "fee": "1.5", "closedPnl": "0", "dir": "Open Long", "startPosition": "0"}
+    ]});
+    tracker.handle_user_fills(&fills);
+    tracker.handle_user_fills(&fills);
+    // Historical snapshot is ignored
+    tracker.handle_user_fills(&json!({"isSnapshot": true, "user": "0xabc", "fills": [
+        {"coin": "ETH", "px": "2000", "sz": "5", "side": "B", "time": 0, "oid": 0, "tid": 1}
+    ]}));
+    tracker.handle_user_fundings(&json!({"user": "0xabc", "fundings": [
+        {"time": 2, "coin": "ETH", "usdc": "-0.75", "szi": "2", "fundingRate": "0.0000125"}
+    ]}));
+
+    let position = tracker.position("ETH").unwrap();
+    assert!(approx(position.szi, 2.0));
+    assert!(approx(position.cumulative_funding, -0.75));
+    assert!(approx(tracker.total_net_realized_pnl(), -2.25));
+}
+
+#[test]
+fn test_fill_dedup_is_bounded() {
+    let tracker = PositionTracker::new().with_seen_fills_capacity(2);
+    let fill = |tid: u64| {
+        json!({"user": "0xabc", "fills": [
+            {"coin": "ETH", "px": "3000", "sz": "1", "side": "B", "time": tid, "oid": tid, "tid": tid}
+        ]})
+    };
+    for tid in [1, 2, 2, 3] {
+        tracker.handle_user_fills(&fill(tid));
+    }
+    assert!(approx(tracker.position("ETH").unwrap().szi, 3.0));
+
+    // Trade 1 has been evicted by 2 and 3, so a redelivery is applied again
+    tracker.handle_user_fills(&fill(3));
+    tracker.handle_user_fills(&fill(1));
+    assert!(approx(tracker.position("ETH").unwrap().szi, 4.0));
+}
+
+#[test]
+fn test_reconcile_reports_divergence() {
+    let tracker = PositionTracker::new();
+    let mut alerts = tracker.alerts();
+    tracker.apply_fill("BTC", true, 65000.0, 0.1, 0.0);
+    tracker.apply_fill("SOL", false, 150.0, 10.0, 0.0);
+
+    let state = json!({"assetPositions": [
+        {"type": "oneWay", "position": {"coin": "BTC", "szi": "0.1", "entryPx": "65000.0"}},
+        {"type": "oneWay", "position": {"coin": "ETH", "szi": "1.0", "entryPx": "3000.0"}},
+    ]});
+    let divergences = tracker.reconcile_state(&state);
+    let coins: Vec<&str> = divergences.iter().map(|d| d.coin.as_str()).collect();
+    assert_eq!(coins, vec!["ETH", "SOL"]);
+    assert_eq!(alerts.try_recv().unwrap().coin, "ETH");
+
+    tracker.sync_from_state(&state);
+    assert!(tracker.reconcile_state(&state).is_empty());
+    assert!(tracker.position("SOL").unwrap().is_flat());
+}
@@ -0,0 +1,133 @@
+//! Tests for estimating market order impact from the L2 book
+
+use hyperliquid_core::analytics::{estimate_fill_price, max_size_within_slippage};
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::LegStatus;
+use hyperliquid_core::stream::LocalBook;
+use hyperliquid_core::types::L2BookSnapshot;
+use hyperliquid_core::{
+    ExchangeClient, ExchangeClientConfig, HttpClient, HttpClientConfig, HyperliquidError,
+};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+/// Mid of 100 with a thin bid and a deeper ask side
+fn book() -> Value {
+    let level = |px: &str, sz: &str| json!({"px": px, "sz": sz, "n": 1});
+    json!({
+        "coin": "BTC",
+        "levels": [
+            [level("99", "1"), level("98", "2")],
+            [level("101", "1"), level("102", "2"), level("104", "5")]
+        ],
+        "time": 1
+    })
+}
+
+#[test]
+fn test_estimate_walks_the_taken_side() {
+    let snapshot: L2BookSnapshot = serde_json::from_value(book()).unwrap();
+
+    let buy = estimate_fill_price(&snapshot, true, 2.0).unwrap();
+    assert!(buy.is_complete());
+    assert_eq!(buy.avg_px, 101.5);
+    assert_eq!(buy.worst_px, 102.0);
+    assert_eq!(buy.reference_px, 100.0);
+    assert_eq!(buy.levels, 2);
+    assert!((buy.impact_bps() - 150.0).abs() < 1e-9);
+    assert!((buy.worst_bps() - 200.0).abs() < 1e-9);
+
+    let sell = estimate_fill_price(&snapshot, false, 10.0).unwrap();
+    assert!(!sell.is_complete());
+    assert_eq!(sell.filled_sz, 3.0);
+    assert!((sell.avg_px - 295.0 / 3.0).abs() < 1e-9);
+    assert!(sell.impact_bps() > 0.0);
+
+    // A streamed book gives the same answers
+    let local = LocalBook::new("BTC");
+    assert!(local.apply(&book()));
+    assert_eq!(estimate_fill_price(&local, true, 2.0).unwrap(), buy);
+    assert!(estimate_fill_price(&LocalBook::new("ETH"), true, 1.0).is_none());
+}
+
+#[test]
+fn test_max_size_keeps_the_average_within_budget() {
+    let snapshot: L2BookSnapshot = serde_json::from_value(book()).unwrap();
+
+    // 1 @ 101 and 1 @ 102 average exactly 150 bps over the mid
+    assert_eq!(max_size_within_slippage(&snapshot, true, 150.0), 2.0);
+    let sz = max_size_within_slippage(&snapshot, true, 250.0);
+    let estimate = estimate_fill_price(&snapshot, true, sz).unwrap();
+    assert!((estimate.impact_bps() - 250.0).abs() < 1e-6);
+
+    // The touch is already beyond 50 bps
+    assert_eq!(max_size_within_slippage(&snapshot, true, 50.0), 0.0);
+    assert_eq!(max_size_within_slippage(&snapshot, false, 100.0), 1.0);
+    assert_eq!(max_size_within_slippage(&snapshot, false, 10_000.0), 3.0);
+}
+
+#[tokio::test]
+async fn test_market_order_prices_off_the_book() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "meta"})))
+        .with_body(
+            json!({"universe": [
+                {"name": "BTC", "szDecimals": 2, "maxLeverage": 50, "onlyIsolated": false}
+            ]})
+            .to_string(),
+        )
+        .create_async()
+        .await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "l2Book", "coin": "BTC"}),
+        ))
+        .with_body(book().to_string())
+        .create_async()
+        .await;
+    let order = server
+        .mock("POST", "/exchange")
+        .match_body(Matcher::PartialJson(
+            json!({"action": {"type": "order", "orders": [
+                {"a": 0, "b": true, "r": false, "t": {"limit": {"tif": "Ioc"}}}
+            ]}}),
+        ))
+        .with_body(
+            json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+                {"filled": {"totalSz": "2.0", "avgPx": "101.5", "oid": 9}}
+            ]}}})
+            .to_string(),
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let account = "0x1234567890abcdef1234567890abcdef12345678"
+        .parse()
+        .unwrap();
+    let mut config = ExchangeClientConfig::testnet(account);
+    config.base_url = server.url();
+    let exchange = ExchangeClient::new(config);
+    let info = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let wallet = Wallet::generate_testnet().unwrap();
+
+    let result = exchange
+        .market_order(&info, "BTC", true, 2.0, 200, &wallet, None)
+        .await
+        .unwrap();
+    assert_eq!(result.limit_px, 102.0);
+    assert_eq!(result.estimate.avg_px, 101.5);
+    assert_eq!(result.leg, LegStatus::Done);
+    assert_eq!(result.filled_sz(), 2.0);
+
+    // Nothing within 50 bps: not sent
+    let error = exchange
+        .market_order(&info, "BTC", true, 2.0, 50, &wallet, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, HyperliquidError::Validation(_)));
+    order.assert_async().await;
+}
@@ -0,0 +1,36 @@
+//! Tests for the Prometheus metrics endpoint
+
+#![cfg(feature = "prometheus")]
+
+use hyperliquid_core::{MetricsConfig, PrometheusExporter};
+
+#[tokio::test]
+async fn test_metrics_served_at_configured_path() {
+    let config = MetricsConfig {
+        endpoint: "/custom-metrics".to_string(),
+        namespace: "hl_test".to_string(),
+        ..Default::default()
+    };
+
+    let exporter = PrometheusExporter::install(&config).unwrap();
+    metrics::counter!("hyperliquid_test_scrapes_total").increment(3);
+
+    let server = exporter
+        .serve("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    let base = format!("http://{}", server.local_addr());
+
+    let response = reqwest::get(format!("{}/custom-metrics", base))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body = response.text().await.unwrap();
+    assert!(body.contains("hl_test_test_scrapes_total 3"));
+    assert!(!body.contains("hyperliquid_test_scrapes_total"));
+
+    let response = reqwest::get(format!("{}/metrics", base)).await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    server.shutdown().await;
+}
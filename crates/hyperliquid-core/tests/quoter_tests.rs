@@ -0,0 +1,141 @@
+//! Tests for the throttled quoter
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use hyperliquid_core::quoter::{ActionResult, QuoteAction, QuoteLevel, QuoteVenue, Quoter};
+use hyperliquid_core::HyperliquidError;
+
+/// Rests every order under a new oid and records what was sent
+#[derive(Clone, Default)]
+struct MockVenue {
+    sent: Arc<Mutex<Vec<Vec<QuoteAction>>>>,
+    next_oid: Arc<AtomicU64>,
+    reject_places: bool,
+}
+
+impl MockVenue {
+    fn batches(&self) -> Vec<Vec<QuoteAction>> {
+        std::mem::take(&mut *self.sent.lock().unwrap())
+    }
+}
+
+impl QuoteVenue for MockVenue {
+    async fn submit(&self, actions: &[QuoteAction]) -> Result<Vec<ActionResult>, HyperliquidError> {
+        self.sent.lock().unwrap().push(actions.to_vec());
+        Ok(actions
+            .iter()
+            .map(|action| match action {
+                QuoteAction::Cancel { .. } => ActionResult::Done,
+                QuoteAction::Place { .. } if self.reject_places => {
+                    ActionResult::Rejected("Post only order would have immediately matched".into())
+                }
+                _ => ActionResult::Resting {
+                    oid: self.next_oid.fetch_add(1, Ordering::Relaxed) + 1,
+                },
+            })
+            .collect())
+    }
+}
+
+fn level(px: f64, sz: f64) -> Option<QuoteLevel> {
+    Some(QuoteLevel::new(px, sz))
+}
+
+#[tokio::test]
+async fn test_updates_become_minimal_actions() {
+    let venue = MockVenue::default();
+    let quoter = Quoter::new(venue.clone(), 100).with_price_tolerance_bps(1.0);
+
+    quoter.set_quote("BTC", level(100.0, 1.0), level(101.0, 1.0));
+    assert_eq!(quoter.flush().await.unwrap(), 2);
+    let placed = venue.batches().concat();
+    assert!(placed
+        .iter()
+        .all(|action| matches!(action, QuoteAction::Place { .. })));
+    let bid = quoter.live("BTC", true).unwrap();
+    assert_eq!(bid.px, 100.0);
+
+    // Inside the tolerance: nothing to send
+    quoter.set_quote("BTC", level(100.005, 1.0), level(101.0, 1.0));
+    assert_eq!(quoter.flush().await.unwrap(), 0);
+
+    // A moved bid is modified in place, a pulled ask canceled first
+    quoter.set_quote("BTC", level(99.0, 1.0), None);
+    assert_eq!(quoter.flush().await.unwrap(), 2);
+    let actions = venue.batches().concat();
+    assert!(matches!(
+        actions[0],
+        QuoteAction::Cancel { is_buy: false, .. }
+    ));
+    assert!(
+        matches!(actions[1], QuoteAction::Modify { oid, px, .. } if oid == bid.oid && px == 99.0)
+    );
+    assert_eq!(quoter.live("BTC", false), None);
+    assert_ne!(quoter.live("BTC", true).unwrap().oid, bid.oid);
+}
+
+#[tokio::test]
+async fn test_rapid_updates_are_coalesced_under_the_rate() {
+    let venue = MockVenue::default();
+    let quoter = Quoter::new(venue.clone(), 2);
+
+    for coin in ["BTC", "ETH"] {
+        quoter.set_quote(coin, level(100.0, 1.0), level(101.0, 1.0));
+    }
+    // Superseded before anything was sent
+    for px in [98.0, 97.0, 96.0] {
+        quoter.set_quote("BTC", level(px, 1.0), level(101.0, 1.0));
+    }
+    assert_eq!(quoter.plan().len(), 4);
+
+    // Only two actions fit in the budget; the rest wait
+    assert_eq!(quoter.flush().await.unwrap(), 2);
+    assert_eq!(quoter.flush().await.unwrap(), 0);
+    assert_eq!(quoter.plan().len(), 2);
+    let sent = venue.batches().concat();
+    assert!(sent.iter().all(|action| match action {
+        QuoteAction::Place {
+            coin,
+            is_buy: true,
+            px,
+            ..
+        } if coin == "BTC" => *px == 96.0,
+        _ => true,
+    }));
+
+    let stats = quoter.stats();
+    assert_eq!(stats.coalesced, 3);
+    assert_eq!(stats.actions_sent, 2);
+}
+
+#[tokio::test]
+async fn test_rejected_target_waits_for_a_new_one() {
+    let venue = MockVenue {
+        reject_places: true,
+        ..Default::default()
+    };
+    let quoter = Quoter::new(venue.clone(), 100);
+
+    quoter.set_quote("BTC", level(100.0, 1.0), None);
+    assert_eq!(quoter.flush().await.unwrap(), 1);
+    assert_eq!(quoter.stats().rejected, 1);
+    // Not retried until the target changes
+    assert!(quoter.plan().is_empty());
+    quoter.set_quote("BTC", level(99.0, 1.0), None);
+    assert_eq!(quoter.plan().len(), 1);
+}
+
+#[tokio::test]
+async fn test_filled_quote_is_replaced() {
+    let venue = MockVenue::default();
+    let quoter = Quoter::new(venue.clone(), 100);
+
+    quoter.set_quote("BTC", level(100.0, 1.0), None);
+    quoter.flush().await.unwrap();
+    let oid = quoter.live("BTC", true).unwrap().oid;
+
+    quoter.order_done(oid);
+    assert_eq!(quoter.live("BTC", true), None);
+    assert!(matches!(quoter.plan()[..], [QuoteAction::Place { .. }]));
+}
@@ -0,0 +1,163 @@
+//! Tests for target-weight rebalancing
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyperliquid_core::execution::{
+    ChildOrder, ChildResult, ExecutionState, Executor, Quote, Venue,
+};
+use hyperliquid_core::rebalance::{Holdings, Rebalancer};
+use hyperliquid_core::HyperliquidError;
+use tokio::sync::watch;
+
+/// Venue filling every child in full and recording the order they arrive in
+#[derive(Clone, Default)]
+struct FillingVenue {
+    sent: Arc<Mutex<Vec<ChildOrder>>>,
+}
+
+impl Venue for FillingVenue {
+    async fn place(&self, order: &ChildOrder) -> Result<ChildResult, HyperliquidError> {
+        self.sent.lock().unwrap().push(order.clone());
+        Ok(ChildResult::Filled {
+            sz: order.sz,
+            avg_px: order.limit_px,
+        })
+    }
+
+    async fn cancel(&self, _coin: &str, _oid: u64) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+}
+
+fn quotes(px: f64) -> watch::Receiver<Option<Quote>> {
+    let (tx, rx) = watch::channel(Some(Quote { bid: px, ask: px }));
+    // Keep the sender alive for the duration of the test
+    std::mem::forget(tx);
+    rx
+}
+
+fn prices() -> HashMap<String, f64> {
+    [("BTC", 50_000.0), ("ETH", 2_000.0), ("SOL", 100.0)]
+        .into_iter()
+        .map(|(coin, px)| (coin.to_string(), px))
+        .collect()
+}
+
+#[test]
+fn test_plan_trades_only_out_of_band_coins() {
+    // BTC 0.55 (target 0.5), ETH 0.205 (target 0.2), SOL held without a target
+    let holdings = Holdings::new(10_000.0)
+        .with_position("BTC", 0.11)
+        .with_position("ETH", 1.025)
+        .with_position("SOL", -3.0);
+    let rebalancer =
+        Rebalancer::new([("BTC", 0.5), ("ETH", 0.2), ("DOGE", 0.0)]).with_tolerance(0.02);
+    let plan = rebalancer.plan(&holdings, &prices()).unwrap();
+
+    let legs: Vec<_> = plan
+        .legs
+        .iter()
+        .map(|leg| (leg.coin.as_str(), leg.is_buy))
+        .collect();
+    assert_eq!(legs, [("BTC", false), ("SOL", true)]);
+    assert!((plan.legs[0].sz - 0.01).abs() < 1e-9);
+    assert!((plan.legs[0].current_weight - 0.55).abs() < 1e-9);
+    // Closed exactly
+    assert_eq!(plan.legs[1].sz, 3.0);
+    assert!((plan.turnover() - 800.0).abs() < 1e-6);
+
+    // A wider band on BTC leaves it alone
+    let plan = rebalancer
+        .clone()
+        .with_band("BTC", 0.1)
+        .plan(&holdings, &prices())
+        .unwrap();
+    assert_eq!(plan.legs.len(), 1);
+}
+
+#[test]
+fn test_plan_orders_reducing_legs_first_and_drops_dust() {
+    let holdings = Holdings::new(10_000.0)
+        .with_position("BTC", 0.1)
+        .with_position("ETH", 0.5);
+    // BTC 0.5 -> 0.3 reduces; ETH 0.1 -> 0.3 adds, SOL opens short
+    let rebalancer = Rebalancer::new([("BTC", 0.3), ("ETH", 0.3), ("SOL", -0.1)]);
+    let plan = rebalancer.plan(&holdings, &prices()).unwrap();
+    let legs: Vec<_> = plan
+        .legs
+        .iter()
+        .map(|leg| (leg.coin.as_str(), leg.is_buy, leg.is_reducing()))
+        .collect();
+    assert_eq!(
+        legs,
+        [
+            ("BTC", false, true),
+            ("ETH", true, false),
+            ("SOL", false, false)
+        ]
+    );
+
+    let plan = rebalancer
+        .with_tolerance(0.0)
+        .with_min_notional(2_500.0)
+        .plan(&holdings, &prices())
+        .unwrap();
+    assert!(plan.is_empty());
+}
+
+#[test]
+fn test_plan_rejects_bad_input() {
+    let holdings = Holdings::new(1_000.0).with_position("XRP", 10.0);
+    let rebalancer = Rebalancer::new([("BTC", 0.5)]);
+    assert!(matches!(
+        rebalancer.plan(&holdings, &prices()),
+        Err(HyperliquidError::Validation(_))
+    ));
+    assert!(rebalancer.plan(&Holdings::new(0.0), &prices()).is_err());
+    assert!(Rebalancer::new([("BTC", f64::NAN)])
+        .plan(&Holdings::new(1_000.0), &prices())
+        .is_err());
+    assert!(Rebalancer::new([("BTC", 0.5)])
+        .with_tolerance(-0.1)
+        .plan(&Holdings::new(1_000.0), &prices())
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_execute_works_legs_through_executors() {
+    let holdings = Holdings::new(10_000.0)
+        .with_position("BTC", 0.1)
+        .with_position("ETH", 0.5);
+    let rebalancer = Rebalancer::new([("BTC", 0.3), ("ETH", 0.3)])
+        .with_schedule(Duration::from_millis(20), 2)
+        .with_max_slippage(0.05);
+    let plan = rebalancer.plan(&holdings, &prices()).unwrap();
+
+    let venue = FillingVenue::default();
+    let mut executors = HashMap::new();
+    for (coin, px) in prices() {
+        executors.insert(coin, Executor::new(venue.clone(), quotes(px)));
+    }
+    let reports = rebalancer.execute(&plan, &executors).await.unwrap();
+
+    assert_eq!(reports.len(), 2);
+    for (report, leg) in reports.iter().zip(&plan.legs) {
+        assert_eq!(report.state, ExecutionState::Completed);
+        assert!((report.filled_sz - leg.sz).abs() < 1e-9);
+    }
+    let sent = venue.sent.lock().unwrap();
+    assert_eq!(sent.len(), 4);
+    // The BTC sell finishes before the ETH buy starts
+    assert!(sent[..2]
+        .iter()
+        .all(|child| child.coin == "BTC" && !child.is_buy));
+    assert!(sent[2..]
+        .iter()
+        .all(|child| child.coin == "ETH" && child.is_buy));
+    assert!(sent.iter().all(|child| child.tif == "Ioc"));
+
+    executors.remove("ETH");
+    assert!(rebalancer.execute(&plan, &executors).await.is_err());
+}
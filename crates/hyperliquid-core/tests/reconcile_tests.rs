@@ -0,0 +1,95 @@
+//! Tests for fill, ledger and position reconciliation
+
+use std::time::Duration;
+
+use hyperliquid_core::positions::PositionTracker;
+use hyperliquid_core::reconcile::{ReconcileEvent, Reconciler};
+use serde_json::json;
+
+const NOW: u64 = 1_000_000;
+
+#[test]
+fn test_reports_missing_duplicate_and_unknown_fills() {
+    let reconciler = Reconciler::new("0xabc")
+        .with_start_time(0)
+        .with_grace(Duration::from_secs(10));
+    let fill = |tid: u64, time: u64| {
+        json!({"coin": "BTC", "px": "60000", "sz": "0.1", "side": "B", "time": time,
+               "oid": 7, "tid": tid, "fee": "1", "closedPnl": "0"})
+    };
+    reconciler.record_fills(&json!({"user": "0xabc", "fills": [fill(1, 100)]}));
+    reconciler.record_fills(&json!({"user": "0xabc", "fills": [fill(1, 100)]}));
+    reconciler.record_fills(&json!({"user": "0xabc", "fills": [fill(3, 200)]}));
+    // Snapshots replay history and aren't processing
+    reconciler.record_fills(&json!({"isSnapshot": true, "user": "0xabc", "fills": [fill(1, 100)]}));
+
+    // Fill 2 is missing locally; fill 4 is too recent to expect yet
+    let exchange = json!([fill(1, 100), fill(2, 150), fill(4, NOW - 1000)]);
+    let ledger = json!([
+        {"time": 300, "hash": "0x01", "delta": {"type": "deposit", "usdc": "500.0"}}
+    ]);
+    let events = reconciler
+        .reconcile_responses(&exchange, &ledger, None, NOW)
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            ReconcileEvent::MissingFill {
+                coin: "BTC".to_string(),
+                oid: 7,
+                tid: 2,
+                time: 150
+            },
+            ReconcileEvent::DuplicateFill { tid: 1, count: 2 },
+            ReconcileEvent::UnknownFill { tid: 3, time: 200 },
+            ReconcileEvent::LedgerChange {
+                time: 300,
+                hash: "0x01".to_string(),
+                kind: "deposit".to_string(),
+                usdc: Some(500.0)
+            },
+        ]
+    );
+
+    // Nothing new to report on the next run
+    assert!(reconciler
+        .reconcile_responses(&exchange, &ledger, None, NOW)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_reports_pnl_and_position_drift() {
+    let tracker = PositionTracker::new();
+    tracker.apply_fill("ETH", true, 3000.0, 1.0, 1.0);
+    tracker.apply_fill("ETH", false, 3100.0, 1.0, 1.0);
+    let reconciler = Reconciler::new("0xabc")
+        .with_start_time(0)
+        .with_position_tracker(tracker);
+    let mut events = reconciler.events();
+
+    let fills = json!([
+        {"coin": "ETH", "px": "3000", "sz": "1", "side": "B", "time": 1, "oid": 1, "tid": 1,
+         "fee": "1", "closedPnl": "0"},
+        {"coin": "ETH", "px": "3100", "sz": "1", "side": "A", "time": 2, "oid": 2, "tid": 2,
+         "fee": "1", "closedPnl": "90"},
+    ]);
+    let state = json!({"assetPositions": [
+        {"type": "oneWay", "position": {"coin": "ETH", "szi": "0.5", "entryPx": "3050"}}
+    ]});
+    let found = reconciler
+        .reconcile_responses(&fills, &json!([]), Some(&state), NOW)
+        .unwrap();
+
+    assert!(found.contains(&ReconcileEvent::PnlDrift {
+        local: 98.0,
+        exchange: 88.0,
+        difference: 10.0
+    }));
+    assert!(found.contains(&ReconcileEvent::PositionDrift {
+        coin: "ETH".to_string(),
+        local_szi: 0.0,
+        exchange_szi: 0.5
+    }));
+    assert!(events.try_recv().is_ok());
+}
@@ -0,0 +1,147 @@
+//! Tests for the market data recorder
+
+#![cfg(feature = "recorder")]
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use hyperliquid_core::data::{JsonlSink, RecordFormat, RecordSink, RecordedMessage, Recorder};
+use hyperliquid_core::stream::WebSocketResponse;
+use serde_json::{json, Value};
+
+/// Writer whose contents stay readable after the sink takes it
+#[derive(Clone, Default)]
+struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuf {
+    fn bytes(&self) -> Vec<u8> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn responses() -> Vec<WebSocketResponse> {
+    vec![
+        WebSocketResponse {
+            channel: "l2Book".to_string(),
+            data: json!({"coin": "BTC", "time": 1_000, "levels": [[], []]}),
+            time: None,
+            received_at: Instant::now(),
+        },
+        WebSocketResponse {
+            channel: "trades".to_string(),
+            data: json!([{"coin": "BTC", "px": "65000", "sz": "0.1", "time": 2_000}]),
+            time: None,
+            received_at: Instant::now(),
+        },
+        WebSocketResponse {
+            channel: "allMids".to_string(),
+            data: json!({"mids": {"BTC": "65000"}}),
+            time: Some(3_000),
+            received_at: Instant::now(),
+        },
+    ]
+}
+
+fn record(format: RecordFormat) -> Vec<u8> {
+    let buf = SharedBuf::default();
+    let recorder = Recorder::from_boxed(format.sink(buf.clone()).unwrap());
+    for response in responses() {
+        recorder.record(&response).unwrap();
+    }
+    recorder.finish().unwrap();
+    buf.bytes()
+}
+
+#[test]
+fn test_message_time_falls_back_to_the_envelope() {
+    let times: Vec<u64> = responses()
+        .iter()
+        .map(|response| RecordedMessage::from_response(response).time)
+        .collect();
+    assert_eq!(times, vec![1_000, 2_000, 3_000]);
+}
+
+#[test]
+fn test_jsonl_recording() {
+    let bytes = record(RecordFormat::Jsonl);
+    let lines: Vec<Value> = String::from_utf8(bytes)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["channel"], "l2Book");
+    assert_eq!(lines[0]["data"]["coin"], "BTC");
+    assert_eq!(lines[2]["time"], 3_000);
+
+    // Readable as recorded messages again
+    let mut sink = JsonlSink::new(Vec::new());
+    let message: RecordedMessage = serde_json::from_value(lines[1].clone()).unwrap();
+    sink.write(&message).unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Value>(&sink.into_inner()).unwrap(),
+        lines[1]
+    );
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn test_protobuf_recording_round_trips() {
+    use hyperliquid_core::data::recorder::decode_protobuf;
+
+    let bytes = record(RecordFormat::Protobuf);
+    let messages = decode_protobuf(&bytes).unwrap();
+    let expected: Vec<RecordedMessage> = responses()
+        .iter()
+        .map(RecordedMessage::from_response)
+        .collect();
+    assert_eq!(messages, expected);
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn test_arrow_ipc_recording() {
+    use arrow_array::{Array, StringArray, TimestampMillisecondArray};
+    use arrow_ipc::reader::StreamReader;
+    use hyperliquid_core::data::recorder::record_schema;
+
+    let bytes = record(RecordFormat::ArrowIpc);
+    let reader = StreamReader::try_new(&bytes[..], None).unwrap();
+    assert_eq!(reader.schema(), record_schema());
+    let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+    assert_eq!(batches.len(), 1);
+
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), 3);
+    let channels = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(channels.value(1), "trades");
+    let times = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<TimestampMillisecondArray>()
+        .unwrap();
+    assert_eq!(times.value(2), 3_000);
+    let data = batch
+        .column(2)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    let first: Value = serde_json::from_str(data.value(0)).unwrap();
+    assert_eq!(first["coin"], "BTC");
+}
@@ -0,0 +1,122 @@
+//! Tests for the strategy runner
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use hyperliquid_core::oms::TrackedOrder;
+use hyperliquid_core::runner::{Runner, Strategy, StrategyContext};
+use hyperliquid_core::stream::LocalBook;
+use hyperliquid_core::{Config, HyperliquidError};
+use serde::Deserialize;
+
+const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+/// Records whether it was started
+#[derive(Default)]
+struct Probe {
+    started: Arc<AtomicBool>,
+}
+
+impl Strategy for Probe {
+    async fn on_start(&mut self, _ctx: &StrategyContext) -> Result<(), HyperliquidError> {
+        self.started.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn on_book(
+        &mut self,
+        _ctx: &StrategyContext,
+        _book: &LocalBook,
+    ) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+
+    async fn on_fill(
+        &mut self,
+        _ctx: &StrategyContext,
+        _order: &TrackedOrder,
+        _px: f64,
+        _sz: f64,
+    ) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+
+    async fn on_timer(&mut self, _ctx: &StrategyContext) -> Result<(), HyperliquidError> {
+        Ok(())
+    }
+}
+
+fn config(toml: &str) -> Config {
+    toml::from_str(toml).unwrap()
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Params {
+    spread_bps: f64,
+    levels: u32,
+}
+
+#[test]
+fn test_strategy_section() {
+    let config = config(
+        r#"
+        [strategy]
+        coins = ["BTC", "ETH"]
+        timer_interval_ms = 250
+
+        [strategy.params]
+        spread_bps = 4.5
+        levels = 3
+        "#,
+    );
+    assert_eq!(config.strategy.coins, ["BTC", "ETH"]);
+    assert_eq!(config.strategy.timer_interval_ms, 250);
+    assert!(config.strategy.cancel_on_shutdown);
+    assert_eq!(
+        config.strategy.params::<Params>().unwrap(),
+        Params {
+            spread_bps: 4.5,
+            levels: 3
+        }
+    );
+
+    // Without the section nothing is streamed and params are empty
+    let config = Config::default();
+    assert!(config.strategy.coins.is_empty());
+    assert_eq!(config.strategy.timer_interval_ms, 1000);
+    assert!(matches!(
+        config.strategy.params::<Params>(),
+        Err(HyperliquidError::Config(_))
+    ));
+}
+
+#[test]
+fn test_runner_rejects_unusable_config() {
+    let missing_key = Runner::new(Config::default(), Probe::default());
+    assert!(matches!(missing_key, Err(HyperliquidError::Config(_))));
+
+    let zero_timer = config(&format!(
+        "[security]\nprivate_key = \"{}\"\n[strategy]\ntimer_interval_ms = 0\n",
+        KEY
+    ));
+    assert!(matches!(
+        Runner::new(zero_timer, Probe::default()),
+        Err(HyperliquidError::Config(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_startup_failure_skips_the_strategy() {
+    // Nothing listens here, so loading the universe fails
+    let config = config(&format!(
+        "[environment]\nenv = \"testnet\"\nbase_url = \"http://127.0.0.1:1\"\n\
+         [security]\nprivate_key = \"{}\"\n",
+        KEY
+    ));
+    let probe = Probe::default();
+    let started = probe.started.clone();
+    let runner = Runner::new(config, probe).unwrap();
+
+    assert!(runner.run().await.is_err());
+    assert!(!started.load(Ordering::SeqCst));
+}
@@ -1,7 +1,8 @@
 //! Tests for Tokio async runtime configuration
 
 use hyperliquid_core::runtime::{
-    RuntimeConfig, ConfiguredRuntime,
+    RuntimeConfig, ConfiguredRuntime, RuntimeMetricsSnapshot, ThreadPriority, ThreadRole,
+    spawn_role_thread,
     create_default_runtime, create_high_throughput_runtime,
     create_low_latency_runtime, create_single_threaded_runtime,
 };
@@ -191,6 +192,69 @@ fn test_runtime_invalid_configuration() {
     assert!(runtime.is_ok());
 }
 
+/// Test runtime metrics snapshot and background reporting
+#[test]
+fn test_runtime_metrics() {
+    let config = RuntimeConfig {
+        worker_threads: 2,
+        enable_metrics: true,
+        metrics_interval_ms: 10,
+        ..Default::default()
+    };
+
+    let runtime = ConfiguredRuntime::new(config).unwrap();
+    assert!(runtime.is_reporting_metrics());
+
+    runtime.block_on(async {
+        time::sleep(Duration::from_millis(50)).await;
+    });
+
+    let snapshot = runtime.metrics();
+    assert_eq!(snapshot.workers, 2);
+    // The metrics reporter itself is alive
+    assert!(snapshot.alive_tasks >= 1);
+
+    let snapshot = RuntimeMetricsSnapshot::capture(runtime.inner().handle());
+    assert_eq!(snapshot.workers, 2);
+
+    runtime.shutdown().unwrap();
+}
+
+/// Test runtime metrics are off by default
+#[test]
+fn test_runtime_metrics_disabled_by_default() {
+    let runtime = create_default_runtime().unwrap();
+    assert!(!runtime.is_reporting_metrics());
+    assert!(runtime.metrics().workers > 0);
+}
+
+/// Test worker thread naming, pinning and priority options
+#[test]
+fn test_runtime_thread_placement() {
+    let config = RuntimeConfig::new(2, 16, 1024 * 1024)
+        .with_thread_role(ThreadRole::Http)
+        .with_core_ids(vec![0])
+        .with_thread_priority(ThreadPriority::Normal);
+
+    let runtime = ConfiguredRuntime::new(config).unwrap();
+    let name = runtime.block_on(async {
+        tokio::spawn(async { std::thread::current().name().map(str::to_string) })
+            .await
+            .unwrap()
+    });
+    assert_eq!(name.as_deref(), Some("hyperliquid-http"));
+}
+
+/// Test role threads are named after their role
+#[test]
+fn test_spawn_role_thread() {
+    let handle = spawn_role_thread(ThreadRole::Signer, None, ThreadPriority::Normal, || {
+        std::thread::current().name().map(str::to_string)
+    })
+    .unwrap();
+    assert_eq!(handle.join().unwrap().as_deref(), Some("hyperliquid-signer"));
+}
+
 /// Test runtime drop without explicit shutdown
 #[test]
 fn test_runtime_drop_without_shutdown() {
@@ -0,0 +1,91 @@
+//! Tests for the rate-limit-aware polling scheduler
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::scheduler::{info_weight, Job, RateLimiter, Scheduler};
+use hyperliquid_core::HyperliquidError;
+
+#[test]
+fn test_limiter_budget_and_backoff() {
+    let limiter = RateLimiter::new(60);
+    assert!(limiter.try_acquire(50).is_ok());
+    let wait = limiter.try_acquire(20).unwrap_err();
+    assert!(wait > Duration::from_secs(9) && wait <= Duration::from_secs(10));
+
+    assert_eq!(limiter.back_off(None), Duration::from_secs(1));
+    assert_eq!(limiter.back_off(None), Duration::from_secs(2));
+    assert!(limiter.paused_for().is_some());
+    assert!(limiter.try_acquire(1).is_err());
+    assert_eq!(
+        limiter.back_off(Some(Duration::from_secs(5))),
+        Duration::from_secs(5)
+    );
+
+    limiter.record_success();
+    assert_eq!(limiter.back_off(None), Duration::from_secs(1));
+}
+
+#[test]
+fn test_start_checks_planned_weight() {
+    assert_eq!(info_weight("l2Book"), 2);
+    assert_eq!(info_weight("userFills"), 20);
+
+    let job = |name: &str, interval_ms: u64, weight: u32| {
+        Job::new(name, Duration::from_millis(interval_ms), weight, || async {
+            Ok(())
+        })
+    };
+    let scheduler = Scheduler::new(RateLimiter::hyperliquid())
+        .with_job(job("state", 1000, 2))
+        .with_job(job("fills", 2000, 20));
+    assert_eq!(scheduler.planned_weight_per_minute(), 720.0);
+
+    let rejected = Scheduler::new(RateLimiter::hyperliquid())
+        .with_job(job("fills", 1000, 20))
+        .start();
+    assert!(matches!(rejected, Err(HyperliquidError::Config(_))));
+}
+
+#[tokio::test]
+async fn test_jobs_run_and_back_off_on_429() {
+    let limiter = RateLimiter::new(60_000);
+    let runs = Arc::new(AtomicU32::new(0));
+    let limited = Arc::new(AtomicU32::new(0));
+
+    let counter = runs.clone();
+    let calls = limited.clone();
+    let handle = Scheduler::new(limiter.clone())
+        .with_jitter(0.0)
+        .with_job(Job::new("ok", Duration::from_millis(10), 1, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        }))
+        .with_job(Job::new(
+            "limited",
+            Duration::from_millis(10),
+            1,
+            move || {
+                let first = calls.fetch_add(1, Ordering::SeqCst) == 0;
+                async move {
+                    if first {
+                        Err(HyperliquidError::RateLimit("too many requests".to_string()))
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+        ))
+        .start()
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let stats = handle.stats();
+    assert_eq!(stats["limited"].rate_limited, 1);
+    assert!(limiter.paused_for().is_some());
+    // Both jobs are held by the shared pause after the first 429
+    assert!(runs.load(Ordering::SeqCst) <= 2);
+    assert_eq!(limited.load(Ordering::SeqCst), 1);
+    handle.shutdown();
+}
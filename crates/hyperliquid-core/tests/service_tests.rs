@@ -0,0 +1,92 @@
+//! Tests for the axum service helpers
+#![cfg(feature = "axum")]
+
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use hyperliquid_core::service::{Info, SdkRouterExt, SdkState};
+use hyperliquid_core::stream::WebSocketClient;
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use serde_json::Value;
+use tower::ServiceExt;
+
+async fn get_json(app: &Router, path: &str) -> (StatusCode, Value) {
+    let response = app
+        .clone()
+        .oneshot(Request::get(path).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn info_client() -> InfoClient {
+    InfoClient::new(HttpClient::new("http://127.0.0.1:1", HttpClientConfig::default()).unwrap())
+}
+
+#[tokio::test]
+async fn test_ready_follows_gates_and_websocket() {
+    let state = SdkState::new();
+    let warmup = state.gate("warmup");
+    let app = Router::new().with_sdk(state.clone());
+
+    let (status, body) = get_json(&app, "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["gates"]["warmup"], false);
+    assert_eq!(body["websocket"], Value::Null);
+
+    warmup.open();
+    assert!(state.gate("warmup").is_open());
+    let (status, body) = get_json(&app, "/ready").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["ready"], true);
+
+    // A disconnected stream keeps the service unready and degrades health
+    let state = state.with_websocket(Arc::new(WebSocketClient::new().unwrap()));
+    let app = Router::new().with_sdk(state);
+    let (status, body) = get_json(&app, "/ready").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["websocket"], false);
+    let (status, body) = get_json(&app, "/health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["websocket"]["connected"], false);
+    assert_eq!(body["websocket"]["subscriptions"], 0);
+}
+
+#[tokio::test]
+async fn test_extractors_and_require_ready_layer() {
+    async fn handler(Info(_info): Info) -> &'static str {
+        "ok"
+    }
+
+    let state = SdkState::new();
+    let app = Router::new()
+        .route("/info", get(handler))
+        .with_sdk(state.clone());
+    let (status, body) = get_json(&app, "/info").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["error"], "info client not configured");
+
+    let state = state.with_info(info_client());
+    let gate = state.gate("meta");
+    let app = Router::new()
+        .route("/info", get(handler).layer(state.require_ready()))
+        .with_sdk(state.clone());
+    let (status, body) = get_json(&app, "/info").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["error"], "not ready");
+
+    gate.open();
+    let response = app
+        .oneshot(Request::get("/info").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let (status, _) = get_json(&Router::new().with_sdk(state), "/health").await;
+    assert_eq!(status, StatusCode::OK);
+}
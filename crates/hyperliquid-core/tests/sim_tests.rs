@@ -0,0 +1,156 @@
+//! Tests for the paper-trading simulator
+
+#![cfg(feature = "sim")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::oms::{OrderManager, OrderState};
+use hyperliquid_core::sim::{SimConfig, SimExchange};
+use hyperliquid_core::Meta;
+use serde_json::{json, Value};
+
+fn sim() -> SimExchange {
+    let meta: Meta = serde_json::from_value(json!({"universe": [
+        {"name": "BTC", "onlyIsolated": false, "szDecimals": 5, "maxLeverage": 50},
+        {"name": "ETH", "onlyIsolated": false, "szDecimals": 4, "maxLeverage": 50},
+    ]}))
+    .unwrap();
+    SimExchange::new(&meta).with_config(SimConfig::default().with_fees(0.001, 0.0))
+}
+
+fn wallet() -> Wallet {
+    Wallet::new(
+        "0x1234567890123456789012345678901234567890123456789012345678901234",
+        false,
+    )
+    .unwrap()
+}
+
+fn book(coin: &str, bids: &[(&str, &str)], asks: &[(&str, &str)]) -> Value {
+    let side = |levels: &[(&str, &str)]| -> Vec<Value> {
+        levels
+            .iter()
+            .map(|(px, sz)| json!({"px": px, "sz": sz, "n": 1}))
+            .collect()
+    };
+    json!({"coin": coin, "time": 1, "levels": [side(bids), side(asks)]})
+}
+
+fn order(asset: u32, is_buy: bool, px: &str, sz: &str, tif: &str) -> Value {
+    json!({"type": "order", "grouping": "na", "orders": [
+        {"a": asset, "b": is_buy, "p": px, "s": sz, "r": false, "t": {"limit": {"tif": tif}}}
+    ]})
+}
+
+fn status(response: &Value) -> &Value {
+    &response["response"]["data"]["statuses"][0]
+}
+
+#[tokio::test]
+async fn test_taking_order_walks_the_book() {
+    let sim = sim();
+    let mut fills = sim.user_fills();
+    sim.apply_l2_book(&book(
+        "ETH",
+        &[("2999", "5")],
+        &[("3000", "1"), ("3001", "1"), ("3005", "10")],
+    ));
+
+    let response = sim
+        .post_signed_action(order(1, true, "3002", "3", "Ioc"), &wallet(), None)
+        .await
+        .unwrap();
+    assert_eq!(response["status"], "ok");
+    assert_eq!(status(&response)["filled"]["totalSz"], "2");
+    assert_eq!(status(&response)["filled"]["avgPx"], "3000.5");
+    assert_eq!(
+        fills.try_recv().unwrap()["fills"].as_array().unwrap().len(),
+        2
+    );
+    assert!((sim.fees_paid() - 6.001).abs() < 1e-9);
+
+    // Liquidity taken stays consumed until the next snapshot
+    let response = sim
+        .post_signed_action(order(1, true, "3002", "1", "Ioc"), &wallet(), None)
+        .await
+        .unwrap();
+    assert!(status(&response)["error"]
+        .as_str()
+        .unwrap()
+        .contains("could not immediately match"));
+
+    let response = sim
+        .post_signed_action(order(1, false, "2990", "1", "Alo"), &wallet(), None)
+        .await
+        .unwrap();
+    assert!(status(&response)["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Post only order would have immediately matched"));
+}
+
+#[tokio::test]
+async fn test_resting_order_fills_when_book_trades_through() {
+    let sim = sim();
+    let oms = OrderManager::new();
+    let mut fills = sim.user_fills();
+    let mut updates = sim.order_updates();
+    sim.apply_l2_book(&book("BTC", &[("64000", "1")], &[("64010", "1")]));
+
+    let key = oms.record_submission("BTC", true, 0.5, 64005.0, None);
+    let response = sim
+        .post_signed_action(order(0, true, "64005", "0.5", "Gtc"), &wallet(), None)
+        .await
+        .unwrap();
+    oms.record_response(&[key], &response);
+    assert_eq!(oms.get(key).unwrap().state, OrderState::Acked);
+    assert_eq!(sim.open_orders().len(), 1);
+
+    sim.apply_l2_book(&book("BTC", &[("64000", "1")], &[("64004", "0.2")]));
+    sim.apply_l2_book(&book("BTC", &[("64000", "1")], &[("64003", "1")]));
+    while let Ok(payload) = fills.try_recv() {
+        oms.handle_user_fills(&payload);
+    }
+    while let Ok(payload) = updates.try_recv() {
+        oms.handle_order_updates(&payload);
+    }
+
+    let tracked = oms.get(key).unwrap();
+    assert_eq!(tracked.state, OrderState::Filled);
+    // Maker fills at its own price with no fee
+    assert_eq!(tracked.avg_fill_px, 64005.0);
+    assert_eq!(sim.fees_paid(), 0.0);
+    assert!(sim.open_orders().is_empty());
+
+    let response = sim
+        .post_signed_action(
+            json!({"type": "cancel", "cancels": [{"a": 0, "o": 1}]}),
+            &wallet(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        status(&response)["error"],
+        "Order was never placed, already canceled, or filled."
+    );
+}
+
+#[tokio::test]
+async fn test_fills_are_stamped_with_the_clock() {
+    let clock = ManualClock::new(1_700_000_000_000);
+    let sim = sim().with_clock(Arc::new(clock.clone()));
+    let mut fills = sim.user_fills();
+    sim.apply_l2_book(&book("ETH", &[("2999", "5")], &[("3000", "5")]));
+
+    for expected in [1_700_000_000_000u64, 1_700_000_060_000] {
+        sim.post_signed_action(order(1, true, "3000", "1", "Ioc"), &wallet(), None)
+            .await
+            .unwrap();
+        assert_eq!(fills.try_recv().unwrap()["fills"][0]["time"], expected);
+        clock.advance(Duration::from_secs(60));
+    }
+}
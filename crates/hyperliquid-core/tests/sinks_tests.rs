@@ -0,0 +1,158 @@
+//! Tests for the message bus publisher
+#![cfg(feature = "sinks")]
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use hyperliquid_core::sinks::{Publisher, Sink, SinkEvent, SinkFuture};
+use hyperliquid_core::stream::WebSocketResponse;
+use serde_json::{json, Value};
+
+type Published = Arc<Mutex<Vec<(String, String, Vec<u8>)>>>;
+
+struct RecordingSink(Published);
+
+impl Sink for RecordingSink {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    fn publish<'a>(&'a self, topic: &'a str, key: &'a str, payload: Vec<u8>) -> SinkFuture<'a> {
+        Box::pin(async move {
+            self.0
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), key.to_string(), payload));
+            Ok(())
+        })
+    }
+}
+
+fn response(channel: &str, data: Value) -> WebSocketResponse {
+    WebSocketResponse {
+        channel: channel.to_string(),
+        data,
+        time: None,
+        received_at: Instant::now(),
+    }
+}
+
+fn book(bids: &[(&str, &str)], asks: &[(&str, &str)]) -> WebSocketResponse {
+    let side = |levels: &[(&str, &str)]| -> Vec<Value> {
+        levels
+            .iter()
+            .map(|(px, sz)| json!({"px": px, "sz": sz, "n": 1}))
+            .collect()
+    };
+    response(
+        "l2Book",
+        json!({"coin": "BTC", "time": 1_000, "levels": [side(bids), side(asks)]}),
+    )
+}
+
+#[tokio::test]
+async fn test_publishes_trades_as_json() {
+    let published = Published::default();
+    let publisher = Publisher::new(RecordingSink(published.clone())).with_per_coin_topics(true);
+
+    publisher
+        .handle(&response(
+            "trades",
+            json!([{"coin": "PURR/USDC", "side": "B", "px": "0.2", "sz": "100", "time": 5, "tid": 9, "hash": "0x0"}]),
+        ))
+        .await
+        .unwrap();
+
+    let published = published.lock().unwrap();
+    assert_eq!(published.len(), 1);
+    let (topic, key, payload) = &published[0];
+    assert_eq!(topic, "hyperliquid.trades.PURR_USDC");
+    assert_eq!(key, "PURR/USDC");
+    let payload: Value = serde_json::from_slice(payload).unwrap();
+    assert_eq!(payload["type"], "trade");
+    assert_eq!(payload["side"], "buy");
+    assert_eq!(payload["px"], 0.2);
+    assert_eq!(payload["tid"], 9);
+}
+
+#[test]
+fn test_book_deltas_only_carry_changed_levels() {
+    let publisher = Publisher::new(RecordingSink(Published::default()));
+
+    let first = publisher.normalize(&book(&[("100", "1"), ("99", "2")], &[("101", "1")]));
+    let SinkEvent::BookDelta(first) = &first[0] else {
+        panic!("expected a book delta");
+    };
+    assert!(first.snapshot);
+    assert_eq!(first.bids.len(), 2);
+
+    let second = publisher.normalize(&book(&[("100", "3")], &[("101", "1"), ("102", "5")]));
+    let SinkEvent::BookDelta(second) = &second[0] else {
+        panic!("expected a book delta");
+    };
+    assert!(!second.snapshot);
+    let bids: Vec<(f64, f64)> = second.bids.iter().map(|l| (l.px, l.sz)).collect();
+    assert_eq!(bids, vec![(100.0, 3.0), (99.0, 0.0)]);
+    let asks: Vec<(f64, f64)> = second.asks.iter().map(|l| (l.px, l.sz)).collect();
+    assert_eq!(asks, vec![(102.0, 5.0)]);
+
+    // An unchanged book publishes nothing
+    assert!(publisher
+        .normalize(&book(&[("100", "3")], &[("101", "1"), ("102", "5")]))
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_fills_skip_snapshots_and_key_by_user() {
+    let published = Published::default();
+    let publisher = Publisher::new(RecordingSink(published.clone())).with_topic_prefix("hl");
+    let fill = json!({
+        "coin": "ETH", "side": "A", "px": "2000", "sz": "0.5", "fee": "0.35",
+        "closedPnl": "12", "crossed": true, "oid": 7, "tid": 8, "time": 10
+    });
+
+    let user = "0xabc";
+    publisher
+        .handle(&response(
+            "userFills",
+            json!({"user": user, "isSnapshot": true, "fills": [fill.clone()]}),
+        ))
+        .await
+        .unwrap();
+    assert!(published.lock().unwrap().is_empty());
+
+    publisher
+        .handle(&response(
+            "userFills",
+            json!({"user": user, "fills": [fill]}),
+        ))
+        .await
+        .unwrap();
+    let published = published.lock().unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].0, "hl.fills");
+    assert_eq!(published[0].1, user);
+}
+
+#[cfg(feature = "avro")]
+#[test]
+fn test_avro_payload_has_registry_header() {
+    use hyperliquid_core::sinks::Encoding;
+
+    let publisher = Publisher::new(RecordingSink(Published::default()));
+    let event = publisher
+        .normalize(&response(
+            "trades",
+            json!([{"coin": "BTC", "side": "A", "px": "60000", "sz": "0.1", "time": 5, "tid": 9}]),
+        ))
+        .remove(0);
+
+    let plain = Encoding::Avro { schema_id: None }.encode(&event).unwrap();
+    let framed = Encoding::Avro {
+        schema_id: Some(42),
+    }
+    .encode(&event)
+    .unwrap();
+    assert_eq!(&framed[..5], &[0, 0, 0, 0, 42]);
+    assert_eq!(&framed[5..], &plain[..]);
+}
@@ -0,0 +1,91 @@
+//! Tests for the spot pair universe and its name/index/asset maps
+
+use hyperliquid_core::types::{SpotMeta, SpotUniverse, SPOT_ASSET_OFFSET};
+use hyperliquid_core::{HttpClient, HttpClientConfig, InfoClient};
+use mockito::Matcher;
+use serde_json::{json, Value};
+
+fn spot_meta() -> Value {
+    json!({
+        "universe": [
+            {"tokens": [1, 0], "name": "PURR/USDC", "index": 0, "isCanonical": true},
+            {"tokens": [150, 0], "name": "@107", "index": 107, "isCanonical": false}
+        ],
+        "tokens": [
+            {"name": "USDC", "szDecimals": 8, "weiDecimals": 8, "index": 0,
+             "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054", "isCanonical": true, "evmContract": null},
+            {"name": "PURR", "szDecimals": 0, "weiDecimals": 5, "index": 1,
+             "tokenId": "0xc1fb593aeffbeb02f85e0308e9956a90", "isCanonical": true, "evmContract": null},
+            {"name": "HYPE", "szDecimals": 2, "weiDecimals": 8, "index": 150,
+             "tokenId": "0x0d01dc56dcaaca66ad901c959b4011ec", "isCanonical": false, "evmContract": null}
+        ]
+    })
+}
+
+#[test]
+fn test_pairs_resolve_by_name_index_and_asset() {
+    let universe = SpotUniverse::from_spot_meta(&spot_meta()).unwrap();
+    assert_eq!(universe.len(), 2);
+
+    let purr = universe.pair("PURR/USDC").unwrap();
+    assert!(purr.is_canonical);
+    assert_eq!(purr.asset_id(), SPOT_ASSET_OFFSET);
+    assert_eq!(universe.sz_decimals("PURR/USDC"), Some(0));
+
+    // Non-canonical pairs resolve by wire name and by token names
+    let hype = universe.pair("HYPE/USDC").unwrap();
+    assert_eq!(hype.name, "@107");
+    assert_eq!(universe.coin("HYPE/USDC"), Some("@107"));
+    assert_eq!(universe.asset_id("@107"), Some(10_107));
+    assert_eq!(universe.pair_by_index(107), Some(hype));
+    assert_eq!(universe.pair_by_asset(10_107), Some(hype));
+    assert_eq!(universe.display_name(hype).as_deref(), Some("HYPE/USDC"));
+    assert_eq!(universe.token(hype.base()).unwrap().name, "HYPE");
+    assert_eq!(universe.token(hype.quote()).unwrap().name, "USDC");
+    assert_eq!(universe.sz_decimals("@107"), Some(2));
+
+    assert!(universe.pair("BTC/USDC").is_none());
+    assert!(universe.pair_by_asset(5).is_none());
+}
+
+#[test]
+fn test_spot_meta_carries_the_universe() {
+    let meta: SpotMeta = serde_json::from_value(json!({
+        "name": "spot",
+        "onlyIsolated": false,
+        "type_": null,
+        "tokens": [],
+        "universe": spot_meta()["universe"]
+    }))
+    .unwrap();
+    assert_eq!(meta.universe.len(), 2);
+    assert_eq!(meta.universe[1].tokens, [150, 0]);
+
+    // Older payloads without pairs still parse
+    let meta: SpotMeta = serde_json::from_value(json!({
+        "name": "spot", "onlyIsolated": false, "type_": null, "tokens": []
+    }))
+    .unwrap();
+    assert!(meta.universe.is_empty());
+}
+
+#[tokio::test]
+async fn test_info_client_resolves_spot_assets() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(json!({"type": "spotMeta"})))
+        .with_body(spot_meta().to_string())
+        .create_async()
+        .await;
+
+    let mut client =
+        InfoClient::new(HttpClient::new(server.url(), HttpClientConfig::default()).unwrap());
+    assert_eq!(client.asset_for_coin("HYPE/USDC"), None);
+    client.initialize_spot_assets().await.unwrap();
+
+    assert_eq!(client.asset_for_coin("HYPE/USDC"), Some(10_107));
+    assert_eq!(client.asset_for_coin("PURR/USDC"), Some(10_000));
+    assert_eq!(client.sz_decimals_for_coin("@107"), Some(2));
+    assert_eq!(client.spot_pairs().unwrap().len(), 2);
+}
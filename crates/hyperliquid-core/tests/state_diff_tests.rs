@@ -0,0 +1,114 @@
+//! Tests for diffing successive account snapshots
+
+use hyperliquid_core::state_diff::{StateChange, StateDiffer};
+use hyperliquid_core::types::ClearinghouseState;
+use serde_json::{json, Value};
+
+fn position(coin: &str, szi: &str, entry_px: &str) -> Value {
+    json!({"type": "oneWay", "position": {
+        "coin": coin, "szi": szi, "leverage": {"type": "cross", "value": 10},
+        "entryPx": entry_px, "positionValue": "0.0", "unrealizedPnl": "0.0",
+        "returnOnEquity": "0.0", "liquidationPx": null, "marginUsed": "0.0",
+        "maxLeverage": 50
+    }})
+}
+
+fn state(margin_used: &str, withdrawable: &str, positions: Vec<Value>) -> ClearinghouseState {
+    let summary = json!({"accountValue": "10000.0", "totalNtlPos": "0.0",
+                         "totalRawUsd": "10000.0", "totalMarginUsed": margin_used});
+    serde_json::from_value(json!({
+        "marginSummary": summary,
+        "crossMarginSummary": summary,
+        "withdrawable": withdrawable,
+        "assetPositions": positions,
+        "time": 1
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_position_changes() {
+    let mut differ = StateDiffer::new();
+    let baseline = state(
+        "100.0",
+        "9900.0",
+        vec![
+            position("BTC", "0.1", "60000.0"),
+            position("ETH", "1.0", "3000.0"),
+        ],
+    );
+    assert!(differ.diff(&baseline).is_empty());
+    assert!(differ.diff(&baseline).is_empty());
+
+    let changes = differ.diff(&state(
+        "100.0",
+        "9900.0",
+        vec![
+            position("BTC", "0.2", "61000.0"),
+            position("ETH", "-0.5", "3100.0"),
+            position("SOL", "10.0", "150.0"),
+        ],
+    ));
+    assert_eq!(
+        changes,
+        vec![
+            StateChange::PositionResized {
+                coin: "BTC".to_string(),
+                previous_szi: 0.1,
+                szi: 0.2,
+                entry_px: Some(61000.0),
+            },
+            StateChange::PositionClosed {
+                coin: "ETH".to_string(),
+                previous_szi: 1.0,
+            },
+            StateChange::PositionOpened {
+                coin: "ETH".to_string(),
+                szi: -0.5,
+                entry_px: Some(3100.0),
+            },
+            StateChange::PositionOpened {
+                coin: "SOL".to_string(),
+                szi: 10.0,
+                entry_px: Some(150.0),
+            },
+        ]
+    );
+
+    let changes = differ.diff(&state(
+        "100.0",
+        "9900.0",
+        vec![position("BTC", "0.2", "61000.0")],
+    ));
+    assert_eq!(changes.len(), 2);
+    assert!(changes
+        .iter()
+        .all(|change| matches!(change, StateChange::PositionClosed { .. })));
+}
+
+#[test]
+fn test_margin_and_withdrawable_respect_the_minimum_change() {
+    let mut differ = StateDiffer::new().with_min_change(1.0);
+    differ.diff(&state("100.0", "9900.0", vec![]));
+
+    assert!(differ.diff(&state("100.6", "9899.4", vec![])).is_empty());
+    // Small moves add up against the last reported value
+    let changes = differ.diff(&state("101.2", "9898.8", vec![]));
+    assert_eq!(
+        changes,
+        vec![
+            StateChange::MarginChanged {
+                previous: 100.0,
+                current: 101.2,
+                account_value: 10000.0,
+            },
+            StateChange::WithdrawableChanged {
+                previous: 9900.0,
+                current: 9898.8,
+            },
+        ]
+    );
+
+    differ.reset();
+    assert!(differ.diff(&state("500.0", "0.0", vec![])).is_empty());
+}
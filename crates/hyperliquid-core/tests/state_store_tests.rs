@@ -0,0 +1,119 @@
+//! Tests for the persistent state store and the components using it
+
+use std::sync::Arc;
+
+use hyperliquid_core::crypto::NonceManager;
+use hyperliquid_core::oms::{OrderManager, OrderState};
+use hyperliquid_core::positions::PositionTracker;
+use hyperliquid_core::store::{MemoryStore, StateStore};
+use serde_json::json;
+
+fn exercise(store: &dyn StateStore) {
+    store.put("oms/order/2", b"two").unwrap();
+    store.put("oms/order/1", b"one").unwrap();
+    store.put("oms/other", b"x").unwrap();
+    store.put("positions/BTC", b"btc").unwrap();
+    store.put("oms/order/1", b"uno").unwrap();
+
+    assert_eq!(store.get("oms/order/1").unwrap(), Some(b"uno".to_vec()));
+    assert_eq!(store.get("missing").unwrap(), None);
+    let keys: Vec<String> = store
+        .scan("oms/order/")
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    assert_eq!(keys, vec!["oms/order/1", "oms/order/2"]);
+
+    store.delete("oms/order/1").unwrap();
+    assert_eq!(store.scan("oms/order/").unwrap().len(), 1);
+    store.flush().unwrap();
+}
+
+#[test]
+fn test_memory_store() {
+    exercise(&MemoryStore::new());
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlite_store() {
+    let store = hyperliquid_core::store::SqliteStore::in_memory().unwrap();
+    exercise(&store);
+    // LIKE wildcards in the prefix match literally
+    store.put("a_b", b"1").unwrap();
+    store.put("axb", b"2").unwrap();
+    assert_eq!(store.scan("a_").unwrap().len(), 1);
+}
+
+#[cfg(feature = "sled")]
+#[test]
+fn test_sled_store() {
+    let path = std::env::temp_dir().join(format!("hyperliquid-sled-{}", uuid::Uuid::new_v4()));
+    exercise(&hyperliquid_core::store::SledStore::open(&path).unwrap());
+    let _ = std::fs::remove_dir_all(path);
+}
+
+#[test]
+fn test_order_manager_survives_restart() {
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+    let oms = OrderManager::new().with_store(store.clone()).unwrap();
+    let resting = oms.record_submission("BTC", true, 1.0, 60000.0, Some("0x01"));
+    let rejected = oms.record_submission("ETH", false, 2.0, 3000.0, None);
+    oms.record_response(
+        &[resting, rejected],
+        &json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+            {"resting": {"oid": 77}},
+            {"error": "Insufficient margin"}
+        ]}}}),
+    );
+    drop(oms);
+
+    let restored = OrderManager::new().with_store(store.clone()).unwrap();
+    let order = restored.order_by_oid(77).unwrap();
+    assert_eq!(order.key, resting);
+    assert_eq!(order.state, OrderState::Acked);
+    assert_eq!(restored.order("0x01").unwrap().oid, Some(77));
+    assert_eq!(restored.open_orders().len(), 1);
+
+    // Updates after the restart still find the order
+    restored.handle_order_updates(
+        &json!([{"order": {"oid": 77, "coin": "BTC"}, "status": "canceled"}]),
+    );
+    assert_eq!(restored.get(resting).unwrap().state, OrderState::Canceled);
+
+    // New keys don't collide with restored ones
+    let next = restored.record_submission("SOL", true, 1.0, 150.0, None);
+    assert_ne!(next, resting);
+    assert_ne!(next, rejected);
+
+    assert_eq!(restored.prune_closed(), 2);
+    assert_eq!(store.scan("oms/order/").unwrap().len(), 1);
+}
+
+#[test]
+fn test_positions_and_nonces_survive_restart() {
+    let store: Arc<dyn StateStore> = Arc::new(MemoryStore::new());
+    let tracker = PositionTracker::new().with_store(store.clone()).unwrap();
+    tracker.apply_fill("BTC", true, 60000.0, 1.0, 6.0);
+    tracker.apply_fill("BTC", false, 61000.0, 0.5, 3.0);
+    tracker.apply_funding("BTC", -1.5);
+
+    let restored = PositionTracker::new().with_store(store.clone()).unwrap();
+    let position = restored.position("BTC").unwrap();
+    assert_eq!(position.szi, 0.5);
+    assert_eq!(position.realized_pnl, 500.0);
+    assert_eq!(position.fees, 9.0);
+    assert_eq!(position.cumulative_funding, -1.5);
+
+    let nonces = NonceManager::new()
+        .with_store(store.clone(), "0xABC")
+        .unwrap();
+    let first = nonces.next().unwrap();
+    let second = nonces.next().unwrap();
+    assert!(second > first);
+
+    let resumed = NonceManager::new().with_store(store, "0xabc").unwrap();
+    assert_eq!(resumed.last(), second);
+    assert!(resumed.next().unwrap() > second);
+}
@@ -0,0 +1,75 @@
+//! Tests for client-side WebSocket subscription caps
+
+use hyperliquid_core::stream::{
+    SubscriptionLimits, SubscriptionUsage, WebSocketClient, WebSocketClientConfig, WebSocketError,
+};
+use hyperliquid_core::{Address, Subscription};
+
+fn user(n: u8) -> Address {
+    format!("0x{:040x}", n).parse().unwrap()
+}
+
+fn trades(coin: &str) -> Subscription {
+    Subscription::Trades {
+        coin: coin.to_string(),
+    }
+}
+
+#[test]
+fn test_limits_count_subscriptions_and_unique_users() {
+    let limits = SubscriptionLimits {
+        max_subscriptions: 3,
+        max_unique_users: 1,
+    };
+    let active = vec![trades("BTC"), Subscription::UserFills { user: user(1) }];
+    assert_eq!(
+        SubscriptionUsage::of(&active),
+        SubscriptionUsage {
+            subscriptions: 2,
+            unique_users: 1
+        }
+    );
+
+    // Another channel for a user already in use is fine, a second user isn't
+    assert!(limits
+        .check(&active, &Subscription::OrderUpdates { user: user(1) })
+        .is_ok());
+    assert!(matches!(
+        limits.check(&active, &Subscription::OrderUpdates { user: user(2) }),
+        Err(WebSocketError::SubscriptionLimit(_))
+    ));
+
+    let mut full = active.clone();
+    full.push(trades("ETH"));
+    assert!(matches!(
+        limits.check(&full, &trades("SOL")),
+        Err(WebSocketError::SubscriptionLimit(_))
+    ));
+    // Re-subscribing to an active subscription is never rejected
+    assert!(limits.check(&full, &trades("BTC")).is_ok());
+}
+
+#[tokio::test]
+async fn test_client_rejects_subscription_past_cap() {
+    let config = WebSocketClientConfig {
+        subscription_limits: SubscriptionLimits {
+            max_subscriptions: 1,
+            ..SubscriptionLimits::default()
+        },
+        ..WebSocketClientConfig::default()
+    };
+    let client = WebSocketClient::with_config(config).unwrap();
+
+    // Not connected, but the subscription is still recorded for restore
+    assert!(matches!(
+        client.subscribe(trades("BTC")).await,
+        Err(WebSocketError::NotConnected)
+    ));
+    assert!(!client.can_subscribe(&trades("ETH")).await);
+    assert!(matches!(
+        client.subscribe(trades("ETH")).await,
+        Err(WebSocketError::SubscriptionLimit(_))
+    ));
+    assert_eq!(client.subscriptions().await, vec![trades("BTC")]);
+    assert_eq!(client.subscription_usage().await.subscriptions, 1);
+}
@@ -0,0 +1,92 @@
+//! Tests for canonical symbol mapping
+
+use hyperliquid_core::symbols::{ContractType, Instrument, SymbolMap};
+use hyperliquid_core::types::{Meta, SpotUniverse};
+use serde_json::json;
+
+fn meta() -> Meta {
+    serde_json::from_value(json!({
+        "universe": [
+            {"name": "BTC", "onlyIsolated": false, "szDecimals": 5, "maxLeverage": 40},
+            {"name": "kPEPE", "onlyIsolated": false, "szDecimals": 0, "maxLeverage": 10}
+        ],
+        "exchange": null
+    }))
+    .unwrap()
+}
+
+fn spot() -> SpotUniverse {
+    SpotUniverse::from_spot_meta(&json!({
+        "universe": [
+            {"tokens": [1, 0], "name": "PURR/USDC", "index": 0, "isCanonical": true},
+            {"tokens": [197, 0], "name": "@142", "index": 142, "isCanonical": false}
+        ],
+        "tokens": [
+            {"name": "USDC", "szDecimals": 8, "weiDecimals": 8, "index": 0,
+             "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054", "isCanonical": true, "evmContract": null},
+            {"name": "PURR", "szDecimals": 0, "weiDecimals": 5, "index": 1,
+             "tokenId": "0xc1fb593aeffbeb02f85e0308e9956a90", "isCanonical": true, "evmContract": null},
+            {"name": "UBTC", "szDecimals": 5, "weiDecimals": 10, "index": 197,
+             "tokenId": "0x8f254b963e8468305d409b33aa137c67", "isCanonical": false, "evmContract": null}
+        ]
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_symbols_parse_and_format() {
+    let perp: Instrument = "BTC/USDC:USDC".parse().unwrap();
+    assert_eq!(perp, Instrument::perpetual("BTC", "USDC"));
+    assert_eq!(perp.to_string(), "BTC/USDC:USDC");
+
+    let spot: Instrument = "PURR/USDC".parse().unwrap();
+    assert_eq!(spot.contract, ContractType::Spot);
+    assert_eq!(spot.to_string(), "PURR/USDC");
+
+    // Inverse contracts and malformed symbols are rejected
+    assert!("BTC/USD:BTC".parse::<Instrument>().is_err());
+    assert!("BTC".parse::<Instrument>().is_err());
+    assert!("/USDC".parse::<Instrument>().is_err());
+}
+
+#[test]
+fn test_coins_map_both_ways() {
+    let symbols = SymbolMap::new().with_perps(&meta()).with_spot(&spot());
+    assert_eq!(symbols.len(), 4);
+
+    assert_eq!(symbols.symbol("BTC").as_deref(), Some("BTC/USDC:USDC"));
+    assert_eq!(symbols.symbol("PURR/USDC").as_deref(), Some("PURR/USDC"));
+    assert_eq!(symbols.symbol("@142").as_deref(), Some("UBTC/USDC"));
+    assert_eq!(symbols.coin("BTC/USDC:USDC"), Some("BTC"));
+    assert_eq!(symbols.coin("UBTC/USDC"), Some("@142"));
+    assert_eq!(symbols.coin("PURR/USDC"), Some("PURR/USDC"));
+    // Coins resolve to themselves
+    assert_eq!(symbols.coin("@142"), Some("@142"));
+    assert_eq!(symbols.coin("ETH/USDC:USDC"), None);
+    assert_eq!(symbols.coin("ETH"), None);
+}
+
+#[test]
+fn test_aliases() {
+    let symbols = SymbolMap::new()
+        .with_perps(&meta())
+        .with_spot(&spot())
+        .with_asset_alias("UBTC", "BTC")
+        .with_asset_alias("kPEPE", "1000PEPE")
+        .with_alias("XBTUSD", "BTC")
+        .with_alias("GONE", "DELISTED");
+
+    assert_eq!(symbols.symbol("@142").as_deref(), Some("BTC/USDC"));
+    assert_eq!(symbols.coin("BTC/USDC"), Some("@142"));
+    assert_eq!(symbols.coin("UBTC/USDC"), None);
+    // Perp and spot of one asset stay apart
+    assert_eq!(symbols.coin("BTC/USDC:USDC"), Some("BTC"));
+    assert_eq!(symbols.coin("1000PEPE/USDC:USDC"), Some("kPEPE"));
+
+    assert_eq!(symbols.coin("XBTUSD"), Some("BTC"));
+    assert_eq!(symbols.coin("GONE"), None);
+
+    // Markets added by hand use the aliases too
+    let symbols = symbols.with_market("TEST", Instrument::perpetual("UBTC", "USDT"));
+    assert_eq!(symbols.coin("BTC/USDT:USDT"), Some("TEST"));
+}
@@ -0,0 +1,93 @@
+//! Tests for transaction cost analysis
+
+use std::time::Duration;
+
+use hyperliquid_core::analytics::TcaRecorder;
+use hyperliquid_core::execution::ParentOrder;
+use serde_json::{json, Value};
+
+fn parent(is_buy: bool) -> ParentOrder {
+    ParentOrder::twap("BTC", is_buy, 2.0, 70_000.0, Duration::from_secs(60), 2)
+}
+
+fn fill(
+    oid: u64,
+    tid: u64,
+    side: &str,
+    (px, sz): (&str, &str),
+    fee: &str,
+    crossed: bool,
+    time: u64,
+) -> Value {
+    json!({
+        "coin": "BTC", "px": px, "sz": sz, "side": side, "time": time, "oid": oid,
+        "fee": fee, "crossed": crossed, "tid": tid, "closedPnl": "0.0", "hash": "0x0"
+    })
+}
+
+#[test]
+fn test_report_breakdown() {
+    let tca = TcaRecorder::new();
+    tca.start("buy", &parent(true), 60_000.0, Some(1_000));
+    tca.handle_trades(&json!([
+        {"coin": "BTC", "side": "B", "px": "60000.0", "sz": "1.0", "time": 1_100, "tid": 1},
+        {"coin": "BTC", "side": "A", "px": "60030.0", "sz": "1.0", "time": 1_200, "tid": 2},
+        {"coin": "BTC", "side": "B", "px": "61000.0", "sz": "5.0", "time": 9_000, "tid": 3}
+    ]));
+
+    let fills = json!({"user": "0x0", "fills": [
+        fill(11, 100, "B", ("60010.0", "1.0"), "1.5", true, 1_100),
+        fill(12, 101, "B", ("60030.0", "1.0"), "-0.3", false, 1_200),
+        fill(12, 101, "B", ("60030.0", "1.0"), "-0.3", false, 1_200),
+        fill(13, 102, "A", ("60030.0", "1.0"), "1.0", true, 1_300)
+    ]});
+    tca.handle_user_fills(&fills);
+
+    let report = tca.finish("buy", Some(2_000)).unwrap();
+    assert_eq!(report.fills, 2);
+    assert_eq!(report.filled_sz, 2.0);
+    assert_eq!(report.avg_px, 60_020.0);
+    assert!((report.arrival_slippage_bps.unwrap() - 10.0 / 3.0).abs() < 1e-6);
+    assert_eq!(report.market_vwap, Some(60_015.0));
+    assert!(report.vwap_slippage_bps.unwrap() > 0.0);
+    assert_eq!((report.taker_sz, report.maker_sz), (1.0, 1.0));
+    assert!((report.total_fees - 1.2).abs() < 1e-9);
+    assert_eq!(report.maker_share(), Some(0.5));
+}
+
+#[test]
+fn test_linked_children_and_export() {
+    let tca = TcaRecorder::new();
+    tca.start("sell", &parent(false), 60_000.0, Some(0));
+    tca.link_child("sell", 21).unwrap();
+    assert!(tca.link_child("missing", 1).is_err());
+
+    tca.handle_user_fills(&json!({"fills": [
+        fill(21, 200, "A", ("59940.0", "0.5"), "0.9", true, 10),
+        fill(22, 201, "A", ("59000.0", "0.5"), "0.9", true, 20)
+    ]}));
+    let report = tca.report("sell").unwrap();
+    assert_eq!(report.fills, 1);
+    // Selling below the arrival mid is a cost
+    assert!((report.arrival_slippage_bps.unwrap() - 10.0).abs() < 1e-6);
+    assert_eq!(report.market_vwap, None);
+
+    let mut csv = Vec::new();
+    tca.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv
+        .lines()
+        .nth(1)
+        .unwrap()
+        .starts_with("sell,BTC,sell,2,0.5,59940,"));
+
+    let mut json = Vec::new();
+    tca.write_json(&mut json).unwrap();
+    let reports: Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(reports[0]["id"], "sell");
+
+    tca.finish("sell", Some(100));
+    assert_eq!(tca.prune_finished(), 1);
+    assert!(tca.reports().is_empty());
+}
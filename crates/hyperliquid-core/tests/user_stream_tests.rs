@@ -0,0 +1,107 @@
+//! Tests for the user event stream and its reconnect gap-fill
+
+use hyperliquid_core::stream::{UserEventKind, UserStream, WebSocketEvent};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use serde_json::{json, Value};
+
+fn fill(tid: u64, time: u64) -> Value {
+    json!({"coin": "BTC", "px": "60000", "sz": "0.1", "side": "B", "time": time,
+           "oid": 7, "tid": tid, "fee": "1", "closedPnl": "0", "crossed": true})
+}
+
+fn now_millis() -> u64 {
+    chrono::Utc::now().timestamp_millis() as u64
+}
+
+#[tokio::test]
+async fn test_live_events_are_deduplicated_and_snapshots_skipped() {
+    let client = HttpClient::new("http://127.0.0.1:9", HttpClientConfig::default()).unwrap();
+    let stream = UserStream::new(client, "0xabc");
+    let mut events = stream.events();
+
+    stream.handle_user_fills(&json!({"user": "0xabc", "isSnapshot": true, "fills": [fill(1, 10)]}));
+    stream.handle_user_fills(&json!({"user": "0xabc", "fills": [fill(2, 20), fill(2, 20)]}));
+    stream.handle_order_updates(&json!([
+        {"order": {"oid": 7, "coin": "BTC"}, "status": "filled", "statusTimestamp": 20}
+    ]));
+
+    let first = events.try_recv().unwrap();
+    assert_eq!(first.time, 20);
+    assert!(!first.recovered);
+    assert!(matches!(first.kind, UserEventKind::Fill(ref f) if f["tid"] == 2));
+    assert!(matches!(
+        events.try_recv().unwrap().kind,
+        UserEventKind::OrderUpdate(_)
+    ));
+    assert!(events.try_recv().is_err());
+
+    // Nothing to recover without a disconnect
+    assert_eq!(stream.recover().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_reconnect_recovers_missed_events_in_order() {
+    let mut server = mockito::Server::new_async().await;
+    let now = now_millis();
+    let fills = server
+        .mock("POST", "/info")
+        .match_body(mockito::Matcher::PartialJson(
+            json!({"type": "userFillsByTime", "user": "0xabc"}),
+        ))
+        .with_body(json!([fill(1, now - 2_000), fill(2, now - 1_000)]).to_string())
+        .create_async()
+        .await;
+    let orders = server
+        .mock("POST", "/info")
+        .match_body(mockito::Matcher::PartialJson(
+            json!({"type": "historicalOrders"}),
+        ))
+        .with_body(
+            json!([
+                {"order": {"oid": 7, "coin": "BTC"}, "status": "filled", "statusTimestamp": now - 1_000},
+                {"order": {"oid": 5, "coin": "BTC"}, "status": "canceled", "statusTimestamp": now - 3_600_000}
+            ])
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let stream = UserStream::new(client, "0xabc");
+    let mut events = stream.events();
+
+    stream.handle_user_fills(&json!({"user": "0xabc", "fills": [fill(1, now - 2_000)]}));
+    assert!(!events.try_recv().unwrap().recovered);
+
+    stream
+        .handle_ws_event(&WebSocketEvent::Disconnected)
+        .await
+        .unwrap();
+    assert_eq!(
+        stream
+            .handle_ws_event(&WebSocketEvent::Connected)
+            .await
+            .unwrap(),
+        2
+    );
+    fills.assert_async().await;
+    orders.assert_async().await;
+
+    // Fill 1 was already delivered; the old cancel is outside the window
+    let recovered: Vec<_> = std::iter::from_fn(|| events.try_recv().ok()).collect();
+    assert_eq!(recovered.len(), 2);
+    assert!(recovered.iter().all(|event| event.recovered));
+    assert!(matches!(recovered[0].kind, UserEventKind::Fill(ref f) if f["tid"] == 2));
+    assert!(
+        matches!(recovered[1].kind, UserEventKind::OrderUpdate(ref u) if u["status"] == "filled")
+    );
+
+    // The window is cleared once recovered
+    assert_eq!(
+        stream
+            .handle_ws_event(&WebSocketEvent::Connected)
+            .await
+            .unwrap(),
+        0
+    );
+}
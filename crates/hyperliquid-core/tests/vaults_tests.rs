@@ -0,0 +1,137 @@
+//! Tests for vault operator reports
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::store::MemoryStore;
+use hyperliquid_core::vaults::{user_vault_equities, VaultDetails, VaultMonitor, VaultReport};
+use hyperliquid_core::{HttpClient, HttpClientConfig};
+use mockito::{Matcher, Server};
+use serde_json::{json, Value};
+
+const VAULT: &str = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303";
+const LEADER: &str = "0x677d831aef5328190852e24f13c46cac05f984e7";
+
+fn details(follower_pnl: &str) -> Value {
+    json!({
+        "name": "Test Vault",
+        "vaultAddress": VAULT,
+        "leader": LEADER,
+        "description": "",
+        "portfolio": [["day", {
+            "accountValueHistory": [[1_700_000_000_000u64, "1500.0"]],
+            "pnlHistory": [[1_700_000_000_000u64, "100.0"]],
+            "vlm": "25000.0"
+        }]],
+        "apr": 0.25,
+        "followerState": null,
+        "leaderFraction": 0.2,
+        "leaderCommission": 0,
+        "followers": [
+            {"user": "Leader", "vaultEquity": "300.0", "pnl": "40.0", "allTimePnl": "40.0",
+             "daysFollowing": 90, "vaultEntryTime": 1_690_000_000_000u64, "lockupUntil": null},
+            {"user": "0xaaa", "vaultEquity": "900.0", "pnl": follower_pnl,
+             "allTimePnl": "120.0", "daysFollowing": 30, "vaultEntryTime": 1_697_000_000_000u64,
+             "lockupUntil": 1_697_345_600_000u64},
+            {"user": "0xbbb", "vaultEquity": "300.0", "pnl": "-20.0", "allTimePnl": "-20.0",
+             "daysFollowing": 5, "vaultEntryTime": 1_699_500_000_000u64}
+        ],
+        "maxDistributable": 100.0,
+        "maxWithdrawable": 250.0,
+        "isClosed": false,
+        "relationship": {"type": "normal"},
+        "allowDeposits": true,
+        "alwaysCloseOnWithdraw": false
+    })
+}
+
+#[test]
+fn test_report_from_details() {
+    let details: VaultDetails = serde_json::from_value(details("100.0")).unwrap();
+    assert_eq!(details.portfolio("day").unwrap().pnl_history.len(), 1);
+    assert!(details.portfolio("allTime").is_none());
+
+    let report = VaultReport::from_details(&details, 0.1, 42);
+    assert_eq!(report.time, 42);
+    assert_eq!(report.total_equity, 1500.0);
+    assert_eq!(report.leader_equity, 300.0);
+    assert_eq!(report.followers.len(), 2);
+    assert_eq!(report.follower_equity, 1200.0);
+    assert_eq!(report.follower_pnl, 80.0);
+
+    let aaa = report.follower("0xAAA").unwrap();
+    assert_eq!(aaa.share, 0.6);
+    assert_eq!(aaa.lockup_until, Some(1_697_345_600_000));
+    assert!((aaa.accrued_profit_share - 10.0).abs() < 1e-9);
+    // Losses accrue nothing
+    assert_eq!(report.follower("0xbbb").unwrap().accrued_profit_share, 0.0);
+
+    assert!((report.accrued_profit_share - 10.0).abs() < 1e-9);
+    assert_eq!(report.leader_pnl.own_pnl, 40.0);
+    assert!((report.leader_pnl.total() - 50.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_monitor_records_follower_history() {
+    let mut server = Server::new_async().await;
+    let request = Matcher::PartialJson(json!({"type": "vaultDetails", "vaultAddress": VAULT}));
+    let first = server
+        .mock("POST", "/info")
+        .match_body(request.clone())
+        .with_body(details("100.0").to_string())
+        .expect(1)
+        .create_async()
+        .await;
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+    let clock = ManualClock::new(1_000);
+    let monitor = VaultMonitor::new(client, VAULT, Arc::new(MemoryStore::new()))
+        .with_profit_share(0.2)
+        .with_clock(Arc::new(clock.clone()));
+
+    monitor.sample().await.unwrap();
+    first.assert_async().await;
+    first.remove_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(request)
+        .with_body(details("150.0").to_string())
+        .create_async()
+        .await;
+    clock.advance(Duration::from_secs(1));
+    let report = monitor.sample().await.unwrap();
+    assert!((report.accrued_profit_share - 30.0).abs() < 1e-9);
+
+    let history = monitor.history(0..u64::MAX).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[1].time, 2_000);
+    assert_eq!(
+        monitor.follower_history("0xaaa", 0..u64::MAX).unwrap(),
+        vec![(1_000, 900.0, 100.0), (2_000, 900.0, 150.0)]
+    );
+    assert!(monitor
+        .follower_history("0xccc", 0..u64::MAX)
+        .unwrap()
+        .is_empty());
+    assert!((monitor.profit_share_accrued(0..u64::MAX).unwrap() - 10.0).abs() < 1e-9);
+    assert_eq!(monitor.profit_share_accrued(0..1_500).unwrap(), 0.0);
+}
+
+#[tokio::test]
+async fn test_user_vault_equities() {
+    let mut server = Server::new_async().await;
+    server
+        .mock("POST", "/info")
+        .match_body(Matcher::PartialJson(
+            json!({"type": "userVaultEquities", "user": "0xaaa"}),
+        ))
+        .with_body(json!([{"vaultAddress": VAULT, "equity": "900.5"}]).to_string())
+        .create_async()
+        .await;
+    let client = HttpClient::new(server.url(), HttpClientConfig::default()).unwrap();
+
+    assert_eq!(
+        user_vault_equities(&client, "0xaaa").await.unwrap(),
+        vec![(VAULT.to_string(), 900.5)]
+    );
+}
@@ -0,0 +1,129 @@
+//! Tests for the heartbeat watchdog
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::exchange::OrderLatencyTracker;
+use hyperliquid_core::oms::{OrderManager, TrackedOrder};
+use hyperliquid_core::watchdog::{BlindReason, KillSwitch, TripAction, Watchdog};
+use hyperliquid_core::HyperliquidError;
+use serde_json::json;
+
+const START: u64 = 1_700_000_000_000;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Call {
+    Cancel(Vec<u64>),
+    Schedule(Option<u64>),
+}
+
+/// Records every protective action
+#[derive(Clone, Default)]
+struct MockSwitch {
+    calls: Arc<Mutex<Vec<Call>>>,
+}
+
+impl MockSwitch {
+    fn calls(&self) -> Vec<Call> {
+        std::mem::take(&mut *self.calls.lock().unwrap())
+    }
+}
+
+impl KillSwitch for MockSwitch {
+    async fn cancel_orders(&self, orders: &[TrackedOrder]) -> Result<(), HyperliquidError> {
+        let oids = orders.iter().filter_map(|order| order.oid).collect();
+        self.calls.lock().unwrap().push(Call::Cancel(oids));
+        Ok(())
+    }
+
+    async fn schedule_cancel(&self, time: Option<u64>) -> Result<(), HyperliquidError> {
+        self.calls.lock().unwrap().push(Call::Schedule(time));
+        Ok(())
+    }
+}
+
+fn resting(oms: &OrderManager, oid: u64) {
+    let key = oms.record_submission("BTC", true, 0.01, 60000.0, None);
+    oms.record_response(
+        &[key],
+        &json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"resting": {"oid": oid}}]
+        }}}),
+    );
+}
+
+#[tokio::test]
+async fn test_silent_feed_cancels_open_orders_once() {
+    let clock = ManualClock::new(START);
+    let switch = MockSwitch::default();
+    let oms = OrderManager::new();
+    resting(&oms, 7);
+    resting(&oms, 8);
+    let watchdog = Watchdog::new(switch.clone(), oms)
+        .with_clock(Arc::new(clock.clone()))
+        .with_feed_timeout(Duration::from_secs(3));
+
+    clock.advance(Duration::from_secs(2));
+    watchdog.record_message();
+    clock.advance(Duration::from_secs(2));
+    assert_eq!(watchdog.check().await.unwrap(), None);
+    assert!(switch.calls().is_empty());
+
+    clock.advance(Duration::from_secs(1));
+    assert_eq!(
+        watchdog.check().await.unwrap(),
+        Some(BlindReason::FeedSilent(Duration::from_secs(3)))
+    );
+    assert!(watchdog.is_tripped());
+    assert_eq!(switch.calls(), vec![Call::Cancel(vec![7, 8])]);
+
+    // Still blind: nothing more is sent
+    clock.advance(Duration::from_secs(5));
+    assert!(watchdog.check().await.unwrap().is_some());
+    assert!(switch.calls().is_empty());
+
+    watchdog.record_message();
+    assert_eq!(watchdog.check().await.unwrap(), None);
+    assert!(!watchdog.is_tripped());
+    assert!(switch.calls().is_empty());
+    assert_eq!(watchdog.trips(), 1);
+}
+
+#[tokio::test]
+async fn test_schedule_cancel_is_armed_and_disarmed() {
+    let clock = ManualClock::new(START);
+    let switch = MockSwitch::default();
+    let watchdog = Watchdog::new(switch.clone(), OrderManager::new())
+        .with_clock(Arc::new(clock.clone()))
+        .with_feed_timeout(Duration::from_secs(3))
+        .with_action(TripAction::ScheduleCancel(Duration::from_secs(10)));
+
+    clock.advance(Duration::from_secs(3));
+    assert!(watchdog.check().await.unwrap().is_some());
+    assert_eq!(switch.calls(), vec![Call::Schedule(Some(START + 13_000))]);
+
+    // Recovery before the deadline stands the switch down
+    watchdog.record_message();
+    watchdog.check().await.unwrap();
+    assert_eq!(switch.calls(), vec![Call::Schedule(None)]);
+}
+
+#[tokio::test]
+async fn test_overdue_acks_trip() {
+    let switch = MockSwitch::default();
+    let tracker = Arc::new(OrderLatencyTracker::new(10));
+    let watchdog = Watchdog::new(switch.clone(), OrderManager::new())
+        .with_ack_timeout(tracker.clone(), Duration::from_millis(20));
+
+    tracker.start(&json!({"type": "order", "orders": [{"a": 0, "c": "0x01"}]}));
+    assert_eq!(watchdog.check().await.unwrap(), None);
+    std::thread::sleep(Duration::from_millis(30));
+    assert!(matches!(
+        watchdog.check().await.unwrap(),
+        Some(BlindReason::AckOverdue(_))
+    ));
+    // No open orders to cancel
+    assert!(switch.calls().is_empty());
+    assert_eq!(watchdog.trips(), 1);
+}
@@ -0,0 +1,68 @@
+//! Tests for parsing `webData2` snapshots
+
+use hyperliquid_core::types::{LeverageType, WebData2};
+use serde_json::json;
+
+#[test]
+fn test_parses_web_data2_snapshot() {
+    let data = json!({
+        "user": "0x1234567890abcdef1234567890abcdef12345678",
+        "clearinghouseState": {
+            "marginSummary": {"accountValue": "10000.0", "totalNtlPos": "3000.0",
+                              "totalRawUsd": "7000.0", "totalMarginUsed": "300.0"},
+            "crossMarginSummary": {"accountValue": "10000.0", "totalNtlPos": "3000.0",
+                                   "totalRawUsd": "7000.0", "totalMarginUsed": "300.0"},
+            "crossMaintenanceMarginUsed": "30.0",
+            "withdrawable": "9700.0",
+            "assetPositions": [{"type": "oneWay", "position": {
+                "coin": "ETH", "szi": "-1.0", "leverage": {"type": "cross", "value": 10},
+                "entryPx": "3000.0", "positionValue": "3000.0", "unrealizedPnl": "0.0",
+                "returnOnEquity": "0.0", "liquidationPx": "12000.0", "marginUsed": "300.0",
+                "maxLeverage": 50,
+                "cumFunding": {"allTime": "1.5", "sinceOpen": "0.5", "sinceChange": "0.5"}
+            }}],
+            "time": 1_700_000_000_000u64
+        },
+        "openOrders": [{
+            "coin": "ETH", "side": "B", "limitPx": "2900.0", "sz": "1.0", "oid": 7,
+            "timestamp": 1, "origSz": "1.0", "orderType": "Limit", "reduceOnly": true
+        }],
+        "meta": {"universe": [
+            {"name": "BTC", "szDecimals": 5, "maxLeverage": 50, "onlyIsolated": false},
+            {"name": "ETH", "szDecimals": 4, "maxLeverage": 50, "onlyIsolated": false}
+        ]},
+        "assetCtxs": [
+            {"funding": "0.0000125", "openInterest": "100.0", "prevDayPx": "59000.0",
+             "dayNtlVlm": "1000000.0", "premium": "0.0", "oraclePx": "60000.0",
+             "markPx": "60001.0", "midPx": "60000.5", "impactPxs": ["60000.0", "60001.0"],
+             "dayBaseVlm": "16.6"},
+            {"funding": "0.0000125", "openInterest": "1000.0", "prevDayPx": "2900.0",
+             "dayNtlVlm": "500000.0", "premium": "0.0", "oraclePx": "3000.0",
+             "markPx": "3000.5", "midPx": "3000.25", "impactPxs": ["3000.0", "3000.5"],
+             "dayBaseVlm": "166.6"}
+        ],
+        "serverTime": 1_700_000_000_123u64,
+        "agentAddress": null,
+        "agentValidUntil": null,
+        "cumLedger": "10000.0",
+        "isVault": false,
+        "totalVaultEquity": "0.0",
+        "leadingVaults": [],
+        "twapStates": []
+    });
+
+    let web_data: WebData2 = serde_json::from_value(data).unwrap();
+    assert_eq!(web_data.server_time, 1_700_000_000_123);
+
+    let position = web_data.clearinghouse_state.position("ETH").unwrap();
+    assert_eq!(position.szi(), -1.0);
+    assert_eq!(position.leverage.type_, LeverageType::Cross);
+    assert_eq!(position.leverage.value, 10);
+    assert_eq!(position.cum_funding.as_ref().unwrap().all_time, "1.5");
+    assert!(web_data.clearinghouse_state.position("BTC").is_none());
+
+    assert_eq!(web_data.mark_px("ETH"), Some(3000.5));
+    assert_eq!(web_data.mark_px("SOL"), None);
+    assert_eq!(web_data.open_orders_for("ETH").count(), 1);
+    assert!(web_data.open_orders[0].reduce_only);
+}
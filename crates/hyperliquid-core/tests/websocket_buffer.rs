@@ -1,10 +1,12 @@
 //! Tests for WebSocket circular buffer functionality
 
 use hyperliquid_core::stream::{
-    CircularBuffer, WebSocketClient, WebSocketClientConfig, WebSocketResponse,
+    spsc_channel, BufferMode, CircularBuffer, SpscWaitStrategy, WebSocketClient,
+    WebSocketClientConfig, WebSocketResponse,
 };
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::time::{sleep, Duration};
 
 /// Create a test WebSocket response
@@ -12,6 +14,8 @@ fn create_test_response(channel: &str, data: serde_json::Value) -> WebSocketResp
     WebSocketResponse {
         channel: channel.to_string(),
         data,
+        time: None,
+        received_at: Instant::now(),
     }
 }
 
@@ -40,6 +44,44 @@ async fn test_buffer_creation_and_config() {
     assert_eq!(client.buffer_size(), 0);
 }
 
+#[tokio::test]
+async fn test_spsc_buffer_mode_config() {
+    let config = WebSocketClientConfig {
+        buffer_mode: BufferMode::Spsc {
+            wait_strategy: SpscWaitStrategy::BusySpin,
+        },
+        ..WebSocketClientConfig::default()
+    };
+
+    let client = WebSocketClient::with_config(config).unwrap();
+    assert!(client.is_buffer_enabled());
+    assert!(client.is_spsc_enabled());
+    // The evicting circular buffer is not allocated in SPSC mode
+    assert!(client.buffer_stats().is_none());
+}
+
+#[tokio::test]
+async fn test_spsc_handoff_preserves_messages() {
+    let (mut producer, mut consumer) = spsc_channel(16, SpscWaitStrategy::Park);
+
+    let handle = std::thread::spawn(move || {
+        let mut indices = Vec::new();
+        while let Some(message) = consumer.pop_blocking() {
+            indices.push(message.data["index"].as_i64().unwrap());
+        }
+        indices
+    });
+
+    for i in 0..5 {
+        producer
+            .try_push(create_test_response("test", json!({"index": i})))
+            .unwrap();
+    }
+    drop(producer);
+
+    assert_eq!(handle.join().unwrap(), vec![0, 1, 2, 3, 4]);
+}
+
 #[tokio::test]
 async fn test_buffer_insert_and_read() {
     let buffer = CircularBuffer::new(10);
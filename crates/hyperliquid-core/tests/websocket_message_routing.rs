@@ -1,11 +1,12 @@
 //! Tests for WebSocket message parsing and routing
 
 use hyperliquid_core::stream::{
-    WebSocketClient, WebSocketClientConfig, WebSocketResponse,
-    MessageRouter, Subscription
+    WebSocketClient, WebSocketClientConfig, WebSocketResponse, WebSocketPostRequest,
+    WebSocketPostResponse, PostRequestType, WebSocketError, MessageRouter, Subscription
 };
 use serde_json::json;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
 
 /// Test basic message parsing from JSON string
@@ -82,18 +83,21 @@ async fn test_message_routing() {
         channel: "allMids".to_string(),
         data: json!({"BTC": "50000.5"}),
         time: Some(1234567890),
+        received_at: Instant::now(),
     };
 
     let trades_message = WebSocketResponse {
         channel: "trades.BTC".to_string(),
         data: json!({"price": "50000.5", "size": "1.0"}),
         time: Some(1234567891),
+        received_at: Instant::now(),
     };
 
     let unrelated_message = WebSocketResponse {
         channel: "l2Book.ETH".to_string(),
         data: json!({"bids": [], "asks": []}),
         time: Some(1234567892),
+        received_at: Instant::now(),
     };
 
     // Route messages
@@ -122,6 +126,7 @@ async fn test_channel_to_subscription_mapping() {
         channel: "allMids".to_string(),
         data: json!({}),
         time: None,
+        received_at: Instant::now(),
     };
 
     let subscription = MessageRouter::channel_to_subscription(&response);
@@ -133,6 +138,7 @@ async fn test_channel_to_subscription_mapping() {
         channel: "trades.BTC".to_string(),
         data: json!({}),
         time: None,
+        received_at: Instant::now(),
     };
 
     let subscription = MessageRouter::channel_to_subscription(&response);
@@ -147,6 +153,7 @@ async fn test_channel_to_subscription_mapping() {
         channel: "candle.BTC.1m".to_string(),
         data: json!({}),
         time: None,
+        received_at: Instant::now(),
     };
 
     let subscription = MessageRouter::channel_to_subscription(&response);
@@ -164,6 +171,7 @@ async fn test_channel_to_subscription_mapping() {
         channel: "userEvents.0x1234".to_string(),
         data: json!({}),
         time: None,
+        received_at: Instant::now(),
     };
 
     let subscription = MessageRouter::channel_to_subscription(&response);
@@ -178,6 +186,7 @@ async fn test_channel_to_subscription_mapping() {
         channel: "unknown.123".to_string(),
         data: json!({}),
         time: None,
+        received_at: Instant::now(),
     };
 
     let subscription = MessageRouter::channel_to_subscription(&response);
@@ -246,6 +255,7 @@ async fn test_broadcast_fallback() {
         channel: "trades.ETH".to_string(),  // No handler registered for this
         data: json!({"price": "3000.0"}),
         time: None,
+        received_at: Instant::now(),
     };
 
     // Route the message
@@ -297,4 +307,40 @@ async fn test_websocket_client_routing() {
 
     assert_eq!(client.handler_count().await, 0);
     assert!(!client.has_handler(&Subscription::AllMids).await);
-}
\ No newline at end of file
+}
+/// Test post request/response wire format
+#[test]
+fn test_post_message_format() {
+    let request = WebSocketPostRequest::new(
+        42,
+        PostRequestType::Info,
+        json!({"type": "l2Book", "coin": "BTC"}),
+    );
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({
+            "method": "post",
+            "id": 42,
+            "request": {"type": "info", "payload": {"type": "l2Book", "coin": "BTC"}}
+        })
+    );
+
+    let message = WebSocketResponse::try_from(
+        r#"{"channel":"post","data":{"id":42,"response":{"type":"info","payload":{"type":"l2Book","data":{}}}}}"#,
+    )
+    .unwrap();
+    assert_eq!(message.channel, "post");
+    let response: WebSocketPostResponse = serde_json::from_value(message.data).unwrap();
+    assert_eq!(response.id, 42);
+    assert_eq!(response.response.type_, "info");
+}
+
+/// Test that posting without a connection fails and leaves nothing pending
+#[tokio::test]
+async fn test_post_requires_connection() {
+    let client = WebSocketClient::new().unwrap();
+
+    let result = client.post(PostRequestType::Info, json!({"type": "allMids"})).await;
+    assert!(matches!(result, Err(WebSocketError::NotConnected)));
+    assert_eq!(client.pending_post_count(), 0);
+}
@@ -0,0 +1,131 @@
+//! Tests for the canonical wire form of prices and sizes
+
+use std::str::FromStr;
+
+use hyperliquid_core::types::precision::{
+    float_to_wire, to_wire_string, PrecisionError, WireFormat,
+};
+use rust_decimal::Decimal;
+
+fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn test_to_wire_string_is_canonical() {
+    let cases = [
+        ("50000", "50000"),
+        ("50000.0", "50000"),
+        ("50000.00000000", "50000"),
+        ("1.50", "1.5"),
+        ("0.10", "0.1"),
+        ("0.00000001", "0.00000001"),
+        ("0.0012300", "0.00123"),
+        ("100", "100"),
+        ("0", "0"),
+        ("0.000", "0"),
+        ("-0", "0"),
+        ("-0.00", "0"),
+        ("-1.50", "-1.5"),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(to_wire_string(dec(input)), expected, "input {}", input);
+    }
+}
+
+#[test]
+fn test_float_to_wire_matches_to_wire_string() {
+    for value in [0.1, 0.3, 1.5, 3000.0, 60123.0, 0.00012345, 1e-8] {
+        let expected = to_wire_string(Decimal::from_str(&format!("{}", value)).unwrap());
+        assert_eq!(float_to_wire(value).unwrap(), expected, "value {}", value);
+    }
+    assert_eq!(float_to_wire(0.1 + 0.2).unwrap(), "0.3");
+}
+
+#[test]
+fn test_perp_prices() {
+    // BTC: szDecimals 5, so at most 1 price decimal
+    let btc = WireFormat::perp(5);
+    for (px, wire) in [
+        ("60123", "60123"),
+        ("60123.0", "60123"),
+        ("123456", "123456"),
+        ("1234.5", "1234.5"),
+        ("0.1", "0.1"),
+    ] {
+        assert_eq!(btc.price(dec(px)).unwrap(), wire, "price {}", px);
+    }
+    for px in ["60123.5", "1234.56", "0.01", "-1"] {
+        assert!(
+            btc.price(dec(px)).is_err(),
+            "price {} should be rejected",
+            px
+        );
+    }
+
+    // ETH: szDecimals 4
+    let eth = WireFormat::perp(4);
+    assert_eq!(eth.price(dec("3000.1")).unwrap(), "3000.1");
+    assert_eq!(eth.price(dec("12.34")).unwrap(), "12.34");
+    assert!(eth.price(dec("3000.15")).is_err());
+    assert!(eth.price(dec("1.234")).is_err());
+
+    // szDecimals 0 leaves 6 price decimals, still at most 5 significant figures
+    let meme = WireFormat::perp(0);
+    assert_eq!(meme.price(dec("0.012345")).unwrap(), "0.012345");
+    assert_eq!(meme.price(dec("0.0012340")).unwrap(), "0.001234");
+    assert!(meme.price(dec("0.0012345")).is_err());
+    assert!(meme.price(dec("1.23456")).is_err());
+    assert!(meme.price(dec("0.0000001")).is_err());
+}
+
+#[test]
+fn test_spot_prices() {
+    // Spot allows 8 - szDecimals decimals
+    let purr = WireFormat::spot(0);
+    assert_eq!(purr.price(dec("0.00012345")).unwrap(), "0.00012345");
+    assert!(purr.price(dec("0.000123456")).is_err());
+    assert_eq!(purr.price_decimals(), 8);
+
+    let spot = WireFormat::spot(2);
+    assert_eq!(spot.price(dec("0.000123")).unwrap(), "0.000123");
+    assert!(spot.price(dec("0.0000123")).is_err());
+}
+
+#[test]
+fn test_sizes() {
+    let btc = WireFormat::perp(5);
+    assert_eq!(btc.size(dec("0.00100")).unwrap(), "0.001");
+    assert_eq!(btc.size(dec("1.0")).unwrap(), "1");
+    assert_eq!(btc.size(dec("0.00001")).unwrap(), "0.00001");
+    assert!(btc.size(dec("0.000001")).is_err());
+    assert!(btc.size(dec("-1")).is_err());
+
+    let whole = WireFormat::perp(0);
+    assert_eq!(whole.size(dec("250.000")).unwrap(), "250");
+    assert!(whole.size(dec("0.5")).is_err());
+}
+
+#[test]
+fn test_float_inputs_drop_binary_noise() {
+    let eth = WireFormat::perp(4);
+    assert_eq!(eth.size_f64(0.1 + 0.2).unwrap(), "0.3");
+    assert_eq!(eth.price_f64(2999.9999999999995).unwrap(), "3000");
+    assert_eq!(eth.price_f64(3000.1).unwrap(), "3000.1");
+    assert!(eth.price_f64(3000.15).is_err());
+}
+
+#[test]
+fn test_rejection_names_the_rule() {
+    let err = WireFormat::perp(5).price(dec("1234.56")).unwrap_err();
+    match &err {
+        PrecisionError::WireFormatError { value, reason } => {
+            assert_eq!(value, "1234.56");
+            assert!(reason.contains("decimals"), "{}", reason);
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+
+    let err = WireFormat::perp(0).price(dec("1.23456")).unwrap_err();
+    assert!(err.to_string().contains("significant figures"), "{}", err);
+}
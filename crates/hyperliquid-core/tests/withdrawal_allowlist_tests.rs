@@ -0,0 +1,165 @@
+//! Tests for the withdrawal allowlist
+
+use std::sync::Arc;
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{
+    AuditLog, AuditRecord, AuditResult, ExchangeClient, ExchangeClientConfig, WithdrawalAllowlist,
+};
+use hyperliquid_core::types::TransferRequest;
+use hyperliquid_core::{Config, HyperliquidError};
+
+const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const TREASURY: &str = "0x1111111111111111111111111111111111111111";
+const STRANGER: &str = "0x2222222222222222222222222222222222222222";
+
+#[test]
+fn test_addresses_and_patterns() {
+    let allowlist = WithdrawalAllowlist::new(
+        [
+            "0x1111111111111111111111111111111111111111",
+            "0x20*",
+            "0xABCD????????????????????????????????????",
+        ],
+        true,
+    )
+    .unwrap();
+    assert!(allowlist.is_allowed(TREASURY));
+    assert!(allowlist.is_allowed("0x2000000000000000000000000000000000000000"));
+    assert!(allowlist.is_allowed("0x20000000000000000000000000000000000000C5"));
+    assert!(allowlist.is_allowed("0xabcd000000000000000000000000000000000001"));
+    assert!(!allowlist.is_allowed(STRANGER));
+    // Only full addresses match, whatever the pattern
+    assert!(!allowlist.is_allowed("0x20"));
+    assert!(!allowlist.is_allowed("0xabcd00000000000000000000000000000000000"));
+    assert!(!allowlist.is_allowed("treasury"));
+
+    for bad in [
+        "treasury",
+        "0x1234",
+        "0x11111111111111111111111111111111111111111",
+        "0xzz*",
+    ] {
+        assert!(
+            matches!(
+                WithdrawalAllowlist::new([bad], true),
+                Err(HyperliquidError::Validation(_))
+            ),
+            "{} accepted",
+            bad
+        );
+    }
+}
+
+#[test]
+fn test_strict_mode_refuses() {
+    let strict = WithdrawalAllowlist::new([TREASURY], true).unwrap();
+    assert!(strict.check("withdraw3", TREASURY).is_ok());
+    assert!(matches!(
+        strict.check("withdraw3", STRANGER),
+        Err(HyperliquidError::DestinationNotAllowed { ref action, ref destination })
+            if action == "withdraw3" && destination == STRANGER
+    ));
+
+    // Lenient mode only logs
+    let lenient = WithdrawalAllowlist::new([TREASURY], false).unwrap();
+    assert!(lenient.check("usdSend", STRANGER).is_ok());
+}
+
+#[test]
+fn test_security_config() {
+    let config: Config = toml::from_str(&format!(
+        "[security]\nstrict_mode = true\nwithdrawal_allowlist = [\"{}\", \"0x20*\"]\n",
+        TREASURY
+    ))
+    .unwrap();
+    let allowlist = WithdrawalAllowlist::from_config(&config.security)
+        .unwrap()
+        .unwrap();
+    assert!(allowlist.is_strict());
+    assert_eq!(allowlist.entries().len(), 2);
+
+    // Nothing configured allows every destination
+    assert!(
+        WithdrawalAllowlist::from_config(&Config::default().security)
+            .unwrap()
+            .is_none()
+    );
+
+    let config: Config =
+        toml::from_str("[security]\nwithdrawal_allowlist = [\"0x1234\"]\n").unwrap();
+    let issues = config.validate_all();
+    assert!(issues
+        .iter()
+        .any(|issue| issue.path == "security.withdrawal_allowlist[0]"));
+}
+
+#[tokio::test]
+async fn test_client_refuses_before_signing() {
+    let mut server = mockito::Server::new_async().await;
+    let sent = server
+        .mock("POST", "/exchange")
+        .with_body(r#"{"status":"ok","response":{"type":"default"}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let path = std::env::temp_dir().join(format!(
+        "hyperliquid-allowlist-audit-{}.jsonl",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let audit = Arc::new(AuditLog::open(&path).unwrap());
+
+    let wallet = Wallet::new(KEY, false).unwrap();
+    let mut config = ExchangeClientConfig::testnet(wallet.address().parse().unwrap());
+    config.base_url = server.url();
+    let exchange = ExchangeClient::new(config)
+        .with_audit_log(audit.clone())
+        .with_withdrawal_allowlist(WithdrawalAllowlist::new([TREASURY], true).unwrap());
+
+    let result = exchange.withdraw(STRANGER, "100", &wallet).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::DestinationNotAllowed { .. })
+    ));
+    let result = exchange
+        .spot_send(
+            STRANGER,
+            "PURR:0xc4bf3f870c0e9465323c0b6ed28096c2",
+            "1",
+            &wallet,
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::DestinationNotAllowed { .. })
+    ));
+    let transfer = TransferRequest {
+        destination: STRANGER.to_string(),
+        amount: "1".to_string(),
+        token: "USDC".to_string(),
+    };
+    let result = exchange.transfer(transfer, &[]).await;
+    assert!(matches!(
+        result,
+        Err(HyperliquidError::DestinationNotAllowed { ref action, .. }) if action == "transfer"
+    ));
+    // Only the allowed destination is sent
+    exchange.usd_send(TREASURY, "100", &wallet).await.unwrap();
+    sent.assert_async().await;
+
+    assert_eq!(audit.len(), 4);
+    let log = std::fs::read_to_string(&path).unwrap();
+    let records: Vec<AuditRecord> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records[0].action, "withdraw3");
+    assert!(matches!(records[0].result, AuditResult::Failed { .. }));
+    assert_eq!(records[3].action, "usdSend");
+    assert!(matches!(records[3].result, AuditResult::Submitted));
+    // Recorded under the signing wallet
+    let signer = wallet.address().to_lowercase();
+    assert!(records.iter().all(|record| record.signer == signer));
+    let _ = std::fs::remove_file(&path);
+}
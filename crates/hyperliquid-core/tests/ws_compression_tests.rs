@@ -0,0 +1,144 @@
+//! Tests for permessage-deflate on the WebSocket client
+
+use flate2::{Compress, Compression, FlushCompress};
+use hyperliquid_core::stream::{
+    DeflateParams, Inflater, WebSocketClient, WebSocketClientConfig, WebSocketEvent,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+
+/// Compress one message the way a permessage-deflate sender does
+fn deflate(compress: &mut Compress, text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len() + 64);
+    compress
+        .compress_vec(text.as_bytes(), &mut out, FlushCompress::Sync)
+        .unwrap();
+    assert!(out.ends_with(&[0x00, 0x00, 0xff, 0xff]));
+    out.truncate(out.len() - 4);
+    out
+}
+
+/// Unmasked server frame with FIN set
+fn frame(first_byte: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = vec![first_byte];
+    if payload.len() < 126 {
+        frame.push(payload.len() as u8);
+    } else {
+        frame.push(126);
+        frame.extend((payload.len() as u16).to_be_bytes());
+    }
+    frame.extend_from_slice(payload);
+    frame
+}
+
+#[test]
+fn test_params_parse_the_accepted_extension() {
+    assert_eq!(
+        DeflateParams::from_header(
+            "permessage-deflate; server_no_context_takeover; client_max_window_bits=12"
+        ),
+        Some(DeflateParams {
+            server_no_context_takeover: true,
+            client_max_window_bits: Some(12),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        DeflateParams::from_header("x-webkit-deflate-frame, permessage-deflate"),
+        Some(DeflateParams::default())
+    );
+    assert_eq!(DeflateParams::from_header("x-webkit-deflate-frame"), None);
+}
+
+#[test]
+fn test_inflater_keeps_the_window_across_messages() {
+    let mut compress = Compress::new(Compression::default(), false);
+    let mut inflater = Inflater::new(DeflateParams::default());
+    let message = r#"{"channel":"allMids","data":{"mids":{"BTC":"50000.0","ETH":"3000.0"}}}"#;
+
+    let first = deflate(&mut compress, message);
+    assert_eq!(inflater.inflate(&first).unwrap(), message);
+    // The repeat is encoded as a back-reference into the previous message
+    let second = deflate(&mut compress, message);
+    assert!(second.len() < first.len());
+    assert_eq!(inflater.inflate(&second).unwrap(), message);
+
+    assert!(inflater.inflate(&[0xff, 0xff, 0xff]).is_err());
+}
+
+#[tokio::test]
+async fn test_client_inflates_compressed_messages_in_order() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let compressed = r#"{"channel":"allMids","data":{"mids":{"BTC":"50000.0"}}}"#;
+    let plain = r#"{"channel":"allMids","data":{"mids":{"BTC":"50001.0"}}}"#;
+
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut chunk = [0u8; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = socket.read(&mut chunk).await.unwrap();
+            request.extend_from_slice(&chunk[..read]);
+        }
+        let request = String::from_utf8(request).unwrap();
+        assert!(request.contains("permessage-deflate"));
+        let key = request
+            .lines()
+            .find_map(|line| line.strip_prefix("Sec-WebSocket-Key: "))
+            .or_else(|| {
+                request
+                    .lines()
+                    .find_map(|line| line.strip_prefix("sec-websocket-key: "))
+            })
+            .unwrap();
+
+        let mut response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\
+             Sec-WebSocket-Extensions: permessage-deflate; client_max_window_bits=15\r\n\r\n",
+            derive_accept_key(key.trim().as_bytes())
+        )
+        .into_bytes();
+        // Frames in the same write as the handshake response
+        let mut compress = Compress::new(Compression::default(), false);
+        response.extend(frame(0xc1, &deflate(&mut compress, compressed)));
+        response.extend(frame(0x81, plain.as_bytes()));
+        socket.write_all(&response).await.unwrap();
+        socket
+    });
+
+    let mut client = WebSocketClient::with_config(WebSocketClientConfig {
+        url,
+        auto_reconnect: false,
+        enable_heartbeat: false,
+        enable_buffer: false,
+        enable_compression: true,
+        ..Default::default()
+    })
+    .unwrap();
+    client.connect().await.unwrap();
+
+    let mut mids = Vec::new();
+    while mids.len() < 2 {
+        match client.next_event().await.unwrap() {
+            WebSocketEvent::Data(response) => {
+                mids.push(response.data["mids"]["BTC"].as_str().unwrap().to_string())
+            }
+            WebSocketEvent::Error(e) => panic!("unexpected error: {}", e),
+            _ => {}
+        }
+    }
+    assert_eq!(mids, vec!["50000.0", "50001.0"]);
+
+    let stats = client.compression_stats().unwrap();
+    assert!(stats.negotiated);
+    assert_eq!(stats.messages, 1);
+    assert_eq!(stats.decompressed_bytes, compressed.len() as u64);
+    assert!(stats.compressed_bytes > 0);
+    assert_eq!(stats.errors, 0);
+
+    client.shutdown().await.unwrap();
+    drop(server);
+}
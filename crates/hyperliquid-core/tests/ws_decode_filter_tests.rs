@@ -0,0 +1,90 @@
+//! Tests for limiting which WebSocket channels are decoded
+
+use futures::SinkExt;
+use hyperliquid_core::stream::{
+    DecodeFilter, RawMessage, WebSocketClient, WebSocketClientConfig, WebSocketEvent,
+};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::Message;
+
+const TRADES: &str =
+    r#"{"channel":"trades","data":[{"coin":"BTC","px":"50000.0","sz":"0.1","time":1}]}"#;
+const WEB_DATA: &str = r#"{"channel":"webData2","data":{"clearinghouseState":{},"user":"0xabc"}}"#;
+
+#[test]
+fn test_filter_matches_channel_names() {
+    let only = DecodeFilter::only(["trades", "activeAssetCtx"]);
+    assert!(only.decodes("trades"));
+    assert!(only.decodes("activeAssetCtx.BTC"));
+    assert!(!only.decodes("webData2"));
+    // Post responses resolve pending requests, so they are always decoded
+    assert!(only.decodes("post"));
+
+    let except = DecodeFilter::except(["webData2"]);
+    assert!(except.decodes("trades"));
+    assert!(!except.decodes("webData2"));
+    assert!(DecodeFilter::All.decodes("webData2"));
+}
+
+#[test]
+fn test_skipped_channel_reads_only_the_envelope() {
+    let filter = DecodeFilter::only(["trades"]);
+    assert_eq!(filter.skipped_channel(TRADES), None);
+    assert_eq!(
+        filter.skipped_channel(WEB_DATA),
+        Some("webData2".to_string())
+    );
+    assert_eq!(filter.skipped_channel("ping"), None);
+    assert_eq!(DecodeFilter::All.skipped_channel(WEB_DATA), None);
+
+    let raw = RawMessage::new("webData2", WEB_DATA);
+    assert_eq!(raw.as_bytes(), WEB_DATA.as_bytes());
+    let decoded = raw.decode().unwrap();
+    assert_eq!(decoded.channel, "webData2");
+    assert_eq!(decoded.data["user"], "0xabc");
+    assert_eq!(decoded.received_at, raw.received_at);
+}
+
+#[tokio::test]
+async fn test_client_passes_excluded_channels_through_raw() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(socket).await.unwrap();
+        for text in [WEB_DATA, TRADES] {
+            ws.send(Message::Text(text.to_string())).await.unwrap();
+        }
+        ws
+    });
+
+    let config = WebSocketClientConfig {
+        url,
+        auto_reconnect: false,
+        enable_heartbeat: false,
+        enable_buffer: false,
+        ..Default::default()
+    }
+    .with_decode_filter(DecodeFilter::only(["trades"]));
+    let mut client = WebSocketClient::with_config(config).unwrap();
+    client.connect().await.unwrap();
+
+    let mut raw = None;
+    let mut data = None;
+    while raw.is_none() || data.is_none() {
+        match client.next_event().await.unwrap() {
+            WebSocketEvent::Raw(message) => raw = Some(message),
+            WebSocketEvent::Data(response) => data = Some(response),
+            WebSocketEvent::Error(e) => panic!("unexpected error: {}", e),
+            _ => {}
+        }
+    }
+    let raw = raw.unwrap();
+    assert_eq!(raw.channel, "webData2");
+    assert_eq!(&*raw.text, WEB_DATA);
+    let data = data.unwrap();
+    assert_eq!(data.channel, "trades");
+    assert_eq!(data.data[0]["px"], "50000.0");
+
+    drop(server.await.unwrap());
+}
@@ -0,0 +1,90 @@
+//! Tests for WS receive timestamps and feed latency tracking
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use hyperliquid_core::clock::ManualClock;
+use hyperliquid_core::stream::{FeedLatencyTracker, WebSocketResponse};
+use serde_json::json;
+
+const NOW: u64 = 1_700_000_000_000;
+
+fn received(channel: &str, time: u64, ago: Duration) -> WebSocketResponse {
+    WebSocketResponse {
+        channel: channel.to_string(),
+        data: json!({"coin": "BTC", "time": time}),
+        time: None,
+        received_at: Instant::now() - ago,
+    }
+}
+
+#[test]
+fn test_exchange_time_sources() {
+    let parsed = WebSocketResponse::try_from(
+        r#"{"channel": "l2Book", "data": {"coin": "BTC", "time": 5, "levels": [[], []]}}"#,
+    )
+    .unwrap();
+    assert_eq!(parsed.exchange_time(), Some(5));
+    assert!(parsed.age() < Duration::from_secs(5));
+
+    let trades = WebSocketResponse::new("trades", json!([{"coin": "BTC", "time": 7}]), None);
+    assert_eq!(trades.exchange_time(), Some(7));
+    let mids = WebSocketResponse::new("allMids", json!({"mids": {}}), Some(9));
+    assert_eq!(mids.exchange_time(), Some(9));
+    let bare = WebSocketResponse::new("notification", json!({"notification": "hi"}), None);
+    assert_eq!(bare.exchange_time(), None);
+    assert_eq!(bare.latency(), None);
+}
+
+#[test]
+fn test_age_and_latency() {
+    let clock = ManualClock::new(NOW);
+    let response = received("l2Book", NOW - 150, Duration::from_millis(100));
+
+    assert!(response.age() >= Duration::from_millis(100));
+    assert!(response.is_stale(Duration::from_millis(50)));
+    assert!(!response.is_stale(Duration::from_secs(60)));
+
+    // Received about 100ms before NOW, 50ms after the exchange stamped it
+    let latency = response.latency_at(&clock).unwrap();
+    assert!(latency <= Duration::from_millis(50), "{:?}", latency);
+    assert!(latency >= Duration::from_millis(40), "{:?}", latency);
+    assert_eq!(
+        response.exchange_age(&clock),
+        Some(Duration::from_millis(150))
+    );
+
+    // Exchange clock ahead of ours
+    let skewed = received("l2Book", NOW + 1_000, Duration::ZERO);
+    assert_eq!(skewed.latency_at(&clock), Some(Duration::ZERO));
+}
+
+#[test]
+fn test_tracker_stats_by_channel() {
+    let clock = ManualClock::new(NOW);
+    let tracker = FeedLatencyTracker::new().with_clock(Arc::new(clock));
+
+    for delay in [20, 60, 40] {
+        tracker.record(&received("trades", NOW - delay, Duration::ZERO));
+    }
+    tracker.record(&received("l2Book", NOW - 5, Duration::ZERO));
+    assert_eq!(
+        tracker.record(&WebSocketResponse::new("pong", json!(null), None)),
+        None
+    );
+
+    let trades = tracker.stats("trades").unwrap();
+    assert_eq!(trades.count, 3);
+    // Allow for the few microseconds between building and recording
+    assert!(trades.min >= Duration::from_millis(19) && trades.min <= Duration::from_millis(20));
+    assert!(trades.max >= Duration::from_millis(59) && trades.max <= Duration::from_millis(60));
+    assert!(trades.last >= Duration::from_millis(39) && trades.last <= Duration::from_millis(40));
+    assert!(trades.mean >= Duration::from_millis(39) && trades.mean <= Duration::from_millis(40));
+
+    let all = tracker.all();
+    assert_eq!(all.keys().collect::<Vec<_>>(), ["l2Book", "trades"]);
+    assert!(tracker.stats("pong").is_none());
+
+    tracker.reset();
+    assert!(tracker.all().is_empty());
+}
@@ -0,0 +1,18 @@
+//! Regenerate the C header from the exported functions
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("invalid cbindgen.toml");
+
+    match cbindgen::generate_with_config(&crate_dir, config) {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{}/include/hyperliquid.h", crate_dir));
+        }
+        // Keep building with the checked-in header if parsing fails
+        Err(e) => println!("cargo:warning=failed to generate hyperliquid.h: {}", e),
+    }
+
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,122 @@
+#ifndef HYPERLIQUID_H
+#define HYPERLIQUID_H
+
+/* Generated by cbindgen from crates/hyperliquid-ffi. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// ABI version of this library; bumped on any breaking change
+#define HL_ABI_VERSION 1
+
+// Result of an FFI call
+typedef enum HlStatus {
+  // Success
+  HL_STATUS_OK = 0,
+  // A required argument was null or malformed
+  HL_STATUS_INVALID_ARGUMENT = 1,
+  // Connection or transport failure, including timeouts
+  HL_STATUS_NETWORK = 2,
+  // Request was rate limited
+  HL_STATUS_RATE_LIMITED = 3,
+  // Exchange rejected the request
+  HL_STATUS_REJECTED = 4,
+  // Key loading or signing failed
+  HL_STATUS_SIGNING = 5,
+  // Operation needs a private key but the client was created without one
+  HL_STATUS_NO_WALLET = 6,
+  // Unexpected internal error (including caught panics)
+  HL_STATUS_INTERNAL = 99,
+} HlStatus;
+
+// Opaque client handle
+typedef struct HlClient HlClient;
+
+// Called for every message on a subscription
+//
+// `channel` and `data_json` are only valid for the duration of the call.
+// Callbacks run on an internal runtime thread and must not block for long.
+typedef void (*HlMessageCallback)(void *user_data, const char *channel, const char *data_json);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Create a client for `base_url`
+//
+// `private_key` (hex) may be null for a read-only client that can only use
+// the market data feed. The handle is written to `out` and must be released
+// with `hl_client_free`.
+HlStatus hl_client_new(const char *base_url, const char *private_key, HlClient **out);
+
+// Release a client, closing its market data feed (null is ignored)
+void hl_client_free(HlClient *client);
+
+// Signing address of the client, or null for a read-only client
+//
+// The pointer is owned by the client and valid until `hl_client_free`.
+const char *hl_client_address(const HlClient *client);
+
+// Place a limit order
+//
+// `tif` is `"Gtc"`, `"Ioc"` or `"Alo"` (null means `"Gtc"`); `cloid` is an
+// optional 0x-prefixed 16-byte client order id. The exchange response is
+// written to `out_json`.
+HlStatus hl_place_order(const HlClient *client,
+                        const char *coin,
+                        bool is_buy,
+                        double sz,
+                        double limit_px,
+                        const char *tif,
+                        bool reduce_only,
+                        const char *cloid,
+                        char **out_json);
+
+// Cancel an order by exchange order id
+HlStatus hl_cancel_order(const HlClient *client, const char *coin, uint64_t oid, char **out_json);
+
+// Cancel an order by client order id
+HlStatus hl_cancel_order_by_cloid(const HlClient *client,
+                                  const char *coin,
+                                  const char *cloid,
+                                  char **out_json);
+
+// Subscribe to a market data channel
+//
+// `channel` is the wire channel name (`"l2Book"`, `"trades"`, `"bbo"`,
+// `"candle"`, `"allMids"`, `"userFills"`, ...); `coin`, `interval` and `user`
+// are passed when the channel needs them and may otherwise be null. Each
+// message is delivered to `callback` with `user_data`. Subscribing to the
+// same channel and parameters again replaces the previous callback. The
+// subscription id is written to `out_id`.
+HlStatus hl_subscribe(const HlClient *client,
+                      const char *channel,
+                      const char *coin,
+                      const char *interval,
+                      const char *user,
+                      HlMessageCallback callback,
+                      void *user_data,
+                      uint64_t *out_id);
+
+// Cancel a subscription made with `hl_subscribe`
+HlStatus hl_unsubscribe(const HlClient *client, uint64_t subscription_id);
+
+// ABI version of the loaded library; compare against `HL_ABI_VERSION`
+uint32_t hl_abi_version(void);
+
+// Message for the last error on this thread, or null if none
+//
+// The pointer stays valid until the next failing call on the same thread.
+const char *hl_last_error(void);
+
+// Free a string returned by this library (null is ignored)
+void hl_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* HYPERLIQUID_H */
@@ -0,0 +1,323 @@
+//! Client lifecycle and order placement
+
+use std::collections::HashMap;
+use std::ffi::{c_char, CString};
+
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{ExchangeClient, ExchangeClientConfig};
+use hyperliquid_core::info::InfoClient;
+use hyperliquid_core::types::{PrecisionError, WireFormat};
+use hyperliquid_core::HttpClient;
+
+use crate::feed::Feed;
+use crate::{guard, optional_str, required_str, runtime, write_string};
+use crate::{FfiError, FfiResult, HlStatus};
+
+/// Opaque client handle
+pub struct HlClient {
+    info: InfoClient,
+    trading: Option<Trading>,
+    assets: OnceCell<HashMap<String, (u32, WireFormat)>>,
+    pub(crate) feed: Feed,
+}
+
+/// Signing state, present when the client was created with a private key
+struct Trading {
+    exchange: ExchangeClient,
+    wallet: Wallet,
+    address: CString,
+}
+
+impl HlClient {
+    fn new(base_url: &str, private_key: Option<&str>) -> FfiResult<Self> {
+        let http = HttpClient::with_default_config(base_url.to_string())?;
+        let mainnet = base_url.contains("api.hyperliquid.xyz");
+
+        let trading = match private_key {
+            Some(key) => {
+                let wallet = Wallet::new(key, mainnet)?;
+                let account = wallet
+                    .address()
+                    .parse()
+                    .map_err(|_| FfiError::new(HlStatus::Signing, "invalid wallet address"))?;
+                let mut config = if mainnet {
+                    ExchangeClientConfig::mainnet(account)
+                } else {
+                    ExchangeClientConfig::testnet(account)
+                };
+                config.base_url = base_url.to_string();
+                let address = CString::new(wallet.address()).unwrap_or_default();
+                Some(Trading {
+                    exchange: ExchangeClient::new(config),
+                    wallet,
+                    address,
+                })
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            info: InfoClient::new(http),
+            trading,
+            assets: OnceCell::new(),
+            feed: Feed::new(websocket_url(base_url)),
+        })
+    }
+
+    fn trading(&self) -> FfiResult<&Trading> {
+        self.trading.as_ref().ok_or_else(|| {
+            FfiError::new(
+                HlStatus::NoWallet,
+                "client was created without a private key",
+            )
+        })
+    }
+
+    /// Resolve a coin to its perp asset index
+    async fn asset(&self, coin: &str) -> FfiResult<u32> {
+        Ok(self.asset_format(coin).await?.0)
+    }
+
+    /// Resolve a coin to its perp asset index and price and size rules
+    async fn asset_format(&self, coin: &str) -> FfiResult<(u32, WireFormat)> {
+        let assets = self
+            .assets
+            .get_or_try_init(|| async {
+                let meta = self.info.meta("").await?;
+                Ok::<_, FfiError>(
+                    meta.universe
+                        .iter()
+                        .enumerate()
+                        .map(|(index, asset)| {
+                            let format = WireFormat::perp(asset.szDecimals.max(0) as u32);
+                            (asset.name.clone(), (index as u32, format))
+                        })
+                        .collect(),
+                )
+            })
+            .await?;
+        assets
+            .get(coin)
+            .copied()
+            .ok_or_else(|| FfiError::invalid(format!("unknown coin: {}", coin)))
+    }
+
+    /// Sign and submit an L1 action, writing the response JSON to `out`
+    ///
+    /// Exchange rejections return `Rejected` but still hand back the response.
+    unsafe fn submit(&self, action: Value, out: *mut *mut c_char) -> FfiResult<()> {
+        let trading = self.trading()?;
+        let response = runtime().block_on(trading.exchange.post_signed_action(
+            action,
+            &trading.wallet,
+            None,
+        ))?;
+        let rejection = rejection(&response);
+        write_string(out, response.to_string())?;
+        match rejection {
+            Some(reason) => Err(FfiError::new(HlStatus::Rejected, reason)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reason for an exchange-level or per-order rejection, if any
+fn rejection(response: &Value) -> Option<String> {
+    if response.get("status").and_then(Value::as_str) == Some("err") {
+        return Some(
+            response
+                .get("response")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error")
+                .to_string(),
+        );
+    }
+    response
+        .pointer("/response/data/statuses")
+        .and_then(Value::as_array)?
+        .iter()
+        .find_map(|status| status.get("error").and_then(Value::as_str))
+        .map(str::to_string)
+}
+
+fn websocket_url(base_url: &str) -> String {
+    format!(
+        "{}/ws",
+        base_url.trim_end_matches('/').replacen("http", "ws", 1)
+    )
+}
+
+unsafe fn client_ref<'a>(client: *const HlClient) -> FfiResult<&'a HlClient> {
+    client
+        .as_ref()
+        .ok_or_else(|| FfiError::invalid("client must not be null"))
+}
+
+/// Create a client for `base_url`
+///
+/// `private_key` (hex) may be null for a read-only client that can only use
+/// the market data feed. The handle is written to `out` and must be released
+/// with `hl_client_free`.
+#[no_mangle]
+pub unsafe extern "C" fn hl_client_new(
+    base_url: *const c_char,
+    private_key: *const c_char,
+    out: *mut *mut HlClient,
+) -> HlStatus {
+    guard(|| {
+        let base_url = required_str(base_url, "base_url")?;
+        let private_key = optional_str(private_key, "private_key")?;
+        if out.is_null() {
+            return Err(FfiError::invalid("output pointer must not be null"));
+        }
+        let client = HlClient::new(base_url, private_key)?;
+        *out = Box::into_raw(Box::new(client));
+        Ok(())
+    })
+}
+
+/// Release a client, closing its market data feed (null is ignored)
+#[no_mangle]
+pub unsafe extern "C" fn hl_client_free(client: *mut HlClient) {
+    if !client.is_null() {
+        let client = Box::from_raw(client);
+        client.feed.close();
+    }
+}
+
+/// Signing address of the client, or null for a read-only client
+///
+/// The pointer is owned by the client and valid until `hl_client_free`.
+#[no_mangle]
+pub unsafe extern "C" fn hl_client_address(client: *const HlClient) -> *const c_char {
+    client
+        .as_ref()
+        .and_then(|client| client.trading.as_ref())
+        .map(|trading| trading.address.as_ptr())
+        .unwrap_or(std::ptr::null())
+}
+
+/// Place a limit order
+///
+/// `tif` is `"Gtc"`, `"Ioc"` or `"Alo"` (null means `"Gtc"`); `cloid` is an
+/// optional 0x-prefixed 16-byte client order id. A `sz` or `limit_px` the
+/// coin's size and tick rules don't allow returns `InvalidArgument`. The
+/// exchange response is written to `out_json`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn hl_place_order(
+    client: *const HlClient,
+    coin: *const c_char,
+    is_buy: bool,
+    sz: f64,
+    limit_px: f64,
+    tif: *const c_char,
+    reduce_only: bool,
+    cloid: *const c_char,
+    out_json: *mut *mut c_char,
+) -> HlStatus {
+    guard(|| {
+        let client = client_ref(client)?;
+        let coin = required_str(coin, "coin")?;
+        let tif = optional_str(tif, "tif")?.unwrap_or("Gtc");
+        let cloid = optional_str(cloid, "cloid")?;
+        if !(sz > 0.0 && limit_px > 0.0) {
+            return Err(FfiError::invalid("sz and limit_px must be positive"));
+        }
+
+        let (asset, format) = runtime().block_on(client.asset_format(coin))?;
+        let invalid = |e: PrecisionError| FfiError::invalid(e.to_string());
+        let mut order = json!({
+            "a": asset,
+            "b": is_buy,
+            "p": format.price_f64(limit_px).map_err(invalid)?,
+            "s": format.size_f64(sz).map_err(invalid)?,
+            "r": reduce_only,
+            "t": {"limit": {"tif": tif}},
+        });
+        if let Some(cloid) = cloid {
+            order["c"] = json!(cloid);
+        }
+        client.submit(
+            json!({"type": "order", "orders": [order], "grouping": "na"}),
+            out_json,
+        )
+    })
+}
+
+/// Cancel an order by exchange order id
+#[no_mangle]
+pub unsafe extern "C" fn hl_cancel_order(
+    client: *const HlClient,
+    coin: *const c_char,
+    oid: u64,
+    out_json: *mut *mut c_char,
+) -> HlStatus {
+    guard(|| {
+        let client = client_ref(client)?;
+        let coin = required_str(coin, "coin")?;
+        let asset = runtime().block_on(client.asset(coin))?;
+        client.submit(
+            json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]}),
+            out_json,
+        )
+    })
+}
+
+/// Cancel an order by client order id
+#[no_mangle]
+pub unsafe extern "C" fn hl_cancel_order_by_cloid(
+    client: *const HlClient,
+    coin: *const c_char,
+    cloid: *const c_char,
+    out_json: *mut *mut c_char,
+) -> HlStatus {
+    guard(|| {
+        let client = client_ref(client)?;
+        let coin = required_str(coin, "coin")?;
+        let cloid = required_str(cloid, "cloid")?;
+        let asset = runtime().block_on(client.asset(coin))?;
+        client.submit(
+            json!({
+                "type": "cancelByCloid",
+                "cancels": [{"asset": asset, "cloid": cloid}],
+            }),
+            out_json,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejection() {
+        let ok = json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"resting": {"oid": 1}}]
+        }}});
+        assert_eq!(rejection(&ok), None);
+
+        let per_order = json!({"status": "ok", "response": {"type": "order", "data": {
+            "statuses": [{"error": "Insufficient margin"}]
+        }}});
+        assert_eq!(
+            rejection(&per_order).as_deref(),
+            Some("Insufficient margin")
+        );
+
+        let whole = json!({"status": "err", "response": "User or API Wallet does not exist."});
+        assert!(rejection(&whole).unwrap().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        assert_eq!(
+            websocket_url("https://api.hyperliquid.xyz/"),
+            "wss://api.hyperliquid.xyz/ws"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Callback-based market data feed
+
+use std::collections::HashMap;
+use std::ffi::{c_char, c_void, CString};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use hyperliquid_core::stream::{WebSocketClient, WebSocketClientConfig, WebSocketResponse};
+use hyperliquid_core::types::Subscription;
+
+use crate::client::HlClient;
+use crate::{guard, optional_str, required_str, runtime};
+use crate::{FfiError, FfiResult, HlStatus};
+
+/// Called for every message on a subscription
+///
+/// `channel` and `data_json` are only valid for the duration of the call.
+/// Callbacks run on an internal runtime thread and must not block for long.
+pub type HlMessageCallback = Option<
+    unsafe extern "C" fn(user_data: *mut c_void, channel: *const c_char, data_json: *const c_char),
+>;
+
+/// Caller-provided context passed back to the callback
+#[derive(Clone, Copy)]
+struct UserData(*mut c_void);
+
+// The caller guarantees `user_data` may be used from the callback thread
+unsafe impl Send for UserData {}
+unsafe impl Sync for UserData {}
+
+/// WebSocket connection shared by a client's subscriptions
+pub(crate) struct Feed {
+    url: String,
+    ws: Arc<Mutex<Option<WebSocketClient>>>,
+    subscriptions: std::sync::Mutex<HashMap<u64, Subscription>>,
+    next_id: AtomicU64,
+}
+
+impl Feed {
+    pub(crate) fn new(url: String) -> Self {
+        Self {
+            url,
+            ws: Arc::new(Mutex::new(None)),
+            subscriptions: std::sync::Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Connect on first use and return a handle to the socket
+    async fn connected(&self) -> FfiResult<WebSocketClient> {
+        let mut ws = self.ws.lock().await;
+        if let Some(client) = ws.as_ref() {
+            return Ok(client.clone());
+        }
+
+        let config = WebSocketClientConfig {
+            url: self.url.clone(),
+            ..WebSocketClientConfig::default()
+        };
+        let mut client = WebSocketClient::with_config(config).map_err(ws_error)?;
+        client.connect().await.map_err(ws_error)?;
+        let handle = client.clone();
+        *ws = Some(client);
+        Ok(handle)
+    }
+
+    fn subscribe(
+        &self,
+        subscription: Subscription,
+        callback: unsafe extern "C" fn(*mut c_void, *const c_char, *const c_char),
+        user_data: UserData,
+    ) -> FfiResult<u64> {
+        runtime().block_on(async {
+            let ws = self.connected().await?;
+            ws.register_handler(subscription.clone(), move |response: WebSocketResponse| {
+                let user_data = user_data;
+                let (Ok(channel), Ok(data)) = (
+                    CString::new(response.channel),
+                    CString::new(response.data.to_string()),
+                ) else {
+                    warn!("Dropping message containing NUL bytes");
+                    return;
+                };
+                unsafe { callback(user_data.0, channel.as_ptr(), data.as_ptr()) };
+            })
+            .await;
+            ws.subscribe(subscription.clone()).await.map_err(ws_error)
+        })?;
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, subscription);
+        Ok(id)
+    }
+
+    fn unsubscribe(&self, id: u64) -> FfiResult<()> {
+        let subscription = self
+            .subscriptions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+            .ok_or_else(|| FfiError::invalid(format!("unknown subscription id {}", id)))?;
+
+        runtime().block_on(async {
+            let ws = self.connected().await?;
+            ws.unregister_handler(&subscription).await;
+            ws.unsubscribe(subscription).await.map_err(ws_error)
+        })
+    }
+
+    /// Shut the socket down without blocking (safe to call from a callback)
+    pub(crate) fn close(&self) {
+        let ws = Arc::clone(&self.ws);
+        runtime().spawn(async move {
+            if let Some(ws) = ws.lock().await.take() {
+                let _ = ws.shutdown().await;
+            }
+        });
+    }
+}
+
+fn ws_error(e: impl std::fmt::Display) -> FfiError {
+    FfiError::new(HlStatus::Network, format!("WebSocket error: {}", e))
+}
+
+/// Subscribe to a market data channel
+///
+/// `channel` is the wire channel name (`"l2Book"`, `"trades"`, `"bbo"`,
+/// `"candle"`, `"allMids"`, `"userFills"`, ...); `coin`, `interval` and `user`
+/// are passed when the channel needs them and may otherwise be null. Each
+/// message is delivered to `callback` with `user_data`. Subscribing to the
+/// same channel and parameters again replaces the previous callback. The
+/// subscription id is written to `out_id`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn hl_subscribe(
+    client: *const HlClient,
+    channel: *const c_char,
+    coin: *const c_char,
+    interval: *const c_char,
+    user: *const c_char,
+    callback: HlMessageCallback,
+    user_data: *mut c_void,
+    out_id: *mut u64,
+) -> HlStatus {
+    guard(|| {
+        let client = client
+            .as_ref()
+            .ok_or_else(|| FfiError::invalid("client must not be null"))?;
+        let channel = required_str(channel, "channel")?;
+        let callback = callback.ok_or_else(|| FfiError::invalid("callback must not be null"))?;
+        if out_id.is_null() {
+            return Err(FfiError::invalid("output pointer must not be null"));
+        }
+
+        let mut value = json!({ "type": channel });
+        for (key, arg) in [("coin", coin), ("interval", interval), ("user", user)] {
+            if let Some(arg) = optional_str(arg, key)? {
+                value[key] = json!(arg);
+            }
+        }
+        let subscription: Subscription = serde_json::from_value(value)
+            .map_err(|e| FfiError::invalid(format!("invalid subscription {}: {}", channel, e)))?;
+
+        *out_id = client
+            .feed
+            .subscribe(subscription, callback, UserData(user_data))?;
+        Ok(())
+    })
+}
+
+/// Cancel a subscription made with `hl_subscribe`
+#[no_mangle]
+pub unsafe extern "C" fn hl_unsubscribe(client: *const HlClient, subscription_id: u64) -> HlStatus {
+    guard(|| {
+        let client = client
+            .as_ref()
+            .ok_or_else(|| FfiError::invalid("client must not be null"))?;
+        client.feed.unsubscribe(subscription_id)
+    })
+}
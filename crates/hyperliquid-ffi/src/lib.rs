@@ -0,0 +1,249 @@
+//! C ABI for the Hyperliquid Rust SDK
+//!
+//! Exposes client lifecycle, order placement and a callback-based market data
+//! feed as `extern "C"` functions, for use from C, C++ and C# (P/Invoke). The
+//! header is generated into `include/hyperliquid.h` by cbindgen.
+//!
+//! Conventions:
+//! - Every fallible function returns an [`HlStatus`]; on failure,
+//!   [`hl_last_error`] describes the error on the calling thread.
+//! - Results are written through out-pointers. Strings returned by the library
+//!   are NUL-terminated JSON owned by the caller and must be released with
+//!   [`hl_string_free`].
+//! - Calls block the calling thread; requests run on an internal tokio
+//!   runtime. Clients may be shared between threads.
+//! - Breaking changes to any exported signature bump [`HL_ABI_VERSION`].
+//!
+//! ```c
+//! HlClient *client = NULL;
+//! if (hl_client_new("https://api.hyperliquid.xyz", key_hex, &client) != HL_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", hl_last_error());
+//! }
+//! char *response = NULL;
+//! hl_place_order(client, "BTC", true, 0.01, 65000.0, "Gtc", false, NULL, &response);
+//! hl_string_free(response);
+//! hl_client_free(client);
+//! ```
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+
+use hyperliquid_core::HyperliquidError;
+
+mod client;
+mod feed;
+
+pub use client::{
+    hl_cancel_order, hl_cancel_order_by_cloid, hl_client_address, hl_client_free, hl_client_new,
+    hl_place_order, HlClient,
+};
+pub use feed::{hl_subscribe, hl_unsubscribe, HlMessageCallback};
+
+/// ABI version of this library; bumped on any breaking change
+pub const HL_ABI_VERSION: u32 = 1;
+
+/// Result of an FFI call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HlStatus {
+    /// Success
+    Ok = 0,
+    /// A required argument was null or malformed
+    InvalidArgument = 1,
+    /// Connection or transport failure, including timeouts
+    Network = 2,
+    /// Request was rate limited
+    RateLimited = 3,
+    /// Exchange rejected the request
+    Rejected = 4,
+    /// Key loading or signing failed
+    Signing = 5,
+    /// Operation needs a private key but the client was created without one
+    NoWallet = 6,
+    /// Unexpected internal error (including caught panics)
+    Internal = 99,
+}
+
+/// Error carried to the C boundary
+#[derive(Debug)]
+pub(crate) struct FfiError {
+    status: HlStatus,
+    message: String,
+}
+
+impl FfiError {
+    pub(crate) fn new(status: HlStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn invalid(message: impl Into<String>) -> Self {
+        Self::new(HlStatus::InvalidArgument, message)
+    }
+}
+
+impl From<HyperliquidError> for FfiError {
+    fn from(err: HyperliquidError) -> Self {
+        let status = match &err {
+            HyperliquidError::Network(_)
+            | HyperliquidError::Timeout(_)
+            | HyperliquidError::RetryExhausted { .. }
+            | HyperliquidError::WebSocket(_)
+            | HyperliquidError::Tls(_) => HlStatus::Network,
+            HyperliquidError::RateLimit(_) | HyperliquidError::RateLimitWithRetry { .. } => {
+                HlStatus::RateLimited
+            }
+            HyperliquidError::Client { .. } => HlStatus::Rejected,
+            HyperliquidError::Signing(_) | HyperliquidError::Authentication(_) => HlStatus::Signing,
+            HyperliquidError::Validation(_)
+            | HyperliquidError::Config(_)
+            | HyperliquidError::InvalidUrl(_) => HlStatus::InvalidArgument,
+            _ => HlStatus::Internal,
+        };
+        Self::new(status, err.to_string())
+    }
+}
+
+pub(crate) type FfiResult<T> = Result<T, FfiError>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: &str) {
+    // Interior NULs would truncate the message; replace them
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run an FFI body, converting errors and panics into a status code
+pub(crate) fn guard(f: impl FnOnce() -> FfiResult<()>) -> HlStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => HlStatus::Ok,
+        Ok(Err(e)) => {
+            set_last_error(&e.message);
+            e.status
+        }
+        Err(_) => {
+            set_last_error("internal panic");
+            HlStatus::Internal
+        }
+    }
+}
+
+/// Shared runtime driving all clients
+pub(crate) fn runtime() -> &'static tokio::runtime::Runtime {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("hyperliquid-ffi")
+            .build()
+            .expect("failed to start tokio runtime")
+    })
+}
+
+/// Read a required C string argument
+pub(crate) unsafe fn required_str<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    optional_str(ptr, name)?.ok_or_else(|| FfiError::invalid(format!("{} must not be null", name)))
+}
+
+/// Read an optional (nullable) C string argument
+pub(crate) unsafe fn optional_str<'a>(
+    ptr: *const c_char,
+    name: &str,
+) -> FfiResult<Option<&'a str>> {
+    if ptr.is_null() {
+        return Ok(None);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map(Some)
+        .map_err(|_| FfiError::invalid(format!("{} is not valid UTF-8", name)))
+}
+
+/// Hand a string to the caller through `out`
+pub(crate) unsafe fn write_string(out: *mut *mut c_char, value: String) -> FfiResult<()> {
+    if out.is_null() {
+        return Err(FfiError::invalid("output pointer must not be null"));
+    }
+    let value =
+        CString::new(value).map_err(|_| FfiError::new(HlStatus::Internal, "NUL in output"))?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+/// ABI version of the loaded library; compare against `HL_ABI_VERSION`
+#[no_mangle]
+pub extern "C" fn hl_abi_version() -> u32 {
+    HL_ABI_VERSION
+}
+
+/// Message for the last error on this thread, or null if none
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn hl_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map(|message| message.as_ptr())
+            .unwrap_or(std::ptr::null())
+    })
+}
+
+/// Free a string returned by this library (null is ignored)
+#[no_mangle]
+pub unsafe extern "C" fn hl_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_errors_set_last_error() {
+        let status = guard(|| Err(FfiError::invalid("coin must not be null")));
+        assert_eq!(status, HlStatus::InvalidArgument);
+        let message = unsafe { CStr::from_ptr(hl_last_error()) };
+        assert_eq!(message.to_str().unwrap(), "coin must not be null");
+
+        let status = guard(|| panic!("boom"));
+        assert_eq!(status, HlStatus::Internal);
+    }
+
+    #[test]
+    fn test_null_arguments_are_rejected() {
+        let mut client: *mut HlClient = ptr::null_mut();
+        let status = unsafe { hl_client_new(ptr::null(), ptr::null(), &mut client) };
+        assert_eq!(status, HlStatus::InvalidArgument);
+        assert!(client.is_null());
+
+        let mut out: *mut c_char = ptr::null_mut();
+        let coin = CString::new("BTC").unwrap();
+        let status = unsafe { hl_cancel_order(ptr::null(), coin.as_ptr(), 1, &mut out) };
+        assert_eq!(status, HlStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        let mut out: *mut c_char = ptr::null_mut();
+        unsafe {
+            write_string(&mut out, "{\"status\":\"ok\"}".to_string()).unwrap();
+            assert_eq!(CStr::from_ptr(out).to_str().unwrap(), "{\"status\":\"ok\"}");
+            hl_string_free(out);
+            hl_string_free(ptr::null_mut());
+        }
+        assert_eq!(hl_abi_version(), HL_ABI_VERSION);
+    }
+}
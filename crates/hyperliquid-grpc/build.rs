@@ -1,9 +1,21 @@
+use std::env;
 use std::io::Result;
+use std::path::PathBuf;
 
 fn main() -> Result<()> {
+    let descriptor_path =
+        PathBuf::from(env::var("OUT_DIR").unwrap()).join("hyperliquid_descriptor.bin");
+
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
+        .file_descriptor_set_path(descriptor_path)
+        // JSON (de)serialization for the gateway, with proto field names
+        .message_attribute(
+            ".hyperliquid",
+            "#[derive(serde::Serialize, serde::Deserialize)]\n#[serde(default)]",
+        )
+        .enum_attribute(".hyperliquid", "#[derive(serde::Serialize, serde::Deserialize)]")
         .out_dir("src/pb")
         .compile(
             &["proto/hyperliquid.proto"],
@@ -0,0 +1,235 @@
+//! Caller authentication and roles
+//!
+//! Callers are identified either by a bearer token (`authorization: Bearer
+//! <token>` metadata) or, with mutual TLS, by the SHA-256 fingerprint of their
+//! client certificate. Each identity maps to a [`Role`]; order RPCs require
+//! [`Role::Trading`], everything else [`Role::ReadOnly`].
+//!
+//! ```no_run
+//! use hyperliquid_grpc::auth::{AuthConfig, Role};
+//!
+//! let auth = AuthConfig::new()
+//!     .with_token("dashboard-token", Role::ReadOnly)
+//!     .with_token("strategy-token", Role::Trading)
+//!     .with_certificate_fingerprint("3f:a1:...", Role::Trading);
+//! ```
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ring::digest::{digest, SHA256};
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+
+/// What an authenticated caller may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    /// Market data and account queries
+    ReadOnly,
+    /// Everything, including placing and cancelling orders
+    Trading,
+}
+
+impl Role {
+    /// Whether this role grants `required`
+    pub fn allows(self, required: Role) -> bool {
+        self >= required
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "read_only" | "readonly" => Ok(Role::ReadOnly),
+            "trading" => Ok(Role::Trading),
+            other => Err(format!("unknown role: {}", other)),
+        }
+    }
+}
+
+/// Known callers and their roles
+///
+/// Tokens are stored as SHA-256 digests so lookups don't compare secrets
+/// byte by byte.
+#[derive(Debug, Clone, Default)]
+pub struct AuthConfig {
+    tokens: HashMap<Vec<u8>, Role>,
+    certificates: HashMap<Vec<u8>, Role>,
+    anonymous: Option<Role>,
+}
+
+impl AuthConfig {
+    /// An empty configuration that rejects every caller
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept `token` as a bearer token granting `role`
+    pub fn with_token(mut self, token: impl AsRef<str>, role: Role) -> Self {
+        self.tokens.insert(sha256(token.as_ref().as_bytes()), role);
+        self
+    }
+
+    /// Accept the client certificate with this SHA-256 fingerprint
+    ///
+    /// The fingerprint is hex, optionally colon-separated as printed by
+    /// `openssl x509 -noout -fingerprint -sha256`. Invalid fingerprints are
+    /// ignored with a warning.
+    pub fn with_certificate_fingerprint(mut self, fingerprint: &str, role: Role) -> Self {
+        let cleaned: String = fingerprint
+            .trim_start_matches("sha256/")
+            .chars()
+            .filter(|c| *c != ':')
+            .collect();
+        match hex::decode(cleaned) {
+            Ok(bytes) if bytes.len() == 32 => {
+                self.certificates.insert(bytes, role);
+            }
+            _ => tracing::warn!("ignoring invalid certificate fingerprint: {}", fingerprint),
+        }
+        self
+    }
+
+    /// Grant `role` to callers presenting no credentials
+    pub fn with_anonymous(mut self, role: Role) -> Self {
+        self.anonymous = Some(role);
+        self
+    }
+
+    /// Identify the caller of `request`
+    ///
+    /// A token, when present, must be valid. Otherwise the highest role of the
+    /// presented client certificates applies, falling back to the anonymous
+    /// role.
+    pub fn authenticate<T>(&self, request: &Request<T>) -> Result<Role, Status> {
+        if let Some(header) = request.metadata().get("authorization") {
+            let token = header
+                .to_str()
+                .ok()
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .ok_or_else(|| Status::unauthenticated("malformed authorization header"))?;
+            return self
+                .tokens
+                .get(&sha256(token.trim().as_bytes()))
+                .copied()
+                .ok_or_else(|| Status::unauthenticated("invalid token"));
+        }
+
+        let from_certificate = request.peer_certs().and_then(|certs| {
+            certs
+                .iter()
+                .filter_map(|cert| self.certificates.get(&sha256(cert.get_ref())))
+                .max()
+                .copied()
+        });
+
+        from_certificate
+            .or(self.anonymous)
+            .ok_or_else(|| Status::unauthenticated("missing credentials"))
+    }
+
+    /// Wrap this configuration in a tonic interceptor
+    pub fn interceptor(self) -> AuthInterceptor {
+        AuthInterceptor {
+            config: Arc::new(self),
+        }
+    }
+}
+
+/// Interceptor that authenticates each call and records the caller's [`Role`]
+/// in the request extensions
+#[derive(Debug, Clone)]
+pub struct AuthInterceptor {
+    config: Arc<AuthConfig>,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let role = self.config.authenticate(&request)?;
+        request.extensions_mut().insert(role);
+        Ok(request)
+    }
+}
+
+/// Check the role recorded by [`AuthInterceptor`] grants `required`
+///
+/// Requests without a recorded role are only allowed when `enforced` is false,
+/// i.e. the server runs without authentication.
+pub fn authorize<T>(request: &Request<T>, required: Role, enforced: bool) -> Result<(), Status> {
+    match request.extensions().get::<Role>() {
+        Some(role) if role.allows(required) => Ok(()),
+        Some(role) => Err(Status::permission_denied(format!(
+            "{:?} callers may not perform this operation",
+            role
+        ))),
+        None if enforced => Err(Status::unauthenticated("missing credentials")),
+        None => Ok(()),
+    }
+}
+
+fn sha256(bytes: &[u8]) -> Vec<u8> {
+    digest(&SHA256, bytes).as_ref().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with_token(token: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        request
+    }
+
+    #[test]
+    fn test_token_roles() {
+        let auth = AuthConfig::new()
+            .with_token("reader", Role::ReadOnly)
+            .with_token("trader", Role::Trading);
+
+        assert_eq!(
+            auth.authenticate(&request_with_token("reader")).unwrap(),
+            Role::ReadOnly
+        );
+        assert_eq!(
+            auth.authenticate(&request_with_token("trader")).unwrap(),
+            Role::Trading
+        );
+        assert!(auth.authenticate(&request_with_token("nope")).is_err());
+        assert!(auth.authenticate(&Request::new(())).is_err());
+
+        let open = auth.with_anonymous(Role::ReadOnly);
+        assert_eq!(
+            open.authenticate(&Request::new(())).unwrap(),
+            Role::ReadOnly
+        );
+    }
+
+    #[test]
+    fn test_authorize() {
+        let mut interceptor = AuthConfig::new()
+            .with_token("reader", Role::ReadOnly)
+            .interceptor();
+        let request = interceptor.call(request_with_token("reader")).unwrap();
+
+        assert!(authorize(&request, Role::ReadOnly, true).is_ok());
+        let denied = authorize(&request, Role::Trading, true).unwrap_err();
+        assert_eq!(denied.code(), tonic::Code::PermissionDenied);
+
+        assert!(authorize(&Request::new(()), Role::Trading, false).is_ok());
+        assert!(authorize(&Request::new(()), Role::Trading, true).is_err());
+    }
+
+    #[test]
+    fn test_role_parsing() {
+        assert_eq!("read-only".parse::<Role>().unwrap(), Role::ReadOnly);
+        assert_eq!("Trading".parse::<Role>().unwrap(), Role::Trading);
+        assert!("admin".parse::<Role>().is_err());
+    }
+}
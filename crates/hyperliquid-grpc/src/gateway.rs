@@ -0,0 +1,185 @@
+//! JSON gateway (feature `gateway`)
+//!
+//! An axum router exposing each unary RPC as `POST /v1/<Method>`, for clients
+//! that can't speak gRPC. Request and response bodies are the protobuf
+//! messages as JSON with the proto field names, and requests go through the
+//! same [`HyperliquidGrpcServer`] handlers, so validation, signing and roles
+//! behave identically. Bearer tokens are accepted in the `Authorization`
+//! header; client certificates are not.
+//!
+//! ```text
+//! curl -X POST localhost:8080/v1/GetL2Book -d '{"coin": "BTC", "levels": 5}'
+//! curl -X POST localhost:8080/v1/PlaceOrder \
+//!     -H 'Authorization: Bearer strategy-token' \
+//!     -d '{"order": {"coin": "BTC", "is_buy": true, "sz": "0.01", "limit_px": "65000"}}'
+//! ```
+//!
+//! Errors are returned as `{"code": "InvalidArgument", "message": "..."}` with
+//! the HTTP status corresponding to the gRPC code. Streaming RPCs are not
+//! available through the gateway.
+
+use std::net::SocketAddr;
+
+use axum::extract::State;
+use axum::http::{header::AUTHORIZATION, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use tonic::{Code, Status};
+
+use crate::server::pb::hyperliquid_service_server::HyperliquidService;
+use crate::server::pb::*;
+use crate::server::HyperliquidGrpcServer;
+
+/// gRPC status carried to an HTTP response
+#[derive(Debug)]
+pub struct GatewayError(pub Status);
+
+impl From<Status> for GatewayError {
+    fn from(status: Status) -> Self {
+        Self(status)
+    }
+}
+
+impl IntoResponse for GatewayError {
+    fn into_response(self) -> Response {
+        let body = json!({
+            "code": format!("{:?}", self.0.code()),
+            "message": self.0.message(),
+        });
+        (http_status(self.0.code()), Json(body)).into_response()
+    }
+}
+
+/// HTTP status for a gRPC code, following the google.rpc mapping
+fn http_status(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Build the tonic request for `body`, authenticating it like the gRPC
+/// interceptor would
+fn to_request<T>(
+    server: &HyperliquidGrpcServer,
+    headers: &HeaderMap,
+    body: T,
+) -> Result<tonic::Request<T>, GatewayError> {
+    let mut request = tonic::Request::new(body);
+    if let Some(value) = headers.get(AUTHORIZATION) {
+        let value = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| Status::unauthenticated("malformed authorization header"))?;
+        request.metadata_mut().insert("authorization", value);
+    }
+    if let Some(auth) = server.auth() {
+        let role = auth.authenticate(&request)?;
+        request.extensions_mut().insert(role);
+    }
+    Ok(request)
+}
+
+/// Route `POST /v1/<Method>` to the handler of the same RPC
+macro_rules! rpc {
+    ($method:ident, $request:ty) => {
+        post(
+            |State(server): State<HyperliquidGrpcServer>,
+             headers: HeaderMap,
+             Json(body): Json<$request>| async move {
+                let request = to_request(&server, &headers, body)?;
+                let response = server.$method(request).await?;
+                Ok::<_, GatewayError>(Json(response.into_inner()))
+            },
+        )
+    };
+}
+
+/// Router serving the JSON gateway for `server`
+pub fn router(server: HyperliquidGrpcServer) -> Router {
+    Router::new()
+        .route("/healthz", get(|| async { StatusCode::OK }))
+        .route("/v1/GetMeta", rpc!(get_meta, MetaRequest))
+        .route("/v1/GetUserState", rpc!(get_user_state, UserStateRequest))
+        .route("/v1/GetAllMids", rpc!(get_all_mids, AllMidsRequest))
+        .route("/v1/GetL2Book", rpc!(get_l2_book, L2BookRequest))
+        .route("/v1/GetTrades", rpc!(get_trades, TradesRequest))
+        .route("/v1/GetCandles", rpc!(get_candles, CandlesRequest))
+        .route("/v1/QueryOrder", rpc!(query_order, QueryOrderRequest))
+        .route("/v1/PlaceOrder", rpc!(place_order, PlaceOrderRequest))
+        .route("/v1/CancelOrder", rpc!(cancel_order, CancelOrderRequest))
+        .route("/v1/BatchOrders", rpc!(batch_orders, BatchOrdersRequest))
+        .route("/v1/ModifyOrder", rpc!(modify_order, ModifyOrderRequest))
+        .route(
+            "/v1/GetOpenOrders",
+            rpc!(get_open_orders, OpenOrdersRequest),
+        )
+        .route(
+            "/v1/SetTradingEnabled",
+            rpc!(set_trading_enabled, SetTradingEnabledRequest),
+        )
+        .route(
+            "/v1/GetTradingStatus",
+            rpc!(get_trading_status, TradingStatusRequest),
+        )
+        .with_state(server)
+}
+
+/// Serve the JSON gateway for `server` on `addr`
+pub async fn serve_gateway(
+    server: HyperliquidGrpcServer,
+    addr: SocketAddr,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if server.auth().is_some() {
+        tracing::warn!("JSON gateway serves plain HTTP; terminate TLS in front of it");
+    }
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    println!("JSON gateway listening on {}", addr);
+    axum::serve(listener, router(server)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_status() {
+        assert_eq!(http_status(Code::InvalidArgument), StatusCode::BAD_REQUEST);
+        assert_eq!(http_status(Code::PermissionDenied), StatusCode::FORBIDDEN);
+        assert_eq!(
+            http_status(Code::ResourceExhausted),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(
+            http_status(Code::Internal),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn test_order_request_json() {
+        let request: PlaceOrderRequest = serde_json::from_value(json!({
+            "order": {"coin": "BTC", "is_buy": true, "sz": "0.01", "limit_px": "65000"}
+        }))
+        .unwrap();
+        let order = request.order.unwrap();
+        assert_eq!(order.coin, "BTC");
+        assert!(order.is_buy);
+        assert!(order.time_in_force.is_empty());
+    }
+}
@@ -5,6 +5,18 @@
 
 pub mod server;
 pub mod pb;
+pub mod orders;
+pub mod auth;
+pub mod metrics;
+pub mod sessions;
+#[cfg(feature = "gateway")]
+pub mod gateway;
 
-pub use server::{HyperliquidGrpcServer, serve};
+pub use auth::{AuthConfig, AuthInterceptor, Role};
+pub use metrics::MetricsLayer;
+#[cfg(feature = "gateway")]
+pub use gateway::{router as gateway_router, serve_gateway};
+pub use orders::OrderSigner;
+pub use sessions::SessionStore;
+pub use server::{HyperliquidGrpcServer, ServeConfig, TlsFiles, serve, serve_with};
 pub use pb::hyperliquid_service_server::HyperliquidServiceServer;
\ No newline at end of file
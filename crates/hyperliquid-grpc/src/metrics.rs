@@ -0,0 +1,107 @@
+//! RPC metrics
+//!
+//! [`MetricsLayer`] records a counter and a latency histogram per RPC through
+//! the `metrics` facade, so they show up on the Prometheus exporter started by
+//! `hyperliquid_core::start_prometheus_exporter`:
+//!
+//! - `hyperliquid_grpc_requests_total{method, code}`
+//! - `hyperliquid_grpc_request_duration_seconds{method}`
+//!
+//! For streaming RPCs the duration covers the time to the response headers,
+//! not the lifetime of the stream.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use tonic::Code;
+use tower::{Layer, Service};
+
+/// Tower layer adding RPC metrics to every service on the server
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsLayer;
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner }
+    }
+}
+
+/// Service produced by [`MetricsLayer`]
+#[derive(Debug, Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for MetricsService<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let method = request.uri().path().to_string();
+        let start = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            let code = match &result {
+                Ok(response) => grpc_code(response.headers()),
+                Err(_) => Code::Internal,
+            };
+            record(method, code, start.elapsed().as_secs_f64());
+            result
+        })
+    }
+}
+
+/// Status of a response; unary successes carry `grpc-status` in the trailers,
+/// so a missing header means `Ok`
+fn grpc_code(headers: &http::HeaderMap) -> Code {
+    headers
+        .get("grpc-status")
+        .map(|status| Code::from_bytes(status.as_bytes()))
+        .unwrap_or(Code::Ok)
+}
+
+fn record(method: String, code: Code, seconds: f64) {
+    // Unknown paths come from arbitrary clients; don't let them create series
+    let method = if code == Code::Unimplemented {
+        "unknown".to_string()
+    } else {
+        method
+    };
+    metrics::counter!(
+        "hyperliquid_grpc_requests_total",
+        "method" => method.clone(),
+        "code" => format!("{:?}", code),
+    )
+    .increment(1);
+    metrics::histogram!("hyperliquid_grpc_request_duration_seconds", "method" => method)
+        .record(seconds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_code() {
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(grpc_code(&headers), Code::Ok);
+
+        headers.insert("grpc-status", "7".parse().unwrap());
+        assert_eq!(grpc_code(&headers), Code::PermissionDenied);
+    }
+}
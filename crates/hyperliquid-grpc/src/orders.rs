@@ -0,0 +1,445 @@
+//! Server-side order signing
+//!
+//! The gRPC server holds a single signing key and submits orders on behalf of
+//! its callers, acting as a central order gateway. Exchange responses are
+//! translated into typed [`pb::OrderStatus`] values.
+//!
+//! Orders for coins disabled in the signer's [`KillSwitchRegistry`] are
+//! rejected without being sent, unless they are reduce-only.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+use tonic::Status;
+
+use hyperliquid_core::crypto::Wallet;
+use hyperliquid_core::exchange::{ExchangeClient, ExchangeClientConfig};
+use hyperliquid_core::killswitch::{KillSwitchRegistry, Scope};
+use hyperliquid_core::types::WireFormat;
+use hyperliquid_core::{Config, Environment, HttpClient, HyperliquidError, InfoClient};
+
+use crate::server::pb::{self, OrderState};
+use crate::server::HyperliquidGrpcServer;
+
+/// Signing key and exchange client used for order RPCs
+pub struct OrderSigner {
+    exchange: ExchangeClient,
+    info: InfoClient,
+    wallet: Wallet,
+    vault_address: Option<String>,
+    assets: OnceCell<HashMap<String, (u32, WireFormat)>>,
+    kill_switches: KillSwitchRegistry,
+}
+
+impl OrderSigner {
+    /// Create a signer for `private_key` (hex) against `base_url`
+    pub fn new(private_key: &str, base_url: &str) -> Result<Self, HyperliquidError> {
+        let mainnet = base_url.contains(Environment::Mainnet.base_url());
+        let wallet = Wallet::new(private_key, mainnet)?;
+        let account = wallet
+            .address()
+            .parse()
+            .map_err(|_| HyperliquidError::Signing("invalid wallet address".to_string()))?;
+        let mut config = if mainnet {
+            ExchangeClientConfig::mainnet(account)
+        } else {
+            ExchangeClientConfig::testnet(account)
+        };
+        config.base_url = base_url.to_string();
+
+        Ok(Self {
+            exchange: ExchangeClient::new(config),
+            info: InfoClient::new(HttpClient::with_default_config(base_url.to_string())?),
+            wallet,
+            vault_address: None,
+            assets: OnceCell::new(),
+            kill_switches: KillSwitchRegistry::new(),
+        })
+    }
+
+    /// Create a signer from `security.private_key`, if one is configured
+    pub fn from_config(config: &Config) -> Result<Option<Self>, HyperliquidError> {
+        let Some(key) = config.security.private_key.as_ref() else {
+            return Ok(None);
+        };
+        let base_url = config
+            .environment
+            .base_url
+            .clone()
+            .unwrap_or_else(|| config.environment.env.base_url().to_string());
+        Self::new(key.expose(), &base_url).map(Some)
+    }
+
+    /// Trade on behalf of a vault or subaccount
+    pub fn with_vault_address(mut self, vault_address: impl Into<String>) -> Self {
+        self.vault_address = Some(vault_address.into());
+        self
+    }
+
+    /// Share `switches` with other components instead of a private registry
+    pub fn with_kill_switches(mut self, switches: KillSwitchRegistry) -> Self {
+        self.kill_switches = switches;
+        self
+    }
+
+    /// Address of the signing wallet
+    pub fn address(&self) -> String {
+        self.wallet.address()
+    }
+
+    /// Switches consulted before orders are sent
+    pub fn kill_switches(&self) -> &KillSwitchRegistry {
+        &self.kill_switches
+    }
+
+    /// Resolve a coin to its perp asset index
+    async fn asset(&self, coin: &str) -> Result<u32, Status> {
+        Ok(self.asset_format(coin).await?.0)
+    }
+
+    /// Resolve a coin to its perp asset index and price and size rules
+    async fn asset_format(&self, coin: &str) -> Result<(u32, WireFormat), Status> {
+        let assets = self
+            .assets
+            .get_or_try_init(|| async {
+                let meta = self.info.meta("").await?;
+                Ok::<_, HyperliquidError>(
+                    meta.universe
+                        .iter()
+                        .enumerate()
+                        .map(|(index, asset)| {
+                            let format = WireFormat::perp(asset.szDecimals.max(0) as u32);
+                            (asset.name.clone(), (index as u32, format))
+                        })
+                        .collect(),
+                )
+            })
+            .await
+            .map_err(HyperliquidGrpcServer::map_error)?;
+        assets
+            .get(coin)
+            .copied()
+            .ok_or_else(|| Status::invalid_argument(format!("unknown coin: {}", coin)))
+    }
+
+    async fn submit(&self, action: Value) -> Result<Value, Status> {
+        self.exchange
+            .post_signed_action(action, &self.wallet, self.vault_address.as_deref())
+            .await
+            .map_err(HyperliquidGrpcServer::map_error)
+    }
+
+    /// Place `orders` in one signed action, returning a status per order
+    ///
+    /// Orders refused by a kill switch are rejected in place; the others are
+    /// still sent.
+    pub async fn place_orders(
+        &self,
+        orders: &[pb::OrderRequest],
+    ) -> Result<Vec<pb::OrderStatus>, Status> {
+        if orders.is_empty() {
+            return Err(Status::invalid_argument("at least one order is required"));
+        }
+        let mut statuses: Vec<Option<pb::OrderStatus>> = vec![None; orders.len()];
+        let mut wires = Vec::with_capacity(orders.len());
+        let mut sent = Vec::with_capacity(orders.len());
+        for (index, order) in orders.iter().enumerate() {
+            if !order.reduce_only {
+                if let Err(e) = self.kill_switches.check(&order.coin) {
+                    statuses[index] = Some(rejected(&e.to_string(), &order.cloid));
+                    continue;
+                }
+            }
+            let (asset, format) = self.asset_format(&order.coin).await?;
+            wires.push(order_wire(asset, format, order)?);
+            sent.push(index);
+        }
+
+        if !wires.is_empty() {
+            let response = self
+                .submit(json!({"type": "order", "orders": wires, "grouping": "na"}))
+                .await?;
+            let cloids: Vec<&str> = sent
+                .iter()
+                .map(|&index| orders[index].cloid.as_str())
+                .collect();
+            for (index, status) in sent.into_iter().zip(parse_statuses(&response, &cloids)) {
+                statuses[index] = Some(status);
+            }
+        }
+        Ok(statuses.into_iter().flatten().collect())
+    }
+
+    /// Cancel the account's open orders in `scope`, returning how many were
+    /// cancelled
+    pub async fn cancel_open_orders(&self, scope: &Scope) -> Result<u32, Status> {
+        let user = self
+            .vault_address
+            .clone()
+            .unwrap_or_else(|| self.wallet.address());
+        let open = self
+            .info
+            .open_orders(&user, "")
+            .await
+            .map_err(HyperliquidGrpcServer::map_error)?;
+        let mut cancels = Vec::new();
+        for order in open.iter().filter(|order| scope.covers(&order.coin)) {
+            let asset = self.asset(&order.coin).await?;
+            cancels.push(json!({"a": asset, "o": order.oid}));
+        }
+        if cancels.is_empty() {
+            return Ok(0);
+        }
+
+        let response = self
+            .submit(json!({"type": "cancel", "cancels": cancels}))
+            .await?;
+        let statuses = parse_statuses(&response, &vec![""; cancels.len()]);
+        Ok(statuses
+            .iter()
+            .filter(|status| status.state != OrderState::Rejected as i32)
+            .count() as u32)
+    }
+
+    /// Cancel one order by exchange order id or, if `order_id` is empty, by cloid
+    pub async fn cancel_order(
+        &self,
+        request: &pb::CancelOrderRequest,
+    ) -> Result<pb::OrderStatus, Status> {
+        let asset = self.asset(&request.coin).await?;
+        let response = self.submit(cancel_action(asset, request)?).await?;
+        let mut statuses = parse_statuses(&response, &[request.cloid.as_str()]);
+        let mut status = statuses
+            .pop()
+            .unwrap_or_else(|| rejected("missing status", ""));
+        if status.state == OrderState::Unspecified as i32 {
+            status.state = OrderState::Canceled as i32;
+            status.oid = request.order_id.parse().unwrap_or_default();
+        }
+        Ok(status)
+    }
+}
+
+/// Build the cancel action for one order, by oid or else by cloid
+fn cancel_action(asset: u32, request: &pb::CancelOrderRequest) -> Result<Value, Status> {
+    if !request.order_id.is_empty() {
+        let oid: u64 = request
+            .order_id
+            .parse()
+            .map_err(|_| Status::invalid_argument("order_id must be a number"))?;
+        Ok(json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]}))
+    } else if !request.cloid.is_empty() {
+        Ok(json!({
+            "type": "cancelByCloid",
+            "cancels": [{"asset": asset, "cloid": request.cloid}],
+        }))
+    } else {
+        Err(Status::invalid_argument("order_id or cloid is required"))
+    }
+}
+
+/// Build the wire form of a limit order
+///
+/// The size and price are re-emitted in the canonical form of `format`, so
+/// `"0.010"` is sent as `"0.01"` and off-tick prices are refused here.
+fn order_wire(asset: u32, format: WireFormat, order: &pb::OrderRequest) -> Result<Value, Status> {
+    if !matches!(order.order_type.to_ascii_lowercase().as_str(), "" | "limit") {
+        return Err(Status::invalid_argument(format!(
+            "unsupported order_type: {}",
+            order.order_type
+        )));
+    }
+    let tif = match order.time_in_force.to_ascii_lowercase().as_str() {
+        "" | "gtc" => "Gtc",
+        "ioc" => "Ioc",
+        "alo" => "Alo",
+        other => {
+            return Err(Status::invalid_argument(format!(
+                "unsupported time_in_force: {}",
+                other
+            )))
+        }
+    };
+    let positive = |name: &str, value: &str| {
+        Decimal::from_str(value)
+            .ok()
+            .filter(|value| value.is_sign_positive() && !value.is_zero())
+            .ok_or_else(|| Status::invalid_argument(format!("{} must be a positive decimal", name)))
+    };
+    let sz = format
+        .size(positive("sz", &order.sz)?)
+        .map_err(|e| Status::invalid_argument(format!("sz: {}", e)))?;
+    let limit_px = format
+        .price(positive("limit_px", &order.limit_px)?)
+        .map_err(|e| Status::invalid_argument(format!("limit_px: {}", e)))?;
+
+    let mut wire = json!({
+        "a": asset,
+        "b": order.is_buy,
+        "p": limit_px,
+        "s": sz,
+        "r": order.reduce_only,
+        "t": {"limit": {"tif": tif}},
+    });
+    if !order.cloid.is_empty() {
+        wire["c"] = json!(order.cloid);
+    }
+    Ok(wire)
+}
+
+fn rejected(error: &str, cloid: &str) -> pb::OrderStatus {
+    pb::OrderStatus {
+        state: OrderState::Rejected as i32,
+        cloid: cloid.to_string(),
+        error: error.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Translate `response.data.statuses` into one status per request entry
+///
+/// A whole-request error (`{"status": "err"}`) rejects every entry. Cancel
+/// responses report plain `"success"`, which is left `Unspecified` for the
+/// caller to fill in.
+fn parse_statuses(response: &Value, cloids: &[&str]) -> Vec<pb::OrderStatus> {
+    if response.get("status").and_then(Value::as_str) == Some("err") {
+        let reason = match response.get("response") {
+            Some(Value::String(reason)) => reason.clone(),
+            Some(other) => other.to_string(),
+            None => "unknown error".to_string(),
+        };
+        return cloids
+            .iter()
+            .map(|cloid| rejected(&reason, cloid))
+            .collect();
+    }
+
+    let statuses = response
+        .pointer("/response/data/statuses")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    cloids
+        .iter()
+        .enumerate()
+        .map(|(index, cloid)| {
+            let Some(status) = statuses.get(index) else {
+                return rejected("missing status", cloid);
+            };
+            let mut parsed = pb::OrderStatus {
+                cloid: cloid.to_string(),
+                ..Default::default()
+            };
+            if let Some(resting) = status.get("resting") {
+                parsed.state = OrderState::Resting as i32;
+                parsed.oid = resting["oid"].as_u64().unwrap_or_default();
+            } else if let Some(filled) = status.get("filled") {
+                parsed.state = OrderState::Filled as i32;
+                parsed.oid = filled["oid"].as_u64().unwrap_or_default();
+                parsed.total_sz = filled["totalSz"].as_str().unwrap_or_default().to_string();
+                parsed.avg_px = filled["avgPx"].as_str().unwrap_or_default().to_string();
+            } else if let Some(error) = status.get("error").and_then(Value::as_str) {
+                return rejected(error, cloid);
+            }
+            parsed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(tif: &str) -> pb::OrderRequest {
+        pb::OrderRequest {
+            coin: "BTC".to_string(),
+            is_buy: true,
+            sz: "0.01".to_string(),
+            limit_px: "65000".to_string(),
+            order_type: String::new(),
+            time_in_force: tif.to_string(),
+            reduce_only: false,
+            cloid: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_order_wire() {
+        let btc = WireFormat::perp(5);
+        let wire = order_wire(3, btc, &order("ioc")).unwrap();
+        assert_eq!(wire["a"], 3);
+        assert_eq!(wire["p"], "65000");
+        assert_eq!(wire["t"]["limit"]["tif"], "Ioc");
+        assert!(wire.get("c").is_none());
+
+        // Re-emitted in canonical form
+        let mut padded = order("");
+        padded.sz = "0.0100".to_string();
+        padded.limit_px = "65000.0".to_string();
+        let wire = order_wire(3, btc, &padded).unwrap();
+        assert_eq!(wire["s"], "0.01");
+        assert_eq!(wire["p"], "65000");
+
+        assert!(order_wire(0, btc, &order("fok")).is_err());
+        let mut bad = order("");
+        bad.sz = "-1".to_string();
+        assert!(order_wire(0, btc, &bad).is_err());
+        bad.sz = "0.000001".to_string();
+        assert!(order_wire(0, btc, &bad).is_err());
+        let mut off_tick = order("");
+        off_tick.limit_px = "65000.5".to_string();
+        assert!(order_wire(0, btc, &off_tick).is_err());
+    }
+
+    #[test]
+    fn test_cancel_action() {
+        let mut request = pb::CancelOrderRequest {
+            coin: "BTC".to_string(),
+            order_id: "42".to_string(),
+            cloid: "0xabc".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            cancel_action(3, &request).unwrap(),
+            json!({"type": "cancel", "cancels": [{"a": 3, "o": 42}]})
+        );
+
+        request.order_id.clear();
+        assert_eq!(
+            cancel_action(3, &request).unwrap(),
+            json!({"type": "cancelByCloid", "cancels": [{"asset": 3, "cloid": "0xabc"}]})
+        );
+
+        request.cloid.clear();
+        assert!(cancel_action(3, &request).is_err());
+        request.order_id = "x".to_string();
+        assert!(cancel_action(3, &request).is_err());
+    }
+
+    #[test]
+    fn test_parse_statuses() {
+        let response = json!({"status": "ok", "response": {"type": "order", "data": {"statuses": [
+            {"resting": {"oid": 77}},
+            {"filled": {"oid": 78, "totalSz": "0.02", "avgPx": "64990.5"}},
+            {"error": "Insufficient margin to place order."},
+        ]}}});
+        let statuses = parse_statuses(&response, &["", "0xabc", ""]);
+        assert_eq!(statuses[0].state, OrderState::Resting as i32);
+        assert_eq!(statuses[0].oid, 77);
+        assert_eq!(statuses[1].state, OrderState::Filled as i32);
+        assert_eq!(statuses[1].avg_px, "64990.5");
+        assert_eq!(statuses[1].cloid, "0xabc");
+        assert_eq!(statuses[2].state, OrderState::Rejected as i32);
+        assert!(statuses[2].error.contains("Insufficient margin"));
+
+        let whole = json!({"status": "err", "response": "User or API Wallet does not exist."});
+        let statuses = parse_statuses(&whole, &["", ""]);
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses
+            .iter()
+            .all(|s| s.state == OrderState::Rejected as i32));
+    }
+}
@@ -2,12 +2,21 @@
 //!
 //! This module provides gRPC endpoints for the Hyperliquid SDK.
 
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
-use tonic::{transport::Server, Request, Response, Status, Code};
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+use tonic::server::NamedService;
+use tonic::{Request, Response, Status, Code};
+use tonic_health::ServingStatus;
 
 // Import generated protobuf code
 pub mod pb {
     tonic::include_proto!("hyperliquid");
+
+    /// Encoded descriptors for server reflection
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("hyperliquid_descriptor");
 }
 
 use pb::{
@@ -17,18 +26,29 @@ use pb::{
     TradesRequest, TradesResponse, CandlesRequest, CandlesResponse,
     QueryOrderRequest, QueryOrderResponse, PlaceOrderRequest, PlaceOrderResponse,
     CancelOrderRequest, CancelOrderResponse, ModifyOrderRequest, ModifyOrderResponse,
+    BatchOrdersRequest, BatchOrdersResponse, OrderState,
     OpenOrdersRequest, OpenOrdersResponse, StreamsSubscriptionRequest,
-    StreamResponse, Error as GrpcError,
+    StreamResponse, SetTradingEnabledRequest, SetTradingEnabledResponse,
+    TradingStatusRequest, TradingStatusResponse,
 };
 
 // Import core functionality
 use hyperliquid_core::{InfoClient, HttpClient, Config};
+use hyperliquid_core::killswitch::Scope;
 use hyperliquid_core::types::*;
 
+use crate::auth::{authorize, AuthConfig, Role};
+use crate::metrics::MetricsLayer;
+use crate::orders::OrderSigner;
+use crate::sessions::SessionStore;
+
 /// gRPC server implementation
 #[derive(Clone)]
 pub struct HyperliquidGrpcServer {
     info_client: InfoClient,
+    signer: Option<Arc<OrderSigner>>,
+    auth: Option<AuthConfig>,
+    sessions: SessionStore,
 }
 
 impl HyperliquidGrpcServer {
@@ -38,39 +58,80 @@ impl HyperliquidGrpcServer {
         let http_client = HttpClient::new_with_config(config.http.clone());
         let info_client = InfoClient::new(http_client);
 
-        Ok(Self { info_client })
+        let signer = OrderSigner::from_config(&config)?.map(Arc::new);
+
+        Ok(Self { info_client, signer, auth: None, sessions: SessionStore::new() })
+    }
+
+    /// Sign and submit orders with `signer`, enabling the order RPCs
+    pub fn with_signer(mut self, signer: OrderSigner) -> Self {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+
+    /// Require callers to authenticate; order RPCs need the trading role
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Serve stream subscriptions from `sessions`
+    pub fn with_sessions(mut self, sessions: SessionStore) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Stream sessions; publish updates here to deliver them to subscribers
+    pub fn sessions(&self) -> &SessionStore {
+        &self.sessions
+    }
+
+    /// Authentication settings, if callers must authenticate
+    pub(crate) fn auth(&self) -> Option<&AuthConfig> {
+        self.auth.as_ref()
+    }
+
+    fn authorize<T>(&self, request: &Request<T>, required: Role) -> Result<(), Status> {
+        authorize(request, required, self.auth.is_some())
+    }
+
+    fn signer(&self) -> Result<&OrderSigner, Status> {
+        self.signer
+            .as_deref()
+            .ok_or_else(|| Status::failed_precondition("no signer configured on this server"))
     }
 
     /// Convert core error to gRPC error
-    fn map_error(error: hyperliquid_core::error::HyperliquidError) -> Status {
+    pub(crate) fn map_error(error: hyperliquid_core::error::HyperliquidError) -> Status {
+        use hyperliquid_core::error::HyperliquidError as E;
+
         match error {
-            hyperliquid_core::error::HyperliquidError::Network(e) => {
-                Status::new(Code::Internal, format!("Network error: {}", e))
-            }
-            hyperliquid_core::error::HyperliquidError::Api { code, message } => {
-                Status::new(Code::Internal, format!("API error {}: {}", code, message))
-            }
-            hyperliquid_core::error::HyperliquidError::Signing(e) => {
-                Status::new(Code::InvalidArgument, format!("Signing error: {}", e))
+            E::Network(e) => Status::new(Code::Unavailable, format!("Network error: {}", e)),
+            E::Http { status, message, .. } => {
+                Status::new(Code::Internal, format!("HTTP error {}: {}", status, message))
             }
-            hyperliquid_core::error::HyperliquidError::Serialization(e) => {
-                Status::new(Code::Internal, format!("Serialization error: {}", e))
+            E::Server { status, message } => {
+                Status::new(Code::Unavailable, format!("Server error {}: {}", status, message))
             }
-            hyperliquid_core::error::HyperliquidError::Deserialization(e) => {
-                Status::new(Code::Internal, format!("Deserialization error: {}", e))
+            E::Client { code, message, .. } => {
+                Status::new(Code::FailedPrecondition, format!("API error {}: {}", code, message))
             }
-            hyperliquid_core::error::HyperliquidError::Timeout => {
-                Status::new(Code::DeadlineExceeded, "Request timeout")
+            E::Signing(e) => Status::new(Code::InvalidArgument, format!("Signing error: {}", e)),
+            E::Authentication(e) => {
+                Status::new(Code::Unauthenticated, format!("Authentication error: {}", e))
             }
-            hyperliquid_core::error::HyperliquidError::RateLimit => {
-                Status::new(Code::ResourceExhausted, "Rate limit exceeded")
+            E::Json(e) => Status::new(Code::Internal, format!("Serialization error: {}", e)),
+            E::Timeout(e) => Status::new(Code::DeadlineExceeded, format!("Request timeout: {}", e)),
+            E::RateLimit(_) | E::RateLimitWithRetry { .. } | E::RetryExhausted { .. } => {
+                Status::new(Code::ResourceExhausted, error.to_string())
             }
-            hyperliquid_core::error::HyperliquidError::Config(e) => {
+            E::Config(e) | E::InvalidUrl(e) | E::Tls(e) => {
                 Status::new(Code::InvalidArgument, format!("Configuration error: {}", e))
             }
-            hyperliquid_core::error::HyperliquidError::WebSocket(e) => {
-                Status::new(Code::Internal, format!("WebSocket error: {}", e))
-            }
+            E::Validation(e) => Status::new(Code::InvalidArgument, e),
+            E::TradingDisabled { .. } => Status::new(Code::FailedPrecondition, error.to_string()),
+            E::WebSocket(e) => Status::new(Code::Internal, format!("WebSocket error: {}", e)),
+            E::Unknown(e) => Status::new(Code::Unknown, e),
         }
     }
 
@@ -296,33 +357,75 @@ impl HyperliquidService for HyperliquidGrpcServer {
 
     async fn place_order(
         &self,
-        _request: Request<PlaceOrderRequest>,
+        request: Request<PlaceOrderRequest>,
     ) -> Result<Response<PlaceOrderResponse>, Status> {
-        // TODO: Implement order placement
+        self.authorize(&request, Role::Trading)?;
+        let request = request.into_inner();
+        let order = request
+            .order
+            .ok_or_else(|| Status::invalid_argument("order is required"))?;
+
+        let status = self
+            .signer()?
+            .place_orders(std::slice::from_ref(&order))
+            .await?
+            .pop()
+            .unwrap_or_default();
+        let success = status.state != OrderState::Rejected as i32;
         let response = PlaceOrderResponse {
-            success: false,
-            order_id: "".to_string(),
-            message: "Not implemented yet".to_string(),
+            success,
+            order_id: if status.oid > 0 { status.oid.to_string() } else { String::new() },
+            message: status.error.clone(),
+            status: Some(status),
         };
         Ok(Response::new(response))
     }
 
     async fn cancel_order(
         &self,
-        _request: Request<CancelOrderRequest>,
+        request: Request<CancelOrderRequest>,
     ) -> Result<Response<CancelOrderResponse>, Status> {
-        // TODO: Implement order cancellation
+        self.authorize(&request, Role::Trading)?;
+        let request = request.into_inner();
+
+        let status = self.signer()?.cancel_order(&request).await?;
         let response = CancelOrderResponse {
-            success: false,
-            message: "Not implemented yet".to_string(),
+            success: status.state == OrderState::Canceled as i32,
+            message: status.error.clone(),
+            status: Some(status),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn batch_orders(
+        &self,
+        request: Request<BatchOrdersRequest>,
+    ) -> Result<Response<BatchOrdersResponse>, Status> {
+        self.authorize(&request, Role::Trading)?;
+        let request = request.into_inner();
+
+        let statuses = self.signer()?.place_orders(&request.orders).await?;
+        let rejected = statuses
+            .iter()
+            .filter(|status| status.state == OrderState::Rejected as i32)
+            .count();
+        let response = BatchOrdersResponse {
+            success: rejected == 0,
+            message: if rejected == 0 {
+                String::new()
+            } else {
+                format!("{} of {} orders rejected", rejected, statuses.len())
+            },
+            statuses,
         };
         Ok(Response::new(response))
     }
 
     async fn modify_order(
         &self,
-        _request: Request<ModifyOrderRequest>,
+        request: Request<ModifyOrderRequest>,
     ) -> Result<Response<ModifyOrderResponse>, Status> {
+        self.authorize(&request, Role::Trading)?;
         // TODO: Implement order modification
         let response = ModifyOrderResponse {
             success: false,
@@ -342,42 +445,171 @@ impl HyperliquidService for HyperliquidGrpcServer {
         Ok(Response::new(response))
     }
 
+    async fn set_trading_enabled(
+        &self,
+        request: Request<SetTradingEnabledRequest>,
+    ) -> Result<Response<SetTradingEnabledResponse>, Status> {
+        self.authorize(&request, Role::Trading)?;
+        let request = request.into_inner();
+        let signer = self.signer()?;
+        let scope = if request.coin.is_empty() {
+            Scope::All
+        } else {
+            Scope::coin(request.coin)
+        };
+
+        if request.enabled {
+            signer.kill_switches().enable(&scope).map_err(Self::map_error)?;
+            return Ok(Response::new(SetTradingEnabledResponse {
+                success: true,
+                message: format!("trading enabled for {}", scope),
+                canceled: 0,
+            }));
+        }
+
+        let reason = if request.reason.is_empty() {
+            "disabled over gRPC".to_string()
+        } else {
+            request.reason
+        };
+        signer.kill_switches().disable(scope.clone(), reason).map_err(Self::map_error)?;
+        let canceled = if request.cancel_open_orders {
+            signer.cancel_open_orders(&scope).await?
+        } else {
+            0
+        };
+        Ok(Response::new(SetTradingEnabledResponse {
+            success: true,
+            message: format!("trading disabled for {}", scope),
+            canceled,
+        }))
+    }
+
+    async fn get_trading_status(
+        &self,
+        _request: Request<TradingStatusRequest>,
+    ) -> Result<Response<TradingStatusResponse>, Status> {
+        let halts = self
+            .signer()?
+            .kill_switches()
+            .halts()
+            .into_iter()
+            .map(|halt| pb::TradingHalt {
+                coin: match halt.scope {
+                    Scope::All => String::new(),
+                    Scope::Coin(coin) => coin,
+                },
+                reason: halt.reason,
+                since: halt.since,
+            })
+            .collect();
+        Ok(Response::new(TradingStatusResponse { halts }))
+    }
+
     type SubscribeToStreamsStream = tokio_stream::wrappers::ReceiverStream<Result<StreamResponse, Status>>;
 
     async fn subscribe_to_streams(
         &self,
-        _request: Request<StreamsSubscriptionRequest>,
+        request: Request<StreamsSubscriptionRequest>,
     ) -> Result<Response<Self::SubscribeToStreamsStream>, Status> {
-        // TODO: Implement streaming subscriptions
-        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let request = request.into_inner();
 
-        // Send error for now
-        let error = GrpcError {
-            code: "NOT_IMPLEMENTED".to_string(),
-            message: "Streaming not implemented yet".to_string(),
+        let receiver = if request.session_token.is_empty() {
+            self.sessions.open(request.subscriptions)?
+        } else {
+            self.sessions.resume(&request.session_token, request.last_sequence)?
         };
+        Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(receiver)))
+    }
+}
 
-        let stream_response = StreamResponse {
-            response: Some(pb::stream_response::Response::Error(error)),
-        };
+/// TLS certificate and key for the server, in PEM files
+#[derive(Debug, Clone)]
+pub struct TlsFiles {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// CA used to verify client certificates; setting it enables mutual TLS
+    pub client_ca_path: Option<PathBuf>,
+}
 
-        let _ = tx.send(Ok(stream_response)).await;
+impl TlsFiles {
+    fn load(&self) -> std::io::Result<ServerTlsConfig> {
+        let identity = Identity::from_pem(
+            std::fs::read(&self.cert_path)?,
+            std::fs::read(&self.key_path)?,
+        );
+        let mut tls = ServerTlsConfig::new().identity(identity);
+        if let Some(ca) = &self.client_ca_path {
+            tls = tls.client_ca_root(Certificate::from_pem(std::fs::read(ca)?));
+        }
+        Ok(tls)
+    }
+}
+
+/// Listener settings for [`serve_with`]
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    pub addr: SocketAddr,
+    pub tls: Option<TlsFiles>,
+}
 
-        Ok(Response::new(tokio_stream::wrappers::ReceiverStream::new(rx)))
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            addr: "[::1]:50051".parse().expect("valid default address"),
+            tls: None,
+        }
     }
 }
 
-/// Start the gRPC server
+/// Start the gRPC server with default settings
 pub async fn serve() -> Result<(), Box<dyn std::error::Error>> {
-    let addr = "[::1]:50051".parse()?;
     let server = HyperliquidGrpcServer::new().await?;
+    serve_with(server, ServeConfig::default()).await
+}
 
-    println!("gRPC server listening on {}", addr);
+/// Serve `server` with TLS and authentication as configured
+///
+/// Alongside the Hyperliquid service, the server exposes the standard
+/// `grpc.health.v1.Health` and server reflection services. Those two skip
+/// authentication so load balancers and tooling can reach them. Every RPC is
+/// recorded by [`MetricsLayer`].
+pub async fn serve_with(
+    server: HyperliquidGrpcServer,
+    config: ServeConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut builder = Server::builder();
+    if let Some(tls) = &config.tls {
+        builder = builder.tls_config(tls.load()?)?;
+    } else if server.auth.is_some() {
+        tracing::warn!("gRPC authentication enabled without TLS; tokens are sent in plaintext");
+    }
 
-    Server::builder()
-        .add_service(HyperliquidServiceServer::new(server))
-        .serve(addr)
-        .await?;
+    let (mut health, health_service) = tonic_health::server::health_reporter();
+    health
+        .set_service_status(
+            <HyperliquidServiceServer<HyperliquidGrpcServer> as NamedService>::NAME,
+            ServingStatus::Serving,
+        )
+        .await;
+    let reflection = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(pb::FILE_DESCRIPTOR_SET)
+        .build()?;
+
+    let router = builder
+        .layer(MetricsLayer)
+        .add_service(health_service)
+        .add_service(reflection);
+    let router = match server.auth.clone() {
+        Some(auth) => router.add_service(HyperliquidServiceServer::with_interceptor(
+            server,
+            auth.interceptor(),
+        )),
+        None => router.add_service(HyperliquidServiceServer::new(server)),
+    };
+
+    println!("gRPC server listening on {}", config.addr);
+    router.serve(config.addr).await?;
 
     Ok(())
-}
\ No newline at end of file
+}
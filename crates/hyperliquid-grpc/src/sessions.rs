@@ -0,0 +1,403 @@
+//! Resumable stream sessions
+//!
+//! Every `SubscribeToStreams` call runs in a session. The server numbers the
+//! updates of each session and keeps the most recent ones in a bounded replay
+//! window. Each [`StreamResponse`] carries its sequence and the session token.
+//! A consumer that restarts calls `SubscribeToStreams` again with the token
+//! and the last sequence it processed. It gets the updates it missed, then
+//! live ones. A session with no consumer is kept for the session TTL.
+//!
+//! Updates enter through [`SessionStore::publish`], which multiplexes one
+//! upstream feed onto every session subscribed to it. A consumer that falls a
+//! full replay window behind is disconnected rather than buffered without
+//! bound; it can resume from its last sequence.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use ring::rand::{SecureRandom, SystemRandom};
+use tokio::sync::mpsc;
+use tonic::Status;
+
+use crate::server::pb::{stream_response::Response, StreamResponse, StreamSubscription};
+
+/// Default number of updates kept per session for replay
+pub const DEFAULT_REPLAY_WINDOW: usize = 4096;
+
+/// Default time a session without a consumer is kept
+pub const DEFAULT_SESSION_TTL: Duration = Duration::from_secs(300);
+
+/// Receiving end of a session, served as the RPC's response stream
+pub type SessionReceiver = mpsc::Receiver<Result<StreamResponse, Status>>;
+
+type SessionSender = mpsc::Sender<Result<StreamResponse, Status>>;
+
+struct Session {
+    subscriptions: Vec<StreamSubscription>,
+    next_sequence: u64,
+    replay: VecDeque<StreamResponse>,
+    consumer: Option<SessionSender>,
+    /// When the last consumer went away
+    detached_at: Instant,
+}
+
+impl Session {
+    fn detach(&mut self, now: Instant) {
+        if self.consumer.take().is_some() {
+            self.detached_at = now;
+        }
+    }
+
+    fn is_attached(&mut self, now: Instant) -> bool {
+        if self
+            .consumer
+            .as_ref()
+            .is_some_and(|consumer| consumer.is_closed())
+        {
+            self.detach(now);
+        }
+        self.consumer.is_some()
+    }
+}
+
+/// Stream sessions of a server
+///
+/// Cloning shares the sessions.
+#[derive(Clone)]
+pub struct SessionStore {
+    replay_window: usize,
+    ttl: Duration,
+    sessions: Arc<Mutex<HashMap<String, Session>>>,
+    rng: SystemRandom,
+}
+
+impl SessionStore {
+    pub fn new() -> Self {
+        Self {
+            replay_window: DEFAULT_REPLAY_WINDOW,
+            ttl: DEFAULT_SESSION_TTL,
+            sessions: Arc::new(Mutex::new(HashMap::new())),
+            rng: SystemRandom::new(),
+        }
+    }
+
+    /// Keep the last `updates` updates of each session for replay
+    pub fn with_replay_window(mut self, updates: usize) -> Self {
+        self.replay_window = updates.max(1);
+        self
+    }
+
+    /// Keep sessions without a consumer for `ttl`
+    pub fn with_session_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Session>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn expire(&self, sessions: &mut HashMap<String, Session>, now: Instant) {
+        let ttl = self.ttl;
+        sessions.retain(|_, session| {
+            session.is_attached(now) || now.duration_since(session.detached_at) < ttl
+        });
+        metrics::gauge!("hyperliquid_grpc_stream_sessions").set(sessions.len() as f64);
+    }
+
+    fn new_token(&self) -> Result<String, Status> {
+        let mut bytes = [0u8; 16];
+        self.rng
+            .fill(&mut bytes)
+            .map_err(|_| Status::internal("failed to generate a session token"))?;
+        Ok(hex::encode(bytes))
+    }
+
+    /// Start a session for `subscriptions`
+    ///
+    /// The first response on the receiver carries the session token.
+    pub fn open(&self, subscriptions: Vec<StreamSubscription>) -> Result<SessionReceiver, Status> {
+        if subscriptions.is_empty() {
+            return Err(Status::invalid_argument(
+                "at least one subscription is required",
+            ));
+        }
+        let token = self.new_token()?;
+        let (sender, receiver) = mpsc::channel(self.replay_window);
+        sender
+            .try_send(Ok(StreamResponse {
+                response: None,
+                sequence: 0,
+                session_token: token.clone(),
+            }))
+            .expect("new channel has capacity");
+
+        let now = Instant::now();
+        let mut sessions = self.lock();
+        sessions.insert(
+            token,
+            Session {
+                subscriptions,
+                next_sequence: 1,
+                replay: VecDeque::new(),
+                consumer: Some(sender),
+                detached_at: now,
+            },
+        );
+        self.expire(&mut sessions, now);
+        Ok(receiver)
+    }
+
+    /// Reattach to session `token`, replaying updates after `last_sequence`
+    ///
+    /// Fails with `NOT_FOUND` for an unknown or expired session and
+    /// `OUT_OF_RANGE` if updates after `last_sequence` have already left the
+    /// replay window. A consumer still attached to the session is
+    /// disconnected.
+    pub fn resume(&self, token: &str, last_sequence: u64) -> Result<SessionReceiver, Status> {
+        let now = Instant::now();
+        let mut sessions = self.lock();
+        self.expire(&mut sessions, now);
+        let session = sessions
+            .get_mut(token)
+            .ok_or_else(|| Status::not_found("unknown or expired stream session"))?;
+
+        if last_sequence >= session.next_sequence {
+            return Err(Status::invalid_argument(format!(
+                "sequence {} was never sent; the session is at {}",
+                last_sequence,
+                session.next_sequence - 1
+            )));
+        }
+        let oldest = session
+            .replay
+            .front()
+            .map_or(session.next_sequence, |update| update.sequence);
+        if last_sequence + 1 < oldest {
+            return Err(Status::out_of_range(format!(
+                "updates after {} are no longer buffered; the replay window starts at {}",
+                last_sequence, oldest
+            )));
+        }
+
+        // The window fits in the channel, so the whole replay is queued
+        // before any live update
+        let (sender, receiver) = mpsc::channel(self.replay_window);
+        for update in session
+            .replay
+            .iter()
+            .filter(|update| update.sequence > last_sequence)
+        {
+            sender
+                .try_send(Ok(update.clone()))
+                .expect("replay fits in the channel");
+        }
+        session.consumer = Some(sender);
+        metrics::counter!("hyperliquid_grpc_stream_resumes_total").increment(1);
+        Ok(receiver)
+    }
+
+    /// Deliver `update` to every session subscribed to it
+    ///
+    /// The sequence and session token are filled in per session. Returns the
+    /// number of sessions the update was added to.
+    pub fn publish(&self, update: &StreamResponse) -> usize {
+        let now = Instant::now();
+        let mut sessions = self.lock();
+        let mut delivered = 0;
+        for (token, session) in sessions.iter_mut() {
+            if !subscribed(&session.subscriptions, update) {
+                continue;
+            }
+            let update = StreamResponse {
+                response: update.response.clone(),
+                sequence: session.next_sequence,
+                session_token: token.clone(),
+            };
+            session.next_sequence += 1;
+            if session.replay.len() == self.replay_window {
+                session.replay.pop_front();
+            }
+            session.replay.push_back(update.clone());
+            delivered += 1;
+
+            if let Some(consumer) = &session.consumer {
+                if let Err(e) = consumer.try_send(Ok(update)) {
+                    if let mpsc::error::TrySendError::Full(_) = e {
+                        tracing::warn!(
+                            "stream session {} fell a replay window behind; disconnecting",
+                            token
+                        );
+                    }
+                    session.detach(now);
+                }
+            }
+        }
+        self.expire(&mut sessions, now);
+        delivered
+    }
+
+    /// End session `token`, disconnecting its consumer
+    pub fn close(&self, token: &str) -> bool {
+        self.lock().remove(token).is_some()
+    }
+
+    /// Number of live sessions, with or without a consumer
+    pub fn len(&self) -> usize {
+        let mut sessions = self.lock();
+        self.expire(&mut sessions, Instant::now());
+        sessions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for SessionStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionStore")
+            .field("replay_window", &self.replay_window)
+            .field("ttl", &self.ttl)
+            .field("sessions", &self.lock().len())
+            .finish()
+    }
+}
+
+/// Whether any of `subscriptions` covers `update`; errors go to every session
+fn subscribed(subscriptions: &[StreamSubscription], update: &StreamResponse) -> bool {
+    let (kind, coin, user) = match &update.response {
+        Some(Response::TradeUpdate(update)) => ("trades", update.coin.as_str(), None),
+        Some(Response::L2BookUpdate(update)) => ("l2_book", update.coin.as_str(), None),
+        Some(Response::CandleUpdate(update)) => ("candles", update.coin.as_str(), None),
+        Some(Response::OrderUpdate(update)) => (
+            "order_updates",
+            update
+                .order
+                .as_ref()
+                .map_or("", |order| order.coin.as_str()),
+            Some(update.address.as_str()),
+        ),
+        Some(Response::Error(_)) => return true,
+        None => return false,
+    };
+    subscriptions.iter().any(|subscription| {
+        subscription.r#type == kind
+            && (subscription.coins.is_empty() || subscription.coins.iter().any(|c| c == coin))
+            && match (subscription.options.get("user"), user) {
+                (Some(wanted), Some(user)) => wanted.eq_ignore_ascii_case(user),
+                _ => true,
+            }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::pb::TradeUpdate;
+
+    fn subscription(kind: &str, coins: &[&str]) -> StreamSubscription {
+        StreamSubscription {
+            r#type: kind.to_string(),
+            coins: coins.iter().map(|coin| coin.to_string()).collect(),
+            options: HashMap::new(),
+        }
+    }
+
+    fn trade(coin: &str) -> StreamResponse {
+        StreamResponse {
+            response: Some(Response::TradeUpdate(TradeUpdate {
+                coin: coin.to_string(),
+                trade: None,
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn drain(receiver: &mut SessionReceiver) -> Vec<StreamResponse> {
+        let mut updates = Vec::new();
+        while let Ok(update) = receiver.try_recv() {
+            updates.push(update.unwrap());
+        }
+        updates
+    }
+
+    #[test]
+    fn test_resume_replays_missed_updates() {
+        let store = SessionStore::new().with_replay_window(3);
+        let mut receiver = store.open(vec![subscription("trades", &["BTC"])]).unwrap();
+        let token = drain(&mut receiver)[0].session_token.clone();
+
+        assert_eq!(store.publish(&trade("BTC")), 1);
+        assert_eq!(store.publish(&trade("ETH")), 0);
+        let delivered = drain(&mut receiver);
+        assert_eq!(delivered.len(), 1);
+        assert_eq!(delivered[0].sequence, 1);
+
+        // The consumer restarts after processing sequence 1
+        drop(receiver);
+        for _ in 0..3 {
+            store.publish(&trade("BTC"));
+        }
+        assert_eq!(store.len(), 1);
+
+        let mut receiver = store.resume(&token, 1).unwrap();
+        let sequences: Vec<u64> = drain(&mut receiver).iter().map(|u| u.sequence).collect();
+        assert_eq!(sequences, vec![2, 3, 4]);
+        store.publish(&trade("BTC"));
+        assert_eq!(drain(&mut receiver)[0].sequence, 5);
+
+        // Sequence 2 has left the three-update window
+        assert_eq!(
+            store.resume(&token, 1).unwrap_err().code(),
+            tonic::Code::OutOfRange
+        );
+        assert_eq!(
+            store.resume(&token, 9).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            store.resume("nope", 0).unwrap_err().code(),
+            tonic::Code::NotFound
+        );
+    }
+
+    #[test]
+    fn test_detached_sessions_expire() {
+        let store = SessionStore::new().with_session_ttl(Duration::ZERO);
+        let receiver = store.open(vec![subscription("trades", &[])]).unwrap();
+        assert_eq!(store.len(), 1);
+        drop(receiver);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_subscription_matching() {
+        let mut by_user = subscription("order_updates", &[]);
+        by_user
+            .options
+            .insert("user".to_string(), "0xABC".to_string());
+        let order = |address: &str| StreamResponse {
+            response: Some(Response::OrderUpdate(crate::server::pb::OrderUpdate {
+                address: address.to_string(),
+                order: None,
+            })),
+            ..Default::default()
+        };
+
+        assert!(subscribed(&[by_user.clone()], &order("0xabc")));
+        assert!(!subscribed(&[by_user], &order("0xdef")));
+        assert!(subscribed(&[subscription("trades", &[])], &trade("SOL")));
+        assert!(!subscribed(
+            &[subscription("l2_book", &["SOL"])],
+            &trade("SOL")
+        ));
+    }
+}
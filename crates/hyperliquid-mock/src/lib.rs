@@ -0,0 +1,435 @@
+//! Mock Hyperliquid API server
+//!
+//! Serves `/info`, `/exchange` and `/ws` on a local port so bots built on the
+//! SDK can be integration tested without testnet. Responses are either canned
+//! per request type or scripted as a queue that is consumed before falling
+//! back to the canned ones, which is how errors, rate limits and slow
+//! responses are injected.
+//!
+//! ```no_run
+//! use hyperliquid_mock::{MockResponse, MockServer, Route};
+//! use serde_json::json;
+//!
+//! # async fn example() -> Result<(), hyperliquid_core::HyperliquidError> {
+//! let mock = MockServer::start().await?;
+//! mock.on_info("allMids", json!({"BTC": "65000.0"}));
+//! mock.script(Route::exchange("order"), [MockResponse::rate_limited()]);
+//!
+//! // Point the SDK's clients at mock.base_url() and mock.ws_url()
+//! mock.send_ws("allMids", json!({"mids": {"BTC": "65001.0"}}));
+//! mock.shutdown().await;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests that match nothing get the exchange's defaults: orders rest with
+//! increasing oids, cancels succeed, other actions return `ok`, and unknown
+//! info types are rejected with a 422 like the real API.
+
+mod response;
+mod ws;
+
+pub use response::{MockResponse, RecordedRequest, Route};
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{
+    HeaderValue, CONNECTION, CONTENT_TYPE, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE,
+};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tracing::{debug, info};
+
+use hyperliquid_core::HyperliquidError;
+
+use crate::response::Endpoint;
+use crate::ws::WsCommand;
+
+const WS_COMMAND_CAPACITY: usize = 1024;
+
+#[derive(Default)]
+struct State {
+    info: HashMap<String, MockResponse>,
+    exchange: HashMap<String, MockResponse>,
+    scripts: Vec<(Route, VecDeque<MockResponse>)>,
+    requests: Vec<RecordedRequest>,
+    next_oid: u64,
+    subscriptions: Vec<Value>,
+    on_subscribe: HashMap<String, Vec<Value>>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Shared {
+    state: Arc<Mutex<State>>,
+    ws_tx: broadcast::Sender<WsCommand>,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn resolve(&self, endpoint: Endpoint, path: &str, body: Value) -> MockResponse {
+        let kind = match endpoint {
+            Endpoint::Info => body.get("type"),
+            Endpoint::Exchange => body.pointer("/action/type"),
+        }
+        .and_then(Value::as_str)
+        .unwrap_or("")
+        .to_string();
+
+        let mut state = self.lock();
+        state.requests.push(RecordedRequest {
+            path: path.to_string(),
+            kind: kind.clone(),
+            body: body.clone(),
+        });
+
+        let scripted = state
+            .scripts
+            .iter_mut()
+            .find(|(route, queue)| !queue.is_empty() && route.matches(endpoint, &kind))
+            .and_then(|(_, queue)| queue.pop_front());
+        if let Some(response) = scripted {
+            return response;
+        }
+
+        let canned = match endpoint {
+            Endpoint::Info => state.info.get(&kind),
+            Endpoint::Exchange => state.exchange.get(&kind),
+        };
+        if let Some(response) = canned {
+            return response.clone();
+        }
+
+        match endpoint {
+            Endpoint::Info => MockResponse::status(
+                422,
+                json!("Failed to deserialize the JSON body into the target type"),
+            ),
+            Endpoint::Exchange => state.default_exchange(&kind, &body),
+        }
+    }
+}
+
+impl State {
+    fn default_exchange(&mut self, kind: &str, body: &Value) -> MockResponse {
+        let count = |field: &str| {
+            body.pointer(&format!("/action/{}", field))
+                .and_then(Value::as_array)
+                .map_or(0, Vec::len)
+        };
+        let data = match kind {
+            "order" => {
+                let statuses: Vec<Value> = (0..count("orders"))
+                    .map(|_| {
+                        self.next_oid += 1;
+                        json!({"resting": {"oid": self.next_oid}})
+                    })
+                    .collect();
+                json!({"type": "order", "data": {"statuses": statuses}})
+            }
+            "cancel" | "cancelByCloid" => {
+                json!({"type": "cancel", "data": {"statuses": vec!["success"; count("cancels")]}})
+            }
+            _ => json!({"type": "default"}),
+        };
+        MockResponse::json(json!({"status": "ok", "response": data}))
+    }
+}
+
+/// Running mock server
+pub struct MockServer {
+    local_addr: SocketAddr,
+    shared: Shared,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Start on an ephemeral port on localhost
+    pub async fn start() -> Result<Self, HyperliquidError> {
+        Self::bind(SocketAddr::from(([127, 0, 0, 1], 0))).await
+    }
+
+    /// Bind `addr` and serve until shut down
+    pub async fn bind(addr: SocketAddr) -> Result<Self, HyperliquidError> {
+        let listener = TcpListener::bind(addr).await.map_err(|e| {
+            HyperliquidError::Config(format!("failed to bind mock server {}: {}", addr, e))
+        })?;
+        let local_addr = listener.local_addr().map_err(|e| {
+            HyperliquidError::Config(format!("failed to read mock server address: {}", e))
+        })?;
+        let (ws_tx, _) = broadcast::channel(WS_COMMAND_CAPACITY);
+        let shared = Shared {
+            state: Arc::new(Mutex::new(State::default())),
+            ws_tx,
+        };
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+
+        info!(addr = %local_addr, "Serving mock Hyperliquid API");
+
+        let server_shared = shared.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let stream = tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => stream,
+                        Err(e) => {
+                            debug!("Mock server accept failed: {}", e);
+                            continue;
+                        }
+                    },
+                    _ = &mut shutdown_rx => break,
+                };
+
+                let shared = server_shared.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(move |request| {
+                        let shared = shared.clone();
+                        async move { Ok::<_, Infallible>(handle(shared, request).await) }
+                    });
+                    if let Err(e) = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .with_upgrades()
+                        .await
+                    {
+                        debug!("Mock connection error: {}", e);
+                    }
+                });
+            }
+            // Drop open websockets along with the listener
+            let _ = server_shared.ws_tx.send(WsCommand::Disconnect);
+        });
+
+        Ok(Self {
+            local_addr,
+            shared,
+            shutdown_tx: Some(shutdown_tx),
+            task,
+        })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// HTTP base URL to configure clients with
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.local_addr)
+    }
+
+    /// WebSocket URL to configure stream clients with
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}/ws", self.local_addr)
+    }
+
+    /// Answer `/info` requests of `request_type` with `response` until replaced
+    pub fn on_info(&self, request_type: &str, response: impl Into<MockResponse>) {
+        self.shared
+            .lock()
+            .info
+            .insert(request_type.to_string(), response.into());
+    }
+
+    /// Answer `/exchange` actions of `action_type` with `response` until replaced
+    pub fn on_exchange(&self, action_type: &str, response: impl Into<MockResponse>) {
+        self.shared
+            .lock()
+            .exchange
+            .insert(action_type.to_string(), response.into());
+    }
+
+    /// Queue one-shot responses for requests matching `route`
+    ///
+    /// Queued responses are served in order ahead of canned ones; scripts are
+    /// checked in the order they were added.
+    pub fn script(&self, route: Route, responses: impl IntoIterator<Item = MockResponse>) {
+        let mut state = self.shared.lock();
+        let responses: VecDeque<MockResponse> = responses.into_iter().collect();
+        match state.scripts.iter_mut().find(|(r, _)| *r == route) {
+            Some((_, queue)) => queue.extend(responses),
+            None => state.scripts.push((route, responses)),
+        }
+    }
+
+    /// Fail the next `count` requests matching `route` with `response`
+    pub fn fail_next(&self, route: Route, count: usize, response: MockResponse) {
+        self.script(route, std::iter::repeat(response).take(count));
+    }
+
+    /// Send `messages` to a client right after it subscribes to `subscription_type`
+    pub fn on_subscribe(&self, subscription_type: &str, messages: impl IntoIterator<Item = Value>) {
+        self.shared.lock().on_subscribe.insert(
+            subscription_type.to_string(),
+            messages.into_iter().collect(),
+        );
+    }
+
+    /// Push a `{"channel", "data"}` message to every open websocket
+    pub fn send_ws(&self, channel: &str, data: Value) {
+        self.send_ws_raw(json!({"channel": channel, "data": data}));
+    }
+
+    /// Push an arbitrary message to every open websocket
+    pub fn send_ws_raw(&self, message: Value) {
+        let _ = self.shared.ws_tx.send(WsCommand::Send(message));
+    }
+
+    /// Close every open websocket, e.g. to exercise reconnects
+    pub fn disconnect_ws(&self) {
+        let _ = self.shared.ws_tx.send(WsCommand::Disconnect);
+    }
+
+    /// Subscriptions currently held by websocket clients
+    pub fn subscriptions(&self) -> Vec<Value> {
+        self.shared.lock().subscriptions.clone()
+    }
+
+    /// Every HTTP request received so far, oldest first
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.shared.lock().requests.clone()
+    }
+
+    /// Requests received so far that match `route`
+    pub fn requests_matching(&self, route: &Route) -> Vec<RecordedRequest> {
+        self.shared
+            .lock()
+            .requests
+            .iter()
+            .filter(|request| {
+                let endpoint = if request.path == "/info" {
+                    Endpoint::Info
+                } else {
+                    Endpoint::Exchange
+                };
+                route.matches(endpoint, &request.kind)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Forget canned responses, scripts and recorded requests
+    pub fn reset(&self) {
+        let mut state = self.shared.lock();
+        *state = State::default();
+    }
+
+    /// Stop accepting connections and wait for the accept loop to exit
+    pub async fn shutdown(mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+impl std::fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockServer")
+            .field("local_addr", &self.local_addr)
+            .finish()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
+}
+
+async fn handle(shared: Shared, mut request: Request<Incoming>) -> Response<Full<Bytes>> {
+    let path = request.uri().path().to_string();
+    let endpoint = match (request.method(), path.as_str()) {
+        (&Method::GET, "/ws") => return upgrade(shared, &mut request),
+        (&Method::POST, "/info") => Endpoint::Info,
+        (&Method::POST, "/exchange") => Endpoint::Exchange,
+        (_, "/info" | "/exchange") => return respond(StatusCode::METHOD_NOT_ALLOWED, Value::Null),
+        _ => return respond(StatusCode::NOT_FOUND, Value::Null),
+    };
+
+    let body = match request.into_body().collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(e) => return respond(StatusCode::BAD_REQUEST, json!(e.to_string())),
+    };
+    let body: Value = match serde_json::from_slice(&body) {
+        Ok(body) => body,
+        Err(e) => return respond(StatusCode::BAD_REQUEST, json!(e.to_string())),
+    };
+
+    let mut response = shared.resolve(endpoint, &path, body);
+    loop {
+        match response {
+            MockResponse::Delayed {
+                delay,
+                response: inner,
+            } => {
+                tokio::time::sleep(delay).await;
+                response = *inner;
+            }
+            MockResponse::Json(body) => return respond(StatusCode::OK, body),
+            MockResponse::Status { status, body } => {
+                let status =
+                    StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                return respond(status, body);
+            }
+        }
+    }
+}
+
+fn upgrade(shared: Shared, request: &mut Request<Incoming>) -> Response<Full<Bytes>> {
+    let Some(key) = request.headers().get(SEC_WEBSOCKET_KEY) else {
+        return respond(
+            StatusCode::BAD_REQUEST,
+            json!("expected a websocket upgrade"),
+        );
+    };
+    let accept = derive_accept_key(key.as_bytes());
+
+    let on_upgrade = hyper::upgrade::on(request);
+    tokio::spawn(async move {
+        match on_upgrade.await {
+            Ok(upgraded) => ws::serve(upgraded, shared).await,
+            Err(e) => debug!("Mock websocket upgrade failed: {}", e),
+        }
+    });
+
+    let mut response = Response::new(Full::new(Bytes::new()));
+    *response.status_mut() = StatusCode::SWITCHING_PROTOCOLS;
+    let headers = response.headers_mut();
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(CONNECTION, HeaderValue::from_static("Upgrade"));
+    if let Ok(accept) = HeaderValue::from_str(&accept) {
+        headers.insert(SEC_WEBSOCKET_ACCEPT, accept);
+    }
+    response
+}
+
+fn respond(status: StatusCode, body: Value) -> Response<Full<Bytes>> {
+    let body = if body.is_null() {
+        Bytes::new()
+    } else {
+        Bytes::from(body.to_string())
+    };
+    let mut response = Response::new(Full::new(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
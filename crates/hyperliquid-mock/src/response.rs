@@ -0,0 +1,109 @@
+//! Canned responses and request matching
+
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+/// Response returned by the mock for a matched request
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockResponse {
+    /// `200 OK` with a JSON body
+    Json(Value),
+    /// Arbitrary status code with a JSON body
+    Status { status: u16, body: Value },
+    /// Wait before sending the inner response
+    Delayed {
+        delay: Duration,
+        response: Box<MockResponse>,
+    },
+}
+
+impl MockResponse {
+    pub fn json(body: Value) -> Self {
+        MockResponse::Json(body)
+    }
+
+    pub fn status(status: u16, body: Value) -> Self {
+        MockResponse::Status { status, body }
+    }
+
+    /// Exchange-level rejection, as `/exchange` reports it with a 200
+    pub fn error(message: impl Into<String>) -> Self {
+        MockResponse::Json(json!({"status": "err", "response": message.into()}))
+    }
+
+    /// `429 Too Many Requests`
+    pub fn rate_limited() -> Self {
+        MockResponse::status(429, json!(null))
+    }
+
+    /// `500 Internal Server Error`
+    pub fn server_error() -> Self {
+        MockResponse::status(500, json!(null))
+    }
+
+    /// Send this response after `delay`, e.g. to exercise client timeouts
+    pub fn delayed(self, delay: Duration) -> Self {
+        MockResponse::Delayed {
+            delay,
+            response: Box::new(self),
+        }
+    }
+}
+
+impl From<Value> for MockResponse {
+    fn from(body: Value) -> Self {
+        MockResponse::Json(body)
+    }
+}
+
+/// Which requests a scripted response applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Route {
+    /// `/info` requests with this `type`
+    Info(String),
+    /// `/exchange` requests whose action has this `type`
+    Exchange(String),
+    /// Any `/info` request
+    AnyInfo,
+    /// Any `/exchange` request
+    AnyExchange,
+    /// Any HTTP request
+    Any,
+}
+
+impl Route {
+    pub fn info(request_type: impl Into<String>) -> Self {
+        Route::Info(request_type.into())
+    }
+
+    pub fn exchange(action_type: impl Into<String>) -> Self {
+        Route::Exchange(action_type.into())
+    }
+
+    pub(crate) fn matches(&self, endpoint: Endpoint, kind: &str) -> bool {
+        match self {
+            Route::Info(expected) => endpoint == Endpoint::Info && expected == kind,
+            Route::Exchange(expected) => endpoint == Endpoint::Exchange && expected == kind,
+            Route::AnyInfo => endpoint == Endpoint::Info,
+            Route::AnyExchange => endpoint == Endpoint::Exchange,
+            Route::Any => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endpoint {
+    Info,
+    Exchange,
+}
+
+/// Request received by the mock
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    /// `/info` or `/exchange`
+    pub path: String,
+    /// Info request `type`, or the exchange action's `type`
+    pub kind: String,
+    pub body: Value,
+}
@@ -0,0 +1,96 @@
+//! `/ws` endpoint
+
+use futures::{SinkExt, StreamExt};
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::debug;
+
+use crate::Shared;
+
+/// Instruction fanned out to every open connection
+#[derive(Debug, Clone)]
+pub(crate) enum WsCommand {
+    Send(Value),
+    Disconnect,
+}
+
+pub(crate) async fn serve(upgraded: Upgraded, shared: Shared) {
+    let ws = WebSocketStream::from_raw_socket(TokioIo::new(upgraded), Role::Server, None).await;
+    let (mut sink, mut stream) = ws.split();
+    let mut commands = shared.ws_tx.subscribe();
+
+    loop {
+        tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    for reply in handle_message(&shared, &text) {
+                        if sink.send(Message::Text(reply.to_string())).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+                Some(Ok(Message::Ping(payload))) => {
+                    let _ = sink.send(Message::Pong(payload)).await;
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    debug!("Mock websocket error: {}", e);
+                    break;
+                }
+            },
+            command = commands.recv() => match command {
+                Ok(WsCommand::Send(message)) => {
+                    if sink.send(Message::Text(message.to_string())).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(WsCommand::Disconnect) => {
+                    let _ = sink.close().await;
+                    break;
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+/// Replies to a client message: acks, pongs and any scripted snapshots
+fn handle_message(shared: &Shared, text: &str) -> Vec<Value> {
+    let Ok(message) = serde_json::from_str::<Value>(text) else {
+        return vec![
+            json!({"channel": "error", "data": format!("Error parsing JSON into valid websocket request: {}", text)}),
+        ];
+    };
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+    match method {
+        "ping" => vec![json!({"channel": "pong"})],
+        "subscribe" | "unsubscribe" => {
+            let subscription = message.get("subscription").cloned().unwrap_or(Value::Null);
+            let mut replies = vec![json!({
+                "channel": "subscriptionResponse",
+                "data": {"method": method, "subscription": subscription},
+            })];
+            let mut state = shared.lock();
+            if method == "subscribe" {
+                let kind = subscription
+                    .get("type")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string();
+                replies.extend(state.on_subscribe.get(&kind).cloned().unwrap_or_default());
+                state.subscriptions.push(subscription);
+            } else {
+                state.subscriptions.retain(|s| *s != subscription);
+            }
+            replies
+        }
+        _ => vec![json!({"channel": "error", "data": format!("Unknown method: {}", text)})],
+    }
+}
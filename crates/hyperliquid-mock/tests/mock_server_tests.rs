@@ -0,0 +1,104 @@
+//! Tests for the mock API server
+
+use futures::{SinkExt, StreamExt};
+use hyperliquid_mock::{MockResponse, MockServer, Route};
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::Message;
+
+async fn post(mock: &MockServer, path: &str, body: Value) -> (u16, Value) {
+    let response = reqwest::Client::new()
+        .post(format!("{}{}", mock.base_url(), path))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let text = response.text().await.unwrap();
+    (status, serde_json::from_str(&text).unwrap_or(Value::Null))
+}
+
+fn order_action() -> Value {
+    json!({
+        "action": {"type": "order", "orders": [{"a": 0, "b": true, "p": "65000", "s": "0.1", "r": false, "t": {"limit": {"tif": "Gtc"}}}], "grouping": "na"},
+        "nonce": 1,
+        "signature": {"r": "0x0", "s": "0x0", "v": 27},
+    })
+}
+
+#[tokio::test]
+async fn test_canned_and_scripted_responses() {
+    let mock = MockServer::start().await.unwrap();
+    mock.on_info("allMids", json!({"BTC": "65000.0"}));
+
+    let (status, body) = post(&mock, "/info", json!({"type": "allMids"})).await;
+    assert_eq!(status, 200);
+    assert_eq!(body["BTC"], "65000.0");
+
+    let (status, _) = post(&mock, "/info", json!({"type": "notARequest"})).await;
+    assert_eq!(status, 422);
+
+    // Injected failures come first, then the default resting response
+    mock.fail_next(Route::exchange("order"), 1, MockResponse::rate_limited());
+    mock.script(
+        Route::AnyExchange,
+        [MockResponse::error("Insufficient margin to place order.")],
+    );
+    let (status, _) = post(&mock, "/exchange", order_action()).await;
+    assert_eq!(status, 429);
+    let (_, body) = post(&mock, "/exchange", order_action()).await;
+    assert_eq!(body["status"], "err");
+    let (_, body) = post(&mock, "/exchange", order_action()).await;
+    assert_eq!(body["response"]["data"]["statuses"][0]["resting"]["oid"], 1);
+
+    let orders = mock.requests_matching(&Route::exchange("order"));
+    assert_eq!(orders.len(), 3);
+    assert_eq!(orders[0].body["action"]["orders"][0]["p"], "65000");
+    assert_eq!(mock.requests().len(), 5);
+
+    mock.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_websocket_subscriptions() {
+    let mock = MockServer::start().await.unwrap();
+    mock.on_subscribe(
+        "l2Book",
+        [json!({"channel": "l2Book", "data": {"coin": "BTC", "levels": [[], []], "time": 1}})],
+    );
+
+    let (mut ws, _) = tokio_tungstenite::connect_async(mock.ws_url())
+        .await
+        .unwrap();
+    ws.send(Message::Text(
+        json!({"method": "subscribe", "subscription": {"type": "l2Book", "coin": "BTC"}})
+            .to_string(),
+    ))
+    .await
+    .unwrap();
+
+    let mut received = Vec::new();
+    for _ in 0..2 {
+        if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+            received.push(serde_json::from_str::<Value>(&text).unwrap());
+        }
+    }
+    assert_eq!(received[0]["channel"], "subscriptionResponse");
+    assert_eq!(received[1]["data"]["coin"], "BTC");
+    assert_eq!(mock.subscriptions().len(), 1);
+
+    mock.send_ws("trades", json!([{"coin": "BTC", "px": "65000"}]));
+    if let Message::Text(text) = ws.next().await.unwrap().unwrap() {
+        let message: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(message["channel"], "trades");
+    } else {
+        panic!("expected a text message");
+    }
+
+    mock.disconnect_ws();
+    assert!(matches!(
+        ws.next().await,
+        Some(Ok(Message::Close(_))) | None | Some(Err(_))
+    ));
+
+    mock.shutdown().await;
+}
@@ -0,0 +1,321 @@
+# Type stubs for the hyperliquid_rs extension module.
+#
+# Keep in sync with src/lib.rs, src/errors.rs, src/exchange.rs, src/frames.rs, src/stream.rs and src/types.rs.
+
+from typing import Any, AsyncIterator, Awaitable, Callable, Optional
+
+def init_runtime(worker_threads: Optional[int] = None) -> None: ...
+
+class HyperliquidError(Exception):
+    status: Optional[int]
+    code: Optional[int]
+    retry_after: Optional[int]
+    reason: Optional[str]
+    index: Optional[int]
+    data: Optional[Any]
+    attempts: Optional[int]
+
+class NetworkError(HyperliquidError): ...
+class RequestTimeoutError(NetworkError): ...
+class HttpError(HyperliquidError): ...
+class RateLimitError(HttpError): ...
+class ServerError(HttpError): ...
+class ClientError(HttpError): ...
+class RetryExhaustedError(HyperliquidError): ...
+class OrderRejectedError(HyperliquidError): ...
+class SigningError(HyperliquidError): ...
+class AuthenticationError(HyperliquidError): ...
+class WebSocketError(HyperliquidError): ...
+class ConfigError(HyperliquidError): ...
+class ValidationError(HyperliquidError): ...
+
+class AssetMeta:
+    name: str
+    sz_decimals: int
+    max_leverage: int
+    only_isolated: bool
+
+class Meta:
+    universe: list[AssetMeta]
+    def asset(self, name: str) -> Optional[AssetMeta]: ...
+    def __len__(self) -> int: ...
+
+class MarginSummary:
+    account_value: str
+    total_margin_used: str
+    total_ntl_pos: str
+    total_raw_usd: str
+
+class Position:
+    coin: str
+    szi: str
+    entry_px: Optional[str]
+    leverage: Optional[str]
+    liquidation_px: Optional[str]
+    position_value: str
+    margin_used: Optional[str]
+    unrealized_pnl: Optional[str]
+    return_on_equity: Optional[str]
+
+class UserState:
+    margin_summary: MarginSummary
+    cross_margin_summary: Optional[MarginSummary]
+    withdrawable: str
+    positions: list[Position]
+    def position(self, coin: str) -> Optional[Position]: ...
+
+class BookLevel:
+    px: str
+    sz: str
+    n: int
+
+class L2BookSnapshot:
+    coin: str
+    time: int
+    bids: list[BookLevel]
+    asks: list[BookLevel]
+    def best_bid(self) -> Optional[BookLevel]: ...
+    def best_ask(self) -> Optional[BookLevel]: ...
+
+class Fill:
+    coin: str
+    px: str
+    sz: str
+    time: int
+    oid: int
+    fee: str
+    dir: Optional[str]
+    cloid: Optional[str]
+
+class OpenOrder:
+    coin: str
+    oid: int
+    limit_px: str
+    sz: str
+    time: int
+    order_type: str
+    reduce_only: bool
+    is_trigger: bool
+    trigger_px: Optional[str]
+    cloid: Optional[str]
+
+class Candle:
+    coin: str
+    interval: str
+    start: int
+    end: int
+    open: str
+    high: str
+    low: str
+    close: str
+    volume: str
+    trades: Optional[int]
+
+class PyHttpClientConfig:
+    max_connections_per_host: int
+    max_total_connections: int
+    connect_timeout_ms: int
+    request_timeout_ms: int
+    http2: bool
+    compression: bool
+    keepalive: bool
+    keepalive_ms: int
+    user_agent: str
+    proxy_url: Optional[str]
+    def __init__(self) -> None: ...
+
+class PyHttpClient:
+    def __init__(
+        self, base_url: str, config: Optional[PyHttpClientConfig] = None
+    ) -> None: ...
+    @staticmethod
+    def with_default_config(base_url: str) -> PyHttpClient: ...
+    @property
+    def base_url(self) -> str: ...
+    @property
+    def config(self) -> PyHttpClientConfig: ...
+    def post(self, path: str, body: str) -> Awaitable[str]: ...
+    def get(self, path: str) -> Awaitable[str]: ...
+    def put(self, path: str, body: str) -> Awaitable[str]: ...
+    def delete(self, path: str) -> Awaitable[str]: ...
+
+class PyInfoClient:
+    def __init__(self, http_client: PyHttpClient) -> None: ...
+    @staticmethod
+    def with_default_config(base_url: str) -> PyInfoClient: ...
+    def meta(self, dex: Optional[str]) -> Awaitable[Meta]: ...
+    def user_state(self, address: str, dex: Optional[str]) -> Awaitable[UserState]: ...
+    def open_orders(
+        self, address: str, dex: Optional[str]
+    ) -> Awaitable[list[OpenOrder]]: ...
+    def frontend_open_orders(
+        self, address: str, dex: Optional[str]
+    ) -> Awaitable[list[OpenOrder]]: ...
+    def user_fills(self, address: str) -> Awaitable[list[Fill]]: ...
+    def l2_book(self, coin: str, dex: Optional[str] = None) -> Awaitable[L2BookSnapshot]: ...
+    def candles_snapshot(
+        self, coin: str, interval: str, dex: Optional[str] = None
+    ) -> Awaitable[list[Candle]]: ...
+    def all_mids(self, dex: Optional[str]) -> Awaitable[dict[str, str]]: ...
+    # Available when built with the `numpy` feature
+    def l2_book_arrays(
+        self, coin: str, dex: Optional[str] = None
+    ) -> Awaitable[dict[str, Any]]: ...
+    def candles_arrays(
+        self, coin: str, interval: str, dex: Optional[str] = None
+    ) -> Awaitable[dict[str, Any]]: ...
+    # Available when built with the `arrow` feature; returns a pyarrow.RecordBatch
+    def candles_dataframe(
+        self, coin: str, interval: str, dex: Optional[str] = None
+    ) -> Awaitable[Any]: ...
+    def funding_history(
+        self,
+        coin: str,
+        start_time: Optional[int] = None,
+        end_time: Optional[int] = None,
+        dex: Optional[str] = None,
+    ) -> Awaitable[str]: ...
+    def user_staking_summary(
+        self, address: str, dex: Optional[str] = None
+    ) -> Awaitable[str]: ...
+    def user_staking_delegations(
+        self, address: str, dex: Optional[str] = None
+    ) -> Awaitable[str]: ...
+    def user_staking_rewards(
+        self, address: str, dex: Optional[str] = None
+    ) -> Awaitable[str]: ...
+    def delegator_history(
+        self, address: str, dex: Optional[str] = None
+    ) -> Awaitable[str]: ...
+    def user_non_funding_ledger_updates(
+        self, user: str, start_time: int, end_time: Optional[int]
+    ) -> Awaitable[str]: ...
+    def user_non_funding_ledger_updates_mainnet(
+        self, user: str, start_time: int, end_time: Optional[int]
+    ) -> Awaitable[str]: ...
+    def portfolio(self, user: str) -> Awaitable[str]: ...
+    def portfolio_mainnet(self, user: str) -> Awaitable[str]: ...
+    def user_vault_equities(
+        self, user: str, dex: Optional[str] = None
+    ) -> Awaitable[str]: ...
+    def user_vault_equities_mainnet(self, user: str) -> Awaitable[str]: ...
+
+class PyExchangeClientConfig:
+    def __init__(self, account: str) -> None: ...
+    @staticmethod
+    def mainnet(account: str) -> PyExchangeClientConfig: ...
+    @staticmethod
+    def testnet(account: str) -> PyExchangeClientConfig: ...
+    @property
+    def base_url(self) -> str: ...
+    @property
+    def account(self) -> str: ...
+
+class PyExchangeClient:
+    def __init__(
+        self,
+        config: PyExchangeClientConfig,
+        private_key: str,
+        vault_address: Optional[str] = None,
+    ) -> None: ...
+    @staticmethod
+    def from_private_key(
+        private_key: str, mainnet: bool = True, vault_address: Optional[str] = None
+    ) -> PyExchangeClient: ...
+    @staticmethod
+    def from_keystore(
+        path: str,
+        password: str,
+        mainnet: bool = True,
+        vault_address: Optional[str] = None,
+    ) -> PyExchangeClient: ...
+    @property
+    def address(self) -> str: ...
+    def place_order(
+        self,
+        coin: str,
+        is_buy: bool,
+        sz: float,
+        limit_px: float,
+        tif: str = "Gtc",
+        reduce_only: bool = False,
+        cloid: Optional[str] = None,
+    ) -> Awaitable[str]: ...
+    def cancel(self, coin: str, oid: int) -> Awaitable[str]: ...
+    def cancel_by_cloid(self, coin: str, cloid: str) -> Awaitable[str]: ...
+    def modify(
+        self,
+        oid: int,
+        coin: str,
+        is_buy: bool,
+        sz: float,
+        limit_px: float,
+        tif: str = "Gtc",
+        reduce_only: bool = False,
+        cloid: Optional[str] = None,
+    ) -> Awaitable[str]: ...
+    def update_leverage(
+        self, coin: str, leverage: int, is_cross: bool = True
+    ) -> Awaitable[str]: ...
+    def usd_transfer(self, destination: str, amount: float) -> Awaitable[str]: ...
+    def twap_order(
+        self,
+        coin: str,
+        is_buy: bool,
+        sz: float,
+        minutes: int,
+        reduce_only: bool = False,
+        randomize: bool = False,
+    ) -> Awaitable[str]: ...
+    def twap_cancel(self, coin: str, twap_id: int) -> Awaitable[str]: ...
+    def get_open_orders(self, coin: str) -> Awaitable[str]: ...
+
+Message = dict[str, Any]
+Callback = Callable[[Message], None]
+
+class PyMessageStream(AsyncIterator[Message]):
+    def __aiter__(self) -> PyMessageStream: ...
+    def __anext__(self) -> Awaitable[Message]: ...
+    def close(self) -> Awaitable[None]: ...
+
+class PyWebSocketClient:
+    def __init__(
+        self,
+        url: Optional[str] = None,
+        mainnet: bool = True,
+        auto_reconnect: bool = True,
+    ) -> None: ...
+    def connect(self) -> Awaitable[None]: ...
+    def is_connected(self) -> Awaitable[bool]: ...
+    def subscribe(
+        self,
+        channel: str,
+        callback: Callback,
+        coin: Optional[str] = None,
+        interval: Optional[str] = None,
+        user: Optional[str] = None,
+    ) -> Awaitable[None]: ...
+    def subscribe_l2_book(self, coin: str, callback: Callback) -> Awaitable[None]: ...
+    def subscribe_trades(self, coin: str, callback: Callback) -> Awaitable[None]: ...
+    def subscribe_bbo(self, coin: str, callback: Callback) -> Awaitable[None]: ...
+    def subscribe_candle(
+        self, coin: str, interval: str, callback: Callback
+    ) -> Awaitable[None]: ...
+    def subscribe_all_mids(self, callback: Callback) -> Awaitable[None]: ...
+    def subscribe_order_updates(self, user: str, callback: Callback) -> Awaitable[None]: ...
+    def subscribe_user_fills(self, user: str, callback: Callback) -> Awaitable[None]: ...
+    def stream(
+        self,
+        channel: str,
+        coin: Optional[str] = None,
+        interval: Optional[str] = None,
+        user: Optional[str] = None,
+    ) -> PyMessageStream: ...
+    def unsubscribe(
+        self,
+        channel: str,
+        coin: Optional[str] = None,
+        interval: Optional[str] = None,
+        user: Optional[str] = None,
+    ) -> Awaitable[None]: ...
+    def close(self) -> Awaitable[None]: ...
@@ -0,0 +1,202 @@
+//! Python exception hierarchy
+//!
+//! Errors from the core clients and exchange rejections are raised as
+//! subclasses of `HyperliquidError`. Structured details are exposed as
+//! attributes (`status`, `code`, `retry_after`, `reason`, `index`, `data`,
+//! `attempts`); attributes that don't apply to an error are `None`.
+//!
+//! ```text
+//! HyperliquidError
+//! ├── NetworkError
+//! │   └── RequestTimeoutError
+//! ├── HttpError
+//! │   ├── RateLimitError
+//! │   ├── ServerError
+//! │   └── ClientError
+//! ├── RetryExhaustedError
+//! ├── OrderRejectedError
+//! ├── SigningError
+//! ├── AuthenticationError
+//! ├── WebSocketError
+//! ├── ConfigError
+//! └── ValidationError
+//! ```
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use serde_json::Value;
+
+use hyperliquid_core::HyperliquidError as CoreError;
+
+use crate::stream::json_to_py;
+
+create_exception!(hyperliquid_rs, HyperliquidError, PyException);
+create_exception!(hyperliquid_rs, NetworkError, HyperliquidError);
+create_exception!(hyperliquid_rs, RequestTimeoutError, NetworkError);
+create_exception!(hyperliquid_rs, HttpError, HyperliquidError);
+create_exception!(hyperliquid_rs, RateLimitError, HttpError);
+create_exception!(hyperliquid_rs, ServerError, HttpError);
+create_exception!(hyperliquid_rs, ClientError, HttpError);
+create_exception!(hyperliquid_rs, RetryExhaustedError, HyperliquidError);
+create_exception!(hyperliquid_rs, OrderRejectedError, HyperliquidError);
+create_exception!(hyperliquid_rs, SigningError, HyperliquidError);
+create_exception!(hyperliquid_rs, AuthenticationError, HyperliquidError);
+create_exception!(hyperliquid_rs, WebSocketError, HyperliquidError);
+create_exception!(hyperliquid_rs, ConfigError, HyperliquidError);
+create_exception!(hyperliquid_rs, ValidationError, HyperliquidError);
+
+/// Structured attributes, defaulted to `None` on the base class
+const ATTRIBUTES: &[&str] = &[
+    "status",
+    "code",
+    "retry_after",
+    "reason",
+    "index",
+    "data",
+    "attempts",
+];
+
+/// Set `attrs` on the exception instance
+fn with_attrs(err: PyErr, attrs: &[(&str, PyObject)]) -> PyErr {
+    Python::with_gil(|py| {
+        let value = err.value_bound(py);
+        for (name, attr) in attrs {
+            // Only fails if the instance is frozen, which ours never are
+            let _ = value.setattr(*name, attr);
+        }
+    });
+    err
+}
+
+/// Convert core errors to Python exceptions, prefixing the message with `context`
+///
+/// Used as `.map_err(core_error("Failed to get meta"))`.
+pub fn core_error(context: &'static str) -> impl FnOnce(CoreError) -> PyErr {
+    move |err| to_py_error(format!("{}: {}", context, err), err)
+}
+
+fn to_py_error(message: String, err: CoreError) -> PyErr {
+    Python::with_gil(|py| match err {
+        CoreError::Network(_) => NetworkError::new_err(message),
+        CoreError::Timeout(_) => RequestTimeoutError::new_err(message),
+        CoreError::Http { status, .. } => with_attrs(
+            HttpError::new_err(message),
+            &[("status", status.as_u16().into_py(py))],
+        ),
+        CoreError::RateLimit(_) => with_attrs(
+            RateLimitError::new_err(message),
+            &[("status", 429u16.into_py(py))],
+        ),
+        CoreError::RateLimitWithRetry { retry_after, .. } => with_attrs(
+            RateLimitError::new_err(message),
+            &[
+                ("status", 429u16.into_py(py)),
+                ("retry_after", retry_after.into_py(py)),
+            ],
+        ),
+        CoreError::Server { status, .. } => with_attrs(
+            ServerError::new_err(message),
+            &[("status", status.as_u16().into_py(py))],
+        ),
+        CoreError::Client { code, data, .. } => {
+            let data = data
+                .as_ref()
+                .and_then(|d| json_to_py(py, d).ok())
+                .unwrap_or_else(|| py.None());
+            with_attrs(
+                ClientError::new_err(message),
+                &[("code", code.into_py(py)), ("data", data)],
+            )
+        }
+        CoreError::RetryExhausted { attempts } => with_attrs(
+            RetryExhaustedError::new_err(message),
+            &[("attempts", attempts.into_py(py))],
+        ),
+        CoreError::WebSocket(_) => WebSocketError::new_err(message),
+        CoreError::Signing(_) => SigningError::new_err(message),
+        CoreError::Authentication(_) => AuthenticationError::new_err(message),
+        CoreError::Config(_) | CoreError::InvalidUrl(_) | CoreError::Tls(_) => {
+            ConfigError::new_err(message)
+        }
+        CoreError::Validation(_) | CoreError::Json(_) => ValidationError::new_err(message),
+        CoreError::Io(_) | CoreError::Storage(_) | CoreError::Unknown(_) => {
+            HyperliquidError::new_err(message)
+        }
+    })
+}
+
+/// Raise `OrderRejectedError` if an exchange response reports a rejection
+///
+/// Covers both whole-request errors (`{"status": "err", "response": "..."}`)
+/// and per-order errors in `response.data.statuses`, where `index` is the
+/// position of the first rejected order.
+pub fn check_exchange_response(response: &Value) -> PyResult<()> {
+    if response.get("status").and_then(Value::as_str) == Some("err") {
+        let reason = match response.get("response") {
+            Some(Value::String(reason)) => reason.clone(),
+            Some(other) => other.to_string(),
+            None => "unknown error".to_string(),
+        };
+        return Err(rejected(reason, None));
+    }
+
+    let statuses = response
+        .pointer("/response/data/statuses")
+        .and_then(Value::as_array);
+    if let Some(statuses) = statuses {
+        for (index, status) in statuses.iter().enumerate() {
+            if let Some(reason) = status.get("error").and_then(Value::as_str) {
+                return Err(rejected(reason.to_string(), Some(index)));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn rejected(reason: String, index: Option<usize>) -> PyErr {
+    let err = OrderRejectedError::new_err(format!("Rejected by exchange: {}", reason));
+    Python::with_gil(|py| {
+        with_attrs(
+            err,
+            &[("reason", reason.into_py(py)), ("index", index.into_py(py))],
+        )
+    })
+}
+
+/// Register the exception classes on the module
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    let base = py.get_type_bound::<HyperliquidError>();
+    for attr in ATTRIBUTES {
+        base.setattr(*attr, py.None())?;
+    }
+
+    m.add("HyperliquidError", base)?;
+    m.add("NetworkError", py.get_type_bound::<NetworkError>())?;
+    m.add(
+        "RequestTimeoutError",
+        py.get_type_bound::<RequestTimeoutError>(),
+    )?;
+    m.add("HttpError", py.get_type_bound::<HttpError>())?;
+    m.add("RateLimitError", py.get_type_bound::<RateLimitError>())?;
+    m.add("ServerError", py.get_type_bound::<ServerError>())?;
+    m.add("ClientError", py.get_type_bound::<ClientError>())?;
+    m.add(
+        "RetryExhaustedError",
+        py.get_type_bound::<RetryExhaustedError>(),
+    )?;
+    m.add(
+        "OrderRejectedError",
+        py.get_type_bound::<OrderRejectedError>(),
+    )?;
+    m.add("SigningError", py.get_type_bound::<SigningError>())?;
+    m.add(
+        "AuthenticationError",
+        py.get_type_bound::<AuthenticationError>(),
+    )?;
+    m.add("WebSocketError", py.get_type_bound::<WebSocketError>())?;
+    m.add("ConfigError", py.get_type_bound::<ConfigError>())?;
+    m.add("ValidationError", py.get_type_bound::<ValidationError>())?;
+    Ok(())
+}
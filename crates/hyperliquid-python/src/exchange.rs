@@ -0,0 +1,482 @@
+//! Python bindings for the Exchange API
+//!
+//! Actions are built in the exchange's wire format, signed with the client's
+//! wallet and submitted through the core `ExchangeClient`. Coins are resolved
+//! to perp asset indices from `meta`, fetched once per client, and prices and
+//! sizes are formatted with the `WireFormat` of the asset's `szDecimals`.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ethers_core::types::Address;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use pyo3_async_runtimes::tokio::future_into_py;
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+
+use hyperliquid_core::crypto::{action_types, Wallet};
+use hyperliquid_core::exchange::{ExchangeClient, ExchangeClientConfig};
+use hyperliquid_core::info::InfoClient;
+use hyperliquid_core::types::precision::{float_to_wire, PrecisionError};
+use hyperliquid_core::types::WireFormat;
+use hyperliquid_core::HttpClient;
+
+use crate::errors::{check_exchange_response, core_error, SigningError, ValidationError};
+
+/// Python bindings for ExchangeClientConfig
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PyExchangeClientConfig {
+    pub(crate) inner: ExchangeClientConfig,
+}
+
+#[pymethods]
+impl PyExchangeClientConfig {
+    #[new]
+    fn new(account: String) -> PyResult<Self> {
+        Ok(Self {
+            inner: ExchangeClientConfig::testnet(parse_address(&account)?),
+        })
+    }
+
+    #[staticmethod]
+    fn mainnet(account: String) -> PyResult<Self> {
+        Ok(Self {
+            inner: ExchangeClientConfig::mainnet(parse_address(&account)?),
+        })
+    }
+
+    #[staticmethod]
+    fn testnet(account: String) -> PyResult<Self> {
+        Ok(Self {
+            inner: ExchangeClientConfig::testnet(parse_address(&account)?),
+        })
+    }
+
+    #[getter]
+    fn base_url(&self) -> String {
+        self.inner.base_url.clone()
+    }
+
+    #[getter]
+    fn account(&self) -> String {
+        format!("{:?}", self.inner.account)
+    }
+}
+
+/// Shared state behind a `PyExchangeClient`
+struct ExchangeState {
+    client: ExchangeClient,
+    info: InfoClient,
+    wallet: Wallet,
+    vault_address: Option<String>,
+    assets: OnceCell<HashMap<String, (u32, WireFormat)>>,
+}
+
+impl ExchangeState {
+    /// Resolve a coin to its perp asset index
+    async fn asset(&self, coin: &str) -> PyResult<u32> {
+        Ok(self.asset_format(coin).await?.0)
+    }
+
+    /// Resolve a coin to its perp asset index and price and size rules
+    async fn asset_format(&self, coin: &str) -> PyResult<(u32, WireFormat)> {
+        let assets = self
+            .assets
+            .get_or_try_init(|| async {
+                let meta = self
+                    .info
+                    .meta("")
+                    .await
+                    .map_err(core_error("Failed to load asset metadata"))?;
+                Ok::<_, PyErr>(
+                    meta.universe
+                        .iter()
+                        .enumerate()
+                        .map(|(index, asset)| {
+                            let format = WireFormat::perp(asset.szDecimals.max(0) as u32);
+                            (asset.name.clone(), (index as u32, format))
+                        })
+                        .collect(),
+                )
+            })
+            .await?;
+
+        assets
+            .get(coin)
+            .copied()
+            .ok_or_else(|| ValidationError::new_err(format!("Unknown coin: {}", coin)))
+    }
+
+    /// Build an order wire for `coin`
+    async fn order_wire(&self, order: OrderArgs) -> PyResult<Value> {
+        let (asset, format) = self.asset_format(&order.coin).await?;
+        let mut wire = json!({
+            "a": asset,
+            "b": order.is_buy,
+            "p": format.price_f64(order.limit_px).map_err(precision_error)?,
+            "s": format.size_f64(order.sz).map_err(precision_error)?,
+            "r": order.reduce_only,
+            "t": {"limit": {"tif": order.tif}},
+        });
+        if let Some(cloid) = order.cloid {
+            wire["c"] = json!(cloid);
+        }
+        Ok(wire)
+    }
+
+    /// Sign and submit an L1 action, returning the JSON response
+    ///
+    /// Rejections reported in the response raise `OrderRejectedError`.
+    async fn submit(&self, action: Value) -> PyResult<String> {
+        let response = self
+            .client
+            .post_signed_action(action, &self.wallet, self.vault_address.as_deref())
+            .await
+            .map_err(core_error("Exchange request failed"))?;
+        check_exchange_response(&response)?;
+        to_json(&response)
+    }
+}
+
+/// Order parameters shared by place and modify
+struct OrderArgs {
+    coin: String,
+    is_buy: bool,
+    sz: f64,
+    limit_px: f64,
+    tif: String,
+    reduce_only: bool,
+    cloid: Option<String>,
+}
+
+/// Python bindings for ExchangeClient
+///
+/// Construct with a hex private key or an encrypted keystore; every action is
+/// signed locally before submission.
+#[pyclass]
+pub struct PyExchangeClient {
+    state: Arc<ExchangeState>,
+}
+
+impl PyExchangeClient {
+    fn from_wallet(
+        config: ExchangeClientConfig,
+        wallet: Wallet,
+        vault_address: Option<String>,
+    ) -> PyResult<Self> {
+        let http = HttpClient::with_default_config(config.base_url.clone())
+            .map_err(core_error("Failed to create HTTP client"))?;
+
+        Ok(Self {
+            state: Arc::new(ExchangeState {
+                client: ExchangeClient::new(config),
+                info: InfoClient::new(http),
+                wallet,
+                vault_address,
+                assets: OnceCell::new(),
+            }),
+        })
+    }
+
+    fn from_key(private_key: &str, mainnet: bool, vault_address: Option<String>) -> PyResult<Self> {
+        let wallet =
+            Wallet::new(private_key, mainnet).map_err(core_error("Invalid private key"))?;
+        let account = parse_address(&wallet.address())?;
+        let config = if mainnet {
+            ExchangeClientConfig::mainnet(account)
+        } else {
+            ExchangeClientConfig::testnet(account)
+        };
+        Self::from_wallet(config, wallet, vault_address)
+    }
+
+    /// Run an async operation against the shared state
+    fn spawn<'py, F, Fut>(&self, py: Python<'py>, f: F) -> PyResult<Bound<'py, PyAny>>
+    where
+        F: FnOnce(Arc<ExchangeState>) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = PyResult<String>> + Send + 'static,
+    {
+        let state = Arc::clone(&self.state);
+        future_into_py(py, async move { f(state).await })
+    }
+}
+
+#[pymethods]
+impl PyExchangeClient {
+    /// Create a client for `config.account`, signing with `private_key`
+    #[new]
+    #[pyo3(signature = (config, private_key, vault_address=None))]
+    fn new(
+        config: PyExchangeClientConfig,
+        private_key: String,
+        vault_address: Option<String>,
+    ) -> PyResult<Self> {
+        let mainnet = config.inner.base_url.contains("api.hyperliquid.xyz");
+        let wallet =
+            Wallet::new(&private_key, mainnet).map_err(core_error("Invalid private key"))?;
+        Self::from_wallet(config.inner, wallet, vault_address)
+    }
+
+    /// Create a client from a hex private key
+    #[staticmethod]
+    #[pyo3(signature = (private_key, mainnet=true, vault_address=None))]
+    fn from_private_key(
+        private_key: String,
+        mainnet: bool,
+        vault_address: Option<String>,
+    ) -> PyResult<Self> {
+        Self::from_key(&private_key, mainnet, vault_address)
+    }
+
+    /// Create a client from an encrypted JSON keystore file
+    #[staticmethod]
+    #[pyo3(signature = (path, password, mainnet=true, vault_address=None))]
+    fn from_keystore(
+        py: Python<'_>,
+        path: String,
+        password: String,
+        mainnet: bool,
+        vault_address: Option<String>,
+    ) -> PyResult<Self> {
+        // Key derivation is deliberately slow; don't hold the GIL through it
+        let key = py
+            .allow_threads(|| eth_keystore::decrypt_key(&path, password))
+            .map_err(|e| {
+                SigningError::new_err(format!("Failed to decrypt keystore {}: {}", path, e))
+            })?;
+        Self::from_key(&hex::encode(&key), mainnet, vault_address)
+    }
+
+    /// Address of the signing wallet
+    #[getter]
+    fn address(&self) -> String {
+        self.state.wallet.address()
+    }
+
+    /// Place a limit order
+    #[pyo3(signature = (coin, is_buy, sz, limit_px, tif="Gtc".to_string(), reduce_only=false, cloid=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn place_order<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        tif: String,
+        reduce_only: bool,
+        cloid: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let order = OrderArgs {
+            coin,
+            is_buy,
+            sz,
+            limit_px,
+            tif,
+            reduce_only,
+            cloid,
+        };
+        self.spawn(py, move |state| async move {
+            let wire = state.order_wire(order).await?;
+            state
+                .submit(json!({"type": "order", "orders": [wire], "grouping": "na"}))
+                .await
+        })
+    }
+
+    /// Cancel an order by exchange order id
+    fn cancel<'py>(&self, py: Python<'py>, coin: String, oid: u64) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let asset = state.asset(&coin).await?;
+            state
+                .submit(json!({"type": "cancel", "cancels": [{"a": asset, "o": oid}]}))
+                .await
+        })
+    }
+
+    /// Cancel an order by client order id
+    fn cancel_by_cloid<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        cloid: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let asset = state.asset(&coin).await?;
+            state
+                .submit(json!({
+                    "type": "cancelByCloid",
+                    "cancels": [{"asset": asset, "cloid": cloid}],
+                }))
+                .await
+        })
+    }
+
+    /// Replace a resting order's price, size or flags
+    #[pyo3(signature = (oid, coin, is_buy, sz, limit_px, tif="Gtc".to_string(), reduce_only=false, cloid=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn modify<'py>(
+        &self,
+        py: Python<'py>,
+        oid: u64,
+        coin: String,
+        is_buy: bool,
+        sz: f64,
+        limit_px: f64,
+        tif: String,
+        reduce_only: bool,
+        cloid: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let order = OrderArgs {
+            coin,
+            is_buy,
+            sz,
+            limit_px,
+            tif,
+            reduce_only,
+            cloid,
+        };
+        self.spawn(py, move |state| async move {
+            let wire = state.order_wire(order).await?;
+            state
+                .submit(json!({"type": "modify", "oid": oid, "order": wire}))
+                .await
+        })
+    }
+
+    /// Set leverage for a coin
+    #[pyo3(signature = (coin, leverage, is_cross=true))]
+    fn update_leverage<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        leverage: u32,
+        is_cross: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let asset = state.asset(&coin).await?;
+            state
+                .submit(json!({
+                    "type": "updateLeverage",
+                    "asset": asset,
+                    "isCross": is_cross,
+                    "leverage": leverage,
+                }))
+                .await
+        })
+    }
+
+    /// Send USDC to another address
+    fn usd_transfer<'py>(
+        &self,
+        py: Python<'py>,
+        destination: String,
+        amount: f64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let action = json!({
+                "type": "usdSend",
+                "destination": destination,
+                "amount": float_to_wire(amount).map_err(precision_error)?,
+                "time": chrono_ms(),
+            });
+            let response = state
+                .client
+                .post_user_signed_action(
+                    action,
+                    &state.wallet,
+                    action_types::USD_SEND,
+                    "HyperliquidTransaction:UsdSend",
+                )
+                .await
+                .map_err(core_error("Transfer failed"))?;
+            check_exchange_response(&response)?;
+            to_json(&response)
+        })
+    }
+
+    /// Start a TWAP order running for `minutes`
+    #[pyo3(signature = (coin, is_buy, sz, minutes, reduce_only=false, randomize=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn twap_order<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        is_buy: bool,
+        sz: f64,
+        minutes: u32,
+        reduce_only: bool,
+        randomize: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let (asset, format) = state.asset_format(&coin).await?;
+            state
+                .submit(json!({
+                    "type": "twapOrder",
+                    "twap": {
+                        "a": asset,
+                        "b": is_buy,
+                        "s": format.size_f64(sz).map_err(precision_error)?,
+                        "r": reduce_only,
+                        "m": minutes,
+                        "t": randomize,
+                    },
+                }))
+                .await
+        })
+    }
+
+    /// Cancel a running TWAP order
+    fn twap_cancel<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        twap_id: u64,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let asset = state.asset(&coin).await?;
+            state
+                .submit(json!({"type": "twapCancel", "a": asset, "t": twap_id}))
+                .await
+        })
+    }
+
+    /// Get open orders
+    #[pyo3(signature = (coin))]
+    fn get_open_orders<'py>(&self, py: Python<'py>, coin: String) -> PyResult<Bound<'py, PyAny>> {
+        self.spawn(py, move |state| async move {
+            let request = hyperliquid_core::types::exchange::OpenOrdersRequest { coin };
+            let result = state
+                .client
+                .get_open_orders(request)
+                .await
+                .map_err(core_error("Get open orders failed"))?;
+            to_json(&result)
+        })
+    }
+}
+
+fn parse_address(account: &str) -> PyResult<Address> {
+    Address::from_str(account)
+        .map_err(|e| PyRuntimeError::new_err(format!("Invalid address: {}", e)))
+}
+
+/// A price, size or amount the exchange would reject
+fn precision_error(err: PrecisionError) -> PyErr {
+    ValidationError::new_err(err.to_string())
+}
+
+fn chrono_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> PyResult<String> {
+    serde_json::to_string(value)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
+}
@@ -0,0 +1,139 @@
+//! Columnar views of Info API responses
+//!
+//! Builds NumPy arrays (`numpy` feature) and Arrow record batches (`arrow`
+//! feature) directly from the parsed responses, so quant code can go straight
+//! to pandas without a JSON or per-object round-trip. Prices and sizes are
+//! converted to `float64`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use hyperliquid_core::types::Candle;
+#[cfg(feature = "numpy")]
+use hyperliquid_core::types::{L2BookSnapshot, OrderLevel};
+
+fn parse_f64(field: &str, value: &str) -> PyResult<f64> {
+    value
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("Invalid {} value: {}", field, value)))
+}
+
+/// Candle columns in a fixed order
+struct CandleColumns {
+    start: Vec<i64>,
+    end: Vec<i64>,
+    open: Vec<f64>,
+    high: Vec<f64>,
+    low: Vec<f64>,
+    close: Vec<f64>,
+    volume: Vec<f64>,
+    trades: Vec<Option<i64>>,
+}
+
+impl CandleColumns {
+    fn from_candles(candles: &[Candle]) -> PyResult<Self> {
+        let mut columns = Self {
+            start: Vec::with_capacity(candles.len()),
+            end: Vec::with_capacity(candles.len()),
+            open: Vec::with_capacity(candles.len()),
+            high: Vec::with_capacity(candles.len()),
+            low: Vec::with_capacity(candles.len()),
+            close: Vec::with_capacity(candles.len()),
+            volume: Vec::with_capacity(candles.len()),
+            trades: Vec::with_capacity(candles.len()),
+        };
+        for candle in candles {
+            columns.start.push(candle.start);
+            columns.end.push(candle.end);
+            columns.open.push(parse_f64("open", &candle.open)?);
+            columns.high.push(parse_f64("high", &candle.high)?);
+            columns.low.push(parse_f64("low", &candle.low)?);
+            columns.close.push(parse_f64("close", &candle.close)?);
+            columns.volume.push(parse_f64("volume", &candle.volume)?);
+            columns.trades.push(candle.trades);
+        }
+        Ok(columns)
+    }
+}
+
+/// `{"bid_px", "bid_sz", "bid_n", "ask_px", "ask_sz", "ask_n"}` as NumPy arrays
+#[cfg(feature = "numpy")]
+pub fn l2_book_arrays(py: Python<'_>, book: &L2BookSnapshot) -> PyResult<PyObject> {
+    use numpy::PyArray1;
+    use pyo3::types::PyDict;
+
+    fn side(levels: &[OrderLevel]) -> PyResult<(Vec<f64>, Vec<f64>, Vec<i64>)> {
+        let mut px = Vec::with_capacity(levels.len());
+        let mut sz = Vec::with_capacity(levels.len());
+        let mut n = Vec::with_capacity(levels.len());
+        for level in levels {
+            px.push(parse_f64("px", &level.px)?);
+            sz.push(parse_f64("sz", &level.sz)?);
+            n.push(level.n);
+        }
+        Ok((px, sz, n))
+    }
+
+    let arrays = PyDict::new_bound(py);
+    for (prefix, levels) in [("bid", &book.levels[0]), ("ask", &book.levels[1])] {
+        let (px, sz, n) = side(levels)?;
+        arrays.set_item(format!("{}_px", prefix), PyArray1::from_vec_bound(py, px))?;
+        arrays.set_item(format!("{}_sz", prefix), PyArray1::from_vec_bound(py, sz))?;
+        arrays.set_item(format!("{}_n", prefix), PyArray1::from_vec_bound(py, n))?;
+    }
+    arrays.set_item("time", book.time)?;
+    Ok(arrays.into_any().unbind())
+}
+
+/// Candle columns as a dict of NumPy arrays; missing trade counts are -1
+#[cfg(feature = "numpy")]
+pub fn candles_arrays(py: Python<'_>, candles: &[Candle]) -> PyResult<PyObject> {
+    use numpy::PyArray1;
+    use pyo3::types::PyDict;
+
+    let columns = CandleColumns::from_candles(candles)?;
+    let arrays = PyDict::new_bound(py);
+    arrays.set_item("start", PyArray1::from_vec_bound(py, columns.start))?;
+    arrays.set_item("end", PyArray1::from_vec_bound(py, columns.end))?;
+    arrays.set_item("open", PyArray1::from_vec_bound(py, columns.open))?;
+    arrays.set_item("high", PyArray1::from_vec_bound(py, columns.high))?;
+    arrays.set_item("low", PyArray1::from_vec_bound(py, columns.low))?;
+    arrays.set_item("close", PyArray1::from_vec_bound(py, columns.close))?;
+    arrays.set_item("volume", PyArray1::from_vec_bound(py, columns.volume))?;
+    let trades: Vec<i64> = columns.trades.iter().map(|t| t.unwrap_or(-1)).collect();
+    arrays.set_item("trades", PyArray1::from_vec_bound(py, trades))?;
+    Ok(arrays.into_any().unbind())
+}
+
+/// Candles as a `pyarrow.RecordBatch` (call `.to_pandas()` for a DataFrame)
+#[cfg(feature = "arrow")]
+pub fn candles_record_batch(py: Python<'_>, candles: &[Candle]) -> PyResult<PyObject> {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, Float64Array, Int64Array, TimestampMillisecondArray};
+    use arrow::pyarrow::ToPyArrow;
+    use arrow::record_batch::RecordBatch;
+    use pyo3::exceptions::PyRuntimeError;
+
+    let columns = CandleColumns::from_candles(candles)?;
+    let batch = RecordBatch::try_from_iter([
+        (
+            "start",
+            Arc::new(TimestampMillisecondArray::from(columns.start).with_timezone("UTC"))
+                as ArrayRef,
+        ),
+        (
+            "end",
+            Arc::new(TimestampMillisecondArray::from(columns.end).with_timezone("UTC")),
+        ),
+        ("open", Arc::new(Float64Array::from(columns.open))),
+        ("high", Arc::new(Float64Array::from(columns.high))),
+        ("low", Arc::new(Float64Array::from(columns.low))),
+        ("close", Arc::new(Float64Array::from(columns.close))),
+        ("volume", Arc::new(Float64Array::from(columns.volume))),
+        ("trades", Arc::new(Int64Array::from(columns.trades))),
+    ])
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to build record batch: {}", e)))?;
+
+    batch.to_pyarrow(py)
+}
@@ -1,9 +1,27 @@
+//! Python bindings for the Hyperliquid Rust SDK
+//!
+//! Client methods return awaitables driven by a single tokio runtime shared
+//! by every client, so Python code can `await` requests (or gather many of
+//! them concurrently) without paying for a runtime per call.
+
 use pyo3::prelude::*;
 use pyo3::exceptions::PyRuntimeError;
+use pyo3_async_runtimes::tokio::{future_into_py, get_runtime};
+
+use hyperliquid_core::{HttpClient, HttpClientConfig, info::InfoClient};
+use std::collections::HashMap;
 
-use hyperliquid_core::{HttpClient, HttpClientConfig, info::InfoClient, Result as HyperliquidResult, exchange::{ExchangeClient, ExchangeClientConfig}};
-use ethers_core::types::Address;
-use serde_json;
+mod errors;
+mod exchange;
+#[cfg(any(feature = "numpy", feature = "arrow"))]
+mod frames;
+mod stream;
+mod types;
+
+use errors::core_error;
+use exchange::{PyExchangeClient, PyExchangeClientConfig};
+use stream::{PyMessageStream, PyWebSocketClient};
+use types::{PyCandle, PyFill, PyL2BookSnapshot, PyMeta, PyOpenOrder, PyUserState};
 
 /// Python bindings for HttpClientConfig
 #[pyclass]
@@ -122,8 +140,23 @@ impl PyHttpClientConfig {
     }
 }
 
+/// Configure the shared tokio runtime
+///
+/// Only takes effect if called before the first request is made.
+#[pyfunction]
+#[pyo3(signature = (worker_threads=None))]
+fn init_runtime(worker_threads: Option<usize>) {
+    let mut builder = tokio::runtime::Builder::new_multi_thread();
+    builder.enable_all().thread_name("hyperliquid-py");
+    if let Some(threads) = worker_threads {
+        builder.worker_threads(threads);
+    }
+    pyo3_async_runtimes::tokio::init(builder);
+}
+
 /// Python bindings for HttpClient
 #[pyclass]
+#[derive(Clone)]
 pub struct PyHttpClient {
     inner: HttpClient,
 }
@@ -137,7 +170,7 @@ impl PyHttpClient {
             .unwrap_or_else(|| HttpClientConfig::default());
 
         let inner = HttpClient::new(base_url, config)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create HTTP client: {}", e)))?;
+            .map_err(core_error("Failed to create HTTP client"))?;
 
         Ok(Self { inner })
     }
@@ -145,7 +178,7 @@ impl PyHttpClient {
     #[staticmethod]
     fn with_default_config(base_url: String) -> PyResult<Self> {
         let inner = HttpClient::with_default_config(base_url)
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create HTTP client: {}", e)))?;
+            .map_err(core_error("Failed to create HTTP client"))?;
 
         Ok(Self { inner })
     }
@@ -164,18 +197,14 @@ impl PyHttpClient {
 
     /// Make a POST request with JSON body
     #[pyo3(signature = (path, body))]
-    fn post(&self, path: String, body: String) -> PyResult<String> {
-        // This needs to be async, but PyO3 async support is complex
-        // For now, we'll implement a sync wrapper
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn post<'py>(&self, py: Python<'py>, path: String, body: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let body_json: serde_json::Value = serde_json::from_str(&body)
                 .map_err(|e| PyRuntimeError::new_err(format!("Invalid JSON body: {}", e)))?;
 
-            let result: serde_json::Value = self.inner.post(&path, &body_json).await
-                .map_err(|e| PyRuntimeError::new_err(format!("HTTP request failed: {}", e)))?;
+            let result: serde_json::Value = inner.post(&path, &body_json).await
+                .map_err(core_error("HTTP request failed"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
@@ -183,13 +212,11 @@ impl PyHttpClient {
     }
 
     /// Make a GET request
-    fn get(&self, path: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result: serde_json::Value = self.inner.get(&path).await
-                .map_err(|e| PyRuntimeError::new_err(format!("HTTP request failed: {}", e)))?;
+    fn get<'py>(&self, py: Python<'py>, path: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result: serde_json::Value = inner.get(&path).await
+                .map_err(core_error("HTTP request failed"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
@@ -198,16 +225,14 @@ impl PyHttpClient {
 
     /// Make a PUT request with JSON body
     #[pyo3(signature = (path, body))]
-    fn put(&self, path: String, body: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn put<'py>(&self, py: Python<'py>, path: String, body: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let body_json: serde_json::Value = serde_json::from_str(&body)
                 .map_err(|e| PyRuntimeError::new_err(format!("Invalid JSON body: {}", e)))?;
 
-            let result: serde_json::Value = self.inner.put(&path, &body_json).await
-                .map_err(|e| PyRuntimeError::new_err(format!("HTTP request failed: {}", e)))?;
+            let result: serde_json::Value = inner.put(&path, &body_json).await
+                .map_err(core_error("HTTP request failed"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
@@ -215,13 +240,11 @@ impl PyHttpClient {
     }
 
     /// Make a DELETE request
-    fn delete(&self, path: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result: serde_json::Value = self.inner.delete(&path).await
-                .map_err(|e| PyRuntimeError::new_err(format!("HTTP request failed: {}", e)))?;
+    fn delete<'py>(&self, py: Python<'py>, path: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result: serde_json::Value = inner.delete(&path).await
+                .map_err(core_error("HTTP request failed"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
@@ -245,132 +268,166 @@ impl PyInfoClient {
     }
 
     #[staticmethod]
-    fn with_default_config(base_url: String) -> PyResult<Self> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let inner = InfoClient::with_default_config(&base_url).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to create Info client: {}", e)))?;
-            Ok(Self { inner })
+    fn with_default_config(py: Python<'_>, base_url: String) -> PyResult<Self> {
+        py.allow_threads(|| {
+            get_runtime().block_on(async {
+                let inner = InfoClient::with_default_config(&base_url).await
+                    .map_err(core_error("Failed to create Info client"))?;
+                Ok(Self { inner })
+            })
         })
     }
 
     /// Get asset metadata
-    fn meta(&self, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn meta<'py>(&self, py: Python<'py>, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.meta(&dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get meta: {}", e)))?;
+            let result = inner.meta(&dex_str).await
+                .map_err(core_error("Failed to get meta"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize meta: {}", e)))
+            Ok(PyMeta::from(&result))
         })
     }
 
     /// Get user state
-    fn user_state(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn user_state<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.user_state(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user state: {}", e)))?;
+            let result = inner.user_state(&address, &dex_str).await
+                .map_err(core_error("Failed to get user state"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize user state: {}", e)))
+            Ok(PyUserState::from(&result))
         })
     }
 
     /// Get open orders for user
-    fn open_orders(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn open_orders<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.open_orders(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get open orders: {}", e)))?;
+            let result = inner.open_orders(&address, &dex_str).await
+                .map_err(core_error("Failed to get open orders"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize open orders: {}", e)))
+            Ok(result.iter().map(PyOpenOrder::from).collect::<Vec<_>>())
         })
     }
 
     /// Get frontend open orders for user
-    fn frontend_open_orders(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn frontend_open_orders<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.frontend_open_orders(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get frontend open orders: {}", e)))?;
+            let result = inner.frontend_open_orders(&address, &dex_str).await
+                .map_err(core_error("Failed to get frontend open orders"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize frontend open orders: {}", e)))
+            Ok(result.iter().map(PyOpenOrder::from).collect::<Vec<_>>())
         })
     }
 
-    /// Get L2 orderbook snapshot
-    fn l2_book(&self, coin: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+    /// Get the user's most recent fills
+    fn user_fills<'py>(&self, py: Python<'py>, address: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.user_fills(&address).await
+                .map_err(core_error("Failed to get user fills"))?;
 
-        rt.block_on(async {
-            let result = self.inner.l2_book(&coin).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get L2 book: {}", e)))?;
+            Ok(result.iter().map(PyFill::from).collect::<Vec<_>>())
+        })
+    }
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize L2 book: {}", e)))
+    /// Get L2 orderbook snapshot
+    #[pyo3(signature = (coin, dex=None))]
+    fn l2_book<'py>(&self, py: Python<'py>, coin: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let dex_str = dex.unwrap_or_default();
+            let result = inner.l2_book(&coin, &dex_str).await
+                .map_err(core_error("Failed to get L2 book"))?;
+
+            Ok(PyL2BookSnapshot::from(&result))
         })
     }
 
     /// Get candlestick data snapshot (OHLCV)
     #[pyo3(signature = (coin, interval, dex=None))]
-    fn candles_snapshot(&self, coin: String, interval: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+    fn candles_snapshot<'py>(&self, py: Python<'py>, coin: String, interval: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let dex_str = dex.unwrap_or_default();
+            let result = inner.candles_snapshot(&coin, &interval, &dex_str).await
+                .map_err(core_error("Failed to get candles snapshot"))?;
 
-        rt.block_on(async {
+            Ok(result.iter().map(PyCandle::from).collect::<Vec<_>>())
+        })
+    }
+
+    /// Get an L2 book as NumPy arrays (`bid_px`, `bid_sz`, `bid_n`, `ask_*`, `time`)
+    #[cfg(feature = "numpy")]
+    #[pyo3(signature = (coin, dex=None))]
+    fn l2_book_arrays<'py>(&self, py: Python<'py>, coin: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.candles_snapshot(&coin, &interval, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get candles snapshot: {}", e)))?;
+            let result = inner.l2_book(&coin, &dex_str).await
+                .map_err(core_error("Failed to get L2 book"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize candles: {}", e)))
+            Python::with_gil(|py| frames::l2_book_arrays(py, &result))
         })
     }
 
-    /// Get all mid prices
-    fn all_mids(&self, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
+    /// Get candles as a dict of NumPy column arrays
+    #[cfg(feature = "numpy")]
+    #[pyo3(signature = (coin, interval, dex=None))]
+    fn candles_arrays<'py>(&self, py: Python<'py>, coin: String, interval: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let dex_str = dex.unwrap_or_default();
+            let result = inner.candles_snapshot(&coin, &interval, &dex_str).await
+                .map_err(core_error("Failed to get candles snapshot"))?;
+
+            Python::with_gil(|py| frames::candles_arrays(py, &result))
+        })
+    }
 
-        rt.block_on(async {
+    /// Get candles as a `pyarrow.RecordBatch`; call `.to_pandas()` for a DataFrame
+    #[cfg(feature = "arrow")]
+    #[pyo3(signature = (coin, interval, dex=None))]
+    fn candles_dataframe<'py>(&self, py: Python<'py>, coin: String, interval: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.all_mids(&dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get all mids: {}", e)))?;
+            let result = inner.candles_snapshot(&coin, &interval, &dex_str).await
+                .map_err(core_error("Failed to get candles snapshot"))?;
 
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize all mids: {}", e)))
+            Python::with_gil(|py| frames::candles_record_batch(py, &result))
+        })
+    }
+
+    /// Get all mid prices as a `{coin: mid}` dict
+    fn all_mids<'py>(&self, py: Python<'py>, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let dex_str = dex.unwrap_or_default();
+            let result = inner.all_mids(&dex_str).await
+                .map_err(core_error("Failed to get all mids"))?;
+
+            Ok(result
+                .into_iter()
+                .map(|m| (m.coin, m.mid))
+                .collect::<HashMap<String, String>>())
         })
     }
 
     /// Get funding history for a coin (general funding rates, not user-specific)
     #[pyo3(signature = (coin, start_time=None, end_time=None, dex=None))]
-    fn funding_history(&self, coin: String, start_time: Option<i64>, end_time: Option<i64>, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn funding_history<'py>(&self, py: Python<'py>, coin: String, start_time: Option<i64>, end_time: Option<i64>, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.funding_history(&coin, start_time, end_time, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get funding history: {}", e)))?;
+            let result = inner.funding_history(&coin, start_time, end_time, &dex_str).await
+                .map_err(core_error("Failed to get funding history"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize funding history: {}", e)))
@@ -379,14 +436,12 @@ impl PyInfoClient {
 
     /// Get user's staking summary
     #[pyo3(signature = (address, dex=None))]
-    fn user_staking_summary(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn user_staking_summary<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.user_staking_summary(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user staking summary: {}", e)))?;
+            let result = inner.user_staking_summary(&address, &dex_str).await
+                .map_err(core_error("Failed to get user staking summary"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize staking summary: {}", e)))
@@ -395,14 +450,12 @@ impl PyInfoClient {
 
     /// Get user's staking delegations
     #[pyo3(signature = (address, dex=None))]
-    fn user_staking_delegations(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn user_staking_delegations<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.user_staking_delegations(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user staking delegations: {}", e)))?;
+            let result = inner.user_staking_delegations(&address, &dex_str).await
+                .map_err(core_error("Failed to get user staking delegations"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize staking delegations: {}", e)))
@@ -411,14 +464,12 @@ impl PyInfoClient {
 
     /// Get user's staking rewards
     #[pyo3(signature = (address, dex=None))]
-    fn user_staking_rewards(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn user_staking_rewards<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.user_staking_rewards(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user staking rewards: {}", e)))?;
+            let result = inner.user_staking_rewards(&address, &dex_str).await
+                .map_err(core_error("Failed to get user staking rewards"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize staking rewards: {}", e)))
@@ -427,14 +478,12 @@ impl PyInfoClient {
 
     /// Get comprehensive delegator history
     #[pyo3(signature = (address, dex=None))]
-    fn delegator_history(&self, address: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn delegator_history<'py>(&self, py: Python<'py>, address: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.delegator_history(&address, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get delegator history: {}", e)))?;
+            let result = inner.delegator_history(&address, &dex_str).await
+                .map_err(core_error("Failed to get delegator history"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize delegator history: {}", e)))
@@ -442,13 +491,11 @@ impl PyInfoClient {
     }
 
     /// Get user non-funding ledger updates for a time range
-    fn user_non_funding_ledger_updates(&self, user: String, start_time: i64, end_time: Option<i64>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result = self.inner.user_non_funding_ledger_updates(&user, start_time, end_time).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user non-funding ledger updates: {}", e)))?;
+    fn user_non_funding_ledger_updates<'py>(&self, py: Python<'py>, user: String, start_time: i64, end_time: Option<i64>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.user_non_funding_ledger_updates(&user, start_time, end_time).await
+                .map_err(core_error("Failed to get user non-funding ledger updates"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize ledger updates: {}", e)))
@@ -456,13 +503,11 @@ impl PyInfoClient {
     }
 
     /// Get user non-funding ledger updates for mainnet with optional end time
-    fn user_non_funding_ledger_updates_mainnet(&self, user: String, start_time: i64, end_time: Option<i64>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result = self.inner.user_non_funding_ledger_updates_mainnet(&user, start_time, end_time).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user non-funding ledger updates: {}", e)))?;
+    fn user_non_funding_ledger_updates_mainnet<'py>(&self, py: Python<'py>, user: String, start_time: i64, end_time: Option<i64>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.user_non_funding_ledger_updates_mainnet(&user, start_time, end_time).await
+                .map_err(core_error("Failed to get user non-funding ledger updates"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize ledger updates: {}", e)))
@@ -470,13 +515,11 @@ impl PyInfoClient {
     }
 
     /// Get user's portfolio performance data
-    fn portfolio(&self, user: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result = self.inner.portfolio(&user).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get portfolio: {}", e)))?;
+    fn portfolio<'py>(&self, py: Python<'py>, user: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.portfolio(&user).await
+                .map_err(core_error("Failed to get portfolio"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize portfolio: {}", e)))
@@ -484,13 +527,11 @@ impl PyInfoClient {
     }
 
     /// Get user's portfolio performance data for mainnet (default)
-    fn portfolio_mainnet(&self, user: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result = self.inner.portfolio_mainnet(&user).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get portfolio: {}", e)))?;
+    fn portfolio_mainnet<'py>(&self, py: Python<'py>, user: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.portfolio_mainnet(&user).await
+                .map_err(core_error("Failed to get portfolio"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize portfolio: {}", e)))
@@ -499,14 +540,12 @@ impl PyInfoClient {
 
     /// Get user's vault equity positions
     #[pyo3(signature = (user, dex=None))]
-    fn user_vault_equities(&self, user: String, dex: Option<String>) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
+    fn user_vault_equities<'py>(&self, py: Python<'py>, user: String, dex: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
             let dex_str = dex.unwrap_or_default();
-            let result = self.inner.user_vault_equities(&user, &dex_str).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user vault equities: {}", e)))?;
+            let result = inner.user_vault_equities(&user, &dex_str).await
+                .map_err(core_error("Failed to get user vault equities"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize vault equities: {}", e)))
@@ -514,13 +553,11 @@ impl PyInfoClient {
     }
 
     /// Get user's vault equity positions for mainnet (default)
-    fn user_vault_equities_mainnet(&self, user: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let result = self.inner.user_vault_equities_mainnet(&user).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to get user vault equities: {}", e)))?;
+    fn user_vault_equities_mainnet<'py>(&self, py: Python<'py>, user: String) -> PyResult<Bound<'py, PyAny>> {
+        let inner = self.inner.clone();
+        future_into_py(py, async move {
+            let result = inner.user_vault_equities_mainnet(&user).await
+                .map_err(core_error("Failed to get user vault equities"))?;
 
             serde_json::to_string(&result)
                 .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize vault equities: {}", e)))
@@ -528,160 +565,17 @@ impl PyInfoClient {
     }
 }
 
-/// Convert HyperliquidError to Python exceptions
-pub fn to_py_error(err: hyperliquid_core::HyperliquidError) -> PyErr {
-    use hyperliquid_core::HyperliquidError;
-
-    match err {
-        HyperliquidError::Network(_) => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::Http { .. } => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::RateLimit(_) => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::Server { .. } => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::Client { .. } => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::InvalidUrl(_) => pyo3::exceptions::PyValueError::new_err(err.to_string()),
-        HyperliquidError::Json(_) => pyo3::exceptions::PyValueError::new_err(err.to_string()),
-        HyperliquidError::Timeout(_) => pyo3::exceptions::PyTimeoutError::new_err(err.to_string()),
-        HyperliquidError::RetryExhausted { .. } => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::WebSocket(_) => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::Signing(_) => PyRuntimeError::new_err(err.to_string()),
-        HyperliquidError::Config(_) => pyo3::exceptions::PyValueError::new_err(err.to_string()),
-        HyperliquidError::Unknown(_) => PyRuntimeError::new_err(err.to_string()),
-    }
-}
-
-/// Python bindings for ExchangeClientConfig
-#[pyclass]
-#[derive(Clone, Debug)]
-pub struct PyExchangeClientConfig {
-    inner: ExchangeClientConfig,
-}
-
-#[pymethods]
-impl PyExchangeClientConfig {
-    #[new]
-    fn new(account: String) -> PyResult<Self> {
-        let address = Address::from_str(&account)
-            .map_err(|e| PyRuntimeError::new_err(format!("Invalid address: {}", e)))?;
-
-        Ok(Self {
-            inner: ExchangeClientConfig::testnet(address),
-        })
-    }
-
-    #[staticmethod]
-    fn mainnet(account: String) -> PyResult<Self> {
-        let address = Address::from_str(&account)
-            .map_err(|e| PyRuntimeError::new_err(format!("Invalid address: {}", e)))?;
-
-        Ok(Self {
-            inner: ExchangeClientConfig::mainnet(address),
-        })
-    }
-
-    #[staticmethod]
-    fn testnet(account: String) -> PyResult<Self> {
-        let address = Address::from_str(&account)
-            .map_err(|e| PyRuntimeError::new_err(format!("Invalid address: {}", e)))?;
-
-        Ok(Self {
-            inner: ExchangeClientConfig::testnet(address),
-        })
-    }
-
-    #[getter]
-    fn base_url(&self) -> String {
-        self.inner.base_url.clone()
-    }
-
-    #[getter]
-    fn account(&self) -> String {
-        format!("{:?}", self.inner.account)
-    }
-}
-
-/// Python bindings for ExchangeClient
-#[pyclass]
-pub struct PyExchangeClient {
-    inner: ExchangeClient,
-}
-
-#[pymethods]
-impl PyExchangeClient {
-    #[new]
-    fn new(config: PyExchangeClientConfig) -> Self {
-        Self {
-            inner: ExchangeClient::new(config.inner),
-        }
-    }
-
-    /// Place a new order
-    #[pyo3(signature = (order_json))]
-    fn place_order(&self, order_json: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let order: hyperliquid_core::types::exchange::OrderRequest = serde_json::from_str(&order_json)
-                .map_err(|e| PyRuntimeError::new_err(format!("Invalid order JSON: {}", e)))?;
-
-            // Use dummy private key for now (signing not implemented)
-            let dummy_key = [0u8; 32];
-
-            let result = self.inner.place_order(order, &dummy_key).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Order placement failed: {}", e)))?;
-
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
-        })
-    }
-
-    /// Cancel an order
-    #[pyo3(signature = (cancel_json))]
-    fn cancel_order(&self, cancel_json: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let cancel: hyperliquid_core::types::exchange::CancelRequest = serde_json::from_str(&cancel_json)
-                .map_err(|e| PyRuntimeError::new_err(format!("Invalid cancel JSON: {}", e)))?;
-
-            // Use dummy private key for now (signing not implemented)
-            let dummy_key = [0u8; 32];
-
-            let result = self.inner.cancel_order(cancel, &dummy_key).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Cancel failed: {}", e)))?;
-
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
-        })
-    }
-
-    /// Get open orders
-    #[pyo3(signature = (coin))]
-    fn get_open_orders(&self, coin: String) -> PyResult<String> {
-        let rt = tokio::runtime::Runtime::new()
-            .map_err(|e| PyRuntimeError::new_err(format!("Failed to create runtime: {}", e)))?;
-
-        rt.block_on(async {
-            let request = hyperliquid_core::types::exchange::OpenOrdersRequest {
-                coin,
-            };
-
-            let result = self.inner.get_open_orders(request).await
-                .map_err(|e| PyRuntimeError::new_err(format!("Get open orders failed: {}", e)))?;
-
-            serde_json::to_string(&result)
-                .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize response: {}", e)))
-        })
-    }
-}
-
 #[pymodule]
-fn hyperliquid_rs(_py: Python, m: &PyModule) -> PyResult<()> {
+fn hyperliquid_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(init_runtime, m)?)?;
     m.add_class::<PyHttpClientConfig>()?;
     m.add_class::<PyHttpClient>()?;
     m.add_class::<PyInfoClient>()?;
     m.add_class::<PyExchangeClientConfig>()?;
     m.add_class::<PyExchangeClient>()?;
+    m.add_class::<PyWebSocketClient>()?;
+    m.add_class::<PyMessageStream>()?;
+    types::register(m)?;
+    errors::register(m)?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,379 @@
+//! Python bindings for the WebSocket streaming client
+//!
+//! Messages are delivered either to a callback registered per subscription or
+//! through an async iterator returned by `stream()`. Connection management,
+//! heartbeats, reconnects and resubscription are handled by the core
+//! `WebSocketClient`.
+
+use std::sync::Arc;
+
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use pyo3_async_runtimes::tokio::future_into_py;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, Mutex};
+
+use hyperliquid_core::stream::{WebSocketClient, WebSocketClientConfig, WebSocketResponse};
+use hyperliquid_core::types::Subscription;
+
+use crate::errors::{ValidationError, WebSocketError};
+
+/// Python bindings for WebSocketClient
+///
+/// The connection is opened on the first subscription (or by `connect()`).
+/// Callbacks run on a runtime worker thread with the GIL held, so they should
+/// be quick; use `stream()` to consume messages from asyncio code instead.
+#[pyclass]
+pub struct PyWebSocketClient {
+    inner: Arc<Mutex<WebSocketClient>>,
+}
+
+impl PyWebSocketClient {
+    /// Connect if needed, register `handler` and subscribe
+    fn subscribe_with<'py, F>(
+        &self,
+        py: Python<'py>,
+        subscription: Subscription,
+        handler: F,
+    ) -> PyResult<Bound<'py, PyAny>>
+    where
+        F: Fn(WebSocketResponse) + Send + Sync + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            let client = connected(&inner).await?;
+            client.register_handler(subscription.clone(), handler).await;
+            client.subscribe(subscription).await.map_err(ws_error)?;
+            Ok(())
+        })
+    }
+
+    /// Subscribe with a Python callback
+    fn subscribe_callback<'py>(
+        &self,
+        py: Python<'py>,
+        subscription: Subscription,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_with(py, subscription, move |response| {
+            Python::with_gil(|py| {
+                let result =
+                    message_to_py(py, &response).and_then(|message| callback.call1(py, (message,)));
+                if let Err(e) = result {
+                    e.print(py);
+                }
+            })
+        })
+    }
+}
+
+#[pymethods]
+impl PyWebSocketClient {
+    /// Create a client for `url`, or the mainnet/testnet endpoint
+    #[new]
+    #[pyo3(signature = (url=None, mainnet=true, auto_reconnect=true))]
+    fn new(url: Option<String>, mainnet: bool, auto_reconnect: bool) -> PyResult<Self> {
+        let mut config = if mainnet {
+            WebSocketClientConfig::mainnet()
+        } else {
+            WebSocketClientConfig::testnet()
+        };
+        if let Some(url) = url {
+            config.url = url;
+        }
+        config.auto_reconnect = auto_reconnect;
+
+        let client = WebSocketClient::with_config(config).map_err(ws_error)?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(client)),
+        })
+    }
+
+    /// Open the connection
+    fn connect<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            connected(&inner).await?;
+            Ok(())
+        })
+    }
+
+    /// Check if the client is connected
+    fn is_connected<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        future_into_py(
+            py,
+            async move { Ok(inner.lock().await.is_connected().await) },
+        )
+    }
+
+    /// Subscribe to any channel, e.g. `subscribe("candle", callback, coin="BTC", interval="1m")`
+    #[pyo3(signature = (channel, callback, coin=None, interval=None, user=None))]
+    fn subscribe<'py>(
+        &self,
+        py: Python<'py>,
+        channel: &str,
+        callback: PyObject,
+        coin: Option<String>,
+        interval: Option<String>,
+        user: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let subscription = build_subscription(channel, coin, interval, user)?;
+        self.subscribe_callback(py, subscription, callback)
+    }
+
+    /// Subscribe to L2 book updates for a coin
+    fn subscribe_l2_book<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_callback(py, Subscription::L2Book { coin }, callback)
+    }
+
+    /// Subscribe to trades for a coin
+    fn subscribe_trades<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_callback(py, Subscription::Trades { coin }, callback)
+    }
+
+    /// Subscribe to best bid/offer updates for a coin
+    fn subscribe_bbo<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_callback(py, Subscription::Bbo { coin }, callback)
+    }
+
+    /// Subscribe to candles for a coin and interval
+    fn subscribe_candle<'py>(
+        &self,
+        py: Python<'py>,
+        coin: String,
+        interval: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_callback(py, Subscription::Candle { coin, interval }, callback)
+    }
+
+    /// Subscribe to mid prices for all coins
+    fn subscribe_all_mids<'py>(
+        &self,
+        py: Python<'py>,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_callback(py, Subscription::AllMids, callback)
+    }
+
+    /// Subscribe to order updates for a user
+    fn subscribe_order_updates<'py>(
+        &self,
+        py: Python<'py>,
+        user: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let subscription = build_subscription("orderUpdates", None, None, Some(user))?;
+        self.subscribe_callback(py, subscription, callback)
+    }
+
+    /// Subscribe to fills for a user
+    fn subscribe_user_fills<'py>(
+        &self,
+        py: Python<'py>,
+        user: String,
+        callback: PyObject,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let subscription = build_subscription("userFills", None, None, Some(user))?;
+        self.subscribe_callback(py, subscription, callback)
+    }
+
+    /// Return an async iterator over a channel's messages
+    ///
+    /// The subscription is made when iteration starts, so this can be used
+    /// directly in `async for`. Replaces any callback registered for the
+    /// same subscription.
+    #[pyo3(signature = (channel, coin=None, interval=None, user=None))]
+    fn stream(
+        &self,
+        channel: &str,
+        coin: Option<String>,
+        interval: Option<String>,
+        user: Option<String>,
+    ) -> PyResult<PyMessageStream> {
+        let subscription = build_subscription(channel, coin, interval, user)?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        Ok(PyMessageStream {
+            inner: Arc::clone(&self.inner),
+            subscription,
+            tx: Arc::new(std::sync::Mutex::new(Some(tx))),
+            rx: Arc::new(Mutex::new(rx)),
+        })
+    }
+
+    /// Unsubscribe from a channel and drop its callback or stream
+    #[pyo3(signature = (channel, coin=None, interval=None, user=None))]
+    fn unsubscribe<'py>(
+        &self,
+        py: Python<'py>,
+        channel: &str,
+        coin: Option<String>,
+        interval: Option<String>,
+        user: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let subscription = build_subscription(channel, coin, interval, user)?;
+        let inner = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            let client = inner.lock().await.clone();
+            client.unregister_handler(&subscription).await;
+            client.unsubscribe(subscription).await.map_err(ws_error)?;
+            Ok(())
+        })
+    }
+
+    /// Close the connection
+    fn close<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        future_into_py(py, async move {
+            inner.lock().await.shutdown().await.map_err(ws_error)?;
+            Ok(())
+        })
+    }
+}
+
+/// Async iterator over the messages of one subscription
+#[pyclass]
+pub struct PyMessageStream {
+    inner: Arc<Mutex<WebSocketClient>>,
+    subscription: Subscription,
+    /// Sender handed to the router on the first `__anext__`
+    tx: Arc<std::sync::Mutex<Option<mpsc::UnboundedSender<WebSocketResponse>>>>,
+    rx: Arc<Mutex<mpsc::UnboundedReceiver<WebSocketResponse>>>,
+}
+
+#[pymethods]
+impl PyMessageStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let tx = self.tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let inner = Arc::clone(&self.inner);
+        let subscription = self.subscription.clone();
+        let rx = Arc::clone(&self.rx);
+
+        future_into_py(py, async move {
+            if let Some(tx) = tx {
+                let client = connected(&inner).await?;
+                client
+                    .register_handler(subscription.clone(), move |response| {
+                        let _ = tx.send(response);
+                    })
+                    .await;
+                client.subscribe(subscription).await.map_err(ws_error)?;
+            }
+
+            let response = rx
+                .lock()
+                .await
+                .recv()
+                .await
+                .ok_or_else(|| PyStopAsyncIteration::new_err("stream closed"))?;
+            Python::with_gil(|py| message_to_py(py, &response))
+        })
+    }
+
+    /// Unsubscribe and end the iteration
+    fn close<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let inner = Arc::clone(&self.inner);
+        let subscription = self.subscription.clone();
+        // Drop the sender if iteration never started
+        self.tx.lock().unwrap_or_else(|e| e.into_inner()).take();
+        future_into_py(py, async move {
+            let client = inner.lock().await.clone();
+            // Dropping the handler drops the sender, which ends the iterator
+            client.unregister_handler(&subscription).await;
+            client.unsubscribe(subscription).await.map_err(ws_error)?;
+            Ok(())
+        })
+    }
+}
+
+/// Connect the shared client if needed and return a handle to it
+async fn connected(inner: &Mutex<WebSocketClient>) -> PyResult<WebSocketClient> {
+    let mut client = inner.lock().await;
+    if !client.is_connected().await {
+        client.connect().await.map_err(ws_error)?;
+    }
+    Ok(client.clone())
+}
+
+/// Build a subscription from its wire channel name and parameters
+fn build_subscription(
+    channel: &str,
+    coin: Option<String>,
+    interval: Option<String>,
+    user: Option<String>,
+) -> PyResult<Subscription> {
+    let mut value = json!({ "type": channel });
+    if let Some(coin) = coin {
+        value["coin"] = json!(coin);
+    }
+    if let Some(interval) = interval {
+        value["interval"] = json!(interval);
+    }
+    if let Some(user) = user {
+        value["user"] = json!(user);
+    }
+    serde_json::from_value(value)
+        .map_err(|e| ValidationError::new_err(format!("Invalid subscription {}: {}", channel, e)))
+}
+
+/// Convert a message to `{"channel": ..., "data": ..., "time": ...}`
+fn message_to_py(py: Python<'_>, response: &WebSocketResponse) -> PyResult<PyObject> {
+    let message = PyDict::new_bound(py);
+    message.set_item("channel", &response.channel)?;
+    message.set_item("data", json_to_py(py, &response.data)?)?;
+    message.set_item("time", response.time)?;
+    Ok(message.into_any().unbind())
+}
+
+/// Convert a JSON value to the equivalent Python object
+pub(crate) fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_py(py),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_py(py),
+            (None, Some(u)) => u.into_py(py),
+            _ => n.as_f64().unwrap_or_default().into_py(py),
+        },
+        Value::String(s) => s.into_py(py),
+        Value::Array(items) => {
+            let list = PyList::empty_bound(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new_bound(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+fn ws_error(e: impl std::fmt::Display) -> PyErr {
+    WebSocketError::new_err(format!("WebSocket error: {}", e))
+}
@@ -0,0 +1,418 @@
+//! Typed Python views of Info API responses
+//!
+//! Each class copies the fields of the corresponding core type into
+//! snake_case attributes, so Python code gets attribute access and stub-based
+//! type hints instead of parsing JSON strings.
+
+use pyo3::prelude::*;
+
+use hyperliquid_core::types::{
+    AssetMeta, Candle, FrontendOrder, L2BookSnapshot, MarginSummary, Meta, NewOrder, OrderLevel,
+    Position, UserState, WithFee,
+};
+
+/// Perpetual asset metadata
+#[pyclass(name = "AssetMeta", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyAssetMeta {
+    pub name: String,
+    pub sz_decimals: i32,
+    pub max_leverage: i32,
+    pub only_isolated: bool,
+}
+
+#[pymethods]
+impl PyAssetMeta {
+    fn __repr__(&self) -> String {
+        format!(
+            "AssetMeta(name={:?}, sz_decimals={}, max_leverage={})",
+            self.name, self.sz_decimals, self.max_leverage
+        )
+    }
+}
+
+impl From<&AssetMeta> for PyAssetMeta {
+    fn from(meta: &AssetMeta) -> Self {
+        Self {
+            name: meta.name.clone(),
+            sz_decimals: meta.szDecimals,
+            max_leverage: meta.maxLeverage,
+            only_isolated: meta.onlyIsolated,
+        }
+    }
+}
+
+/// Exchange metadata
+#[pyclass(name = "Meta", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyMeta {
+    pub universe: Vec<PyAssetMeta>,
+}
+
+#[pymethods]
+impl PyMeta {
+    /// Find an asset by name
+    fn asset(&self, name: &str) -> Option<PyAssetMeta> {
+        self.universe.iter().find(|a| a.name == name).cloned()
+    }
+
+    fn __len__(&self) -> usize {
+        self.universe.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Meta(universe=<{} assets>)", self.universe.len())
+    }
+}
+
+impl From<&Meta> for PyMeta {
+    fn from(meta: &Meta) -> Self {
+        Self {
+            universe: meta.universe.iter().map(PyAssetMeta::from).collect(),
+        }
+    }
+}
+
+/// Account margin summary
+#[pyclass(name = "MarginSummary", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyMarginSummary {
+    pub account_value: String,
+    pub total_margin_used: String,
+    pub total_ntl_pos: String,
+    pub total_raw_usd: String,
+}
+
+#[pymethods]
+impl PyMarginSummary {
+    fn __repr__(&self) -> String {
+        format!(
+            "MarginSummary(account_value={:?}, total_margin_used={:?})",
+            self.account_value, self.total_margin_used
+        )
+    }
+}
+
+impl From<&MarginSummary> for PyMarginSummary {
+    fn from(summary: &MarginSummary) -> Self {
+        Self {
+            account_value: summary.accountValue.clone(),
+            total_margin_used: summary.totalMarginUsed.clone(),
+            total_ntl_pos: summary.totalNtlPos.clone(),
+            total_raw_usd: summary.totalRawUsd.clone(),
+        }
+    }
+}
+
+/// Open perpetual position
+#[pyclass(name = "Position", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyPosition {
+    pub coin: String,
+    pub szi: String,
+    pub entry_px: Option<String>,
+    pub leverage: Option<String>,
+    pub liquidation_px: Option<String>,
+    pub position_value: String,
+    pub margin_used: Option<String>,
+    pub unrealized_pnl: Option<String>,
+    pub return_on_equity: Option<String>,
+}
+
+#[pymethods]
+impl PyPosition {
+    fn __repr__(&self) -> String {
+        format!(
+            "Position(coin={:?}, szi={:?}, entry_px={:?})",
+            self.coin, self.szi, self.entry_px
+        )
+    }
+}
+
+impl From<&Position> for PyPosition {
+    fn from(position: &Position) -> Self {
+        let details = &position.position;
+        Self {
+            coin: position.coin.clone(),
+            szi: details.szi.clone(),
+            entry_px: details.entryPx.clone(),
+            leverage: details.leverage.clone(),
+            liquidation_px: details.liquidationPx.clone(),
+            position_value: details.positionValue.clone(),
+            margin_used: details.marginUsed.clone(),
+            unrealized_pnl: details.rawPNL.clone(),
+            return_on_equity: details.returnOnEquity.clone(),
+        }
+    }
+}
+
+/// Perpetuals account state
+#[pyclass(name = "UserState", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyUserState {
+    pub margin_summary: PyMarginSummary,
+    pub cross_margin_summary: Option<PyMarginSummary>,
+    pub withdrawable: String,
+    pub positions: Vec<PyPosition>,
+}
+
+#[pymethods]
+impl PyUserState {
+    /// Find the position for a coin
+    fn position(&self, coin: &str) -> Option<PyPosition> {
+        self.positions.iter().find(|p| p.coin == coin).cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "UserState(account_value={:?}, positions=<{}>)",
+            self.margin_summary.account_value,
+            self.positions.len()
+        )
+    }
+}
+
+impl From<&UserState> for PyUserState {
+    fn from(state: &UserState) -> Self {
+        Self {
+            margin_summary: PyMarginSummary::from(&state.marginSummary),
+            cross_margin_summary: state.crossMarginSummary.as_ref().map(|s| PyMarginSummary {
+                account_value: s.accountValue.clone(),
+                total_margin_used: s.totalMarginUsed.clone(),
+                total_ntl_pos: s.totalNtlPos.clone(),
+                total_raw_usd: s.totalRawUsd.clone(),
+            }),
+            withdrawable: state.withdrawable.clone(),
+            positions: state.positions.iter().map(PyPosition::from).collect(),
+        }
+    }
+}
+
+/// One price level of an L2 book
+#[pyclass(name = "BookLevel", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyBookLevel {
+    pub px: String,
+    pub sz: String,
+    pub n: i64,
+}
+
+#[pymethods]
+impl PyBookLevel {
+    fn __repr__(&self) -> String {
+        format!(
+            "BookLevel(px={:?}, sz={:?}, n={})",
+            self.px, self.sz, self.n
+        )
+    }
+}
+
+impl From<&OrderLevel> for PyBookLevel {
+    fn from(level: &OrderLevel) -> Self {
+        Self {
+            px: level.px.clone(),
+            sz: level.sz.clone(),
+            n: level.n,
+        }
+    }
+}
+
+/// L2 order book snapshot
+#[pyclass(name = "L2BookSnapshot", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyL2BookSnapshot {
+    pub coin: String,
+    pub time: i64,
+    pub bids: Vec<PyBookLevel>,
+    pub asks: Vec<PyBookLevel>,
+}
+
+#[pymethods]
+impl PyL2BookSnapshot {
+    /// Best bid level, if any
+    fn best_bid(&self) -> Option<PyBookLevel> {
+        self.bids.first().cloned()
+    }
+
+    /// Best ask level, if any
+    fn best_ask(&self) -> Option<PyBookLevel> {
+        self.asks.first().cloned()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "L2BookSnapshot(coin={:?}, bids=<{}>, asks=<{}>)",
+            self.coin,
+            self.bids.len(),
+            self.asks.len()
+        )
+    }
+}
+
+impl From<&L2BookSnapshot> for PyL2BookSnapshot {
+    fn from(book: &L2BookSnapshot) -> Self {
+        Self {
+            coin: book.coin.clone(),
+            time: book.time,
+            bids: book.levels[0].iter().map(PyBookLevel::from).collect(),
+            asks: book.levels[1].iter().map(PyBookLevel::from).collect(),
+        }
+    }
+}
+
+/// User fill
+#[pyclass(name = "Fill", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyFill {
+    pub coin: String,
+    pub px: String,
+    pub sz: String,
+    pub time: i64,
+    pub oid: i64,
+    pub fee: String,
+    pub dir: Option<String>,
+    pub cloid: Option<String>,
+}
+
+#[pymethods]
+impl PyFill {
+    fn __repr__(&self) -> String {
+        format!(
+            "Fill(coin={:?}, px={:?}, sz={:?}, oid={})",
+            self.coin, self.px, self.sz, self.oid
+        )
+    }
+}
+
+impl From<&WithFee> for PyFill {
+    fn from(fill: &WithFee) -> Self {
+        Self {
+            coin: fill.coin.clone(),
+            px: fill.px.clone(),
+            sz: fill.sz.clone(),
+            time: fill.time,
+            oid: fill.oid,
+            fee: fill.fee.clone(),
+            dir: fill.dir.clone(),
+            cloid: fill.cloid.clone(),
+        }
+    }
+}
+
+/// Resting order
+#[pyclass(name = "OpenOrder", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyOpenOrder {
+    pub coin: String,
+    pub oid: i64,
+    pub limit_px: String,
+    pub sz: String,
+    pub time: i64,
+    pub order_type: String,
+    pub reduce_only: bool,
+    pub is_trigger: bool,
+    pub trigger_px: Option<String>,
+    pub cloid: Option<String>,
+}
+
+#[pymethods]
+impl PyOpenOrder {
+    fn __repr__(&self) -> String {
+        format!(
+            "OpenOrder(coin={:?}, oid={}, limit_px={:?}, sz={:?})",
+            self.coin, self.oid, self.limit_px, self.sz
+        )
+    }
+}
+
+impl From<&NewOrder> for PyOpenOrder {
+    fn from(order: &NewOrder) -> Self {
+        Self {
+            coin: order.coin.clone(),
+            oid: order.oid,
+            limit_px: order.limitPx.clone(),
+            sz: order.sz.clone(),
+            time: order.time,
+            order_type: format!("{:?}", order.orderType),
+            reduce_only: order.reduceOnly.unwrap_or(false),
+            is_trigger: order.isTrigger.unwrap_or(false),
+            trigger_px: order.triggerPx.clone(),
+            cloid: order.cloid.clone(),
+        }
+    }
+}
+
+impl From<&FrontendOrder> for PyOpenOrder {
+    fn from(order: &FrontendOrder) -> Self {
+        Self {
+            coin: order.coin.clone(),
+            oid: order.oid as i64,
+            limit_px: order.limit_px.clone(),
+            sz: order.sz.clone(),
+            time: order.timestamp as i64,
+            order_type: format!("{:?}", order.order_type),
+            reduce_only: order.reduce_only,
+            is_trigger: order.is_trigger,
+            trigger_px: order.is_trigger.then(|| order.trigger_px.clone()),
+            cloid: order.cloid.clone(),
+        }
+    }
+}
+
+/// OHLCV candle
+#[pyclass(name = "Candle", module = "hyperliquid_rs", get_all, frozen)]
+#[derive(Clone, Debug)]
+pub struct PyCandle {
+    pub coin: String,
+    pub interval: String,
+    pub start: i64,
+    pub end: i64,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub trades: Option<i64>,
+}
+
+#[pymethods]
+impl PyCandle {
+    fn __repr__(&self) -> String {
+        format!(
+            "Candle(coin={:?}, start={}, open={:?}, close={:?})",
+            self.coin, self.start, self.open, self.close
+        )
+    }
+}
+
+impl From<&Candle> for PyCandle {
+    fn from(candle: &Candle) -> Self {
+        Self {
+            coin: candle.coin.clone(),
+            interval: candle.interval.clone(),
+            start: candle.start,
+            end: candle.end,
+            open: candle.open.clone(),
+            high: candle.high.clone(),
+            low: candle.low.clone(),
+            close: candle.close.clone(),
+            volume: candle.volume.clone(),
+            trades: candle.trades,
+        }
+    }
+}
+
+/// Register the response classes on the module
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyAssetMeta>()?;
+    m.add_class::<PyMeta>()?;
+    m.add_class::<PyMarginSummary>()?;
+    m.add_class::<PyPosition>()?;
+    m.add_class::<PyUserState>()?;
+    m.add_class::<PyBookLevel>()?;
+    m.add_class::<PyL2BookSnapshot>()?;
+    m.add_class::<PyFill>()?;
+    m.add_class::<PyOpenOrder>()?;
+    m.add_class::<PyCandle>()?;
+    Ok(())
+}
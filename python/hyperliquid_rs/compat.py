@@ -0,0 +1,410 @@
+"""Drop-in replacements for the official ``hyperliquid-python-sdk`` clients
+
+Existing bots can switch to the Rust backend by changing one import::
+
+    # from hyperliquid.info import Info
+    # from hyperliquid.exchange import Exchange
+    from hyperliquid_rs.compat import Exchange, Info
+
+Method names, arguments and return shapes (plain dicts/lists in the API's
+camelCase format) follow the official SDK. Calls are synchronous like the
+official SDK; use the native ``PyInfoClient`` / ``PyExchangeClient`` classes
+from asyncio code instead.
+"""
+
+import asyncio
+import itertools
+import json
+import threading
+from typing import Any, Callable, Optional, Union
+
+MAINNET_API_URL = "https://api.hyperliquid.xyz"
+TESTNET_API_URL = "https://api.hyperliquid-testnet.xyz"
+
+DEFAULT_SLIPPAGE = 0.05
+
+
+def _rust() -> Any:
+    """Import the compiled Rust module"""
+    try:
+        import _hyperliquid_rs as _rust_module  # type: ignore
+
+        return _rust_module
+    except ImportError as e:
+        raise ImportError(
+            "hyperliquid_rs.compat requires the Rust module. "
+            "Please build with: maturin develop"
+        ) from e
+
+
+class _Runner:
+    """Runs the Rust awaitables to completion on a private event loop"""
+
+    def __init__(self) -> None:
+        self._loop = asyncio.new_event_loop()
+        self._lock = threading.Lock()
+
+    def run(self, factory: Callable[[], Any]) -> Any:
+        async def call() -> Any:
+            # The awaitable must be created while the loop is running
+            return await factory()
+
+        with self._lock:
+            return self._loop.run_until_complete(call())
+
+
+def _run_json(runner: _Runner, factory: Callable[[], Any]) -> Any:
+    return json.loads(runner.run(factory))
+
+
+def _parse_order_type(order_type: dict[str, Any]) -> str:
+    """Extract the time in force from an official-SDK order type"""
+    if "limit" in order_type:
+        return str(order_type["limit"]["tif"])
+    raise ValueError(
+        f"Unsupported order type {order_type!r}: only limit orders are supported"
+    )
+
+
+def _slippage_price(
+    mid: float, is_buy: bool, slippage: float, sz_decimals: int
+) -> float:
+    """Aggressive limit price for a market order, rounded like the official SDK"""
+    px = mid * (1 + slippage) if is_buy else mid * (1 - slippage)
+    # 5 significant figures, and at most 6 - szDecimals decimals for perps
+    return round(float(f"{px:.5g}"), 6 - sz_decimals)
+
+
+def _subscription_args(subscription: dict[str, Any]) -> dict[str, Any]:
+    """Map an official-SDK subscription dict to ``PyWebSocketClient`` arguments"""
+    return {
+        "channel": subscription["type"],
+        "coin": subscription.get("coin"),
+        "interval": subscription.get("interval"),
+        "user": subscription.get("user"),
+    }
+
+
+class Info:
+    """Read-only API client compatible with ``hyperliquid.info.Info``"""
+
+    def __init__(
+        self,
+        base_url: Optional[str] = None,
+        skip_ws: bool = False,
+        meta: Optional[dict[str, Any]] = None,
+        spot_meta: Optional[dict[str, Any]] = None,
+        perp_dexs: Optional[list[str]] = None,
+        timeout: Optional[float] = None,
+    ):
+        rust = _rust()
+        self.base_url = base_url or MAINNET_API_URL
+        self._runner = _Runner()
+        self._http = rust.PyHttpClient.with_default_config(self.base_url)
+        self._ws: Any = None
+        if not skip_ws:
+            self._ws = rust.PyWebSocketClient(
+                url=self.base_url.replace("http", "ws", 1) + "/ws"
+            )
+        self._subscription_ids = itertools.count(1)
+        self._subscriptions: dict[int, dict[str, Any]] = {}
+
+        meta = meta if meta is not None else self.meta()
+        self.coin_to_asset = {
+            asset["name"]: index for index, asset in enumerate(meta["universe"])
+        }
+        self.name_to_coin = {name: name for name in self.coin_to_asset}
+        self.asset_to_sz_decimals = {
+            index: asset["szDecimals"] for index, asset in enumerate(meta["universe"])
+        }
+
+    def post(self, url_path: str, payload: dict[str, Any]) -> Any:
+        return _run_json(
+            self._runner, lambda: self._http.post(url_path, json.dumps(payload))
+        )
+
+    def _info(self, payload: dict[str, Any]) -> Any:
+        return self.post("/info", payload)
+
+    def name_to_asset(self, name: str) -> int:
+        return self.coin_to_asset[self.name_to_coin[name]]
+
+    def user_state(self, address: str, dex: str = "") -> Any:
+        return self._info({"type": "clearinghouseState", "user": address, "dex": dex})
+
+    def spot_user_state(self, address: str) -> Any:
+        return self._info({"type": "spotClearinghouseState", "user": address})
+
+    def open_orders(self, address: str, dex: str = "") -> Any:
+        return self._info({"type": "openOrders", "user": address, "dex": dex})
+
+    def frontend_open_orders(self, address: str, dex: str = "") -> Any:
+        return self._info({"type": "frontendOpenOrders", "user": address, "dex": dex})
+
+    def all_mids(self, dex: str = "") -> Any:
+        return self._info({"type": "allMids", "dex": dex})
+
+    def user_fills(self, address: str) -> Any:
+        return self._info({"type": "userFills", "user": address})
+
+    def user_fills_by_time(
+        self, address: str, start_time: int, end_time: Optional[int] = None
+    ) -> Any:
+        return self._info(
+            {
+                "type": "userFillsByTime",
+                "user": address,
+                "startTime": start_time,
+                "endTime": end_time,
+            }
+        )
+
+    def meta(self, dex: str = "") -> Any:
+        return self._info({"type": "meta", "dex": dex})
+
+    def meta_and_asset_ctxs(self) -> Any:
+        return self._info({"type": "metaAndAssetCtxs"})
+
+    def spot_meta(self) -> Any:
+        return self._info({"type": "spotMeta"})
+
+    def spot_meta_and_asset_ctxs(self) -> Any:
+        return self._info({"type": "spotMetaAndAssetCtxs"})
+
+    def funding_history(
+        self, name: str, startTime: int, endTime: Optional[int] = None
+    ) -> Any:
+        return self._info(
+            {
+                "type": "fundingHistory",
+                "coin": self.name_to_coin[name],
+                "startTime": startTime,
+                "endTime": endTime,
+            }
+        )
+
+    def user_funding_history(
+        self, user: str, startTime: int, endTime: Optional[int] = None
+    ) -> Any:
+        return self._info(
+            {
+                "type": "userFunding",
+                "user": user,
+                "startTime": startTime,
+                "endTime": endTime,
+            }
+        )
+
+    def l2_snapshot(self, name: str) -> Any:
+        return self._info({"type": "l2Book", "coin": self.name_to_coin[name]})
+
+    def candles_snapshot(
+        self, name: str, interval: str, startTime: int, endTime: int
+    ) -> Any:
+        return self._info(
+            {
+                "type": "candleSnapshot",
+                "req": {
+                    "coin": self.name_to_coin[name],
+                    "interval": interval,
+                    "startTime": startTime,
+                    "endTime": endTime,
+                },
+            }
+        )
+
+    def user_fees(self, address: str) -> Any:
+        return self._info({"type": "userFees", "user": address})
+
+    def query_order_by_oid(self, user: str, oid: int) -> Any:
+        return self._info({"type": "orderStatus", "user": user, "oid": oid})
+
+    def query_order_by_cloid(self, user: str, cloid: Any) -> Any:
+        return self._info({"type": "orderStatus", "user": user, "oid": str(cloid)})
+
+    def subscribe(
+        self, subscription: dict[str, Any], callback: Callable[[Any], None]
+    ) -> int:
+        if self._ws is None:
+            raise RuntimeError("Cannot call subscribe since skip_ws was used")
+        args = _subscription_args(subscription)
+        self._runner.run(lambda: self._ws.subscribe(callback=callback, **args))
+        subscription_id = next(self._subscription_ids)
+        self._subscriptions[subscription_id] = subscription
+        return subscription_id
+
+    def unsubscribe(self, subscription: dict[str, Any], subscription_id: int) -> bool:
+        if self._ws is None:
+            raise RuntimeError("Cannot call unsubscribe since skip_ws was used")
+        if self._subscriptions.pop(subscription_id, None) is None:
+            return False
+        args = _subscription_args(subscription)
+        self._runner.run(lambda: self._ws.unsubscribe(**args))
+        return True
+
+    def disconnect_websocket(self) -> None:
+        if self._ws is None:
+            raise RuntimeError("Cannot call disconnect_websocket since skip_ws was used")
+        self._runner.run(self._ws.close)
+
+
+class Exchange:
+    """Trading client compatible with ``hyperliquid.exchange.Exchange``
+
+    ``wallet`` may be an ``eth_account`` ``LocalAccount`` or a hex private key.
+    """
+
+    def __init__(
+        self,
+        wallet: Union[Any, str],
+        base_url: Optional[str] = None,
+        meta: Optional[dict[str, Any]] = None,
+        vault_address: Optional[str] = None,
+        account_address: Optional[str] = None,
+        spot_meta: Optional[dict[str, Any]] = None,
+        perp_dexs: Optional[list[str]] = None,
+        timeout: Optional[float] = None,
+    ):
+        rust = _rust()
+        self.base_url = base_url or MAINNET_API_URL
+        self.wallet = wallet
+        self.vault_address = vault_address
+        self.account_address = account_address
+        self.info = Info(self.base_url, skip_ws=True, meta=meta)
+        self._runner = self.info._runner
+
+        private_key = wallet if isinstance(wallet, str) else wallet.key.hex()
+        mainnet = self.base_url == MAINNET_API_URL
+        if account_address is not None:
+            config = (
+                rust.PyExchangeClientConfig.mainnet(account_address)
+                if mainnet
+                else rust.PyExchangeClientConfig.testnet(account_address)
+            )
+            self._client = rust.PyExchangeClient(config, private_key, vault_address)
+        else:
+            self._client = rust.PyExchangeClient.from_private_key(
+                private_key, mainnet, vault_address
+            )
+
+    def _call(self, factory: Callable[[], Any]) -> Any:
+        return _run_json(self._runner, factory)
+
+    def order(
+        self,
+        name: str,
+        is_buy: bool,
+        sz: float,
+        limit_px: float,
+        order_type: dict[str, Any],
+        reduce_only: bool = False,
+        cloid: Optional[Any] = None,
+        builder: Optional[dict[str, Any]] = None,
+    ) -> Any:
+        if builder is not None:
+            raise ValueError("Builder fees are not supported by the Rust backend")
+        tif = _parse_order_type(order_type)
+        coin = self.info.name_to_coin[name]
+        return self._call(
+            lambda: self._client.place_order(
+                coin,
+                is_buy,
+                sz,
+                limit_px,
+                tif,
+                reduce_only,
+                None if cloid is None else str(cloid),
+            )
+        )
+
+    def modify_order(
+        self,
+        oid: int,
+        name: str,
+        is_buy: bool,
+        sz: float,
+        limit_px: float,
+        order_type: dict[str, Any],
+        reduce_only: bool = False,
+        cloid: Optional[Any] = None,
+    ) -> Any:
+        tif = _parse_order_type(order_type)
+        coin = self.info.name_to_coin[name]
+        return self._call(
+            lambda: self._client.modify(
+                oid,
+                coin,
+                is_buy,
+                sz,
+                limit_px,
+                tif,
+                reduce_only,
+                None if cloid is None else str(cloid),
+            )
+        )
+
+    def market_open(
+        self,
+        name: str,
+        is_buy: bool,
+        sz: float,
+        px: Optional[float] = None,
+        slippage: float = DEFAULT_SLIPPAGE,
+        cloid: Optional[Any] = None,
+        builder: Optional[dict[str, Any]] = None,
+    ) -> Any:
+        px = self._slippage_price(name, is_buy, slippage, px)
+        return self.order(
+            name, is_buy, sz, px, {"limit": {"tif": "Ioc"}}, False, cloid, builder
+        )
+
+    def market_close(
+        self,
+        coin: str,
+        sz: Optional[float] = None,
+        px: Optional[float] = None,
+        slippage: float = DEFAULT_SLIPPAGE,
+        cloid: Optional[Any] = None,
+        builder: Optional[dict[str, Any]] = None,
+    ) -> Any:
+        address = self.account_address or self.vault_address or self._client.address
+        positions = self.info.user_state(address)["assetPositions"]
+        for position in positions:
+            item = position["position"]
+            if item["coin"] != coin:
+                continue
+            szi = float(item["szi"])
+            if sz is None:
+                sz = abs(szi)
+            is_buy = szi < 0
+            px = self._slippage_price(coin, is_buy, slippage, px)
+            return self.order(
+                coin, is_buy, sz, px, {"limit": {"tif": "Ioc"}}, True, cloid, builder
+            )
+        return None
+
+    def _slippage_price(
+        self, name: str, is_buy: bool, slippage: float, px: Optional[float]
+    ) -> float:
+        coin = self.info.name_to_coin[name]
+        if px is None:
+            px = float(self.info.all_mids()[coin])
+        sz_decimals = self.info.asset_to_sz_decimals[self.info.name_to_asset(coin)]
+        return _slippage_price(px, is_buy, slippage, sz_decimals)
+
+    def cancel(self, name: str, oid: int) -> Any:
+        coin = self.info.name_to_coin[name]
+        return self._call(lambda: self._client.cancel(coin, oid))
+
+    def cancel_by_cloid(self, name: str, cloid: Any) -> Any:
+        coin = self.info.name_to_coin[name]
+        return self._call(lambda: self._client.cancel_by_cloid(coin, str(cloid)))
+
+    def update_leverage(self, leverage: int, name: str, is_cross: bool = True) -> Any:
+        coin = self.info.name_to_coin[name]
+        return self._call(
+            lambda: self._client.update_leverage(coin, leverage, is_cross)
+        )
+
+    def usd_transfer(self, amount: float, destination: str) -> Any:
+        return self._call(lambda: self._client.usd_transfer(destination, amount))
@@ -0,0 +1,34 @@
+"""Tests for the official-SDK compatibility layer"""
+
+import pytest
+
+from hyperliquid_rs.compat import (
+    _parse_order_type,
+    _slippage_price,
+    _subscription_args,
+)
+
+
+class TestCompatHelpers:
+    """Test the helpers that don't need the Rust module"""
+
+    def test_parse_order_type(self):
+        assert _parse_order_type({"limit": {"tif": "Gtc"}}) == "Gtc"
+        assert _parse_order_type({"limit": {"tif": "Ioc"}}) == "Ioc"
+        with pytest.raises(ValueError):
+            _parse_order_type({"trigger": {"triggerPx": 1.0, "isMarket": True}})
+
+    def test_slippage_price(self):
+        assert _slippage_price(100.0, True, 0.05, 2) == 105.0
+        assert _slippage_price(100.0, False, 0.05, 2) == 95.0
+        # Rounded to 5 significant figures
+        assert _slippage_price(67234.5, True, 0.01, 5) == 67907.0
+
+    def test_subscription_args(self):
+        args = _subscription_args({"type": "candle", "coin": "BTC", "interval": "1m"})
+        assert args == {
+            "channel": "candle",
+            "coin": "BTC",
+            "interval": "1m",
+            "user": None,
+        }